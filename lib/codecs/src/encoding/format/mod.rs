@@ -10,6 +10,7 @@ mod logfmt;
 mod native;
 mod native_json;
 mod raw_message;
+mod syslog;
 mod text;
 
 use std::fmt::Debug;
@@ -22,6 +23,7 @@ pub use logfmt::{LogfmtSerializer, LogfmtSerializerConfig};
 pub use native::{NativeSerializer, NativeSerializerConfig};
 pub use native_json::{NativeJsonSerializer, NativeJsonSerializerConfig};
 pub use raw_message::{RawMessageSerializer, RawMessageSerializerConfig};
+pub use syslog::{SyslogRfc, SyslogSerializer, SyslogSerializerConfig, SyslogSerializerOptions};
 pub use text::{TextSerializer, TextSerializerConfig};
 use vector_core::event::Event;
 
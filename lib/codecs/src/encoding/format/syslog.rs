@@ -0,0 +1,541 @@
+use std::{collections::BTreeMap, fmt::Write};
+
+use bytes::{BufMut, BytesMut};
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde::{Deserialize, Serialize};
+use tokio_util::codec::Encoder;
+use value::Value;
+use vector_config::configurable_component;
+use vector_core::{
+    config::{log_schema, DataType},
+    event::{Event, LogEvent},
+    schema,
+};
+
+/// The facility used when an event doesn't have a valid one.
+const DEFAULT_FACILITY: u8 = 1; // user
+
+/// The severity used when an event doesn't have a valid one.
+const DEFAULT_SEVERITY: u8 = 6; // informational
+
+const NIL: &str = "-";
+
+/// Config used to build a `SyslogSerializer`.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct SyslogSerializerConfig {
+    /// Options for the syslog serializer.
+    pub syslog: SyslogSerializerOptions,
+}
+
+impl SyslogSerializerConfig {
+    /// Creates a new `SyslogSerializerConfig`.
+    pub const fn new(syslog: SyslogSerializerOptions) -> Self {
+        Self { syslog }
+    }
+
+    /// Build the `SyslogSerializer` from this configuration.
+    pub fn build(&self) -> SyslogSerializer {
+        SyslogSerializer::new(self.syslog.clone())
+    }
+
+    /// The data type of events that are accepted by `SyslogSerializer`.
+    pub fn input_type(&self) -> DataType {
+        DataType::Log
+    }
+
+    /// The schema required by the serializer.
+    pub fn schema_requirement(&self) -> schema::Requirement {
+        // All fields are optional, and fall back to a default or the nil value when missing.
+        schema::Requirement::empty()
+    }
+}
+
+/// The syslog protocol to encode events with.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum SyslogRfc {
+    /// The BSD syslog protocol, as specified in [RFC 3164][rfc3164].
+    ///
+    /// Message IDs and structured data aren't part of this format, and are left out.
+    ///
+    /// [rfc3164]: https://www.rfc-editor.org/rfc/rfc3164
+    Rfc3164,
+
+    /// The syslog protocol, as specified in [RFC 5424][rfc5424].
+    ///
+    /// [rfc5424]: https://www.rfc-editor.org/rfc/rfc5424
+    Rfc5424,
+}
+
+impl Default for SyslogRfc {
+    fn default() -> Self {
+        Self::Rfc5424
+    }
+}
+
+/// Syslog serializer options.
+///
+/// The defaults match the fields set by the `syslog` decoder, so that decoded events are encoded
+/// back as they were received.
+#[configurable_component]
+#[derive(Clone, Debug)]
+pub struct SyslogSerializerOptions {
+    /// The syslog protocol to encode events with.
+    #[serde(default)]
+    pub rfc: SyslogRfc,
+
+    /// The field containing the facility of the event.
+    ///
+    /// The facility can either be a name, such as `local0`, or its numeric code. Events without a
+    /// valid facility use `user`.
+    #[serde(default = "default_facility_field")]
+    pub facility_field: String,
+
+    /// The field containing the severity of the event.
+    ///
+    /// The severity can either be a name, such as `warning`, or its numeric code. Events without a
+    /// valid severity use `informational`.
+    #[serde(default = "default_severity_field")]
+    pub severity_field: String,
+
+    /// The field containing the hostname of the event.
+    ///
+    /// Events without it use the global `host_key` field instead.
+    #[serde(default = "default_hostname_field")]
+    pub hostname_field: String,
+
+    /// The field containing the name of the application that produced the event.
+    #[serde(default = "default_app_name_field")]
+    pub app_name_field: String,
+
+    /// The field containing the process ID of the application that produced the event.
+    #[serde(default = "default_proc_id_field")]
+    pub proc_id_field: String,
+
+    /// The field containing the type of the message.
+    #[serde(default = "default_msg_id_field")]
+    pub msg_id_field: String,
+
+    /// The field containing the structured data of the event.
+    ///
+    /// The field must be an object, whose keys are the IDs of the structured data elements, and
+    /// whose values are objects of the parameters of each element. When unset, no structured data
+    /// is sent.
+    #[serde(default)]
+    pub structured_data_field: Option<String>,
+}
+
+impl Default for SyslogSerializerOptions {
+    fn default() -> Self {
+        Self {
+            rfc: SyslogRfc::default(),
+            facility_field: default_facility_field(),
+            severity_field: default_severity_field(),
+            hostname_field: default_hostname_field(),
+            app_name_field: default_app_name_field(),
+            proc_id_field: default_proc_id_field(),
+            msg_id_field: default_msg_id_field(),
+            structured_data_field: None,
+        }
+    }
+}
+
+fn default_facility_field() -> String {
+    "facility".to_string()
+}
+
+fn default_severity_field() -> String {
+    "severity".to_string()
+}
+
+fn default_hostname_field() -> String {
+    "hostname".to_string()
+}
+
+fn default_app_name_field() -> String {
+    "appname".to_string()
+}
+
+fn default_proc_id_field() -> String {
+    "procid".to_string()
+}
+
+fn default_msg_id_field() -> String {
+    "msgid".to_string()
+}
+
+/// Serializer that converts an `Event` to bytes using the syslog format.
+#[derive(Debug, Clone)]
+pub struct SyslogSerializer {
+    options: SyslogSerializerOptions,
+}
+
+impl SyslogSerializer {
+    /// Creates a new `SyslogSerializer`.
+    pub const fn new(options: SyslogSerializerOptions) -> Self {
+        Self { options }
+    }
+
+    fn priority(&self, log: &LogEvent) -> u8 {
+        let facility = log
+            .get(self.options.facility_field.as_str())
+            .and_then(|value| code(value, facility_code, 23))
+            .unwrap_or(DEFAULT_FACILITY);
+        let severity = log
+            .get(self.options.severity_field.as_str())
+            .and_then(|value| code(value, severity_code, 7))
+            .unwrap_or(DEFAULT_SEVERITY);
+        facility * 8 + severity
+    }
+
+    fn hostname(&self, log: &LogEvent) -> Option<String> {
+        log.get(self.options.hostname_field.as_str())
+            .or_else(|| log.get(log_schema().host_key()))
+            .map(|value| value.to_string_lossy().into_owned())
+    }
+
+    fn field(&self, log: &LogEvent, field: &str) -> Option<String> {
+        log.get(field)
+            .map(|value| value.to_string_lossy().into_owned())
+    }
+
+    fn encode_rfc5424(&self, log: &LogEvent, buffer: &mut BytesMut) {
+        let timestamp = log
+            .get(log_schema().timestamp_key())
+            .and_then(Value::as_timestamp)
+            .map(|timestamp| timestamp.to_rfc3339_opts(SecondsFormat::AutoSi, true));
+
+        let mut header = format!("<{}>1 ", self.priority(log));
+        header.push_str(timestamp.as_deref().unwrap_or(NIL));
+        for (value, max_len) in [
+            (self.hostname(log), 255),
+            (self.field(log, &self.options.app_name_field), 48),
+            (self.field(log, &self.options.proc_id_field), 128),
+            (self.field(log, &self.options.msg_id_field), 32),
+        ] {
+            header.push(' ');
+            header.push_str(&header_field(value.as_deref(), max_len));
+        }
+        header.push(' ');
+        header.push_str(&self.structured_data(log));
+        buffer.put_slice(header.as_bytes());
+
+        if let Some(message) = log.get(log_schema().message_key()) {
+            buffer.put_u8(b' ');
+            buffer.put_slice(message.to_string_lossy().as_bytes());
+        }
+    }
+
+    fn encode_rfc3164(&self, log: &LogEvent, buffer: &mut BytesMut) {
+        let timestamp = log
+            .get(log_schema().timestamp_key())
+            .and_then(Value::as_timestamp)
+            .copied()
+            .unwrap_or_else(Utc::now);
+
+        let mut header = format!(
+            "<{}>{} {}",
+            self.priority(log),
+            rfc3164_timestamp(timestamp),
+            header_field(self.hostname(log).as_deref(), 255)
+        );
+        if let Some(tag) = self
+            .field(log, &self.options.app_name_field)
+            .map(|app_name| tag(&app_name))
+            .filter(|tag| !tag.is_empty())
+        {
+            header.push(' ');
+            header.push_str(&tag);
+            if let Some(proc_id) = self.field(log, &self.options.proc_id_field) {
+                let _ = write!(header, "[{}]", header_field(Some(&proc_id), 128));
+            }
+            header.push(':');
+        }
+        buffer.put_slice(header.as_bytes());
+
+        if let Some(message) = log.get(log_schema().message_key()) {
+            buffer.put_u8(b' ');
+            buffer.put_slice(message.to_string_lossy().as_bytes());
+        }
+    }
+
+    fn structured_data(&self, log: &LogEvent) -> String {
+        let elements = self
+            .options
+            .structured_data_field
+            .as_deref()
+            .and_then(|field| log.get(field))
+            .and_then(Value::as_object)
+            .map(structured_data)
+            .unwrap_or_default();
+
+        if elements.is_empty() {
+            NIL.to_string()
+        } else {
+            elements
+        }
+    }
+}
+
+impl Encoder<Event> for SyslogSerializer {
+    type Error = vector_common::Error;
+
+    fn encode(&mut self, event: Event, buffer: &mut BytesMut) -> Result<(), Self::Error> {
+        let log = event.into_log();
+        match self.options.rfc {
+            SyslogRfc::Rfc3164 => self.encode_rfc3164(&log, buffer),
+            SyslogRfc::Rfc5424 => self.encode_rfc5424(&log, buffer),
+        }
+        Ok(())
+    }
+}
+
+/// Gets the numeric code of a facility or severity, given either as a name or a number.
+fn code(value: &Value, by_name: fn(&str) -> Option<u8>, max: u8) -> Option<u8> {
+    match value {
+        Value::Integer(code) => u8::try_from(*code).ok().filter(|code| *code <= max),
+        Value::Bytes(_) => {
+            let name = value.to_string_lossy();
+            name.parse::<u8>()
+                .ok()
+                .filter(|code| *code <= max)
+                .or_else(|| by_name(&name.to_lowercase()))
+        }
+        _ => None,
+    }
+}
+
+fn facility_code(name: &str) -> Option<u8> {
+    let code = match name {
+        "kern" => 0,
+        "user" => 1,
+        "mail" => 2,
+        "daemon" => 3,
+        "auth" => 4,
+        "syslog" => 5,
+        "lpr" => 6,
+        "news" => 7,
+        "uucp" => 8,
+        "cron" => 9,
+        "authpriv" => 10,
+        "ftp" => 11,
+        "ntp" => 12,
+        "audit" | "security" => 13,
+        "alert" | "console" => 14,
+        "clockd" | "solaris-cron" => 15,
+        "local0" => 16,
+        "local1" => 17,
+        "local2" => 18,
+        "local3" => 19,
+        "local4" => 20,
+        "local5" => 21,
+        "local6" => 22,
+        "local7" => 23,
+        _ => return None,
+    };
+    Some(code)
+}
+
+fn severity_code(name: &str) -> Option<u8> {
+    let code = match name {
+        "emerg" | "emergency" | "panic" => 0,
+        "alert" => 1,
+        "crit" | "critical" => 2,
+        "err" | "error" => 3,
+        "warning" | "warn" => 4,
+        "notice" => 5,
+        "info" | "informational" => 6,
+        "debug" => 7,
+        _ => return None,
+    };
+    Some(code)
+}
+
+/// Formats a header field, which may only contain printable US-ASCII characters.
+///
+/// Other characters, including spaces, are replaced with underscores, and the field is truncated
+/// to `max_len` characters. Missing or empty fields are sent as the nil value.
+fn header_field(value: Option<&str>, max_len: usize) -> String {
+    match value {
+        Some(value) if !value.is_empty() => value
+            .chars()
+            .take(max_len)
+            .map(|c| if c.is_ascii_graphic() { c } else { '_' })
+            .collect(),
+        _ => NIL.to_string(),
+    }
+}
+
+/// Formats a structured data name, which additionally may not contain `=`, `]` or `"`.
+fn sd_name(name: &str) -> String {
+    name.chars()
+        .take(32)
+        .map(|c| match c {
+            '=' | ']' | '"' => '_',
+            c if c.is_ascii_graphic() => c,
+            _ => '_',
+        })
+        .collect()
+}
+
+fn structured_data(elements: &BTreeMap<String, Value>) -> String {
+    let mut output = String::new();
+    for (id, params) in elements {
+        let _ = write!(output, "[{}", sd_name(id));
+        if let Some(params) = params.as_object() {
+            for (name, value) in params {
+                let _ = write!(output, " {}=\"", sd_name(name));
+                for c in value.to_string_lossy().chars() {
+                    if matches!(c, '"' | '\\' | ']') {
+                        output.push('\\');
+                    }
+                    output.push(c);
+                }
+                output.push('"');
+            }
+        }
+        output.push(']');
+    }
+    output
+}
+
+/// Formats the tag of an RFC 3164 message, which may only contain up to 32 alphanumeric
+/// characters.
+fn tag(app_name: &str) -> String {
+    app_name
+        .chars()
+        .filter(char::is_ascii_alphanumeric)
+        .take(32)
+        .collect()
+}
+
+fn rfc3164_timestamp(timestamp: DateTime<Utc>) -> String {
+    timestamp.format("%b %e %H:%M:%S").to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::BytesMut;
+    use chrono::TimeZone;
+    use vector_common::btreemap;
+
+    use super::*;
+
+    fn event() -> Event {
+        let mut log = LogEvent::from("Connection refused");
+        log.insert(
+            log_schema().timestamp_key(),
+            Utc.ymd(2003, 10, 11).and_hms_milli(22, 14, 15, 3),
+        );
+        log.insert("hostname", "mymachine.example.com");
+        log.insert("facility", "auth");
+        log.insert("severity", "crit");
+        log.insert("appname", "su");
+        log.insert("procid", 1234);
+        log.insert("msgid", "ID47");
+        log.into()
+    }
+
+    fn serialize(options: SyslogSerializerOptions, event: Event) -> String {
+        let mut serializer = SyslogSerializerConfig::new(options).build();
+        let mut bytes = BytesMut::new();
+        serializer.encode(event, &mut bytes).unwrap();
+        String::from_utf8(bytes.to_vec()).unwrap()
+    }
+
+    #[test]
+    fn serialize_rfc5424() {
+        assert_eq!(
+            serialize(SyslogSerializerOptions::default(), event()),
+            "<34>1 2003-10-11T22:14:15.003Z mymachine.example.com su 1234 ID47 - Connection refused"
+        );
+    }
+
+    #[test]
+    fn serialize_rfc5424_structured_data() {
+        let mut event = event();
+        event.as_mut_log().insert(
+            "sd",
+            Value::from(btreemap! {
+                "exampleSDID@32473" => btreemap! {
+                    "eventSource" => "Application",
+                    "iut" => "3",
+                },
+                "examplePriority@32473" => btreemap! {
+                    "class" => "high\"]",
+                },
+            }),
+        );
+        let options = SyslogSerializerOptions {
+            structured_data_field: Some("sd".to_string()),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            serialize(options, event),
+            r#"<34>1 2003-10-11T22:14:15.003Z mymachine.example.com su 1234 ID47 [examplePriority@32473 class="high\"\]"][exampleSDID@32473 eventSource="Application" iut="3"] Connection refused"#
+        );
+    }
+
+    #[test]
+    fn serialize_rfc5424_missing_fields() {
+        let event = Event::from(LogEvent::from("hello world"));
+
+        assert_eq!(
+            serialize(SyslogSerializerOptions::default(), event),
+            "<14>1 - - - - - - hello world"
+        );
+    }
+
+    #[test]
+    fn serialize_rfc5424_field_mappings() {
+        let mut event = Event::from(LogEvent::from("hello"));
+        let log = event.as_mut_log();
+        log.insert("level", 3);
+        log.insert("source.facility", "local7");
+        log.insert("service", "my app");
+
+        let options = SyslogSerializerOptions {
+            facility_field: "source.facility".to_string(),
+            severity_field: "level".to_string(),
+            app_name_field: "service".to_string(),
+            ..Default::default()
+        };
+
+        assert_eq!(serialize(options, event), "<187>1 - - my_app - - - hello");
+    }
+
+    #[test]
+    fn serialize_rfc3164() {
+        let options = SyslogSerializerOptions {
+            rfc: SyslogRfc::Rfc3164,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            serialize(options, event()),
+            "<34>Oct 11 22:14:15 mymachine.example.com su[1234]: Connection refused"
+        );
+    }
+
+    #[test]
+    fn serialize_rfc3164_pads_day() {
+        let mut event = event();
+        let log = event.as_mut_log();
+        log.insert(
+            log_schema().timestamp_key(),
+            Utc.ymd(2003, 10, 1).and_hms(2, 4, 5),
+        );
+        log.remove("procid");
+        let options = SyslogSerializerOptions {
+            rfc: SyslogRfc::Rfc3164,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            serialize(options, event),
+            "<34>Oct  1 02:04:05 mymachine.example.com su: Connection refused"
+        );
+    }
+}
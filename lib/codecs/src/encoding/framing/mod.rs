@@ -7,6 +7,7 @@ mod bytes;
 mod character_delimited;
 mod length_delimited;
 mod newline_delimited;
+mod octet_counting;
 
 use std::fmt::Debug;
 
@@ -16,6 +17,7 @@ pub use character_delimited::{
 use dyn_clone::DynClone;
 pub use length_delimited::{LengthDelimitedEncoder, LengthDelimitedEncoderConfig};
 pub use newline_delimited::{NewlineDelimitedEncoder, NewlineDelimitedEncoderConfig};
+pub use octet_counting::{OctetCountingEncoder, OctetCountingEncoderConfig};
 use tokio_util::codec::LinesCodecError;
 
 pub use self::bytes::{BytesEncoder, BytesEncoderConfig};
@@ -24,7 +24,8 @@ pub use encoding::{
     GelfSerializer, GelfSerializerConfig, JsonSerializer, JsonSerializerConfig,
     LengthDelimitedEncoder, LengthDelimitedEncoderConfig, LogfmtSerializer, LogfmtSerializerConfig,
     NativeJsonSerializer, NativeJsonSerializerConfig, NativeSerializer, NativeSerializerConfig,
-    NewlineDelimitedEncoder, NewlineDelimitedEncoderConfig, RawMessageSerializer,
-    RawMessageSerializerConfig, TextSerializer, TextSerializerConfig,
+    NewlineDelimitedEncoder, NewlineDelimitedEncoderConfig, OctetCountingEncoder,
+    OctetCountingEncoderConfig, RawMessageSerializer, RawMessageSerializerConfig, SyslogSerializer,
+    SyslogSerializerConfig, TextSerializer, TextSerializerConfig,
 };
 pub use gelf::{gelf_fields, VALID_FIELD_REGEX};
@@ -1,8 +1,11 @@
 use anyhow::Context;
 use graphql_client::GraphQLQuery;
 use indoc::indoc;
+use reqwest::StatusCode;
 use url::Url;
 
+use crate::ConnectOptions;
+
 /// Wrapped `Result` type, that returns deserialized GraphQL response data.
 pub type QueryResult<T> =
     anyhow::Result<graphql_client::Response<<T as GraphQLQuery>::ResponseData>>;
@@ -11,25 +14,58 @@ pub type QueryResult<T> =
 #[derive(Debug)]
 pub struct Client {
     url: Url,
+    client: reqwest::Client,
+    authorization: Option<String>,
 }
 
 impl Client {
     /// Returns a new GraphQL query client, bound to the provided URL.
     pub fn new(url: Url) -> Self {
-        Self { url }
+        Self {
+            url,
+            client: reqwest::Client::new(),
+            authorization: None,
+        }
+    }
+
+    /// Returns a new GraphQL query client, bound to the provided URL, authenticating with the
+    /// provided credentials.
+    pub fn new_with_options(url: Url, options: &ConnectOptions) -> anyhow::Result<Self> {
+        Ok(Self {
+            url,
+            client: options.http_client()?,
+            authorization: options.authorization(),
+        })
     }
 
-    pub async fn new_with_healthcheck(url: Url) -> Option<Self> {
+    pub async fn new_with_healthcheck(url: Url, options: &ConnectOptions) -> Option<Self> {
         #![allow(clippy::print_stderr)]
 
         use crate::gql::HealthQueryExt;
 
         // Create a new API client for connecting to the local/remote Vector instance.
-        let client = Self::new(url.clone());
+        let client = match Self::new_with_options(url.clone(), options) {
+            Ok(client) => client,
+            Err(error) => {
+                eprintln!("{:#}", error);
+                return None;
+            }
+        };
 
         // Check that the GraphQL server is reachable
         match client.health_query().await {
             Ok(_) => Some(client),
+            Err(error) if is_unauthorized(&error) => {
+                eprintln!(
+                    indoc! {"
+                    Vector API server ({}) rejected the credentials.
+
+                    Provide a token with `--token` or the `VECTOR_API_TOKEN` environment variable,
+                    or a client certificate with `--crt-file` and `--key-file`."},
+                    url
+                );
+                None
+            }
             _ => {
                 eprintln!(
                     indoc! {"
@@ -54,13 +90,15 @@ impl Client {
         &self,
         request_body: &graphql_client::QueryBody<T::Variables>,
     ) -> QueryResult<T> {
-        let client = reqwest::Client::new();
+        let mut request = self.client.post(self.url.clone()).json(request_body);
+        if let Some(authorization) = &self.authorization {
+            request = request.header(reqwest::header::AUTHORIZATION, authorization);
+        }
 
-        client
-            .post(self.url.clone())
-            .json(request_body)
+        request
             .send()
             .await
+            .and_then(reqwest::Response::error_for_status)
             .with_context(|| {
                 format!(
                     "Couldn't send '{}' query to {}",
@@ -78,3 +116,13 @@ impl Client {
             })
     }
 }
+
+/// Whether a query failed because the server rejected the credentials.
+fn is_unauthorized(error: &anyhow::Error) -> bool {
+    error
+        .downcast_ref::<reqwest::Error>()
+        .and_then(reqwest::Error::status)
+        .map_or(false, |status| {
+            matches!(status, StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN)
+        })
+}
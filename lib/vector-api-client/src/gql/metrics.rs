@@ -167,6 +167,36 @@ impl component_sent_events_throughputs_subscription::ComponentSentEventsThroughp
 )]
 pub struct ComponentErrorsTotalsSubscription;
 
+/// ComponentErrorsThroughputsSubscription contains the rate of errors (metrics ending in
+/// `_errors_total`), against specific components.
+#[derive(GraphQLQuery, Debug, Copy, Clone)]
+#[graphql(
+    schema_path = "graphql/schema.json",
+    query_path = "graphql/subscriptions/component_errors_throughputs.graphql",
+    response_derives = "Debug"
+)]
+pub struct ComponentErrorsThroughputsSubscription;
+
+/// ComponentBufferUsagesSubscription contains the number of events and bytes held in the buffers
+/// of specific components, their limits and the number of events they discarded.
+#[derive(GraphQLQuery, Debug, Copy, Clone)]
+#[graphql(
+    schema_path = "graphql/schema.json",
+    query_path = "graphql/subscriptions/component_buffer_usages.graphql",
+    response_derives = "Debug"
+)]
+pub struct ComponentBufferUsagesSubscription;
+
+/// ComponentCpuTimeThroughputsSubscription contains the time, in nanoseconds, spent polling the
+/// tasks of specific components.
+#[derive(GraphQLQuery, Debug, Copy, Clone)]
+#[graphql(
+    schema_path = "graphql/schema.json",
+    query_path = "graphql/subscriptions/component_cpu_time_throughputs.graphql",
+    response_derives = "Debug"
+)]
+pub struct ComponentCpuTimeThroughputsSubscription;
+
 /// Extension methods for metrics subscriptions
 pub trait MetricsSubscriptionExt {
     /// Executes an uptime metrics subscription.
@@ -242,6 +272,23 @@ pub trait MetricsSubscriptionExt {
         &self,
         interval: i64,
     ) -> crate::BoxedSubscription<ComponentErrorsTotalsSubscription>;
+
+    /// Executes a component errors throughputs subscription.
+    fn component_errors_throughputs_subscription(
+        &self,
+        interval: i64,
+    ) -> crate::BoxedSubscription<ComponentErrorsThroughputsSubscription>;
+
+    /// Executes a component buffer usages subscription.
+    fn component_buffer_usages_subscription(
+        &self,
+        interval: i64,
+    ) -> crate::BoxedSubscription<ComponentBufferUsagesSubscription>;
+
+    fn component_cpu_time_throughputs_subscription(
+        &self,
+        interval: i64,
+    ) -> crate::BoxedSubscription<ComponentCpuTimeThroughputsSubscription>;
 }
 
 impl MetricsSubscriptionExt for crate::SubscriptionClient {
@@ -394,4 +441,39 @@ impl MetricsSubscriptionExt for crate::SubscriptionClient {
 
         self.start::<ComponentErrorsTotalsSubscription>(&request_body)
     }
+
+    /// Executes a component errors throughputs subscription.
+    fn component_errors_throughputs_subscription(
+        &self,
+        interval: i64,
+    ) -> BoxedSubscription<ComponentErrorsThroughputsSubscription> {
+        let request_body = ComponentErrorsThroughputsSubscription::build_query(
+            component_errors_throughputs_subscription::Variables { interval },
+        );
+
+        self.start::<ComponentErrorsThroughputsSubscription>(&request_body)
+    }
+
+    /// Executes a component buffer usages subscription.
+    fn component_buffer_usages_subscription(
+        &self,
+        interval: i64,
+    ) -> BoxedSubscription<ComponentBufferUsagesSubscription> {
+        let request_body = ComponentBufferUsagesSubscription::build_query(
+            component_buffer_usages_subscription::Variables { interval },
+        );
+
+        self.start::<ComponentBufferUsagesSubscription>(&request_body)
+    }
+
+    fn component_cpu_time_throughputs_subscription(
+        &self,
+        interval: i64,
+    ) -> BoxedSubscription<ComponentCpuTimeThroughputsSubscription> {
+        let request_body = ComponentCpuTimeThroughputsSubscription::build_query(
+            component_cpu_time_throughputs_subscription::Variables { interval },
+        );
+
+        self.start::<ComponentCpuTimeThroughputsSubscription>(&request_body)
+    }
 }
@@ -17,6 +17,16 @@ type DateTime = chrono::DateTime<chrono::Utc>;
 )]
 pub struct OutputEventsByComponentIdPatternsSubscription;
 
+/// EventLineageSubscription allows tracing the events matching a VRL condition through the
+/// components of the topology.
+#[derive(GraphQLQuery, Debug, Copy, Clone)]
+#[graphql(
+    schema_path = "graphql/schema.json",
+    query_path = "graphql/subscriptions/event_lineage.graphql",
+    response_derives = "Debug"
+)]
+pub struct EventLineageSubscription;
+
 /// Tap encoding format type that is more convenient to use for public clients than the
 /// generated `output_events_by_component_id_patterns_subscription::EventEncodingType`.
 #[derive(clap::ValueEnum, Debug, Clone, Copy)]
@@ -53,16 +63,37 @@ impl From<TapEncodingFormat>
     }
 }
 
+/// Sampling, filtering and projection options of an output events subscription.
+#[derive(Debug, Clone, Default)]
+pub struct TapSubscriptionOptions {
+    /// The maximum number of events to sample per interval.
+    pub limit: i64,
+    /// The sampling interval, in milliseconds.
+    pub interval: i64,
+    /// A VRL condition the events must match.
+    pub filter: Option<String>,
+    /// The fields logs are projected to, unless empty.
+    pub fields: Vec<String>,
+}
+
 pub trait TapSubscriptionExt {
-    /// Executes an output events subscription.
+    /// Executes an output events subscription. Events are matched by the `filter` VRL condition,
+    /// and projected to the `fields` of logs unless empty, before they're sampled.
     fn output_events_by_component_id_patterns_subscription(
         &self,
         outputs_patterns: Vec<String>,
         inputs_patterns: Vec<String>,
         encoding: TapEncodingFormat,
-        limit: i64,
-        interval: i64,
+        options: TapSubscriptionOptions,
     ) -> crate::BoxedSubscription<OutputEventsByComponentIdPatternsSubscription>;
+
+    /// Executes an event lineage subscription, tracing up to `limit` events matching the
+    /// `condition` VRL condition when ingested by sources.
+    fn event_lineage_subscription(
+        &self,
+        condition: String,
+        limit: i64,
+    ) -> crate::BoxedSubscription<EventLineageSubscription>;
 }
 
 impl TapSubscriptionExt for crate::SubscriptionClient {
@@ -72,19 +103,35 @@ impl TapSubscriptionExt for crate::SubscriptionClient {
         outputs_patterns: Vec<String>,
         inputs_patterns: Vec<String>,
         encoding: TapEncodingFormat,
-        limit: i64,
-        interval: i64,
+        options: TapSubscriptionOptions,
     ) -> BoxedSubscription<OutputEventsByComponentIdPatternsSubscription> {
         let request_body = OutputEventsByComponentIdPatternsSubscription::build_query(
             output_events_by_component_id_patterns_subscription::Variables {
                 outputs_patterns,
                 inputs_patterns: Some(inputs_patterns),
-                limit,
-                interval,
+                limit: options.limit,
+                interval: options.interval,
                 encoding: encoding.into(),
+                filter: options.filter,
+                fields: (!options.fields.is_empty()).then_some(options.fields),
             },
         );
 
         self.start::<OutputEventsByComponentIdPatternsSubscription>(&request_body)
     }
+
+    /// Executes an event lineage subscription.
+    fn event_lineage_subscription(
+        &self,
+        condition: String,
+        limit: i64,
+    ) -> BoxedSubscription<EventLineageSubscription> {
+        let request_body =
+            EventLineageSubscription::build_query(event_lineage_subscription::Variables {
+                condition,
+                limit,
+            });
+
+        self.start::<EventLineageSubscription>(&request_body)
+    }
 }
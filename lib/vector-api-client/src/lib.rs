@@ -15,8 +15,10 @@
 mod client;
 /// GraphQL queries
 pub mod gql;
+mod options;
 mod subscription;
 pub mod test;
 
 pub use client::*;
+pub use options::*;
 pub use subscription::*;
@@ -0,0 +1,78 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::Context;
+
+/// Credentials and TLS options for connecting to a Vector API server.
+#[derive(clap::Args, Clone, Debug, Default)]
+pub struct ConnectOptions {
+    /// Bearer token to authenticate to the Vector API with
+    #[arg(long, env = "VECTOR_API_TOKEN")]
+    pub token: Option<String>,
+
+    /// CA certificate, in PEM format, to verify the certificate of the Vector API with
+    #[arg(long)]
+    pub ca_file: Option<PathBuf>,
+
+    /// Client certificate, in PEM format, to authenticate to the Vector API with
+    #[arg(long, requires = "key_file")]
+    pub crt_file: Option<PathBuf>,
+
+    /// Private key of the client certificate, in PEM (PKCS#8) format
+    #[arg(long, requires = "crt_file")]
+    pub key_file: Option<PathBuf>,
+}
+
+impl ConnectOptions {
+    /// Returns the value of the `Authorization` header to send, if a token is set.
+    pub(crate) fn authorization(&self) -> Option<String> {
+        self.token.as_ref().map(|token| format!("Bearer {}", token))
+    }
+
+    /// Returns an HTTP client, trusting the CA certificate and presenting the client certificate.
+    pub(crate) fn http_client(&self) -> anyhow::Result<reqwest::Client> {
+        let mut builder = reqwest::Client::builder();
+        if let Some(ca_file) = &self.ca_file {
+            let ca = read(ca_file)?;
+            builder = builder.add_root_certificate(
+                reqwest::Certificate::from_pem(&ca)
+                    .with_context(|| format!("Couldn't parse CA certificate {:?}", ca_file))?,
+            );
+        }
+        if let Some((crt_file, key_file)) = self.identity_files() {
+            let identity = reqwest::Identity::from_pkcs8_pem(&read(crt_file)?, &read(key_file)?)
+                .with_context(|| format!("Couldn't parse client certificate {:?}", crt_file))?;
+            builder = builder.identity(identity);
+        }
+        builder.build().context("Couldn't build HTTP client")
+    }
+
+    /// Returns a TLS connector for WebSockets, trusting the CA certificate and presenting the
+    /// client certificate.
+    pub(crate) fn tls_connector(&self) -> anyhow::Result<native_tls::TlsConnector> {
+        let mut builder = native_tls::TlsConnector::builder();
+        if let Some(ca_file) = &self.ca_file {
+            let ca = read(ca_file)?;
+            builder.add_root_certificate(
+                native_tls::Certificate::from_pem(&ca)
+                    .with_context(|| format!("Couldn't parse CA certificate {:?}", ca_file))?,
+            );
+        }
+        if let Some((crt_file, key_file)) = self.identity_files() {
+            let identity = native_tls::Identity::from_pkcs8(&read(crt_file)?, &read(key_file)?)
+                .with_context(|| format!("Couldn't parse client certificate {:?}", crt_file))?;
+            builder.identity(identity);
+        }
+        builder.build().context("Couldn't build TLS connector")
+    }
+
+    fn identity_files(&self) -> Option<(&PathBuf, &PathBuf)> {
+        self.crt_file.as_ref().zip(self.key_file.as_ref())
+    }
+}
+
+fn read(path: &Path) -> anyhow::Result<Vec<u8>> {
+    fs::read(path).with_context(|| format!("Couldn't read {:?}", path))
+}
@@ -13,10 +13,20 @@ use tokio::sync::{
     mpsc, oneshot,
 };
 use tokio_stream::{wrappers::BroadcastStream, Stream, StreamExt};
-use tokio_tungstenite::{connect_async, tungstenite::Message};
+use tokio_tungstenite::{
+    connect_async_tls_with_config,
+    tungstenite::{
+        client::IntoClientRequest,
+        http::{header::AUTHORIZATION, HeaderValue},
+        Message,
+    },
+    Connector,
+};
 use url::Url;
 use uuid::Uuid;
 
+use crate::ConnectOptions;
+
 /// Subscription GraphQL response, returned from an active stream.
 pub type BoxedSubscription<T> = Pin<
     Box<
@@ -176,7 +186,30 @@ impl SubscriptionClient {
 pub async fn connect_subscription_client(
     url: Url,
 ) -> Result<SubscriptionClient, tokio_tungstenite::tungstenite::Error> {
-    let (ws, _) = connect_async(url).await?;
+    connect_subscription_client_with_options(url, &ConnectOptions::default()).await
+}
+
+/// Connect to a new WebSocket GraphQL server endpoint, like `connect_subscription_client`,
+/// authenticating with the provided credentials.
+pub async fn connect_subscription_client_with_options(
+    url: Url,
+    options: &ConnectOptions,
+) -> Result<SubscriptionClient, tokio_tungstenite::tungstenite::Error> {
+    let mut request = url.into_client_request()?;
+    if let Some(authorization) = options.authorization() {
+        request
+            .headers_mut()
+            .insert(AUTHORIZATION, HeaderValue::from_str(&authorization)?);
+    }
+    let connector = options.tls_connector().map_err(|error| {
+        tokio_tungstenite::tungstenite::Error::Io(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            format!("{:#}", error),
+        ))
+    })?;
+
+    let (ws, _) =
+        connect_async_tls_with_config(request, None, Some(Connector::NativeTls(connector))).await?;
     let (mut ws_tx, mut ws_rx) = futures::StreamExt::split(ws);
 
     let (send_tx, mut send_rx) = mpsc::unbounded_channel::<Payload>();
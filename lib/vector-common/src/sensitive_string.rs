@@ -1,11 +1,34 @@
+use std::cell::Cell;
+
 use vector_config::{configurable_component, ConfigurableString};
 
+const REDACTED: &str = "**REDACTED**";
+
+thread_local! {
+    static SERIALIZE_REDACTED: Cell<bool> = Cell::new(false);
+}
+
+/// Serializes sensitive strings redacted rather than in plain text while running the given
+/// function, such as to print configurations.
+pub fn serialize_redacted<T>(f: impl FnOnce() -> T) -> T {
+    struct Reset(bool);
+
+    impl Drop for Reset {
+        fn drop(&mut self) {
+            SERIALIZE_REDACTED.with(|redacted| redacted.set(self.0));
+        }
+    }
+
+    let _reset = Reset(SERIALIZE_REDACTED.with(|redacted| redacted.replace(true)));
+    f()
+}
+
 /// Wrapper for sensitive strings containing credentials
 #[configurable_component(no_deser, no_ser)]
 #[cfg_attr(
     feature = "serde",
-    derive(::serde::Deserialize, ::serde::Serialize),
-    serde(from = "String", into = "String")
+    derive(::serde::Deserialize),
+    serde(from = "String")
 )]
 #[configurable(metadata(sensitive))]
 #[derive(Clone, Default, PartialEq, Eq)]
@@ -25,16 +48,27 @@ impl From<SensitiveString> for String {
 
 impl ConfigurableString for SensitiveString {}
 
+#[cfg(feature = "serde")]
+impl ::serde::Serialize for SensitiveString {
+    fn serialize<S: ::serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if SERIALIZE_REDACTED.with(Cell::get) {
+            serializer.serialize_str(REDACTED)
+        } else {
+            serializer.serialize_str(&self.0)
+        }
+    }
+}
+
 impl std::fmt::Display for SensitiveString {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "**REDACTED**")
+        write!(f, "{}", REDACTED)
     }
 }
 
 impl std::fmt::Debug for SensitiveString {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         // we keep the double quotes here to keep the String behavior
-        write!(f, "\"**REDACTED**\"")
+        write!(f, "\"{}\"", REDACTED)
     }
 }
 
@@ -57,6 +91,15 @@ mod tests {
         assert_eq!(result, json_value);
     }
 
+    #[test]
+    fn serialization_redacted() {
+        let value = SensitiveString("foo".to_string());
+        let result = serialize_redacted(|| serde_json::to_string(&value).unwrap());
+        assert_eq!(result, "\"**REDACTED**\"");
+        let result = serde_json::to_string(&value).unwrap();
+        assert_eq!(result, "\"foo\"");
+    }
+
     #[test]
     fn hide_content() {
         let value = SensitiveString("hello world".to_string());
@@ -2,13 +2,14 @@
 
 use std::collections::BTreeMap;
 use std::sync::Arc;
+use std::time::Instant;
 
 use serde::{Deserialize, Serialize};
 use value::{Kind, Secrets, Value};
 use vector_common::EventDataEq;
 
 use super::{BatchNotifier, EventFinalizer, EventFinalizers, EventStatus};
-use crate::config::LogNamespace;
+use crate::config::{ComponentKey, LogNamespace};
 use crate::{schema, ByteSizeOf};
 
 const DATADOG_API_KEY: &str = "datadog_api_key";
@@ -35,6 +36,44 @@ pub struct EventMetadata {
     /// TODO(Jean): must not skip serialization to track schemas across restarts.
     #[serde(default = "default_schema_definition", skip)]
     schema_definition: Arc<schema::Definition>,
+
+    /// The source that ingested the event into the topology, and when.
+    ///
+    /// This is not preserved across restarts, as the ingest time is monotonic.
+    #[serde(default, skip)]
+    ingest: Option<IngestMetadata>,
+
+    /// The ID under which the lineage of the event through the topology is traced, if it was
+    /// selected for tracing when ingested.
+    #[serde(default, skip)]
+    trace_id: Option<u64>,
+}
+
+/// The source that ingested an event into the topology, and when.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IngestMetadata {
+    source_id: Arc<ComponentKey>,
+    timestamp: Instant,
+}
+
+impl IngestMetadata {
+    /// Creates `IngestMetadata` for an event ingested by the given source at the given time.
+    pub fn new(source_id: Arc<ComponentKey>, timestamp: Instant) -> Self {
+        Self {
+            source_id,
+            timestamp,
+        }
+    }
+
+    /// Returns the ID of the source that ingested the event.
+    pub fn source_id(&self) -> &ComponentKey {
+        &self.source_id
+    }
+
+    /// Returns the monotonic time at which the event was ingested.
+    pub fn timestamp(&self) -> Instant {
+        self.timestamp
+    }
 }
 
 fn default_metadata_value() -> Value {
@@ -98,6 +137,8 @@ impl Default for EventMetadata {
             secrets: Secrets::new(),
             finalizers: Default::default(),
             schema_definition: default_schema_definition(),
+            ingest: None,
+            trace_id: None,
         }
     }
 }
@@ -158,9 +199,23 @@ impl EventMetadata {
     /// Merge the other `EventMetadata` into this.
     /// If a Datadog API key is not set in `self`, the one from `other` will be used.
     /// If a Splunk HEC token is not set in `self`, the one from `other` will be used.
+    /// The earliest ingest metadata of both is kept.
+    /// If a trace ID is not set in `self`, the one from `other` will be used.
     pub fn merge(&mut self, other: Self) {
         self.finalizers.merge(other.finalizers);
         self.secrets.merge(other.secrets);
+        if let Some(other) = other.ingest {
+            if self
+                .ingest
+                .as_ref()
+                .map_or(true, |ingest| other.timestamp < ingest.timestamp)
+            {
+                self.ingest = Some(other);
+            }
+        }
+        if self.trace_id.is_none() {
+            self.trace_id = other.trace_id;
+        }
     }
 
     /// Update the finalizer(s) status.
@@ -202,6 +257,26 @@ impl EventMetadata {
     pub fn set_schema_definition(&mut self, definition: &Arc<schema::Definition>) {
         self.schema_definition = Arc::clone(definition);
     }
+
+    /// Get the source that ingested the event into the topology, and when.
+    pub fn ingest(&self) -> Option<&IngestMetadata> {
+        self.ingest.as_ref()
+    }
+
+    /// Set the source that ingested the event into the topology, and when.
+    pub fn set_ingest(&mut self, ingest: IngestMetadata) {
+        self.ingest = Some(ingest);
+    }
+
+    /// Get the ID under which the lineage of the event is traced.
+    pub fn trace_id(&self) -> Option<u64> {
+        self.trace_id
+    }
+
+    /// Set the ID under which the lineage of the event is traced.
+    pub fn set_trace_id(&mut self, trace_id: u64) {
+        self.trace_id = Some(trace_id);
+    }
 }
 
 impl EventDataEq for EventMetadata {
@@ -249,4 +324,39 @@ mod test {
         assert_eq!(metadata.datadog_api_key().unwrap().as_ref(), SECRET);
         assert_eq!(metadata.splunk_hec_token().unwrap().as_ref(), SECRET2);
     }
+
+    #[test]
+    fn merge_keeps_earliest_ingest() {
+        let source_id = Arc::new(ComponentKey::from("in"));
+        let earlier = Instant::now();
+        let later = earlier + std::time::Duration::from_secs(1);
+
+        let mut metadata = EventMetadata::default();
+        metadata.set_ingest(IngestMetadata::new(Arc::clone(&source_id), later));
+        let mut other = EventMetadata::default();
+        other.set_ingest(IngestMetadata::new(Arc::clone(&source_id), earlier));
+
+        metadata.merge(other);
+        assert_eq!(metadata.ingest().unwrap().timestamp(), earlier);
+
+        metadata.merge(EventMetadata::default());
+        assert_eq!(metadata.ingest().unwrap().timestamp(), earlier);
+    }
+
+    #[test]
+    fn merge_keeps_trace_id() {
+        let mut metadata = EventMetadata::default();
+        metadata.merge(EventMetadata::default());
+        assert_eq!(metadata.trace_id(), None);
+
+        let mut other = EventMetadata::default();
+        other.set_trace_id(1);
+        metadata.merge(other);
+        assert_eq!(metadata.trace_id(), Some(1));
+
+        let mut other = EventMetadata::default();
+        other.set_trace_id(2);
+        metadata.merge(other);
+        assert_eq!(metadata.trace_id(), Some(1));
+    }
 }
@@ -1,6 +1,6 @@
 use std::collections::{BTreeMap, BTreeSet};
 
-use lookup::LookupBuf;
+use lookup::{LookupBuf, OwnedValuePath};
 use value::Kind;
 
 use super::Definition;
@@ -13,6 +13,9 @@ use super::Definition;
 pub struct Requirement {
     /// Semantic meanings confingured for this requirement.
     meaning: BTreeMap<&'static str, SemanticMeaning>,
+
+    /// Fields required by this requirement, by path, with the type they must have.
+    fields: BTreeMap<OwnedValuePath, Kind>,
 }
 
 /// The semantic meaning of an event.
@@ -36,6 +39,7 @@ impl Requirement {
     pub fn empty() -> Self {
         Self {
             meaning: BTreeMap::default(),
+            fields: BTreeMap::default(),
         }
     }
 
@@ -45,7 +49,7 @@ impl Requirement {
     /// 2. The unknown fields are set to "any".
     /// 3. There are no required meanings defined.
     pub fn is_empty(&self) -> bool {
-        self.meaning.is_empty()
+        self.meaning.is_empty() && self.fields.is_empty()
     }
 
     /// Add a restriction to the schema.
@@ -66,6 +70,16 @@ impl Requirement {
         self
     }
 
+    /// Add a required field to the schema.
+    ///
+    /// Unlike semantic meanings, fields are looked up by path in the provided `Definition`, and
+    /// are only missing if the definition guarantees they are undefined.
+    #[must_use]
+    pub fn required_field(mut self, path: OwnedValuePath, kind: Kind) -> Self {
+        self.fields.insert(path, kind);
+        self
+    }
+
     fn insert_meaning(&mut self, identifier: &'static str, kind: Kind, optional: bool) {
         let meaning = SemanticMeaning { kind, optional };
         self.meaning.insert(identifier, meaning);
@@ -117,6 +131,23 @@ impl Requirement {
             }
         }
 
+        for (path, kind) in &self.fields {
+            let definition_kind = definition.event_kind().at_path(path);
+
+            if definition_kind.is_undefined() {
+                errors.push(ValidationError::FieldMissing { path: path.clone() });
+            } else if kind
+                .is_superset(&definition_kind.without_undefined())
+                .is_err()
+            {
+                errors.push(ValidationError::FieldKind {
+                    path: path.clone(),
+                    want: kind.clone(),
+                    got: definition_kind,
+                });
+            }
+        }
+
         if errors.is_empty() {
             Ok(())
         } else {
@@ -176,6 +207,16 @@ pub enum ValidationError {
         identifier: &'static str,
         paths: BTreeSet<LookupBuf>,
     },
+
+    /// A required field is missing.
+    FieldMissing { path: OwnedValuePath },
+
+    /// A required field has an invalid `[Kind]`.
+    FieldKind {
+        path: OwnedValuePath,
+        want: Kind,
+        got: Kind,
+    },
 }
 
 impl ValidationError {
@@ -217,6 +258,12 @@ impl std::fmt::Display for ValidationError {
                     .collect::<Vec<_>>()
                     .join(", ")
             ),
+            Self::FieldMissing { path } => write!(f, "missing field: {}", path),
+            Self::FieldKind { path, want, got } => write!(
+                f,
+                "invalid field: {} (expected {}, got {})",
+                path, want, got
+            ),
         }
     }
 }
@@ -343,6 +390,43 @@ mod tests {
                     }],
                 },
             ),
+            (
+                "missing required field",
+                TestCase {
+                    requirement: Requirement::empty()
+                        .required_field(owned_value_path!("foo"), Kind::any()),
+                    definition: Definition::empty_legacy_namespace(),
+                    errors: vec![ValidationError::FieldMissing {
+                        path: owned_value_path!("foo"),
+                    }],
+                },
+            ),
+            (
+                "possibly defined required field",
+                TestCase {
+                    requirement: Requirement::empty()
+                        .required_field(owned_value_path!("foo"), Kind::any()),
+                    definition: Definition::default_legacy_namespace(),
+                    errors: vec![],
+                },
+            ),
+            (
+                "invalid required field kind",
+                TestCase {
+                    requirement: Requirement::empty()
+                        .required_field(owned_value_path!("foo"), Kind::bytes()),
+                    definition: Definition::empty_legacy_namespace().with_event_field(
+                        &owned_value_path!("foo"),
+                        Kind::integer(),
+                        None,
+                    ),
+                    errors: vec![ValidationError::FieldKind {
+                        path: owned_value_path!("foo"),
+                        want: Kind::bytes(),
+                        got: Kind::integer(),
+                    }],
+                },
+            ),
         ]) {
             let got = requirement.validate(&definition);
             let want = if errors.is_empty() {
@@ -38,7 +38,7 @@ pub const TEST_PEM_CLIENT_KEY_PATH: &str =
 
 /// Configures the TLS options for incoming/outgoing connections.
 #[configurable_component]
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
 pub struct TlsEnableableConfig {
     /// Whether or not to require TLS for incoming/outgoing connections.
     ///
@@ -67,7 +67,7 @@ impl TlsEnableableConfig {
 
 /// TlsEnableableConfig for `sources`, adding metadata from the client certificate
 #[configurable_component]
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
 pub struct TlsSourceConfig {
     /// Event field for client certificate metadata.
     pub client_metadata_key: Option<String>,
@@ -77,7 +77,7 @@ pub struct TlsSourceConfig {
 
 /// Standard TLS options.
 #[configurable_component]
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
 #[serde(deny_unknown_fields)]
 pub struct TlsConfig {
     /// Enables certificate verification.
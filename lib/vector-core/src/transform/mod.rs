@@ -384,6 +384,17 @@ impl TransformOutputsBuf {
         std::mem::take(&mut self.named_buffers)
     }
 
+    /// Iterates over the buffered events, along with the name of the output they are sent to.
+    pub fn iter_events(&self) -> impl Iterator<Item = (Option<&str>, EventRef<'_>)> {
+        self.primary_buffer
+            .iter()
+            .flat_map(|buf| buf.iter_events().map(|event| (None, event)))
+            .chain(self.named_buffers.iter().flat_map(|(name, buf)| {
+                buf.iter_events()
+                    .map(move |event| (Some(name.as_str()), event))
+            }))
+    }
+
     pub fn len(&self) -> usize {
         self.primary_buffer.as_ref().map_or(0, OutputBuffer::len)
             + self
@@ -8,7 +8,7 @@ use std::{
 };
 
 use ::value::Value;
-use clap::Parser;
+use clap::{Parser, Subcommand};
 use lookup::{owned_value_path, OwnedTargetPath};
 use value::Secrets;
 use vector_common::TimeZone;
@@ -18,11 +18,18 @@ use vrl::{CompilationResult, CompileConfig};
 
 #[cfg(feature = "repl")]
 use super::repl;
-use super::Error;
+use super::{fmt, test, Error};
 
 #[derive(Parser, Debug)]
-#[command(name = "VRL", about = "Vector Remap Language CLI")]
+#[command(
+    name = "VRL",
+    about = "Vector Remap Language CLI",
+    args_conflicts_with_subcommands = true
+)]
 pub struct Opts {
+    #[command(subcommand)]
+    command: Option<Command>,
+
     /// The VRL program to execute. The program ".foo = true", for example, sets the event object's
     /// `foo` field to `true`.
     #[arg(id = "PROGRAM")]
@@ -54,14 +61,18 @@ pub struct Opts {
     print_warnings: bool,
 }
 
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Format VRL programs.
+    Fmt(fmt::Opts),
+
+    /// Run VRL programs against input and expected output fixtures.
+    Test(test::Opts),
+}
+
 impl Opts {
     fn timezone(&self) -> Result<TimeZone, Error> {
-        if let Some(ref tz) = self.timezone {
-            TimeZone::parse(tz)
-                .ok_or_else(|| Error::Parse(format!("unable to parse timezone: {}", tz)))
-        } else {
-            Ok(TimeZone::default())
-        }
+        parse_timezone(self.timezone.as_deref())
     }
 
     fn read_program(&self) -> Result<String, Error> {
@@ -94,8 +105,22 @@ impl Opts {
     }
 }
 
+pub(crate) fn parse_timezone(tz: Option<&str>) -> Result<TimeZone, Error> {
+    if let Some(tz) = tz {
+        TimeZone::parse(tz).ok_or_else(|| Error::Parse(format!("unable to parse timezone: {}", tz)))
+    } else {
+        Ok(TimeZone::default())
+    }
+}
+
 #[must_use]
 pub fn cmd(opts: &Opts) -> exitcode::ExitCode {
+    match &opts.command {
+        Some(Command::Fmt(opts)) => return fmt::cmd(opts),
+        Some(Command::Test(opts)) => return test::cmd(opts),
+        None => {}
+    }
+
     match run(opts) {
         Ok(_) => exitcode::OK,
         Err(err) => {
@@ -214,7 +239,7 @@ fn execute(
     }
 }
 
-fn serde_to_vrl(value: serde_json::Value) -> Value {
+pub(crate) fn serde_to_vrl(value: serde_json::Value) -> Value {
     use serde_json::Value as JsonValue;
 
     match value {
@@ -232,7 +257,7 @@ fn serde_to_vrl(value: serde_json::Value) -> Value {
     }
 }
 
-fn read<R: Read>(mut reader: R) -> Result<String, Error> {
+pub(crate) fn read<R: Read>(mut reader: R) -> Result<String, Error> {
     let mut buffer = String::new();
     reader.read_to_string(&mut buffer)?;
 
@@ -0,0 +1,751 @@
+//! Canonical formatting of VRL programs.
+//!
+//! The formatter re-emits the parsed AST with stable indentation and spacing.
+//! Literals and paths are copied verbatim from the source, so escaping and
+//! quoting are never altered. Comments are not part of the AST, they are
+//! re-attached to the statement (or array/object element) they precede or
+//! trail. A comment that can't be attached that way makes formatting fail,
+//! rather than silently dropping it.
+
+use std::{
+    fmt::Write as _,
+    fs,
+    io::{self, Write as _},
+    path::{Path, PathBuf},
+};
+
+use clap::Parser;
+use parser::ast::{
+    Assignment, AssignmentTarget, Block, Container, Expr, FunctionCall, IfStatement, Node,
+    Predicate, Query, QueryTarget, RootExpr, Unary,
+};
+use vrl::diagnostic::{DiagnosticList, Formatter};
+
+use crate::{cmd::read, Error};
+
+const INDENT: &str = "    ";
+
+#[derive(Parser, Debug)]
+pub struct Opts {
+    /// The `.vrl` files to format. Directories are searched recursively. If no path is given,
+    /// the program is read from stdin and the formatted program is written to stdout.
+    #[arg(id = "PATH")]
+    paths: Vec<PathBuf>,
+
+    /// Don't write the formatted programs, exit with a non-zero code if any of them isn't
+    /// formatted. Useful in CI.
+    #[arg(long)]
+    check: bool,
+}
+
+#[must_use]
+pub fn cmd(opts: &Opts) -> exitcode::ExitCode {
+    match run(opts) {
+        Ok(true) => exitcode::OK,
+        Ok(false) => exitcode::DATAERR,
+        Err(err) => {
+            #[allow(clippy::print_stderr)]
+            {
+                eprintln!("{}", err);
+            }
+            exitcode::SOFTWARE
+        }
+    }
+}
+
+/// Formats the given paths, returns `false` if `--check` is set and any of them wasn't formatted.
+fn run(opts: &Opts) -> Result<bool, Error> {
+    if opts.paths.is_empty() {
+        let source = read(io::stdin())?;
+        let formatted = format(&source)?;
+
+        if opts.check {
+            return Ok(formatted == source);
+        }
+
+        io::stdout().write_all(formatted.as_bytes())?;
+        return Ok(true);
+    }
+
+    let mut files = vec![];
+    for path in &opts.paths {
+        collect_programs(path, &mut files)?;
+    }
+
+    let mut unformatted = 0;
+    for file in files {
+        let source = fs::read_to_string(&file)?;
+        let formatted =
+            format(&source).map_err(|err| Error::Format(format!("{}: {}", file.display(), err)))?;
+
+        if formatted == source {
+            continue;
+        }
+
+        if opts.check {
+            #[allow(clippy::print_stdout)]
+            {
+                println!("{} is not formatted", file.display());
+            }
+            unformatted += 1;
+        } else {
+            fs::write(&file, formatted)?;
+        }
+    }
+
+    Ok(unformatted == 0)
+}
+
+/// Collects all `.vrl` files at the given path, recursing into directories.
+pub(crate) fn collect_programs(path: &Path, files: &mut Vec<PathBuf>) -> Result<(), Error> {
+    if !path.is_dir() {
+        files.push(path.to_owned());
+        return Ok(());
+    }
+
+    let mut entries = fs::read_dir(path)?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<Result<Vec<_>, _>>()?;
+    entries.sort();
+
+    for entry in entries {
+        if entry.is_dir() {
+            collect_programs(&entry, files)?;
+        } else if entry.extension().map_or(false, |ext| ext == "vrl") {
+            files.push(entry);
+        }
+    }
+
+    Ok(())
+}
+
+/// Formats a VRL program.
+///
+/// # Errors
+///
+/// Returns an error if the program can't be parsed, or if a comment in the program can't be
+/// preserved.
+pub fn format(source: &str) -> Result<String, Error> {
+    let program = parse(source)?;
+
+    let mut printer = Printer::new(source);
+    printer.program(&program);
+
+    if let Some(&start) = printer.unplaced.first() {
+        return Err(Error::Format(format!(
+            "unable to preserve the comment on line {}, move it onto its own line",
+            line_number(source, start)
+        )));
+    }
+
+    let formatted = printer.out;
+
+    // Guard against the formatter changing the meaning of the program. The `Display`
+    // implementation of the AST ignores spans, so two equal renderings mean two equal programs.
+    let reparsed = parse(&formatted)?;
+    if reparsed.to_string() != program.to_string() {
+        return Err(Error::Format(
+            "formatting changed the program, please report this as a bug".to_owned(),
+        ));
+    }
+
+    Ok(formatted)
+}
+
+fn parse(source: &str) -> Result<parser::Program, Error> {
+    let render = |err: parser::Error| {
+        let diagnostics = DiagnosticList::from(vec![Box::new(err) as Box<_>]);
+        Error::Parse(Formatter::new(source, diagnostics).colored().to_string())
+    };
+
+    let program = parser::parse(source).map_err(render)?;
+
+    // Root expressions that fail to parse are kept in the AST for error recovery.
+    if let Some(err) = program.0.iter().find_map(|expr| match expr.inner() {
+        RootExpr::Error(err) => Some(err.clone()),
+        RootExpr::Expr(_) => None,
+    }) {
+        return Err(render(err));
+    }
+
+    Ok(program)
+}
+
+/// A comment in the source, spanning from the `#` up to (excluding) the newline.
+struct Comment {
+    start: usize,
+    end: usize,
+}
+
+/// Finds all comments in the source, skipping `#` characters inside string literals.
+fn comments(source: &str) -> Vec<Comment> {
+    let mut comments = vec![];
+    let mut chars = source.char_indices().peekable();
+
+    while let Some((pos, chr)) = chars.next() {
+        match chr {
+            '#' => {
+                let end = source[pos..]
+                    .find('\n')
+                    .map_or(source.len(), |end| pos + end);
+                comments.push(Comment {
+                    start: pos,
+                    end: pos + source[pos..end].trim_end().len(),
+                });
+
+                while chars.peek().map_or(false, |&(pos, _)| pos < end) {
+                    chars.next();
+                }
+            }
+            // Regular strings, quoted path segments and raw (`s'`, `r'`, `t'`) literals. Both
+            // allow escaping the closing quote with a backslash.
+            '"' | '\'' => {
+                while let Some((_, c)) = chars.next() {
+                    match c {
+                        '\\' => {
+                            chars.next();
+                        }
+                        c if c == chr => break,
+                        _ => {}
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    comments
+}
+
+fn line_number(source: &str, pos: usize) -> usize {
+    source[..pos].matches('\n').count() + 1
+}
+
+struct Printer<'a> {
+    source: &'a str,
+    comments: Vec<Comment>,
+
+    /// The index of the next comment to be emitted.
+    next_comment: usize,
+
+    /// The position in the source up to which everything has been emitted.
+    pos: usize,
+
+    /// Comments that ended up inside an expression, which can't be preserved.
+    unplaced: Vec<usize>,
+
+    indent: usize,
+    out: String,
+}
+
+impl<'a> Printer<'a> {
+    fn new(source: &'a str) -> Self {
+        Self {
+            source,
+            comments: comments(source),
+            next_comment: 0,
+            pos: 0,
+            unplaced: vec![],
+            indent: 0,
+            out: String::new(),
+        }
+    }
+
+    fn program(&mut self, program: &parser::Program) {
+        let exprs = program
+            .iter()
+            .filter_map(|expr| match expr.inner() {
+                RootExpr::Expr(expr) => Some(expr),
+                RootExpr::Error(_) => None,
+            })
+            .collect::<Vec<_>>();
+
+        self.sequence(
+            &exprs,
+            self.source.len(),
+            "",
+            |expr| (expr.start(), expr.end()),
+            |printer, expr| {
+                printer.expr(expr);
+            },
+        );
+    }
+
+    /// Writes a sequence of items, one per line, interleaved with the comments preceding and
+    /// trailing each item. Single blank lines between items are preserved.
+    fn sequence<T>(
+        &mut self,
+        items: &[T],
+        end: usize,
+        separator: &str,
+        span: impl Fn(&T) -> (usize, usize),
+        mut write: impl FnMut(&mut Self, &T),
+    ) {
+        let mut first = true;
+
+        for item in items {
+            let (start, item_end) = span(item);
+
+            self.leading_comments(start, &mut first);
+            self.blank_line(start, &mut first);
+            self.write_indent();
+            write(self, item);
+            self.out.push_str(separator);
+            self.pos = item_end;
+            self.trailing_comment();
+            self.out.push('\n');
+        }
+
+        self.leading_comments(end, &mut first);
+    }
+
+    /// Writes all comments that start before `limit`, each on its own line.
+    fn leading_comments(&mut self, limit: usize, first: &mut bool) {
+        while let Some(comment) = self.comments.get(self.next_comment) {
+            if comment.start >= limit {
+                break;
+            }
+
+            let (start, end) = (comment.start, comment.end);
+            self.next_comment += 1;
+
+            if start < self.pos {
+                self.unplaced.push(start);
+                continue;
+            }
+
+            self.blank_line(start, first);
+            self.write_indent();
+            self.out.push_str(&self.source[start..end]);
+            self.out.push('\n');
+            self.pos = end;
+        }
+    }
+
+    /// Writes a comment that follows the last emitted item on the same line.
+    fn trailing_comment(&mut self) {
+        while let Some(comment) = self.comments.get(self.next_comment) {
+            if comment.start >= self.pos {
+                break;
+            }
+
+            // The comment is nested inside the item we just wrote.
+            self.unplaced.push(comment.start);
+            self.next_comment += 1;
+        }
+
+        if let Some(comment) = self.comments.get(self.next_comment) {
+            if !self.source[self.pos..comment.start].contains('\n') {
+                self.out.push(' ');
+                self.out.push_str(&self.source[comment.start..comment.end]);
+                self.pos = comment.end;
+                self.next_comment += 1;
+            }
+        }
+    }
+
+    /// Preserves a blank line between the previously emitted item and the one at `next`.
+    fn blank_line(&mut self, next: usize, first: &mut bool) {
+        if !std::mem::take(first) && self.source[self.pos..next].matches('\n').count() > 1 {
+            self.out.push('\n');
+        }
+    }
+
+    fn write_indent(&mut self) {
+        for _ in 0..self.indent {
+            self.out.push_str(INDENT);
+        }
+    }
+
+    fn verbatim(&mut self, start: usize, end: usize) {
+        self.out.push_str(self.source[start..end].trim());
+    }
+
+    fn expr(&mut self, expr: &Node<Expr>) {
+        match expr.inner() {
+            Expr::Literal(_) | Expr::Variable(_) => self.verbatim(expr.start(), expr.end()),
+            Expr::Container(container) => self.container(container),
+            Expr::IfStatement(statement) => self.if_statement(statement),
+            Expr::Op(op) => {
+                self.expr(&op.0);
+                self.out.push(' ');
+                self.out.push_str(op.1.as_str());
+                self.out.push(' ');
+                self.expr(&op.2);
+            }
+            Expr::Assignment(assignment) => self.assignment(assignment),
+            Expr::Query(query) => self.query(query),
+            Expr::FunctionCall(call) => self.function_call(call),
+            Expr::Unary(unary) => match unary.inner() {
+                Unary::Not(not) => {
+                    let (_, expr) = not.inner().clone().take();
+                    self.out.push('!');
+                    self.expr(&expr);
+                }
+            },
+            Expr::Abort(abort) => {
+                self.out.push_str("abort");
+                if let Some(message) = &abort.message {
+                    self.out.push(' ');
+                    self.expr(message);
+                }
+            }
+        }
+    }
+
+    fn container(&mut self, container: &Node<Container>) {
+        match container.inner() {
+            Container::Group(group) => {
+                self.out.push('(');
+                self.expr(&group.0);
+                self.out.push(')');
+            }
+            Container::Block(block) => self.block(block),
+            Container::Array(array) => {
+                let items = array.inner().clone().into_iter().collect::<Vec<_>>();
+
+                if items.is_empty() {
+                    self.out.push_str("[]");
+                } else if self.is_multiline(container.start(), container.end()) {
+                    self.out.push_str("[\n");
+                    self.indent += 1;
+                    self.sequence(
+                        &items,
+                        container.end() - 1,
+                        ",",
+                        |item| (item.start(), item.end()),
+                        Self::expr,
+                    );
+                    self.indent -= 1;
+                    self.write_indent();
+                    self.out.push(']');
+                } else {
+                    self.out.push('[');
+                    self.comma_separated(&items, Self::expr);
+                    self.out.push(']');
+                }
+            }
+            Container::Object(object) => {
+                // Keep the keys in source order, rather than the sorted order of the AST.
+                let mut items = object.inner().clone().into_iter().collect::<Vec<_>>();
+                items.sort_by_key(|(key, _)| key.start());
+
+                let write = |printer: &mut Self, (key, value): &(Node<String>, Node<Expr>)| {
+                    printer.verbatim(key.start(), key.end());
+                    printer.out.push_str(": ");
+                    printer.expr(value);
+                };
+
+                if items.is_empty() {
+                    self.out.push_str("{}");
+                } else if self.is_multiline(container.start(), container.end()) {
+                    self.out.push_str("{\n");
+                    self.indent += 1;
+                    self.sequence(
+                        &items,
+                        container.end() - 1,
+                        ",",
+                        |(key, value)| (key.start(), value.end()),
+                        write,
+                    );
+                    self.indent -= 1;
+                    self.write_indent();
+                    self.out.push('}');
+                } else {
+                    self.out.push_str("{ ");
+                    self.comma_separated(&items, write);
+                    self.out.push_str(" }");
+                }
+            }
+        }
+    }
+
+    fn block(&mut self, block: &Node<Block>) {
+        let exprs = block.inner().0.iter().collect::<Vec<_>>();
+
+        self.out.push_str("{\n");
+        self.indent += 1;
+        self.sequence(
+            &exprs,
+            block.end() - 1,
+            "",
+            |expr| (expr.start(), expr.end()),
+            |printer, expr| {
+                printer.expr(expr);
+            },
+        );
+        self.indent -= 1;
+        self.write_indent();
+        self.out.push('}');
+    }
+
+    fn if_statement(&mut self, statement: &IfStatement) {
+        self.out.push_str("if ");
+
+        match statement.predicate.inner() {
+            Predicate::One(expr) => self.expr(expr),
+            Predicate::Many(exprs) => {
+                self.out.push('(');
+                for (i, expr) in exprs.iter().enumerate() {
+                    if i > 0 {
+                        self.out.push_str("; ");
+                    }
+                    self.expr(expr);
+                }
+                self.out.push(')');
+            }
+        }
+
+        self.out.push(' ');
+        self.block(&statement.if_node);
+
+        if let Some(alternative) = &statement.else_node {
+            self.out.push_str(" else ");
+
+            // An `else if` is parsed as an `else` block holding a single `if` statement, sharing
+            // the span of the block.
+            match alternative.inner().0.as_slice() {
+                [expr] if expr.start() == alternative.start() => {
+                    if let Expr::IfStatement(statement) = expr.inner() {
+                        self.if_statement(statement);
+                    } else {
+                        self.block(alternative);
+                    }
+                }
+                _ => self.block(alternative),
+            }
+        }
+    }
+
+    fn assignment(&mut self, assignment: &Assignment) {
+        match assignment {
+            Assignment::Single { target, op, expr } => {
+                self.assignment_target(target);
+                let _ = write!(self.out, " {} ", op);
+                self.expr(expr);
+            }
+            Assignment::Infallible { ok, err, op, expr } => {
+                self.assignment_target(ok);
+                self.out.push_str(", ");
+                self.assignment_target(err);
+                let _ = write!(self.out, " {} ", op);
+                self.expr(expr);
+            }
+        }
+    }
+
+    fn assignment_target(&mut self, target: &Node<AssignmentTarget>) {
+        match target.inner() {
+            AssignmentTarget::Query(query) => self.query(query),
+            _ => self.verbatim(target.start(), target.end()),
+        }
+    }
+
+    fn query(&mut self, query: &Query) {
+        match query.target.inner() {
+            QueryTarget::Internal(_) | QueryTarget::External(_) => {
+                self.verbatim(
+                    query.target.start(),
+                    query.path.end().max(query.target.end()),
+                );
+            }
+            QueryTarget::FunctionCall(call) => {
+                self.function_call(call);
+                self.verbatim(query.target.end(), query.path.end());
+            }
+            QueryTarget::Container(container) => {
+                self.container(&Node::new(query.target.span(), container.clone()));
+                self.verbatim(query.target.end(), query.path.end());
+            }
+        }
+    }
+
+    fn function_call(&mut self, call: &FunctionCall) {
+        self.out.push_str(&call.ident);
+        if call.abort_on_error {
+            self.out.push('!');
+        }
+
+        let write = |printer: &mut Self, argument: &Node<parser::ast::FunctionArgument>| {
+            if let Some(ident) = &argument.ident {
+                printer.out.push_str(ident);
+                printer.out.push_str(": ");
+            }
+            printer.expr(&argument.expr);
+        };
+
+        match call.arguments.last() {
+            Some(last) if self.is_multiline(call.ident.start(), last.end()) => {
+                let end = self.closing_delimiter(last.end(), ')');
+
+                self.out.push_str("(\n");
+                self.indent += 1;
+                self.sequence(
+                    &call.arguments,
+                    end,
+                    ",",
+                    |argument| (argument.start(), argument.end()),
+                    write,
+                );
+                self.indent -= 1;
+                self.write_indent();
+                self.out.push(')');
+            }
+            _ => {
+                self.out.push('(');
+                self.comma_separated(&call.arguments, write);
+                self.out.push(')');
+            }
+        }
+
+        if let Some(closure) = &call.closure {
+            self.out.push_str(" -> |");
+            for (i, variable) in closure.variables.iter().enumerate() {
+                if i > 0 {
+                    self.out.push_str(", ");
+                }
+                if variable.is_empty() {
+                    self.out.push('_');
+                } else {
+                    self.out.push_str(variable);
+                }
+            }
+            self.out.push_str("| ");
+            self.block(&closure.block);
+        }
+    }
+
+    fn comma_separated<T>(&mut self, items: &[T], mut write: impl FnMut(&mut Self, &T)) {
+        for (i, item) in items.iter().enumerate() {
+            if i > 0 {
+                self.out.push_str(", ");
+            }
+            write(self, item);
+        }
+    }
+
+    /// Finds the position of the delimiter closing a list, skipping the trailing comma, whitespace
+    /// and comments after the last item.
+    fn closing_delimiter(&self, from: usize, delimiter: char) -> usize {
+        let mut chars = self.source[from..].char_indices();
+
+        while let Some((pos, chr)) = chars.next() {
+            match chr {
+                '#' => {
+                    for (_, chr) in chars.by_ref() {
+                        if chr == '\n' {
+                            break;
+                        }
+                    }
+                }
+                chr if chr == delimiter => return from + pos,
+                _ => {}
+            }
+        }
+
+        self.source.len()
+    }
+
+    fn is_multiline(&self, start: usize, end: usize) -> bool {
+        self.source[start..end].contains('\n')
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+
+    use super::*;
+
+    #[test]
+    fn formats_program() {
+        let source = indoc! {r#"
+            # normalize the event
+            .foo    =   "bar"
+            if .status==500{
+            .level = "error"   # server side
+
+
+            } else if exists(.code) { .level="warn" } else {
+              del(.level)
+            }
+            .tags = {"b":1,"a":[1,2,  3]}
+            .list = map_values(.list) -> |v|   { upcase!(v) }
+        "#};
+
+        let expected = indoc! {r#"
+            # normalize the event
+            .foo = "bar"
+            if .status == 500 {
+                .level = "error" # server side
+            } else if exists(.code) {
+                .level = "warn"
+            } else {
+                del(.level)
+            }
+            .tags = { "b": 1, "a": [1, 2, 3] }
+            .list = map_values(.list) -> |v| {
+                upcase!(v)
+            }
+        "#};
+
+        assert_eq!(format(source).unwrap(), expected);
+    }
+
+    #[test]
+    fn preserves_blank_lines_and_multiline_containers() {
+        let source = indoc! {r#"
+            .a = 1
+
+
+
+            .b = {
+              # first
+              "x": s'raw\n',
+              "y": null
+            }
+        "#};
+
+        let expected = indoc! {r#"
+            .a = 1
+
+            .b = {
+                # first
+                "x": s'raw\n',
+                "y": null,
+            }
+        "#};
+
+        assert_eq!(format(source).unwrap(), expected);
+    }
+
+    #[test]
+    fn is_idempotent() {
+        let source = indoc! {r#"
+            .message = parse_json!(string!(.message))
+            .x, err = .a + (.b * 2) ?? 0
+            abort
+        "#};
+
+        let formatted = format(source).unwrap();
+        assert_eq!(format(&formatted).unwrap(), formatted);
+    }
+
+    #[test]
+    fn rejects_unplaceable_comments() {
+        let source = indoc! {r#"
+            .a = 1 +
+              # inside an operation
+              2
+        "#};
+
+        assert!(matches!(format(source), Err(Error::Format(_))));
+    }
+
+    #[test]
+    fn ignores_hashes_in_strings() {
+        let source = ".a = \"#not a comment\"\n";
+
+        assert_eq!(format(source).unwrap(), source);
+    }
+}
@@ -13,8 +13,10 @@
 )]
 
 pub mod cmd;
+pub mod fmt;
 #[cfg(feature = "repl")]
 mod repl;
+pub mod test;
 
 pub use cmd::{cmd, Opts};
 
@@ -33,6 +35,9 @@ pub enum Error {
     #[error("input error: {}", .0)]
     Json(#[from] serde_json::Error),
 
+    #[error("format error: {}", .0)]
+    Format(String),
+
     #[error("repl feature disabled, program input required")]
     ReplFeature,
 
@@ -0,0 +1,384 @@
+//! Runs VRL programs against input and expected output fixtures.
+//!
+//! For a program `foo.vrl`, every `foo.input.json` or `foo.<case>.input.json` file next to it is
+//! a test case holding the event to run the program against. The resulting event is compared
+//! with the matching `foo.output.json` (or `foo.<case>.output.json`) file. An output fixture
+//! containing `null` expects the program to abort.
+//!
+//! Without an output fixture, a case passes as long as the program doesn't fail, which is how
+//! programs relying on `assert` and `assert_eq` are tested. A program without any input fixture
+//! is run once against an empty event.
+
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use ::value::Value;
+use clap::Parser;
+use value::Secrets;
+use vector_common::TimeZone;
+use vrl::{
+    diagnostic::Formatter, state, state::TypeState, CompilationResult, CompileConfig, Function,
+    Program, Runtime, TargetValueRef, Terminate,
+};
+
+use crate::{
+    cmd::{parse_timezone, serde_to_vrl},
+    fmt::collect_programs,
+    Error,
+};
+
+const INPUT_SUFFIX: &str = ".input.json";
+const OUTPUT_SUFFIX: &str = ".output.json";
+
+#[derive(Parser, Debug)]
+pub struct Opts {
+    /// The `.vrl` programs to test. Directories are searched recursively.
+    #[arg(id = "PATH", required = true)]
+    paths: Vec<PathBuf>,
+
+    /// The timezone used to parse dates.
+    #[arg(short = 'z', long)]
+    timezone: Option<String>,
+
+    /// Stop at the first failing test case.
+    #[arg(short, long)]
+    fail_early: bool,
+}
+
+#[must_use]
+pub fn cmd(opts: &Opts) -> exitcode::ExitCode {
+    match run(opts) {
+        Ok(true) => exitcode::OK,
+        Ok(false) => exitcode::DATAERR,
+        Err(err) => {
+            #[allow(clippy::print_stderr)]
+            {
+                eprintln!("{}", err);
+            }
+            exitcode::SOFTWARE
+        }
+    }
+}
+
+/// A single fixture pair of a program.
+struct Case {
+    name: String,
+    input: Option<PathBuf>,
+    output: Option<PathBuf>,
+}
+
+/// Runs all test cases, returns `false` if any of them failed.
+#[allow(clippy::print_stdout)]
+fn run(opts: &Opts) -> Result<bool, Error> {
+    let timezone = parse_timezone(opts.timezone.as_deref())?;
+
+    let mut files = vec![];
+    for path in &opts.paths {
+        collect_programs(path, &mut files)?;
+    }
+
+    let mut functions = stdlib::all();
+    functions.extend(vector_vrl_functions::vrl_functions());
+
+    let mut passed = 0;
+    let mut failed = 0;
+
+    'programs: for file in files {
+        let source = fs::read_to_string(&file)?;
+
+        let program = match compile(&source, &functions) {
+            Ok(program) => program,
+            Err(diagnostics) => {
+                println!("{} ... FAILED (compilation)", file.display());
+                println!("{}", diagnostics);
+                failed += 1;
+
+                if opts.fail_early {
+                    break;
+                }
+                continue;
+            }
+        };
+
+        for case in cases(&file)? {
+            let name = if case.name.is_empty() {
+                file.display().to_string()
+            } else {
+                format!("{}::{}", file.display(), case.name)
+            };
+
+            match run_case(&program, &case, timezone) {
+                Ok(()) => {
+                    println!("{} ... ok", name);
+                    passed += 1;
+                }
+                Err(reason) => {
+                    println!("{} ... FAILED", name);
+                    println!("{}", reason);
+                    failed += 1;
+
+                    if opts.fail_early {
+                        break 'programs;
+                    }
+                }
+            }
+        }
+    }
+
+    println!();
+    println!(
+        "test result: {}. {} passed; {} failed",
+        if failed == 0 { "ok" } else { "FAILED" },
+        passed,
+        failed
+    );
+
+    Ok(failed == 0)
+}
+
+fn compile(source: &str, functions: &[Box<dyn Function>]) -> Result<Program, String> {
+    let CompilationResult { program, .. } = vrl::compile_with_state(
+        source,
+        functions,
+        &TypeState::default(),
+        CompileConfig::default(),
+    )
+    .map_err(|diagnostics| Formatter::new(source, diagnostics).colored().to_string())?;
+
+    Ok(program)
+}
+
+/// Finds the fixtures of the given program.
+fn cases(program: &Path) -> Result<Vec<Case>, Error> {
+    let dir = program.parent().unwrap_or_else(|| Path::new("."));
+    let dir = if dir.as_os_str().is_empty() {
+        Path::new(".")
+    } else {
+        dir
+    };
+    let stem = program
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_default();
+
+    let mut names = fs::read_dir(dir)?
+        .map(|entry| entry.map(|entry| entry.file_name().to_string_lossy().into_owned()))
+        .collect::<Result<Vec<_>, _>>()?
+        .into_iter()
+        .filter_map(|file_name| {
+            let case = file_name
+                .strip_prefix(stem.as_str())?
+                .strip_suffix(INPUT_SUFFIX)?;
+
+            match case {
+                "" => Some(String::new()),
+                case => case.strip_prefix('.').map(ToOwned::to_owned),
+            }
+        })
+        .collect::<Vec<_>>();
+    names.sort();
+
+    if names.is_empty() {
+        return Ok(vec![Case {
+            name: String::new(),
+            input: None,
+            output: None,
+        }]);
+    }
+
+    Ok(names
+        .into_iter()
+        .map(|name| {
+            let prefix = if name.is_empty() {
+                stem.clone()
+            } else {
+                format!("{}.{}", stem, name)
+            };
+            let output = dir.join(format!("{}{}", prefix, OUTPUT_SUFFIX));
+
+            Case {
+                name,
+                input: Some(dir.join(format!("{}{}", prefix, INPUT_SUFFIX))),
+                output: output.exists().then_some(output),
+            }
+        })
+        .collect())
+}
+
+/// Runs the program against a single case, returns the reason of the failure, if any.
+fn run_case(program: &Program, case: &Case, timezone: TimeZone) -> Result<(), String> {
+    let mut object = match &case.input {
+        Some(path) => read_json(path).map(serde_to_vrl)?,
+        None => Value::Object(BTreeMap::new()),
+    };
+    let want = case.output.as_deref().map(read_json).transpose()?;
+
+    let mut metadata = Value::Object(BTreeMap::new());
+    let mut secrets = Secrets::new();
+    let mut target = TargetValueRef {
+        value: &mut object,
+        metadata: &mut metadata,
+        secrets: &mut secrets,
+    };
+
+    let mut runtime = Runtime::new(state::Runtime::default());
+    let got = match runtime.resolve(&mut target, program, &timezone) {
+        Ok(_) => serde_json::to_value(&object).map_err(|err| err.to_string())?,
+        Err(Terminate::Abort(_)) => serde_json::Value::Null,
+        Err(err @ Terminate::Error(_)) => return Err(format!("  error: {}", err)),
+    };
+
+    match want {
+        Some(want) if want != got => {
+            let want = serde_json::to_string_pretty(&want).map_err(|err| err.to_string())?;
+            let got = serde_json::to_string_pretty(&got).map_err(|err| err.to_string())?;
+
+            Err(format!("{}", prettydiff::diff_lines(&want, &got)))
+        }
+        None if got.is_null() => Err("  error: program aborted".to_owned()),
+        _ => Ok(()),
+    }
+}
+
+fn read_json(path: &Path) -> Result<serde_json::Value, String> {
+    let content = fs::read_to_string(path)
+        .map_err(|err| format!("  unable to read {}: {}", path.display(), err))?;
+
+    serde_json::from_str(&content)
+        .map_err(|err| format!("  unable to parse {}: {}", path.display(), err))
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::TempDir;
+
+    use super::*;
+
+    fn write(dir: &TempDir, name: &str, content: &str) -> PathBuf {
+        let path = dir.path().join(name);
+        fs::write(&path, content).unwrap();
+        path
+    }
+
+    fn program(source: &str) -> Program {
+        let mut functions = stdlib::all();
+        functions.extend(vector_vrl_functions::vrl_functions());
+        compile(source, &functions).unwrap()
+    }
+
+    fn run_program(source: &str, input: &str, output: Option<&str>) -> Result<(), String> {
+        let dir = TempDir::new().unwrap();
+        let case = Case {
+            name: String::new(),
+            input: Some(write(&dir, "case.input.json", input)),
+            output: output.map(|output| write(&dir, "case.output.json", output)),
+        };
+
+        run_case(&program(source), &case, TimeZone::default())
+    }
+
+    fn opts(dir: &TempDir) -> Opts {
+        Opts {
+            paths: vec![dir.path().to_owned()],
+            timezone: None,
+            fail_early: false,
+        }
+    }
+
+    #[test]
+    fn finds_cases() {
+        let dir = TempDir::new().unwrap();
+        let file = write(&dir, "foo.vrl", ".b = 1");
+        write(&dir, "foo.input.json", "{}");
+        write(&dir, "foo.output.json", "{}");
+        write(&dir, "foo.bar.input.json", "{}");
+        write(&dir, "foo.baz.output.json", "{}");
+        write(&dir, "foobar.input.json", "{}");
+        write(&dir, "other.input.json", "{}");
+
+        let cases = cases(&file).unwrap();
+        let cases = cases
+            .iter()
+            .map(|case| (case.name.as_str(), case.input.clone(), case.output.clone()))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            cases,
+            vec![
+                (
+                    "",
+                    Some(dir.path().join("foo.input.json")),
+                    Some(dir.path().join("foo.output.json"))
+                ),
+                ("bar", Some(dir.path().join("foo.bar.input.json")), None),
+            ]
+        );
+    }
+
+    #[test]
+    fn runs_programs_without_fixtures_once() {
+        let dir = TempDir::new().unwrap();
+        let file = write(&dir, "foo.vrl", ".b = 1");
+
+        let cases = cases(&file).unwrap();
+
+        assert_eq!(cases.len(), 1);
+        assert!(cases[0].name.is_empty());
+        assert!(cases[0].input.is_none());
+        assert!(cases[0].output.is_none());
+    }
+
+    #[test]
+    fn runs_cases() {
+        let source = ".b = .a";
+
+        assert_eq!(
+            run_program(source, r#"{"a": 1}"#, Some(r#"{"a": 1, "b": 1}"#)),
+            Ok(())
+        );
+        assert!(run_program(source, r#"{"a": 1}"#, Some(r#"{"a": 1, "b": 2}"#)).is_err());
+        assert_eq!(run_program(source, r#"{"a": 1}"#, None), Ok(()));
+        assert!(run_program("assert!(.a == 2)", r#"{"a": 1}"#, None)
+            .unwrap_err()
+            .starts_with("  error: "));
+    }
+
+    #[test]
+    fn runs_aborting_cases() {
+        assert_eq!(run_program("abort", "{}", Some("null")), Ok(()));
+        assert_eq!(
+            run_program("abort", "{}", None),
+            Err("  error: program aborted".to_owned())
+        );
+        assert!(run_program("abort", "{}", Some("{}")).is_err());
+    }
+
+    #[test]
+    fn exits_with_outcome() {
+        let dir = TempDir::new().unwrap();
+        write(&dir, "ok.vrl", ".b = .a");
+        write(&dir, "ok.input.json", r#"{"a": 1}"#);
+        write(&dir, "ok.output.json", r#"{"a": 1, "b": 1}"#);
+        assert_eq!(cmd(&opts(&dir)), exitcode::OK);
+
+        write(&dir, "failed.vrl", ".b = .a");
+        write(&dir, "failed.input.json", r#"{"a": 1}"#);
+        write(&dir, "failed.output.json", r#"{"a": 1, "b": 2}"#);
+        assert_eq!(cmd(&opts(&dir)), exitcode::DATAERR);
+
+        // Programs that fail to compile fail their test as well.
+        let dir = TempDir::new().unwrap();
+        write(&dir, "invalid.vrl", ".b = ");
+        assert_eq!(cmd(&opts(&dir)), exitcode::DATAERR);
+
+        let missing = Opts {
+            paths: vec![dir.path().join("missing.vrl")],
+            timezone: None,
+            fail_early: false,
+        };
+        assert_eq!(cmd(&missing), exitcode::SOFTWARE);
+    }
+}
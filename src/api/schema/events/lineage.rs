@@ -0,0 +1,71 @@
+use async_graphql::{Enum, Object};
+
+use crate::internal_telemetry::lineage::{Action, Record};
+
+#[derive(Enum, Copy, Clone, PartialEq, Eq)]
+/// What a component did to a traced event
+pub enum LineageAction {
+    /// Ingested by a source
+    Ingested,
+    /// Sent by a transform unchanged
+    Passed,
+    /// Modified by a transform
+    Mutated,
+    /// Dropped by a transform
+    Dropped,
+    /// Delivered by a sink
+    Delivered,
+    /// Failed to be delivered by a sink
+    Failed,
+}
+
+impl From<Action> for LineageAction {
+    fn from(action: Action) -> Self {
+        match action {
+            Action::Ingested => Self::Ingested,
+            Action::Passed => Self::Passed,
+            Action::Mutated => Self::Mutated,
+            Action::Dropped => Self::Dropped,
+            Action::Delivered => Self::Delivered,
+            Action::Failed => Self::Failed,
+        }
+    }
+}
+
+/// A record of what a component did to an event traced through the topology
+#[derive(Debug, Clone)]
+pub struct LineageRecord(Record);
+
+impl From<Record> for LineageRecord {
+    fn from(record: Record) -> Self {
+        Self(record)
+    }
+}
+
+#[Object]
+impl LineageRecord {
+    /// ID of the trace of the event
+    pub async fn trace_id(&self) -> i64 {
+        self.0.trace_id as i64
+    }
+
+    /// Component ID
+    pub async fn component_id(&self) -> &str {
+        self.0.component_id.id()
+    }
+
+    /// Component kind
+    pub async fn component_kind(&self) -> &str {
+        self.0.component_kind
+    }
+
+    /// Named output the event was sent to, if any
+    pub async fn output(&self) -> Option<&str> {
+        self.0.output.as_deref()
+    }
+
+    /// What the component did to the event
+    pub async fn action(&self) -> LineageAction {
+        self.0.action.into()
+    }
+}
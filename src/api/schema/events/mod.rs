@@ -1,4 +1,5 @@
 mod encoding;
+pub mod lineage;
 pub mod log;
 pub mod metric;
 pub mod notification;
@@ -7,15 +8,22 @@ pub mod trace;
 
 use std::collections::HashSet;
 
-use async_graphql::{Context, Subscription};
+use async_graphql::{Context, Guard, Subscription};
 use encoding::EventEncodingType;
 use futures::{stream, Stream, StreamExt};
+use lineage::LineageRecord;
 use output::OutputEventsPayload;
 use rand::{rngs::SmallRng, Rng, SeedableRng};
 use tokio::{select, sync::mpsc, time};
 use tokio_stream::wrappers::ReceiverStream;
 
-use crate::{api::tap::TapController, topology::WatchRx};
+use crate::{
+    api::tap::{TapController, TapFilter},
+    conditions::{ConditionConfig, VrlConfig},
+    config::api::Scope,
+    internal_telemetry::lineage::Subscription,
+    topology::WatchRx,
+};
 
 /// Patterns (glob) used by tap to match against components and access events
 /// flowing into (for_inputs) or out of (for_outputs) specified components
@@ -43,12 +51,29 @@ impl TapPatterns {
     }
 }
 
+/// Rejects clients whose scope doesn't allow tapping events. Schemas executed without a scope,
+/// such as in tests, aren't authenticated.
+struct TapGuard;
+
+#[async_trait::async_trait]
+impl Guard for TapGuard {
+    async fn check(&self, ctx: &Context<'_>) -> async_graphql::Result<()> {
+        match ctx.data_opt::<Scope>() {
+            Some(Scope::ReadOnly) => {
+                Err("The `read_only` scope doesn't allow tapping events.".into())
+            }
+            Some(Scope::Tap) | None => Ok(()),
+        }
+    }
+}
+
 #[derive(Debug, Default)]
 pub struct EventsSubscription;
 
 #[Subscription]
 impl EventsSubscription {
     /// A stream of events emitted from matched component ID patterns
+    #[graphql(guard = "TapGuard")]
     pub async fn output_events_by_component_id_patterns<'a>(
         &'a self,
         ctx: &'a Context<'a>,
@@ -56,15 +81,44 @@ impl EventsSubscription {
         inputs_patterns: Option<Vec<String>>,
         #[graphql(default = 500)] interval: u32,
         #[graphql(default = 100, validator(minimum = 1, maximum = 10_000))] limit: u32,
-    ) -> impl Stream<Item = Vec<OutputEventsPayload>> + 'a {
+        filter: Option<String>,
+        fields: Option<Vec<String>>,
+    ) -> async_graphql::Result<impl Stream<Item = Vec<OutputEventsPayload>> + 'a> {
         let watch_rx = ctx.data_unchecked::<WatchRx>().clone();
 
         let patterns = TapPatterns {
             for_outputs: outputs_patterns.into_iter().collect(),
             for_inputs: inputs_patterns.unwrap_or_default().into_iter().collect(),
         };
+        // Events are filtered as they're tapped, before they're sampled.
+        let filter = TapFilter::new(filter, fields).map_err(|error| error.to_string())?;
+
         // Client input is confined to `u32` to provide sensible bounds.
-        create_events_stream(watch_rx, patterns, interval as u64, limit as usize)
+        Ok(create_events_stream(
+            watch_rx,
+            patterns,
+            filter,
+            interval as u64,
+            limit as usize,
+        ))
+    }
+
+    /// A stream of records of what components do to the events matching a VRL condition when
+    /// ingested by sources, tracing up to `limit` events
+    #[graphql(guard = "TapGuard")]
+    pub async fn event_lineage(
+        &self,
+        condition: String,
+        #[graphql(default = 10, validator(minimum = 1, maximum = 1_000))] limit: u32,
+    ) -> async_graphql::Result<impl Stream<Item = LineageRecord>> {
+        let condition = ConditionConfig::Vrl(VrlConfig {
+            source: condition,
+            runtime: Default::default(),
+        })
+        .build(&Default::default())
+        .map_err(|error| error.to_string())?;
+
+        Ok(Subscription::new(condition, limit as usize).map(Into::into))
     }
 }
 
@@ -74,6 +128,7 @@ impl EventsSubscription {
 pub(crate) fn create_events_stream(
     watch_rx: WatchRx,
     patterns: TapPatterns,
+    filter: TapFilter,
     interval: u64,
     limit: usize,
 ) -> impl Stream<Item = Vec<OutputEventsPayload>> {
@@ -91,7 +146,7 @@ pub(crate) fn create_events_stream(
     tokio::spawn(async move {
         // Create a tap controller. When this drops out of scope, clean up will be performed on the
         // event handlers and topology observation that the tap controller provides.
-        let _tap_controller = TapController::new(watch_rx, tap_tx, patterns);
+        let _tap_controller = TapController::new(watch_rx, tap_tx, patterns, filter);
 
         // A tick interval to represent when to 'cut' the results back to the client.
         let mut interval = time::interval(time::Duration::from_millis(interval));
@@ -0,0 +1,64 @@
+use async_graphql::Object;
+
+use super::sum_metrics;
+use crate::{
+    config::ComponentKey,
+    event::{Metric, MetricValue},
+};
+
+pub struct ComponentBufferUsage {
+    component_key: ComponentKey,
+    metrics: Vec<Metric>,
+}
+
+impl ComponentBufferUsage {
+    /// Returns a new `ComponentBufferUsage` from the buffer metrics of a component, which are
+    /// summed across the stages of the buffer
+    pub fn new(component_key: ComponentKey, metrics: Vec<Metric>) -> Self {
+        Self {
+            component_key,
+            metrics,
+        }
+    }
+
+    fn sum(&self, name: &str) -> Option<f64> {
+        let sum = sum_metrics(self.metrics.iter().filter(|m| m.name() == name))?;
+        match sum.value() {
+            MetricValue::Counter { value } | MetricValue::Gauge { value } => Some(*value),
+            _ => None,
+        }
+    }
+}
+
+#[Object]
+impl ComponentBufferUsage {
+    /// Component id
+    async fn component_id(&self) -> &str {
+        self.component_key.id()
+    }
+
+    /// Number of events in the buffer
+    async fn events(&self) -> f64 {
+        self.sum("buffer_events").unwrap_or(0.00)
+    }
+
+    /// Number of bytes in the buffer
+    async fn byte_size(&self) -> f64 {
+        self.sum("buffer_byte_size").unwrap_or(0.00)
+    }
+
+    /// Maximum number of events the buffer can hold, if limited by events
+    async fn max_events(&self) -> Option<f64> {
+        self.sum("buffer_max_event_size")
+    }
+
+    /// Maximum number of bytes the buffer can hold, if limited by bytes
+    async fn max_byte_size(&self) -> Option<f64> {
+        self.sum("buffer_max_byte_size")
+    }
+
+    /// Total number of events discarded by the buffer
+    async fn discarded_events_total(&self) -> f64 {
+        self.sum("buffer_discarded_events_total").unwrap_or(0.00)
+    }
+}
@@ -0,0 +1,31 @@
+use async_graphql::Object;
+
+use crate::config::ComponentKey;
+
+pub struct ComponentCpuTimeThroughput {
+    component_key: ComponentKey,
+    throughput: i64,
+}
+
+impl ComponentCpuTimeThroughput {
+    /// Returns a new `ComponentCpuTimeThroughput`, set to the provided id/throughput values
+    pub const fn new(component_key: ComponentKey, throughput: i64) -> Self {
+        Self {
+            component_key,
+            throughput,
+        }
+    }
+}
+
+#[Object]
+impl ComponentCpuTimeThroughput {
+    /// Component id
+    async fn component_id(&self) -> &str {
+        self.component_key.id()
+    }
+
+    /// Nanoseconds spent polling the tasks of the component
+    async fn throughput(&self) -> i64 {
+        self.throughput
+    }
+}
@@ -1,3 +1,5 @@
+mod buffer;
+mod cpu_time;
 mod errors;
 mod events_in;
 mod events_out;
@@ -16,8 +18,10 @@ mod uptime;
 mod host;
 
 use async_graphql::{Interface, Object, Subscription};
+pub use buffer::ComponentBufferUsage;
 use chrono::{DateTime, Utc};
-pub use errors::{ComponentErrorsTotal, ErrorsTotal};
+pub use cpu_time::ComponentCpuTimeThroughput;
+pub use errors::{ComponentErrorsThroughput, ComponentErrorsTotal, ErrorsTotal};
 pub use events_in::EventsInTotal;
 pub use events_out::EventsOutTotal;
 pub use filter::*;
@@ -297,6 +301,57 @@ impl MetricsSubscription {
             .map(|m| m.into_iter().map(ComponentErrorsTotal::new).collect())
     }
 
+    /// Component error throughput metrics over `interval`.
+    async fn component_errors_throughputs(
+        &self,
+        #[graphql(default = 1000, validator(minimum = 10, maximum = 60_000))] interval: i32,
+    ) -> impl Stream<Item = Vec<ComponentErrorsThroughput>> {
+        component_counter_throughputs(interval, &|m| m.name().ends_with("_errors_total")).map(|m| {
+            m.into_iter()
+                .map(|(m, throughput)| {
+                    ComponentErrorsThroughput::new(
+                        ComponentKey::from(m.tag_value("component_id").unwrap()),
+                        throughput as i64,
+                    )
+                })
+                .collect()
+        })
+    }
+
+    /// Component buffer usage, summed across the stages of the buffer of each component, every
+    /// `interval`.
+    async fn component_buffer_usages(
+        &self,
+        #[graphql(default = 1000, validator(minimum = 10, maximum = 60_000))] interval: i32,
+    ) -> impl Stream<Item = Vec<ComponentBufferUsage>> {
+        component_to_filtered_metrics(interval, &|m| m.name().starts_with("buffer_")).map(|map| {
+            map.into_iter()
+                .map(|(id, metrics)| ComponentBufferUsage::new(ComponentKey::from(id), metrics))
+                .collect()
+        })
+    }
+
+    /// Component CPU time, in nanoseconds spent polling the tasks of each component, over
+    /// `interval`.
+    async fn component_cpu_time_throughputs(
+        &self,
+        #[graphql(default = 1000, validator(minimum = 10, maximum = 60_000))] interval: i32,
+    ) -> impl Stream<Item = Vec<ComponentCpuTimeThroughput>> {
+        component_counter_throughputs(interval, &|m| {
+            m.name() == "component_cpu_time_nanoseconds_total"
+        })
+        .map(|m| {
+            m.into_iter()
+                .map(|(m, throughput)| {
+                    ComponentCpuTimeThroughput::new(
+                        ComponentKey::from(m.tag_value("component_id").unwrap()),
+                        throughput as i64,
+                    )
+                })
+                .collect()
+        })
+    }
+
     /// All metrics.
     async fn metrics(
         &self,
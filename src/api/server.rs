@@ -9,11 +9,24 @@ use async_graphql::{
     Data, Request, Schema,
 };
 use async_graphql_warp::{graphql_protocol, GraphQLResponse, GraphQLWebSocket};
+use sha2::{Digest, Sha256};
 use tokio::sync::oneshot;
-use warp::{filters::BoxedFilter, http::Response, ws::Ws, Filter, Reply};
+use warp::{
+    filters::BoxedFilter,
+    http::{Response, StatusCode},
+    ws::Ws,
+    Filter, Rejection, Reply,
+};
 
 use super::{handler, schema, ShutdownTx};
-use crate::{config, topology};
+use crate::{
+    config::{
+        self,
+        api::{AuthOptions, Scope},
+    },
+    tls::MaybeTlsSettings,
+    topology,
+};
 
 pub struct Server {
     _shutdown: ShutdownTx,
@@ -23,16 +36,35 @@ pub struct Server {
 impl Server {
     /// Start the API server. This creates the routes and spawns a Warp server. The server is
     /// gracefully shut down when Self falls out of scope by way of the oneshot sender closing.
-    pub fn start(
+    pub async fn start(
         config: &config::Config,
         watch_rx: topology::WatchRx,
         running: Arc<AtomicBool>,
-    ) -> Self {
-        let routes = make_routes(config.api.playground, watch_rx, running);
+    ) -> crate::Result<Self> {
+        let tls_config = config.api.tls.as_ref().map(|tls| &tls.tls_config);
+        if let Some(auth) = &config.api.auth {
+            let verifies_clients = tls_config.map_or(false, |tls| {
+                tls.enabled.unwrap_or(false) && tls.options.verify_certificate.unwrap_or(false)
+            });
+            if auth.client_certificate_scope.is_some() && !verifies_clients {
+                return Err("`api.auth.client_certificate_scope` requires `api.tls.enabled` and `api.tls.verify_certificate`.".into());
+            }
+        }
+
+        let authenticator = Authenticator {
+            auth: config.api.auth.clone(),
+        };
+        let routes = make_routes(config.api.playground, watch_rx, running, authenticator);
+
+        let tls = MaybeTlsSettings::from_config(&tls_config.cloned(), true)?;
+        let listener = tls
+            .bind(&config.api.address.expect("No socket address"))
+            .await?;
+        let addr = listener.local_addr()?;
 
         let (_shutdown, rx) = oneshot::channel();
-        let (addr, server) = warp::serve(routes).bind_with_graceful_shutdown(
-            config.api.address.expect("No socket address"),
+        let server = warp::serve(routes).serve_incoming_with_graceful_shutdown(
+            listener.accept_stream(),
             async {
                 rx.await.ok();
             },
@@ -44,7 +76,7 @@ impl Server {
         // Spawn the server in the background.
         tokio::spawn(server);
 
-        Self { _shutdown, addr }
+        Ok(Self { _shutdown, addr })
     }
 
     /// Returns a copy of the SocketAddr that the server was started on.
@@ -60,10 +92,60 @@ impl Server {
     }
 }
 
+/// Authenticates the clients of the API, telling the scope they're allowed.
+#[derive(Clone, Debug)]
+struct Authenticator {
+    auth: Option<AuthOptions>,
+}
+
+impl Authenticator {
+    /// Returns the scope of a client from its `Authorization` header, if it's allowed at all.
+    ///
+    /// Clients without a token are authenticated by their certificate, which the TLS handshake
+    /// already required and verified when `client_certificate_scope` is set.
+    fn scope(&self, authorization: Option<&str>) -> Option<Scope> {
+        let auth = match &self.auth {
+            Some(auth) => auth,
+            None => return Some(Scope::Tap),
+        };
+
+        match authorization {
+            Some(authorization) => {
+                // Tokens are compared by their digests, so that the time taken doesn't tell how
+                // much of a valid token the client guessed.
+                let token = Sha256::digest(authorization.strip_prefix("Bearer ")?);
+                auth.tokens
+                    .iter()
+                    .find(|candidate| Sha256::digest(candidate.token.inner()) == token)
+                    .map(|candidate| candidate.scope)
+            }
+            None => auth.client_certificate_scope,
+        }
+    }
+}
+
+/// Rejection of clients that failed to authenticate.
+#[derive(Debug)]
+struct Unauthorized;
+
+impl warp::reject::Reject for Unauthorized {}
+
+async fn recover_unauthorized(rejection: Rejection) -> Result<impl Reply, Rejection> {
+    if rejection.find::<Unauthorized>().is_some() {
+        Ok(warp::reply::with_status(
+            "Unauthorized",
+            StatusCode::UNAUTHORIZED,
+        ))
+    } else {
+        Err(rejection)
+    }
+}
+
 fn make_routes(
     playground: bool,
     watch_tx: topology::WatchRx,
     running: Arc<AtomicBool>,
+    authenticator: Authenticator,
 ) -> BoxedFilter<(impl Reply,)> {
     // Routes...
 
@@ -78,13 +160,18 @@ fn make_routes(
     // GraphQL subscription handler. Creates a Warp WebSocket handler and for each connection,
     // parses the required headers for GraphQL and builds per-connection context based on the
     // provided `WatchTx` channel sender. This allows GraphQL resolvers to subscribe to
-    // topology changes.
-    let graphql_subscription_handler =
-        warp::ws()
-            .and(graphql_protocol())
-            .map(move |ws: Ws, protocol: WebSocketProtocols| {
+    // topology changes. Clients are authenticated when initializing the connection, either by
+    // the `Authorization` header of the upgrade request, or by the `Authorization` key of the
+    // initialization payload, since browsers can't set headers on WebSockets.
+    let ws_authenticator = authenticator.clone();
+    let graphql_subscription_handler = warp::ws()
+        .and(graphql_protocol())
+        .and(warp::header::optional::<String>("authorization"))
+        .map(
+            move |ws: Ws, protocol: WebSocketProtocols, authorization: Option<String>| {
                 let schema = schema::build_schema().finish();
                 let watch_tx = watch_tx.clone();
+                let authenticator = ws_authenticator.clone();
 
                 let reply = ws.on_upgrade(move |socket| {
                     let mut data = Data::default();
@@ -92,6 +179,21 @@ fn make_routes(
 
                     GraphQLWebSocket::new(socket, schema, protocol)
                         .with_data(data)
+                        .on_connection_init(move |payload| async move {
+                            let authorization = authorization.or_else(|| {
+                                payload
+                                    .get("Authorization")
+                                    .and_then(|value| value.as_str())
+                                    .map(ToOwned::to_owned)
+                            });
+                            let scope = authenticator
+                                .scope(authorization.as_deref())
+                                .ok_or("Unauthorized")?;
+
+                            let mut data = Data::default();
+                            data.insert(scope);
+                            Ok::<_, async_graphql::Error>(data)
+                        })
                         .serve()
                 });
 
@@ -100,18 +202,28 @@ fn make_routes(
                     "Sec-WebSocket-Protocol",
                     protocol.sec_websocket_protocol(),
                 )
-            });
+            },
+        );
 
     // Handle GraphQL queries. Headers will first be parsed to determine whether the query is
     // a subscription and if so, an attempt will be made to upgrade the connection to WebSockets.
-    // All other queries will fall back to the default HTTP handler.
-    let graphql_handler = warp::path("graphql").and(graphql_subscription_handler.or(
-        async_graphql_warp::graphql(schema::build_schema().finish()).and_then(
-            |(schema, request): (Schema<_, _, _>, Request)| async move {
-                Ok::<_, Infallible>(GraphQLResponse::from(schema.execute(request).await))
-            },
-        ),
-    ));
+    // All other queries will fall back to the default HTTP handler, after authenticating the
+    // client by its `Authorization` header.
+    let graphql_handler = warp::path("graphql").and(
+        graphql_subscription_handler.or(warp::header::optional::<String>("authorization")
+            .and_then(move |authorization: Option<String>| {
+                let scope = authenticator.scope(authorization.as_deref());
+                async move { scope.ok_or_else(|| warp::reject::custom(Unauthorized)) }
+            })
+            .and(async_graphql_warp::graphql(schema::build_schema().finish()))
+            .and_then(
+                |scope: Scope, (schema, request): (Schema<_, _, _>, Request)| async move {
+                    Ok::<_, Infallible>(GraphQLResponse::from(
+                        schema.execute(request.data(scope)).await,
+                    ))
+                },
+            )),
+    );
 
     // Provide a playground for executing GraphQL queries/mutations/subscriptions.
     let graphql_playground = if playground {
@@ -134,6 +246,7 @@ fn make_routes(
         .or(graphql_handler)
         .or(graphql_playground)
         .or(not_found)
+        .recover(recover_unauthorized)
         .with(
             warp::cors()
                 .allow_any_origin()
@@ -146,6 +259,7 @@ fn make_routes(
                     "Access-Control-Allow-Origin",
                     "Access-Control-Request-Headers",
                     "Content-Type",
+                    "Authorization",
                     "X-Apollo-Tracing", // for Apollo GraphQL clients
                     "Pragma",
                     "Host",
@@ -162,3 +276,51 @@ fn with_shared(
 ) -> impl Filter<Extract = (Arc<AtomicBool>,), Error = Infallible> + Clone {
     warp::any().map(move || Arc::<AtomicBool>::clone(&shared))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::api::Token;
+
+    #[test]
+    fn authenticates_scopes() {
+        let authenticator = Authenticator {
+            auth: Some(AuthOptions {
+                tokens: vec![
+                    Token {
+                        token: "reader".to_owned().into(),
+                        scope: Scope::ReadOnly,
+                    },
+                    Token {
+                        token: "tapper".to_owned().into(),
+                        scope: Scope::Tap,
+                    },
+                ],
+                client_certificate_scope: None,
+            }),
+        };
+
+        assert_eq!(
+            authenticator.scope(Some("Bearer reader")),
+            Some(Scope::ReadOnly)
+        );
+        assert_eq!(authenticator.scope(Some("Bearer tapper")), Some(Scope::Tap));
+        assert_eq!(authenticator.scope(Some("Bearer other")), None);
+        assert_eq!(authenticator.scope(Some("reader")), None);
+        assert_eq!(authenticator.scope(None), None);
+    }
+
+    #[test]
+    fn authenticates_client_certificates() {
+        let authenticator = Authenticator {
+            auth: Some(AuthOptions {
+                tokens: Vec::new(),
+                client_certificate_scope: Some(Scope::ReadOnly),
+            }),
+        };
+        assert_eq!(authenticator.scope(None), Some(Scope::ReadOnly));
+
+        let authenticator = Authenticator { auth: None };
+        assert_eq!(authenticator.scope(None), Some(Scope::Tap));
+    }
+}
@@ -1,9 +1,11 @@
 use std::{
     collections::{HashMap, HashSet},
     num::NonZeroUsize,
+    sync::Arc,
 };
 
 use futures::{future::try_join_all, FutureExt};
+use lookup::lookup_v2::parse_value_path;
 use tokio::sync::{
     mpsc as tokio_mpsc,
     mpsc::error::{SendError, TrySendError},
@@ -20,8 +22,10 @@ use super::{
     ShutdownRx, ShutdownTx,
 };
 use crate::{
+    codecs::Transformer,
+    conditions::{Condition, ConditionConfig, VrlConfig},
     config::ComponentKey,
-    event::{EventArray, LogArray, MetricArray, TraceArray},
+    event::{Event, EventArray, LogArray, MetricArray, TraceArray},
     topology::{fanout, fanout::ControlChannel, TapOutput, TapResource, WatchRx},
 };
 
@@ -83,6 +87,16 @@ pub enum TapPayload {
 }
 
 impl TapPayload {
+    /// Returns whether the payload has no events, such as when none matched the tap filter.
+    fn is_empty(&self) -> bool {
+        match self {
+            Self::Log(_, logs) => logs.is_empty(),
+            Self::Metric(_, metrics) => metrics.is_empty(),
+            Self::Trace(_, traces) => traces.is_empty(),
+            Self::Notification(_) => false,
+        }
+    }
+
     /// Raise a `matched` event against the provided pattern.
     pub fn matched<T: Into<String>>(pattern: T) -> Self {
         Self::Notification(Notification::Matched(Matched::new(pattern.into())))
@@ -125,24 +139,108 @@ impl TapPayload {
     }
 }
 
+/// A tap filter selects the events matching a VRL condition, and projects logs to a set of
+/// fields. It's applied to events as they're tapped, so that events that don't match aren't
+/// sampled.
+#[derive(Clone, Debug, Default)]
+pub struct TapFilter {
+    condition: Option<Condition>,
+    transformer: Option<Transformer>,
+}
+
+impl TapFilter {
+    /// Compiles the `filter` VRL condition and parses the `fields` paths.
+    pub fn new(filter: Option<String>, fields: Option<Vec<String>>) -> crate::Result<Self> {
+        let condition = filter
+            .map(|source| {
+                ConditionConfig::Vrl(VrlConfig {
+                    source,
+                    runtime: Default::default(),
+                })
+                .build(&Default::default())
+            })
+            .transpose()?;
+
+        let transformer = fields
+            .map(|fields| {
+                let only_fields = fields
+                    .iter()
+                    .map(|field| {
+                        parse_value_path(field)
+                            .map_err(|error| format!("Invalid field {:?}: {}", field, error))
+                    })
+                    .collect::<Result<_, _>>()?;
+                Transformer::new(Some(only_fields), None, None)
+            })
+            .transpose()?;
+
+        Ok(Self {
+            condition,
+            transformer,
+        })
+    }
+
+    /// Returns the events matching the condition, projected to the fields.
+    fn apply<T: Into<Event>>(&self, events: Vec<T>, from_event: fn(Event) -> T) -> Vec<T> {
+        if self.condition.is_none() && self.transformer.is_none() {
+            return events;
+        }
+
+        events
+            .into_iter()
+            .filter_map(|event| {
+                let mut event = event.into();
+                if let Some(condition) = &self.condition {
+                    let (matched, checked) = condition.check(event);
+                    if !matched {
+                        return None;
+                    }
+                    event = checked;
+                }
+                if let Some(transformer) = &self.transformer {
+                    transformer.transform(&mut event);
+                }
+                Some(from_event(event))
+            })
+            .collect()
+    }
+}
+
 /// A `TapTransformer` transforms raw events and ships them to the global tap receiver.
 #[derive(Clone)]
 pub struct TapTransformer {
     tap_tx: TapSender,
     output: TapOutput,
+    filter: Arc<TapFilter>,
 }
 
 impl TapTransformer {
-    pub const fn new(tap_tx: TapSender, output: TapOutput) -> Self {
-        Self { tap_tx, output }
+    pub const fn new(tap_tx: TapSender, output: TapOutput, filter: Arc<TapFilter>) -> Self {
+        Self {
+            tap_tx,
+            output,
+            filter,
+        }
     }
 
     pub fn try_send(&mut self, events: EventArray) {
         let payload = match events {
-            EventArray::Logs(logs) => TapPayload::Log(self.output.clone(), logs),
-            EventArray::Metrics(metrics) => TapPayload::Metric(self.output.clone(), metrics),
-            EventArray::Traces(traces) => TapPayload::Trace(self.output.clone(), traces),
+            EventArray::Logs(logs) => TapPayload::Log(
+                self.output.clone(),
+                self.filter.apply(logs, Event::into_log),
+            ),
+            EventArray::Metrics(metrics) => TapPayload::Metric(
+                self.output.clone(),
+                self.filter.apply(metrics, Event::into_metric),
+            ),
+            EventArray::Traces(traces) => TapPayload::Trace(
+                self.output.clone(),
+                self.filter.apply(traces, Event::into_trace),
+            ),
         };
+        if payload.is_empty() {
+            return;
+        }
 
         if let Err(TrySendError::Closed(payload)) = self.tap_tx.try_send(payload) {
             debug!(
@@ -165,10 +263,21 @@ impl TapController {
     /// Creates a new tap sink, and spawns a handler for watching for topology changes
     /// and a separate inner handler for events. Uses a oneshot channel to trigger shutdown
     /// of handlers when the `TapSink` drops out of scope.
-    pub fn new(watch_rx: WatchRx, tap_tx: TapSender, patterns: TapPatterns) -> Self {
+    pub fn new(
+        watch_rx: WatchRx,
+        tap_tx: TapSender,
+        patterns: TapPatterns,
+        filter: TapFilter,
+    ) -> Self {
         let (_shutdown, shutdown_rx) = oneshot::channel();
 
-        tokio::spawn(tap_handler(patterns, tap_tx, watch_rx, shutdown_rx));
+        tokio::spawn(tap_handler(
+            patterns,
+            Arc::new(filter),
+            tap_tx,
+            watch_rx,
+            shutdown_rx,
+        ));
 
         Self { _shutdown }
     }
@@ -237,6 +346,7 @@ async fn send_invalid_output_pattern_match(
 /// `LogEvent`s` when a component matches one or more of the provided patterns.
 async fn tap_handler(
     patterns: TapPatterns,
+    filter: Arc<TapFilter>,
     tx: TapSender,
     mut watch_rx: WatchRx,
     mut shutdown_rx: ShutdownRx,
@@ -316,7 +426,7 @@ async fn tap_handler(
                             // wrap each event payload with the necessary metadata before forwarding
                             // it to our global tap receiver.
                             let (tap_buffer_tx, mut tap_buffer_rx) = TopologyBuilder::standalone_memory(TAP_BUFFER_SIZE, WhenFull::DropNewest).await;
-                            let mut tap_transformer = TapTransformer::new(tx.clone(), output.clone());
+                            let mut tap_transformer = TapTransformer::new(tx.clone(), output.clone(), Arc::clone(&filter));
 
                             tokio::spawn(async move {
                                 while let Some(events) = tap_buffer_rx.next().await {
@@ -418,6 +528,8 @@ async fn tap_handler(
     feature = "transforms-remap",
 ))]
 mod tests {
+    use std::collections::BTreeMap;
+
     use futures::StreamExt;
     use tokio::sync::watch;
 
@@ -425,7 +537,7 @@ mod tests {
     use crate::api::schema::events::output::OutputEventsPayload;
     use crate::api::schema::events::{create_events_stream, log, metric};
     use crate::config::{Config, OutputId};
-    use crate::event::{LogEvent, Metric, MetricKind, MetricValue};
+    use crate::event::{LogEvent, Metric, MetricKind, MetricValue, Value};
     use crate::sinks::blackhole::BlackholeConfig;
     use crate::sources::demo_logs::{DemoLogsConfig, OutputFormat};
     use crate::test_util::{start_topology, trace_init};
@@ -448,6 +560,43 @@ mod tests {
         }
     }
 
+    #[test]
+    /// Filters should select events by condition and project them to fields.
+    fn filters_events() {
+        let filter = TapFilter::new(
+            Some(r#".tenant == "a""#.to_string()),
+            Some(vec!["message".to_string(), "http.status".to_string()]),
+        )
+        .unwrap();
+
+        let logs = vec![
+            LogEvent::from(BTreeMap::from([
+                ("tenant".to_string(), Value::from("a")),
+                ("message".to_string(), Value::from("kept")),
+                (
+                    "http".to_string(),
+                    Value::from(BTreeMap::from([
+                        ("status".to_string(), Value::from(200)),
+                        ("path".to_string(), Value::from("/")),
+                    ])),
+                ),
+            ])),
+            LogEvent::from(BTreeMap::from([
+                ("tenant".to_string(), Value::from("b")),
+                ("message".to_string(), Value::from("dropped")),
+            ])),
+        ];
+
+        let logs = filter.apply(logs, Event::into_log);
+        assert_eq!(logs.len(), 1);
+        assert_eq!(logs[0].get("message"), Some(&Value::from("kept")));
+        assert_eq!(logs[0].get("http.status"), Some(&Value::from(200)));
+        assert_eq!(logs[0].get("http.path"), None);
+        assert_eq!(logs[0].get("tenant"), None);
+
+        assert!(TapFilter::new(Some("not vrl (".to_string()), None).is_err());
+    }
+
     #[tokio::test]
     /// A tap sink should match a pattern, receive the correct notifications,
     /// and receive events
@@ -484,6 +633,7 @@ mod tests {
                 HashSet::from([pattern_matched.to_string(), pattern_not_matched.to_string()]),
                 HashSet::new(),
             ),
+            TapFilter::default(),
         );
 
         // Add the outputs to trigger a change event.
@@ -594,6 +744,7 @@ mod tests {
         let source_tap_stream = create_events_stream(
             topology.watch(),
             TapPatterns::new(HashSet::from(["in".to_string()]), HashSet::new()),
+            TapFilter::default(),
             500,
             100,
         );
@@ -652,6 +803,7 @@ mod tests {
         let source_tap_stream = create_events_stream(
             topology.watch(),
             TapPatterns::new(HashSet::from(["to_metric".to_string()]), HashSet::new()),
+            TapFilter::default(),
             500,
             100,
         );
@@ -702,6 +854,7 @@ mod tests {
         let transform_tap_stream = create_events_stream(
             topology.watch(),
             TapPatterns::new(HashSet::from(["transform".to_string()]), HashSet::new()),
+            TapFilter::default(),
             500,
             100,
         );
@@ -758,6 +911,7 @@ mod tests {
                 HashSet::new(),
                 HashSet::from(["transform".to_string(), "in".to_string()]),
             ),
+            TapFilter::default(),
             500,
             100,
         );
@@ -829,6 +983,7 @@ mod tests {
         let tap_stream = create_events_stream(
             topology.watch(),
             TapPatterns::new(HashSet::new(), HashSet::from(["out".to_string()])),
+            TapFilter::default(),
             500,
             100,
         );
@@ -892,6 +1047,7 @@ mod tests {
                 HashSet::from(["transform.dropped".to_string()]),
                 HashSet::new(),
             ),
+            TapFilter::default(),
             500,
             100,
         );
@@ -965,6 +1121,7 @@ mod tests {
         let mut transform_tap_all_outputs_stream = create_events_stream(
             topology.watch(),
             TapPatterns::new(HashSet::from(["transform*".to_string()]), HashSet::new()),
+            TapFilter::default(),
             500,
             100,
         );
@@ -146,6 +146,11 @@ impl Application {
                     crate::internal_telemetry::allocations::TRACK_ALLOCATIONS
                         .store(true, Ordering::Relaxed);
                 }
+                if root_opts.latency_tracking {
+                    use std::sync::atomic::Ordering;
+                    crate::internal_telemetry::latency::TRACK_LATENCY
+                        .store(true, Ordering::Relaxed);
+                }
                 trace::init(color, json, &level, root_opts.internal_log_rate_limit);
                 info!(
                     message = "Internal log rate limit configured.",
@@ -235,7 +240,7 @@ impl Application {
                     .ok_or(exitcode::CONFIG)?;
 
                 #[cfg(feature = "api")]
-                let api = config.api;
+                let api = config.api.clone();
 
                 let result = topology::start_validated(config, diff, pieces).await;
                 let (topology, graceful_crash) = result.ok_or(exitcode::CONFIG)?;
@@ -272,7 +277,7 @@ impl Application {
         let opts = self.opts;
 
         #[cfg(feature = "api")]
-        let api_config = self.config.api;
+        let api_config = self.config.api.clone();
 
         #[cfg(feature = "enterprise")]
         let mut enterprise = self.config.enterprise;
@@ -298,7 +303,14 @@ impl Application {
                     playground: api_config.playground
                 });
 
-                Some(api::Server::start(topology.config(), topology.watch(), Arc::<AtomicBool>::clone(&topology.running)))
+                match api::Server::start(topology.config(), topology.watch(), Arc::<AtomicBool>::clone(&topology.running)).await {
+                    Ok(api_server) => Some(api_server),
+                    Err(error) => {
+                        error!(message = "Failed to start the API server.", %error);
+                        topology.stop().await;
+                        std::process::exit(exitcode::CONFIG);
+                    }
+                }
             } else {
                 info!(message="API is disabled, enable by setting `api.enabled` to `true` and use commands like `vector top`.");
                 None
@@ -307,6 +319,8 @@ impl Application {
             let mut sources_finished = topology.sources_finished();
 
             let signal = loop {
+                let probation_deadline = topology.probation_deadline();
+
                 tokio::select! {
                     signal = signal_rx.recv() => {
                         match signal {
@@ -417,6 +431,30 @@ impl Application {
                             Ok(signal) => break signal,
                         }
                     }
+                    // End the probation of a reloaded configuration, rolling it back if it degraded the pipeline.
+                    _ = async move {
+                        match probation_deadline {
+                            Some(deadline) => tokio::time::sleep_until(deadline).await,
+                            None => futures::future::pending().await,
+                        }
+                    } => {
+                        match topology.end_probation().await {
+                            Ok(true) => {
+                                #[cfg(feature = "api")]
+                                // Pass the restored config to the API server.
+                                if let Some(ref api_server) = api_server {
+                                    api_server.update_config(topology.config());
+                                }
+                            },
+                            Ok(false) => (),
+                            // Trigger graceful shutdown for what remains of the topology
+                            Err(()) => {
+                                emit!(VectorRecoveryError);
+                                break SignalTo::Shutdown;
+                            }
+                        }
+                        sources_finished = topology.sources_finished();
+                    }
                     // Trigger graceful shutdown if a component crashed, or all sources have ended.
                     _ = graceful_crash.next() => break SignalTo::Shutdown,
                     _ = &mut sources_finished => break SignalTo::Shutdown,
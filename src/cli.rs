@@ -148,6 +148,12 @@ pub struct RootOpts {
     #[arg(short, long, env = "VECTOR_WATCH_CONFIG")]
     pub watch_config: bool,
 
+    /// Record the latency of events from their source to the sinks delivering them.
+    ///
+    /// This tracks the delivery of every event reaching a sink, which has a cost.
+    #[arg(long, env = "VECTOR_LATENCY_TRACKING")]
+    pub latency_tracking: bool,
+
     /// Set the internal log rate limit
     #[arg(
         short,
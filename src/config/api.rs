@@ -1,10 +1,13 @@
 use std::net::{Ipv4Addr, SocketAddr};
 
+use vector_common::sensitive_string::SensitiveString;
 use vector_config::configurable_component;
 
+use crate::tls::TlsSourceConfig;
+
 /// API options.
 #[configurable_component]
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, PartialEq)]
 #[serde(default, deny_unknown_fields)]
 pub struct Options {
     /// Whether or not the API endpoint is available.
@@ -18,6 +21,24 @@ pub struct Options {
     /// Whether or not to expose the GraphQL playground on the API endpoint.
     #[serde(default = "default_playground")]
     pub playground: bool,
+
+    /// Configures the TLS options for the API endpoint.
+    ///
+    /// When `verify_certificate` is enabled, clients must present a certificate signed by
+    /// `ca_file`.
+    #[configurable(derived)]
+    #[serde(
+        default,
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    pub tls: Option<TlsSourceConfig>,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    pub auth: Option<AuthOptions>,
 }
 
 impl Default for Options {
@@ -26,10 +47,55 @@ impl Default for Options {
             enabled: default_enabled(),
             playground: default_playground(),
             address: default_address(),
+            tls: None,
+            auth: None,
         }
     }
 }
 
+/// Authentication options for the API endpoint.
+///
+/// When set, clients must authenticate with one of the `tokens`, or with a client certificate
+/// if `client_certificate_scope` is set.
+#[configurable_component]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct AuthOptions {
+    /// The bearer tokens that clients can authenticate with.
+    #[serde(default)]
+    pub tokens: Vec<Token>,
+
+    /// The scope of clients that authenticate with a client certificate rather than a token.
+    ///
+    /// Requires `tls.verify_certificate` to be enabled, so that clients must present a
+    /// certificate signed by `tls.ca_file`. By default, clients must authenticate with a token.
+    pub client_certificate_scope: Option<Scope>,
+}
+
+/// A bearer token that clients of the API can authenticate with.
+#[configurable_component]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Token {
+    /// The token, which clients send in the `Authorization: Bearer <token>` header.
+    pub token: SensitiveString,
+
+    /// The scope of clients that authenticate with this token.
+    pub scope: Scope,
+}
+
+/// What clients of the API are allowed to do.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum Scope {
+    /// Query the components, health and metrics of Vector, without tapping events.
+    ReadOnly,
+
+    /// Tap the events flowing through components, in addition to the `read_only` scope.
+    Tap,
+}
+
 const fn default_enabled() -> bool {
     false
 }
@@ -66,10 +132,15 @@ impl Options {
             }
         };
 
+        let tls = merge_equal("tls", self.tls.clone(), other.tls)?;
+        let auth = merge_equal("auth", self.auth.clone(), other.auth)?;
+
         let options = Options {
             address,
             enabled: self.enabled | other.enabled,
             playground: self.playground & other.playground,
+            tls,
+            auth,
         };
 
         *self = options;
@@ -77,12 +148,23 @@ impl Options {
     }
 }
 
+/// Merges options that must be the same wherever they're set.
+fn merge_equal<T: PartialEq>(name: &str, a: Option<T>, b: Option<T>) -> Result<Option<T>, String> {
+    match (a, b) {
+        (None, b) => Ok(b),
+        (a, None) => Ok(a),
+        (Some(a), Some(b)) if a == b => Ok(Some(a)),
+        _ => Err(format!("Conflicting `api.{}` options.", name)),
+    }
+}
+
 #[test]
 fn bool_merge() {
     let mut a = Options {
         enabled: true,
         address: None,
         playground: false,
+        ..Options::default()
     };
 
     a.merge(Options::default()).unwrap();
@@ -93,6 +175,7 @@ fn bool_merge() {
             enabled: true,
             address: default_address(),
             playground: false,
+            ..Options::default()
         }
     );
 }
@@ -104,6 +187,7 @@ fn bind_merge() {
         enabled: true,
         address: Some(address),
         playground: true,
+        ..Options::default()
     };
 
     a.merge(Options::default()).unwrap();
@@ -114,6 +198,7 @@ fn bind_merge() {
             enabled: true,
             address: Some(address),
             playground: true,
+            ..Options::default()
         }
     );
 }
@@ -132,3 +217,32 @@ fn bind_conflict() {
 
     assert!(a.merge(b).is_err());
 }
+
+#[test]
+fn auth_merge() {
+    let auth = AuthOptions {
+        tokens: vec![Token {
+            token: "secret".to_owned().into(),
+            scope: Scope::ReadOnly,
+        }],
+        client_certificate_scope: None,
+    };
+    let mut a = Options {
+        auth: Some(auth.clone()),
+        ..Options::default()
+    };
+
+    a.merge(Options::default()).unwrap();
+    a.merge(Options {
+        auth: Some(auth.clone()),
+        ..Options::default()
+    })
+    .unwrap();
+    assert_eq!(a.auth, Some(auth));
+
+    let b = Options {
+        auth: Some(AuthOptions::default()),
+        ..Options::default()
+    };
+    assert!(a.merge(b).is_err());
+}
@@ -18,8 +18,8 @@ use super::api;
 #[cfg(feature = "enterprise")]
 use super::enterprise;
 use super::{
-    compiler, schema, ComponentKey, Config, EnrichmentTableOuter, HealthcheckOptions, SinkOuter,
-    SourceOuter, TestDefinition, TransformOuter,
+    compiler, schema, ComponentKey, Config, EnrichmentTableOuter, HealthcheckOptions,
+    ReloadOptions, SinkOuter, SourceOuter, TestDefinition, TransformOuter,
 };
 
 /// A complete Vector configuration.
@@ -49,6 +49,13 @@ pub struct ConfigBuilder {
     #[serde(default)]
     pub healthchecks: HealthcheckOptions,
 
+    #[configurable(derived)]
+    #[serde(
+        default,
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    pub reload: ReloadOptions,
+
     /// All configured enrichment tables.
     #[serde(default)]
     pub enrichment_tables: IndexMap<ComponentKey, EnrichmentTableOuter>,
@@ -89,6 +96,8 @@ struct ConfigBuilderHash<'a> {
     schema: &'a schema::Options,
     global: &'a GlobalOptions,
     healthchecks: &'a HealthcheckOptions,
+    #[serde(skip_serializing_if = "crate::serde::skip_serializing_if_default")]
+    reload: ReloadOptions,
     enrichment_tables: BTreeMap<&'a ComponentKey, &'a EnrichmentTableOuter>,
     sources: BTreeMap<&'a ComponentKey, &'a SourceOuter>,
     sinks: BTreeMap<&'a ComponentKey, &'a SinkOuter<String>>,
@@ -168,6 +177,7 @@ impl<'a> From<&'a ConfigBuilder> for ConfigBuilderHash<'a> {
             schema: &value.schema,
             global: &value.global,
             healthchecks: &value.healthchecks,
+            reload: value.reload,
             enrichment_tables: value.enrichment_tables.iter().collect(),
             sources: value.sources.iter().collect(),
             sinks: value.sinks.iter().collect(),
@@ -189,6 +199,7 @@ impl From<Config> for ConfigBuilder {
             #[cfg(feature = "enterprise")]
             enterprise,
             healthchecks,
+            reload,
             enrichment_tables,
             sources,
             sinks,
@@ -219,6 +230,7 @@ impl From<Config> for ConfigBuilder {
             #[cfg(feature = "enterprise")]
             enterprise,
             healthchecks,
+            reload,
             enrichment_tables,
             sources,
             sinks,
@@ -332,6 +344,10 @@ impl ConfigBuilder {
 
         self.healthchecks.merge(with.healthchecks);
 
+        if let Err(error) = self.reload.merge(with.reload) {
+            errors.push(error);
+        }
+
         with.enrichment_tables.keys().for_each(|k| {
             if self.enrichment_tables.contains_key(k) {
                 errors.push(format!("duplicate enrichment_table name found: {}", k));
@@ -3,13 +3,18 @@ use std::path::PathBuf;
 use clap::Parser;
 use serde_json::Value;
 
-use super::{load_builder_from_paths, load_source_from_paths, process_paths, ConfigBuilder};
+use super::{
+    diff_cmd, load_builder_from_paths, load_source_from_paths, process_paths, ConfigBuilder,
+};
 use crate::cli::handle_config_errors;
 use crate::config;
 
 #[derive(Parser, Debug, Clone)]
-#[command(rename_all = "kebab-case")]
+#[command(rename_all = "kebab-case", args_conflicts_with_subcommands = true)]
 pub struct Opts {
+    #[command(subcommand)]
+    command: Option<Command>,
+
     /// Pretty print JSON
     #[arg(short, long)]
     pretty: bool,
@@ -57,6 +62,15 @@ pub struct Opts {
     pub config_dirs: Vec<PathBuf>,
 }
 
+#[derive(clap::Subcommand, Debug, Clone)]
+#[command(rename_all = "kebab-case")]
+enum Command {
+    /// Compare two configurations, showing the components that a reload from the old to the new
+    /// one would add, rebuild or remove, and the buffers it would drop. Exits with a non-zero code
+    /// if the configurations differ.
+    Diff(diff_cmd::Opts),
+}
+
 impl Opts {
     fn paths_with_formats(&self) -> Vec<config::ConfigPath> {
         config::merge_path_lists(vec![
@@ -163,6 +177,10 @@ fn serialize_to_json(
 /// Pipelines expansions, etc. The JSON result of this serialization can itself be used as a config,
 /// which also makes it useful for version control or treating as a singular unit of configuration.
 pub fn cmd(opts: &Opts) -> exitcode::ExitCode {
+    if let Some(Command::Diff(opts)) = &opts.command {
+        return diff_cmd::cmd(opts);
+    }
+
     let paths = opts.paths_with_formats();
     // Start by serializing to a `ConfigBuilder`. This will leverage validation in config
     // builder fields which we'll use to error out if required.
@@ -54,6 +54,10 @@ pub fn compile(mut builder: ConfigBuilder) -> Result<(Config, Vec<String>), Vec<
         errors.extend(output_errors);
     }
 
+    if let Err(reload_errors) = validation::check_reload(&builder) {
+        errors.extend(reload_errors);
+    }
+
     #[cfg(feature = "enterprise")]
     let hash = Some(builder.sha256_hash());
 
@@ -68,6 +72,7 @@ pub fn compile(mut builder: ConfigBuilder) -> Result<(Config, Vec<String>), Vec<
         #[cfg(feature = "enterprise")]
         enterprise,
         healthchecks,
+        reload,
         enrichment_tables,
         sources,
         sinks,
@@ -125,6 +130,7 @@ pub fn compile(mut builder: ConfigBuilder) -> Result<(Config, Vec<String>), Vec<
             enterprise,
             hash,
             healthchecks,
+            reload,
             enrichment_tables,
             sources,
             sinks,
@@ -136,7 +142,19 @@ pub fn compile(mut builder: ConfigBuilder) -> Result<(Config, Vec<String>), Vec<
 
         config.propagate_acknowledgements()?;
 
-        let warnings = validation::warnings(&config);
+        let mut warnings = validation::warnings(&config);
+
+        // Sinks can only be checked against the definitions of their inputs once all transforms
+        // are known to be valid. Failing requirements are only fatal if schema validation is
+        // enabled.
+        if config.schema.validation || config.schema.enabled {
+            if let Err(requirement_errors) = validation::check_sink_requirements(&config) {
+                if config.schema.validation {
+                    return Err(requirement_errors);
+                }
+                warnings.extend(requirement_errors);
+            }
+        }
 
         Ok((config, warnings))
     } else {
@@ -175,6 +193,8 @@ pub(super) fn expand_macros(
 
 /// Expand globs in input lists
 pub(crate) fn expand_globs(config: &mut ConfigBuilder) {
+    // Sink outputs are deliberately not candidates, so that catch-all patterns like `*` don't
+    // wire the events a sink rejected back into it. They have to be referenced by name.
     let candidates = config
         .sources
         .iter()
@@ -309,6 +329,56 @@ mod test {
         );
     }
 
+    #[cfg(all(
+        feature = "sources-demo_logs",
+        feature = "transforms-remap",
+        feature = "sinks-loki"
+    ))]
+    #[test]
+    fn sink_requirements() {
+        use crate::config::{format, Format};
+
+        let compile_with = |schema: &str| {
+            let config = format!(
+                r#"
+                {}
+
+                [sources.in]
+                type = "demo_logs"
+                format = "shuffle"
+                lines = ["line"]
+
+                [transforms.parse]
+                type = "remap"
+                inputs = ["in"]
+                source = '. = {{ "message": .message }}'
+
+                [sinks.out]
+                type = "loki"
+                inputs = ["parse"]
+                endpoint = "http://localhost:3100"
+                encoding.codec = "json"
+                labels.app = "{{{{ app }}}}"
+                labels.host = "{{{{ host | default: unknown }}}}"
+                "#,
+                schema
+            );
+            compile(format::deserialize(&config, Format::Toml).unwrap())
+        };
+        let error =
+            r#"Input "parse" of sink "out" fails its schema requirement: missing field: app"#;
+
+        assert_eq!(
+            compile_with("[schema]\nvalidation = true").unwrap_err(),
+            vec![error.to_owned()]
+        );
+
+        let (_, warnings) = compile_with("[schema]\nenabled = true").unwrap();
+        assert_eq!(warnings, vec![error.to_owned()]);
+
+        assert!(compile_with("").unwrap().1.is_empty());
+    }
+
     fn without_ports(outputs: Inputs<OutputId>) -> Vec<ComponentKey> {
         outputs
             .into_iter()
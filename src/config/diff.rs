@@ -62,7 +62,7 @@ pub struct Difference {
 }
 
 impl Difference {
-    fn new<C>(old: &IndexMap<ComponentKey, C>, new: &IndexMap<ComponentKey, C>) -> Self
+    pub(super) fn new<C>(old: &IndexMap<ComponentKey, C>, new: &IndexMap<ComponentKey, C>) -> Self
     where
         C: serde::Serialize + serde::Deserialize<'static>,
     {
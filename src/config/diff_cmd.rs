@@ -0,0 +1,501 @@
+use std::{
+    collections::BTreeSet,
+    fmt::{self, Display, Formatter},
+    path::{Path, PathBuf},
+};
+
+use clap::Parser;
+use indexmap::IndexMap;
+use serde::Serialize;
+use serde_json::Value;
+use vector_common::sensitive_string::serialize_redacted;
+
+use super::{diff::Difference, load_from_paths, process_paths, ComponentKey, Config, ConfigPath};
+use crate::cli::handle_config_errors;
+
+/// Exit code signaling that the configurations differ, following `diff`.
+const CHANGED: exitcode::ExitCode = 1;
+
+#[derive(Parser, Debug, Clone)]
+#[command(rename_all = "kebab-case")]
+pub(crate) struct Opts {
+    /// The current configuration, as a file or a directory of files.
+    /// File format is detected from the file name.
+    old: PathBuf,
+
+    /// The new configuration, as a file or a directory of files.
+    /// File format is detected from the file name.
+    new: PathBuf,
+}
+
+/// How a component is affected by the change.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Status {
+    Added,
+    Removed,
+    Changed,
+}
+
+/// A change of a single option, identified by its dotted path.
+#[derive(Clone, Debug, PartialEq)]
+enum OptionChange {
+    Added(String, Value),
+    Removed(String, Value),
+    Changed(String, Value, Value),
+}
+
+impl Display for OptionChange {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Added(path, value) => write!(f, "+ {}: {}", path, value),
+            Self::Removed(path, value) => write!(f, "- {}: {}", path, value),
+            Self::Changed(path, old, new) => write!(f, "~ {}: {} -> {}", path, old, new),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct ComponentDiff {
+    kind: &'static str,
+    key: String,
+    status: Status,
+    buffer_dropped: bool,
+    options: Vec<OptionChange>,
+}
+
+impl Display for ComponentDiff {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let (sign, status) = match self.status {
+            Status::Added => ("+", "added"),
+            Status::Removed => ("-", "removed"),
+            Status::Changed => ("~", "changed, will be rebuilt"),
+        };
+        write!(f, "{} {}.{} ({}", sign, self.kind, self.key, status)?;
+        if self.buffer_dropped {
+            write!(f, ", buffer will be dropped")?;
+        }
+        writeln!(f, ")")?;
+
+        for option in &self.options {
+            writeln!(f, "    {}", option)?;
+        }
+        Ok(())
+    }
+}
+
+/// The difference between two configurations, as applied by a reload.
+#[derive(Clone, Debug, Default, PartialEq)]
+struct Report {
+    global: Vec<OptionChange>,
+    components: Vec<ComponentDiff>,
+}
+
+impl Report {
+    fn new(old: &Config, new: &Config) -> Self {
+        let mut components = Vec::new();
+
+        components_diff(
+            &mut components,
+            "sources",
+            &old.sources,
+            &new.sources,
+            |_| false,
+        );
+        components_diff(
+            &mut components,
+            "transforms",
+            &old.transforms,
+            &new.transforms,
+            |_| false,
+        );
+        // Sinks whose buffer configuration didn't change keep their buffer on reload.
+        components_diff(
+            &mut components,
+            "sinks",
+            &old.sinks,
+            &new.sinks,
+            |key| match (old.sink(key), new.sink(key)) {
+                (Some(old), Some(new)) => old.buffer != new.buffer,
+                (Some(_), None) => true,
+                _ => false,
+            },
+        );
+        components_diff(
+            &mut components,
+            "enrichment_tables",
+            &old.enrichment_tables,
+            &new.enrichment_tables,
+            |_| false,
+        );
+
+        Self {
+            global: options_diff(&Options::new(&old.global), &Options::new(&new.global)),
+            components,
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.global.is_empty() && self.components.is_empty()
+    }
+}
+
+impl Display for Report {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return writeln!(f, "No changes.");
+        }
+
+        if !self.global.is_empty() {
+            writeln!(
+                f,
+                "~ global options (changed, reloading is not supported and requires a restart)"
+            )?;
+            for option in &self.global {
+                writeln!(f, "    {}", option)?;
+            }
+        }
+
+        for component in &self.components {
+            write!(f, "{}", component)?;
+        }
+
+        let count = |status| {
+            self.components
+                .iter()
+                .filter(|component| component.status == status)
+                .count()
+        };
+        writeln!(
+            f,
+            "\n{} added, {} changed, {} removed.",
+            count(Status::Added),
+            count(Status::Changed),
+            count(Status::Removed)
+        )
+    }
+}
+
+fn to_value<T: Serialize>(value: &T) -> Value {
+    serde_json::to_value(value).expect("should serialize config to JSON. Please report.")
+}
+
+/// The serialized options of a configuration. Changes are detected on their plain values, but
+/// printed from their values with sensitive strings redacted.
+#[derive(Clone, Debug, Default)]
+struct Options {
+    value: Value,
+    redacted: Value,
+}
+
+impl Options {
+    fn new<T: Serialize>(config: &T) -> Self {
+        Self {
+            value: to_value(config),
+            redacted: serialize_redacted(|| to_value(config)),
+        }
+    }
+}
+
+fn components_diff<C: Serialize + serde::Deserialize<'static>>(
+    components: &mut Vec<ComponentDiff>,
+    kind: &'static str,
+    old: &IndexMap<ComponentKey, C>,
+    new: &IndexMap<ComponentKey, C>,
+    buffer_dropped: impl Fn(&ComponentKey) -> bool,
+) {
+    let difference = Difference::new(old, new);
+
+    let keys = difference
+        .to_add
+        .iter()
+        .chain(difference.to_change.iter())
+        .chain(difference.to_remove.iter())
+        .collect::<BTreeSet<_>>();
+
+    for key in keys {
+        let (status, options) = if difference.to_add.contains(key) {
+            (
+                Status::Added,
+                options_diff(&Options::default(), &Options::new(&new[key])),
+            )
+        } else if difference.to_remove.contains(key) {
+            (
+                Status::Removed,
+                options_diff(&Options::new(&old[key]), &Options::default()),
+            )
+        } else {
+            (
+                Status::Changed,
+                options_diff(&Options::new(&old[key]), &Options::new(&new[key])),
+            )
+        };
+
+        components.push(ComponentDiff {
+            kind,
+            key: key.to_string(),
+            status,
+            buffer_dropped: status != Status::Added && buffer_dropped(key),
+            options,
+        });
+    }
+}
+
+/// Lists the changed options between two serialized configurations, descending into objects.
+fn options_diff(old: &Options, new: &Options) -> Vec<OptionChange> {
+    let mut changes = Vec::new();
+    collect_options_diff(
+        &mut changes,
+        None,
+        (&old.value, &old.redacted),
+        (&new.value, &new.redacted),
+    );
+    changes
+}
+
+/// Walks the plain and redacted values of both configurations side by side, as
+/// `(value, redacted)` pairs.
+fn collect_options_diff(
+    changes: &mut Vec<OptionChange>,
+    path: Option<&str>,
+    old: (&Value, &Value),
+    new: (&Value, &Value),
+) {
+    let empty = Value::Object(Default::default());
+
+    match (old.0, new.0) {
+        (Value::Object(old_value), Value::Object(new_value)) => {
+            for key in old_value
+                .keys()
+                .chain(new_value.keys())
+                .collect::<BTreeSet<_>>()
+            {
+                let path = match path {
+                    Some(path) => format!("{}.{}", path, key),
+                    None => key.clone(),
+                };
+                collect_options_diff(
+                    changes,
+                    Some(&path),
+                    (field(old.0, key), field(old.1, key)),
+                    (field(new.0, key), field(new.1, key)),
+                );
+            }
+        }
+        (Value::Object(_), Value::Null) => {
+            collect_options_diff(changes, path, old, (&empty, &empty))
+        }
+        (Value::Null, Value::Object(_)) => {
+            collect_options_diff(changes, path, (&empty, &empty), new)
+        }
+        (old_value, new_value) if old_value == new_value => {}
+        (Value::Null, _) => changes.push(OptionChange::Added(
+            path.unwrap_or_default().to_owned(),
+            new.1.clone(),
+        )),
+        (_, Value::Null) => changes.push(OptionChange::Removed(
+            path.unwrap_or_default().to_owned(),
+            old.1.clone(),
+        )),
+        _ => changes.push(OptionChange::Changed(
+            path.unwrap_or_default().to_owned(),
+            old.1.clone(),
+            new.1.clone(),
+        )),
+    }
+}
+
+fn field<'a>(value: &'a Value, key: &str) -> &'a Value {
+    static NULL: Value = Value::Null;
+    value.get(key).unwrap_or(&NULL)
+}
+
+fn load(path: &Path) -> Result<Config, exitcode::ExitCode> {
+    let path = if path.is_dir() {
+        ConfigPath::Dir(path.to_path_buf())
+    } else {
+        ConfigPath::File(path.to_path_buf(), None)
+    };
+
+    let paths = process_paths(&[path]).ok_or(exitcode::CONFIG)?;
+    load_from_paths(&paths).map_err(handle_config_errors)
+}
+
+/// Function used by the `vector config diff` subcommand for showing how a configuration change
+/// would be applied by a reload. Exits with a non-zero code if the configurations differ, so it
+/// can be used as a gate in CI.
+pub(crate) fn cmd(opts: &Opts) -> exitcode::ExitCode {
+    let old = match load(&opts.old) {
+        Ok(config) => config,
+        Err(code) => return code,
+    };
+    let new = match load(&opts.new) {
+        Ok(config) => config,
+        Err(code) => return code,
+    };
+
+    let report = Report::new(&old, &new);
+
+    #[allow(clippy::print_stdout)]
+    {
+        print!("{}", report);
+    }
+
+    if report.is_empty() {
+        exitcode::OK
+    } else {
+        CHANGED
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn diffs_nested_options() {
+        let old = json!({
+            "type": "http",
+            "inputs": ["in"],
+            "encoding": { "codec": "json", "except_fields": null },
+            "batch": { "max_events": 100 },
+        });
+        let new = json!({
+            "type": "http",
+            "inputs": ["in", "other"],
+            "encoding": { "codec": "text" },
+            "buffer": { "max_events": 500 },
+        });
+
+        let (old, new) = (Options::new(&old), Options::new(&new));
+
+        assert_eq!(
+            options_diff(&old, &new),
+            vec![
+                OptionChange::Removed("batch.max_events".to_owned(), json!(100)),
+                OptionChange::Added("buffer.max_events".to_owned(), json!(500)),
+                OptionChange::Changed("encoding.codec".to_owned(), json!("json"), json!("text")),
+                OptionChange::Changed("inputs".to_owned(), json!(["in"]), json!(["in", "other"])),
+            ]
+        );
+        assert!(options_diff(&old, &old).is_empty());
+    }
+
+    #[test]
+    fn redacts_sensitive_options() {
+        use vector_common::sensitive_string::SensitiveString;
+
+        #[derive(Serialize)]
+        struct Auth {
+            user: String,
+            password: SensitiveString,
+        }
+
+        let old = Options::new(&Auth {
+            user: "vector".to_owned(),
+            password: SensitiveString::from("old secret".to_owned()),
+        });
+        let new = Options::new(&Auth {
+            user: "vector".to_owned(),
+            password: SensitiveString::from("new secret".to_owned()),
+        });
+
+        let changes = options_diff(&old, &new);
+        assert_eq!(
+            changes,
+            vec![OptionChange::Changed(
+                "password".to_owned(),
+                json!("**REDACTED**"),
+                json!("**REDACTED**")
+            )]
+        );
+        assert!(!changes[0].to_string().contains("secret"));
+        assert!(options_diff(&old, &old).is_empty());
+        assert_eq!(
+            options_diff(&Options::default(), &new),
+            vec![
+                OptionChange::Added("password".to_owned(), json!("**REDACTED**")),
+                OptionChange::Added("user".to_owned(), json!("vector")),
+            ]
+        );
+    }
+
+    #[cfg(all(feature = "sources-demo_logs", feature = "sinks-blackhole"))]
+    #[test]
+    fn reports_rebuilt_components_and_dropped_buffers() {
+        use crate::config::{load_from_str, Format};
+
+        let old = load_from_str(
+            r#"
+            [sources.in]
+            type = "demo_logs"
+            format = "shuffle"
+            lines = ["line"]
+
+            [sinks.kept]
+            type = "blackhole"
+            inputs = ["in"]
+
+            [sinks.rebuffered]
+            type = "blackhole"
+            inputs = ["in"]
+
+            [sinks.removed]
+            type = "blackhole"
+            inputs = ["in"]
+            "#,
+            Format::Toml,
+        )
+        .unwrap();
+        let new = load_from_str(
+            r#"
+            [sources.in]
+            type = "demo_logs"
+            format = "shuffle"
+            lines = ["line"]
+
+            [sinks.kept]
+            type = "blackhole"
+            inputs = ["in"]
+            print_interval_secs = 10
+
+            [sinks.rebuffered]
+            type = "blackhole"
+            inputs = ["in"]
+            buffer.max_events = 1000
+
+            [sinks.added]
+            type = "blackhole"
+            inputs = ["in"]
+            "#,
+            Format::Toml,
+        )
+        .unwrap();
+
+        let report = Report::new(&old, &new);
+        let summary = report
+            .components
+            .iter()
+            .map(|component| {
+                (
+                    component.key.as_str(),
+                    component.status,
+                    component.buffer_dropped,
+                )
+            })
+            .collect::<Vec<_>>();
+
+        assert!(report.global.is_empty());
+        assert_eq!(
+            summary,
+            vec![
+                ("added", Status::Added, false),
+                ("kept", Status::Changed, false),
+                ("rebuffered", Status::Changed, true),
+                ("removed", Status::Removed, true),
+            ]
+        );
+        assert!(Report::new(&old, &old).is_empty());
+    }
+}
@@ -17,6 +17,7 @@ pub enum Node {
     },
     Sink {
         ty: DataType,
+        outputs: Vec<Output>,
     },
 }
 
@@ -90,6 +91,7 @@ impl Graph {
                 id.clone(),
                 Node::Sink {
                     ty: config.inner.input().data_type(),
+                    outputs: config.inner.outputs(),
                 },
             );
         }
@@ -162,7 +164,7 @@ impl Graph {
         match self.nodes[key] {
             Node::Source { .. } => panic!("no inputs on sources"),
             Node::Transform { in_ty, .. } => in_ty,
-            Node::Sink { ty } => ty,
+            Node::Sink { ty, .. } => ty,
         }
     }
 
@@ -170,16 +172,16 @@ impl Graph {
     ///
     /// # Panics
     ///
-    /// Will panic if the given id is not present in the graph or identifies a sink, which can't
-    /// have inputs.
+    /// Will panic if the given id is not present in the graph.
     fn get_output_type(&self, id: &OutputId) -> DataType {
         match &self.nodes[&id.component] {
-            Node::Source { outputs } | Node::Transform { outputs, .. } => outputs
+            Node::Source { outputs }
+            | Node::Transform { outputs, .. }
+            | Node::Sink { outputs, .. } => outputs
                 .iter()
                 .find(|output| output.port == id.port)
                 .map(|output| output.ty)
                 .expect("output didn't exist"),
-            Node::Sink { .. } => panic!("no outputs on sinks"),
         }
     }
 
@@ -264,14 +266,12 @@ impl Graph {
         self.nodes
             .iter()
             .flat_map(|(key, node)| match node {
-                Node::Sink { .. } => vec![],
-                Node::Source { outputs } | Node::Transform { outputs, .. } => outputs
-                    .iter()
-                    .map(|output| OutputId {
-                        component: key.clone(),
-                        port: output.port.clone(),
-                    })
-                    .collect(),
+                Node::Source { outputs }
+                | Node::Transform { outputs, .. }
+                | Node::Sink { outputs, .. } => outputs.iter().map(move |output| OutputId {
+                    component: key.clone(),
+                    port: output.port.clone(),
+                }),
             })
             .collect()
     }
@@ -349,7 +349,7 @@ impl Graph {
             .into_iter()
             .filter(|path| {
                 if let Some(key) = path.last() {
-                    matches!(self.nodes.get(key), Some(Node::Sink { ty: _ }))
+                    matches!(self.nodes.get(key), Some(Node::Sink { .. }))
                 } else {
                     false
                 }
@@ -411,7 +411,13 @@ mod test {
         fn add_sink(&mut self, id: &str, ty: DataType, inputs: Vec<&str>) {
             let id = ComponentKey::from(id);
             let inputs = clean_inputs(inputs);
-            self.nodes.insert(id.clone(), Node::Sink { ty });
+            self.nodes.insert(
+                id.clone(),
+                Node::Sink {
+                    ty,
+                    outputs: vec![],
+                },
+            );
             for from in inputs {
                 self.edges.push(Edge {
                     from,
@@ -420,6 +426,16 @@ mod test {
             }
         }
 
+        fn add_sink_output(&mut self, id: &str, name: &str, ty: DataType) {
+            let id = id.into();
+            match self.nodes.get_mut(&id) {
+                Some(Node::Sink { outputs, .. }) => {
+                    outputs.push(Output::default(ty).with_port(name))
+                }
+                _ => panic!("invalid sink"),
+            }
+        }
+
         fn test_add_input(&mut self, node: &str, input: &str) -> Result<(), String> {
             let available_inputs = self.input_map().unwrap();
             let expansions = IndexMap::new();
@@ -615,6 +631,39 @@ mod test {
         );
     }
 
+    #[test]
+    fn allows_sink_outputs() {
+        let mut graph = Graph::default();
+        graph.add_source("in", DataType::Log);
+        graph.add_sink("es", DataType::Log, vec!["in"]);
+        graph.add_sink_output("es", "dropped", DataType::Log);
+        graph.add_sink("quarantine", DataType::Log, vec![]);
+        graph.add_sink("bad", DataType::Log, vec![]);
+
+        assert_eq!(Ok(()), graph.test_add_input("quarantine", "es.dropped"));
+        assert_eq!(Ok(()), graph.typecheck());
+        graph.check_for_cycles().unwrap();
+
+        let expected =
+            "Input \"es.not_dropped\" for sink \"bad\" doesn't match any components.".to_string();
+        assert_eq!(Err(expected), graph.test_add_input("bad", "es.not_dropped"));
+    }
+
+    #[test]
+    fn detects_cycles_through_sink_outputs() {
+        let mut graph = Graph::default();
+        graph.add_source("in", DataType::Log);
+        graph.add_transform("fix", DataType::Log, DataType::Log, vec!["in"]);
+        graph.add_sink("es", DataType::Log, vec!["fix"]);
+        graph.add_sink_output("es", "dropped", DataType::Log);
+        graph.test_add_input("fix", "es.dropped").unwrap();
+
+        assert_eq!(
+            Err("Cyclic dependency detected in the chain [ es -> fix ]".into()),
+            graph.check_for_cycles()
+        );
+    }
+
     #[test]
     fn disallows_ambiguous_inputs() {
         let mut graph = Graph::default();
@@ -20,6 +20,7 @@ mod builder;
 mod cmd;
 mod compiler;
 mod diff;
+mod diff_cmd;
 mod enrichment_table;
 #[cfg(feature = "enterprise")]
 pub mod enterprise;
@@ -107,6 +108,7 @@ pub struct Config {
     pub enterprise: Option<enterprise::Options>,
     pub global: GlobalOptions,
     pub healthchecks: HealthcheckOptions,
+    pub reload: ReloadOptions,
     sources: IndexMap<ComponentKey, SourceOuter>,
     sinks: IndexMap<ComponentKey, SinkOuter<OutputId>>,
     transforms: IndexMap<ComponentKey, TransformOuter<OutputId>>,
@@ -247,6 +249,77 @@ impl Default for HealthcheckOptions {
     }
 }
 
+/// Options for reloading the configuration.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct ReloadOptions {
+    #[configurable(derived)]
+    #[serde(skip_serializing_if = "crate::serde::skip_serializing_if_default")]
+    pub probation: Option<ProbationOptions>,
+}
+
+impl ReloadOptions {
+    fn merge(&mut self, other: Self) -> Result<(), String> {
+        match (self.probation, other.probation) {
+            (Some(this), Some(that)) if this != that => {
+                Err("conflicting values for 'reload.probation' found".to_owned())
+            }
+            (None, probation) => {
+                self.probation = probation;
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Probation of reloaded configurations.
+///
+/// After a reload, the pipeline is monitored for a window in which the error rates of components
+/// and the throughput of each sink kept from the previous configuration are compared against those
+/// of the previous configuration, which is restored if the pipeline regressed. The healthchecks of
+/// the reloaded sinks are also required to pass.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ProbationOptions {
+    /// How long, in seconds, to monitor the pipeline after a reload. Must be greater than zero.
+    #[serde(default = "default_probation_window_secs")]
+    pub window_secs: u64,
+
+    /// The increase, in errors per second, of the error rate of any component that is considered
+    /// a regression.
+    #[serde(default = "default_max_error_rate_increase")]
+    pub max_error_rate_increase: f64,
+
+    /// The ratio of the previous throughput of a sink, in events sent per second, below which its
+    /// throughput is considered a regression. Must be between 0 and 1.
+    #[serde(default = "default_min_throughput_ratio")]
+    pub min_throughput_ratio: f64,
+
+    /// The number of events a sink must have sent under the previous configuration for its
+    /// throughput to be compared.
+    #[serde(default = "default_min_baseline_events")]
+    pub min_baseline_events: u64,
+}
+
+const fn default_probation_window_secs() -> u64 {
+    60
+}
+
+const fn default_max_error_rate_increase() -> f64 {
+    1.0
+}
+
+const fn default_min_throughput_ratio() -> f64 {
+    0.5
+}
+
+const fn default_min_baseline_events() -> u64 {
+    100
+}
+
 #[macro_export]
 macro_rules! impl_generate_config_from_default {
     ($type:ty) => {
@@ -631,6 +704,35 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn bad_probation() {
+        let err = load(
+            r#"
+            [reload.probation]
+            window_secs = 0
+            min_throughput_ratio = 1.5
+
+            [sources.in]
+            type = "test_basic"
+
+            [sinks.out]
+            type = "test_basic"
+            inputs = ["in"]
+            "#,
+            Format::Toml,
+        )
+        .await
+        .unwrap_err();
+
+        assert_eq!(
+            vec![
+                "Option \"reload.probation.window_secs\" must be greater than 0.",
+                "Option \"reload.probation.min_throughput_ratio\" must be between 0 and 1.",
+            ],
+            err,
+        );
+    }
+
     #[tokio::test]
     async fn duplicate_name() {
         let err = load(
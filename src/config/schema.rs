@@ -13,6 +13,9 @@ pub struct Options {
     pub enabled: bool,
 
     /// Whether or not schema validation is enabled.
+    ///
+    /// When enabled, configurations with sinks whose schema requirements aren't met by their
+    /// inputs are rejected. Otherwise, these are only reported as warnings if schema is enabled.
     #[serde(default = "default_validation")]
     pub validation: bool,
 
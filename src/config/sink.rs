@@ -4,12 +4,15 @@ use serde::Serialize;
 use vector_buffers::{BufferConfig, BufferType};
 use vector_config::{configurable_component, Configurable, NamedComponent};
 use vector_core::{
-    config::{AcknowledgementsConfig, GlobalOptions, Input},
+    config::{AcknowledgementsConfig, GlobalOptions, Input, Output},
     sink::VectorSink,
 };
 
 use super::{id::Inputs, schema, ComponentKey, ProxyConfig, Resource};
-use crate::sinks::{util::UriSerde, Healthcheck, Sinks};
+use crate::sinks::{
+    util::{dropped::DroppedSender, UriSerde},
+    Healthcheck, Sinks,
+};
 
 /// Fully resolved sink component.
 #[configurable_component]
@@ -190,6 +193,14 @@ pub trait SinkConfig: NamedComponent + core::fmt::Debug + Send + Sync {
     /// Gets the input configuration for this sink.
     fn input(&self) -> Input;
 
+    /// Gets the list of outputs exposed by this sink.
+    ///
+    /// Most sinks have no outputs. Sinks that can reroute the events they permanently reject
+    /// expose a `dropped` output, which is wired into the topology like a transform output.
+    fn outputs(&self) -> Vec<Output> {
+        Vec::new()
+    }
+
     /// Gets the list of resources, if any, used by this sink.
     ///
     /// Resources represent dependencies -- network ports, file descriptors, and so on -- that
@@ -211,6 +222,7 @@ pub struct SinkContext {
     pub globals: GlobalOptions,
     pub proxy: ProxyConfig,
     pub schema: schema::Options,
+    pub dropped: Option<DroppedSender>,
 }
 
 impl SinkContext {
@@ -221,6 +233,7 @@ impl SinkContext {
             globals: GlobalOptions::default(),
             proxy: ProxyConfig::default(),
             schema: schema::Options::default(),
+            dropped: None,
         }
     }
 
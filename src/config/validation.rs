@@ -1,5 +1,5 @@
 use crate::config::schema;
-use crate::topology::schema::merged_definition;
+use crate::topology::schema::{merged_definition, validate_sink_expectations};
 use futures_util::{stream, FutureExt, StreamExt, TryFutureExt, TryStreamExt};
 use heim::{disk::Partition, units::information::byte};
 use indexmap::IndexMap;
@@ -147,6 +147,30 @@ pub fn check_resources(config: &ConfigBuilder) -> Result<(), Vec<String>> {
     }
 }
 
+/// Check that the probation of reloaded configurations is configured with sensible values.
+pub fn check_reload(config: &ConfigBuilder) -> Result<(), Vec<String>> {
+    let mut errors = vec![];
+
+    if let Some(probation) = config.reload.probation {
+        if probation.window_secs == 0 {
+            errors
+                .push("Option \"reload.probation.window_secs\" must be greater than 0.".to_owned());
+        }
+        if !(0.0..=1.0).contains(&probation.min_throughput_ratio) {
+            errors.push(
+                "Option \"reload.probation.min_throughput_ratio\" must be between 0 and 1."
+                    .to_owned(),
+            );
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
 /// To avoid collisions between `output` metric tags, check that a component
 /// does not have a named output with the name [`DEFAULT_OUTPUT`]
 pub fn check_outputs(config: &ConfigBuilder) -> Result<(), Vec<String>> {
@@ -185,6 +209,20 @@ pub fn check_outputs(config: &ConfigBuilder) -> Result<(), Vec<String>> {
         }
     }
 
+    for (key, sink) in config.sinks.iter() {
+        if sink
+            .inner
+            .outputs()
+            .iter()
+            .map(|output| output.port.as_deref().unwrap_or(""))
+            .any(|name| name == DEFAULT_OUTPUT)
+        {
+            errors.push(format!(
+                "Sink {key} cannot have a named output with reserved name: `{DEFAULT_OUTPUT}`"
+            ));
+        }
+    }
+
     if errors.is_empty() {
         Ok(())
     } else {
@@ -323,6 +361,22 @@ async fn process_partitions(partitions: Vec<Partition>) -> heim::Result<IndexMap
         .await
 }
 
+/// Check the schema requirements of the sinks against the definitions of the events they
+/// receive.
+pub fn check_sink_requirements(config: &Config) -> Result<(), Vec<String>> {
+    let errors = config
+        .sinks()
+        .filter_map(|(key, sink)| validate_sink_expectations(key, sink, config).err())
+        .flatten()
+        .collect::<Vec<_>>();
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
 pub fn warnings(config: &Config) -> Vec<String> {
     let mut warnings = vec![];
     let mut cache = HashMap::new();
@@ -356,7 +410,21 @@ pub fn warnings(config: &Config) -> Vec<String> {
             .collect::<Vec<_>>()
     });
 
-    for (input_type, id) in transform_ids.chain(source_ids) {
+    let sink_ids = config.sinks.iter().flat_map(|(key, sink)| {
+        sink.inner
+            .outputs()
+            .iter()
+            .map(|output| {
+                if let Some(port) = &output.port {
+                    ("sink", OutputId::from((key, port.clone())))
+                } else {
+                    ("sink", OutputId::from(key))
+                }
+            })
+            .collect::<Vec<_>>()
+    });
+
+    for (input_type, id) in transform_ids.chain(source_ids).chain(sink_ids) {
         if !config
             .transforms
             .iter()
@@ -122,6 +122,97 @@ mod s3 {
             counter!("sqs_message_delete_batch_failed_total", 1);
         }
     }
+
+    #[derive(Debug)]
+    pub struct S3ListObjectsError<'a, E> {
+        pub bucket: &'a str,
+        pub error: &'a E,
+    }
+
+    impl<'a, E: std::fmt::Display> InternalEvent for S3ListObjectsError<'a, E> {
+        fn emit(self) {
+            error!(
+                message = "Failed to list S3 objects.",
+                bucket = %self.bucket,
+                error = %self.error,
+                error_code = "failed_listing_s3_objects",
+                error_type = error_type::REQUEST_FAILED,
+                stage = error_stage::RECEIVING,
+                internal_log_rate_limit = true,
+            );
+            counter!(
+                "component_errors_total", 1,
+                "error_code" => "failed_listing_s3_objects",
+                "error_type" => error_type::REQUEST_FAILED,
+                "stage" => error_stage::RECEIVING,
+            );
+        }
+    }
+
+    #[derive(Debug)]
+    pub struct S3ObjectProcessingError<'a> {
+        pub error: &'a ProcessingError,
+    }
+
+    impl<'a> S3ObjectProcessingError<'a> {
+        const fn error_type(&self) -> &'static str {
+            match self.error {
+                ProcessingError::GetObject { .. } => error_type::REQUEST_FAILED,
+                ProcessingError::ReadObject { .. } => error_type::READER_FAILED,
+                ProcessingError::PipelineSend { .. } => error_type::WRITER_FAILED,
+                ProcessingError::ErrorAcknowledgement => error_type::ACKNOWLEDGMENT_FAILED,
+                ProcessingError::WrongRegion { .. } => error_type::CONDITION_FAILED,
+                ProcessingError::InvalidSqsMessage { .. }
+                | ProcessingError::UnsupportedS3EventVersion { .. } => error_type::PARSER_FAILED,
+            }
+        }
+    }
+
+    impl<'a> InternalEvent for S3ObjectProcessingError<'a> {
+        fn emit(self) {
+            let error_type = self.error_type();
+            error!(
+                message = "Failed to process S3 object.",
+                error = %self.error,
+                error_code = "failed_processing_s3_object",
+                error_type,
+                stage = error_stage::PROCESSING,
+                internal_log_rate_limit = true,
+            );
+            counter!(
+                "component_errors_total", 1,
+                "error_code" => "failed_processing_s3_object",
+                "error_type" => error_type,
+                "stage" => error_stage::PROCESSING,
+            );
+        }
+    }
+
+    #[derive(Debug)]
+    pub struct S3CheckpointWriteError<'a> {
+        pub error: std::io::Error,
+        pub filename: &'a std::path::Path,
+    }
+
+    impl<'a> InternalEvent for S3CheckpointWriteError<'a> {
+        fn emit(self) {
+            error!(
+                message = "Failed writing checkpoint.",
+                filename = %self.filename.display(),
+                error = %self.error,
+                error_code = "writing_checkpoint",
+                error_type = error_type::WRITER_FAILED,
+                stage = error_stage::RECEIVING,
+                internal_log_rate_limit = true,
+            );
+            counter!(
+                "component_errors_total", 1,
+                "error_code" => "writing_checkpoint",
+                "error_type" => error_type::WRITER_FAILED,
+                "stage" => error_stage::RECEIVING,
+            );
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -0,0 +1,37 @@
+use std::fmt::Display;
+
+use metrics::counter;
+use vector_common::internal_event::{
+    error_stage, error_type, ComponentEventsDropped, UNINTENTIONAL,
+};
+use vector_core::internal_event::InternalEvent;
+
+use crate::emit;
+
+#[derive(Debug)]
+pub struct ClickhouseCoercionError<'a, T, E> {
+    pub column: &'a str,
+    pub column_type: &'a T,
+    pub error: E,
+}
+
+impl<'a, T: Display, E: Display> InternalEvent for ClickhouseCoercionError<'a, T, E> {
+    fn emit(self) {
+        let reason = "Event field could not be coerced to the type of its column.";
+        error!(
+            message = reason,
+            column = %self.column,
+            column_type = %self.column_type,
+            error = %self.error,
+            error_type = error_type::CONVERSION_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::CONVERSION_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+        emit!(ComponentEventsDropped::<UNINTENTIONAL> { count: 1, reason });
+    }
+}
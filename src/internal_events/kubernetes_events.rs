@@ -0,0 +1,49 @@
+use metrics::counter;
+use vector_common::internal_event::{error_stage, error_type};
+use vector_core::internal_event::InternalEvent;
+
+#[derive(Debug)]
+pub struct KubernetesEventsWatchError<E> {
+    pub error: E,
+}
+
+impl<E: std::fmt::Display> InternalEvent for KubernetesEventsWatchError<E> {
+    fn emit(self) {
+        error!(
+            message = "Failed watching Kubernetes events. Retrying.",
+            error = %self.error,
+            error_type = error_type::REQUEST_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::REQUEST_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct KubernetesEventsCheckpointError<E> {
+    pub error: E,
+    pub path: std::path::PathBuf,
+}
+
+impl<E: std::fmt::Display> InternalEvent for KubernetesEventsCheckpointError<E> {
+    fn emit(self) {
+        error!(
+            message = "Failed writing checkpoint.",
+            error = %self.error,
+            path = ?self.path,
+            error_type = error_type::WRITER_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::WRITER_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
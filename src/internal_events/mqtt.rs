@@ -0,0 +1,120 @@
+use crate::emit;
+use metrics::counter;
+use rumqttc::{ClientError, ConnectionError};
+use vector_common::internal_event::{
+    error_stage, error_type, ComponentEventsDropped, UNINTENTIONAL,
+};
+use vector_core::internal_event::InternalEvent;
+
+#[derive(Debug)]
+pub struct MqttConnectionError {
+    pub error: ConnectionError,
+    pub stage: &'static str,
+}
+
+impl InternalEvent for MqttConnectionError {
+    fn emit(self) {
+        error!(
+            message = "MQTT connection error, reconnecting.",
+            error = %self.error,
+            error_type = error_type::CONNECTION_FAILED,
+            stage = self.stage,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::CONNECTION_FAILED,
+            "stage" => self.stage,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct MqttAckError {
+    pub error: ClientError,
+}
+
+impl InternalEvent for MqttAckError {
+    fn emit(self) {
+        error!(
+            message = "Unable to ack.",
+            error = %self.error,
+            error_type = error_type::ACKNOWLEDGMENT_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::ACKNOWLEDGMENT_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct MqttDeliveryErrored<'a> {
+    pub topic: &'a str,
+}
+
+impl<'a> InternalEvent for MqttDeliveryErrored<'a> {
+    fn emit(self) {
+        error!(
+            message = "Events of delivery failed to be delivered, acknowledging it anyway.",
+            topic = %self.topic,
+            error_type = error_type::ACKNOWLEDGMENT_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::ACKNOWLEDGMENT_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct MqttSubscribeError {
+    pub error: ClientError,
+}
+
+impl InternalEvent for MqttSubscribeError {
+    fn emit(self) {
+        error!(
+            message = "Unable to subscribe.",
+            error = %self.error,
+            error_type = error_type::COMMAND_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::COMMAND_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct MqttPublishError {
+    pub error: ClientError,
+}
+
+impl InternalEvent for MqttPublishError {
+    fn emit(self) {
+        let reason = "Failed to publish message.";
+        error!(
+            message = reason,
+            error = %self.error,
+            error_type = error_type::WRITER_FAILED,
+            stage = error_stage::SENDING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::WRITER_FAILED,
+            "stage" => error_stage::SENDING,
+        );
+        emit!(ComponentEventsDropped::<UNINTENTIONAL> { count: 1, reason });
+    }
+}
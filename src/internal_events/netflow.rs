@@ -0,0 +1,51 @@
+use crate::emit;
+use metrics::counter;
+use vector_common::internal_event::{
+    error_stage, error_type, ComponentEventsDropped, UNINTENTIONAL,
+};
+use vector_core::internal_event::InternalEvent;
+
+#[derive(Debug)]
+pub struct NetflowParseError<E> {
+    pub error: E,
+    pub peer_addr: std::net::SocketAddr,
+}
+
+impl<E: std::fmt::Display> InternalEvent for NetflowParseError<E> {
+    fn emit(self) {
+        let reason = "Failed to parse flow export packet.";
+        error!(
+            message = reason,
+            error = %self.error,
+            peer_addr = %self.peer_addr,
+            error_type = error_type::PARSER_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::PARSER_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+        emit!(ComponentEventsDropped::<UNINTENTIONAL> { count: 1, reason });
+    }
+}
+
+#[derive(Debug)]
+pub struct NetflowTemplateMissing {
+    pub template_id: u16,
+    pub peer_addr: std::net::SocketAddr,
+}
+
+impl InternalEvent for NetflowTemplateMissing {
+    fn emit(self) {
+        // Exporters send their templates periodically, so the records of the first packets they
+        // send after Vector starts can't be decoded yet.
+        warn!(
+            message = "Discarding data records whose template has not been received yet.",
+            template_id = self.template_id,
+            peer_addr = %self.peer_addr,
+            internal_log_rate_limit = true,
+        );
+    }
+}
@@ -96,6 +96,22 @@ impl InternalEvent for VectorReloadError {
     }
 }
 
+#[derive(Debug)]
+pub struct VectorReloadRolledBack<'a> {
+    pub reason: &'a str,
+}
+
+impl InternalEvent for VectorReloadRolledBack<'_> {
+    fn emit(self) {
+        warn!(
+            target: "vector",
+            message = "Reloaded configuration failed probation; rolling back to the previous configuration.",
+            reason = %self.reason,
+        );
+        counter!("reload_rollbacks_total", 1);
+    }
+}
+
 #[derive(Debug)]
 pub struct VectorConfigLoadError;
 
@@ -0,0 +1,32 @@
+use crate::emit;
+use metrics::counter;
+use vector_common::internal_event::{
+    error_stage, error_type, ComponentEventsDropped, UNINTENTIONAL,
+};
+use vector_core::internal_event::InternalEvent;
+
+#[derive(Debug)]
+pub struct SnmpTrapParseError<E> {
+    pub error: E,
+    pub peer_addr: std::net::SocketAddr,
+}
+
+impl<E: std::fmt::Display> InternalEvent for SnmpTrapParseError<E> {
+    fn emit(self) {
+        let reason = "Failed to parse SNMP trap.";
+        error!(
+            message = reason,
+            error = %self.error,
+            peer_addr = %self.peer_addr,
+            error_type = error_type::PARSER_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::PARSER_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+        emit!(ComponentEventsDropped::<UNINTENTIONAL> { count: 1, reason });
+    }
+}
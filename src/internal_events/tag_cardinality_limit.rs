@@ -54,3 +54,11 @@ impl<'a> InternalEvent for TagCardinalityValueLimitReached<'a> {
         counter!("value_limit_reached_total", 1);
     }
 }
+
+pub struct TagCardinalityLimitReset;
+
+impl InternalEvent for TagCardinalityLimitReset {
+    fn emit(self) {
+        debug!(message = "Reset interval elapsed; forgetting accepted tag values.");
+    }
+}
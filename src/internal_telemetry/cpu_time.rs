@@ -0,0 +1,98 @@
+//! CPU time accounting exposed via internal telemetry.
+//!
+//! The time spent polling the tasks of a component is the time the component keeps a worker
+//! thread busy, which is recorded in the `component_cpu_time_nanoseconds_total` counter, tagged
+//! with the component of the span the task runs in.
+
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+    time::Instant,
+};
+
+use metrics::{register_counter, Counter};
+use pin_project::pin_project;
+
+const CPU_TIME_NAME: &str = "component_cpu_time_nanoseconds_total";
+
+/// A future that records the time spent polling the inner future.
+#[pin_project]
+pub struct CpuTime<F> {
+    #[pin]
+    inner: F,
+    cpu_time: Option<Counter>,
+}
+
+impl<F: Future> Future for CpuTime<F> {
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        // The counter is registered on the first poll, so that it is tagged with the component of
+        // the span the future is instrumented with.
+        let cpu_time = this
+            .cpu_time
+            .get_or_insert_with(|| register_counter!(CPU_TIME_NAME));
+
+        let start = Instant::now();
+        let result = this.inner.poll(cx);
+        cpu_time.increment(start.elapsed().as_nanos() as u64);
+
+        result
+    }
+}
+
+pub trait CpuTimeExt: Future + Sized {
+    /// Records the time spent polling this future as CPU time of the current component.
+    fn track_cpu_time(self) -> CpuTime<Self> {
+        CpuTime {
+            inner: self,
+            cpu_time: None,
+        }
+    }
+}
+
+impl<F: Future> CpuTimeExt for F {}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use tracing::Instrument;
+    use vector_core::event::MetricValue;
+
+    use super::*;
+    use crate::{metrics::Controller, test_util::trace_init};
+
+    const COMPONENT_ID: &str = "cpu_time_component";
+
+    fn cpu_time() -> f64 {
+        Controller::get()
+            .expect("There must be a controller")
+            .capture_metrics()
+            .into_iter()
+            .filter(|metric| {
+                metric.name() == CPU_TIME_NAME
+                    && metric.tag_value("component_id").as_deref() == Some(COMPONENT_ID)
+            })
+            .map(|metric| match metric.value() {
+                MetricValue::Counter { value } => *value,
+                value => panic!("unexpected metric value: {:?}", value),
+            })
+            .sum()
+    }
+
+    #[tokio::test]
+    async fn records_cpu_time_of_component() {
+        trace_init();
+        assert_eq!(cpu_time(), 0.0);
+
+        async { std::thread::sleep(Duration::from_millis(5)) }
+            .track_cpu_time()
+            .instrument(info_span!("component", component_id = COMPONENT_ID))
+            .await;
+
+        assert!(cpu_time() >= Duration::from_millis(5).as_nanos() as f64);
+    }
+}
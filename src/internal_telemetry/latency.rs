@@ -0,0 +1,156 @@
+//! End-to-end latency exposed via internal telemetry.
+//!
+//! Sources stamp the events they ingest with their ID and a monotonic ingest time, from which
+//! sinks record the time events took to be delivered in the `source_to_sink_latency_seconds`
+//! histogram, tagged with the ID of the source alongside the tags of the sink.
+//!
+//! Tracking the delivery of events adds a finalizer to each of them, so it is only enabled by the
+//! `--latency-tracking` flag.
+
+use std::{
+    collections::HashMap,
+    sync::atomic::{AtomicBool, Ordering},
+    time::Instant,
+};
+
+use metrics::{register_histogram, Histogram};
+use vector_core::event::{BatchNotifier, BatchStatus, EventArray, EventContainer, EventFinalizer};
+
+use crate::config::ComponentKey;
+
+const LATENCY_NAME: &str = "source_to_sink_latency_seconds";
+
+pub static TRACK_LATENCY: AtomicBool = AtomicBool::new(false);
+
+/// Records the latency of the events delivered by a sink, per source.
+#[derive(Default)]
+pub struct Latency {
+    histograms: HashMap<ComponentKey, Histogram>,
+}
+
+impl Latency {
+    /// Tracks the delivery of the given events, whose latency is recorded once their finalizers
+    /// are done with, if they were all delivered. Does nothing unless latency tracking is enabled.
+    ///
+    /// This must be called from within the span of the sink for the histograms to be tagged with
+    /// it.
+    pub fn track(&mut self, events: &mut EventArray) {
+        if !TRACK_LATENCY.load(Ordering::Relaxed) {
+            return;
+        }
+
+        let mut ingests = Vec::new();
+        let mut notifier = None;
+        for mut event in events.iter_events_mut() {
+            match event.metadata().ingest() {
+                Some(ingest) => {
+                    ingests.push((self.histogram(ingest.source_id()), ingest.timestamp()));
+                }
+                None => continue,
+            }
+
+            let (batch, _) = notifier.get_or_insert_with(BatchNotifier::new_with_receiver);
+            event
+                .metadata_mut()
+                .add_finalizer(EventFinalizer::new(batch.clone()));
+        }
+
+        if let Some((_, receiver)) = notifier {
+            tokio::spawn(async move {
+                if receiver.await == BatchStatus::Delivered {
+                    let now = Instant::now();
+                    for (histogram, timestamp) in ingests {
+                        histogram.record(now.saturating_duration_since(timestamp));
+                    }
+                }
+            });
+        }
+    }
+
+    fn histogram(&mut self, source_id: &ComponentKey) -> Histogram {
+        if !self.histograms.contains_key(source_id) {
+            let histogram =
+                register_histogram!(LATENCY_NAME, "source_id" => source_id.id().to_owned());
+            self.histograms.insert(source_id.clone(), histogram);
+        }
+
+        self.histograms[source_id].clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::Arc, time::Duration};
+
+    use vector_core::event::{EventStatus, IngestMetadata, LogEvent, MetricValue};
+
+    use super::*;
+    use crate::metrics::{self, Controller};
+
+    const SOURCE_ID: &str = "latency_source";
+
+    fn ingested() -> EventArray {
+        let mut log = LogEvent::from("latency");
+        log.metadata_mut().set_ingest(IngestMetadata::new(
+            Arc::new(ComponentKey::from(SOURCE_ID)),
+            Instant::now(),
+        ));
+        vec![log].into()
+    }
+
+    async fn recorded() -> u64 {
+        // The latency is recorded by a task once the events are finalized.
+        tokio::time::sleep(Duration::from_millis(10)).await;
+
+        Controller::get()
+            .expect("There must be a controller")
+            .capture_metrics()
+            .into_iter()
+            .filter(|metric| {
+                metric.name() == LATENCY_NAME
+                    && metric.tag_value("source_id").as_deref() == Some(SOURCE_ID)
+            })
+            .map(|metric| match metric.value() {
+                MetricValue::AggregatedHistogram { count, .. } => *count,
+                value => panic!("unexpected metric value: {:?}", value),
+            })
+            .sum()
+    }
+
+    #[tokio::test]
+    async fn records_latency_of_delivered_events() {
+        metrics::init_test();
+        let mut latency = Latency::default();
+
+        // Events are not tracked unless latency tracking is enabled.
+        let mut events = ingested();
+        latency.track(&mut events);
+        assert!(events
+            .iter_events()
+            .all(|event| event.metadata().finalizers().is_empty()));
+        drop(events);
+        assert_eq!(recorded().await, 0);
+
+        TRACK_LATENCY.store(true, Ordering::Relaxed);
+
+        let mut events = ingested();
+        latency.track(&mut events);
+        drop(events);
+        assert_eq!(recorded().await, 1);
+
+        let mut events = ingested();
+        latency.track(&mut events);
+        events
+            .iter_events()
+            .for_each(|event| event.metadata().update_status(EventStatus::Errored));
+        drop(events);
+        assert_eq!(recorded().await, 1);
+
+        // Events ingested before being stamped with a source are not tracked.
+        let mut events = EventArray::from(vec![LogEvent::from("unstamped")]);
+        latency.track(&mut events);
+        assert!(events
+            .iter_events()
+            .all(|event| event.metadata().finalizers().is_empty()));
+    }
+}
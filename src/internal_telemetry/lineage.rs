@@ -0,0 +1,583 @@
+//! Lineage of events through the topology.
+//!
+//! While a client is subscribed, sources select the events matching its condition and tag them
+//! with a trace ID in their metadata. Every component these events reach then records what it did
+//! to them, which is streamed back to the subscriber until it is dropped.
+
+use std::{
+    collections::{HashMap, HashSet},
+    pin::Pin,
+    sync::{
+        atomic::{AtomicU64, AtomicUsize, Ordering},
+        Arc, Mutex, RwLock,
+    },
+    task::{Context, Poll},
+};
+
+use futures::Stream;
+use once_cell::sync::Lazy;
+use tokio::sync::mpsc::{self, error::TrySendError};
+use vector_common::EventDataEq;
+use vector_core::event::{
+    BatchNotifier, BatchStatus, Event, EventArray, EventContainer, EventFinalizer, EventRef,
+};
+
+use crate::{conditions::Condition, config::ComponentKey};
+
+/// The number of records buffered for a subscriber, past which records are dropped.
+const RECORDS_BUFFER_SIZE: usize = 1000;
+
+static SUBSCRIBERS: Lazy<RwLock<Vec<Arc<Subscriber>>>> = Lazy::new(Default::default);
+
+/// The number of subscribers, to skip tracing altogether without locking when there are none.
+static SUBSCRIBERS_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+static NEXT_SUBSCRIBER_ID: AtomicUsize = AtomicUsize::new(0);
+static NEXT_TRACE_ID: AtomicU64 = AtomicU64::new(1);
+
+/// What a component did to a traced event.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Action {
+    /// The event was ingested by a source.
+    Ingested,
+    /// The event was sent by a transform unchanged.
+    Passed,
+    /// The event was modified by a transform.
+    Mutated,
+    /// The event was dropped by a transform.
+    Dropped,
+    /// The event was delivered by a sink.
+    Delivered,
+    /// The event failed to be delivered by a sink.
+    Failed,
+}
+
+/// A record of what a component did to a traced event.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Record {
+    pub trace_id: u64,
+    pub component_id: ComponentKey,
+    pub component_kind: &'static str,
+    /// The named output the event was sent to, if any.
+    pub output: Option<String>,
+    pub action: Action,
+}
+
+struct Subscriber {
+    id: usize,
+    condition: Condition,
+    /// The number of events left to select, as every selected event is tracked until the
+    /// subscriber is dropped.
+    remaining: AtomicUsize,
+    trace_ids: Mutex<HashSet<u64>>,
+    tx: mpsc::Sender<Record>,
+}
+
+impl Subscriber {
+    fn is_tracing(&self, trace_id: u64) -> bool {
+        self.trace_ids
+            .lock()
+            .expect("lineage mutex poisoned")
+            .contains(&trace_id)
+    }
+
+    /// Checks the event against the condition, unless enough events were already selected.
+    fn select(&self, event: Event) -> (bool, Event) {
+        if self.remaining.load(Ordering::Relaxed) == 0 {
+            return (false, event);
+        }
+
+        let (matched, event) = self.condition.check(event);
+        let selected = matched
+            && self
+                .remaining
+                .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |remaining| {
+                    remaining.checked_sub(1)
+                })
+                .is_ok();
+        (selected, event)
+    }
+
+    fn send(&self, record: Record) {
+        if let Err(TrySendError::Full(record)) = self.tx.try_send(record) {
+            debug!(message = "Couldn't send lineage record.", ?record);
+        }
+    }
+}
+
+/// A subscription to the lineage of the events matching a condition, which stops tracing once
+/// dropped.
+pub struct Subscription {
+    id: usize,
+    rx: mpsc::Receiver<Record>,
+}
+
+impl Subscription {
+    /// Traces the lineage of up to `limit` events matching the condition when ingested.
+    pub fn new(condition: Condition, limit: usize) -> Self {
+        let (tx, rx) = mpsc::channel(RECORDS_BUFFER_SIZE);
+        let id = NEXT_SUBSCRIBER_ID.fetch_add(1, Ordering::Relaxed);
+
+        let mut subscribers = SUBSCRIBERS.write().expect("lineage lock poisoned");
+        subscribers.push(Arc::new(Subscriber {
+            id,
+            condition,
+            remaining: AtomicUsize::new(limit),
+            trace_ids: Default::default(),
+            tx,
+        }));
+        SUBSCRIBERS_COUNT.store(subscribers.len(), Ordering::Relaxed);
+
+        Self { id, rx }
+    }
+}
+
+impl Drop for Subscription {
+    fn drop(&mut self) {
+        let mut subscribers = SUBSCRIBERS.write().expect("lineage lock poisoned");
+        subscribers.retain(|subscriber| subscriber.id != self.id);
+        SUBSCRIBERS_COUNT.store(subscribers.len(), Ordering::Relaxed);
+    }
+}
+
+impl Stream for Subscription {
+    type Item = Record;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.rx.poll_recv(cx)
+    }
+}
+
+fn is_active() -> bool {
+    SUBSCRIBERS_COUNT.load(Ordering::Relaxed) > 0
+}
+
+fn current_subscribers() -> Vec<Arc<Subscriber>> {
+    SUBSCRIBERS.read().expect("lineage lock poisoned").clone()
+}
+
+/// Sends the record to the subscribers tracing its event.
+fn record(
+    subscribers: &[Arc<Subscriber>],
+    trace_id: u64,
+    component_id: &ComponentKey,
+    component_kind: &'static str,
+    output: Option<&str>,
+    action: Action,
+) {
+    for subscriber in subscribers {
+        if subscriber.is_tracing(trace_id) {
+            subscriber.send(Record {
+                trace_id,
+                component_id: component_id.clone(),
+                component_kind,
+                output: output.map(ToOwned::to_owned),
+                action,
+            });
+        }
+    }
+}
+
+fn is_traced(subscribers: &[Arc<Subscriber>], trace_id: u64) -> bool {
+    subscribers
+        .iter()
+        .any(|subscriber| subscriber.is_tracing(trace_id))
+}
+
+/// Selects the events ingested by a source that match the condition of a subscriber, and tags
+/// them with a new trace ID. Events already traced are left as they are.
+pub fn select(source_id: &ComponentKey, output: Option<&str>, events: EventArray) -> EventArray {
+    if !is_active() {
+        return events;
+    }
+
+    let subscribers = current_subscribers();
+    let select_event = |mut event: Event| {
+        if event.metadata().trace_id().is_some() {
+            return event;
+        }
+
+        let mut trace_id = None;
+        for subscriber in &subscribers {
+            let (selected, checked) = subscriber.select(event);
+            event = checked;
+            if selected {
+                let trace_id =
+                    *trace_id.get_or_insert_with(|| NEXT_TRACE_ID.fetch_add(1, Ordering::Relaxed));
+                subscriber
+                    .trace_ids
+                    .lock()
+                    .expect("lineage mutex poisoned")
+                    .insert(trace_id);
+            }
+        }
+
+        if let Some(trace_id) = trace_id {
+            event.metadata_mut().set_trace_id(trace_id);
+            record(
+                &subscribers,
+                trace_id,
+                source_id,
+                "source",
+                output,
+                Action::Ingested,
+            );
+        }
+        event
+    };
+
+    match events {
+        EventArray::Logs(logs) => EventArray::Logs(
+            logs.into_iter()
+                .map(|log| select_event(log.into()).into_log())
+                .collect(),
+        ),
+        EventArray::Metrics(metrics) => EventArray::Metrics(
+            metrics
+                .into_iter()
+                .map(|metric| select_event(metric.into()).into_metric())
+                .collect(),
+        ),
+        EventArray::Traces(traces) => EventArray::Traces(
+            traces
+                .into_iter()
+                .map(|trace| select_event(trace.into()).into_trace())
+                .collect(),
+        ),
+    }
+}
+
+/// The traced events received by a transform, against which the events it sends are compared to
+/// record what it did to them.
+#[derive(Default)]
+pub struct TransformLineage {
+    inputs: HashMap<u64, Event>,
+}
+
+impl TransformLineage {
+    /// Keeps a copy of the traced events among those received by the transform.
+    pub fn receive<'a>(&mut self, events: impl IntoIterator<Item = EventRef<'a>>) {
+        if !is_active() {
+            return;
+        }
+
+        let mut subscribers = None;
+        for event in events {
+            if let Some(trace_id) = event.metadata().trace_id() {
+                let subscribers = subscribers.get_or_insert_with(current_subscribers);
+                if is_traced(subscribers, trace_id) {
+                    self.inputs.insert(trace_id, to_event(event));
+                }
+            }
+        }
+    }
+
+    /// Keeps a copy of the traced events among those received by a task transform, which may send
+    /// them long after receiving them.
+    ///
+    /// The events are tracked until the transform is done with them, such as when it drops them or
+    /// once the event they are merged into is delivered, at which point those it didn't send are
+    /// recorded as dropped.
+    pub fn receive_tracked(
+        lineage: &Arc<Mutex<Self>>,
+        component_id: &ComponentKey,
+        events: &mut EventArray,
+    ) {
+        if !is_active() {
+            return;
+        }
+
+        let subscribers = current_subscribers();
+        let mut tracked = HashSet::new();
+        {
+            let mut lineage = lineage.lock().expect("lineage mutex poisoned");
+            for event in events.iter_events() {
+                if let Some(trace_id) = event.metadata().trace_id() {
+                    if is_traced(&subscribers, trace_id) {
+                        lineage.inputs.insert(trace_id, to_event(event));
+                        tracked.insert(trace_id);
+                    }
+                }
+            }
+        }
+        if tracked.is_empty() {
+            return;
+        }
+
+        for mut event in events.iter_events_mut() {
+            let trace_id = match event.metadata().trace_id() {
+                Some(trace_id) if tracked.contains(&trace_id) => trace_id,
+                _ => continue,
+            };
+
+            let (batch, receiver) = BatchNotifier::new_with_receiver();
+            event
+                .metadata_mut()
+                .add_finalizer(EventFinalizer::new(batch));
+
+            let lineage = Arc::clone(lineage);
+            let component_id = component_id.clone();
+            tokio::spawn(async move {
+                receiver.await;
+                let unsent = lineage
+                    .lock()
+                    .expect("lineage mutex poisoned")
+                    .inputs
+                    .remove(&trace_id)
+                    .is_some();
+                if unsent {
+                    record(
+                        &current_subscribers(),
+                        trace_id,
+                        &component_id,
+                        "transform",
+                        None,
+                        Action::Dropped,
+                    );
+                }
+            });
+        }
+    }
+
+    /// Records the traced events sent by the transform as passed or mutated, depending on whether
+    /// they differ from the event received.
+    ///
+    /// The received events are forgotten afterwards, so events sent without having been received
+    /// since, such as those aggregated over time, are recorded as passed. Those no longer traced by
+    /// any subscriber are forgotten too.
+    pub fn send<'a>(
+        &mut self,
+        component_id: &ComponentKey,
+        events: impl IntoIterator<Item = (Option<&'a str>, EventRef<'a>)>,
+    ) {
+        if self.inputs.is_empty() && !is_active() {
+            return;
+        }
+
+        let subscribers = current_subscribers();
+        let mut sent = HashSet::new();
+        for (output, event) in events {
+            if let Some(trace_id) = event.metadata().trace_id() {
+                let action = match self.inputs.get(&trace_id) {
+                    Some(input) if !event.event_data_eq(input) => Action::Mutated,
+                    _ => Action::Passed,
+                };
+                record(
+                    &subscribers,
+                    trace_id,
+                    component_id,
+                    "transform",
+                    output,
+                    action,
+                );
+                sent.insert(trace_id);
+            }
+        }
+
+        self.inputs
+            .retain(|trace_id, _| !sent.contains(trace_id) && is_traced(&subscribers, *trace_id));
+    }
+
+    /// Records the traced events received but not sent by the transform as dropped.
+    pub fn drop_unsent(&mut self, component_id: &ComponentKey) {
+        if self.inputs.is_empty() {
+            return;
+        }
+
+        let subscribers = current_subscribers();
+        for (trace_id, _) in self.inputs.drain() {
+            record(
+                &subscribers,
+                trace_id,
+                component_id,
+                "transform",
+                None,
+                Action::Dropped,
+            );
+        }
+    }
+}
+
+/// Tracks the delivery of the traced events received by a sink, recorded once their finalizers
+/// are done with.
+pub fn deliver(component_id: &ComponentKey, events: &mut EventArray) {
+    if !is_active() {
+        return;
+    }
+
+    let subscribers = current_subscribers();
+    for mut event in events.iter_events_mut() {
+        let trace_id = match event.metadata().trace_id() {
+            Some(trace_id) if is_traced(&subscribers, trace_id) => trace_id,
+            _ => continue,
+        };
+
+        let (batch, receiver) = BatchNotifier::new_with_receiver();
+        event
+            .metadata_mut()
+            .add_finalizer(EventFinalizer::new(batch));
+
+        let subscribers = subscribers.clone();
+        let component_id = component_id.clone();
+        tokio::spawn(async move {
+            let action = match receiver.await {
+                BatchStatus::Delivered => Action::Delivered,
+                BatchStatus::Errored | BatchStatus::Rejected => Action::Failed,
+            };
+            record(&subscribers, trace_id, &component_id, "sink", None, action);
+        });
+    }
+}
+
+fn to_event(event: EventRef<'_>) -> Event {
+    match event {
+        EventRef::Log(log) => log.clone().into(),
+        EventRef::Metric(metric) => metric.clone().into(),
+        EventRef::Trace(trace) => trace.clone().into(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::iter;
+
+    use futures::StreamExt;
+
+    use super::*;
+    use crate::{
+        conditions::{ConditionConfig, VrlConfig},
+        config::{DataType, Output},
+        event::LogEvent,
+        transforms::TransformOutputsBuf,
+    };
+
+    fn subscribe(source: &str, limit: usize) -> Subscription {
+        let condition = ConditionConfig::Vrl(VrlConfig {
+            source: source.to_owned(),
+            runtime: Default::default(),
+        })
+        .build(&Default::default())
+        .unwrap();
+        Subscription::new(condition, limit)
+    }
+
+    fn log(message: &str, tenant: &str) -> LogEvent {
+        let mut log = LogEvent::from(message);
+        log.insert("tenant", tenant);
+        log
+    }
+
+    fn traced(events: &EventArray) -> Vec<Option<u64>> {
+        events
+            .iter_events()
+            .map(|event| event.metadata().trace_id())
+            .collect()
+    }
+
+    // The subscribers are global, so lineage is tested end to end in a single test.
+    #[tokio::test]
+    async fn traces_lineage() {
+        let source_id = ComponentKey::from("in");
+        let transform_id = ComponentKey::from("route");
+        let sink_id = ComponentKey::from("out");
+
+        let untraced = select(&source_id, None, vec![log("a", "lineage")].into());
+        assert_eq!(traced(&untraced), vec![None]);
+
+        let mut subscription = subscribe(r#".tenant == "lineage""#, 2);
+
+        let events = select(
+            &source_id,
+            None,
+            vec![
+                log("b", "lineage"),
+                log("c", "untraced"),
+                log("d", "lineage"),
+            ]
+            .into(),
+        );
+        let trace_ids = traced(&events);
+        let (first, second) = match trace_ids[..] {
+            [Some(first), None, Some(second)] => (first, second),
+            _ => panic!("unexpected trace IDs: {:?}", trace_ids),
+        };
+
+        // The limit is reached, so no more events are selected.
+        let events_after_limit = select(&source_id, None, vec![log("e", "lineage")].into());
+        assert_eq!(traced(&events_after_limit), vec![None]);
+
+        let task_events = events.clone();
+        let mut lineage = TransformLineage::default();
+        lineage.receive(events.iter_events());
+        let mut outputs =
+            TransformOutputsBuf::new_with_capacity(vec![Output::default(DataType::Log)], 1);
+        let mut events = events.into_events();
+        let mut mutated = events.next().unwrap();
+        mutated.as_mut_log().insert("routed", true);
+        outputs.push(mutated);
+        lineage.send(&transform_id, outputs.iter_events());
+        lineage.drop_unsent(&transform_id);
+
+        let mut delivered = EventArray::from(outputs.drain().next().unwrap().into_log());
+        deliver(&sink_id, &mut delivered);
+        drop(delivered);
+
+        let mut records = Vec::new();
+        for _ in 0..5 {
+            records.push(subscription.next().await.unwrap());
+        }
+        let summary = records
+            .iter()
+            .map(|record| {
+                (
+                    record.trace_id,
+                    record.component_id.id().to_owned(),
+                    record.action,
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            summary,
+            vec![
+                (first, "in".to_owned(), Action::Ingested),
+                (second, "in".to_owned(), Action::Ingested),
+                (first, "route".to_owned(), Action::Mutated),
+                (second, "route".to_owned(), Action::Dropped),
+                (first, "out".to_owned(), Action::Delivered),
+            ]
+        );
+
+        // Task transforms record the events they're done with without sending them as dropped.
+        let task_id = ComponentKey::from("dedupe");
+        let task_lineage = Arc::new(Mutex::new(TransformLineage::default()));
+        let mut received = task_events.clone();
+        TransformLineage::receive_tracked(&task_lineage, &task_id, &mut received);
+        let mut received = received.into_events();
+        let sent = received.next().unwrap();
+        task_lineage
+            .lock()
+            .unwrap()
+            .send(&task_id, iter::once((None, EventRef::from(&sent))));
+        drop(received);
+
+        let records = [
+            subscription.next().await.unwrap(),
+            subscription.next().await.unwrap(),
+        ];
+        assert_eq!(
+            records.map(|record| (record.trace_id, record.action)),
+            [(first, Action::Passed), (second, Action::Dropped)]
+        );
+        assert!(task_lineage.lock().unwrap().inputs.is_empty());
+
+        // Events no longer traced are forgotten.
+        let mut pending = TransformLineage::default();
+        pending.receive(task_events.iter_events());
+        drop(subscription);
+        pending.send(&task_id, iter::empty());
+        assert!(pending.inputs.is_empty());
+
+        let events = select(&source_id, None, vec![log("f", "lineage")].into());
+        assert_eq!(traced(&events), vec![None]);
+    }
+}
@@ -1,2 +1,5 @@
 #[cfg(feature = "allocation-tracing")]
 pub mod allocations;
+pub mod cpu_time;
+pub mod latency;
+pub mod lineage;
@@ -0,0 +1,258 @@
+use std::time::Duration;
+
+use rumqttc::{Key, MqttOptions, TlsConfiguration, Transport};
+use snafu::{ResultExt, Snafu};
+use vector_common::sensitive_string::SensitiveString;
+use vector_config::configurable_component;
+
+use crate::tls::TlsEnableableConfig;
+
+#[derive(Debug, Snafu)]
+pub enum MqttConfigError {
+    #[snafu(display("MQTT TLS Config Error: `ca_file` is required when TLS is enabled"))]
+    TlsMissingCa,
+    #[snafu(display("MQTT TLS Config Error: missing key"))]
+    TlsMissingKey,
+    #[snafu(display("MQTT TLS Config Error: missing cert"))]
+    TlsMissingCert,
+    #[snafu(display("MQTT TLS Config Error: could not read {}: {}", path, source))]
+    TlsReadFile {
+        path: String,
+        source: std::io::Error,
+    },
+    #[snafu(display("MQTT Auth Config Error: `password` requires `user` to be set"))]
+    MissingUser,
+}
+
+/// Connection options for MQTT.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct MqttConnectionConfig {
+    /// The MQTT broker to connect to.
+    pub(crate) host: String,
+
+    /// The TCP port of the MQTT broker.
+    #[serde(default = "default_port")]
+    pub(crate) port: u16,
+
+    /// The client ID to connect with.
+    ///
+    /// The broker keeps the session of the client, including unacknowledged deliveries, under this
+    /// ID. Two clients connected with the same ID disconnect each other.
+    ///
+    /// By default, sources connect with an ID derived from their component ID, so that their
+    /// session is resumed when they restart, and sinks connect with a random ID.
+    pub(crate) client_id: Option<String>,
+
+    /// The username to authenticate with.
+    pub(crate) user: Option<String>,
+
+    /// The password to authenticate with.
+    pub(crate) password: Option<SensitiveString>,
+
+    /// The interval, in seconds, at which the client pings the broker to keep the connection alive.
+    #[serde(default = "default_keep_alive_secs")]
+    pub(crate) keep_alive_secs: u64,
+
+    #[configurable(derived)]
+    pub(crate) tls: Option<TlsEnableableConfig>,
+}
+
+impl Default for MqttConnectionConfig {
+    fn default() -> Self {
+        Self {
+            host: "localhost".to_string(),
+            port: default_port(),
+            client_id: None,
+            user: None,
+            password: None,
+            keep_alive_secs: default_keep_alive_secs(),
+            tls: None,
+        }
+    }
+}
+
+const fn default_port() -> u16 {
+    1883
+}
+
+const fn default_keep_alive_secs() -> u64 {
+    60
+}
+
+/// The quality of service level of MQTT deliveries.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum MqttQoS {
+    /// Messages are delivered at most once, and may be lost (QoS 0).
+    AtMostOnce,
+
+    /// Messages are delivered at least once, and may be duplicated (QoS 1).
+    AtLeastOnce,
+
+    /// Messages are delivered exactly once (QoS 2).
+    ExactlyOnce,
+}
+
+impl Default for MqttQoS {
+    fn default() -> Self {
+        Self::AtLeastOnce
+    }
+}
+
+impl From<MqttQoS> for rumqttc::QoS {
+    fn from(qos: MqttQoS) -> Self {
+        match qos {
+            MqttQoS::AtMostOnce => rumqttc::QoS::AtMostOnce,
+            MqttQoS::AtLeastOnce => rumqttc::QoS::AtLeastOnce,
+            MqttQoS::ExactlyOnce => rumqttc::QoS::ExactlyOnce,
+        }
+    }
+}
+
+impl MqttConnectionConfig {
+    /// Builds the options of an MQTT client connecting with this configuration, and with the
+    /// given client ID unless one is configured.
+    pub(crate) fn options(&self, default_client_id: &str) -> Result<MqttOptions, MqttConfigError> {
+        let client_id = self.client_id.as_deref().unwrap_or(default_client_id);
+        let mut options = MqttOptions::new(client_id, &self.host, self.port);
+        options.set_keep_alive(Duration::from_secs(self.keep_alive_secs));
+
+        match (&self.user, &self.password) {
+            (Some(user), password) => {
+                let password = password
+                    .as_ref()
+                    .map(|password| password.inner())
+                    .unwrap_or("");
+                options.set_credentials(user, password);
+            }
+            (None, Some(_)) => return Err(MqttConfigError::MissingUser),
+            (None, None) => {}
+        }
+
+        if let Some(tls) = &self.tls {
+            if tls.enabled.unwrap_or(false) {
+                options.set_transport(Transport::tls_with_config(tls_configuration(tls)?));
+            }
+        }
+
+        Ok(options)
+    }
+}
+
+fn tls_configuration(tls: &TlsEnableableConfig) -> Result<TlsConfiguration, MqttConfigError> {
+    let read = |path: &std::path::PathBuf| {
+        std::fs::read(path).context(TlsReadFileSnafu {
+            path: path.display().to_string(),
+        })
+    };
+
+    let ca = match &tls.options.ca_file {
+        Some(ca_file) => read(ca_file)?,
+        None => return Err(MqttConfigError::TlsMissingCa),
+    };
+
+    let client_auth = match (&tls.options.crt_file, &tls.options.key_file) {
+        (None, None) => None,
+        (Some(crt_file), Some(key_file)) => Some((read(crt_file)?, Key::RSA(read(key_file)?))),
+        (Some(_), None) => return Err(MqttConfigError::TlsMissingKey),
+        (None, Some(_)) => return Err(MqttConfigError::TlsMissingCert),
+    };
+
+    let alpn = tls.options.alpn_protocols.as_ref().map(|protocols| {
+        protocols
+            .iter()
+            .map(|protocol| protocol.as_bytes().to_vec())
+            .collect()
+    });
+
+    Ok(TlsConfiguration::Simple {
+        ca,
+        alpn,
+        client_auth,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(s: &str) -> Result<MqttOptions, crate::Error> {
+        toml::from_str(s)
+            .map_err(Into::into)
+            .and_then(|config: MqttConnectionConfig| config.options("default").map_err(Into::into))
+    }
+
+    #[test]
+    fn options_defaults() {
+        let options = parse(r#"host = "broker""#).unwrap();
+        assert_eq!(options.broker_address(), ("broker".to_string(), 1883));
+        assert_eq!(options.client_id(), "default");
+        assert_eq!(options.keep_alive(), Duration::from_secs(60));
+        assert_eq!(options.credentials(), None);
+    }
+
+    #[test]
+    fn options_client_id() {
+        let options = parse(
+            r#"
+            host = "broker"
+            client_id = "client"
+        "#,
+        )
+        .unwrap();
+        assert_eq!(options.client_id(), "client");
+    }
+
+    #[test]
+    fn options_credentials() {
+        let options = parse(
+            r#"
+            host = "broker"
+            user = "username"
+            password = "password"
+        "#,
+        )
+        .unwrap();
+        assert_eq!(
+            options.credentials(),
+            Some(("username".to_string(), "password".to_string()))
+        );
+    }
+
+    #[test]
+    fn options_password_without_user() {
+        parse(
+            r#"
+            host = "broker"
+            password = "password"
+        "#,
+        )
+        .unwrap_err();
+    }
+
+    #[test]
+    fn options_tls_without_ca() {
+        parse(
+            r#"
+            host = "broker"
+            tls.enabled = true
+        "#,
+        )
+        .unwrap_err();
+    }
+
+    #[test]
+    fn options_tls_with_ca() {
+        parse(
+            r#"
+            host = "broker"
+            tls.enabled = true
+            tls.ca_file = "tests/data/ca/certs/ca.cert.pem"
+        "#,
+        )
+        .unwrap();
+    }
+}
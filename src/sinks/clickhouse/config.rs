@@ -1,3 +1,5 @@
+use std::fmt;
+
 use vector_config::configurable_component;
 
 use crate::{
@@ -32,9 +34,15 @@ pub struct ClickhouseConfig {
     pub database: Option<String>,
 
     /// Sets `input_format_skip_unknown_fields`, allowing Clickhouse to discard fields not present in the table schema.
+    ///
+    /// Only used by the `json_each_row` format, as the binary formats only ever send the columns of the table.
     #[serde(default)]
     pub skip_unknown_fields: bool,
 
+    #[configurable(derived)]
+    #[serde(default)]
+    pub format: Format,
+
     #[configurable(derived)]
     #[serde(default = "Compression::gzip_default")]
     pub compression: Compression,
@@ -69,13 +77,55 @@ pub struct ClickhouseConfig {
     pub acknowledgements: AcknowledgementsConfig,
 }
 
+/// The format events are inserted in.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Derivative, Eq, PartialEq)]
+#[derivative(Default)]
+#[serde(rename_all = "snake_case")]
+pub enum Format {
+    /// Events are sent as JSON objects, which Clickhouse parses and converts to the types of the columns.
+    ///
+    /// This is the default.
+    #[derivative(Default)]
+    JsonEachRow,
+
+    /// Events are coerced to the types of the columns of the table, and sent row by row in binary.
+    ///
+    /// The columns of the table are fetched when the sink starts.
+    RowBinary,
+
+    /// Events are coerced to the types of the columns of the table, and sent column by column in binary.
+    ///
+    /// The columns of the table are fetched when the sink starts.
+    Native,
+}
+
+impl fmt::Display for Format {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::JsonEachRow => write!(f, "json_each_row"),
+            Self::RowBinary => write!(f, "row_binary"),
+            Self::Native => write!(f, "native"),
+        }
+    }
+}
+
 impl_generate_config_from_default!(ClickhouseConfig);
 
+impl ClickhouseConfig {
+    /// The table, qualified with its database and quoted, to use in queries.
+    pub(super) fn qualified_table(&self) -> String {
+        format!(
+            "\"{}\".\"{}\"",
+            self.database.as_deref().unwrap_or("default"),
+            self.table.replace('\"', "\\\"")
+        )
+    }
+}
+
 #[async_trait::async_trait]
 impl SinkConfig for ClickhouseConfig {
     async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
-        // later we can build different sink(http, native) here
-        // according to the clickhouseConfig
         build_http_sink(self, cx).await
     }
 
@@ -1,20 +1,28 @@
+use std::{io::Write, sync::Arc};
+
 use bytes::{BufMut, Bytes, BytesMut};
 use futures::{FutureExt, SinkExt};
 use http::{Request, StatusCode, Uri};
 use hyper::Body;
 use snafu::ResultExt;
 
-use super::ClickhouseConfig;
+use super::{
+    config::Format,
+    row::{write_native, write_row_binary, Row},
+    schema::{fetch_columns, Column},
+    ClickhouseConfig,
+};
 use crate::{
     codecs::Transformer,
     config::SinkContext,
     event::Event,
     http::{HttpClient, HttpError, MaybeAuth},
+    internal_events::ClickhouseCoercionError,
     sinks::{
         util::{
             http::{BatchedHttpSink, HttpEventEncoder, HttpRetryLogic, HttpSink},
             retries::{RetryAction, RetryLogic},
-            Buffer, TowerRequestConfig,
+            Buffer, Compressor, TowerRequestConfig, VecBuffer,
         },
         Healthcheck, HealthcheckError, UriParseSnafu, VectorSink,
     },
@@ -25,7 +33,6 @@ pub(crate) async fn build_http_sink(
     cfg: &ClickhouseConfig,
     cx: SinkContext,
 ) -> crate::Result<(VectorSink, Healthcheck)> {
-    let batch = cfg.batch.into_batch_settings()?;
     let request = cfg.request.unwrap_with(&TowerRequestConfig::default());
     let tls_settings = TlsSettings::from_options(&cfg.tls)?;
     let client = HttpClient::new(tls_settings, &cx.proxy)?;
@@ -35,19 +42,44 @@ pub(crate) async fn build_http_sink(
         ..cfg.clone()
     };
 
-    let sink = BatchedHttpSink::with_logic(
-        config.clone(),
-        Buffer::new(batch.size, cfg.compression),
-        ClickhouseRetryLogic::default(),
-        request,
-        batch.timeout,
-        client.clone(),
-    )
-    .sink_map_err(|error| error!(message = "Fatal clickhouse sink error.", %error));
+    let sink = match config.format {
+        Format::JsonEachRow => {
+            let batch = cfg.batch.into_batch_settings()?;
+            let sink = BatchedHttpSink::with_logic(
+                config.clone(),
+                Buffer::new(batch.size, cfg.compression),
+                ClickhouseRetryLogic::default(),
+                request,
+                batch.timeout,
+                client.clone(),
+            )
+            .sink_map_err(|error| error!(message = "Fatal clickhouse sink error.", %error));
+            VectorSink::from_event_sink(sink)
+        }
+        format => {
+            // The binary formats need the types of the columns to serialize values.
+            let columns = fetch_columns(&client, &config, format).await?;
+            let batch = cfg.batch.into_batch_settings()?;
+            let sink = BatchedHttpSink::with_logic(
+                ClickhouseBinarySink {
+                    config: config.clone(),
+                    format,
+                    columns: columns.into(),
+                },
+                VecBuffer::new(batch.size),
+                ClickhouseRetryLogic::default(),
+                request,
+                batch.timeout,
+                client.clone(),
+            )
+            .sink_map_err(|error| error!(message = "Fatal clickhouse sink error.", %error));
+            VectorSink::from_event_sink(sink)
+        }
+    };
 
     let healthcheck = healthcheck(client, config).boxed();
 
-    Ok((VectorSink::from_event_sink(sink), healthcheck))
+    Ok((sink, healthcheck))
 }
 
 pub struct ClickhouseEventEncoder {
@@ -109,6 +141,84 @@ impl HttpSink for ClickhouseConfig {
     }
 }
 
+/// Inserts events in the `RowBinary` or `Native` format, with their values coerced to the types of
+/// the columns of the table.
+struct ClickhouseBinarySink {
+    config: ClickhouseConfig,
+    format: Format,
+    columns: Arc<[Column]>,
+}
+
+struct ClickhouseRowEncoder {
+    transformer: Transformer,
+    columns: Arc<[Column]>,
+}
+
+impl HttpEventEncoder<Row> for ClickhouseRowEncoder {
+    fn encode_event(&mut self, mut event: Event) -> Option<Row> {
+        self.transformer.transform(&mut event);
+        let log = event.into_log();
+
+        Row::new(&log, &self.columns)
+            .map_err(|(index, error)| {
+                let column = &self.columns[index];
+                emit!(ClickhouseCoercionError {
+                    column: &column.name,
+                    column_type: &column.ty,
+                    error,
+                });
+            })
+            .ok()
+    }
+}
+
+#[async_trait::async_trait]
+impl HttpSink for ClickhouseBinarySink {
+    type Input = Row;
+    type Output = Vec<Row>;
+    type Encoder = ClickhouseRowEncoder;
+
+    fn build_encoder(&self) -> Self::Encoder {
+        ClickhouseRowEncoder {
+            transformer: self.config.encoding.clone(),
+            columns: Arc::clone(&self.columns),
+        }
+    }
+
+    async fn build_request(&self, rows: Self::Output) -> crate::Result<http::Request<Bytes>> {
+        let mut body = BytesMut::new();
+        match self.format {
+            Format::Native => write_native(&mut body, &self.columns, &rows),
+            _ => write_row_binary(&mut body, &self.columns, &rows),
+        }
+        let mut compressor = Compressor::from(self.config.compression);
+        compressor.write_all(&body)?;
+        let body = compressor.finish()?;
+
+        let uri = set_binary_uri_query(
+            &self.config.endpoint.with_default_parts().uri,
+            &self.config.qualified_table(),
+            &self.columns,
+            self.format,
+        )
+        .expect("Unable to encode uri");
+
+        let mut builder = Request::post(&uri).header("Content-Type", "application/octet-stream");
+
+        if let Some(ce) = self.config.compression.content_encoding() {
+            builder = builder.header("Content-Encoding", ce);
+        }
+
+        let mut request = builder.body(body.freeze()).unwrap();
+
+        if let Some(auth) = &self.config.auth {
+            auth.apply(&mut request);
+        }
+
+        Ok(request)
+    }
+}
+
 async fn healthcheck(client: HttpClient, config: ClickhouseConfig) -> crate::Result<()> {
     // TODO: check if table exists?
     let uri = format!("{}/?query=SELECT%201", config.endpoint.with_default_parts());
@@ -154,6 +264,46 @@ fn set_uri_query(uri: &Uri, database: &str, table: &str, skip_unknown: bool) ->
         .map_err(Into::into)
 }
 
+/// Builds the URI of inserts in a binary format, which name the columns they insert into as the
+/// other columns are computed by the server.
+fn set_binary_uri_query(
+    uri: &Uri,
+    table: &str,
+    columns: &[Column],
+    format: Format,
+) -> crate::Result<Uri> {
+    let columns = columns
+        .iter()
+        .map(|column| format!("\"{}\"", column.name.replace('\"', "\\\"")))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let format = match format {
+        Format::Native => "Native",
+        _ => "RowBinary",
+    };
+    let query = url::form_urlencoded::Serializer::new(String::new())
+        .append_pair(
+            "query",
+            format!("INSERT INTO {} ({}) FORMAT {}", table, columns, format).as_str(),
+        )
+        .finish();
+
+    let mut uri = uri.to_string();
+    if !uri.ends_with('/') {
+        uri.push('/');
+    }
+    uri.push('?');
+    if format == "Native" {
+        // `LowCardinality` columns are sent as their inner type.
+        uri.push_str("low_cardinality_allow_in_native_format=0&");
+    }
+    uri.push_str(query.as_str());
+
+    uri.parse::<Uri>()
+        .context(UriParseSnafu)
+        .map_err(Into::into)
+}
+
 #[derive(Debug, Default, Clone)]
 struct ClickhouseRetryLogic {
     inner: HttpRetryLogic,
@@ -195,7 +345,7 @@ impl RetryLogic for ClickhouseRetryLogic {
 
 #[cfg(test)]
 mod tests {
-    use super::*;
+    use super::{super::schema::ColumnType, *};
 
     #[test]
     fn generate_config() {
@@ -223,6 +373,38 @@ mod tests {
         assert_eq!(uri.to_string(), "http://localhost:80/?input_format_import_nested_json=1&query=INSERT+INTO+%22my_database%22.%22my_%5C%22table%5C%22%22+FORMAT+JSONEachRow");
     }
 
+    #[test]
+    fn encode_binary_valid() {
+        let columns = [
+            Column {
+                name: "message".to_owned(),
+                ty: ColumnType::String,
+            },
+            Column {
+                name: "level".to_owned(),
+                ty: ColumnType::LowCardinality(Box::new(ColumnType::String)),
+            },
+        ];
+
+        let uri = set_binary_uri_query(
+            &"http://localhost:80".parse().unwrap(),
+            "\"my_database\".\"my_table\"",
+            &columns,
+            Format::RowBinary,
+        )
+        .unwrap();
+        assert_eq!(uri.to_string(), "http://localhost:80/?query=INSERT+INTO+%22my_database%22.%22my_table%22+%28%22message%22%2C+%22level%22%29+FORMAT+RowBinary");
+
+        let uri = set_binary_uri_query(
+            &"http://localhost:80".parse().unwrap(),
+            "\"my_database\".\"my_table\"",
+            &columns,
+            Format::Native,
+        )
+        .unwrap();
+        assert_eq!(uri.to_string(), "http://localhost:80/?low_cardinality_allow_in_native_format=0&query=INSERT+INTO+%22my_database%22.%22my_table%22+%28%22message%22%2C+%22level%22%29+FORMAT+Native");
+    }
+
     #[test]
     fn encode_invalid() {
         set_uri_query(
@@ -136,6 +136,153 @@ async fn skip_unknown_fields() {
     assert_eq!(receiver.try_recv(), Ok(BatchStatus::Delivered));
 }
 
+#[tokio::test]
+async fn insert_events_binary_formats() {
+    trace_init();
+
+    for format in [Format::RowBinary, Format::Native] {
+        let table = gen_table();
+        let host = clickhouse_address();
+
+        let mut batch = BatchConfig::default();
+        batch.max_events = Some(2);
+
+        let config = ClickhouseConfig {
+            endpoint: host.parse().unwrap(),
+            table: table.clone(),
+            format,
+            compression: Compression::gzip_default(),
+            batch,
+            request: TowerRequestConfig {
+                retry_attempts: Some(1),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let client = ClickhouseClient::new(host);
+        client
+            .create_table(
+                &table,
+                "host LowCardinality(String), timestamp DateTime64(3, 'UTC'), message String, \
+                 level Nullable(String), count UInt32, items Array(LowCardinality(String)), \
+                 labels Map(String, UInt8), day Date MATERIALIZED toDate(timestamp)",
+            )
+            .await;
+
+        let (sink, _hc) = config.build(SinkContext::new_test()).await.unwrap();
+
+        let (mut first, mut receiver) = make_event();
+        first.as_mut_log().insert("level", "info");
+        first.as_mut_log().insert("count", "42");
+        first.as_mut_log().insert("items", vec!["item1", "item2"]);
+        first.as_mut_log().insert("labels.a", 1);
+        let (second, _) = make_event();
+
+        run_and_assert_sink_compliance(
+            sink,
+            stream::iter(vec![first.clone(), second]),
+            &HTTP_SINK_TAGS,
+        )
+        .await;
+
+        let output = client.select_all(&table).await;
+        assert_eq!(2, output.rows, "{}", format);
+
+        let timestamp = first
+            .as_log()
+            .get(log_schema().timestamp_key())
+            .unwrap()
+            .as_timestamp()
+            .unwrap()
+            .format("%Y-%m-%d %H:%M:%S%.3f")
+            .to_string();
+        let row = output
+            .data
+            .iter()
+            .find(|row| row["level"] == "info")
+            .expect("the first event should be inserted");
+        assert_eq!(row["host"], "example.com");
+        assert_eq!(row["timestamp"], timestamp.as_str());
+        assert_eq!(row["message"], "raw log line");
+        assert_eq!(row["count"], 42);
+        assert_eq!(row["items"], serde_json::json!(["item1", "item2"]));
+        assert_eq!(row["labels"], serde_json::json!({"a": 1}));
+        assert!(output.data.iter().any(|row| row["level"].is_null()));
+
+        assert_eq!(receiver.try_recv(), Ok(BatchStatus::Delivered));
+    }
+}
+
+#[tokio::test]
+async fn drops_events_failing_coercion() {
+    trace_init();
+
+    let table = gen_table();
+    let host = clickhouse_address();
+
+    let mut batch = BatchConfig::default();
+    batch.max_events = Some(1);
+
+    let config = ClickhouseConfig {
+        endpoint: host.parse().unwrap(),
+        table: table.clone(),
+        format: Format::RowBinary,
+        compression: Compression::None,
+        batch,
+        ..Default::default()
+    };
+
+    let client = ClickhouseClient::new(host);
+    client
+        .create_table(&table, "host String, timestamp DateTime, count UInt8")
+        .await;
+
+    let (sink, _hc) = config.build(SinkContext::new_test()).await.unwrap();
+
+    let (mut input_event, _receiver) = make_event();
+    input_event.as_mut_log().insert("count", 300);
+
+    sink.run_events(vec![input_event]).await.unwrap();
+
+    let output = client.select_all(&table).await;
+    assert_eq!(0, output.rows);
+}
+
+#[tokio::test]
+async fn rejects_unsupported_column_types() {
+    trace_init();
+
+    let table = gen_table();
+    let host = clickhouse_address();
+
+    let config = ClickhouseConfig {
+        endpoint: host.parse().unwrap(),
+        table: table.clone(),
+        format: Format::Native,
+        ..Default::default()
+    };
+
+    let client = ClickhouseClient::new(host);
+    client
+        .create_table(
+            &table,
+            "host String, timestamp DateTime, point Tuple(Float64, Float64)",
+        )
+        .await;
+
+    let error = config
+        .build(SinkContext::new_test())
+        .await
+        .map(|_| ())
+        .unwrap_err();
+    assert!(
+        error.to_string().contains("column `point`"),
+        "unexpected error: {}",
+        error
+    );
+}
+
 #[tokio::test]
 async fn insert_events_unix_timestamps() {
     trace_init();
@@ -2,4 +2,6 @@ mod config;
 mod http_sink;
 #[cfg(all(test, feature = "clickhouse-integration-tests"))]
 mod integration_tests;
-pub use self::config::ClickhouseConfig;
+mod row;
+mod schema;
+pub use self::config::{ClickhouseConfig, Format};
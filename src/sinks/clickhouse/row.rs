@@ -0,0 +1,852 @@
+//! Coerces event values into the types of the columns of the table, and serializes them in the
+//! `RowBinary` and `Native` formats.
+use std::{
+    fmt,
+    net::{IpAddr, Ipv4Addr},
+};
+
+use bytes::{BufMut, Bytes, BytesMut};
+use chrono::{DateTime, NaiveDate, NaiveDateTime, TimeZone, Utc};
+use vector_core::{
+    event::{LogEvent, Value},
+    ByteSizeOf,
+};
+
+use super::schema::{Column, ColumnType};
+use crate::sinks::util::EncodedLength;
+
+/// A value coerced for a column of a given type.
+///
+/// Values are only ever serialized with the type they were coerced for, which gives the width of
+/// integers.
+#[derive(Clone, Debug, PartialEq)]
+pub(super) enum ColumnValue {
+    Null,
+    Int(i128),
+    Float(f64),
+    Bytes(Bytes),
+    Array(Vec<ColumnValue>),
+    Map(Vec<(ColumnValue, ColumnValue)>),
+}
+
+/// An error coercing a value, with where the value is within the column.
+#[derive(Debug, PartialEq)]
+pub(super) struct CoercionError {
+    /// The path of the value within the column, such as `[2]` for the third element of an array.
+    pub(super) path: String,
+    pub(super) reason: String,
+}
+
+impl CoercionError {
+    fn new(reason: impl Into<String>) -> Self {
+        Self {
+            path: String::new(),
+            reason: reason.into(),
+        }
+    }
+
+    fn within(mut self, segment: String) -> Self {
+        self.path.insert_str(0, &segment);
+        self
+    }
+}
+
+impl fmt::Display for CoercionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.path.is_empty() {
+            write!(f, "{}", self.reason)
+        } else {
+            write!(f, "at `{}`: {}", self.path, self.reason)
+        }
+    }
+}
+
+/// The values of an event for each column of the table.
+#[derive(Clone, Debug)]
+pub(super) struct Row {
+    values: Vec<ColumnValue>,
+    size: usize,
+}
+
+impl Row {
+    /// Coerces the fields of an event for each column.
+    ///
+    /// Columns are filled with the top-level field named like them or, failing that, with the
+    /// field at the path given by their name, so that a `request.method` column is filled with the
+    /// `method` field of the `request` object.
+    pub(super) fn new(log: &LogEvent, columns: &[Column]) -> Result<Self, (usize, CoercionError)> {
+        let values = columns
+            .iter()
+            .enumerate()
+            .map(|(index, column)| {
+                let value = log
+                    .as_map()
+                    .and_then(|fields| fields.get(&column.name))
+                    .or_else(|| log.get(column.name.as_str()));
+                coerce(&column.ty, value).map_err(|error| (index, error))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let size = values.iter().map(ColumnValue::size).sum();
+        Ok(Self { values, size })
+    }
+}
+
+impl EncodedLength for Row {
+    fn encoded_length(&self) -> usize {
+        self.size
+    }
+}
+
+impl ByteSizeOf for Row {
+    fn allocated_bytes(&self) -> usize {
+        self.size
+    }
+}
+
+impl ColumnValue {
+    /// The approximate size of the value once serialized.
+    fn size(&self) -> usize {
+        match self {
+            Self::Null => 1,
+            Self::Int(_) | Self::Float(_) => 8,
+            Self::Bytes(bytes) => bytes.len() + 1,
+            Self::Array(values) => values.iter().map(Self::size).sum::<usize>() + 1,
+            Self::Map(entries) => {
+                entries
+                    .iter()
+                    .map(|(key, value)| key.size() + value.size())
+                    .sum::<usize>()
+                    + 1
+            }
+        }
+    }
+
+    fn int(&self) -> i128 {
+        match self {
+            Self::Int(int) => *int,
+            _ => 0,
+        }
+    }
+
+    fn float(&self) -> f64 {
+        match self {
+            Self::Float(float) => *float,
+            _ => 0.0,
+        }
+    }
+
+    fn bytes(&self) -> &[u8] {
+        match self {
+            Self::Bytes(bytes) => bytes,
+            _ => &[],
+        }
+    }
+}
+
+impl ColumnType {
+    /// The value of missing fields, for types that aren't `Nullable`.
+    fn default_value(&self) -> ColumnValue {
+        match self {
+            Self::Nullable(_) => ColumnValue::Null,
+            Self::LowCardinality(inner) => inner.default_value(),
+            Self::Float32 | Self::Float64 => ColumnValue::Float(0.0),
+            Self::String | Self::FixedString(_) => ColumnValue::Bytes(Bytes::new()),
+            Self::Uuid | Self::Ipv6 => ColumnValue::Bytes(Bytes::from_static(&[0; 16])),
+            Self::Enum8(variants) => ColumnValue::Int(variants.first().map_or(0, |v| v.1.into())),
+            Self::Enum16(variants) => ColumnValue::Int(variants.first().map_or(0, |v| v.1.into())),
+            Self::Array(_) => ColumnValue::Array(Vec::new()),
+            Self::Map(_, _) => ColumnValue::Map(Vec::new()),
+            _ => ColumnValue::Int(0),
+        }
+    }
+}
+
+/// Coerces a value for a column of type `ty`.
+///
+/// Missing and null values become `NULL` for `Nullable` columns, and the default value of the type
+/// otherwise, as ClickHouse does for `JSONEachRow`. Strings are parsed into numbers, booleans,
+/// dates, and addresses, as a lot of sources only produce strings.
+pub(super) fn coerce(ty: &ColumnType, value: Option<&Value>) -> Result<ColumnValue, CoercionError> {
+    let value = match value {
+        None | Some(Value::Null) => return Ok(ty.default_value()),
+        Some(value) => value,
+    };
+    let incompatible = || CoercionError::new(format!("cannot coerce {} value", value.kind_str()));
+    let out_of_range =
+        || CoercionError::new(format!("{} is out of range", value.to_string_lossy()));
+
+    let coerced = match ty {
+        ColumnType::Nullable(inner) | ColumnType::LowCardinality(inner) => {
+            return coerce(inner, Some(value))
+        }
+        ColumnType::Bool => match value {
+            Value::Boolean(boolean) => ColumnValue::Int((*boolean).into()),
+            Value::Integer(int @ (0 | 1)) => ColumnValue::Int((*int).into()),
+            Value::Bytes(_) => match value.to_string_lossy().trim() {
+                "true" | "1" => ColumnValue::Int(1),
+                "false" | "0" => ColumnValue::Int(0),
+                _ => return Err(incompatible()),
+            },
+            _ => return Err(incompatible()),
+        },
+        ColumnType::UInt8 => integer(value, 0, u8::MAX.into())?,
+        ColumnType::UInt16 => integer(value, 0, u16::MAX.into())?,
+        ColumnType::UInt32 => integer(value, 0, u32::MAX.into())?,
+        ColumnType::UInt64 => integer(value, 0, u64::MAX.into())?,
+        ColumnType::Int8 => integer(value, i8::MIN.into(), i8::MAX.into())?,
+        ColumnType::Int16 => integer(value, i16::MIN.into(), i16::MAX.into())?,
+        ColumnType::Int32 => integer(value, i32::MIN.into(), i32::MAX.into())?,
+        ColumnType::Int64 => integer(value, i64::MIN.into(), i64::MAX.into())?,
+        ColumnType::Float32 | ColumnType::Float64 => match value {
+            Value::Integer(int) => ColumnValue::Float(*int as f64),
+            Value::Float(float) => ColumnValue::Float(float.into_inner()),
+            Value::Bytes(_) => value
+                .to_string_lossy()
+                .trim()
+                .parse()
+                .map(ColumnValue::Float)
+                .map_err(|_| incompatible())?,
+            _ => return Err(incompatible()),
+        },
+        ColumnType::Decimal { precision, scale } => {
+            let scaled = match value {
+                Value::Integer(int) => i128::from(*int).checked_mul(10_i128.pow(*scale)),
+                Value::Float(float) => {
+                    let scaled = float.into_inner() * 10_f64.powi(*scale as i32);
+                    // Floats this large don't fit any decimal, whose precision is at most 38.
+                    (scaled.abs() < 1e38).then(|| scaled.round() as i128)
+                }
+                Value::Bytes(_) => Some(
+                    parse_decimal(value.to_string_lossy().trim(), *scale)
+                        .ok_or_else(incompatible)?,
+                ),
+                _ => return Err(incompatible()),
+            };
+            match scaled {
+                Some(scaled) if scaled.unsigned_abs() < 10_u128.pow(*precision) => {
+                    ColumnValue::Int(scaled)
+                }
+                _ => return Err(out_of_range()),
+            }
+        }
+        ColumnType::String => ColumnValue::Bytes(value.coerce_to_bytes()),
+        ColumnType::FixedString(length) => {
+            let bytes = value.coerce_to_bytes();
+            if bytes.len() > *length {
+                return Err(CoercionError::new(format!(
+                    "string of {} bytes is longer than {} bytes",
+                    bytes.len(),
+                    length
+                )));
+            }
+            ColumnValue::Bytes(bytes)
+        }
+        ColumnType::Uuid => {
+            let uuid = match value {
+                Value::Bytes(_) => uuid::Uuid::parse_str(value.to_string_lossy().trim())
+                    .map_err(|_| incompatible())?,
+                _ => return Err(incompatible()),
+            };
+            // UUIDs are serialized as two little-endian 64-bit integers, most significant first.
+            let (high, low) = uuid.as_u64_pair();
+            let mut bytes = BytesMut::with_capacity(16);
+            bytes.put_u64_le(high);
+            bytes.put_u64_le(low);
+            ColumnValue::Bytes(bytes.freeze())
+        }
+        ColumnType::Ipv4 => match address(value).ok_or_else(incompatible)? {
+            IpAddr::V4(address) => ColumnValue::Int(u32::from(address).into()),
+            IpAddr::V6(address) => match address.to_ipv4_mapped() {
+                Some(address) => ColumnValue::Int(u32::from(address).into()),
+                None => return Err(out_of_range()),
+            },
+        },
+        ColumnType::Ipv6 => {
+            let octets = match address(value).ok_or_else(incompatible)? {
+                IpAddr::V4(address) => address.to_ipv6_mapped().octets(),
+                IpAddr::V6(address) => address.octets(),
+            };
+            ColumnValue::Bytes(Bytes::copy_from_slice(&octets))
+        }
+        ColumnType::Date | ColumnType::Date32 => {
+            let days = match value {
+                // Integers are days since the Unix epoch, as for ClickHouse.
+                Value::Integer(days) => *days,
+                _ => {
+                    let timestamp = timestamp(value).ok_or_else(incompatible)?;
+                    timestamp
+                        .naive_utc()
+                        .date()
+                        .signed_duration_since(epoch())
+                        .num_days()
+                }
+            };
+            match ty {
+                ColumnType::Date => integer(&Value::Integer(days), 0, u16::MAX.into()),
+                _ => integer(&Value::Integer(days), -25_567, 120_529),
+            }
+            .map_err(|_| out_of_range())?
+        }
+        ColumnType::DateTime(_) => {
+            let seconds = timestamp(value).ok_or_else(incompatible)?.timestamp();
+            integer(&Value::Integer(seconds), 0, u32::MAX.into()).map_err(|_| out_of_range())?
+        }
+        ColumnType::DateTime64(precision, _) => {
+            let timestamp = timestamp(value).ok_or_else(incompatible)?;
+            let ticks = i128::from(timestamp.timestamp()) * 10_i128.pow(*precision)
+                + i128::from(timestamp.timestamp_subsec_nanos()) / 10_i128.pow(9 - precision);
+            i64::try_from(ticks).map_err(|_| out_of_range())?;
+            ColumnValue::Int(ticks)
+        }
+        ColumnType::Enum8(variants) => enum_value(value, variants)?,
+        ColumnType::Enum16(variants) => enum_value(value, variants)?,
+        ColumnType::Array(inner) => match value {
+            Value::Array(values) => ColumnValue::Array(
+                values
+                    .iter()
+                    .enumerate()
+                    .map(|(index, value)| {
+                        coerce(inner, Some(value))
+                            .map_err(|error| error.within(format!("[{}]", index)))
+                    })
+                    .collect::<Result<_, _>>()?,
+            ),
+            _ => return Err(incompatible()),
+        },
+        ColumnType::Map(key_type, value_type) => match value {
+            Value::Object(fields) => ColumnValue::Map(
+                fields
+                    .iter()
+                    .map(|(key, value)| {
+                        let within = |error: CoercionError| error.within(format!(".{}", key));
+                        Ok((
+                            coerce(key_type, Some(&Value::from(key.as_str()))).map_err(within)?,
+                            coerce(value_type, Some(value)).map_err(within)?,
+                        ))
+                    })
+                    .collect::<Result<_, _>>()?,
+            ),
+            _ => return Err(incompatible()),
+        },
+    };
+    Ok(coerced)
+}
+
+fn integer(value: &Value, min: i128, max: i128) -> Result<ColumnValue, CoercionError> {
+    let int = match value {
+        Value::Integer(int) => i128::from(*int),
+        Value::Float(float) if float.fract() == 0.0 && float.abs() < 1e38 => {
+            float.into_inner() as i128
+        }
+        Value::Boolean(boolean) => (*boolean).into(),
+        Value::Bytes(_) => value.to_string_lossy().trim().parse().map_err(|_| {
+            CoercionError::new(format!(
+                "cannot coerce `{}` to an integer",
+                value.to_string_lossy()
+            ))
+        })?,
+        _ => {
+            return Err(CoercionError::new(format!(
+                "cannot coerce {} value",
+                value.kind_str()
+            )))
+        }
+    };
+    if (min..=max).contains(&int) {
+        Ok(ColumnValue::Int(int))
+    } else {
+        Err(CoercionError::new(format!("{} is out of range", int)))
+    }
+}
+
+/// Parses a decimal number exactly, scaled by `10^scale`. Digits past the scale are truncated.
+fn parse_decimal(string: &str, scale: u32) -> Option<i128> {
+    let (negative, digits) = match string.strip_prefix('-') {
+        Some(digits) => (true, digits),
+        None => (false, string.strip_prefix('+').unwrap_or(string)),
+    };
+    let (integer, fraction) = digits.split_once('.').unwrap_or((digits, ""));
+    if integer.is_empty() && fraction.is_empty()
+        || !integer
+            .bytes()
+            .chain(fraction.bytes())
+            .all(|b| b.is_ascii_digit())
+    {
+        return None;
+    }
+
+    let fraction = fraction.bytes().chain(std::iter::repeat(b'0'));
+    integer
+        .bytes()
+        .chain(fraction.take(scale as usize))
+        .try_fold(0_i128, |scaled, digit| {
+            scaled.checked_mul(10)?.checked_add((digit - b'0').into())
+        })
+        .map(|scaled| if negative { -scaled } else { scaled })
+}
+
+fn address(value: &Value) -> Option<IpAddr> {
+    match value {
+        Value::Bytes(_) => value.to_string_lossy().trim().parse().ok(),
+        Value::Integer(int) => u32::try_from(*int)
+            .ok()
+            .map(|int| Ipv4Addr::from(int).into()),
+        _ => None,
+    }
+}
+
+fn epoch() -> NaiveDate {
+    NaiveDate::from_ymd_opt(1970, 1, 1).expect("valid date")
+}
+
+/// Converts timestamps, RFC 3339 strings, `YYYY-MM-DD hh:mm:ss` and `YYYY-MM-DD` strings in UTC,
+/// and numbers as seconds since the Unix epoch.
+fn timestamp(value: &Value) -> Option<DateTime<Utc>> {
+    match value {
+        Value::Timestamp(timestamp) => Some(*timestamp),
+        Value::Integer(seconds) => Utc.timestamp_opt(*seconds, 0).single(),
+        Value::Float(seconds) => {
+            let seconds = seconds.into_inner();
+            let nanos = (seconds.fract() * 1e9).round() as u32;
+            (seconds.abs() < 1e15)
+                .then(|| Utc.timestamp_opt(seconds.floor() as i64, nanos).single())
+                .flatten()
+        }
+        Value::Bytes(_) => {
+            let string = value.to_string_lossy();
+            let string = string.trim();
+            DateTime::parse_from_rfc3339(string)
+                .map(|timestamp| timestamp.with_timezone(&Utc))
+                .or_else(|_| {
+                    NaiveDateTime::parse_from_str(string, "%Y-%m-%d %H:%M:%S%.f")
+                        .map(|timestamp| Utc.from_utc_datetime(&timestamp))
+                })
+                .or_else(|_| {
+                    NaiveDate::parse_from_str(string, "%Y-%m-%d").map(|date| {
+                        Utc.from_utc_datetime(&date.and_hms_opt(0, 0, 0).expect("valid time"))
+                    })
+                })
+                .ok()
+        }
+        _ => None,
+    }
+}
+
+fn enum_value<T>(value: &Value, variants: &[(String, T)]) -> Result<ColumnValue, CoercionError>
+where
+    T: Copy + Into<i128>,
+{
+    let found = match value {
+        Value::Bytes(bytes) => variants
+            .iter()
+            .find(|(variant, _)| variant.as_bytes() == bytes.as_ref()),
+        Value::Integer(int) => variants
+            .iter()
+            .find(|(_, variant)| (*variant).into() == i128::from(*int)),
+        _ => {
+            return Err(CoercionError::new(format!(
+                "cannot coerce {} value",
+                value.kind_str()
+            )))
+        }
+    };
+    found
+        .map(|(_, variant)| ColumnValue::Int((*variant).into()))
+        .ok_or_else(|| {
+            CoercionError::new(format!(
+                "`{}` is not a value of the enum",
+                value.to_string_lossy()
+            ))
+        })
+}
+
+/// Writes the unsigned LEB128 integers ClickHouse prefixes strings and collections with.
+fn put_var_uint(buffer: &mut BytesMut, mut value: u64) {
+    while value >= 0x80 {
+        buffer.put_u8((value as u8) | 0x80);
+        value >>= 7;
+    }
+    buffer.put_u8(value as u8);
+}
+
+fn put_string(buffer: &mut BytesMut, bytes: &[u8]) {
+    put_var_uint(buffer, bytes.len() as u64);
+    buffer.put_slice(bytes);
+}
+
+/// Writes a value of a type that is serialized the same way in `RowBinary` and `Native`.
+fn put_scalar(buffer: &mut BytesMut, ty: &ColumnType, value: &ColumnValue) {
+    match ty {
+        ColumnType::Bool | ColumnType::UInt8 => buffer.put_u8(value.int() as u8),
+        ColumnType::UInt16 | ColumnType::Date => buffer.put_u16_le(value.int() as u16),
+        ColumnType::UInt32 | ColumnType::DateTime(_) | ColumnType::Ipv4 => {
+            buffer.put_u32_le(value.int() as u32)
+        }
+        ColumnType::UInt64 => buffer.put_u64_le(value.int() as u64),
+        ColumnType::Int8 | ColumnType::Enum8(_) => buffer.put_i8(value.int() as i8),
+        ColumnType::Int16 | ColumnType::Enum16(_) => buffer.put_i16_le(value.int() as i16),
+        ColumnType::Int32 | ColumnType::Date32 => buffer.put_i32_le(value.int() as i32),
+        ColumnType::Int64 | ColumnType::DateTime64(_, _) => buffer.put_i64_le(value.int() as i64),
+        ColumnType::Decimal { precision, .. } => match precision {
+            1..=9 => buffer.put_i32_le(value.int() as i32),
+            10..=18 => buffer.put_i64_le(value.int() as i64),
+            _ => buffer.put_i128_le(value.int()),
+        },
+        ColumnType::Float32 => buffer.put_f32_le(value.float() as f32),
+        ColumnType::Float64 => buffer.put_f64_le(value.float()),
+        ColumnType::String => put_string(buffer, value.bytes()),
+        ColumnType::FixedString(length) => {
+            let bytes = value.bytes();
+            buffer.put_slice(bytes);
+            buffer.put_bytes(0, length.saturating_sub(bytes.len()));
+        }
+        ColumnType::Uuid | ColumnType::Ipv6 => buffer.put_slice(value.bytes()),
+        ColumnType::Nullable(_)
+        | ColumnType::LowCardinality(_)
+        | ColumnType::Array(_)
+        | ColumnType::Map(_, _) => unreachable!("composite types aren't scalars"),
+    }
+}
+
+/// Writes the rows of a batch in the `RowBinary` format.
+pub(super) fn write_row_binary(buffer: &mut BytesMut, columns: &[Column], rows: &[Row]) {
+    for row in rows {
+        for (column, value) in columns.iter().zip(&row.values) {
+            put_row_binary(buffer, &column.ty, value);
+        }
+    }
+}
+
+fn put_row_binary(buffer: &mut BytesMut, ty: &ColumnType, value: &ColumnValue) {
+    match (ty, value) {
+        (ColumnType::Nullable(_), ColumnValue::Null) => buffer.put_u8(1),
+        (ColumnType::Nullable(inner), value) => {
+            buffer.put_u8(0);
+            put_row_binary(buffer, inner, value);
+        }
+        (ColumnType::LowCardinality(inner), value) => put_row_binary(buffer, inner, value),
+        (ColumnType::Array(inner), ColumnValue::Array(values)) => {
+            put_var_uint(buffer, values.len() as u64);
+            for value in values {
+                put_row_binary(buffer, inner, value);
+            }
+        }
+        (ColumnType::Map(key_type, value_type), ColumnValue::Map(entries)) => {
+            put_var_uint(buffer, entries.len() as u64);
+            for (key, value) in entries {
+                put_row_binary(buffer, key_type, key);
+                put_row_binary(buffer, value_type, value);
+            }
+        }
+        (ty, value) => put_scalar(buffer, ty, value),
+    }
+}
+
+/// Writes the rows of a batch as a single block in the `Native` format.
+pub(super) fn write_native(buffer: &mut BytesMut, columns: &[Column], rows: &[Row]) {
+    put_var_uint(buffer, columns.len() as u64);
+    put_var_uint(buffer, rows.len() as u64);
+    for (index, column) in columns.iter().enumerate() {
+        put_string(buffer, column.name.as_bytes());
+        put_string(buffer, column.ty.native().to_string().as_bytes());
+        let values = rows
+            .iter()
+            .map(|row| &row.values[index])
+            .collect::<Vec<_>>();
+        put_native_column(buffer, &column.ty, &values);
+    }
+}
+
+fn put_native_column(buffer: &mut BytesMut, ty: &ColumnType, values: &[&ColumnValue]) {
+    match ty {
+        ColumnType::Nullable(inner) => {
+            for value in values {
+                buffer.put_u8((**value == ColumnValue::Null).into());
+            }
+            // The nested column has a value for every row, with any value for the null ones.
+            let default = inner.default_value();
+            let values = values
+                .iter()
+                .map(|value| match value {
+                    ColumnValue::Null => &default,
+                    value => value,
+                })
+                .collect::<Vec<_>>();
+            put_native_column(buffer, inner, &values);
+        }
+        ColumnType::LowCardinality(inner) => put_native_column(buffer, inner, values),
+        ColumnType::Array(inner) => {
+            let elements = values
+                .iter()
+                .map(|value| match value {
+                    ColumnValue::Array(elements) => elements.as_slice(),
+                    _ => &[],
+                })
+                .collect::<Vec<_>>();
+            put_offsets(buffer, elements.iter().map(|elements| elements.len()));
+            let elements = elements.into_iter().flatten().collect::<Vec<_>>();
+            put_native_column(buffer, inner, &elements);
+        }
+        ColumnType::Map(key_type, value_type) => {
+            let entries = values
+                .iter()
+                .map(|value| match value {
+                    ColumnValue::Map(entries) => entries.as_slice(),
+                    _ => &[],
+                })
+                .collect::<Vec<_>>();
+            put_offsets(buffer, entries.iter().map(|entries| entries.len()));
+            let (keys, values): (Vec<_>, Vec<_>) = entries
+                .iter()
+                .flat_map(|entries| entries.iter().map(|(key, value)| (key, value)))
+                .unzip();
+            put_native_column(buffer, key_type, &keys);
+            put_native_column(buffer, value_type, &values);
+        }
+        ty => {
+            for value in values {
+                put_scalar(buffer, ty, value);
+            }
+        }
+    }
+}
+
+/// Writes the offsets of the ends of each row's elements in a nested column.
+fn put_offsets(buffer: &mut BytesMut, lengths: impl Iterator<Item = usize>) {
+    let mut offset = 0;
+    for length in lengths {
+        offset += length as u64;
+        buffer.put_u64_le(offset);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn coerce_to(name: &str, value: impl Into<Value>) -> Result<ColumnValue, CoercionError> {
+        coerce(&ColumnType::parse(name).unwrap(), Some(&value.into()))
+    }
+
+    fn column(name: &str, ty: &str) -> Column {
+        Column {
+            name: name.to_owned(),
+            ty: ColumnType::parse(ty).unwrap(),
+        }
+    }
+
+    #[test]
+    fn coerces_numbers() {
+        assert_eq!(coerce_to("UInt8", 255), Ok(ColumnValue::Int(255)));
+        assert_eq!(coerce_to("Int16", "-12"), Ok(ColumnValue::Int(-12)));
+        assert_eq!(coerce_to("UInt32", 3.0), Ok(ColumnValue::Int(3)));
+        assert_eq!(coerce_to("Float32", 2), Ok(ColumnValue::Float(2.0)));
+        assert_eq!(coerce_to("Bool", "true"), Ok(ColumnValue::Int(1)));
+        assert_eq!(
+            coerce_to("UInt8", 256).unwrap_err().to_string(),
+            "256 is out of range"
+        );
+        assert_eq!(coerce_to("Int32", true), Ok(ColumnValue::Int(1)),);
+        assert_eq!(
+            coerce_to("UInt64", "abc").unwrap_err().to_string(),
+            "cannot coerce `abc` to an integer"
+        );
+    }
+
+    #[test]
+    fn coerces_decimals() {
+        assert_eq!(
+            coerce_to("Decimal(9, 2)", "12.345"),
+            Ok(ColumnValue::Int(1234))
+        );
+        assert_eq!(
+            coerce_to("Decimal(9, 2)", "-0.5"),
+            Ok(ColumnValue::Int(-50))
+        );
+        assert_eq!(coerce_to("Decimal(9, 2)", 3), Ok(ColumnValue::Int(300)));
+        assert_eq!(coerce_to("Decimal(9, 2)", 1.25), Ok(ColumnValue::Int(125)));
+        assert!(coerce_to("Decimal(4, 2)", "100").is_err());
+        assert!(coerce_to("Decimal(9, 2)", "1.2.3").is_err());
+    }
+
+    #[test]
+    fn coerces_timestamps() {
+        let timestamp = Utc.timestamp_opt(1_600_000_000, 123_456_789).unwrap();
+
+        assert_eq!(
+            coerce_to("DateTime", timestamp),
+            Ok(ColumnValue::Int(1_600_000_000))
+        );
+        assert_eq!(
+            coerce_to("DateTime64(3, 'UTC')", timestamp),
+            Ok(ColumnValue::Int(1_600_000_000_123))
+        );
+        assert_eq!(
+            coerce_to("DateTime64(6)", "2020-09-13T12:26:40.123456Z"),
+            Ok(ColumnValue::Int(1_600_000_000_123_456))
+        );
+        assert_eq!(
+            coerce_to("DateTime", "2020-09-13 12:26:40"),
+            Ok(ColumnValue::Int(1_600_000_000))
+        );
+        assert_eq!(coerce_to("Date", "1970-01-11"), Ok(ColumnValue::Int(10)));
+        assert_eq!(coerce_to("Date32", "1969-12-31"), Ok(ColumnValue::Int(-1)));
+        assert!(coerce_to("Date", "1969-12-31").is_err());
+        assert!(coerce_to("DateTime", "yesterday").is_err());
+    }
+
+    #[test]
+    fn coerces_strings_and_addresses() {
+        assert_eq!(
+            coerce_to("LowCardinality(String)", 42),
+            Ok(ColumnValue::Bytes("42".into()))
+        );
+        assert!(coerce_to("FixedString(2)", "abc").is_err());
+        assert_eq!(
+            coerce_to("IPv4", "10.0.0.1"),
+            Ok(ColumnValue::Int(0x0a00_0001))
+        );
+        assert_eq!(
+            coerce_to("IPv6", "10.0.0.1"),
+            Ok(ColumnValue::Bytes(Bytes::copy_from_slice(
+                &Ipv4Addr::new(10, 0, 0, 1).to_ipv6_mapped().octets()
+            )))
+        );
+        assert_eq!(
+            coerce_to("UUID", "00112233-4455-6677-8899-aabbccddeeff"),
+            Ok(ColumnValue::Bytes(Bytes::from_static(&[
+                0x77, 0x66, 0x55, 0x44, 0x33, 0x22, 0x11, 0x00, 0xff, 0xee, 0xdd, 0xcc, 0xbb, 0xaa,
+                0x99, 0x88
+            ])))
+        );
+        assert_eq!(
+            coerce_to("Enum8('info' = 1, 'error' = 2)", "error"),
+            Ok(ColumnValue::Int(2))
+        );
+        assert_eq!(
+            coerce_to("Enum8('info' = 1, 'error' = 2)", "debug")
+                .unwrap_err()
+                .to_string(),
+            "`debug` is not a value of the enum"
+        );
+    }
+
+    #[test]
+    fn coerces_collections_with_paths() {
+        assert_eq!(
+            coerce_to("Array(UInt8)", vec![Value::from(1), Value::from("2")]),
+            Ok(ColumnValue::Array(vec![
+                ColumnValue::Int(1),
+                ColumnValue::Int(2)
+            ]))
+        );
+        assert_eq!(
+            coerce_to(
+                "Array(Array(UInt8))",
+                vec![Value::from(vec![Value::from(1), Value::from(300)])]
+            )
+            .unwrap_err()
+            .to_string(),
+            "at `[0][1]`: 300 is out of range"
+        );
+
+        let mut labels = LogEvent::default();
+        labels.insert("count", 1);
+        labels.insert("replicas", "three");
+        assert_eq!(
+            coerce_to("Map(String, UInt8)", labels.value().clone())
+                .unwrap_err()
+                .to_string(),
+            "at `.replicas`: cannot coerce `three` to an integer"
+        );
+    }
+
+    #[test]
+    fn coerces_missing_values_to_defaults() {
+        let ty = ColumnType::parse("Nullable(UInt8)").unwrap();
+        assert_eq!(coerce(&ty, None), Ok(ColumnValue::Null));
+        assert_eq!(coerce(&ty, Some(&Value::Null)), Ok(ColumnValue::Null));
+
+        let ty = ColumnType::parse("Array(String)").unwrap();
+        assert_eq!(coerce(&ty, None), Ok(ColumnValue::Array(Vec::new())));
+    }
+
+    #[test]
+    fn fills_columns_from_fields() {
+        let mut log = LogEvent::from("hello");
+        log.insert("request.method", "GET");
+        log.insert("\"status.code\"", 200);
+        let columns = [
+            column("message", "String"),
+            column("request.method", "LowCardinality(String)"),
+            column("status.code", "UInt16"),
+            column("missing", "Nullable(String)"),
+        ];
+
+        let row = Row::new(&log, &columns).unwrap();
+        assert_eq!(
+            row.values,
+            vec![
+                ColumnValue::Bytes("hello".into()),
+                ColumnValue::Bytes("GET".into()),
+                ColumnValue::Int(200),
+                ColumnValue::Null,
+            ]
+        );
+
+        let columns = [column("message", "UInt8")];
+        let (index, error) = Row::new(&log, &columns).unwrap_err();
+        assert_eq!(index, 0);
+        assert_eq!(error.to_string(), "cannot coerce `hello` to an integer");
+    }
+
+    #[test]
+    fn writes_row_binary() {
+        let columns = [
+            column("id", "UInt16"),
+            column("name", "Nullable(String)"),
+            column("tags", "Array(LowCardinality(String))"),
+        ];
+        let mut log = LogEvent::default();
+        log.insert("id", 258);
+        log.insert("tags", vec![Value::from("a"), Value::from("bc")]);
+        let rows = [Row::new(&log, &columns).unwrap()];
+
+        let mut buffer = BytesMut::new();
+        write_row_binary(&mut buffer, &columns, &rows);
+        assert_eq!(buffer.as_ref(), &[2, 1, 1, 2, 1, b'a', 2, b'b', b'c'][..]);
+    }
+
+    #[test]
+    fn writes_native() {
+        let columns = [
+            column("name", "Nullable(String)"),
+            column("tags", "Map(LowCardinality(String), UInt8)"),
+        ];
+        let mut first = LogEvent::default();
+        first.insert("name", "x");
+        first.insert("tags.a", 1);
+        first.insert("tags.b", 2);
+        let mut second = LogEvent::default();
+        second.insert("tags.c", 3);
+        let rows = [
+            Row::new(&first, &columns).unwrap(),
+            Row::new(&second, &columns).unwrap(),
+        ];
+
+        let mut buffer = BytesMut::new();
+        write_native(&mut buffer, &columns, &rows);
+
+        let mut expected = vec![2, 2];
+        expected.extend(b"\x04name\x10Nullable(String)");
+        // The null map, then the strings, with an empty one for the null.
+        expected.extend([0, 1, 1, b'x', 0]);
+        expected.extend(b"\x04tags\x12Map(String, UInt8)");
+        // The offsets of the entries of each row, then the keys, then the values.
+        expected.extend(2_u64.to_le_bytes());
+        expected.extend(3_u64.to_le_bytes());
+        expected.extend([1, b'a', 1, b'b', 1, b'c']);
+        expected.extend([1, 2, 3]);
+        assert_eq!(buffer.as_ref(), expected.as_slice());
+    }
+}
@@ -0,0 +1,543 @@
+//! Fetches the columns of the table, and parses their types, for the binary insert formats.
+use std::fmt;
+
+use http::{Request, StatusCode};
+use hyper::Body;
+use serde::Deserialize;
+use snafu::{ResultExt, Snafu};
+
+use super::{config::Format, ClickhouseConfig};
+use crate::http::HttpClient;
+
+#[derive(Debug, Snafu)]
+pub(super) enum SchemaError {
+    #[snafu(display("failed to describe table {}: {}", table, source))]
+    Request {
+        table: String,
+        source: crate::http::HttpError,
+    },
+    #[snafu(display("failed to describe table {}: {}", table, source))]
+    ReadBody { table: String, source: hyper::Error },
+    #[snafu(display(
+        "failed to describe table {}: unexpected status {}: {}",
+        table,
+        status,
+        body
+    ))]
+    UnexpectedStatus {
+        table: String,
+        status: StatusCode,
+        body: String,
+    },
+    #[snafu(display("invalid description of table {}: {}", table, source))]
+    InvalidDescription {
+        table: String,
+        source: serde_json::Error,
+    },
+    #[snafu(display(
+        "column `{}` has type `{}`, which the `{}` format doesn't support; use the `json_each_row` format instead",
+        column,
+        ty,
+        format
+    ))]
+    UnsupportedType {
+        column: String,
+        ty: String,
+        format: Format,
+    },
+}
+
+/// A column that can be inserted into.
+#[derive(Clone, Debug, PartialEq)]
+pub(super) struct Column {
+    pub(super) name: String,
+    pub(super) ty: ColumnType,
+}
+
+/// The types of columns that event values are coerced to.
+#[derive(Clone, Debug, PartialEq)]
+pub(super) enum ColumnType {
+    Bool,
+    UInt8,
+    UInt16,
+    UInt32,
+    UInt64,
+    Int8,
+    Int16,
+    Int32,
+    Int64,
+    Float32,
+    Float64,
+    Decimal { precision: u32, scale: u32 },
+    String,
+    FixedString(usize),
+    Uuid,
+    Ipv4,
+    Ipv6,
+    Date,
+    Date32,
+    DateTime(Option<String>),
+    DateTime64(u32, Option<String>),
+    Enum8(Vec<(String, i8)>),
+    Enum16(Vec<(String, i16)>),
+    Nullable(Box<ColumnType>),
+    LowCardinality(Box<ColumnType>),
+    Array(Box<ColumnType>),
+    Map(Box<ColumnType>, Box<ColumnType>),
+}
+
+impl ColumnType {
+    /// Parses a type as ClickHouse names it, such as `Array(LowCardinality(String))`.
+    ///
+    /// Returns `None` for types that aren't supported.
+    pub(super) fn parse(name: &str) -> Option<Self> {
+        let mut parser = Parser { input: name };
+        let ty = parser.parse_type()?;
+        parser.input.trim().is_empty().then_some(ty)
+    }
+
+    /// The type of the column sent in `Native` blocks.
+    ///
+    /// `LowCardinality` columns are sent as their inner type, which the server converts with the
+    /// `low_cardinality_allow_in_native_format` setting disabled.
+    pub(super) fn native(&self) -> Self {
+        match self {
+            Self::LowCardinality(inner) => inner.native(),
+            Self::Nullable(inner) => Self::Nullable(Box::new(inner.native())),
+            Self::Array(inner) => Self::Array(Box::new(inner.native())),
+            Self::Map(key, value) => Self::Map(Box::new(key.native()), Box::new(value.native())),
+            ty => ty.clone(),
+        }
+    }
+}
+
+impl fmt::Display for ColumnType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Bool => write!(f, "Bool"),
+            Self::UInt8 => write!(f, "UInt8"),
+            Self::UInt16 => write!(f, "UInt16"),
+            Self::UInt32 => write!(f, "UInt32"),
+            Self::UInt64 => write!(f, "UInt64"),
+            Self::Int8 => write!(f, "Int8"),
+            Self::Int16 => write!(f, "Int16"),
+            Self::Int32 => write!(f, "Int32"),
+            Self::Int64 => write!(f, "Int64"),
+            Self::Float32 => write!(f, "Float32"),
+            Self::Float64 => write!(f, "Float64"),
+            Self::Decimal { precision, scale } => write!(f, "Decimal({}, {})", precision, scale),
+            Self::String => write!(f, "String"),
+            Self::FixedString(length) => write!(f, "FixedString({})", length),
+            Self::Uuid => write!(f, "UUID"),
+            Self::Ipv4 => write!(f, "IPv4"),
+            Self::Ipv6 => write!(f, "IPv6"),
+            Self::Date => write!(f, "Date"),
+            Self::Date32 => write!(f, "Date32"),
+            Self::DateTime(None) => write!(f, "DateTime"),
+            Self::DateTime(Some(timezone)) => write!(f, "DateTime({})", quote(timezone)),
+            Self::DateTime64(precision, None) => write!(f, "DateTime64({})", precision),
+            Self::DateTime64(precision, Some(timezone)) => {
+                write!(f, "DateTime64({}, {})", precision, quote(timezone))
+            }
+            Self::Enum8(variants) => write_enum(f, "Enum8", variants),
+            Self::Enum16(variants) => write_enum(f, "Enum16", variants),
+            Self::Nullable(inner) => write!(f, "Nullable({})", inner),
+            Self::LowCardinality(inner) => write!(f, "LowCardinality({})", inner),
+            Self::Array(inner) => write!(f, "Array({})", inner),
+            Self::Map(key, value) => write!(f, "Map({}, {})", key, value),
+        }
+    }
+}
+
+fn write_enum<T: fmt::Display>(
+    f: &mut fmt::Formatter<'_>,
+    name: &str,
+    variants: &[(String, T)],
+) -> fmt::Result {
+    write!(f, "{}(", name)?;
+    for (index, (variant, value)) in variants.iter().enumerate() {
+        if index > 0 {
+            write!(f, ", ")?;
+        }
+        write!(f, "{} = {}", quote(variant), value)?;
+    }
+    write!(f, ")")
+}
+
+fn quote(string: &str) -> String {
+    format!("'{}'", string.replace('\\', "\\\\").replace('\'', "\\'"))
+}
+
+struct Parser<'a> {
+    input: &'a str,
+}
+
+impl<'a> Parser<'a> {
+    fn parse_type(&mut self) -> Option<ColumnType> {
+        let name = self.identifier()?;
+        let ty = match name {
+            "Bool" | "Boolean" => ColumnType::Bool,
+            "UInt8" => ColumnType::UInt8,
+            "UInt16" => ColumnType::UInt16,
+            "UInt32" => ColumnType::UInt32,
+            "UInt64" => ColumnType::UInt64,
+            "Int8" => ColumnType::Int8,
+            "Int16" => ColumnType::Int16,
+            "Int32" => ColumnType::Int32,
+            "Int64" => ColumnType::Int64,
+            "Float32" => ColumnType::Float32,
+            "Float64" => ColumnType::Float64,
+            "Decimal" => {
+                self.expect('(')?;
+                let precision = self.number()?;
+                self.expect(',')?;
+                let scale = self.number()?;
+                self.expect(')')?;
+                decimal(precision, scale)?
+            }
+            "Decimal32" | "Decimal64" | "Decimal128" => {
+                let precision = match name {
+                    "Decimal32" => 9,
+                    "Decimal64" => 18,
+                    _ => 38,
+                };
+                self.expect('(')?;
+                let scale = self.number()?;
+                self.expect(')')?;
+                decimal(precision, scale)?
+            }
+            "String" => ColumnType::String,
+            "FixedString" => {
+                self.expect('(')?;
+                let length = self.number()?;
+                self.expect(')')?;
+                ColumnType::FixedString(length as usize)
+            }
+            "UUID" => ColumnType::Uuid,
+            "IPv4" => ColumnType::Ipv4,
+            "IPv6" => ColumnType::Ipv6,
+            "Date" => ColumnType::Date,
+            "Date32" => ColumnType::Date32,
+            "DateTime" => {
+                let timezone = if self.eat('(') {
+                    let timezone = self.string()?;
+                    self.expect(')')?;
+                    Some(timezone)
+                } else {
+                    None
+                };
+                ColumnType::DateTime(timezone)
+            }
+            "DateTime64" => {
+                self.expect('(')?;
+                let precision = self.number()?;
+                let timezone = if self.eat(',') {
+                    Some(self.string()?)
+                } else {
+                    None
+                };
+                self.expect(')')?;
+                if precision > 9 {
+                    return None;
+                }
+                ColumnType::DateTime64(precision, timezone)
+            }
+            "Enum8" => ColumnType::Enum8(self.enum_variants()?),
+            "Enum16" => ColumnType::Enum16(self.enum_variants()?),
+            "Nullable" | "LowCardinality" | "Array" => {
+                self.expect('(')?;
+                let inner = Box::new(self.parse_type()?);
+                self.expect(')')?;
+                match name {
+                    "Nullable" => ColumnType::Nullable(inner),
+                    "LowCardinality" => ColumnType::LowCardinality(inner),
+                    _ => ColumnType::Array(inner),
+                }
+            }
+            "Map" => {
+                self.expect('(')?;
+                let key = Box::new(self.parse_type()?);
+                self.expect(',')?;
+                let value = Box::new(self.parse_type()?);
+                self.expect(')')?;
+                ColumnType::Map(key, value)
+            }
+            _ => return None,
+        };
+        Some(ty)
+    }
+
+    fn enum_variants<T: TryFrom<i64>>(&mut self) -> Option<Vec<(String, T)>> {
+        self.expect('(')?;
+        let mut variants = Vec::new();
+        loop {
+            let variant = self.string()?;
+            self.expect('=')?;
+            let value = self.integer()?.try_into().ok()?;
+            variants.push((variant, value));
+            if !self.eat(',') {
+                break;
+            }
+        }
+        self.expect(')')?;
+        Some(variants)
+    }
+
+    fn identifier(&mut self) -> Option<&'a str> {
+        self.input = self.input.trim_start();
+        let end = self
+            .input
+            .find(|c: char| !c.is_ascii_alphanumeric() && c != '_')
+            .unwrap_or(self.input.len());
+        let (identifier, rest) = self.input.split_at(end);
+        self.input = rest;
+        (!identifier.is_empty()).then_some(identifier)
+    }
+
+    fn number(&mut self) -> Option<u32> {
+        self.input = self.input.trim_start();
+        let end = self
+            .input
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(self.input.len());
+        let (number, rest) = self.input.split_at(end);
+        self.input = rest;
+        number.parse().ok()
+    }
+
+    fn integer(&mut self) -> Option<i64> {
+        let negative = self.eat('-');
+        let number = i64::from(self.number()?);
+        Some(if negative { -number } else { number })
+    }
+
+    /// Parses a single-quoted string, with backslash escapes.
+    fn string(&mut self) -> Option<String> {
+        self.expect('\'')?;
+        let mut string = String::new();
+        let mut chars = self.input.char_indices();
+        while let Some((index, c)) = chars.next() {
+            match c {
+                '\\' => string.push(chars.next()?.1),
+                '\'' => {
+                    self.input = &self.input[index + 1..];
+                    return Some(string);
+                }
+                c => string.push(c),
+            }
+        }
+        None
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        self.input = self.input.trim_start();
+        match self.input.strip_prefix(c) {
+            Some(rest) => {
+                self.input = rest;
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn expect(&mut self, c: char) -> Option<()> {
+        self.eat(c).then_some(())
+    }
+}
+
+fn decimal(precision: u32, scale: u32) -> Option<ColumnType> {
+    // `Decimal256` values don't fit the integers values are coerced to.
+    ((1..=38).contains(&precision) && scale <= precision)
+        .then_some(ColumnType::Decimal { precision, scale })
+}
+
+#[derive(Deserialize)]
+struct ColumnDescription {
+    name: String,
+    #[serde(rename = "type")]
+    ty: String,
+    #[serde(default)]
+    default_type: String,
+}
+
+/// Fetches the columns of the table that can be inserted into, in their order in the table.
+///
+/// `MATERIALIZED`, `ALIAS` and `EPHEMERAL` columns are left out, as the server computes them.
+pub(super) async fn fetch_columns(
+    client: &HttpClient,
+    config: &ClickhouseConfig,
+    format: Format,
+) -> Result<Vec<Column>, SchemaError> {
+    let table = config.qualified_table();
+    let query = url::form_urlencoded::Serializer::new(String::new())
+        .append_pair(
+            "query",
+            &format!("DESCRIBE TABLE {} FORMAT JSONEachRow", table),
+        )
+        .finish();
+    let uri = format!("{}/?{}", config.endpoint.with_default_parts(), query);
+    let mut request = Request::get(uri).body(Body::empty()).unwrap();
+    if let Some(auth) = &config.auth {
+        auth.apply(&mut request);
+    }
+
+    let response = client
+        .send(request)
+        .await
+        .context(RequestSnafu { table: &table })?;
+    let status = response.status();
+    let body = hyper::body::to_bytes(response.into_body())
+        .await
+        .context(ReadBodySnafu { table: &table })?;
+    if status != StatusCode::OK {
+        return Err(SchemaError::UnexpectedStatus {
+            table,
+            status,
+            body: String::from_utf8_lossy(&body).trim().to_owned(),
+        });
+    }
+
+    parse_description(&body, format).map_err(|error| match error {
+        DescriptionError::Json(source) => SchemaError::InvalidDescription { table, source },
+        DescriptionError::Unsupported(error) => error,
+    })
+}
+
+enum DescriptionError {
+    Json(serde_json::Error),
+    Unsupported(SchemaError),
+}
+
+fn parse_description(body: &[u8], format: Format) -> Result<Vec<Column>, DescriptionError> {
+    let mut columns = Vec::new();
+    for line in body.split(|byte| *byte == b'\n') {
+        if line.iter().all(u8::is_ascii_whitespace) {
+            continue;
+        }
+        let description: ColumnDescription =
+            serde_json::from_slice(line).map_err(DescriptionError::Json)?;
+        if !matches!(description.default_type.as_str(), "" | "DEFAULT") {
+            continue;
+        }
+
+        let ty = ColumnType::parse(&description.ty).ok_or_else(|| {
+            DescriptionError::Unsupported(SchemaError::UnsupportedType {
+                column: description.name.clone(),
+                ty: description.ty.clone(),
+                format,
+            })
+        })?;
+        columns.push(Column {
+            name: description.name,
+            ty,
+        });
+    }
+    Ok(columns)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_types() {
+        for name in [
+            "Bool",
+            "UInt64",
+            "Int8",
+            "Float64",
+            "Decimal(18, 4)",
+            "String",
+            "FixedString(16)",
+            "UUID",
+            "IPv6",
+            "Date32",
+            "DateTime",
+            "DateTime('Europe/Paris')",
+            "DateTime64(3)",
+            "DateTime64(9, 'UTC')",
+            "Enum8('a' = 1, 'b\\'c' = -2)",
+            "Nullable(Int32)",
+            "LowCardinality(Nullable(String))",
+            "Array(Array(UInt16))",
+            "Map(LowCardinality(String), Array(Float32))",
+        ] {
+            let ty = ColumnType::parse(name).unwrap_or_else(|| panic!("{} should parse", name));
+            assert_eq!(ty.to_string(), name);
+        }
+
+        assert_eq!(
+            ColumnType::parse("Decimal64(3)"),
+            Some(ColumnType::Decimal {
+                precision: 18,
+                scale: 3
+            })
+        );
+        assert_eq!(
+            ColumnType::parse(" Map( String ,UInt8 ) "),
+            Some(ColumnType::Map(
+                Box::new(ColumnType::String),
+                Box::new(ColumnType::UInt8)
+            ))
+        );
+    }
+
+    #[test]
+    fn rejects_unsupported_types() {
+        for name in [
+            "UInt128",
+            "Decimal(76, 2)",
+            "DateTime64(12)",
+            "Tuple(String, UInt8)",
+            "Nested(a String)",
+            "Array(String",
+            "String)",
+            "",
+        ] {
+            assert_eq!(ColumnType::parse(name), None, "{}", name);
+        }
+    }
+
+    #[test]
+    fn strips_low_cardinality_from_native_types() {
+        let ty = ColumnType::parse(
+            "Map(LowCardinality(String), Array(LowCardinality(Nullable(String))))",
+        )
+        .unwrap();
+        assert_eq!(
+            ty.native().to_string(),
+            "Map(String, Array(Nullable(String)))"
+        );
+    }
+
+    #[test]
+    fn parses_table_descriptions() {
+        let body = br#"{"name":"timestamp","type":"DateTime64(3)","default_type":"","default_expression":""}
+{"name":"message","type":"String","default_type":"DEFAULT","default_expression":"''"}
+{"name":"day","type":"Date","default_type":"MATERIALIZED","default_expression":"toDate(timestamp)"}
+"#;
+        let columns = parse_description(body, Format::RowBinary).ok().unwrap();
+        assert_eq!(
+            columns,
+            vec![
+                Column {
+                    name: "timestamp".to_owned(),
+                    ty: ColumnType::DateTime64(3, None),
+                },
+                Column {
+                    name: "message".to_owned(),
+                    ty: ColumnType::String,
+                },
+            ]
+        );
+
+        let body = br#"{"name":"point","type":"Tuple(Float64, Float64)","default_type":""}"#;
+        match parse_description(body, Format::Native) {
+            Err(DescriptionError::Unsupported(error)) => assert_eq!(
+                error.to_string(),
+                "column `point` has type `Tuple(Float64, Float64)`, which the `native` format doesn't support; use the `json_each_row` format instead"
+            ),
+            _ => panic!("tuples should be unsupported"),
+        }
+    }
+}
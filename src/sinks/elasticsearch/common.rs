@@ -175,6 +175,7 @@ impl ElasticsearchCommon {
                 doc_type,
                 suppress_type_name,
             },
+            reroute_dropped: config.reroute_dropped,
         };
 
         Ok(Self {
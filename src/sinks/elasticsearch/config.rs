@@ -5,27 +5,33 @@ use std::{
 
 use futures::{FutureExt, TryFutureExt};
 use snafu::ResultExt;
+use value::Kind;
 use vector_config::configurable_component;
 
 use crate::{
     aws::RegionOrEndpoint,
     codecs::Transformer,
-    config::{log_schema, AcknowledgementsConfig, DataType, Input, SinkConfig, SinkContext},
+    config::{
+        log_schema, AcknowledgementsConfig, DataType, Input, Output, SinkConfig, SinkContext,
+    },
     event::{EventRef, LogEvent, Value},
     http::HttpClient,
     internal_events::TemplateRenderingError,
+    schema,
     sinks::{
         elasticsearch::{
             health::ElasticsearchHealthLogic,
-            retry::ElasticsearchRetryLogic,
+            retry::{ElasticsearchRejectionLogic, ElasticsearchRetryLogic},
             service::{ElasticsearchService, HttpRequestBuilder},
             sink::ElasticsearchSink,
             BatchActionTemplateSnafu, ElasticsearchApiVersion, ElasticsearchAuth,
             ElasticsearchCommon, ElasticsearchCommonMode, ElasticsearchMode, IndexTemplateSnafu,
         },
         util::{
-            http::RequestConfig, service::HealthConfig, BatchConfig, Compression,
-            RealtimeSizeBasedDefaultBatchSettings, TowerRequestConfig,
+            dropped::{self, RerouteDroppedService},
+            http::RequestConfig,
+            service::HealthConfig,
+            BatchConfig, Compression, RealtimeSizeBasedDefaultBatchSettings, TowerRequestConfig,
         },
         Healthcheck, VectorSink,
     },
@@ -81,6 +87,15 @@ pub struct ElasticsearchConfig {
     #[serde(default)]
     pub request_retry_partial: bool,
 
+    /// Whether or not to reroute events that Elasticsearch permanently rejected to the `dropped`
+    /// output, instead of dropping them.
+    ///
+    /// Events are rejected when the whole bulk request fails with a client error, or when their own
+    /// item in the bulk response fails with one, such as a mapping error. Rejected events are
+    /// annotated with the error and the status code Elasticsearch returned.
+    #[serde(default)]
+    pub reroute_dropped: bool,
+
     /// The name of the event key that should map to Elasticsearch’s [`_id` field][es_id].
     ///
     /// By default, Vector does not set the `_id` field, which allows Elasticsearch to set this
@@ -420,6 +435,11 @@ impl SinkConfig for ElasticsearchConfig {
             health_config,
             ElasticsearchHealthLogic,
         );
+        let service = RerouteDroppedService::new(
+            service,
+            ElasticsearchRejectionLogic,
+            cx.dropped.clone().filter(|_| self.reroute_dropped),
+        );
 
         let sink = ElasticsearchSink::new(&common, self, service)?;
 
@@ -436,7 +456,21 @@ impl SinkConfig for ElasticsearchConfig {
     }
 
     fn input(&self) -> Input {
-        Input::new(DataType::Metric | DataType::Log)
+        // Data streams reject documents without an `@timestamp`, which is remapped from the
+        // timestamp of the event.
+        let requirement = match self.mode {
+            ElasticsearchMode::Bulk => schema::Requirement::empty(),
+            ElasticsearchMode::DataStream => {
+                schema::Requirement::empty().required_meaning("timestamp", Kind::timestamp())
+            }
+        };
+
+        Input::new(DataType::Metric | DataType::Log).with_schema_requirement(requirement)
+    }
+
+    fn outputs(&self) -> Vec<Output> {
+        // Metrics are converted to logs before being sent, so that's what gets rejected.
+        dropped::outputs(self.reroute_dropped, DataType::Log)
     }
 
     fn acknowledgements(&self) -> &AcknowledgementsConfig {
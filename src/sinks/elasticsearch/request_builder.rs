@@ -3,7 +3,7 @@ use vector_common::request_metadata::RequestMetadata;
 use vector_core::ByteSizeOf;
 
 use crate::{
-    event::{EventFinalizers, Finalizable},
+    event::{Event, EventFinalizers, Finalizable},
     sinks::{
         elasticsearch::{
             encoder::{ElasticsearchEncoder, ProcessedEvent},
@@ -20,12 +20,14 @@ use crate::{
 pub struct ElasticsearchRequestBuilder {
     pub compression: Compression,
     pub encoder: ElasticsearchEncoder,
+    pub reroute_dropped: bool,
 }
 
 pub struct Metadata {
     finalizers: EventFinalizers,
     batch_size: usize,
     events_byte_size: usize,
+    dropped_events: Option<Vec<Event>>,
 }
 
 impl RequestBuilder<Vec<ProcessedEvent>> for ElasticsearchRequestBuilder {
@@ -56,10 +58,21 @@ impl RequestBuilder<Vec<ProcessedEvent>> for ElasticsearchRequestBuilder {
 
         let metadata_builder = RequestMetadataBuilder::from_events(&events);
 
+        let finalizers = events.take_finalizers();
+        // The copies are taken after the finalizers, so rerouted events don't hold up
+        // acknowledgement of the originals.
+        let dropped_events = self.reroute_dropped.then(|| {
+            events
+                .iter()
+                .map(|event| Event::from(event.log.clone()))
+                .collect()
+        });
+
         let es_metadata = Metadata {
-            finalizers: events.take_finalizers(),
+            finalizers,
             batch_size: events.len(),
             events_byte_size,
+            dropped_events,
         };
         (es_metadata, metadata_builder, events)
     }
@@ -76,6 +89,7 @@ impl RequestBuilder<Vec<ProcessedEvent>> for ElasticsearchRequestBuilder {
             batch_size: es_metadata.batch_size,
             events_byte_size: es_metadata.events_byte_size,
             metadata,
+            dropped_events: es_metadata.dropped_events,
         }
     }
 }
@@ -5,7 +5,10 @@ use crate::{
     http::HttpError,
     sinks::{
         elasticsearch::service::ElasticsearchResponse,
-        util::retries::{RetryAction, RetryLogic},
+        util::{
+            dropped::{self, Rejection, RejectionLogic},
+            retries::{RetryAction, RetryLogic},
+        },
     },
 };
 
@@ -155,6 +158,76 @@ impl RetryLogic for ElasticsearchRetryLogic {
     }
 }
 
+/// Decides which events Elasticsearch permanently rejected, for rerouting them.
+#[derive(Clone)]
+pub struct ElasticsearchRejectionLogic;
+
+impl RejectionLogic for ElasticsearchRejectionLogic {
+    type Response = ElasticsearchResponse;
+    type Error = crate::Error;
+
+    fn rejected(
+        &self,
+        result: Result<&ElasticsearchResponse, &crate::Error>,
+        count: usize,
+    ) -> Vec<(usize, Rejection)> {
+        // Requests that could not be sent at all were not rejected by Elasticsearch.
+        let response = match result {
+            Ok(response) => &response.http_response,
+            Err(_) => return Vec::new(),
+        };
+        let status = response.status();
+        let body = String::from_utf8_lossy(response.body());
+
+        if is_permanent_error(status) {
+            return dropped::reject_all(
+                count,
+                Rejection {
+                    message: body.into_owned(),
+                    status: Some(status.as_u16()),
+                },
+            );
+        }
+        if !status.is_success() || !body.contains("\"errors\":true") {
+            return Vec::new();
+        }
+
+        match EsResultResponse::parse(&body) {
+            // Items can only be matched up with events if there is exactly one for each of them.
+            Ok(resp) if resp.items.len() == count => resp
+                .items
+                .iter()
+                .enumerate()
+                .filter_map(|(index, item)| {
+                    let result = item.result();
+                    let status = StatusCode::from_u16(result.status?).ok()?;
+                    is_permanent_error(status).then(|| {
+                        let message = match &result.error {
+                            Some(error) => {
+                                format!("error type: {}, reason: {}", error.err_type, error.reason)
+                            }
+                            None => status.to_string(),
+                        };
+                        (
+                            index,
+                            Rejection {
+                                message,
+                                status: Some(status.as_u16()),
+                            },
+                        )
+                    })
+                })
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+}
+
+/// Whether the request, or bulk item, would fail the same way if it was sent again.
+fn is_permanent_error(status: StatusCode) -> bool {
+    status.is_client_error() && status != StatusCode::TOO_MANY_REQUESTS
+}
+
 #[cfg(test)]
 mod tests {
     use bytes::Bytes;
@@ -206,6 +279,65 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn rejects_items_with_client_errors() {
+        let json = "{\"took\":3,\"errors\":true,\"items\":[{\"index\":{\"status\":201}},{\"index\":{\"status\":400,\"error\":{\"type\":\"mapper_parsing_exception\",\"reason\":\"failed to parse field [host]\"}}},{\"index\":{\"status\":429}}]}";
+        let response = ElasticsearchResponse {
+            http_response: Response::builder()
+                .status(StatusCode::OK)
+                .body(Bytes::from(json))
+                .unwrap(),
+            event_status: EventStatus::Rejected,
+            batch_size: 3,
+            events_byte_size: 3,
+        };
+
+        let rejected = ElasticsearchRejectionLogic.rejected(Ok(&response), 3);
+        assert_eq!(rejected.len(), 1);
+        assert_eq!(rejected[0].0, 1);
+        assert_eq!(rejected[0].1.status, Some(400));
+        assert_eq!(
+            rejected[0].1.message,
+            "error type: mapper_parsing_exception, reason: failed to parse field [host]"
+        );
+
+        // Items that can't be matched up with events aren't rerouted.
+        assert!(ElasticsearchRejectionLogic
+            .rejected(Ok(&response), 2)
+            .is_empty());
+    }
+
+    #[test]
+    fn rejects_whole_request_with_client_error() {
+        let response = ElasticsearchResponse {
+            http_response: Response::builder()
+                .status(StatusCode::BAD_REQUEST)
+                .body(Bytes::from("bad request"))
+                .unwrap(),
+            event_status: EventStatus::Rejected,
+            batch_size: 2,
+            events_byte_size: 2,
+        };
+        let rejected = ElasticsearchRejectionLogic.rejected(Ok(&response), 2);
+        assert_eq!(
+            rejected.iter().map(|(index, _)| *index).collect::<Vec<_>>(),
+            vec![0, 1]
+        );
+
+        let response = ElasticsearchResponse {
+            http_response: Response::builder()
+                .status(StatusCode::TOO_MANY_REQUESTS)
+                .body(Bytes::new())
+                .unwrap(),
+            event_status: EventStatus::Errored,
+            batch_size: 2,
+            events_byte_size: 2,
+        };
+        assert!(ElasticsearchRejectionLogic
+            .rejected(Ok(&response), 2)
+            .is_empty());
+    }
+
     #[test]
     fn get_index_error_reason() {
         let json = "{\"took\":185,\"errors\":true,\"items\":[{\"index\":{\"_index\":\"test-hgw28jv10u\",\"_type\":\"log_lines\",\"_id\":\"3GhQLXEBE62DvOOUKdFH\",\"status\":400,\"error\":{\"type\":\"illegal_argument_exception\",\"reason\":\"mapper [message] of different type, current_type [long], merged_type [text]\"}}}]}";
@@ -16,9 +16,10 @@ use vector_core::{internal_event::CountByteSize, stream::DriverResponse, ByteSiz
 
 use crate::sinks::elasticsearch::sign_request;
 use crate::{
-    event::{EventFinalizers, EventStatus, Finalizable},
+    event::{Event, EventFinalizers, EventStatus, Finalizable},
     http::{Auth, HttpClient},
     sinks::util::{
+        dropped::DroppedEvents,
         http::{HttpBatchService, RequestConfig},
         Compression, ElementCount,
     },
@@ -33,6 +34,7 @@ pub struct ElasticsearchRequest {
     pub batch_size: usize,
     pub events_byte_size: usize,
     pub metadata: RequestMetadata,
+    pub dropped_events: Option<Vec<Event>>,
 }
 
 impl ByteSizeOf for ElasticsearchRequest {
@@ -59,6 +61,12 @@ impl MetaDescriptive for ElasticsearchRequest {
     }
 }
 
+impl DroppedEvents for ElasticsearchRequest {
+    fn take_dropped_events(&mut self) -> Option<Vec<Event>> {
+        self.dropped_events.take()
+    }
+}
+
 #[derive(Clone)]
 pub struct ElasticsearchService {
     batch_service: HttpBatchService<
@@ -1,15 +1,21 @@
 use std::collections::HashMap;
 
 use futures::future::FutureExt;
+use lookup::PathPrefix;
+use value::Kind;
 use vector_config::configurable_component;
 
 use super::{healthcheck::healthcheck, sink::LokiSink};
 use crate::{
     codecs::EncodingConfig,
-    config::{AcknowledgementsConfig, DataType, GenerateConfig, Input, SinkConfig, SinkContext},
+    config::{
+        AcknowledgementsConfig, DataType, GenerateConfig, Input, Output, SinkConfig, SinkContext,
+    },
     http::{Auth, HttpClient, MaybeAuth},
     sinks::{
-        util::{BatchConfig, Compression, SinkBatchSettings, TowerRequestConfig, UriSerde},
+        util::{
+            dropped, BatchConfig, Compression, SinkBatchSettings, TowerRequestConfig, UriSerde,
+        },
         VectorSink,
     },
     template::Template,
@@ -103,6 +109,14 @@ pub struct LokiConfig {
     #[serde(default)]
     pub out_of_order_action: OutOfOrderAction,
 
+    /// Whether or not to reroute events that Loki permanently rejected to the `dropped` output,
+    /// instead of dropping them.
+    ///
+    /// Events are rejected when Loki responds to the request carrying them with a client error, such
+    /// as `400 Bad Request`. Rejected events are annotated with the error and the status code.
+    #[serde(default)]
+    pub reroute_dropped: bool,
+
     #[configurable(derived)]
     pub auth: Option<Auth>,
 
@@ -200,6 +214,7 @@ impl SinkConfig for LokiConfig {
             }
         }
 
+        let dropped = cx.dropped.clone().filter(|_| self.reroute_dropped);
         let client = self.build_client(cx)?;
 
         let config = LokiConfig {
@@ -207,7 +222,7 @@ impl SinkConfig for LokiConfig {
             ..self.clone()
         };
 
-        let sink = LokiSink::new(config.clone(), client.clone())?;
+        let sink = LokiSink::new(config.clone(), client.clone(), dropped)?;
 
         let healthcheck = healthcheck(config, client).boxed();
 
@@ -215,7 +230,27 @@ impl SinkConfig for LokiConfig {
     }
 
     fn input(&self) -> Input {
+        // Labels can't be rendered without the event fields they reference.
+        let requirement = self
+            .labels
+            .iter()
+            .flat_map(|(key, value)| {
+                key.required_fields()
+                    .into_iter()
+                    .chain(value.required_fields())
+            })
+            .filter(|field| field.prefix == PathPrefix::Event)
+            .fold(
+                self.encoding.config().schema_requirement(),
+                |requirement, field| requirement.required_field(field.path, Kind::any()),
+            );
+
         Input::new(self.encoding.config().input_type() & DataType::Log)
+            .with_schema_requirement(requirement)
+    }
+
+    fn outputs(&self) -> Vec<Output> {
+        dropped::outputs(self.reroute_dropped, DataType::Log)
     }
 
     fn acknowledgements(&self) -> &AcknowledgementsConfig {
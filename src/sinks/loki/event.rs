@@ -4,7 +4,7 @@ use bytes::Bytes;
 use serde::{ser::SerializeSeq, Serialize};
 use vector_buffers::EventCount;
 use vector_core::{
-    event::{EventFinalizers, Finalizable},
+    event::{Event, EventFinalizers, Finalizable},
     ByteSizeOf, EstimatedJsonEncodedSizeOf,
 };
 
@@ -107,6 +107,8 @@ pub struct LokiRecord {
     pub labels: Labels,
     pub event: LokiEvent,
     pub finalizers: EventFinalizers,
+    /// A copy of the event the record was encoded from, kept when rejected events are rerouted.
+    pub dropped_event: Option<Event>,
 }
 
 impl ByteSizeOf for LokiRecord {
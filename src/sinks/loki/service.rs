@@ -8,7 +8,7 @@ use tower::Service;
 use tracing::Instrument;
 use vector_common::request_metadata::{MetaDescriptive, RequestMetadata};
 use vector_core::{
-    event::{EventFinalizers, EventStatus, Finalizable},
+    event::{Event, EventFinalizers, EventStatus, Finalizable},
     internal_event::CountByteSize,
     stream::DriverResponse,
 };
@@ -16,7 +16,11 @@ use vector_core::{
 use crate::sinks::loki::config::{CompressionConfigAdapter, ExtendedCompression};
 use crate::{
     http::{get_http_scheme_from_uri, Auth, HttpClient},
-    sinks::util::{retries::RetryLogic, UriSerde},
+    sinks::util::{
+        dropped::{self, DroppedEvents, Rejection, RejectionLogic},
+        retries::RetryLogic,
+        UriSerde,
+    },
 };
 
 #[derive(Clone)]
@@ -39,6 +43,39 @@ impl RetryLogic for LokiRetryLogic {
     }
 }
 
+/// Decides which events Loki permanently rejected, for rerouting them.
+#[derive(Clone)]
+pub struct LokiRejectionLogic;
+
+impl RejectionLogic for LokiRejectionLogic {
+    type Response = LokiResponse;
+    type Error = crate::Error;
+
+    fn rejected(
+        &self,
+        result: Result<&LokiResponse, &crate::Error>,
+        count: usize,
+    ) -> Vec<(usize, Rejection)> {
+        match result
+            .err()
+            .and_then(|error| error.downcast_ref::<LokiError>())
+        {
+            Some(error @ LokiError::ServerError { code })
+                if code.is_client_error() && *code != StatusCode::TOO_MANY_REQUESTS =>
+            {
+                dropped::reject_all(
+                    count,
+                    Rejection {
+                        message: error.to_string(),
+                        status: Some(code.as_u16()),
+                    },
+                )
+            }
+            _ => Vec::new(),
+        }
+    }
+}
+
 #[derive(Debug, Snafu)]
 pub enum LokiError {
     #[snafu(display("Server responded with an error: {}", code))]
@@ -77,6 +114,7 @@ pub struct LokiRequest {
     pub payload: Bytes,
     pub tenant_id: Option<String>,
     pub metadata: RequestMetadata,
+    pub dropped_events: Option<Vec<Event>>,
 }
 
 impl Finalizable for LokiRequest {
@@ -91,6 +129,12 @@ impl MetaDescriptive for LokiRequest {
     }
 }
 
+impl DroppedEvents for LokiRequest {
+    fn take_dropped_events(&mut self) -> Option<Vec<Event>> {
+        self.dropped_events.take()
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct LokiService {
     endpoint: UriSerde,
@@ -19,7 +19,7 @@ use vector_core::{
 use super::{
     config::{LokiConfig, OutOfOrderAction},
     event::{LokiBatchEncoder, LokiEvent, LokiRecord, PartitionKey},
-    service::{LokiRequest, LokiRetryLogic, LokiService},
+    service::{LokiRejectionLogic, LokiRequest, LokiRetryLogic, LokiService},
 };
 use crate::sinks::loki::event::LokiBatchEncoding;
 use crate::sinks::{
@@ -36,6 +36,7 @@ use crate::{
     },
     sinks::util::{
         builder::SinkBuilderExt,
+        dropped::{DroppedSender, RerouteDroppedService},
         request_builder::EncodeResult,
         service::{ServiceBuilderExt, Svc},
         Compression, RequestBuilder,
@@ -136,7 +137,7 @@ impl EstimatedJsonEncodedSizeOf for &LokiRecords {
 }
 
 impl RequestBuilder<(PartitionKey, LokiRecords)> for LokiRequestBuilder {
-    type Metadata = (Option<String>, EventFinalizers);
+    type Metadata = (Option<String>, EventFinalizers, Option<Vec<Event>>);
     type Events = LokiRecords;
     type Encoder = LokiBatchEncoder;
     type Payload = Bytes;
@@ -162,8 +163,17 @@ impl RequestBuilder<(PartitionKey, LokiRecords)> for LokiRequestBuilder {
 
         let metadata_builder = RequestMetadataBuilder::from_events(&events);
         let finalizers = events.0.take_finalizers();
-
-        ((key.tenant_id, finalizers), metadata_builder, events)
+        let dropped_events = events
+            .0
+            .iter_mut()
+            .map(|record| record.dropped_event.take())
+            .collect();
+
+        (
+            (key.tenant_id, finalizers, dropped_events),
+            metadata_builder,
+            events,
+        )
     }
 
     fn build_request(
@@ -172,7 +182,7 @@ impl RequestBuilder<(PartitionKey, LokiRecords)> for LokiRequestBuilder {
         metadata: RequestMetadata,
         payload: EncodeResult<Self::Payload>,
     ) -> Self::Request {
-        let (tenant_id, finalizers) = loki_metadata;
+        let (tenant_id, finalizers, dropped_events) = loki_metadata;
         let compression = self.compression;
 
         LokiRequest {
@@ -181,6 +191,7 @@ impl RequestBuilder<(PartitionKey, LokiRecords)> for LokiRequestBuilder {
             payload: payload.into_payload(),
             tenant_id,
             metadata,
+            dropped_events,
         }
     }
 }
@@ -193,6 +204,7 @@ pub(super) struct EventEncoder {
     labels: HashMap<Template, Template>,
     remove_label_fields: bool,
     remove_timestamp: bool,
+    reroute_dropped: bool,
 }
 
 impl EventEncoder {
@@ -240,6 +252,7 @@ impl EventEncoder {
     pub(super) fn encode_event(&mut self, mut event: Event) -> Option<LokiRecord> {
         let tenant_id = self.key_partitioner.partition(&event);
         let finalizers = event.take_finalizers();
+        let dropped_event = self.reroute_dropped.then(|| event.clone());
         let mut labels = self.build_labels(&event);
         self.remove_label_fields(&mut event);
 
@@ -276,6 +289,7 @@ impl EventEncoder {
             },
             partition,
             finalizers,
+            dropped_event,
         })
     }
 }
@@ -358,12 +372,16 @@ pub struct LokiSink {
     pub(super) encoder: EventEncoder,
     batch_settings: BatcherSettings,
     out_of_order_action: OutOfOrderAction,
-    service: Svc<LokiService, LokiRetryLogic>,
+    service: RerouteDroppedService<Svc<LokiService, LokiRetryLogic>, LokiRejectionLogic>,
 }
 
 impl LokiSink {
     #[allow(clippy::missing_const_for_fn)] // const cannot run destructor
-    pub fn new(config: LokiConfig, client: HttpClient) -> crate::Result<Self> {
+    pub fn new(
+        config: LokiConfig,
+        client: HttpClient,
+        dropped: Option<DroppedSender>,
+    ) -> crate::Result<Self> {
         let compression = config.compression;
 
         // if Vector is configured to allow events with out of order timestamps, then then we can
@@ -386,6 +404,7 @@ impl LokiSink {
         let service = tower::ServiceBuilder::new()
             .settings(request_limits, LokiRetryLogic)
             .service(LokiService::new(client, config.endpoint, config.auth)?);
+        let service = RerouteDroppedService::new(service, LokiRejectionLogic, dropped);
 
         let transformer = config.encoding.transformer();
         let serializer = config.encoding.build()?;
@@ -409,6 +428,7 @@ impl LokiSink {
                 labels: config.labels,
                 remove_label_fields: config.remove_label_fields,
                 remove_timestamp: config.remove_timestamp,
+                reroute_dropped: config.reroute_dropped,
             },
             batch_settings: config.batch.into_batcher_settings()?,
             out_of_order_action: config.out_of_order_action,
@@ -513,6 +533,7 @@ mod tests {
             labels: HashMap::default(),
             remove_label_fields: false,
             remove_timestamp: false,
+            reroute_dropped: false,
         };
         let mut event = Event::Log(LogEvent::from("hello world"));
         let log = event.as_mut_log();
@@ -552,6 +573,7 @@ mod tests {
             labels,
             remove_label_fields: false,
             remove_timestamp: false,
+            reroute_dropped: false,
         };
         let mut event = Event::Log(LogEvent::from("hello world"));
         let log = event.as_mut_log();
@@ -584,6 +606,7 @@ mod tests {
             labels: HashMap::default(),
             remove_label_fields: false,
             remove_timestamp: true,
+            reroute_dropped: false,
         };
         let mut event = Event::Log(LogEvent::from("hello world"));
         let log = event.as_mut_log();
@@ -612,6 +635,7 @@ mod tests {
             labels,
             remove_label_fields: true,
             remove_timestamp: false,
+            reroute_dropped: false,
         };
         let mut event = Event::Log(LogEvent::from("hello world"));
         let log = event.as_mut_log();
@@ -631,6 +655,7 @@ mod tests {
             labels: HashMap::default(),
             remove_label_fields: false,
             remove_timestamp: false,
+            reroute_dropped: false,
         };
         let base = chrono::Utc::now();
         let events = random_lines(100)
@@ -1,11 +1,19 @@
 use futures::StreamExt;
 
-use super::{config::LokiConfig, healthcheck::healthcheck, sink::LokiSink};
+use super::{
+    config::LokiConfig,
+    healthcheck::healthcheck,
+    service::{LokiError, LokiRejectionLogic},
+    sink::LokiSink,
+};
 use crate::{
-    config::ProxyConfig,
+    config::{ProxyConfig, SinkConfig},
     event::{Event, LogEvent},
     http::HttpClient,
-    sinks::util::test::{build_test_server, load_sink},
+    sinks::util::{
+        dropped::RejectionLogic,
+        test::{build_test_server, load_sink},
+    },
     test_util,
     tls::TlsSettings,
 };
@@ -15,6 +23,38 @@ fn generate_config() {
     test_util::test_generate_config::<LokiConfig>();
 }
 
+#[test]
+fn reroute_dropped_adds_output() {
+    let config: LokiConfig = toml::from_str(
+        r#"
+        endpoint = "http://localhost:3100"
+        labels = {test_name = "placeholder"}
+        encoding.codec = "json"
+        reroute_dropped = true
+    "#,
+    )
+    .unwrap();
+
+    let outputs = config.outputs();
+    assert_eq!(outputs.len(), 1);
+    assert_eq!(outputs[0].port.as_deref(), Some("dropped"));
+}
+
+#[test]
+fn rejects_events_on_client_errors() {
+    let error: crate::Error = Box::new(LokiError::ServerError {
+        code: http::StatusCode::BAD_REQUEST,
+    });
+    let rejected = LokiRejectionLogic.rejected(Err(&error), 2);
+    assert_eq!(rejected.len(), 2);
+    assert_eq!(rejected[0].1.status, Some(400));
+
+    let error: crate::Error = Box::new(LokiError::ServerError {
+        code: http::StatusCode::TOO_MANY_REQUESTS,
+    });
+    assert!(LokiRejectionLogic.rejected(Err(&error), 2).is_empty());
+}
+
 #[tokio::test]
 async fn interpolate_labels() {
     let (config, cx) = load_sink::<LokiConfig>(
@@ -27,7 +67,7 @@ async fn interpolate_labels() {
     )
     .unwrap();
     let client = config.build_client(cx).unwrap();
-    let mut sink = LokiSink::new(config, client).unwrap();
+    let mut sink = LokiSink::new(config, client, None).unwrap();
 
     let mut e1 = Event::Log(LogEvent::from("hello world"));
 
@@ -68,7 +108,7 @@ async fn use_label_from_dropped_fields() {
     )
     .unwrap();
     let client = config.build_client(cx).unwrap();
-    let mut sink = LokiSink::new(config, client).unwrap();
+    let mut sink = LokiSink::new(config, client, None).unwrap();
 
     let mut e1 = Event::Log(LogEvent::from("hello world"));
 
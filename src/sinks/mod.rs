@@ -66,12 +66,16 @@ pub mod kafka;
 pub mod logdna;
 #[cfg(feature = "sinks-loki")]
 pub mod loki;
+#[cfg(feature = "sinks-mqtt")]
+pub mod mqtt;
 #[cfg(feature = "sinks-nats")]
 pub mod nats;
 #[cfg(feature = "sinks-new_relic")]
 pub mod new_relic;
 #[cfg(feature = "sinks-papertrail")]
 pub mod papertrail;
+#[cfg(feature = "sinks-postgres")]
+pub mod postgres;
 #[cfg(feature = "sinks-prometheus")]
 pub mod prometheus;
 #[cfg(feature = "sinks-pulsar")]
@@ -272,6 +276,10 @@ pub enum Sinks {
     #[cfg(feature = "sinks-loki")]
     Loki(#[configurable(derived)] loki::LokiConfig),
 
+    /// MQTT.
+    #[cfg(feature = "sinks-mqtt")]
+    Mqtt(#[configurable(derived)] mqtt::MqttSinkConfig),
+
     /// NATS.
     #[cfg(feature = "sinks-nats")]
     Nats(#[configurable(derived)] self::nats::NatsSinkConfig),
@@ -284,6 +292,10 @@ pub enum Sinks {
     #[cfg(feature = "sinks-papertrail")]
     Papertrail(#[configurable(derived)] papertrail::PapertrailConfig),
 
+    /// PostgreSQL.
+    #[cfg(feature = "sinks-postgres")]
+    Postgres(#[configurable(derived)] postgres::PostgresConfig),
+
     /// Prometheus Exporter.
     #[cfg(feature = "sinks-prometheus")]
     PrometheusExporter(#[configurable(derived)] prometheus::exporter::PrometheusExporterConfig),
@@ -438,12 +450,16 @@ impl NamedComponent for Sinks {
             Self::Logdna(config) => config.get_component_name(),
             #[cfg(feature = "sinks-loki")]
             Self::Loki(config) => config.get_component_name(),
+            #[cfg(feature = "sinks-mqtt")]
+            Self::Mqtt(config) => config.get_component_name(),
             #[cfg(feature = "sinks-nats")]
             Self::Nats(config) => config.get_component_name(),
             #[cfg(feature = "sinks-new_relic")]
             Self::NewRelic(config) => config.get_component_name(),
             #[cfg(feature = "sinks-papertrail")]
             Self::Papertrail(config) => config.get_component_name(),
+            #[cfg(feature = "sinks-postgres")]
+            Self::Postgres(config) => config.get_component_name(),
             #[cfg(feature = "sinks-prometheus")]
             Self::PrometheusExporter(config) => config.get_component_name(),
             #[cfg(feature = "sinks-prometheus")]
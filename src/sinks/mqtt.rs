@@ -0,0 +1,433 @@
+use std::collections::{HashMap, VecDeque};
+use std::convert::TryFrom;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use bytes::BytesMut;
+use codecs::JsonSerializerConfig;
+use futures::{stream::BoxStream, FutureExt, StreamExt};
+use rumqttc::{AsyncClient, Event as MqttEvent, EventLoop, MqttOptions, Outgoing, Packet};
+use snafu::{ResultExt, Snafu};
+use tokio_util::codec::Encoder as _;
+use uuid::Uuid;
+use vector_common::{
+    estimated_json_encoded_size_of::EstimatedJsonEncodedSizeOf,
+    finalization::EventFinalizers,
+    internal_event::{error_stage, ByteSize, BytesSent, EventsSent, InternalEventHandle, Protocol},
+};
+use vector_config::configurable_component;
+
+use crate::{
+    codecs::{Encoder, EncodingConfig, Transformer},
+    config::{AcknowledgementsConfig, DataType, GenerateConfig, Input, SinkConfig, SinkContext},
+    event::{Event, EventStatus, Finalizable},
+    internal_events::{MqttConnectionError, MqttPublishError, TemplateRenderingError},
+    mqtt::{MqttConfigError, MqttConnectionConfig, MqttQoS},
+    sinks::util::StreamSink,
+    template::{Template, TemplateParseError},
+};
+
+/// The capacity of the request channel between the client and its event loop.
+///
+/// The sink never has more deliveries in flight than this, so publishing never waits on the
+/// event loop, which is polled by the same task.
+const CLIENT_CHANNEL_CAPACITY: usize = 1024;
+
+const RECONNECT_DELAY: Duration = Duration::from_secs(1);
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("invalid encoding: {}", source))]
+    Encoding {
+        source: codecs::encoding::BuildError,
+    },
+    #[snafu(display("invalid topic template: {}", source))]
+    TopicTemplate { source: TemplateParseError },
+    #[snafu(display("{}", source))]
+    Config { source: MqttConfigError },
+}
+
+/// Configuration for the `mqtt` sink.
+#[configurable_component(sink("mqtt"))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct MqttSinkConfig {
+    /// Connection options for the `mqtt` sink.
+    connection: MqttConnectionConfig,
+
+    /// The MQTT topic to publish messages to.
+    #[configurable(metadata(docs::templateable))]
+    topic: String,
+
+    /// Whether the broker retains the last message published to the topic.
+    #[serde(default)]
+    retain: bool,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    qos: MqttQoS,
+
+    #[configurable(derived)]
+    encoding: EncodingConfig,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        deserialize_with = "crate::serde::bool_or_struct",
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    pub acknowledgements: AcknowledgementsConfig,
+}
+
+impl GenerateConfig for MqttSinkConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            connection: MqttConnectionConfig::default(),
+            topic: "vector".into(),
+            retain: false,
+            qos: MqttQoS::default(),
+            encoding: JsonSerializerConfig::new().into(),
+            acknowledgements: Default::default(),
+        })
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+impl SinkConfig for MqttSinkConfig {
+    async fn build(
+        &self,
+        _cx: SinkContext,
+    ) -> crate::Result<(super::VectorSink, super::Healthcheck)> {
+        let sink = MqttSink::new(self.clone())?;
+        let healthcheck = healthcheck(self.connection.clone()).boxed();
+        Ok((super::VectorSink::from_event_streamsink(sink), healthcheck))
+    }
+
+    fn input(&self) -> Input {
+        Input::new(self.encoding.config().input_type() & DataType::Log)
+    }
+
+    fn acknowledgements(&self) -> &AcknowledgementsConfig {
+        &self.acknowledgements
+    }
+}
+
+/// Sinks don't resume sessions, so they connect with a random client ID by default rather than
+/// one shared with other clients.
+fn random_client_id() -> String {
+    format!("vector-{}", Uuid::new_v4().simple())
+}
+
+async fn healthcheck(connection: MqttConnectionConfig) -> crate::Result<()> {
+    // The broker disconnects clients sharing an ID, so don't take over the one of the sink.
+    let connection = MqttConnectionConfig {
+        client_id: connection
+            .client_id
+            .map(|client_id| format!("{}-healthcheck", client_id)),
+        ..connection
+    };
+
+    let (client, mut eventloop) = AsyncClient::new(connection.options(&random_client_id())?, 1);
+    loop {
+        if let MqttEvent::Incoming(Packet::ConnAck(_)) = eventloop.poll().await? {
+            break;
+        }
+    }
+    let _ = client.try_disconnect();
+
+    Ok(())
+}
+
+/// A publish waiting for its packet ID to be assigned by the event loop.
+struct PendingPublish {
+    finalizers: EventFinalizers,
+    event_byte_size: usize,
+    byte_size: usize,
+}
+
+pub struct MqttSink {
+    options: MqttOptions,
+    transformer: Transformer,
+    encoder: Encoder<()>,
+    topic: Template,
+    retain: bool,
+    qos: MqttQoS,
+}
+
+impl MqttSink {
+    fn new(config: MqttSinkConfig) -> Result<Self, BuildError> {
+        let options = config
+            .connection
+            .options(&random_client_id())
+            .context(ConfigSnafu)?;
+        let transformer = config.encoding.transformer();
+        let serializer = config.encoding.build().context(EncodingSnafu)?;
+        let encoder = Encoder::<()>::new(serializer);
+
+        Ok(MqttSink {
+            options,
+            transformer,
+            encoder,
+            topic: Template::try_from(config.topic).context(TopicTemplateSnafu)?,
+            retain: config.retain,
+            qos: config.qos,
+        })
+    }
+
+    fn client(&self) -> (AsyncClient, EventLoop) {
+        AsyncClient::new(self.options.clone(), CLIENT_CHANNEL_CAPACITY)
+    }
+
+    /// Renders and encodes an event, returning the topic and payload to publish it with, along
+    /// with the estimated size of the event.
+    fn encode(&mut self, mut event: Event) -> Option<(String, BytesMut, usize)> {
+        let topic = match self.topic.render_string(&event) {
+            Ok(topic) => topic,
+            Err(error) => {
+                emit!(TemplateRenderingError {
+                    error,
+                    field: Some("topic"),
+                    drop_event: true,
+                });
+                return None;
+            }
+        };
+
+        self.transformer.transform(&mut event);
+
+        let event_byte_size = event.estimated_json_encoded_size_of();
+
+        let mut bytes = BytesMut::new();
+        // Errors are handled by `Encoder`.
+        self.encoder.encode(event, &mut bytes).ok()?;
+
+        Some((topic, bytes, event_byte_size))
+    }
+}
+
+#[async_trait]
+impl StreamSink<Event> for MqttSink {
+    async fn run(mut self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let bytes_sent = register!(BytesSent::from(Protocol::TCP));
+        let qos = self.qos.into();
+
+        let (mut client, mut eventloop) = self.client();
+        let mut input = input.fuse();
+        let mut input_done = false;
+
+        // Publishes are queued to the event loop in order, which reports them back in the same
+        // order along with their packet ID. Deliveries complete once the broker acknowledges
+        // their packet ID, or as soon as they are written for `at_most_once`.
+        let mut pending = VecDeque::<PendingPublish>::new();
+        let mut inflight = HashMap::<u16, PendingPublish>::new();
+
+        let delivered = |publish: PendingPublish| {
+            publish.finalizers.update_status(EventStatus::Delivered);
+            emit!(EventsSent {
+                byte_size: publish.event_byte_size,
+                count: 1,
+                output: None,
+            });
+            bytes_sent.emit(ByteSize(publish.byte_size));
+        };
+
+        loop {
+            if input_done && pending.is_empty() && inflight.is_empty() {
+                break;
+            }
+            let has_capacity = pending.len() + inflight.len() < CLIENT_CHANNEL_CAPACITY;
+
+            tokio::select! {
+                event = input.next(), if has_capacity && !input_done => {
+                    let mut event = match event {
+                        Some(event) => event,
+                        None => {
+                            input_done = true;
+                            continue;
+                        }
+                    };
+                    let finalizers = event.take_finalizers();
+
+                    let (topic, bytes, event_byte_size) = match self.encode(event) {
+                        Some(encoded) => encoded,
+                        None => {
+                            finalizers.update_status(EventStatus::Rejected);
+                            continue;
+                        }
+                    };
+                    let byte_size = bytes.len();
+
+                    match client.try_publish(topic, qos, self.retain, bytes.to_vec()) {
+                        Ok(()) => pending.push_back(PendingPublish {
+                            finalizers,
+                            event_byte_size,
+                            byte_size,
+                        }),
+                        Err(error) => {
+                            finalizers.update_status(EventStatus::Errored);
+                            emit!(MqttPublishError { error });
+                        }
+                    }
+                }
+                polled = eventloop.poll() => match polled {
+                    Ok(MqttEvent::Outgoing(Outgoing::Publish(pkid))) => {
+                        if let Some(publish) = pending.pop_front() {
+                            if self.qos == MqttQoS::AtMostOnce {
+                                delivered(publish);
+                            } else {
+                                inflight.insert(pkid, publish);
+                            }
+                        }
+                    }
+                    Ok(MqttEvent::Incoming(Packet::PubAck(ack))) => {
+                        if let Some(publish) = inflight.remove(&ack.pkid) {
+                            delivered(publish);
+                        }
+                    }
+                    Ok(MqttEvent::Incoming(Packet::PubComp(comp))) => {
+                        if let Some(publish) = inflight.remove(&comp.pkid) {
+                            delivered(publish);
+                        }
+                    }
+                    Ok(_) => {}
+                    Err(error) => {
+                        emit!(MqttConnectionError {
+                            error,
+                            stage: error_stage::SENDING,
+                        });
+
+                        // The event loop would retransmit unacknowledged publishes on reconnect,
+                        // which can't be matched back to their events. Fail them instead, and
+                        // start over with a fresh client.
+                        for publish in pending.drain(..).chain(inflight.drain().map(|(_, p)| p)) {
+                            publish.finalizers.update_status(EventStatus::Errored);
+                        }
+                        (client, eventloop) = self.client();
+                        tokio::time::sleep(RECONNECT_DELAY).await;
+                    }
+                }
+            }
+        }
+
+        let _ = client.try_disconnect();
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<MqttSinkConfig>();
+    }
+
+    #[test]
+    fn rejects_invalid_topic_template() {
+        let config: MqttSinkConfig = toml::from_str(
+            r#"
+            connection.host = "localhost"
+            topic = "logs/{{ foo"
+            encoding.codec = "json"
+        "#,
+        )
+        .unwrap();
+
+        assert!(MqttSink::new(config).is_err());
+    }
+}
+
+#[cfg(feature = "mqtt-integration-tests")]
+#[cfg(test)]
+mod integration_tests {
+    use codecs::TextSerializerConfig;
+    use rumqttc::QoS;
+
+    use super::*;
+    use crate::sinks::VectorSink;
+    use crate::test_util::{
+        components::{run_and_assert_sink_compliance, SINK_TAGS},
+        random_lines_with_stream, random_string, trace_init,
+    };
+
+    fn mqtt_host() -> String {
+        std::env::var("MQTT_HOST").unwrap_or_else(|_| "localhost".into())
+    }
+
+    fn make_config(topic: &str) -> MqttSinkConfig {
+        MqttSinkConfig {
+            connection: MqttConnectionConfig {
+                host: mqtt_host(),
+                client_id: Some(format!("vector-{}", random_string(10))),
+                ..Default::default()
+            },
+            topic: topic.to_string(),
+            retain: false,
+            qos: MqttQoS::AtLeastOnce,
+            encoding: TextSerializerConfig::new().into(),
+            acknowledgements: Default::default(),
+        }
+    }
+
+    /// Subscribes a separate client to `topic`, returning it once subscribed.
+    async fn subscribe(topic: &str) -> (AsyncClient, EventLoop) {
+        let options =
+            MqttOptions::new(format!("consumer-{}", random_string(10)), mqtt_host(), 1883);
+        let (client, mut eventloop) = AsyncClient::new(options, 16);
+        client.subscribe(topic, QoS::AtLeastOnce).await.unwrap();
+        loop {
+            if let MqttEvent::Incoming(Packet::SubAck(_)) = eventloop.poll().await.unwrap() {
+                break;
+            }
+        }
+        (client, eventloop)
+    }
+
+    #[tokio::test]
+    async fn mqtt_happy() {
+        trace_init();
+
+        let topic = format!("test-{}", random_string(10));
+        let config = make_config(&topic);
+        let (_client, mut consumer) = subscribe(&topic).await;
+
+        let sink = VectorSink::from_event_streamsink(MqttSink::new(config).unwrap());
+        let num_events = 100;
+        let (input, events) = random_lines_with_stream(100, num_events, None);
+        run_and_assert_sink_compliance(sink, events, &SINK_TAGS).await;
+
+        let mut output = Vec::new();
+        while output.len() < num_events {
+            let event = tokio::time::timeout(Duration::from_secs(5), consumer.poll())
+                .await
+                .expect("timed out waiting for messages")
+                .unwrap();
+            if let MqttEvent::Incoming(Packet::Publish(publish)) = event {
+                assert_eq!(publish.topic, topic);
+                output.push(String::from_utf8_lossy(&publish.payload).to_string());
+            }
+        }
+
+        assert_eq!(output, input);
+    }
+
+    #[tokio::test]
+    async fn mqtt_healthcheck() {
+        trace_init();
+
+        let config = make_config("test");
+        healthcheck(config.connection).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn mqtt_healthcheck_bad_host() {
+        trace_init();
+
+        let mut config = make_config("test");
+        config.connection.host = "no-such-host".into();
+        healthcheck(config.connection).await.unwrap_err();
+    }
+}
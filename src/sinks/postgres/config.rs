@@ -0,0 +1,356 @@
+use std::sync::Arc;
+
+use futures::FutureExt;
+use snafu::{ResultExt, Snafu};
+use tower::ServiceBuilder;
+use vector_common::sensitive_string::SensitiveString;
+use vector_config::configurable_component;
+
+use crate::{
+    codecs::Transformer,
+    config::{AcknowledgementsConfig, GenerateConfig, Input, SinkConfig, SinkContext},
+    sinks::{
+        util::{BatchConfig, ServiceBuilderExt, SinkBatchSettings, TowerRequestConfig},
+        Healthcheck, VectorSink,
+    },
+    tls::TlsEnableableConfig,
+};
+
+use super::{
+    pool::PostgresPool,
+    service::{PostgresRetryLogic, PostgresService},
+    sink::PostgresSink,
+    sql::Statements,
+};
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("invalid endpoint: {}", source))]
+    InvalidEndpoint { source: tokio_postgres::Error },
+    #[snafu(display("at least one column is required"))]
+    NoColumns,
+    #[snafu(display("`on_conflict` is only supported with the `insert` method"))]
+    OnConflictRequiresInsert,
+    #[snafu(display("`on_conflict.action = \"update\"` requires `on_conflict.target` to be set"))]
+    UpdateRequiresTarget,
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct PostgresDefaultBatchSettings;
+
+impl SinkBatchSettings for PostgresDefaultBatchSettings {
+    const MAX_EVENTS: Option<usize> = Some(1000);
+    const MAX_BYTES: Option<usize> = Some(10_000_000);
+    const TIMEOUT_SECS: f64 = 1.0;
+}
+
+/// Configuration for the `postgres` sink.
+#[configurable_component(sink("postgres"))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct PostgresConfig {
+    /// The PostgreSQL server to connect to, as a connection string.
+    ///
+    /// Both the [key/value format][keyvalue] and the [URI format][uri] are supported.
+    ///
+    /// [keyvalue]: https://www.postgresql.org/docs/current/libpq-connect.html#id-1.7.3.8.3.5
+    /// [uri]: https://www.postgresql.org/docs/current/libpq-connect.html#id-1.7.3.8.3.6
+    pub endpoint: SensitiveString,
+
+    /// The table to insert events into.
+    ///
+    /// The table can be qualified with its schema, such as `public.logs`.
+    pub table: String,
+
+    /// The columns to insert, along with the event field to fill each of them with.
+    pub columns: Vec<PostgresColumn>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub method: PostgresInsertMethod,
+
+    #[configurable(derived)]
+    pub on_conflict: Option<OnConflictConfig>,
+
+    /// The maximum number of connections to keep open to the server.
+    #[serde(default = "default_pool_size")]
+    pub pool_size: usize,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    pub encoding: Transformer,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub batch: BatchConfig<PostgresDefaultBatchSettings>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub request: TowerRequestConfig,
+
+    #[configurable(derived)]
+    pub tls: Option<TlsEnableableConfig>,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        deserialize_with = "crate::serde::bool_or_struct",
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    pub acknowledgements: AcknowledgementsConfig,
+}
+
+/// A column of the table, and the event field to fill it with.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct PostgresColumn {
+    /// The name of the column.
+    pub name: String,
+
+    /// The event field to fill the column with.
+    ///
+    /// Defaults to the field with the same name as the column. Columns whose field is missing
+    /// from an event are set to `NULL`.
+    pub field: Option<String>,
+}
+
+impl PostgresColumn {
+    pub(super) fn field(&self) -> &str {
+        self.field.as_deref().unwrap_or(&self.name)
+    }
+}
+
+/// The method used to insert batches of events.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum PostgresInsertMethod {
+    /// Insert each batch with multi-row `INSERT` statements, within a single transaction.
+    ///
+    /// This is the only method supporting `on_conflict`.
+    Insert,
+
+    /// Stream each batch with `COPY ... FROM STDIN` in the CSV format.
+    ///
+    /// The server parses the text of each value into the type of its column, so this method
+    /// supports columns of any type.
+    CopyCsv,
+
+    /// Stream each batch with `COPY ... FROM STDIN` in the binary format.
+    ///
+    /// This is the fastest method, but only supports boolean, integer, floating point, text,
+    /// `bytea`, `json`, `jsonb` and timestamp columns.
+    CopyBinary,
+}
+
+impl Default for PostgresInsertMethod {
+    fn default() -> Self {
+        Self::Insert
+    }
+}
+
+/// How to handle rows conflicting with existing ones, through `INSERT ... ON CONFLICT`.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct OnConflictConfig {
+    /// The columns of the unique index or constraint to detect conflicts on.
+    ///
+    /// Conflicts are detected on any unique index or constraint when empty, which is only
+    /// supported by the `nothing` action.
+    #[serde(default)]
+    pub target: Vec<String>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub action: ConflictAction,
+}
+
+/// The action taken on conflicting rows.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ConflictAction {
+    /// Skip conflicting rows, through `DO NOTHING`.
+    Nothing,
+
+    /// Update the existing rows with the values of the conflicting ones, through `DO UPDATE`.
+    ///
+    /// All the columns except the ones of the target are updated.
+    Update,
+}
+
+impl Default for ConflictAction {
+    fn default() -> Self {
+        Self::Nothing
+    }
+}
+
+const fn default_pool_size() -> usize {
+    4
+}
+
+impl GenerateConfig for PostgresConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"
+            endpoint = "postgres://postgres@localhost/vector"
+            table = "logs"
+            columns = [
+                { name = "timestamp" },
+                { name = "host" },
+                { name = "message" },
+            ]
+            "#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+impl SinkConfig for PostgresConfig {
+    async fn build(&self, _cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        let statements = Arc::new(self.statements()?);
+        let pg_config: tokio_postgres::Config = self
+            .endpoint
+            .inner()
+            .parse()
+            .context(InvalidEndpointSnafu)?;
+        let pool = Arc::new(PostgresPool::new(pg_config, &self.tls, self.pool_size)?);
+
+        let healthcheck = healthcheck(Arc::clone(&pool), Arc::clone(&statements)).boxed();
+
+        let batch_settings = self.batch.into_batcher_settings()?;
+        let request = self.request.unwrap_with(&TowerRequestConfig::default());
+        let service = ServiceBuilder::new()
+            .settings(request, PostgresRetryLogic)
+            .service(PostgresService::new(pool, statements, self.method));
+
+        let sink = PostgresSink::new(
+            service,
+            self.columns.clone(),
+            self.encoding.clone(),
+            batch_settings,
+        );
+
+        Ok((VectorSink::from_event_streamsink(sink), healthcheck))
+    }
+
+    fn input(&self) -> Input {
+        Input::log()
+    }
+
+    fn acknowledgements(&self) -> &AcknowledgementsConfig {
+        &self.acknowledgements
+    }
+}
+
+impl PostgresConfig {
+    fn statements(&self) -> Result<Statements, BuildError> {
+        if self.columns.is_empty() {
+            return Err(BuildError::NoColumns);
+        }
+
+        if let Some(on_conflict) = &self.on_conflict {
+            if self.method != PostgresInsertMethod::Insert {
+                return Err(BuildError::OnConflictRequiresInsert);
+            }
+            if on_conflict.action == ConflictAction::Update && on_conflict.target.is_empty() {
+                return Err(BuildError::UpdateRequiresTarget);
+            }
+        }
+
+        Ok(Statements::new(
+            &self.table,
+            self.columns.iter().map(|column| column.name.as_str()),
+            self.on_conflict.as_ref(),
+        ))
+    }
+}
+
+/// Checks that the server is reachable, and that the table and its columns exist.
+async fn healthcheck(pool: Arc<PostgresPool>, statements: Arc<Statements>) -> crate::Result<()> {
+    let client = pool.get().await?;
+    client.prepare(&statements.select()).await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<PostgresConfig>();
+    }
+
+    fn parse(s: &str) -> PostgresConfig {
+        toml::from_str(s).unwrap()
+    }
+
+    #[test]
+    fn column_field_defaults_to_name() {
+        let config = parse(
+            r#"
+            endpoint = "postgres://localhost/vector"
+            table = "logs"
+            columns = [{ name = "message" }, { name = "host", field = "hostname" }]
+            "#,
+        );
+
+        assert_eq!(config.columns[0].field(), "message");
+        assert_eq!(config.columns[1].field(), "hostname");
+    }
+
+    #[test]
+    fn requires_columns() {
+        let config = parse(
+            r#"
+            endpoint = "postgres://localhost/vector"
+            table = "logs"
+            columns = []
+            "#,
+        );
+
+        assert!(matches!(config.statements(), Err(BuildError::NoColumns)));
+    }
+
+    #[test]
+    fn on_conflict_requires_insert() {
+        let config = parse(
+            r#"
+            endpoint = "postgres://localhost/vector"
+            table = "logs"
+            columns = [{ name = "message" }]
+            method = "copy_binary"
+            on_conflict.action = "nothing"
+            "#,
+        );
+
+        assert!(matches!(
+            config.statements(),
+            Err(BuildError::OnConflictRequiresInsert)
+        ));
+    }
+
+    #[test]
+    fn update_requires_target() {
+        let config = parse(
+            r#"
+            endpoint = "postgres://localhost/vector"
+            table = "logs"
+            columns = [{ name = "message" }]
+            on_conflict.action = "update"
+            "#,
+        );
+
+        assert!(matches!(
+            config.statements(),
+            Err(BuildError::UpdateRequiresTarget)
+        ));
+    }
+}
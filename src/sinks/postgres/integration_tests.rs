@@ -0,0 +1,207 @@
+use futures::stream;
+use tokio_postgres::{Client, NoTls};
+use vector_core::event::{BatchNotifier, BatchStatus, BatchStatusReceiver, Event, LogEvent};
+
+use super::config::PostgresConfig;
+use crate::{
+    config::{SinkConfig, SinkContext},
+    test_util::{
+        components::{run_and_assert_sink_compliance, SINK_TAGS},
+        random_string, trace_init,
+    },
+    tls,
+};
+
+fn pg_url() -> String {
+    std::env::var("PG_URL").unwrap_or_else(|_| {
+        let host = std::env::var("PG_HOST").unwrap_or_else(|_| "localhost".into());
+        format!("postgres://vector:vector@{}/postgres", host)
+    })
+}
+
+async fn connect() -> Client {
+    let (client, connection) = tokio_postgres::connect(&pg_url(), NoTls).await.unwrap();
+    tokio::spawn(connection);
+    client
+}
+
+/// Creates a table with a random name, returning its name.
+async fn create_table(client: &Client) -> String {
+    let table = format!("test_{}", random_string(10).to_lowercase());
+    client
+        .execute(
+            format!(
+                "CREATE TABLE {} (
+                    id BIGINT PRIMARY KEY,
+                    message TEXT,
+                    level SMALLINT,
+                    payload JSONB,
+                    timestamp TIMESTAMPTZ
+                )",
+                table
+            )
+            .as_str(),
+            &[],
+        )
+        .await
+        .unwrap();
+    table
+}
+
+fn config(table: &str, extra: &str) -> PostgresConfig {
+    toml::from_str(&format!(
+        r#"
+        endpoint = "{}"
+        table = "{}"
+        columns = [
+            {{ name = "id" }},
+            {{ name = "message" }},
+            {{ name = "level", field = "severity" }},
+            {{ name = "payload" }},
+            {{ name = "timestamp" }},
+        ]
+        batch.max_events = 10
+        {}
+        "#,
+        pg_url(),
+        table,
+        extra
+    ))
+    .unwrap()
+}
+
+fn make_events(count: i64, message: &str) -> (Vec<Event>, BatchStatusReceiver) {
+    let (batch, receiver) = BatchNotifier::new_with_receiver();
+    let events = (0..count)
+        .map(|id| {
+            let mut log = LogEvent::from(message).with_batch_notifier(&batch);
+            log.insert("id", id);
+            log.insert("severity", "3");
+            log.insert("payload.id", id);
+            Event::from(log)
+        })
+        .collect();
+    (events, receiver)
+}
+
+async fn run(config: PostgresConfig, events: Vec<Event>) {
+    let (sink, healthcheck) = config.build(SinkContext::new_test()).await.unwrap();
+    healthcheck.await.expect("healthcheck failed");
+    run_and_assert_sink_compliance(sink, stream::iter(events), &SINK_TAGS).await;
+}
+
+async fn insert_events(extra: &str) {
+    trace_init();
+
+    let client = connect().await;
+    let table = create_table(&client).await;
+
+    let (events, receiver) = make_events(25, "hello");
+    run(config(&table, extra), events).await;
+    assert_eq!(receiver.await, BatchStatus::Delivered);
+
+    let rows = client
+        .query(
+            format!(
+                "SELECT id, message, level, payload FROM {} ORDER BY id",
+                table
+            )
+            .as_str(),
+            &[],
+        )
+        .await
+        .unwrap();
+    assert_eq!(rows.len(), 25);
+    for (id, row) in rows.iter().enumerate() {
+        assert_eq!(row.get::<_, i64>("id"), id as i64);
+        assert_eq!(row.get::<_, String>("message"), "hello");
+        assert_eq!(row.get::<_, i16>("level"), 3);
+    }
+}
+
+#[tokio::test]
+async fn insert() {
+    insert_events(r#"method = "insert""#).await;
+}
+
+#[tokio::test]
+async fn copy_binary() {
+    insert_events(r#"method = "copy_binary""#).await;
+}
+
+#[tokio::test]
+async fn copy_csv() {
+    insert_events(r#"method = "copy_csv""#).await;
+}
+
+#[tokio::test]
+async fn insert_tls() {
+    insert_events(&format!(
+        r#"
+        tls.enabled = true
+        tls.ca_file = "{}"
+        tls.verify_hostname = false
+        "#,
+        tls::TEST_PEM_CA_PATH
+    ))
+    .await;
+}
+
+#[tokio::test]
+async fn on_conflict_update() {
+    trace_init();
+
+    let client = connect().await;
+    let table = create_table(&client).await;
+    let config = config(
+        &table,
+        r#"
+        on_conflict.target = ["id"]
+        on_conflict.action = "update"
+        "#,
+    );
+
+    let (events, _) = make_events(5, "first");
+    run(config.clone(), events).await;
+    let (events, receiver) = make_events(5, "second");
+    run(config, events).await;
+    assert_eq!(receiver.await, BatchStatus::Delivered);
+
+    let rows = client
+        .query(format!("SELECT message FROM {}", table).as_str(), &[])
+        .await
+        .unwrap();
+    assert_eq!(rows.len(), 5);
+    assert!(rows
+        .iter()
+        .all(|row| row.get::<_, String>("message") == "second"));
+}
+
+#[tokio::test]
+async fn invalid_value_rejects_batch() {
+    trace_init();
+
+    let client = connect().await;
+    let table = create_table(&client).await;
+
+    let (mut events, receiver) = make_events(1, "hello");
+    events[0].as_mut_log().insert("severity", "not a number");
+
+    let (sink, _) = config(&table, "")
+        .build(SinkContext::new_test())
+        .await
+        .unwrap();
+    sink.run_events(events).await.unwrap();
+    assert_eq!(receiver.await, BatchStatus::Rejected);
+}
+
+#[tokio::test]
+async fn healthcheck_missing_table() {
+    trace_init();
+
+    let (_, healthcheck) = config("no_such_table", "")
+        .build(SinkContext::new_test())
+        .await
+        .unwrap();
+    healthcheck.await.unwrap_err();
+}
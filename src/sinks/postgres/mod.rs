@@ -0,0 +1,14 @@
+//! `PostgreSQL` sink.
+//! Inserts batches of log events into a table, either with multi-row `INSERT` statements or
+//! with `COPY ... FROM STDIN`.
+mod config;
+mod pool;
+mod service;
+mod sink;
+mod sql;
+mod value;
+
+#[cfg(all(test, feature = "postgres-integration-tests"))]
+mod integration_tests;
+
+pub use self::config::PostgresConfig;
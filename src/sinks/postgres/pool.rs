@@ -0,0 +1,122 @@
+//! A pool of connections to the server, shared by the requests of the `postgres` sink.
+use std::{
+    ops::{Deref, DerefMut},
+    sync::{Arc, Mutex},
+};
+
+use postgres_openssl::MakeTlsConnector;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+use tokio_postgres::{config::SslMode, Client, Config, Error as PgError, NoTls};
+
+use crate::tls::{tls_connector_builder, MaybeTlsSettings, TlsEnableableConfig};
+
+/// Connections are opened on demand, up to `size` of them, and kept open between requests.
+pub(super) struct PostgresPool {
+    config: Config,
+    tls: Option<MakeTlsConnector>,
+    idle: Mutex<Vec<Client>>,
+    permits: Arc<Semaphore>,
+}
+
+impl PostgresPool {
+    pub(super) fn new(
+        mut config: Config,
+        tls: &Option<TlsEnableableConfig>,
+        size: usize,
+    ) -> crate::Result<Self> {
+        let tls = match MaybeTlsSettings::from_config(tls, false)? {
+            MaybeTlsSettings::Tls(settings) => {
+                let builder = tls_connector_builder(&MaybeTlsSettings::Tls(settings.clone()))?;
+                let mut connector = MakeTlsConnector::new(builder.build());
+                connector.set_callback(move |connection, _domain| {
+                    settings.apply_connect_configuration(connection);
+                    Ok(())
+                });
+                // Don't fall back to plain text connections when TLS is configured.
+                config.ssl_mode(SslMode::Require);
+                Some(connector)
+            }
+            MaybeTlsSettings::Raw(()) => None,
+        };
+
+        Ok(Self {
+            config,
+            tls,
+            idle: Mutex::new(Vec::new()),
+            permits: Arc::new(Semaphore::new(size.max(1))),
+        })
+    }
+
+    /// Takes an idle connection, or opens a new one if there are none.
+    ///
+    /// Waits for a connection to be returned when `size` of them are already in use.
+    pub(super) async fn get(self: &Arc<Self>) -> Result<PooledClient, PgError> {
+        let permit = Arc::clone(&self.permits)
+            .acquire_owned()
+            .await
+            .expect("pool semaphore is never closed");
+
+        let idle = loop {
+            match self.idle.lock().expect("mutex poisoned").pop() {
+                Some(client) if client.is_closed() => continue,
+                client => break client,
+            }
+        };
+        let client = match idle {
+            Some(client) => client,
+            None => self.connect().await?,
+        };
+
+        Ok(PooledClient {
+            client: Some(client),
+            pool: Arc::clone(self),
+            _permit: permit,
+        })
+    }
+
+    async fn connect(&self) -> Result<Client, PgError> {
+        match &self.tls {
+            Some(tls) => {
+                let (client, connection) = self.config.connect(tls.clone()).await?;
+                tokio::spawn(connection);
+                Ok(client)
+            }
+            None => {
+                let (client, connection) = self.config.connect(NoTls).await?;
+                tokio::spawn(connection);
+                Ok(client)
+            }
+        }
+    }
+}
+
+/// A connection taken from the pool, returned to it when dropped unless it was closed.
+pub(super) struct PooledClient {
+    client: Option<Client>,
+    pool: Arc<PostgresPool>,
+    _permit: OwnedSemaphorePermit,
+}
+
+impl Deref for PooledClient {
+    type Target = Client;
+
+    fn deref(&self) -> &Client {
+        self.client.as_ref().expect("client is only taken on drop")
+    }
+}
+
+impl DerefMut for PooledClient {
+    fn deref_mut(&mut self) -> &mut Client {
+        self.client.as_mut().expect("client is only taken on drop")
+    }
+}
+
+impl Drop for PooledClient {
+    fn drop(&mut self) {
+        if let Some(client) = self.client.take() {
+            if !client.is_closed() {
+                self.pool.idle.lock().expect("mutex poisoned").push(client);
+            }
+        }
+    }
+}
@@ -0,0 +1,327 @@
+//! The tower service that inserts the rows of a request into the table.
+use std::{
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+use bytes::{BufMut, BytesMut};
+use futures::{future::BoxFuture, pin_mut, SinkExt};
+use snafu::{ResultExt, Snafu};
+use tokio::sync::OnceCell;
+use tokio_postgres::{
+    binary_copy::BinaryCopyInWriter,
+    error::SqlState,
+    types::{ToSql, Type},
+    Client,
+};
+use tower::Service;
+use vector_common::{
+    finalization::{EventFinalizers, EventStatus, Finalizable},
+    internal_event::CountByteSize,
+    request_metadata::{MetaDescriptive, RequestMetadata},
+};
+use vector_core::{event::Value, stream::DriverResponse};
+
+use super::{
+    config::PostgresInsertMethod,
+    pool::PostgresPool,
+    sql::Statements,
+    value::{ConversionError, SqlValue},
+};
+use crate::sinks::util::retries::RetryLogic;
+
+/// The rows to insert, holding the value of each column in order.
+#[derive(Clone)]
+pub(super) struct PostgresRequest {
+    pub(super) rows: Vec<Vec<Option<Value>>>,
+    pub(super) finalizers: EventFinalizers,
+    pub(super) metadata: RequestMetadata,
+}
+
+impl Finalizable for PostgresRequest {
+    fn take_finalizers(&mut self) -> EventFinalizers {
+        std::mem::take(&mut self.finalizers)
+    }
+}
+
+impl MetaDescriptive for PostgresRequest {
+    fn get_metadata(&self) -> RequestMetadata {
+        self.metadata
+    }
+}
+
+pub(super) struct PostgresResponse {
+    metadata: RequestMetadata,
+}
+
+impl DriverResponse for PostgresResponse {
+    fn event_status(&self) -> EventStatus {
+        EventStatus::Delivered
+    }
+
+    fn events_sent(&self) -> CountByteSize {
+        CountByteSize(
+            self.metadata.event_count(),
+            self.metadata.events_byte_size(),
+        )
+    }
+
+    fn bytes_sent(&self) -> Option<(usize, &str)> {
+        Some((self.metadata.request_encoded_size(), "postgres"))
+    }
+}
+
+#[derive(Debug, Snafu)]
+pub(super) enum PostgresError {
+    #[snafu(display("failed to connect: {}", source))]
+    Connect { source: tokio_postgres::Error },
+    #[snafu(display("failed to insert rows: {}", source))]
+    Insert { source: tokio_postgres::Error },
+    #[snafu(display("failed to look up the types of the columns: {}", source))]
+    ColumnTypes { source: tokio_postgres::Error },
+    #[snafu(display(
+        "column `{}` has type `{}`, which is only supported by the `copy_csv` method",
+        column,
+        ty
+    ))]
+    UnsupportedType { column: String, ty: Type },
+    #[snafu(display("invalid value for column `{}`: {}", column, source))]
+    Conversion {
+        column: String,
+        source: ConversionError,
+    },
+}
+
+#[derive(Debug, Default, Clone)]
+pub(super) struct PostgresRetryLogic;
+
+impl RetryLogic for PostgresRetryLogic {
+    type Error = PostgresError;
+    type Response = PostgresResponse;
+
+    fn is_retriable_error(&self, error: &Self::Error) -> bool {
+        match error {
+            PostgresError::Connect { .. } => true,
+            PostgresError::Insert { source } | PostgresError::ColumnTypes { source } => {
+                match source.code() {
+                    // Errors without a code come from the connection rather than the server.
+                    None => true,
+                    Some(code) => is_transient(code),
+                }
+            }
+            PostgresError::UnsupportedType { .. } | PostgresError::Conversion { .. } => false,
+        }
+    }
+}
+
+/// Whether the server failed for reasons unrelated to the rows, such as a lack of resources or
+/// a conflict with a concurrent transaction.
+fn is_transient(code: &SqlState) -> bool {
+    let class = &code.code()[..2];
+    // Connection exceptions, transaction rollbacks, insufficient resources, and operator
+    // interventions such as shutdowns.
+    matches!(class, "08" | "40" | "53" | "57")
+}
+
+#[derive(Clone)]
+pub(super) struct PostgresService {
+    pool: Arc<PostgresPool>,
+    statements: Arc<Statements>,
+    method: PostgresInsertMethod,
+    column_types: Arc<OnceCell<Vec<Type>>>,
+}
+
+impl PostgresService {
+    pub(super) fn new(
+        pool: Arc<PostgresPool>,
+        statements: Arc<Statements>,
+        method: PostgresInsertMethod,
+    ) -> Self {
+        Self {
+            pool,
+            statements,
+            method,
+            column_types: Arc::new(OnceCell::new()),
+        }
+    }
+}
+
+impl Service<PostgresRequest> for PostgresService {
+    type Response = PostgresResponse;
+    type Error = PostgresError;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: PostgresRequest) -> Self::Future {
+        let service = self.clone();
+
+        Box::pin(async move {
+            let mut client = service.pool.get().await.context(ConnectSnafu)?;
+            let statements = &service.statements;
+
+            match service.method {
+                PostgresInsertMethod::Insert => {
+                    let types = service.column_types(&client).await?;
+                    let rows = convert_rows(&request.rows, statements.column_names(), types)?;
+                    insert(&mut client, statements, rows).await?;
+                }
+                PostgresInsertMethod::CopyBinary => {
+                    let types = service.column_types(&client).await?;
+                    let rows = convert_rows(&request.rows, statements.column_names(), types)?;
+                    copy_binary(&client, statements, types, rows).await?;
+                }
+                PostgresInsertMethod::CopyCsv => {
+                    copy_csv(&client, statements, &request.rows).await?;
+                }
+            }
+
+            Ok(PostgresResponse {
+                metadata: request.metadata,
+            })
+        })
+    }
+}
+
+impl PostgresService {
+    /// Looks up the types of the columns the first time they're needed.
+    async fn column_types(&self, client: &Client) -> Result<&[Type], PostgresError> {
+        let types = self
+            .column_types
+            .get_or_try_init(|| async {
+                let statement = client
+                    .prepare(&self.statements.select())
+                    .await
+                    .context(ColumnTypesSnafu)?;
+
+                statement
+                    .columns()
+                    .iter()
+                    .map(|column| {
+                        let ty = column.type_().clone();
+                        if SqlValue::supports(&ty) {
+                            Ok(ty)
+                        } else {
+                            Err(PostgresError::UnsupportedType {
+                                column: column.name().to_owned(),
+                                ty,
+                            })
+                        }
+                    })
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .await?;
+
+        Ok(types)
+    }
+}
+
+fn convert_rows(
+    rows: &[Vec<Option<Value>>],
+    names: &[String],
+    types: &[Type],
+) -> Result<Vec<Vec<SqlValue>>, PostgresError> {
+    rows.iter()
+        .map(|row| {
+            row.iter()
+                .zip(names.iter().zip(types))
+                .map(|(value, (name, ty))| {
+                    SqlValue::new(value.as_ref(), ty)
+                        .with_context(|_| ConversionSnafu { column: name })
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Inserts the rows within a transaction, with as many statements as the limit on the number of
+/// parameters requires.
+async fn insert(
+    client: &mut Client,
+    statements: &Statements,
+    rows: Vec<Vec<SqlValue>>,
+) -> Result<(), PostgresError> {
+    let transaction = client.transaction().await.context(InsertSnafu)?;
+
+    for chunk in rows.chunks(statements.max_rows_per_insert()) {
+        let params = chunk
+            .iter()
+            .flatten()
+            .map(|value| value as &(dyn ToSql + Sync))
+            .collect::<Vec<_>>();
+        transaction
+            .execute(statements.insert(chunk.len()).as_str(), &params)
+            .await
+            .context(InsertSnafu)?;
+    }
+
+    transaction.commit().await.context(InsertSnafu)
+}
+
+async fn copy_binary(
+    client: &Client,
+    statements: &Statements,
+    types: &[Type],
+    rows: Vec<Vec<SqlValue>>,
+) -> Result<(), PostgresError> {
+    let sink = client
+        .copy_in(statements.copy("binary").as_str())
+        .await
+        .context(InsertSnafu)?;
+    let writer = BinaryCopyInWriter::new(sink, types);
+    pin_mut!(writer);
+
+    for row in &rows {
+        let values = row
+            .iter()
+            .map(|value| value as &(dyn ToSql + Sync))
+            .collect::<Vec<_>>();
+        writer.as_mut().write(&values).await.context(InsertSnafu)?;
+    }
+
+    writer.finish().await.context(InsertSnafu)?;
+    Ok(())
+}
+
+async fn copy_csv(
+    client: &Client,
+    statements: &Statements,
+    rows: &[Vec<Option<Value>>],
+) -> Result<(), PostgresError> {
+    let mut buffer = BytesMut::new();
+    for row in rows {
+        for (index, value) in row.iter().enumerate() {
+            if index > 0 {
+                buffer.put_u8(b',');
+            }
+            SqlValue::write_csv(value.as_ref(), &mut buffer);
+        }
+        buffer.put_u8(b'\n');
+    }
+
+    let sink = client
+        .copy_in(statements.copy("csv").as_str())
+        .await
+        .context(InsertSnafu)?;
+    pin_mut!(sink);
+
+    sink.send(buffer.freeze()).await.context(InsertSnafu)?;
+    sink.finish().await.context(InsertSnafu)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn transient_errors() {
+        assert!(is_transient(&SqlState::T_R_SERIALIZATION_FAILURE));
+        assert!(is_transient(&SqlState::TOO_MANY_CONNECTIONS));
+        assert!(is_transient(&SqlState::ADMIN_SHUTDOWN));
+        assert!(!is_transient(&SqlState::UNIQUE_VIOLATION));
+        assert!(!is_transient(&SqlState::INVALID_TEXT_REPRESENTATION));
+    }
+}
@@ -0,0 +1,108 @@
+//! The sink that batches events into rows and drives the service inserting them.
+use std::{fmt, num::NonZeroUsize};
+
+use async_trait::async_trait;
+use futures::{stream::BoxStream, StreamExt};
+use tower::Service;
+use vector_core::{
+    stream::{BatcherSettings, DriverResponse},
+    EstimatedJsonEncodedSizeOf,
+};
+
+use super::{config::PostgresColumn, service::PostgresRequest};
+use crate::{
+    codecs::Transformer,
+    event::{Event, Finalizable},
+    sinks::util::{metadata::RequestMetadataBuilder, SinkBuilderExt, StreamSink},
+};
+
+pub(super) struct PostgresSink<S> {
+    service: S,
+    columns: Vec<PostgresColumn>,
+    transformer: Transformer,
+    batch_settings: BatcherSettings,
+}
+
+impl<S> PostgresSink<S>
+where
+    S: Service<PostgresRequest> + Send + 'static,
+    S::Future: Send + 'static,
+    S::Response: DriverResponse + Send + 'static,
+    S::Error: fmt::Debug + Into<crate::Error> + Send,
+{
+    pub(super) const fn new(
+        service: S,
+        columns: Vec<PostgresColumn>,
+        transformer: Transformer,
+        batch_settings: BatcherSettings,
+    ) -> Self {
+        Self {
+            service,
+            columns,
+            transformer,
+            batch_settings,
+        }
+    }
+
+    async fn run_inner(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let Self {
+            service,
+            columns,
+            transformer,
+            batch_settings,
+        } = *self;
+
+        input
+            .batched(batch_settings.into_byte_size_config())
+            .map(|events| build_request(&columns, &transformer, events))
+            .into_driver(service)
+            .run()
+            .await
+    }
+}
+
+#[async_trait]
+impl<S> StreamSink<Event> for PostgresSink<S>
+where
+    S: Service<PostgresRequest> + Send + 'static,
+    S::Future: Send + 'static,
+    S::Response: DriverResponse + Send + 'static,
+    S::Error: fmt::Debug + Into<crate::Error> + Send,
+{
+    async fn run(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        self.run_inner(input).await
+    }
+}
+
+/// Builds a request out of a batch of events, with a row per event holding the values of the
+/// fields of the columns.
+fn build_request(
+    columns: &[PostgresColumn],
+    transformer: &Transformer,
+    mut events: Vec<Event>,
+) -> PostgresRequest {
+    let builder = RequestMetadataBuilder::from_events(&events);
+    // The rows are sent as parameters or `COPY` data rather than JSON, but the size of their JSON
+    // encoding is a close enough estimate of the size of the request.
+    let request_size = NonZeroUsize::new(events.estimated_json_encoded_size_of())
+        .expect("batches should never be empty");
+    let finalizers = events.take_finalizers();
+
+    let rows = events
+        .into_iter()
+        .map(|mut event| {
+            transformer.transform(&mut event);
+            let log = event.into_log();
+            columns
+                .iter()
+                .map(|column| log.get(column.field()).cloned())
+                .collect()
+        })
+        .collect();
+
+    PostgresRequest {
+        rows,
+        finalizers,
+        metadata: builder.with_request_size(request_size),
+    }
+}
@@ -0,0 +1,189 @@
+//! Builds the SQL statements run by the `postgres` sink.
+use super::config::{ConflictAction, OnConflictConfig};
+
+/// The maximum number of parameters of a statement, as limited by the wire protocol.
+const MAX_PARAMETERS: usize = u16::MAX as usize;
+
+/// The statements inserting rows into the table, with its identifiers already quoted.
+#[derive(Debug)]
+pub(super) struct Statements {
+    table: String,
+    names: Vec<String>,
+    columns: Vec<String>,
+    on_conflict: String,
+}
+
+impl Statements {
+    pub(super) fn new<'a>(
+        table: &str,
+        columns: impl IntoIterator<Item = &'a str>,
+        on_conflict: Option<&OnConflictConfig>,
+    ) -> Self {
+        let table = table
+            .split('.')
+            .map(quote_identifier)
+            .collect::<Vec<_>>()
+            .join(".");
+        let names = columns.into_iter().map(String::from).collect::<Vec<_>>();
+        let columns = names
+            .iter()
+            .map(|name| quote_identifier(name))
+            .collect::<Vec<_>>();
+        let on_conflict = on_conflict
+            .map(|on_conflict| on_conflict_clause(on_conflict, &columns))
+            .unwrap_or_default();
+
+        Self {
+            table,
+            names,
+            columns,
+            on_conflict,
+        }
+    }
+
+    /// The names of the columns, unquoted.
+    pub(super) fn column_names(&self) -> &[String] {
+        &self.names
+    }
+
+    /// The maximum number of rows a single `INSERT` statement can hold.
+    pub(super) fn max_rows_per_insert(&self) -> usize {
+        (MAX_PARAMETERS / self.columns.len()).max(1)
+    }
+
+    /// Selects the columns of the table, preparing it reveals their types.
+    pub(super) fn select(&self) -> String {
+        format!("SELECT {} FROM {}", self.columns.join(", "), self.table)
+    }
+
+    /// Inserts `rows` rows, with their values as parameters.
+    pub(super) fn insert(&self, rows: usize) -> String {
+        let width = self.columns.len();
+        let values = (0..rows)
+            .map(|row| {
+                let params = (1..=width)
+                    .map(|column| format!("${}", row * width + column))
+                    .collect::<Vec<_>>();
+                format!("({})", params.join(", "))
+            })
+            .collect::<Vec<_>>();
+
+        format!(
+            "INSERT INTO {} ({}) VALUES {}{}",
+            self.table,
+            self.columns.join(", "),
+            values.join(", "),
+            self.on_conflict
+        )
+    }
+
+    /// Streams rows in the given `COPY` format, either `csv` or `binary`.
+    pub(super) fn copy(&self, format: &str) -> String {
+        format!(
+            "COPY {} ({}) FROM STDIN WITH (FORMAT {})",
+            self.table,
+            self.columns.join(", "),
+            format
+        )
+    }
+}
+
+/// Quotes an identifier, so that it's taken verbatim rather than folded to lowercase.
+fn quote_identifier(identifier: &str) -> String {
+    format!("\"{}\"", identifier.replace('"', "\"\""))
+}
+
+fn on_conflict_clause(on_conflict: &OnConflictConfig, columns: &[String]) -> String {
+    let target = on_conflict
+        .target
+        .iter()
+        .map(|column| quote_identifier(column))
+        .collect::<Vec<_>>();
+
+    let mut clause = String::from(" ON CONFLICT");
+    if !target.is_empty() {
+        clause.push_str(&format!(" ({})", target.join(", ")));
+    }
+
+    let updates = columns
+        .iter()
+        .filter(|column| !target.contains(column))
+        .map(|column| format!("{} = EXCLUDED.{}", column, column))
+        .collect::<Vec<_>>();
+
+    match on_conflict.action {
+        // Updating nothing isn't valid SQL, which happens when all the columns are in the target.
+        ConflictAction::Update if !updates.is_empty() => {
+            clause.push_str(&format!(" DO UPDATE SET {}", updates.join(", ")));
+        }
+        ConflictAction::Update | ConflictAction::Nothing => clause.push_str(" DO NOTHING"),
+    }
+
+    clause
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn statements(on_conflict: Option<OnConflictConfig>) -> Statements {
+        Statements::new(
+            "public.logs",
+            ["id", "message", "weird\"name"],
+            on_conflict.as_ref(),
+        )
+    }
+
+    #[test]
+    fn select() {
+        assert_eq!(
+            statements(None).select(),
+            r#"SELECT "id", "message", "weird""name" FROM "public"."logs""#
+        );
+    }
+
+    #[test]
+    fn insert() {
+        assert_eq!(
+            statements(None).insert(2),
+            r#"INSERT INTO "public"."logs" ("id", "message", "weird""name") VALUES ($1, $2, $3), ($4, $5, $6)"#
+        );
+    }
+
+    #[test]
+    fn insert_on_conflict_do_nothing() {
+        let statements = statements(Some(OnConflictConfig {
+            target: vec![],
+            action: ConflictAction::Nothing,
+        }));
+
+        assert!(statements
+            .insert(1)
+            .ends_with("($1, $2, $3) ON CONFLICT DO NOTHING"));
+    }
+
+    #[test]
+    fn insert_on_conflict_do_update() {
+        let statements = statements(Some(OnConflictConfig {
+            target: vec!["id".into()],
+            action: ConflictAction::Update,
+        }));
+
+        assert!(statements.insert(1).ends_with(
+            r#"ON CONFLICT ("id") DO UPDATE SET "message" = EXCLUDED."message", "weird""name" = EXCLUDED."weird""name""#
+        ));
+    }
+
+    #[test]
+    fn copy() {
+        assert_eq!(
+            statements(None).copy("binary"),
+            r#"COPY "public"."logs" ("id", "message", "weird""name") FROM STDIN WITH (FORMAT binary)"#
+        );
+    }
+
+    #[test]
+    fn max_rows_per_insert() {
+        assert_eq!(statements(None).max_rows_per_insert(), 21845);
+    }
+}
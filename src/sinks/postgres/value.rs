@@ -0,0 +1,251 @@
+//! Converts event values into the types of the columns of the table.
+use std::error::Error;
+
+use bytes::{BufMut, BytesMut};
+use chrono::{DateTime, NaiveDateTime, TimeZone, Utc};
+use snafu::Snafu;
+use tokio_postgres::types::{to_sql_checked, IsNull, ToSql, Type};
+use vector_core::event::Value;
+
+#[derive(Debug, Snafu)]
+pub(super) enum ConversionError {
+    #[snafu(display("cannot convert {} value to column type `{}`", kind, ty))]
+    Incompatible { kind: String, ty: Type },
+}
+
+/// A value converted for a column of a given type.
+///
+/// Values are only ever bound to the column they were converted for, so they accept any type.
+#[derive(Debug)]
+pub(super) enum SqlValue {
+    Null,
+    Bool(bool),
+    Int2(i16),
+    Int4(i32),
+    Int8(i64),
+    Float4(f32),
+    Float8(f64),
+    Text(String),
+    Bytea(Vec<u8>),
+    Json(Vec<u8>),
+    Timestamp(NaiveDateTime),
+    TimestampTz(DateTime<Utc>),
+}
+
+impl SqlValue {
+    /// Whether values can be converted for columns of type `ty`.
+    pub(super) fn supports(ty: &Type) -> bool {
+        [
+            Type::BOOL,
+            Type::INT2,
+            Type::INT4,
+            Type::INT8,
+            Type::FLOAT4,
+            Type::FLOAT8,
+            Type::TEXT,
+            Type::VARCHAR,
+            Type::BPCHAR,
+            Type::NAME,
+            Type::BYTEA,
+            Type::JSON,
+            Type::JSONB,
+            Type::TIMESTAMP,
+            Type::TIMESTAMPTZ,
+        ]
+        .contains(ty)
+    }
+
+    /// Converts a value for a column of type `ty`, with missing values becoming `NULL`.
+    ///
+    /// Strings are parsed into numbers, booleans and timestamps, as a lot of sources only produce
+    /// strings.
+    pub(super) fn new(value: Option<&Value>, ty: &Type) -> Result<Self, ConversionError> {
+        let value = match value {
+            None | Some(Value::Null) => return Ok(Self::Null),
+            Some(value) => value,
+        };
+        let incompatible = || ConversionError::Incompatible {
+            kind: value.kind_str().to_owned(),
+            ty: ty.clone(),
+        };
+
+        let converted = match *ty {
+            Type::BOOL => match value {
+                Value::Boolean(boolean) => Some(Self::Bool(*boolean)),
+                Value::Bytes(_) => value.to_string_lossy().parse().ok().map(Self::Bool),
+                _ => None,
+            },
+            Type::INT2 => integer(value).and_then(|int| int.try_into().ok().map(Self::Int2)),
+            Type::INT4 => integer(value).and_then(|int| int.try_into().ok().map(Self::Int4)),
+            Type::INT8 => integer(value).map(Self::Int8),
+            Type::FLOAT4 => float(value).map(|float| Self::Float4(float as f32)),
+            Type::FLOAT8 => float(value).map(Self::Float8),
+            Type::TEXT | Type::VARCHAR | Type::BPCHAR | Type::NAME => {
+                Some(Self::Text(value.to_string_lossy().into_owned()))
+            }
+            Type::BYTEA => match value {
+                Value::Bytes(bytes) => Some(Self::Bytea(bytes.to_vec())),
+                _ => Some(Self::Bytea(
+                    value.to_string_lossy().into_owned().into_bytes(),
+                )),
+            },
+            Type::JSON | Type::JSONB => serde_json::to_vec(value).ok().map(Self::Json),
+            Type::TIMESTAMP => {
+                timestamp(value).map(|timestamp| Self::Timestamp(timestamp.naive_utc()))
+            }
+            Type::TIMESTAMPTZ => timestamp(value).map(Self::TimestampTz),
+            _ => None,
+        };
+
+        converted.ok_or_else(incompatible)
+    }
+
+    /// Writes the value as a field of a CSV row, for `COPY ... WITH (FORMAT csv)`.
+    ///
+    /// The server parses the text of the field into the type of its column. Non-null fields are
+    /// always quoted, as an unquoted empty field means `NULL`.
+    pub(super) fn write_csv(value: Option<&Value>, buffer: &mut BytesMut) {
+        match value {
+            None | Some(Value::Null) => {}
+            Some(value) => {
+                buffer.put_u8(b'"');
+                buffer.put_slice(value.to_string_lossy().replace('"', "\"\"").as_bytes());
+                buffer.put_u8(b'"');
+            }
+        }
+    }
+}
+
+fn integer(value: &Value) -> Option<i64> {
+    match value {
+        Value::Integer(int) => Some(*int),
+        Value::Float(float) if float.fract() == 0.0 => Some(float.into_inner() as i64),
+        Value::Bytes(_) => value.to_string_lossy().trim().parse().ok(),
+        _ => None,
+    }
+}
+
+fn float(value: &Value) -> Option<f64> {
+    match value {
+        Value::Integer(int) => Some(*int as f64),
+        Value::Float(float) => Some(float.into_inner()),
+        Value::Bytes(_) => value.to_string_lossy().trim().parse().ok(),
+        _ => None,
+    }
+}
+
+/// Converts timestamps, RFC 3339 strings, and integers as seconds since the Unix epoch.
+fn timestamp(value: &Value) -> Option<DateTime<Utc>> {
+    match value {
+        Value::Timestamp(timestamp) => Some(*timestamp),
+        Value::Bytes(_) => DateTime::parse_from_rfc3339(value.to_string_lossy().trim())
+            .ok()
+            .map(|timestamp| timestamp.with_timezone(&Utc)),
+        Value::Integer(seconds) => Utc.timestamp_opt(*seconds, 0).single(),
+        _ => None,
+    }
+}
+
+impl ToSql for SqlValue {
+    fn to_sql(
+        &self,
+        ty: &Type,
+        out: &mut BytesMut,
+    ) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
+        match self {
+            Self::Null => Ok(IsNull::Yes),
+            Self::Bool(value) => value.to_sql(ty, out),
+            Self::Int2(value) => value.to_sql(ty, out),
+            Self::Int4(value) => value.to_sql(ty, out),
+            Self::Int8(value) => value.to_sql(ty, out),
+            Self::Float4(value) => value.to_sql(ty, out),
+            Self::Float8(value) => value.to_sql(ty, out),
+            Self::Text(value) => value.to_sql(ty, out),
+            Self::Bytea(value) => value.to_sql(ty, out),
+            Self::Json(value) => {
+                // The binary format of `jsonb` is its text prefixed with a version number.
+                if *ty == Type::JSONB {
+                    out.put_u8(1);
+                }
+                out.put_slice(value);
+                Ok(IsNull::No)
+            }
+            Self::Timestamp(value) => value.to_sql(ty, out),
+            Self::TimestampTz(value) => value.to_sql(ty, out),
+        }
+    }
+
+    fn accepts(_ty: &Type) -> bool {
+        true
+    }
+
+    to_sql_checked!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn convert(value: impl Into<Value>, ty: Type) -> Result<SqlValue, ConversionError> {
+        SqlValue::new(Some(&value.into()), &ty)
+    }
+
+    #[test]
+    fn missing_and_null_are_null() {
+        assert!(matches!(
+            SqlValue::new(None, &Type::INT4),
+            Ok(SqlValue::Null)
+        ));
+        assert!(matches!(
+            SqlValue::new(Some(&Value::Null), &Type::INT4),
+            Ok(SqlValue::Null)
+        ));
+    }
+
+    #[test]
+    fn integers() {
+        assert!(matches!(convert(42, Type::INT4), Ok(SqlValue::Int4(42))));
+        assert!(matches!(convert("42", Type::INT8), Ok(SqlValue::Int8(42))));
+        assert!(matches!(convert(2.0, Type::INT2), Ok(SqlValue::Int2(2))));
+        assert!(convert(70000, Type::INT2).is_err());
+        assert!(convert(2.5, Type::INT4).is_err());
+        assert!(convert("forty-two", Type::INT4).is_err());
+    }
+
+    #[test]
+    fn texts() {
+        assert!(matches!(convert("foo", Type::TEXT), Ok(SqlValue::Text(text)) if text == "foo"));
+        assert!(matches!(convert(42, Type::VARCHAR), Ok(SqlValue::Text(text)) if text == "42"));
+    }
+
+    #[test]
+    fn timestamps() {
+        let expected = Utc.ymd(2022, 11, 3).and_hms(12, 30, 0);
+        assert!(matches!(
+            convert("2022-11-03T12:30:00Z", Type::TIMESTAMPTZ),
+            Ok(SqlValue::TimestampTz(timestamp)) if timestamp == expected
+        ));
+        assert!(matches!(
+            convert(expected, Type::TIMESTAMP),
+            Ok(SqlValue::Timestamp(timestamp)) if timestamp == expected.naive_utc()
+        ));
+    }
+
+    #[test]
+    fn unsupported_type() {
+        assert!(!SqlValue::supports(&Type::NUMERIC));
+        assert!(convert(42, Type::NUMERIC).is_err());
+    }
+
+    #[test]
+    fn csv_fields() {
+        let mut buffer = BytesMut::new();
+        SqlValue::write_csv(Some(&Value::from("say \"hi\"")), &mut buffer);
+        buffer.put_u8(b',');
+        SqlValue::write_csv(None, &mut buffer);
+        buffer.put_u8(b',');
+        SqlValue::write_csv(Some(&Value::from("")), &mut buffer);
+
+        assert_eq!(&buffer[..], b"\"say \"\"hi\"\"\",,\"\"");
+    }
+}
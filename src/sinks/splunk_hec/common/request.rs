@@ -3,11 +3,11 @@ use std::sync::Arc;
 use bytes::Bytes;
 use vector_common::request_metadata::{MetaDescriptive, RequestMetadata};
 use vector_core::{
-    event::{EventFinalizers, Finalizable},
+    event::{Event, EventFinalizers, Finalizable},
     ByteSizeOf,
 };
 
-use crate::sinks::util::ElementCount;
+use crate::sinks::util::{dropped::DroppedEvents, ElementCount};
 
 #[derive(Clone, Debug)]
 pub struct HecRequest {
@@ -19,6 +19,7 @@ pub struct HecRequest {
     pub source: Option<String>,
     pub sourcetype: Option<String>,
     pub host: Option<String>,
+    pub dropped_events: Option<Vec<Event>>,
 }
 
 impl ByteSizeOf for HecRequest {
@@ -44,3 +45,9 @@ impl MetaDescriptive for HecRequest {
         self.metadata
     }
 }
+
+impl DroppedEvents for HecRequest {
+    fn take_dropped_events(&mut self) -> Option<Vec<Event>> {
+        self.dropped_events.take()
+    }
+}
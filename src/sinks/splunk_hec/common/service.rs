@@ -6,7 +6,7 @@ use std::{
 
 use bytes::Bytes;
 use futures_util::future::BoxFuture;
-use http::Request;
+use http::{Request, StatusCode};
 use serde::{Deserialize, Serialize};
 use snafu::ResultExt;
 use tokio::sync::{mpsc, oneshot, OwnedSemaphorePermit, Semaphore};
@@ -25,7 +25,11 @@ use crate::{
     internal_events::{SplunkIndexerAcknowledgementUnavailableError, SplunkResponseParseError},
     sinks::{
         splunk_hec::common::{build_uri, request::HecRequest, response::HecResponse},
-        util::{sink::Response, Compression},
+        util::{
+            dropped::{self, Rejection, RejectionLogic},
+            sink::Response,
+            Compression,
+        },
         UriParseSnafu,
     },
 };
@@ -167,6 +171,37 @@ where
     }
 }
 
+/// Decides which events Splunk permanently rejected, for rerouting them.
+#[derive(Clone)]
+pub struct HecRejectionLogic;
+
+impl RejectionLogic for HecRejectionLogic {
+    type Response = http::Response<Bytes>;
+    type Error = crate::Error;
+
+    fn rejected(
+        &self,
+        result: Result<&http::Response<Bytes>, &crate::Error>,
+        count: usize,
+    ) -> Vec<(usize, Rejection)> {
+        match result {
+            Ok(response)
+                if response.status().is_client_error()
+                    && response.status() != StatusCode::TOO_MANY_REQUESTS =>
+            {
+                dropped::reject_all(
+                    count,
+                    Rejection {
+                        message: String::from_utf8_lossy(response.body()).into_owned(),
+                        status: Some(response.status().as_u16()),
+                    },
+                )
+            }
+            _ => Vec::new(),
+        }
+    }
+}
+
 pub trait ResponseExt {
     fn body(&self) -> &Bytes;
 }
@@ -352,6 +387,7 @@ mod tests {
             source: None,
             sourcetype: None,
             host: None,
+            dropped_events: None,
         }
     }
 
@@ -10,17 +10,21 @@ use vector_core::sink::VectorSink;
 use super::{encoder::HecLogsEncoder, request_builder::HecLogsRequestBuilder, sink::HecLogsSink};
 use crate::{
     codecs::{Encoder, EncodingConfig},
-    config::{AcknowledgementsConfig, DataType, GenerateConfig, Input, SinkConfig, SinkContext},
+    config::{
+        AcknowledgementsConfig, DataType, GenerateConfig, Input, Output, SinkConfig, SinkContext,
+    },
     http::HttpClient,
     sinks::{
         splunk_hec::common::{
             acknowledgements::HecClientAcknowledgementsConfig,
             build_healthcheck, build_http_batch_service, create_client, host_key,
-            service::{HecService, HttpRequestBuilder},
+            service::{HecRejectionLogic, HecService, HttpRequestBuilder},
             timestamp_key, EndpointTarget, SplunkHecDefaultBatchSettings,
         },
         util::{
-            http::HttpRetryLogic, BatchConfig, Compression, ServiceBuilderExt, TowerRequestConfig,
+            dropped::{self, RerouteDroppedService},
+            http::HttpRetryLogic,
+            BatchConfig, Compression, ServiceBuilderExt, TowerRequestConfig,
         },
         Healthcheck,
     },
@@ -119,6 +123,14 @@ pub struct HecLogsSinkConfig {
     #[configurable(derived)]
     #[serde(default = "default_endpoint_target")]
     pub endpoint_target: EndpointTarget,
+
+    /// Whether or not to reroute events that Splunk permanently rejected to the `dropped` output,
+    /// instead of dropping them.
+    ///
+    /// Events are rejected when Splunk responds to the request carrying them with a client error,
+    /// such as `400 Bad Request`. Rejected events are annotated with the error and the status code.
+    #[serde(default)]
+    pub reroute_dropped: bool,
 }
 
 const fn default_endpoint_target() -> EndpointTarget {
@@ -145,6 +157,7 @@ impl GenerateConfig for HecLogsSinkConfig {
             timestamp_key: timestamp_key(),
             auto_extract_timestamp: None,
             endpoint_target: EndpointTarget::Event,
+            reroute_dropped: false,
         })
         .unwrap()
     }
@@ -173,6 +186,10 @@ impl SinkConfig for HecLogsSinkConfig {
         Input::new(self.encoding.config().input_type() & DataType::Log)
     }
 
+    fn outputs(&self) -> Vec<Output> {
+        dropped::outputs(self.reroute_dropped, DataType::Log)
+    }
+
     fn acknowledgements(&self) -> &AcknowledgementsConfig {
         &self.acknowledgements.inner
     }
@@ -201,6 +218,7 @@ impl HecLogsSinkConfig {
         let request_builder = HecLogsRequestBuilder {
             encoder,
             compression: self.compression,
+            reroute_dropped: self.reroute_dropped,
         };
 
         let request_settings = self.request.unwrap_with(&TowerRequestConfig::default());
@@ -218,6 +236,11 @@ impl HecLogsSinkConfig {
                 self.endpoint_target,
                 self.auto_extract_timestamp.unwrap_or_default(),
             ));
+        let http_service = RerouteDroppedService::new(
+            http_service,
+            HecRejectionLogic,
+            cx.dropped.clone().filter(|_| self.reroute_dropped),
+        );
 
         let service = HecService::new(
             http_service,
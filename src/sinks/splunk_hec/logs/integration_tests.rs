@@ -121,6 +121,7 @@ async fn config(encoding: EncodingConfig, indexed_fields: Vec<String>) -> HecLog
         timestamp_key: Default::default(),
         auto_extract_timestamp: None,
         endpoint_target: EndpointTarget::Event,
+        reroute_dropped: false,
     }
 }
 
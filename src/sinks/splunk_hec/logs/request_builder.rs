@@ -2,7 +2,7 @@ use std::sync::Arc;
 
 use bytes::Bytes;
 use vector_common::request_metadata::RequestMetadata;
-use vector_core::event::{EventFinalizers, Finalizable};
+use vector_core::event::{Event, EventFinalizers, Finalizable};
 
 use super::{
     encoder::HecLogsEncoder,
@@ -19,6 +19,7 @@ use crate::sinks::{
 pub struct HecLogsRequestBuilder {
     pub encoder: HecLogsEncoder,
     pub compression: Compression,
+    pub reroute_dropped: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -29,6 +30,7 @@ pub struct HecRequestMetadata {
     sourcetype: Option<String>,
     index: Option<String>,
     host: Option<String>,
+    dropped_events: Option<Vec<Event>>,
 }
 
 impl RequestBuilder<(Option<Partitioned>, Vec<HecProcessedEvent>)> for HecLogsRequestBuilder {
@@ -54,6 +56,12 @@ impl RequestBuilder<(Option<Partitioned>, Vec<HecProcessedEvent>)> for HecLogsRe
         let (mut partition, mut events) = input;
 
         let finalizers = events.take_finalizers();
+        let dropped_events = self.reroute_dropped.then(|| {
+            events
+                .iter()
+                .map(|event| Event::from(event.event.clone()))
+                .collect()
+        });
 
         let builder = RequestMetadataBuilder::from_events(&events);
 
@@ -65,6 +73,7 @@ impl RequestBuilder<(Option<Partitioned>, Vec<HecProcessedEvent>)> for HecLogsRe
                 sourcetype: partition.as_mut().and_then(|p| p.sourcetype.take()),
                 index: partition.as_mut().and_then(|p| p.index.take()),
                 host: partition.as_mut().and_then(|p| p.host.take()),
+                dropped_events,
             },
             builder,
             events,
@@ -86,6 +95,7 @@ impl RequestBuilder<(Option<Partitioned>, Vec<HecProcessedEvent>)> for HecLogsRe
             index: hec_metadata.index,
             host: hec_metadata.host,
             metadata,
+            dropped_events: hec_metadata.dropped_events,
         }
     }
 }
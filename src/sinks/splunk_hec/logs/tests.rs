@@ -206,6 +206,7 @@ async fn splunk_passthrough_token() {
         timestamp_key: log_schema().timestamp_key().into(),
         auto_extract_timestamp: None,
         endpoint_target: EndpointTarget::Event,
+        reroute_dropped: false,
     };
     let cx = SinkContext::new_test();
 
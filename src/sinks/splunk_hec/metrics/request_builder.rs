@@ -61,6 +61,7 @@ impl RequestBuilder<(Option<Arc<str>>, Vec<HecProcessedEvent>)> for HecMetricsRe
             sourcetype: None,
             host: None,
             metadata,
+            dropped_events: None,
         }
     }
 }
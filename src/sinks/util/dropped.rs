@@ -0,0 +1,316 @@
+//! Rerouting of events permanently rejected by a sink to its `dropped` output.
+//!
+//! Sinks that support this expose a `reroute_dropped` option. When it is enabled, the sink declares
+//! a `dropped` output, which the topology wires up like any transform output, and the sink receives
+//! a [`DroppedSender`] through its `SinkContext`.
+//!
+//! Rerouting happens in [`RerouteDroppedService`], which wraps the sink's service outside of its
+//! retry layer, so that only the final outcome of a request decides which events were rejected.
+
+use std::task::{Context, Poll};
+
+use futures::future::BoxFuture;
+use lookup::{metadata_path, path, PathPrefix};
+use tower::Service;
+use vector_core::config::{log_schema, DataType, LogNamespace, Output};
+
+use crate::{config::ComponentKey, event::Event, internal_events::StreamClosedError, SourceSender};
+
+/// The name of the output that rejected events are sent to.
+pub const DROPPED: &str = "dropped";
+
+/// Gets the outputs of a sink accepting events of type `ty`, given its `reroute_dropped` option.
+pub fn outputs(reroute_dropped: bool, ty: DataType) -> Vec<Output> {
+    if reroute_dropped {
+        vec![Output::default(ty).with_port(DROPPED)]
+    } else {
+        Vec::new()
+    }
+}
+
+/// Why a sink permanently rejected an event.
+#[derive(Clone, Debug)]
+pub struct Rejection {
+    /// The error reported by the downstream service.
+    pub message: String,
+
+    /// The status code returned by the downstream service, if any.
+    pub status: Option<u16>,
+}
+
+/// Marks every one of the `count` events of a request as rejected for the same reason.
+pub fn reject_all(count: usize, rejection: Rejection) -> Vec<(usize, Rejection)> {
+    (0..count).map(|index| (index, rejection.clone())).collect()
+}
+
+/// A request that can carry a copy of the events it was built from, for rerouting them.
+pub trait DroppedEvents {
+    /// Takes the events the request was built from, if they were kept.
+    fn take_dropped_events(&mut self) -> Option<Vec<Event>>;
+}
+
+/// Decides which events of a request were permanently rejected.
+pub trait RejectionLogic: Clone + Send + Sync + 'static {
+    type Response;
+    type Error;
+
+    /// Gets the events that were permanently rejected, given the final outcome of sending a
+    /// request built from `count` events.
+    ///
+    /// Rejected events are identified by their index into the events the request was built from.
+    fn rejected(
+        &self,
+        result: Result<&Self::Response, &Self::Error>,
+        count: usize,
+    ) -> Vec<(usize, Rejection)>;
+}
+
+/// A service that reroutes the events its inner service permanently rejected.
+#[derive(Clone)]
+pub struct RerouteDroppedService<S, L> {
+    inner: S,
+    logic: L,
+    dropped: Option<DroppedSender>,
+}
+
+impl<S, L> RerouteDroppedService<S, L> {
+    pub const fn new(inner: S, logic: L, dropped: Option<DroppedSender>) -> Self {
+        Self {
+            inner,
+            logic,
+            dropped,
+        }
+    }
+}
+
+impl<S, L, Req> Service<Req> for RerouteDroppedService<S, L>
+where
+    Req: DroppedEvents,
+    S: Service<Req>,
+    S::Future: Send + 'static,
+    S::Response: Send + 'static,
+    S::Error: Send + 'static,
+    L: RejectionLogic<Response = S::Response, Error = S::Error>,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut request: Req) -> Self::Future {
+        // The events are taken out of the request before it's sent, so they aren't cloned on
+        // every retry.
+        let events = request.take_dropped_events();
+        let logic = self.logic.clone();
+        let dropped = self.dropped.clone();
+        let future = self.inner.call(request);
+
+        Box::pin(async move {
+            let result = future.await;
+            if let (Some(mut dropped), Some(events)) = (dropped, events) {
+                let rejected = logic.rejected(result.as_ref(), events.len());
+                if !rejected.is_empty() {
+                    let mut events = events.into_iter().map(Some).collect::<Vec<_>>();
+                    let rejected = rejected
+                        .into_iter()
+                        .filter_map(|(index, rejection)| {
+                            events
+                                .get_mut(index)
+                                .and_then(Option::take)
+                                .map(|event| (event, rejection))
+                        })
+                        .collect::<Vec<_>>();
+                    dropped.send(rejected).await;
+                }
+            }
+            result
+        })
+    }
+}
+
+/// Sends the events a sink permanently rejected to its `dropped` output.
+#[derive(Clone, Debug)]
+pub struct DroppedSender {
+    out: SourceSender,
+    component_id: String,
+    component_type: &'static str,
+}
+
+impl DroppedSender {
+    pub fn new(out: SourceSender, key: &ComponentKey, component_type: &'static str) -> Self {
+        Self {
+            out,
+            component_id: key.id().to_string(),
+            component_type,
+        }
+    }
+
+    /// Annotates each event with why it was rejected, and sends them all to the `dropped` output.
+    pub async fn send(&mut self, rejected: impl IntoIterator<Item = (Event, Rejection)>) {
+        let events = rejected
+            .into_iter()
+            .map(|(mut event, rejection)| {
+                self.annotate(&mut event, &rejection);
+                event
+            })
+            .collect::<Vec<_>>();
+        let count = events.len();
+        if count == 0 {
+            return;
+        }
+
+        if let Err(error) = self.out.send_batch_named(DROPPED, events).await {
+            emit!(StreamClosedError { error, count });
+        }
+    }
+
+    fn dropped_data(&self, rejection: &Rejection) -> serde_json::Value {
+        serde_json::json!({
+            "reason": "rejected",
+            "message": rejection.message,
+            "status": rejection.status,
+            "component_id": self.component_id,
+            "component_type": self.component_type,
+            "component_kind": "sink",
+        })
+    }
+
+    fn annotate(&self, event: &mut Event, rejection: &Rejection) {
+        match event {
+            Event::Log(ref mut log) => match log.namespace() {
+                LogNamespace::Legacy => {
+                    log.insert(
+                        (
+                            PathPrefix::Event,
+                            log_schema().metadata_key().concat(path!("dropped")),
+                        ),
+                        self.dropped_data(rejection),
+                    );
+                }
+                LogNamespace::Vector => {
+                    log.insert(
+                        metadata_path!("vector", "dropped"),
+                        self.dropped_data(rejection),
+                    );
+                }
+            },
+            Event::Metric(ref mut metric) => {
+                let m = log_schema().metadata_key();
+                metric.insert_tag(format!("{}.dropped.reason", m), "rejected".into());
+                metric.insert_tag(format!("{}.dropped.message", m), rejection.message.clone());
+                if let Some(status) = rejection.status {
+                    metric.insert_tag(format!("{}.dropped.status", m), status.to_string());
+                }
+                metric.insert_tag(
+                    format!("{}.dropped.component_id", m),
+                    self.component_id.clone(),
+                );
+                metric.insert_tag(
+                    format!("{}.dropped.component_type", m),
+                    self.component_type.into(),
+                );
+                metric.insert_tag(format!("{}.dropped.component_kind", m), "sink".into());
+            }
+            Event::Trace(ref mut trace) => {
+                trace.insert(log_schema().metadata_key(), self.dropped_data(rejection));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::StreamExt;
+    use tower::ServiceExt;
+    use vector_core::event::{EventContainer, EventStatus, LogEvent};
+
+    use super::*;
+
+    #[derive(Clone)]
+    struct RejectOdd;
+
+    impl RejectionLogic for RejectOdd {
+        type Response = ();
+        type Error = ();
+
+        fn rejected(&self, _result: Result<&(), &()>, count: usize) -> Vec<(usize, Rejection)> {
+            (0..count)
+                .filter(|index| index % 2 == 1)
+                .map(|index| {
+                    (
+                        index,
+                        Rejection {
+                            message: "odd".into(),
+                            status: None,
+                        },
+                    )
+                })
+                .collect()
+        }
+    }
+
+    struct TestRequest(Option<Vec<Event>>);
+
+    impl DroppedEvents for TestRequest {
+        fn take_dropped_events(&mut self) -> Option<Vec<Event>> {
+            self.0.take()
+        }
+    }
+
+    #[tokio::test]
+    async fn reroutes_only_rejected_events() {
+        let (mut out, _) = SourceSender::new_test();
+        let mut rx = out.add_outputs(EventStatus::Delivered, DROPPED.to_string());
+        let dropped = DroppedSender::new(out, &ComponentKey::from("out"), "loki");
+
+        let inner = tower::service_fn(|_: TestRequest| async { Ok::<(), ()>(()) });
+        let service = RerouteDroppedService::new(inner, RejectOdd, Some(dropped));
+        let events = (0..4)
+            .map(|i| Event::from(LogEvent::from(i.to_string())))
+            .collect();
+        service.oneshot(TestRequest(Some(events))).await.unwrap();
+
+        let messages = rx
+            .next()
+            .await
+            .unwrap()
+            .into_events()
+            .map(|event| event.as_log()["message"].to_string_lossy().into_owned())
+            .collect::<Vec<_>>();
+        assert_eq!(messages, vec!["1", "3"]);
+    }
+
+    #[tokio::test]
+    async fn annotates_rejected_events() {
+        let (mut out, _) = SourceSender::new_test();
+        let mut rx = out.add_outputs(EventStatus::Delivered, DROPPED.to_string());
+        let mut dropped = DroppedSender::new(out, &ComponentKey::from("out"), "elasticsearch");
+
+        let event = Event::from(LogEvent::from("mapping conflict"));
+        let rejection = Rejection {
+            message: "mapper_parsing_exception".into(),
+            status: Some(400),
+        };
+        dropped.send(vec![(event, rejection)]).await;
+
+        let events = rx.next().await.unwrap();
+        let event = events.into_events().next().unwrap();
+        let log = event.as_log();
+        assert_eq!(log["message"], "mapping conflict".into());
+        assert_eq!(log["metadata.dropped.reason"], "rejected".into());
+        assert_eq!(
+            log["metadata.dropped.message"],
+            "mapper_parsing_exception".into()
+        );
+        assert_eq!(log["metadata.dropped.status"], 400.into());
+        assert_eq!(log["metadata.dropped.component_id"], "out".into());
+        assert_eq!(
+            log["metadata.dropped.component_type"],
+            "elasticsearch".into()
+        );
+        assert_eq!(log["metadata.dropped.component_kind"], "sink".into());
+    }
+}
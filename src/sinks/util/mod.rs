@@ -3,6 +3,7 @@ pub mod batch;
 pub mod buffer;
 pub mod builder;
 pub mod compressor;
+pub mod dropped;
 pub mod encoding;
 pub mod http;
 pub mod metadata;
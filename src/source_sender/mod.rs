@@ -1,4 +1,4 @@
-use std::{collections::HashMap, fmt};
+use std::{collections::HashMap, fmt, sync::Arc, time::Instant};
 
 use chrono::Utc;
 use futures::{Stream, StreamExt};
@@ -8,12 +8,14 @@ use vector_buffers::topology::channel::{self, LimitedReceiver, LimitedSender};
 #[cfg(test)]
 use vector_core::event::{into_event_stream, EventStatus};
 use vector_core::{
-    config::{log_schema, Output},
-    event::{array, Event, EventArray, EventContainer, EventRef},
+    config::{log_schema, ComponentKey, Output},
+    event::{array, Event, EventArray, EventContainer, EventRef, IngestMetadata},
     internal_event::{EventsSent, DEFAULT_OUTPUT},
     ByteSizeOf, EstimatedJsonEncodedSizeOf,
 };
 
+use crate::internal_telemetry::lineage;
+
 mod errors;
 
 pub use errors::{ClosedError, StreamSendError};
@@ -30,6 +32,7 @@ pub struct Builder {
     inner: Option<Inner>,
     named_inners: HashMap<String, Inner>,
     lag_time: Option<Histogram>,
+    source_id: Option<Arc<ComponentKey>>,
 }
 
 impl Builder {
@@ -41,6 +44,16 @@ impl Builder {
             inner: self.inner,
             named_inners: self.named_inners,
             lag_time: self.lag_time,
+            source_id: self.source_id,
+        }
+    }
+
+    /// Stamps the events sent through the outputs added afterwards with the given source and the
+    /// time they are sent at, unless they were already ingested by another source.
+    pub fn with_source_id(self, source_id: ComponentKey) -> Self {
+        Self {
+            source_id: Some(Arc::new(source_id)),
+            ..self
         }
     }
 
@@ -51,13 +64,18 @@ impl Builder {
                     self.buf_size,
                     DEFAULT_OUTPUT.to_owned(),
                     self.lag_time.clone(),
+                    self.source_id.clone(),
                 );
                 self.inner = Some(inner);
                 rx
             }
             Some(name) => {
-                let (inner, rx) =
-                    Inner::new_with_buffer(self.buf_size, name.clone(), self.lag_time.clone());
+                let (inner, rx) = Inner::new_with_buffer(
+                    self.buf_size,
+                    name.clone(),
+                    self.lag_time.clone(),
+                    self.source_id.clone(),
+                );
                 self.named_inners.insert(name, inner);
                 rx
             }
@@ -87,12 +105,13 @@ impl SourceSender {
             inner: None,
             named_inners: Default::default(),
             lag_time: Some(register_histogram!(LAG_TIME_NAME)),
+            source_id: None,
         }
     }
 
     pub fn new_with_buffer(n: usize) -> (Self, LimitedReceiver<EventArray>) {
         let lag_time = Some(register_histogram!(LAG_TIME_NAME));
-        let (inner, rx) = Inner::new_with_buffer(n, DEFAULT_OUTPUT.to_owned(), lag_time);
+        let (inner, rx) = Inner::new_with_buffer(n, DEFAULT_OUTPUT.to_owned(), lag_time, None);
         (
             Self {
                 inner: Some(inner),
@@ -160,7 +179,7 @@ impl SourceSender {
     ) -> impl Stream<Item = EventArray> + Unpin {
         // The lag_time parameter here will need to be filled in if this function is ever used for
         // non-test situations.
-        let (inner, recv) = Inner::new_with_buffer(100, name.clone(), None);
+        let (inner, recv) = Inner::new_with_buffer(100, name.clone(), None, None);
         let recv = recv.into_stream().map(move |mut events| {
             events.iter_events_mut().for_each(|mut event| {
                 let metadata = event.metadata_mut();
@@ -223,6 +242,7 @@ struct Inner {
     inner: LimitedSender<EventArray>,
     output: String,
     lag_time: Option<Histogram>,
+    source_id: Option<Arc<ComponentKey>>,
 }
 
 impl fmt::Debug for Inner {
@@ -230,6 +250,7 @@ impl fmt::Debug for Inner {
         fmt.debug_struct("Inner")
             .field("inner", &self.inner)
             .field("output", &self.output)
+            .field("source_id", &self.source_id)
             // `metrics::Histogram` is missing `impl Debug`
             .finish()
     }
@@ -240,6 +261,7 @@ impl Inner {
         n: usize,
         output: String,
         lag_time: Option<Histogram>,
+        source_id: Option<Arc<ComponentKey>>,
     ) -> (Self, LimitedReceiver<EventArray>) {
         let (tx, rx) = channel::limited(n);
         (
@@ -247,16 +269,19 @@ impl Inner {
                 inner: tx,
                 output,
                 lag_time,
+                source_id,
             },
             rx,
         )
     }
 
-    async fn send(&mut self, events: EventArray) -> Result<(), ClosedError> {
+    async fn send(&mut self, mut events: EventArray) -> Result<(), ClosedError> {
         let reference = Utc::now().timestamp_millis();
         events
             .iter_events()
             .for_each(|event| self.emit_lag_time(event, reference));
+        self.stamp_ingest(&mut events);
+        let events = self.select_traced(events);
         let byte_size = events.estimated_json_encoded_size_of();
         let count = events.len();
         self.inner.send(events).await.map_err(|_| ClosedError)?;
@@ -294,10 +319,12 @@ impl Inner {
 
         let reference = Utc::now().timestamp_millis();
         let events = events.into_iter().map(Into::into);
-        for events in array::events_into_arrays(events, Some(CHUNK_SIZE)) {
+        for mut events in array::events_into_arrays(events, Some(CHUNK_SIZE)) {
             events
                 .iter_events()
                 .for_each(|event| self.emit_lag_time(event, reference));
+            self.stamp_ingest(&mut events);
+            let events = self.select_traced(events);
             let this_count = events.len();
             let this_size = events.estimated_json_encoded_size_of();
             match self.inner.send(events).await {
@@ -325,6 +352,32 @@ impl Inner {
         Ok(())
     }
 
+    /// Stamp the events that weren't already ingested by another source with this source and the
+    /// current time, from which sinks measure the latency of the topology.
+    fn stamp_ingest(&self, events: &mut EventArray) {
+        if let Some(source_id) = &self.source_id {
+            let ingest = IngestMetadata::new(Arc::clone(source_id), Instant::now());
+            events.iter_events_mut().for_each(|mut event| {
+                let metadata = event.metadata_mut();
+                if metadata.ingest().is_none() {
+                    metadata.set_ingest(ingest.clone());
+                }
+            });
+        }
+    }
+
+    /// Select the events to trace the lineage of through the topology, if this is the output of a
+    /// source.
+    fn select_traced(&self, events: EventArray) -> EventArray {
+        match &self.source_id {
+            Some(source_id) => {
+                let output = (self.output != DEFAULT_OUTPUT).then_some(self.output.as_str());
+                lineage::select(source_id, output, events)
+            }
+            None => events,
+        }
+    }
+
     /// Calculate the difference between the reference time and the
     /// timestamp stored in the given event reference, and emit the
     /// different, as expressed in milliseconds, as a histogram.
@@ -402,6 +455,37 @@ mod tests {
         .await;
     }
 
+    #[tokio::test]
+    async fn stamps_ingest_metadata() {
+        let source_id = Arc::new(ComponentKey::from("in"));
+        let (mut inner, rx) = Inner::new_with_buffer(
+            TEST_BUFFER_SIZE,
+            DEFAULT_OUTPUT.to_owned(),
+            None,
+            Some(Arc::clone(&source_id)),
+        );
+
+        let mut ingested = Event::Log(LogEvent::from("ingested"));
+        let earlier = IngestMetadata::new(Arc::new(ComponentKey::from("other")), Instant::now());
+        ingested.metadata_mut().set_ingest(earlier.clone());
+
+        inner
+            .send_batch(vec![Event::Log(LogEvent::from("new")), ingested])
+            .await
+            .expect("Send should not fail");
+
+        let events = rx.into_stream().next().await.expect("Events were sent");
+        let ingests = events
+            .iter_events()
+            .map(|event| event.metadata().ingest().cloned())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            ingests[0].as_ref().map(IngestMetadata::source_id),
+            Some(&*source_id)
+        );
+        assert_eq!(ingests[1], Some(earlier));
+    }
+
     async fn emit_and_test(make_event: impl FnOnce(DateTime<Utc>) -> Event) {
         metrics::init_test();
         let (mut sender, _stream) = SourceSender::new_test();
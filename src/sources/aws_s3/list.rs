@@ -0,0 +1,488 @@
+use std::{
+    collections::{HashMap, HashSet},
+    io,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use aws_sdk_s3::{error::ListObjectsV2Error, model::Object, Client as S3Client};
+use aws_smithy_client::SdkError;
+use aws_types::region::Region;
+use chrono::{DateTime, TimeZone, Utc};
+use futures::FutureExt;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use snafu::{ResultExt, Snafu};
+use tokio::{io::AsyncWriteExt, pin, select};
+use vector_config::configurable_component;
+use vector_core::config::LogNamespace;
+
+use super::ObjectProcessor;
+use crate::{
+    config::{SourceAcknowledgementsConfig, SourceContext},
+    internal_events::{S3CheckpointWriteError, S3ListObjectsError, S3ObjectProcessingError},
+    line_agg,
+};
+
+const CHECKPOINT_FILENAME: &str = "checkpoint.json";
+const TMP_CHECKPOINT_FILENAME: &str = "checkpoint.new.json";
+const JOURNAL_FILENAME: &str = "checkpoint.journal";
+
+/// Bucket listing configuration options.
+#[configurable_component]
+#[derive(Clone, Debug, Derivative)]
+#[derivative(Default)]
+#[serde(deny_unknown_fields)]
+pub(super) struct Config {
+    /// The name of the bucket to read objects from.
+    pub(super) bucket: String,
+
+    /// Only objects whose key starts with this prefix are read.
+    #[configurable(metadata(docs::examples = "logs/"))]
+    pub(super) prefix: Option<String>,
+
+    /// A regular expression that object keys must match in order to be read.
+    #[configurable(metadata(docs::examples = "\\.log(\\.gz)?$"))]
+    pub(super) key_pattern: Option<String>,
+
+    /// Only objects last modified at or after this time are read.
+    #[configurable(metadata(docs::examples = "2022-11-01T00:00:00Z"))]
+    pub(super) modified_after: Option<DateTime<Utc>>,
+
+    /// Only objects last modified before this time are read.
+    #[configurable(metadata(docs::examples = "2022-12-01T00:00:00Z"))]
+    pub(super) modified_before: Option<DateTime<Utc>>,
+
+    /// How long to wait between listings of the bucket, in seconds.
+    ///
+    /// Each listing walks the whole bucket, or `prefix`, and reads any matching object that has not
+    /// been read yet, or that has been modified since it was last read.
+    #[serde(default = "default_poll_secs")]
+    #[derivative(Default(value = "default_poll_secs()"))]
+    pub(super) poll_secs: u32,
+
+    /// The directory used to persist the checkpoint of objects that have already been read.
+    ///
+    /// By default, the global `data_dir` option is used. Please make sure the user Vector is running as has write permissions to this directory.
+    pub(super) data_dir: Option<PathBuf>,
+}
+
+const fn default_poll_secs() -> u32 {
+    60
+}
+
+#[derive(Debug, Snafu)]
+pub(super) enum IngestorNewError {
+    #[snafu(display("Invalid `key_pattern` {:?}: {}", pattern, source))]
+    InvalidKeyPattern {
+        source: regex::Error,
+        pattern: String,
+    },
+    #[snafu(display("Unable to read checkpoint {:?}: {}", path, source))]
+    ReadCheckpoint { source: io::Error, path: PathBuf },
+}
+
+/// The objects that have been read, along with the last-modified time they had when they were.
+///
+/// The checkpoint is persisted as a snapshot, along with a journal of the objects read since the
+/// snapshot was written, so that objects are persisted as they're read without rewriting every
+/// object read before them.
+#[derive(Debug, Default, Deserialize, Serialize)]
+struct Checkpoint {
+    objects: HashMap<String, DateTime<Utc>>,
+
+    /// The objects read since they were last appended to the journal.
+    #[serde(skip)]
+    unjournaled: Vec<JournalEntry>,
+
+    /// Whether the journal holds objects that are not in the snapshot.
+    #[serde(skip)]
+    journaled: bool,
+}
+
+/// An object read, as appended to the journal, one per line.
+#[derive(Debug, Deserialize, Serialize)]
+struct JournalEntry {
+    key: String,
+    last_modified: DateTime<Utc>,
+}
+
+impl Checkpoint {
+    async fn load(dir: &Path) -> io::Result<Self> {
+        let mut checkpoint = match tokio::fs::read(dir.join(CHECKPOINT_FILENAME)).await {
+            Ok(data) => serde_json::from_slice(&data)
+                .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Self::default(),
+            Err(error) => return Err(error),
+        };
+
+        let journal = match tokio::fs::read(dir.join(JOURNAL_FILENAME)).await {
+            Ok(journal) => journal,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(checkpoint),
+            Err(error) => return Err(error),
+        };
+        for line in journal.split(|byte| *byte == b'\n') {
+            // A line that can't be parsed was cut short by a crash while it was being appended,
+            // and its object will be read again.
+            if let Ok(entry) = serde_json::from_slice::<JournalEntry>(line) {
+                checkpoint.objects.insert(entry.key, entry.last_modified);
+                checkpoint.journaled = true;
+            }
+        }
+
+        Ok(checkpoint)
+    }
+
+    fn insert(&mut self, key: String, last_modified: DateTime<Utc>) {
+        self.objects.insert(key.clone(), last_modified);
+        self.unjournaled.push(JournalEntry { key, last_modified });
+    }
+
+    /// Appends the objects read since the last call to the journal.
+    async fn append(&mut self, dir: &Path) -> io::Result<()> {
+        if self.unjournaled.is_empty() {
+            return Ok(());
+        }
+
+        let mut data = Vec::new();
+        for entry in &self.unjournaled {
+            serde_json::to_writer(&mut data, entry)?;
+            data.push(b'\n');
+        }
+
+        let mut journal = tokio::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(dir.join(JOURNAL_FILENAME))
+            .await?;
+        journal.write_all(&data).await?;
+        journal.flush().await?;
+
+        self.unjournaled.clear();
+        self.journaled = true;
+        Ok(())
+    }
+
+    /// Writes all objects to a new snapshot, and empties the journal.
+    async fn persist(&mut self, dir: &Path) -> io::Result<()> {
+        let data = serde_json::to_vec(self)?;
+        let tmp_path = dir.join(TMP_CHECKPOINT_FILENAME);
+        tokio::fs::write(&tmp_path, data).await?;
+        tokio::fs::rename(&tmp_path, dir.join(CHECKPOINT_FILENAME)).await?;
+
+        match tokio::fs::remove_file(dir.join(JOURNAL_FILENAME)).await {
+            Ok(()) => {}
+            Err(error) if error.kind() == io::ErrorKind::NotFound => {}
+            Err(error) => return Err(error),
+        }
+
+        self.unjournaled.clear();
+        self.journaled = false;
+        Ok(())
+    }
+
+    fn is_read(&self, key: &str, last_modified: DateTime<Utc>) -> bool {
+        self.objects
+            .get(key)
+            .map_or(false, |read| *read >= last_modified)
+    }
+}
+
+/// Decides which listed objects are read.
+struct Filter {
+    key_pattern: Option<Regex>,
+    modified_after: Option<DateTime<Utc>>,
+    modified_before: Option<DateTime<Utc>>,
+}
+
+impl Filter {
+    fn matches(&self, key: &str, last_modified: DateTime<Utc>) -> bool {
+        self.key_pattern
+            .as_ref()
+            .map_or(true, |pattern| pattern.is_match(key))
+            && self
+                .modified_after
+                .map_or(true, |after| last_modified >= after)
+            && self
+                .modified_before
+                .map_or(true, |before| last_modified < before)
+    }
+}
+
+pub(super) struct Ingestor {
+    region: Region,
+    s3_client: S3Client,
+    compression: super::Compression,
+    multiline: Option<line_agg::Config>,
+
+    bucket: String,
+    prefix: Option<String>,
+    filter: Filter,
+    poll_interval: Duration,
+
+    data_dir: PathBuf,
+    checkpoint: Checkpoint,
+}
+
+impl Ingestor {
+    pub(super) async fn new(
+        region: Region,
+        s3_client: S3Client,
+        config: Config,
+        compression: super::Compression,
+        multiline: Option<line_agg::Config>,
+        data_dir: PathBuf,
+    ) -> Result<Ingestor, IngestorNewError> {
+        let key_pattern = config
+            .key_pattern
+            .map(|pattern| Regex::new(&pattern).context(InvalidKeyPatternSnafu { pattern }))
+            .transpose()?;
+
+        let checkpoint = Checkpoint::load(&data_dir)
+            .await
+            .context(ReadCheckpointSnafu {
+                path: data_dir.join(CHECKPOINT_FILENAME),
+            })?;
+
+        Ok(Ingestor {
+            region,
+            s3_client,
+            compression,
+            multiline,
+
+            bucket: config.bucket,
+            prefix: config.prefix,
+            filter: Filter {
+                key_pattern,
+                modified_after: config.modified_after,
+                modified_before: config.modified_before,
+            },
+            poll_interval: Duration::from_secs(config.poll_secs.into()),
+
+            data_dir,
+            checkpoint,
+        })
+    }
+
+    pub(super) async fn run(
+        mut self,
+        cx: SourceContext,
+        acknowledgements: SourceAcknowledgementsConfig,
+        log_namespace: LogNamespace,
+    ) -> Result<(), ()> {
+        let acknowledgements = cx.do_acknowledgements(acknowledgements);
+        let mut objects = ObjectProcessor::new(
+            self.s3_client.clone(),
+            self.compression,
+            self.multiline.clone(),
+            cx.out,
+            acknowledgements,
+            log_namespace,
+        );
+
+        let shutdown = cx.shutdown.fuse();
+        pin!(shutdown);
+
+        loop {
+            select! {
+                _ = &mut shutdown => break,
+                _ = self.run_once(&mut objects) => {},
+            }
+            select! {
+                _ = &mut shutdown => break,
+                _ = tokio::time::sleep(self.poll_interval) => {},
+            }
+        }
+
+        // Keep the objects read since the last listed page.
+        self.append_checkpoint().await;
+
+        Ok(())
+    }
+
+    /// Walks the bucket once, reading every matching object that has not been read yet.
+    async fn run_once(&mut self, objects: &mut ObjectProcessor) {
+        let mut listed = HashSet::new();
+        let mut continuation_token = None;
+
+        loop {
+            let page = match self.list_objects(continuation_token.take()).await {
+                Ok(page) => page,
+                Err(error) => {
+                    emit!(S3ListObjectsError {
+                        bucket: &self.bucket,
+                        error: &error,
+                    });
+                    // Keep the checkpoint as-is, since the listing is incomplete.
+                    return;
+                }
+            };
+
+            for object in page.contents.unwrap_or_default() {
+                let (key, last_modified) = match object_key_and_last_modified(object) {
+                    Some(object) => object,
+                    None => continue,
+                };
+                if !self.filter.matches(&key, last_modified) {
+                    continue;
+                }
+                listed.insert(key.clone());
+                if self.checkpoint.is_read(&key, last_modified) {
+                    continue;
+                }
+
+                match objects
+                    .process(&self.bucket, &key, self.region.as_ref())
+                    .await
+                {
+                    Ok(()) => self.checkpoint.insert(key, last_modified),
+                    Err(error) => emit!(S3ObjectProcessingError { error: &error }),
+                }
+            }
+
+            // Only the objects read from this page are appended to the checkpoint, so that large
+            // backfills don't rewrite it over and over.
+            self.append_checkpoint().await;
+
+            match page.next_continuation_token {
+                Some(token) if page.is_truncated => continuation_token = Some(token),
+                _ => break,
+            }
+        }
+
+        // Forget objects that no longer exist, or no longer match, so the checkpoint does not grow
+        // without bound, and fold the journal into the snapshot once per walk of the bucket.
+        let before = self.checkpoint.objects.len();
+        self.checkpoint
+            .objects
+            .retain(|key, _| listed.contains(key.as_str()));
+        if self.checkpoint.objects.len() != before || self.checkpoint.journaled {
+            self.persist_checkpoint().await;
+        }
+    }
+
+    async fn list_objects(
+        &self,
+        continuation_token: Option<String>,
+    ) -> Result<aws_sdk_s3::output::ListObjectsV2Output, SdkError<ListObjectsV2Error>> {
+        self.s3_client
+            .list_objects_v2()
+            .bucket(self.bucket.clone())
+            .set_prefix(self.prefix.clone())
+            .set_continuation_token(continuation_token)
+            .send()
+            .await
+    }
+
+    /// Appends the objects read to the checkpoint journal, or tries again later if it can't be.
+    async fn append_checkpoint(&mut self) {
+        if let Err(error) = self.checkpoint.append(&self.data_dir).await {
+            emit!(S3CheckpointWriteError {
+                error,
+                filename: &self.data_dir.join(JOURNAL_FILENAME),
+            });
+        }
+    }
+
+    /// Persists a snapshot of the checkpoint, or tries again after the next walk if it can't be.
+    async fn persist_checkpoint(&mut self) {
+        if let Err(error) = self.checkpoint.persist(&self.data_dir).await {
+            emit!(S3CheckpointWriteError {
+                error,
+                filename: &self.data_dir.join(CHECKPOINT_FILENAME),
+            });
+        }
+    }
+}
+
+fn object_key_and_last_modified(object: Object) -> Option<(String, DateTime<Utc>)> {
+    let last_modified = object
+        .last_modified
+        .map(|ts| Utc.timestamp(ts.secs(), ts.subsec_nanos()))?;
+    Some((object.key?, last_modified))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn time(s: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(s).unwrap().into()
+    }
+
+    #[test]
+    fn filter_matches_key_pattern_and_last_modified() {
+        let filter = Filter {
+            key_pattern: Some(Regex::new(r"\.log$").unwrap()),
+            modified_after: Some(time("2022-11-01T00:00:00Z")),
+            modified_before: Some(time("2022-12-01T00:00:00Z")),
+        };
+
+        assert!(filter.matches("a/b.log", time("2022-11-01T00:00:00Z")));
+        assert!(filter.matches("a/b.log", time("2022-11-30T23:59:59Z")));
+        assert!(!filter.matches("a/b.txt", time("2022-11-15T00:00:00Z")));
+        assert!(!filter.matches("a/b.log", time("2022-10-31T23:59:59Z")));
+        assert!(!filter.matches("a/b.log", time("2022-12-01T00:00:00Z")));
+    }
+
+    #[tokio::test]
+    async fn checkpoint_round_trips() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let mut checkpoint = Checkpoint::load(dir.path()).await.unwrap();
+        assert!(checkpoint.objects.is_empty());
+
+        checkpoint.insert("a.log".into(), time("2022-11-15T00:00:00Z"));
+        checkpoint.persist(dir.path()).await.unwrap();
+
+        let checkpoint = Checkpoint::load(dir.path()).await.unwrap();
+        assert!(checkpoint.is_read("a.log", time("2022-11-15T00:00:00Z")));
+        assert!(checkpoint.is_read("a.log", time("2022-11-14T00:00:00Z")));
+        // Objects modified since they were read are read again.
+        assert!(!checkpoint.is_read("a.log", time("2022-11-16T00:00:00Z")));
+        assert!(!checkpoint.is_read("b.log", time("2022-11-15T00:00:00Z")));
+    }
+
+    #[tokio::test]
+    async fn checkpoint_journals_objects_read() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let mut checkpoint = Checkpoint::load(dir.path()).await.unwrap();
+        checkpoint.insert("a.log".into(), time("2022-11-15T00:00:00Z"));
+        checkpoint.persist(dir.path()).await.unwrap();
+
+        checkpoint.insert("b.log".into(), time("2022-11-15T00:00:00Z"));
+        checkpoint.append(dir.path()).await.unwrap();
+        checkpoint.insert("c.log".into(), time("2022-11-15T00:00:00Z"));
+        checkpoint.append(dir.path()).await.unwrap();
+
+        // Appending leaves the snapshot as it was.
+        let snapshot = tokio::fs::read(dir.path().join(CHECKPOINT_FILENAME))
+            .await
+            .unwrap();
+        let snapshot: Checkpoint = serde_json::from_slice(&snapshot).unwrap();
+        assert_eq!(snapshot.objects.len(), 1);
+
+        // An entry cut short by a crash is skipped.
+        let mut journal = tokio::fs::OpenOptions::new()
+            .append(true)
+            .open(dir.path().join(JOURNAL_FILENAME))
+            .await
+            .unwrap();
+        journal.write_all(b"{\"key\":\"d.lo").await.unwrap();
+
+        let mut checkpoint = Checkpoint::load(dir.path()).await.unwrap();
+        assert!(checkpoint.journaled);
+        for key in ["a.log", "b.log", "c.log"] {
+            assert!(checkpoint.is_read(key, time("2022-11-15T00:00:00Z")));
+        }
+        assert!(!checkpoint.is_read("d.log", time("2022-11-15T00:00:00Z")));
+
+        // Persisting a snapshot empties the journal.
+        checkpoint.persist(dir.path()).await.unwrap();
+        assert!(!dir.path().join(JOURNAL_FILENAME).exists());
+        let checkpoint = Checkpoint::load(dir.path()).await.unwrap();
+        assert!(!checkpoint.journaled);
+        assert_eq!(checkpoint.objects.len(), 3);
+    }
+}
@@ -1,26 +1,39 @@
-use std::{convert::TryInto, io::ErrorKind};
+use std::{convert::TryInto, future::ready, io::ErrorKind};
 
 use async_compression::tokio::bufread;
-use aws_sdk_s3::types::ByteStream;
-use codecs::BytesDeserializerConfig;
-use futures::{stream, stream::StreamExt, TryStreamExt};
-use lookup::owned_value_path;
+use aws_sdk_s3::{types::ByteStream, Client as S3Client};
+use bytes::Bytes;
+use chrono::{TimeZone, Utc};
+use codecs::{BytesDeserializer, BytesDeserializerConfig, CharacterDelimitedDecoder};
+use futures::{stream, stream::StreamExt, Stream, TryStreamExt};
+use lookup::{metadata_path, owned_value_path, path, PathPrefix};
 use snafu::Snafu;
-use tokio_util::io::StreamReader;
+use tokio_util::{codec::FramedRead, io::StreamReader};
 use value::{kind::Collection, Kind};
+use vector_common::internal_event::{
+    ByteSize, BytesReceived, InternalEventHandle as _, Protocol, Registered,
+};
 use vector_config::{configurable_component, NamedComponent};
-use vector_core::config::{DataType, LegacyKey, LogNamespace};
+use vector_core::{
+    config::{log_schema, DataType, LegacyKey, LogNamespace},
+    ByteSizeOf,
+};
 
 use super::util::MultilineConfig;
 use crate::{
     aws::{auth::AwsAuthentication, create_client, RegionOrEndpoint},
     common::{s3::S3ClientBuilder, sqs::SqsClientBuilder},
     config::{Output, ProxyConfig, SourceAcknowledgementsConfig, SourceConfig, SourceContext},
-    line_agg,
+    event::{BatchNotifier, BatchStatus},
+    internal_events::{EventsReceived, StreamClosedError},
+    line_agg::{self, LineAgg},
     serde::bool_or_struct,
     tls::TlsConfig,
+    SourceSender,
 };
+use sqs::ProcessingError;
 
+mod list;
 pub mod sqs;
 
 /// Compression scheme for objects retrieved from S3.
@@ -56,6 +69,13 @@ enum Strategy {
     /// [aws_sqs]: https://aws.amazon.com/sqs/
     #[derivative(Default)]
     Sqs,
+
+    /// Consumes objects by periodically listing a bucket with [`ListObjectsV2`][list_objects_v2].
+    ///
+    /// Useful for buckets without event notifications, or to backfill historical objects.
+    ///
+    /// [list_objects_v2]: https://docs.aws.amazon.com/AmazonS3/latest/API/API_ListObjectsV2.html
+    List,
 }
 
 /// Configuration for the `aws_s3` source.
@@ -82,6 +102,11 @@ pub struct AwsS3Config {
     /// Only relevant when `strategy = "sqs"`.
     sqs: Option<sqs::Config>,
 
+    /// Configuration options for listing a bucket.
+    ///
+    /// Only relevant when `strategy = "list"`.
+    list: Option<list::Config>,
+
     /// The ARN of an [IAM role][iam_role] to assume at startup.
     ///
     /// [iam_role]: https://docs.aws.amazon.com/IAM/latest/UserGuide/id_roles.html
@@ -129,6 +154,13 @@ impl SourceConfig for AwsS3Config {
                     .await?
                     .run(cx, self.acknowledgements, log_namespace),
             )),
+            Strategy::List => Ok(Box::pin(
+                self.create_list_ingestor(multiline_config, &cx).await?.run(
+                    cx,
+                    self.acknowledgements,
+                    log_namespace,
+                ),
+            )),
         }
     }
 
@@ -240,6 +272,53 @@ impl AwsS3Config {
             None => Err(CreateSqsIngestorError::ConfigMissing {}.into()),
         }
     }
+
+    async fn create_list_ingestor(
+        &self,
+        multiline: Option<line_agg::Config>,
+        cx: &SourceContext,
+    ) -> crate::Result<list::Ingestor> {
+        let config = self
+            .list
+            .as_ref()
+            .ok_or(CreateListIngestorError::ConfigMissing)?;
+
+        let region = self
+            .region
+            .region()
+            .ok_or(CreateListIngestorError::RegionMissing)?;
+
+        let endpoint = self
+            .region
+            .endpoint()
+            .map_err(|_| CreateListIngestorError::InvalidEndpoint)?;
+
+        let data_dir = cx
+            .globals
+            .resolve_and_make_data_subdir(config.data_dir.as_ref(), cx.key.id())?;
+
+        let s3_client = create_client::<S3ClientBuilder>(
+            &self.auth,
+            Some(region.clone()),
+            endpoint,
+            &cx.proxy,
+            &self.tls_options,
+            false,
+        )
+        .await?;
+
+        let ingestor = list::Ingestor::new(
+            region,
+            s3_client,
+            config.clone(),
+            self.compression,
+            multiline,
+            data_dir,
+        )
+        .await?;
+
+        Ok(ingestor)
+    }
 }
 
 #[derive(Debug, Snafu)]
@@ -258,6 +337,244 @@ enum CreateSqsIngestorError {
     InvalidEndpoint,
 }
 
+#[derive(Debug, Snafu)]
+enum CreateListIngestorError {
+    #[snafu(display("Configuration for `list` required when strategy=list"))]
+    ConfigMissing,
+    #[snafu(display("Region is required"))]
+    RegionMissing,
+    #[snafu(display("Endpoint is invalid"))]
+    InvalidEndpoint,
+}
+
+/// Fetches objects from S3 and forwards their lines as events.
+///
+/// Shared by the ingestion strategies, which only differ in how they discover the objects to read.
+struct ObjectProcessor {
+    s3_client: S3Client,
+    compression: Compression,
+    multiline: Option<line_agg::Config>,
+    out: SourceSender,
+    acknowledgements: bool,
+    log_namespace: LogNamespace,
+    bytes_received: Registered<BytesReceived>,
+}
+
+impl ObjectProcessor {
+    fn new(
+        s3_client: S3Client,
+        compression: Compression,
+        multiline: Option<line_agg::Config>,
+        out: SourceSender,
+        acknowledgements: bool,
+        log_namespace: LogNamespace,
+    ) -> Self {
+        Self {
+            s3_client,
+            compression,
+            multiline,
+            out,
+            acknowledgements,
+            log_namespace,
+            bytes_received: register!(BytesReceived::from(Protocol::HTTP)),
+        }
+    }
+
+    /// Reads `s3://{bucket}/{key}`, returning once all of its events have been acknowledged, if
+    /// acknowledgements are enabled.
+    async fn process(
+        &mut self,
+        bucket: &str,
+        key: &str,
+        region: &str,
+    ) -> Result<(), ProcessingError> {
+        let object = self
+            .s3_client
+            .get_object()
+            .bucket(bucket)
+            .key(key)
+            .send()
+            .await
+            .map_err(|source| ProcessingError::GetObject {
+                source,
+                bucket: bucket.to_owned(),
+                key: key.to_owned(),
+            })?;
+
+        let metadata = object.metadata;
+
+        let timestamp = object
+            .last_modified
+            .map(|ts| Utc.timestamp(ts.secs(), ts.subsec_nanos()));
+
+        let log_namespace = self.log_namespace;
+        let (batch, receiver) = BatchNotifier::maybe_new_with_receiver(self.acknowledgements);
+        let object_reader = s3_object_decoder(
+            self.compression,
+            key,
+            object.content_encoding.as_deref(),
+            object.content_type.as_deref(),
+            object.body,
+        )
+        .await;
+
+        // Record the read error seen to propagate up later so we avoid marking the object as
+        // processed
+        //
+        // String is used as we cannot clone std::io::Error to take ownership in closure
+        //
+        // FramedRead likely stops when it gets an i/o error but I found it more clear to
+        // show that we `take_while` there hasn't been an error
+        //
+        // This can result in objects being partially processed before an error, but we
+        // prefer duplicate lines over message loss. Future work could include recording
+        // the offset of the object that has been read, but this would only be relevant in
+        // the case that the same vector instance processes the same message.
+        let mut read_error = None;
+        let bytes_received = self.bytes_received.clone();
+        let lines: Box<dyn Stream<Item = Bytes> + Send + Unpin> = Box::new(
+            FramedRead::new(object_reader, CharacterDelimitedDecoder::new(b'\n'))
+                .map(|res| {
+                    res.map(|bytes| {
+                        bytes_received.emit(ByteSize(bytes.len()));
+                        bytes
+                    })
+                    .map_err(|err| {
+                        read_error = Some(err);
+                    })
+                    .ok()
+                })
+                .take_while(|res| ready(res.is_some()))
+                .map(|r| r.expect("validated by take_while")),
+        );
+
+        let lines = match &self.multiline {
+            Some(config) => Box::new(
+                LineAgg::new(
+                    lines.map(|line| ((), line, ())),
+                    line_agg::Logic::new(config.clone()),
+                )
+                .map(|(_src, line, _context)| line),
+            ),
+            None => lines,
+        };
+
+        let mut stream = lines.map(|line| {
+            let deserializer = BytesDeserializer::new();
+            let mut log = deserializer
+                .parse_single(line, log_namespace)
+                .with_batch_notifier_option(&batch);
+
+            log_namespace.insert_source_metadata(
+                AwsS3Config::NAME,
+                &mut log,
+                Some(LegacyKey::Overwrite(path!("bucket"))),
+                path!("bucket"),
+                Bytes::from(bucket.as_bytes().to_vec()),
+            );
+            log_namespace.insert_source_metadata(
+                AwsS3Config::NAME,
+                &mut log,
+                Some(LegacyKey::Overwrite(path!("object"))),
+                path!("object"),
+                Bytes::from(key.as_bytes().to_vec()),
+            );
+            log_namespace.insert_source_metadata(
+                AwsS3Config::NAME,
+                &mut log,
+                Some(LegacyKey::Overwrite(path!("region"))),
+                path!("region"),
+                Bytes::from(region.as_bytes().to_vec()),
+            );
+
+            if let Some(metadata) = &metadata {
+                for (key, value) in metadata {
+                    log_namespace.insert_source_metadata(
+                        AwsS3Config::NAME,
+                        &mut log,
+                        Some(LegacyKey::Overwrite(key.as_str())),
+                        path!("metadata", key.as_str()),
+                        value.clone(),
+                    );
+                }
+            }
+
+            log_namespace.insert_vector_metadata(
+                &mut log,
+                path!(log_schema().source_type_key()),
+                path!("source_type"),
+                Bytes::from_static(AwsS3Config::NAME.as_bytes()),
+            );
+
+            // This handles the transition from the original timestamp logic. Originally the
+            // `timestamp_key` was populated by the `last_modified` time on the object, falling
+            // back to calling `now()`.
+            match log_namespace {
+                LogNamespace::Vector => {
+                    if let Some(timestamp) = timestamp {
+                        log.insert(metadata_path!(AwsS3Config::NAME, "timestamp"), timestamp);
+                    }
+
+                    log.insert(metadata_path!("vector", "ingest_timestamp"), Utc::now());
+                }
+                LogNamespace::Legacy => {
+                    log.try_insert(
+                        (PathPrefix::Event, log_schema().timestamp_key()),
+                        timestamp.unwrap_or_else(Utc::now),
+                    );
+                }
+            };
+
+            emit!(EventsReceived {
+                count: 1,
+                byte_size: log.size_of()
+            });
+
+            log
+        });
+
+        let send_error = match self.out.send_event_stream(&mut stream).await {
+            Ok(_) => None,
+            Err(error) => {
+                let (count, _) = stream.size_hint();
+                emit!(StreamClosedError { error, count });
+                Some(crate::source_sender::ClosedError)
+            }
+        };
+
+        // Up above, `lines` captures `read_error`, and eventually is captured by `stream`,
+        // so we explicitly drop it so that we can again utilize `read_error` below.
+        drop(stream);
+
+        if let Some(error) = read_error {
+            Err(ProcessingError::ReadObject {
+                source: error,
+                bucket: bucket.to_owned(),
+                key: key.to_owned(),
+            })
+        } else if let Some(error) = send_error {
+            Err(ProcessingError::PipelineSend {
+                source: error,
+                bucket: bucket.to_owned(),
+                key: key.to_owned(),
+            })
+        } else {
+            match receiver {
+                None => Ok(()),
+                Some(receiver) => match receiver.await {
+                    BatchStatus::Delivered => Ok(()),
+                    BatchStatus::Errored => Err(ProcessingError::ErrorAcknowledgement),
+                    BatchStatus::Rejected => {
+                        // Sinks are responsible for emitting ComponentEventsDropped.
+                        // Failed events cannot be retried, so the object is still considered processed.
+                        Ok(())
+                    }
+                },
+            }
+        }
+    }
+}
+
 /// None if body is empty
 async fn s3_object_decoder(
     compression: Compression,
@@ -419,7 +736,7 @@ mod integration_tests {
     use value::Value;
     use vector_config::NamedComponent;
 
-    use super::{sqs, AwsS3Config, Compression, Strategy};
+    use super::{list, sqs, AwsS3Config, Compression, Strategy};
     use crate::{
         aws::{create_client, AwsAuthentication, RegionOrEndpoint},
         common::sqs::SqsClientBuilder,
@@ -672,6 +989,70 @@ mod integration_tests {
         .await;
     }
 
+    #[tokio::test]
+    async fn s3_list_strategy() {
+        trace_init();
+
+        assert_source_compliance(&SOURCE_TAGS, async move {
+            let s3 = s3_client().await;
+            let bucket = create_bucket(&s3).await;
+
+            let logs: Vec<String> = random_lines(100).take(10).collect();
+            for (key, body) in [
+                ("logs/a.log", logs.join("\n")),
+                ("logs/b.txt", "ignored".to_owned()),
+                ("other/c.log", "ignored".to_owned()),
+            ] {
+                s3.put_object()
+                    .bucket(bucket.clone())
+                    .key(key)
+                    .body(ByteStream::from(body.into_bytes()))
+                    .send()
+                    .await
+                    .expect("Could not put object");
+            }
+
+            let data_dir = tempfile::tempdir().unwrap();
+            let config = AwsS3Config {
+                region: RegionOrEndpoint::with_both("us-east-1", s3_address()),
+                strategy: Strategy::List,
+                list: Some(list::Config {
+                    bucket: bucket.clone(),
+                    prefix: Some("logs/".to_owned()),
+                    key_pattern: Some(r"\.log$".to_owned()),
+                    poll_secs: 1,
+                    data_dir: Some(data_dir.path().to_path_buf()),
+                    ..Default::default()
+                }),
+                acknowledgements: true.into(),
+                ..Default::default()
+            };
+
+            let (tx, rx) = SourceSender::new_test_finalize(Delivered);
+            let cx = SourceContext::new_test(tx, None);
+            let source = config.build(cx).await.unwrap();
+            tokio::spawn(async move { source.await.unwrap() });
+
+            let events = collect_n(rx, logs.len()).await;
+            let messages = events
+                .iter()
+                .map(|event| event.as_log()["message"].to_string_lossy())
+                .collect::<Vec<_>>();
+            assert_eq!(messages, logs);
+            for event in &events {
+                assert_eq!(event.as_log()["object"], "logs/a.log".into());
+            }
+
+            // The checkpoint is written once the events are acknowledged.
+            tokio::time::sleep(Duration::from_secs(1)).await;
+            let checkpoint =
+                std::fs::read_to_string(data_dir.path().join("checkpoint.json")).unwrap();
+            assert!(checkpoint.contains("logs/a.log"));
+            assert!(!checkpoint.contains("b.txt"));
+        })
+        .await;
+    }
+
     fn s3_address() -> String {
         std::env::var("S3_ADDRESS").unwrap_or_else(|_| "http://localhost:4566".into())
     }
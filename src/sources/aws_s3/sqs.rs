@@ -1,4 +1,4 @@
-use std::{future::ready, num::NonZeroUsize, panic, sync::Arc};
+use std::{num::NonZeroUsize, panic, sync::Arc};
 
 use aws_sdk_s3::{error::GetObjectError, Client as S3Client};
 use aws_sdk_sqs::{
@@ -9,41 +9,29 @@ use aws_sdk_sqs::{
 };
 use aws_smithy_client::SdkError;
 use aws_types::region::Region;
-use bytes::Bytes;
-use chrono::{TimeZone, Utc};
-use codecs::{decoding::FramingError, BytesDeserializer, CharacterDelimitedDecoder};
-use futures::{FutureExt, Stream, StreamExt, TryFutureExt};
+use codecs::decoding::FramingError;
+use futures::{FutureExt, TryFutureExt};
 use once_cell::sync::Lazy;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use snafu::{ResultExt, Snafu};
 use tokio::{pin, select};
-use tokio_util::codec::FramedRead;
 use tracing::Instrument;
-use vector_common::internal_event::{
-    ByteSize, BytesReceived, InternalEventHandle as _, Protocol, Registered,
-};
-use vector_config::{configurable_component, NamedComponent};
+use vector_config::configurable_component;
 
+use super::ObjectProcessor;
 use crate::{
     config::{SourceAcknowledgementsConfig, SourceContext},
-    event::{BatchNotifier, BatchStatus},
     internal_events::{
-        EventsReceived, SqsMessageDeleteBatchError, SqsMessageDeletePartialError,
-        SqsMessageDeleteSucceeded, SqsMessageProcessingError, SqsMessageProcessingSucceeded,
-        SqsMessageReceiveError, SqsMessageReceiveSucceeded, SqsS3EventRecordInvalidEventIgnored,
-        StreamClosedError,
+        SqsMessageDeleteBatchError, SqsMessageDeletePartialError, SqsMessageDeleteSucceeded,
+        SqsMessageProcessingError, SqsMessageProcessingSucceeded, SqsMessageReceiveError,
+        SqsMessageReceiveSucceeded, SqsS3EventRecordInvalidEventIgnored,
     },
-    line_agg::{self, LineAgg},
+    line_agg,
     shutdown::ShutdownSignal,
-    sources::aws_s3::AwsS3Config,
     tls::TlsConfig,
     SourceSender,
 };
-use lookup::{metadata_path, path, PathPrefix};
-use vector_core::{
-    config::{log_schema, LegacyKey, LogNamespace},
-    ByteSizeOf,
-};
+use vector_core::config::LogNamespace;
 
 static SUPPORTED_S3_EVENT_VERSION: Lazy<semver::VersionReq> =
     Lazy::new(|| semver::VersionReq::parse("~2").unwrap());
@@ -259,11 +247,8 @@ impl Ingestor {
 
 pub struct IngestorProcess {
     state: Arc<State>,
-    out: SourceSender,
+    objects: ObjectProcessor,
     shutdown: ShutdownSignal,
-    acknowledgements: bool,
-    log_namespace: LogNamespace,
-    bytes_received: Registered<BytesReceived>,
 }
 
 impl IngestorProcess {
@@ -274,13 +259,18 @@ impl IngestorProcess {
         log_namespace: LogNamespace,
         acknowledgements: bool,
     ) -> Self {
-        Self {
-            state,
+        let objects = ObjectProcessor::new(
+            state.s3_client.clone(),
+            state.compression,
+            state.multiline.clone(),
             out,
-            shutdown,
             acknowledgements,
             log_namespace,
-            bytes_received: register!(BytesReceived::from(Protocol::HTTP)),
+        );
+        Self {
+            state,
+            objects,
+            shutdown,
         }
     }
 
@@ -405,8 +395,7 @@ impl IngestorProcess {
 
     async fn handle_s3_event(&mut self, s3_event: S3Event) -> Result<(), ProcessingError> {
         for record in s3_event.records {
-            self.handle_s3_event_record(record, self.log_namespace)
-                .await?
+            self.handle_s3_event_record(record).await?
         }
         Ok(())
     }
@@ -414,7 +403,6 @@ impl IngestorProcess {
     async fn handle_s3_event_record(
         &mut self,
         s3_event: S3EventRecord,
-        log_namespace: LogNamespace,
     ) -> Result<(), ProcessingError> {
         let event_version: semver::Version = s3_event.event_version.clone().into();
         if !SUPPORTED_S3_EVENT_VERSION.matches(&event_version) {
@@ -443,191 +431,13 @@ impl IngestorProcess {
             });
         }
 
-        let object_result = self
-            .state
-            .s3_client
-            .get_object()
-            .bucket(s3_event.s3.bucket.name.clone())
-            .key(s3_event.s3.object.key.clone())
-            .send()
+        self.objects
+            .process(
+                &s3_event.s3.bucket.name,
+                &s3_event.s3.object.key,
+                &s3_event.aws_region,
+            )
             .await
-            .context(GetObjectSnafu {
-                bucket: s3_event.s3.bucket.name.clone(),
-                key: s3_event.s3.object.key.clone(),
-            });
-
-        let object = object_result?;
-
-        let metadata = object.metadata;
-
-        let timestamp = object
-            .last_modified
-            .map(|ts| Utc.timestamp(ts.secs(), ts.subsec_nanos()));
-
-        let (batch, receiver) = BatchNotifier::maybe_new_with_receiver(self.acknowledgements);
-        let object_reader = super::s3_object_decoder(
-            self.state.compression,
-            &s3_event.s3.object.key,
-            object.content_encoding.as_deref(),
-            object.content_type.as_deref(),
-            object.body,
-        )
-        .await;
-
-        // Record the read error seen to propagate up later so we avoid ack'ing the SQS
-        // message
-        //
-        // String is used as we cannot clone std::io::Error to take ownership in closure
-        //
-        // FramedRead likely stops when it gets an i/o error but I found it more clear to
-        // show that we `take_while` there hasn't been an error
-        //
-        // This can result in objects being partially processed before an error, but we
-        // prefer duplicate lines over message loss. Future work could include recording
-        // the offset of the object that has been read, but this would only be relevant in
-        // the case that the same vector instance processes the same message.
-        let mut read_error = None;
-        let bytes_received = self.bytes_received.clone();
-        let lines: Box<dyn Stream<Item = Bytes> + Send + Unpin> = Box::new(
-            FramedRead::new(object_reader, CharacterDelimitedDecoder::new(b'\n'))
-                .map(|res| {
-                    res.map(|bytes| {
-                        bytes_received.emit(ByteSize(bytes.len()));
-                        bytes
-                    })
-                    .map_err(|err| {
-                        read_error = Some(err);
-                    })
-                    .ok()
-                })
-                .take_while(|res| ready(res.is_some()))
-                .map(|r| r.expect("validated by take_while")),
-        );
-
-        let lines = match &self.state.multiline {
-            Some(config) => Box::new(
-                LineAgg::new(
-                    lines.map(|line| ((), line, ())),
-                    line_agg::Logic::new(config.clone()),
-                )
-                .map(|(_src, line, _context)| line),
-            ),
-            None => lines,
-        };
-
-        let mut stream = lines.map(|line| {
-            let deserializer = BytesDeserializer::new();
-            let mut log = deserializer
-                .parse_single(line, log_namespace)
-                .with_batch_notifier_option(&batch);
-
-            log_namespace.insert_source_metadata(
-                AwsS3Config::NAME,
-                &mut log,
-                Some(LegacyKey::Overwrite(path!("bucket"))),
-                path!("bucket"),
-                Bytes::from(s3_event.s3.bucket.name.as_bytes().to_vec()),
-            );
-            log_namespace.insert_source_metadata(
-                AwsS3Config::NAME,
-                &mut log,
-                Some(LegacyKey::Overwrite(path!("object"))),
-                path!("object"),
-                Bytes::from(s3_event.s3.object.key.as_bytes().to_vec()),
-            );
-            log_namespace.insert_source_metadata(
-                AwsS3Config::NAME,
-                &mut log,
-                Some(LegacyKey::Overwrite(path!("region"))),
-                path!("region"),
-                Bytes::from(s3_event.aws_region.as_bytes().to_vec()),
-            );
-
-            if let Some(metadata) = &metadata {
-                for (key, value) in metadata {
-                    log_namespace.insert_source_metadata(
-                        AwsS3Config::NAME,
-                        &mut log,
-                        Some(LegacyKey::Overwrite(key.as_str())),
-                        path!("metadata", key.as_str()),
-                        value.clone(),
-                    );
-                }
-            }
-
-            log_namespace.insert_vector_metadata(
-                &mut log,
-                path!(log_schema().source_type_key()),
-                path!("source_type"),
-                Bytes::from_static(AwsS3Config::NAME.as_bytes()),
-            );
-
-            // This handles the transition from the original timestamp logic. Originally the
-            // `timestamp_key` was populated by the `last_modified` time on the object, falling
-            // back to calling `now()`.
-            match log_namespace {
-                LogNamespace::Vector => {
-                    if let Some(timestamp) = timestamp {
-                        log.insert(metadata_path!(AwsS3Config::NAME, "timestamp"), timestamp);
-                    }
-
-                    log.insert(metadata_path!("vector", "ingest_timestamp"), Utc::now());
-                }
-                LogNamespace::Legacy => {
-                    log.try_insert(
-                        (PathPrefix::Event, log_schema().timestamp_key()),
-                        timestamp.unwrap_or_else(Utc::now),
-                    );
-                }
-            };
-
-            emit!(EventsReceived {
-                count: 1,
-                byte_size: log.size_of()
-            });
-
-            log
-        });
-
-        let send_error = match self.out.send_event_stream(&mut stream).await {
-            Ok(_) => None,
-            Err(error) => {
-                let (count, _) = stream.size_hint();
-                emit!(StreamClosedError { error, count });
-                Some(crate::source_sender::ClosedError)
-            }
-        };
-
-        // Up above, `lines` captures `read_error`, and eventually is captured by `stream`,
-        // so we explicitly drop it so that we can again utilize `read_error` below.
-        drop(stream);
-
-        if let Some(error) = read_error {
-            Err(ProcessingError::ReadObject {
-                source: error,
-                bucket: s3_event.s3.bucket.name.clone(),
-                key: s3_event.s3.object.key.clone(),
-            })
-        } else if let Some(error) = send_error {
-            Err(ProcessingError::PipelineSend {
-                source: error,
-                bucket: s3_event.s3.bucket.name.clone(),
-                key: s3_event.s3.object.key.clone(),
-            })
-        } else {
-            match receiver {
-                None => Ok(()),
-                Some(receiver) => match receiver.await {
-                    BatchStatus::Delivered => Ok(()),
-                    BatchStatus::Errored => Err(ProcessingError::ErrorAcknowledgement),
-                    BatchStatus::Rejected => {
-                        // Sinks are responsible for emitting ComponentEventsDropped.
-                        // Failed events cannot be retried, so continue to delete the SQS source message.
-                        Ok(())
-                    }
-                },
-            }
-        }
     }
 
     async fn receive_messages(&mut self) -> Result<Vec<Message>, SdkError<ReceiveMessageError>> {
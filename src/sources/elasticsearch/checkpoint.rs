@@ -0,0 +1,145 @@
+//! Persists how far the query was read, so that documents aren't sent twice across restarts of
+//! Vector, nor across the polls for new documents.
+use std::{
+    collections::BTreeSet,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+
+const CHECKPOINT_FILENAME: &str = "checkpoint.json";
+const TMP_FILENAME: &str = "checkpoint.new";
+
+/// How far the query was read.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub(super) struct Position {
+    /// The sort value of the timestamp field of the last document sent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(super) timestamp: Option<serde_json::Value>,
+
+    /// The IDs of the documents sent with that timestamp, which are queried again since documents
+    /// are searched from that timestamp included.
+    #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+    pub(super) ids: BTreeSet<String>,
+
+    /// Whether the query was read to the end, when it has no timestamp field to resume from.
+    #[serde(default)]
+    pub(super) completed: bool,
+}
+
+impl Position {
+    /// Records that a document was sent, returning whether it wasn't sent yet.
+    ///
+    /// Documents are sorted by timestamp, so a timestamp different from the last one is a later one.
+    pub(super) fn advance(&mut self, timestamp: serde_json::Value, id: &str) -> bool {
+        if self.timestamp.as_ref() == Some(&timestamp) {
+            self.ids.insert(id.to_owned())
+        } else {
+            self.timestamp = Some(timestamp);
+            self.ids = BTreeSet::from([id.to_owned()]);
+            true
+        }
+    }
+}
+
+#[derive(Debug)]
+pub(super) struct Checkpointer {
+    directory: PathBuf,
+    position: Position,
+}
+
+impl Checkpointer {
+    /// Reads the checkpoint of a data directory, if there is one.
+    pub(super) fn load(directory: &Path) -> io::Result<Self> {
+        let position = match fs::read(directory.join(CHECKPOINT_FILENAME)) {
+            Ok(contents) => serde_json::from_slice(&contents).map_err(|error| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("invalid checkpoint: {}", error),
+                )
+            })?,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Position::default(),
+            Err(error) => return Err(error),
+        };
+
+        Ok(Self {
+            directory: directory.to_owned(),
+            position,
+        })
+    }
+
+    pub(super) fn path(&self) -> PathBuf {
+        self.directory.join(CHECKPOINT_FILENAME)
+    }
+
+    pub(super) const fn position(&self) -> &Position {
+        &self.position
+    }
+
+    /// Records that the documents up to this position were sent.
+    pub(super) fn set(&mut self, position: Position) -> io::Result<()> {
+        if position == self.position {
+            return Ok(());
+        }
+
+        // Write the checkpoint beside the previous one and swap them, so that it's never left
+        // half-written.
+        let tmp_path = self.directory.join(TMP_FILENAME);
+        fs::write(&tmp_path, serde_json::to_vec(&position)?)?;
+        fs::rename(&tmp_path, self.path())?;
+
+        self.position = position;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn skips_documents_sent_with_the_last_timestamp() {
+        let mut position = Position::default();
+
+        assert!(position.advance(json!(1_600_000_000_000_u64), "a"));
+        assert!(position.advance(json!(1_600_000_000_000_u64), "b"));
+        assert!(!position.advance(json!(1_600_000_000_000_u64), "a"));
+        assert!(position.advance(json!(1_600_000_001_000_u64), "a"));
+        assert_eq!(position.ids, BTreeSet::from(["a".to_owned()]));
+    }
+
+    #[test]
+    fn persists_position() {
+        let directory = tempfile::tempdir().unwrap();
+
+        let mut checkpointer = Checkpointer::load(directory.path()).unwrap();
+        assert_eq!(checkpointer.position(), &Position::default());
+
+        let mut position = Position::default();
+        position.advance(json!(1_600_000_000_000_u64), "a");
+        checkpointer.set(position.clone()).unwrap();
+
+        let checkpointer = Checkpointer::load(directory.path()).unwrap();
+        assert_eq!(checkpointer.position(), &position);
+        assert_eq!(
+            fs::read_to_string(checkpointer.path()).unwrap(),
+            r#"{"timestamp":1600000000000,"ids":["a"],"completed":false}"#.to_owned()
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_checkpoints() {
+        let directory = tempfile::tempdir().unwrap();
+        fs::write(
+            directory.path().join(CHECKPOINT_FILENAME),
+            "not a checkpoint",
+        )
+        .unwrap();
+
+        let error = Checkpointer::load(directory.path()).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+    }
+}
@@ -0,0 +1,183 @@
+//! Requests to the point in time and search APIs, which Elasticsearch and OpenSearch serve at
+//! different paths.
+use aws_types::{credentials::SharedCredentialsProvider, region::Region};
+use bytes::{Buf, Bytes};
+use http::{header::CONTENT_TYPE, Method, Request, StatusCode};
+use hyper::Body;
+use serde::{de::DeserializeOwned, Deserialize};
+use serde_json::json;
+use snafu::{ResultExt, Snafu};
+use vector_common::internal_event::{
+    ByteSize, BytesReceived, InternalEventHandle as _, Registered,
+};
+
+use crate::http::{Auth, HttpClient};
+
+#[derive(Debug, Snafu)]
+pub(super) enum RequestError {
+    #[snafu(display("failed to build request: {}", source))]
+    BuildRequest { source: http::Error },
+    #[snafu(display("failed to sign request: {}", source))]
+    SignRequest { source: crate::Error },
+    #[snafu(display("request failed: {}", source))]
+    Send { source: crate::http::HttpError },
+    #[snafu(display("failed to read response: {}", source))]
+    ReadBody { source: hyper::Error },
+    #[snafu(display("unexpected status {}: {}", status, body))]
+    UnexpectedStatus { status: StatusCode, body: String },
+    #[snafu(display("invalid response: {}", source))]
+    InvalidResponse { source: serde_json::Error },
+}
+
+/// The search engine serving the endpoint, as told by its root endpoint.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(super) enum Distribution {
+    Elasticsearch,
+    OpenSearch,
+}
+
+#[derive(Debug, Deserialize)]
+pub(super) struct SearchResponse {
+    /// The ID of the point in time, which can change with each search.
+    pub(super) pit_id: Option<String>,
+    pub(super) hits: Hits,
+}
+
+#[derive(Debug, Deserialize)]
+pub(super) struct Hits {
+    pub(super) hits: Vec<Hit>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(super) struct Hit {
+    #[serde(rename = "_index")]
+    pub(super) index: String,
+    #[serde(rename = "_id")]
+    pub(super) id: String,
+    /// Indices can be configured to not store the source of documents.
+    #[serde(rename = "_source", default)]
+    pub(super) source: Option<serde_json::Value>,
+    #[serde(default)]
+    pub(super) sort: Vec<serde_json::Value>,
+}
+
+pub(super) struct Client {
+    pub(super) client: HttpClient,
+    pub(super) base_url: String,
+    pub(super) http_auth: Option<Auth>,
+    pub(super) aws_auth: Option<SharedCredentialsProvider>,
+    pub(super) region: Option<Region>,
+    pub(super) bytes_received: Registered<BytesReceived>,
+}
+
+impl Client {
+    pub(super) async fn distribution(&self) -> Result<Distribution, RequestError> {
+        #[derive(Deserialize)]
+        struct Root {
+            version: Version,
+        }
+
+        #[derive(Deserialize)]
+        struct Version {
+            distribution: Option<String>,
+        }
+
+        let root: Root = self.send(Method::GET, "/", None).await?;
+        Ok(match root.version.distribution.as_deref() {
+            Some("opensearch") => Distribution::OpenSearch,
+            _ => Distribution::Elasticsearch,
+        })
+    }
+
+    /// Opens a point in time, so that the pages of the search are taken from the same state of
+    /// the indices.
+    pub(super) async fn open_point_in_time(
+        &self,
+        distribution: Distribution,
+        index: &str,
+        keep_alive: &str,
+    ) -> Result<String, RequestError> {
+        #[derive(Deserialize)]
+        struct PointInTime {
+            #[serde(alias = "pit_id")]
+            id: String,
+        }
+
+        let path = match distribution {
+            Distribution::Elasticsearch => format!("/{}/_pit?keep_alive={}", index, keep_alive),
+            Distribution::OpenSearch => {
+                format!("/{}/_search/point_in_time?keep_alive={}", index, keep_alive)
+            }
+        };
+        let point_in_time: PointInTime = self.send(Method::POST, &path, None).await?;
+        Ok(point_in_time.id)
+    }
+
+    /// Closes a point in time, rather than holding the resources of the cluster until it expires.
+    pub(super) async fn close_point_in_time(
+        &self,
+        distribution: Distribution,
+        id: &str,
+    ) -> Result<(), RequestError> {
+        let (path, body) = match distribution {
+            Distribution::Elasticsearch => ("/_pit", json!({ "id": id })),
+            Distribution::OpenSearch => ("/_search/point_in_time", json!({ "pit_id": [id] })),
+        };
+        self.send::<serde_json::Value>(Method::DELETE, path, Some(&body))
+            .await
+            .map(drop)
+    }
+
+    pub(super) async fn search(
+        &self,
+        body: &serde_json::Value,
+    ) -> Result<SearchResponse, RequestError> {
+        self.send(Method::POST, "/_search", Some(body)).await
+    }
+
+    async fn send<T: DeserializeOwned>(
+        &self,
+        method: Method,
+        path: &str,
+        body: Option<&serde_json::Value>,
+    ) -> Result<T, RequestError> {
+        let mut builder = Request::builder()
+            .method(method)
+            .uri(format!("{}{}", self.base_url, path));
+        if let Some(authorization) = &self.http_auth {
+            builder = authorization.apply_builder(builder);
+        }
+        let body = match body {
+            Some(body) => {
+                builder = builder.header(CONTENT_TYPE, "application/json");
+                Bytes::from(body.to_string())
+            }
+            None => Bytes::new(),
+        };
+        let mut request = builder.body(body).context(BuildRequestSnafu)?;
+
+        if let Some(credentials_provider) = &self.aws_auth {
+            crate::aws::sign_request("es", &mut request, credentials_provider, &self.region)
+                .await
+                .context(SignRequestSnafu)?;
+        }
+
+        let response = self
+            .client
+            .send(request.map(Body::from))
+            .await
+            .context(SendSnafu)?;
+        let (parts, body) = response.into_parts();
+        let mut body = hyper::body::aggregate(body).await.context(ReadBodySnafu)?;
+        let body = body.copy_to_bytes(body.remaining());
+        self.bytes_received.emit(ByteSize(body.len()));
+
+        if !parts.status.is_success() {
+            return Err(RequestError::UnexpectedStatus {
+                status: parts.status,
+                body: String::from_utf8_lossy(&body).into_owned(),
+            });
+        }
+        serde_json::from_slice(&body).context(InvalidResponseSnafu)
+    }
+}
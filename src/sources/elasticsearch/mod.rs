@@ -0,0 +1,689 @@
+//! This mod implements `elasticsearch` source.
+//! The scope of this source is to run a query against the indices of an
+//! Elasticsearch or OpenSearch cluster, to read existing documents such as
+//! when migrating between clusters, and optionally to poll for new ones.
+
+use std::{num::NonZeroUsize, path::PathBuf, time::Duration};
+
+use chrono::Utc;
+use lookup::{owned_value_path, path};
+use serde_json::json;
+use snafu::{ResultExt, Snafu};
+use value::{kind::Collection, Kind};
+use vector_common::{
+    internal_event::{BytesReceived, EventsReceived, Protocol},
+    sensitive_string::SensitiveString,
+};
+use vector_config::{configurable_component, NamedComponent};
+use vector_core::{
+    config::{LegacyKey, LogNamespace},
+    event::Value,
+    schema::Definition,
+    ByteSizeOf,
+};
+
+use crate::{
+    aws::{AwsAuthentication, RegionOrEndpoint},
+    config::{DataType, GenerateConfig, Output, SourceConfig, SourceContext},
+    event::{Event, LogEvent},
+    http::{Auth, HttpClient, MaybeAuth},
+    internal_events::{ElasticsearchCheckpointError, ElasticsearchRequestError, StreamClosedError},
+    shutdown::ShutdownSignal,
+    sinks::util::UriSerde,
+    sources,
+    tls::{TlsConfig, TlsSettings},
+    SourceSender,
+};
+
+mod checkpoint;
+mod client;
+
+use self::{
+    checkpoint::{Checkpointer, Position},
+    client::{Client, Distribution, Hit, RequestError},
+};
+
+/// How long to wait before querying again after a request failed.
+const RETRY_DELAY: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("invalid query: {}", source))]
+    InvalidQuery { source: serde_json::Error },
+    #[snafu(display("`poll_interval_secs` requires `timestamp_field` to be set"))]
+    PollingWithoutTimestampField,
+    #[snafu(display("the `aws` authentication strategy requires `aws.region` to be set"))]
+    RegionRequired,
+}
+
+/// Authentication strategies.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields, rename_all = "snake_case", tag = "strategy")]
+pub enum ElasticsearchSourceAuth {
+    /// HTTP Basic Authentication.
+    Basic {
+        /// Basic authentication username.
+        user: String,
+
+        /// Basic authentication password.
+        password: SensitiveString,
+    },
+
+    /// Amazon OpenSearch Service-specific authentication.
+    Aws(#[configurable(derived)] AwsAuthentication),
+}
+
+/// Configuration for the `elasticsearch` source.
+#[configurable_component(source("elasticsearch"))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct ElasticsearchSourceConfig {
+    /// The Elasticsearch or OpenSearch endpoint to query, such as `http://10.24.32.122:9200`.
+    endpoint: String,
+
+    /// The indices to query, such as `logs-*`, as a comma-separated list of index names,
+    /// aliases, data streams or patterns.
+    index: String,
+
+    /// The query to run, in the query DSL, as JSON.
+    ///
+    /// By default, all the documents are matched.
+    #[serde(default = "default_query")]
+    query: String,
+
+    /// The field to sort documents by and to resume from, such as `@timestamp`.
+    ///
+    /// When set, documents without this field are skipped, and progress is checkpointed after
+    /// each page. Otherwise, the documents are read in no particular order, and a restart of
+    /// Vector before the end of the query reads it again from the start.
+    timestamp_field: Option<String>,
+
+    /// The interval between polls for new documents, in seconds.
+    ///
+    /// By default, the source stops once the query is read to the end. Polling requires the
+    /// `timestamp_field` option to be set.
+    poll_interval_secs: Option<u64>,
+
+    /// The number of documents to request per page.
+    #[serde(default = "default_page_size")]
+    page_size: NonZeroUsize,
+
+    /// How long to keep the point in time of the search alive between pages, in seconds.
+    #[serde(default = "default_keep_alive_secs")]
+    keep_alive_secs: u64,
+
+    /// The field to add the index of each document to.
+    ///
+    /// The index is added to the `elasticsearch.index` metadata field when the `vector` log
+    /// namespace is used.
+    #[serde(default = "default_index_key")]
+    index_key: String,
+
+    /// The field to add the ID of each document to.
+    ///
+    /// The ID is added to the `elasticsearch.id` metadata field when the `vector` log namespace
+    /// is used.
+    #[serde(default = "default_id_key")]
+    id_key: String,
+
+    /// The directory used to persist the progress of the query.
+    ///
+    /// By default, the global `data_dir` option is used. Please make sure the user Vector is running as has write permissions to this directory.
+    data_dir: Option<PathBuf>,
+
+    #[configurable(derived)]
+    auth: Option<ElasticsearchSourceAuth>,
+
+    #[configurable(derived)]
+    aws: Option<RegionOrEndpoint>,
+
+    #[configurable(derived)]
+    tls: Option<TlsConfig>,
+
+    /// The namespace to use for logs. This overrides the global setting.
+    #[configurable(metadata(docs::hidden))]
+    #[serde(default)]
+    log_namespace: Option<bool>,
+}
+
+fn default_query() -> String {
+    r#"{"match_all":{}}"#.to_owned()
+}
+
+fn default_page_size() -> NonZeroUsize {
+    NonZeroUsize::new(1000).unwrap()
+}
+
+const fn default_keep_alive_secs() -> u64 {
+    60
+}
+
+fn default_index_key() -> String {
+    "_index".to_owned()
+}
+
+fn default_id_key() -> String {
+    "_id".to_owned()
+}
+
+impl GenerateConfig for ElasticsearchSourceConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"
+            endpoint = "http://localhost:9200"
+            index = "logs-*"
+            "#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+impl SourceConfig for ElasticsearchSourceConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<sources::Source> {
+        let log_namespace = cx.log_namespace(self.log_namespace);
+
+        let query = serde_json::from_str(&self.query).context(InvalidQuerySnafu)?;
+        if self.poll_interval_secs.is_some() && self.timestamp_field.is_none() {
+            return Err(BuildError::PollingWithoutTimestampField.into());
+        }
+
+        let authorization = match &self.auth {
+            Some(ElasticsearchSourceAuth::Basic { user, password }) => Some(Auth::Basic {
+                user: user.clone(),
+                password: password.clone(),
+            }),
+            _ => None,
+        };
+        let uri = self.endpoint.parse::<UriSerde>()?;
+        let http_auth = authorization.choose_one(&uri.auth)?;
+        let base_url = uri.uri.to_string().trim_end_matches('/').to_owned();
+
+        let region = self.aws.as_ref().and_then(RegionOrEndpoint::region);
+        let aws_auth = match &self.auth {
+            Some(ElasticsearchSourceAuth::Basic { .. }) | None => None,
+            Some(ElasticsearchSourceAuth::Aws(aws)) => {
+                let region = region.clone().ok_or(BuildError::RegionRequired)?;
+                Some(aws.credentials_provider(region).await?)
+            }
+        };
+
+        let tls = TlsSettings::from_options(&self.tls)?;
+        let client = Client {
+            client: HttpClient::new(tls, &cx.proxy)?,
+            base_url,
+            http_auth,
+            aws_auth,
+            region,
+            bytes_received: register!(BytesReceived::from(Protocol::HTTP)),
+        };
+
+        let data_dir = cx
+            .globals
+            .resolve_and_make_data_subdir(self.data_dir.as_ref(), cx.key.id())?;
+        let checkpointer = Checkpointer::load(&data_dir)?;
+
+        let source = Source {
+            client,
+            checkpointer,
+            distribution: None,
+            index: self.index.clone(),
+            query,
+            timestamp_field: self.timestamp_field.clone(),
+            poll_interval: self.poll_interval_secs.map(Duration::from_secs),
+            page_size: self.page_size.get(),
+            keep_alive: format!("{}s", self.keep_alive_secs),
+            index_key: self.index_key.clone(),
+            id_key: self.id_key.clone(),
+            log_namespace,
+        };
+        Ok(Box::pin(source.run(cx.out, cx.shutdown)))
+    }
+
+    fn outputs(&self, global_log_namespace: LogNamespace) -> Vec<Output> {
+        let log_namespace = global_log_namespace.merge(self.log_namespace);
+        let schema_definition = self.schema_definition(log_namespace);
+
+        vec![Output::default(DataType::Log).with_schema_definition(schema_definition)]
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        false
+    }
+}
+
+impl ElasticsearchSourceConfig {
+    fn schema_definition(&self, log_namespace: LogNamespace) -> Definition {
+        Definition::new_with_default_metadata(Kind::object(Collection::any()), [log_namespace])
+            .with_source_metadata(
+                Self::NAME,
+                Some(LegacyKey::InsertIfEmpty(owned_value_path!(&self.index_key))),
+                &owned_value_path!("index"),
+                Kind::bytes(),
+                None,
+            )
+            .with_source_metadata(
+                Self::NAME,
+                Some(LegacyKey::InsertIfEmpty(owned_value_path!(&self.id_key))),
+                &owned_value_path!("id"),
+                Kind::bytes(),
+                None,
+            )
+            .with_standard_vector_source_metadata()
+    }
+}
+
+/// Why reading the query stopped before its end.
+enum ReadError {
+    Request {
+        request: &'static str,
+        error: RequestError,
+    },
+    StreamClosed,
+}
+
+struct Source {
+    client: Client,
+    checkpointer: Checkpointer,
+    distribution: Option<Distribution>,
+    index: String,
+    query: serde_json::Value,
+    timestamp_field: Option<String>,
+    poll_interval: Option<Duration>,
+    page_size: usize,
+    keep_alive: String,
+    index_key: String,
+    id_key: String,
+    log_namespace: LogNamespace,
+}
+
+impl Source {
+    async fn run(mut self, mut out: SourceSender, mut shutdown: ShutdownSignal) -> Result<(), ()> {
+        if self.checkpointer.position().completed {
+            info!(
+                message = "Query was already read to the end.",
+                path = ?self.checkpointer.path(),
+            );
+            return Ok(());
+        }
+
+        loop {
+            let result = tokio::select! {
+                result = self.read(&mut out) => result,
+                _ = &mut shutdown => break,
+            };
+
+            let delay = match result {
+                Ok(()) => match self.poll_interval {
+                    Some(poll_interval) => poll_interval,
+                    None => {
+                        if self.timestamp_field.is_none() {
+                            let position = Position {
+                                completed: true,
+                                ..Position::default()
+                            };
+                            if let Err(error) = self.checkpointer.set(position) {
+                                emit!(ElasticsearchCheckpointError {
+                                    error,
+                                    path: self.checkpointer.path(),
+                                });
+                            }
+                        }
+                        break;
+                    }
+                },
+                Err(ReadError::Request { request, error }) => {
+                    emit!(ElasticsearchRequestError { error, request });
+                    RETRY_DELAY
+                }
+                Err(ReadError::StreamClosed) => return Err(()),
+            };
+
+            tokio::select! {
+                _ = tokio::time::sleep(delay) => {},
+                _ = &mut shutdown => break,
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Reads the query from the checkpoint to its end, in a new point in time.
+    async fn read(&mut self, out: &mut SourceSender) -> Result<(), ReadError> {
+        let distribution = match self.distribution {
+            Some(distribution) => distribution,
+            None => {
+                let distribution =
+                    self.client
+                        .distribution()
+                        .await
+                        .map_err(|error| ReadError::Request {
+                            request: "info",
+                            error,
+                        })?;
+                self.distribution = Some(distribution);
+                distribution
+            }
+        };
+
+        let mut pit_id = self
+            .client
+            .open_point_in_time(distribution, &self.index, &self.keep_alive)
+            .await
+            .map_err(|error| ReadError::Request {
+                request: "open_point_in_time",
+                error,
+            })?;
+
+        let result = self.read_pages(&mut pit_id, out).await;
+
+        if let Err(error) = self.client.close_point_in_time(distribution, &pit_id).await {
+            emit!(ElasticsearchRequestError {
+                error,
+                request: "close_point_in_time",
+            });
+        }
+        result
+    }
+
+    async fn read_pages(
+        &mut self,
+        pit_id: &mut String,
+        out: &mut SourceSender,
+    ) -> Result<(), ReadError> {
+        let mut search_after = None;
+        loop {
+            let body = search_body(
+                &self.query,
+                self.timestamp_field.as_deref(),
+                self.checkpointer.position(),
+                self.page_size,
+                pit_id,
+                &self.keep_alive,
+                search_after.as_deref(),
+            );
+            let response = self
+                .client
+                .search(&body)
+                .await
+                .map_err(|error| ReadError::Request {
+                    request: "search",
+                    error,
+                })?;
+            if let Some(id) = response.pit_id {
+                *pit_id = id;
+            }
+
+            let hits = response.hits.hits;
+            let page_len = hits.len();
+            search_after = hits.last().map(|hit| hit.sort.clone());
+
+            let mut position = self.checkpointer.position().clone();
+            let now = Utc::now();
+            let events = hits
+                .into_iter()
+                .filter(|hit| match (&self.timestamp_field, hit.sort.first()) {
+                    (Some(_), Some(timestamp)) => position.advance(timestamp.clone(), &hit.id),
+                    _ => true,
+                })
+                .map(|hit| {
+                    Event::from(create_log(
+                        hit,
+                        &self.index_key,
+                        &self.id_key,
+                        self.log_namespace,
+                        now,
+                    ))
+                })
+                .collect::<Vec<_>>();
+
+            if !events.is_empty() {
+                let count = events.len();
+                let byte_size = events.size_of();
+                emit!(EventsReceived { count, byte_size });
+
+                if let Err(error) = out.send_batch(events).await {
+                    emit!(StreamClosedError { error, count });
+                    return Err(ReadError::StreamClosed);
+                }
+            }
+
+            if self.timestamp_field.is_some() {
+                if let Err(error) = self.checkpointer.set(position) {
+                    emit!(ElasticsearchCheckpointError {
+                        error,
+                        path: self.checkpointer.path(),
+                    });
+                }
+            }
+
+            if page_len < self.page_size {
+                return Ok(());
+            }
+        }
+    }
+}
+
+/// Builds the body of a search for the page after `search_after`.
+///
+/// With a timestamp field, documents are searched from the timestamp of the checkpoint included,
+/// since more documents can have the same timestamp.
+fn search_body(
+    query: &serde_json::Value,
+    timestamp_field: Option<&str>,
+    position: &Position,
+    page_size: usize,
+    pit_id: &str,
+    keep_alive: &str,
+    search_after: Option<&[serde_json::Value]>,
+) -> serde_json::Value {
+    let mut body = json!({
+        "size": page_size,
+        "pit": { "id": pit_id, "keep_alive": keep_alive },
+        "track_total_hits": false,
+    });
+
+    match timestamp_field {
+        Some(field) => {
+            let mut filter = vec![json!({ "exists": { "field": field } })];
+            if let Some(timestamp) = &position.timestamp {
+                filter.push(json!({ "range": { field: { "gte": timestamp } } }));
+            }
+            body["query"] = json!({ "bool": { "must": [query], "filter": filter } });
+            body["sort"] = json!([{ field: "asc" }]);
+        }
+        None => body["query"] = query.clone(),
+    }
+
+    if let Some(search_after) = search_after {
+        body["search_after"] = json!(search_after);
+    }
+    body
+}
+
+fn create_log(
+    hit: Hit,
+    index_key: &str,
+    id_key: &str,
+    log_namespace: LogNamespace,
+    now: chrono::DateTime<Utc>,
+) -> LogEvent {
+    let source = hit
+        .source
+        .map(Value::from)
+        .unwrap_or_else(|| Value::Object(Default::default()));
+
+    let mut log = log_namespace.new_log_from_data(source);
+    log_namespace.insert_source_metadata(
+        ElasticsearchSourceConfig::NAME,
+        &mut log,
+        Some(LegacyKey::InsertIfEmpty(index_key)),
+        path!("index"),
+        hit.index,
+    );
+    log_namespace.insert_source_metadata(
+        ElasticsearchSourceConfig::NAME,
+        &mut log,
+        Some(LegacyKey::InsertIfEmpty(id_key)),
+        path!("id"),
+        hit.id,
+    );
+    log_namespace.insert_standard_vector_source_metadata(
+        &mut log,
+        ElasticsearchSourceConfig::NAME,
+        now,
+    );
+    log
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use vector_core::config::log_schema;
+
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<ElasticsearchSourceConfig>();
+    }
+
+    fn config(log_namespace: Option<bool>) -> ElasticsearchSourceConfig {
+        let mut config: ElasticsearchSourceConfig = toml::from_str(
+            r#"
+            endpoint = "http://localhost:9200"
+            index = "logs-*"
+            "#,
+        )
+        .unwrap();
+        config.log_namespace = log_namespace;
+        config
+    }
+
+    fn hit() -> Hit {
+        serde_json::from_value(json!({
+            "_index": "logs-2022.11.01",
+            "_id": "a1b2c3",
+            "_source": {
+                "@timestamp": "2022-11-01T12:00:00Z",
+                "message": "GET /index.html 200",
+                "http": { "status": 200 }
+            },
+            "sort": [1_667_304_000_000_u64]
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn creates_logs_from_hits() {
+        let log = create_log(hit(), "_index", "_id", LogNamespace::Legacy, Utc::now());
+
+        assert_eq!(log["message"], "GET /index.html 200".into());
+        assert_eq!(log["http.status"], 200.into());
+        assert_eq!(log["_index"], "logs-2022.11.01".into());
+        assert_eq!(log["_id"], "a1b2c3".into());
+        assert_eq!(log[log_schema().source_type_key()], "elasticsearch".into());
+
+        let definition = config(None).outputs(LogNamespace::Legacy)[0]
+            .clone()
+            .log_schema_definition
+            .unwrap();
+        assert_eq!(
+            definition.log_namespaces(),
+            &BTreeSet::from([LogNamespace::Legacy])
+        );
+        definition.assert_valid_for_event(&log.into());
+    }
+
+    #[test]
+    fn includes_vector_namespaced_fields() {
+        let log = create_log(hit(), "_index", "_id", LogNamespace::Vector, Utc::now());
+        let meta = log.metadata().value();
+
+        assert_eq!(log["message"], "GET /index.html 200".into());
+        assert!(log.get("_id").is_none());
+        assert_eq!(
+            meta.get(path!(ElasticsearchSourceConfig::NAME, "index"))
+                .unwrap(),
+            &Value::from("logs-2022.11.01")
+        );
+        assert_eq!(
+            meta.get(path!(ElasticsearchSourceConfig::NAME, "id"))
+                .unwrap(),
+            &Value::from("a1b2c3")
+        );
+        assert_eq!(
+            meta.get(path!("vector", "source_type")).unwrap(),
+            &Value::from(ElasticsearchSourceConfig::NAME)
+        );
+
+        let definition = config(Some(true)).outputs(LogNamespace::Vector)[0]
+            .clone()
+            .log_schema_definition
+            .unwrap();
+        definition.assert_valid_for_event(&log.into());
+    }
+
+    #[test]
+    fn searches_whole_query_without_timestamp_field() {
+        let query = json!({ "term": { "service": "web" } });
+        let body = search_body(
+            &query,
+            None,
+            &Position::default(),
+            500,
+            "pit-1",
+            "60s",
+            Some(&[json!(42)]),
+        );
+
+        assert_eq!(
+            body,
+            json!({
+                "size": 500,
+                "pit": { "id": "pit-1", "keep_alive": "60s" },
+                "track_total_hits": false,
+                "query": { "term": { "service": "web" } },
+                "search_after": [42]
+            })
+        );
+    }
+
+    #[test]
+    fn searches_from_checkpointed_timestamp() {
+        let query = json!({ "match_all": {} });
+        let mut position = Position::default();
+        position.advance(json!(1_667_304_000_000_u64), "a1b2c3");
+
+        let body = search_body(
+            &query,
+            Some("@timestamp"),
+            &position,
+            1000,
+            "pit-1",
+            "60s",
+            None,
+        );
+
+        assert_eq!(
+            body,
+            json!({
+                "size": 1000,
+                "pit": { "id": "pit-1", "keep_alive": "60s" },
+                "track_total_hits": false,
+                "query": {
+                    "bool": {
+                        "must": [{ "match_all": {} }],
+                        "filter": [
+                            { "exists": { "field": "@timestamp" } },
+                            { "range": { "@timestamp": { "gte": 1_667_304_000_000_u64 } } }
+                        ]
+                    }
+                },
+                "sort": [{ "@timestamp": "asc" }]
+            })
+        );
+    }
+}
@@ -28,6 +28,8 @@ mod disk;
 mod filesystem;
 mod memory;
 mod network;
+#[cfg(target_os = "linux")]
+mod process;
 
 /// Collector types.
 #[configurable_component]
@@ -58,6 +60,10 @@ pub enum Collector {
 
     /// Network.
     Network,
+
+    /// Process.
+    #[cfg(target_os = "linux")]
+    Process,
 }
 
 /// Filtering configuration.
@@ -83,7 +89,7 @@ pub struct HostMetricsConfig {
 
     /// The list of host metric collector services to use.
     ///
-    /// Defaults to all collectors.
+    /// Defaults to all collectors except `process`.
     pub collectors: Option<Vec<Collector>>,
 
     /// Overrides the default namespace for the metrics emitted by the source.
@@ -109,6 +115,11 @@ pub struct HostMetricsConfig {
     #[configurable(derived)]
     #[serde(default)]
     pub network: network::NetworkConfig,
+
+    #[cfg(target_os = "linux")]
+    #[configurable(derived)]
+    #[serde(default)]
+    pub(crate) process: process::ProcessConfig,
 }
 
 const fn default_scrape_interval() -> f64 {
@@ -170,6 +181,9 @@ impl HostMetricsConfig {
 
     fn has_collector(&self, collector: Collector) -> bool {
         match &self.collectors {
+            #[cfg(target_os = "linux")]
+            None => collector != Collector::Process,
+            #[cfg(not(target_os = "linux"))]
             None => true,
             Some(collectors) => collectors.iter().any(|&c| c == collector),
         }
@@ -230,6 +244,10 @@ impl HostMetrics {
         if self.config.has_collector(Collector::Network) {
             self.network_metrics(&mut buffer).await;
         }
+        #[cfg(target_os = "linux")]
+        if self.config.has_collector(Collector::Process) {
+            self.process_metrics(&mut buffer).await;
+        }
 
         let metrics = buffer.metrics;
         emit!(EventsReceived {
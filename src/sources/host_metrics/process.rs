@@ -0,0 +1,397 @@
+use std::{
+    io,
+    path::{Path, PathBuf},
+};
+
+use heim::units::time::second;
+use tokio::fs;
+use vector_config::configurable_component;
+use vector_core::metric_tags;
+
+use super::{filter_result, FilterList, HostMetrics, MetricsBuffer};
+use crate::internal_events::HostMetricsScrapeDetailError;
+
+/// The duration of the clock ticks the kernel reports process times in, in seconds.
+///
+/// The kernel always reports these in `USER_HZ`, which is 100 on every architecture.
+const CLOCK_TICKS: f64 = 1.0 / 100.0;
+
+const KILOBYTES: f64 = 1024.0;
+
+/// Options for the “process” metrics collector.
+///
+/// This collector is only available on Linux systems. As every process adds its own set of
+/// metrics, it is not part of the default collectors.
+#[configurable_component]
+#[derive(Clone, Debug, Derivative)]
+#[derivative(Default)]
+#[serde(default)]
+pub(crate) struct ProcessConfig {
+    /// Lists of process name patterns to include or exclude.
+    ///
+    /// The name of a process is the name of its executable, truncated to 15 characters.
+    names: FilterList,
+
+    /// Lists of process command line patterns to include or exclude.
+    ///
+    /// The command line of a process is its arguments joined by spaces. Kernel threads don't have a
+    /// command line, so they are excluded as soon as any pattern is included.
+    cmdlines: FilterList,
+
+    /// The maximum number of processes to report metrics for.
+    ///
+    /// When more processes match the filters, only the ones with the highest CPU time are reported.
+    /// This bounds the cardinality of the emitted metrics.
+    #[derivative(Default(value = "default_max_processes()"))]
+    max_processes: usize,
+
+    /// Base procfs directory, for testing use only
+    #[serde(skip_serializing)]
+    base_dir: Option<PathBuf>,
+}
+
+const fn default_max_processes() -> usize {
+    100
+}
+
+impl HostMetrics {
+    pub(super) async fn process_metrics(&self, output: &mut MetricsBuffer) {
+        let config = &self.config.process;
+        let base_dir = config
+            .base_dir
+            .clone()
+            .unwrap_or_else(|| heim::os::linux::procfs_root().to_path_buf());
+
+        let mut processes = match filter_result(
+            load_processes(&base_dir, config).await,
+            "Failed to list processes.",
+        )
+        .await
+        {
+            Some(processes) => processes,
+            None => return,
+        };
+        processes.sort_by(|a, b| b.cpu_ticks().cmp(&a.cpu_ticks()));
+        processes.truncate(config.max_processes);
+
+        let boot_time = filter_result(
+            heim::host::boot_time().await,
+            "Failed to load host boot time info.",
+        )
+        .await
+        .map(|time| time.get::<second>() as f64);
+
+        output.name = "process";
+        for process in processes {
+            let tags = metric_tags!(
+                "name" => process.name,
+                "pid" => process.pid.to_string(),
+            );
+            for (mode, ticks) in [("user", process.utime), ("system", process.stime)] {
+                let mut tags = tags.clone();
+                tags.insert("mode".into(), mode.into());
+                output.counter(
+                    "process_cpu_seconds_total",
+                    ticks as f64 * CLOCK_TICKS,
+                    tags,
+                );
+            }
+            output.gauge(
+                "process_memory_rss_bytes",
+                process.rss_kb as f64 * KILOBYTES,
+                tags.clone(),
+            );
+            output.gauge(
+                "process_memory_virtual_bytes",
+                process.vsize_kb as f64 * KILOBYTES,
+                tags.clone(),
+            );
+            output.gauge("process_threads", process.threads as f64, tags.clone());
+            if let Some(open_fds) = process.open_fds {
+                output.gauge("process_open_fds", open_fds as f64, tags.clone());
+            }
+            if let Some(boot_time) = boot_time {
+                output.gauge(
+                    "process_start_time_seconds",
+                    boot_time + process.start_ticks as f64 * CLOCK_TICKS,
+                    tags,
+                );
+            }
+        }
+    }
+}
+
+/// The statistics of a single process.
+#[derive(Clone, Debug, Default)]
+struct Process {
+    pid: u32,
+    name: String,
+    utime: u64,
+    stime: u64,
+    start_ticks: u64,
+    threads: u64,
+    rss_kb: u64,
+    vsize_kb: u64,
+    open_fds: Option<usize>,
+}
+
+impl Process {
+    const fn cpu_ticks(&self) -> u64 {
+        self.utime + self.stime
+    }
+}
+
+/// Loads the processes matching the configured filters.
+async fn load_processes(base_dir: &Path, config: &ProcessConfig) -> io::Result<Vec<Process>> {
+    let mut processes = Vec::new();
+    let mut dir = fs::read_dir(base_dir).await?;
+    while let Some(entry) = dir.next_entry().await? {
+        let pid = match entry
+            .file_name()
+            .to_str()
+            .and_then(|name| name.parse().ok())
+        {
+            Some(pid) => pid,
+            None => continue,
+        };
+
+        // Processes routinely exit while being scanned, so the errors of a single process are
+        // only reported if they aren't caused by the process being gone.
+        match load_process(&entry.path(), pid, config).await {
+            Ok(Some(process)) => processes.push(process),
+            Ok(None) => (),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => (),
+            Err(error) => emit!(HostMetricsScrapeDetailError {
+                message: "Failed to load process info.",
+                error,
+            }),
+        }
+    }
+    Ok(processes)
+}
+
+/// Loads a single process, returns `None` if it doesn't match the configured filters.
+async fn load_process(
+    path: &Path,
+    pid: u32,
+    config: &ProcessConfig,
+) -> io::Result<Option<Process>> {
+    let stat = fs::read_to_string(path.join("stat")).await?;
+    let mut process = parse_stat(pid, &stat)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "malformed stat file"))?;
+
+    if !config.names.contains_str(Some(&process.name)) {
+        return Ok(None);
+    }
+
+    if config.cmdlines.includes.is_some() || config.cmdlines.excludes.is_some() {
+        let cmdline = fs::read(path.join("cmdline")).await?;
+        let cmdline = (!cmdline.is_empty()).then(|| parse_cmdline(&cmdline));
+        if !config.cmdlines.contains_str(cmdline.as_deref()) {
+            return Ok(None);
+        }
+    }
+
+    let status = fs::read_to_string(path.join("status")).await?;
+    parse_status(&status, &mut process);
+
+    // The file descriptors of processes owned by other users can't be listed without privileges.
+    process.open_fds = count_entries(&path.join("fd")).await.ok();
+
+    Ok(Some(process))
+}
+
+/// Parses the `stat` file of a process, see `proc(5)` for its format.
+fn parse_stat(pid: u32, stat: &str) -> Option<Process> {
+    // The name is enclosed in parentheses and can itself contain spaces or parentheses.
+    let start = stat.find('(')?;
+    let end = stat.rfind(')')?;
+    let name = stat.get(start + 1..end)?.to_owned();
+
+    // The fields following the name, starting with the state (the third field).
+    let fields = stat.get(end + 1..)?.split_whitespace().collect::<Vec<_>>();
+    let field = |index: usize| fields.get(index - 3)?.parse::<u64>().ok();
+
+    Some(Process {
+        pid,
+        name,
+        utime: field(14)?,
+        stime: field(15)?,
+        threads: field(20)?,
+        start_ticks: field(22)?,
+        ..Default::default()
+    })
+}
+
+/// Parses the memory usage out of the `status` file of a process.
+fn parse_status(status: &str, process: &mut Process) {
+    for line in status.lines() {
+        let (key, value) = match line.split_once(':') {
+            Some(pair) => pair,
+            None => continue,
+        };
+        let value = value.trim().trim_end_matches(" kB").parse().unwrap_or(0);
+        match key {
+            "VmRSS" => process.rss_kb = value,
+            "VmSize" => process.vsize_kb = value,
+            _ => (),
+        }
+    }
+}
+
+/// Joins the NUL-separated arguments of the `cmdline` file of a process with spaces.
+fn parse_cmdline(cmdline: &[u8]) -> String {
+    cmdline
+        .strip_suffix(&[0])
+        .unwrap_or(cmdline)
+        .split(|&byte| byte == 0)
+        .map(String::from_utf8_lossy)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+async fn count_entries(path: &Path) -> io::Result<usize> {
+    let mut count = 0;
+    let mut dir = fs::read_dir(path).await?;
+    while dir.next_entry().await?.is_some() {
+        count += 1;
+    }
+    Ok(count)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::{
+        super::{
+            tests::{count_name, count_tag},
+            HostMetrics, HostMetricsConfig, MetricsBuffer,
+        },
+        parse_cmdline, parse_stat, ProcessConfig,
+    };
+    use crate::event::MetricValue;
+
+    fn write_process(base_dir: &std::path::Path, pid: u32, name: &str, utime: u64, cmdline: &str) {
+        let dir = base_dir.join(pid.to_string());
+        fs::create_dir_all(dir.join("fd")).unwrap();
+        fs::write(
+            dir.join("stat"),
+            format!(
+                "{} ({}) S 1 1 1 0 -1 4194560 100 0 0 0 {} 20 0 0 20 0 3 0 500 100000 200 18446744073709551615",
+                pid, name, utime
+            ),
+        )
+        .unwrap();
+        fs::write(
+            dir.join("status"),
+            format!(
+                "Name:\t{}\nVmSize:\t  2048 kB\nVmRSS:\t    1024 kB\nThreads:\t3\n",
+                name
+            ),
+        )
+        .unwrap();
+        fs::write(dir.join("cmdline"), cmdline.replace(' ', "\0")).unwrap();
+        fs::write(dir.join("fd").join("0"), "").unwrap();
+    }
+
+    async fn process_metrics(config: ProcessConfig) -> Vec<crate::event::Metric> {
+        let mut buffer = MetricsBuffer::new(None);
+        HostMetrics::new(HostMetricsConfig {
+            process: config,
+            ..Default::default()
+        })
+        .process_metrics(&mut buffer)
+        .await;
+        buffer.metrics
+    }
+
+    #[test]
+    fn parses_stat() {
+        let process = parse_stat(
+            42,
+            "42 (tmux: server (1)) S 1 42 42 0 -1 4194560 1 0 0 0 7 3 0 0 20 0 2 0 1234 5 6",
+        )
+        .unwrap();
+
+        assert_eq!(process.name, "tmux: server (1)");
+        assert_eq!(process.utime, 7);
+        assert_eq!(process.stime, 3);
+        assert_eq!(process.threads, 2);
+        assert_eq!(process.start_ticks, 1234);
+    }
+
+    #[test]
+    fn parses_cmdline() {
+        assert_eq!(
+            parse_cmdline(b"vector\0--config\0a.toml\0"),
+            "vector --config a.toml"
+        );
+    }
+
+    #[tokio::test]
+    async fn generates_process_metrics() {
+        let base_dir = tempfile::tempdir().unwrap();
+        write_process(base_dir.path(), 1, "init", 10, "/sbin/init");
+        write_process(base_dir.path(), 2, "vector", 20, "vector --config a.toml");
+        fs::create_dir(base_dir.path().join("self")).unwrap();
+
+        let metrics = process_metrics(ProcessConfig {
+            base_dir: Some(base_dir.path().into()),
+            ..Default::default()
+        })
+        .await;
+
+        assert_eq!(count_tag(&metrics, "pid"), metrics.len());
+        assert_eq!(count_name(&metrics, "process_cpu_seconds_total"), 4);
+        for name in &[
+            "process_memory_rss_bytes",
+            "process_memory_virtual_bytes",
+            "process_threads",
+            "process_open_fds",
+            "process_start_time_seconds",
+        ] {
+            assert_eq!(count_name(&metrics, name), 2, "name={}", name);
+        }
+
+        let rss = metrics
+            .iter()
+            .find(|metric| metric.name() == "process_memory_rss_bytes")
+            .unwrap();
+        assert_eq!(rss.value(), &MetricValue::Gauge { value: 1048576.0 });
+    }
+
+    #[tokio::test]
+    async fn filters_and_limits_processes() {
+        let base_dir = tempfile::tempdir().unwrap();
+        write_process(base_dir.path(), 1, "init", 10, "/sbin/init");
+        write_process(base_dir.path(), 2, "vector", 20, "vector --config a.toml");
+        write_process(base_dir.path(), 3, "vector", 30, "vector --config b.toml");
+
+        let pids = |metrics: Vec<crate::event::Metric>| {
+            let mut pids = metrics
+                .iter()
+                .map(|metric| metric.tags().unwrap().get("pid").unwrap().to_owned())
+                .collect::<Vec<_>>();
+            pids.dedup();
+            pids
+        };
+
+        let metrics = process_metrics(ProcessConfig {
+            names: serde_json::from_str(r#"{"includes": ["vec*"]}"#).unwrap(),
+            cmdlines: serde_json::from_str(r#"{"excludes": ["* b.toml"]}"#).unwrap(),
+            base_dir: Some(base_dir.path().into()),
+            ..Default::default()
+        })
+        .await;
+        assert_eq!(pids(metrics), vec!["2"]);
+
+        let metrics = process_metrics(ProcessConfig {
+            max_processes: 2,
+            base_dir: Some(base_dir.path().into()),
+            ..Default::default()
+        })
+        .await;
+        assert_eq!(pids(metrics), vec!["3", "2"]);
+    }
+}
@@ -0,0 +1,104 @@
+//! Persists the resource version of the last Kubernetes event sent, so that the events listed
+//! again when the watch restarts aren't sent twice, including across restarts of Vector.
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+const CHECKPOINT_FILENAME: &str = "checkpoint.txt";
+const TMP_FILENAME: &str = "checkpoint.new";
+
+#[derive(Debug)]
+pub(super) struct Checkpointer {
+    directory: PathBuf,
+    resource_version: Option<u64>,
+}
+
+impl Checkpointer {
+    /// Reads the checkpoint of a data directory, if there is one.
+    pub(super) fn load(directory: &Path) -> io::Result<Self> {
+        let resource_version = match fs::read_to_string(directory.join(CHECKPOINT_FILENAME)) {
+            Ok(contents) => Some(contents.trim().parse::<u64>().map_err(|error| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("invalid resource version: {}", error),
+                )
+            })?),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => None,
+            Err(error) => return Err(error),
+        };
+
+        Ok(Self {
+            directory: directory.to_owned(),
+            resource_version,
+        })
+    }
+
+    pub(super) fn path(&self) -> PathBuf {
+        self.directory.join(CHECKPOINT_FILENAME)
+    }
+
+    /// Whether an event with this resource version wasn't sent yet.
+    ///
+    /// Resource versions are opaque to clients, but the API server gives the revisions of etcd,
+    /// which increase with each change. Events with resource versions that aren't integers are
+    /// always considered new.
+    pub(super) fn is_new(&self, resource_version: Option<u64>) -> bool {
+        match (resource_version, self.resource_version) {
+            (Some(resource_version), Some(checkpoint)) => resource_version > checkpoint,
+            _ => true,
+        }
+    }
+
+    /// Records that the events up to this resource version were sent.
+    pub(super) fn set(&mut self, resource_version: u64) -> io::Result<()> {
+        if !self.is_new(Some(resource_version)) {
+            return Ok(());
+        }
+
+        // Write the checkpoint beside the previous one and swap them, so that it's never left
+        // half-written.
+        let tmp_path = self.directory.join(TMP_FILENAME);
+        fs::write(&tmp_path, format!("{}\n", resource_version))?;
+        fs::rename(&tmp_path, self.path())?;
+
+        self.resource_version = Some(resource_version);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn persists_resource_version() {
+        let directory = tempfile::tempdir().unwrap();
+
+        let mut checkpointer = Checkpointer::load(directory.path()).unwrap();
+        assert!(checkpointer.is_new(Some(1)));
+
+        checkpointer.set(42).unwrap();
+        checkpointer.set(41).unwrap();
+        assert!(!checkpointer.is_new(Some(42)));
+        assert!(checkpointer.is_new(Some(43)));
+        assert!(checkpointer.is_new(None));
+
+        let checkpointer = Checkpointer::load(directory.path()).unwrap();
+        assert!(!checkpointer.is_new(Some(42)));
+        assert!(checkpointer.is_new(Some(43)));
+        assert_eq!(
+            fs::read_to_string(checkpointer.path()).unwrap(),
+            "42\n".to_owned()
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_checkpoints() {
+        let directory = tempfile::tempdir().unwrap();
+        fs::write(directory.path().join(CHECKPOINT_FILENAME), "not a version").unwrap();
+
+        let error = Checkpointer::load(directory.path()).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+    }
+}
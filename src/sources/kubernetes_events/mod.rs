@@ -0,0 +1,649 @@
+//! This mod implements `kubernetes_events` source.
+//! The scope of this source is to watch the `Event` objects of a Kubernetes
+//! cluster, such as the ones reporting `OOMKilling`, `FailedScheduling` or
+//! `BackOff`, and to emit each of them as a log event.
+
+use std::{collections::BTreeMap, path::PathBuf, time::Duration};
+
+use chrono::{DateTime, Utc};
+use futures::{pin_mut, StreamExt};
+use k8s_openapi::api::core::v1::{Event as KubeEvent, ObjectReference, Pod};
+use kube::{
+    api::{Api, ListParams},
+    config::{self, KubeConfigOptions},
+    runtime::{
+        reflector::{self, store::Store, ObjectRef},
+        watcher,
+    },
+    Client, Config as ClientConfig,
+};
+use lookup::{owned_value_path, path};
+use value::{
+    kind::{Collection, Field},
+    Kind,
+};
+use vector_common::internal_event::EventsReceived;
+use vector_config::{configurable_component, NamedComponent};
+use vector_core::{
+    config::{log_schema, LegacyKey, LogNamespace},
+    event::Value,
+    schema::Definition,
+    ByteSizeOf,
+};
+
+use crate::{
+    config::{DataType, GenerateConfig, Output, SourceConfig, SourceContext},
+    event::{Event, LogEvent},
+    internal_events::{
+        KubernetesEventsCheckpointError, KubernetesEventsWatchError, StreamClosedError,
+    },
+    kubernetes::{custom_reflector, meta_cache::MetaCache},
+    shutdown::ShutdownSignal,
+    sources, SourceSender,
+};
+
+mod checkpoint;
+
+use self::checkpoint::Checkpointer;
+
+/// Configuration for the `kubernetes_events` source.
+#[configurable_component(source("kubernetes_events"))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields, default)]
+pub struct Config {
+    /// Specifies the field selector to filter `Event`s with, such as `type=Warning` or
+    /// `involvedObject.namespace=default`.
+    field_selector: Option<String>,
+
+    /// Specifies the label selector to filter `Event`s with.
+    label_selector: Option<String>,
+
+    /// Whether or not to add the labels of the `Pod`s that events are about, in the
+    /// `involved_object.labels` field.
+    ///
+    /// The `Pod`s of the whole cluster are watched to do so.
+    include_pod_labels: bool,
+
+    /// The directory used to persist the resource version of the last event sent.
+    ///
+    /// By default, the global `data_dir` option is used. Please make sure the user Vector is running as has write permissions to this directory.
+    data_dir: Option<PathBuf>,
+
+    /// Optional path to a kubeconfig file readable by Vector. If not set,
+    /// Vector will try to connect to Kubernetes using in-cluster configuration.
+    kube_config_file: Option<PathBuf>,
+
+    /// How long to delay removing `Pod`s from our map when we receive a deletion
+    /// event from the watched stream, so that the events about deleted `Pod`s are
+    /// still given their labels.
+    delay_deletion_ms: usize,
+
+    /// The namespace to use for logs. This overrides the global setting.
+    #[serde(default)]
+    log_namespace: Option<bool>,
+}
+
+impl GenerateConfig for Config {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(&Self::default()).unwrap()
+    }
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            field_selector: None,
+            label_selector: None,
+            include_pod_labels: true,
+            data_dir: None,
+            kube_config_file: None,
+            delay_deletion_ms: default_delay_deletion_ms(),
+            log_namespace: None,
+        }
+    }
+}
+
+const fn default_delay_deletion_ms() -> usize {
+    60_000
+}
+
+#[async_trait::async_trait]
+impl SourceConfig for Config {
+    async fn build(&self, cx: SourceContext) -> crate::Result<sources::Source> {
+        let log_namespace = cx.log_namespace(self.log_namespace);
+
+        // If the user passed a custom Kubeconfig use it, otherwise
+        // we attempt to load the local kubec-config, followed by the
+        // in-cluster environment variables
+        let client_config = match &self.kube_config_file {
+            Some(kc) => {
+                ClientConfig::from_custom_kubeconfig(
+                    config::Kubeconfig::read_from(kc)?,
+                    &KubeConfigOptions::default(),
+                )
+                .await?
+            }
+            None => ClientConfig::infer().await?,
+        };
+        let client = Client::try_from(client_config)?;
+
+        let data_dir = cx
+            .globals
+            .resolve_and_make_data_subdir(self.data_dir.as_ref(), cx.key.id())?;
+        let checkpointer = Checkpointer::load(&data_dir)?;
+
+        let source = Source {
+            client,
+            checkpointer,
+            field_selector: self.field_selector.clone(),
+            label_selector: self.label_selector.clone(),
+            include_pod_labels: self.include_pod_labels,
+            delay_deletion: Duration::from_millis(self.delay_deletion_ms as u64),
+            log_namespace,
+        };
+        Ok(Box::pin(source.run(cx.out, cx.shutdown)))
+    }
+
+    fn outputs(&self, global_log_namespace: LogNamespace) -> Vec<Output> {
+        let log_namespace = global_log_namespace.merge(self.log_namespace);
+        let schema_definition = self.schema_definition(log_namespace);
+
+        vec![Output::default(DataType::Log).with_schema_definition(schema_definition)]
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        false
+    }
+}
+
+impl Config {
+    fn schema_definition(&self, log_namespace: LogNamespace) -> Definition {
+        let object_reference = Collection::empty()
+            .with_known(Field::from("api_version"), Kind::bytes().or_undefined())
+            .with_known(Field::from("field_path"), Kind::bytes().or_undefined())
+            .with_known(Field::from("kind"), Kind::bytes().or_undefined())
+            .with_known(Field::from("name"), Kind::bytes().or_undefined())
+            .with_known(Field::from("namespace"), Kind::bytes().or_undefined())
+            .with_known(
+                Field::from("resource_version"),
+                Kind::bytes().or_undefined(),
+            )
+            .with_known(Field::from("uid"), Kind::bytes().or_undefined());
+        let involved_object = object_reference.clone().with_known(
+            Field::from("labels"),
+            Kind::object(Collection::from_unknown(Kind::bytes())).or_undefined(),
+        );
+        let source = Collection::empty()
+            .with_known(Field::from("component"), Kind::bytes().or_undefined())
+            .with_known(Field::from("host"), Kind::bytes().or_undefined());
+
+        Definition::new_with_default_metadata(Kind::object(Collection::empty()), [log_namespace])
+            .optional_field(
+                &owned_value_path!("message"),
+                Kind::bytes(),
+                Some("message"),
+            )
+            .optional_field(&owned_value_path!("reason"), Kind::bytes(), None)
+            .optional_field(&owned_value_path!("type"), Kind::bytes(), None)
+            .optional_field(&owned_value_path!("action"), Kind::bytes(), None)
+            .optional_field(&owned_value_path!("count"), Kind::integer(), None)
+            .optional_field(
+                &owned_value_path!("first_timestamp"),
+                Kind::timestamp(),
+                None,
+            )
+            .optional_field(
+                &owned_value_path!("last_timestamp"),
+                Kind::timestamp(),
+                None,
+            )
+            .optional_field(&owned_value_path!("event_time"), Kind::timestamp(), None)
+            .optional_field(&owned_value_path!("name"), Kind::bytes(), None)
+            .optional_field(&owned_value_path!("namespace"), Kind::bytes(), None)
+            .optional_field(&owned_value_path!("uid"), Kind::bytes(), None)
+            .optional_field(&owned_value_path!("resource_version"), Kind::bytes(), None)
+            .with_event_field(
+                &owned_value_path!("involved_object"),
+                Kind::object(involved_object),
+                None,
+            )
+            .optional_field(
+                &owned_value_path!("related"),
+                Kind::object(object_reference),
+                None,
+            )
+            .optional_field(&owned_value_path!("source"), Kind::object(source), None)
+            .optional_field(
+                &owned_value_path!("reporting_component"),
+                Kind::bytes(),
+                None,
+            )
+            .optional_field(
+                &owned_value_path!("reporting_instance"),
+                Kind::bytes(),
+                None,
+            )
+            .with_standard_vector_source_metadata()
+            .with_source_metadata(
+                Self::NAME,
+                Some(LegacyKey::Overwrite(owned_value_path!(
+                    log_schema().timestamp_key()
+                ))),
+                &owned_value_path!("timestamp"),
+                Kind::timestamp(),
+                Some("timestamp"),
+            )
+    }
+}
+
+struct Source {
+    client: Client,
+    checkpointer: Checkpointer,
+    field_selector: Option<String>,
+    label_selector: Option<String>,
+    include_pod_labels: bool,
+    delay_deletion: Duration,
+    log_namespace: LogNamespace,
+}
+
+impl Source {
+    async fn run(self, mut out: SourceSender, mut shutdown: ShutdownSignal) -> Result<(), ()> {
+        let Self {
+            client,
+            mut checkpointer,
+            field_selector,
+            label_selector,
+            include_pod_labels,
+            delay_deletion,
+            log_namespace,
+        } = self;
+
+        let mut reflectors = Vec::new();
+
+        let pod_state = include_pod_labels.then(|| {
+            let pods = Api::<Pod>::all(client.clone());
+            let pod_watcher = watcher(pods, ListParams::default());
+            let pod_store_w = reflector::store::Writer::default();
+            let pod_state = pod_store_w.as_reader();
+
+            reflectors.push(tokio::spawn(custom_reflector(
+                pod_store_w,
+                MetaCache::new(),
+                pod_watcher,
+                delay_deletion,
+            )));
+            pod_state
+        });
+
+        let events = Api::<KubeEvent>::all(client);
+        let event_watcher = watcher(
+            events,
+            ListParams {
+                field_selector,
+                label_selector,
+                ..Default::default()
+            },
+        );
+        pin_mut!(event_watcher);
+
+        loop {
+            let kube_events = tokio::select! {
+                result = event_watcher.next() => match result {
+                    Some(Ok(watcher::Event::Applied(event))) => vec![event],
+                    // The watch restarted, listing all the events again.
+                    Some(Ok(watcher::Event::Restarted(events))) => events,
+                    // Events are deleted once they expire, which doesn't need reporting.
+                    Some(Ok(watcher::Event::Deleted(_))) => continue,
+                    Some(Err(error)) => {
+                        emit!(KubernetesEventsWatchError { error });
+                        continue;
+                    }
+                    // The watcher stream should never yield `None`
+                    None => break,
+                },
+                _ = &mut shutdown => break,
+            };
+
+            let mut kube_events = kube_events
+                .into_iter()
+                .map(|event| (resource_version(&event), event))
+                .filter(|(resource_version, _)| checkpointer.is_new(*resource_version))
+                .collect::<Vec<_>>();
+            if kube_events.is_empty() {
+                continue;
+            }
+            // Lists aren't ordered, while the resource versions tell the order of changes.
+            kube_events.sort_by_key(|(resource_version, _)| *resource_version);
+            let last_resource_version = kube_events
+                .iter()
+                .filter_map(|(resource_version, _)| *resource_version)
+                .max();
+
+            let now = Utc::now();
+            let events = kube_events
+                .into_iter()
+                .map(|(_, event)| {
+                    let log = create_log(event, pod_state.as_ref(), log_namespace, now);
+                    Event::from(log)
+                })
+                .collect::<Vec<_>>();
+
+            let count = events.len();
+            let byte_size = events.size_of();
+            emit!(EventsReceived { count, byte_size });
+
+            if let Err(error) = out.send_batch(events).await {
+                emit!(StreamClosedError { error, count });
+                break;
+            }
+
+            if let Some(resource_version) = last_resource_version {
+                if let Err(error) = checkpointer.set(resource_version) {
+                    emit!(KubernetesEventsCheckpointError {
+                        error,
+                        path: checkpointer.path(),
+                    });
+                }
+            }
+        }
+
+        // Stop Kubernetes object reflectors to avoid their leak on vector reload.
+        for reflector in reflectors {
+            reflector.abort();
+        }
+        Ok(())
+    }
+}
+
+fn resource_version(event: &KubeEvent) -> Option<u64> {
+    event
+        .metadata
+        .resource_version
+        .as_deref()
+        .and_then(|resource_version| resource_version.parse().ok())
+}
+
+fn create_log(
+    event: KubeEvent,
+    pods: Option<&Store<Pod>>,
+    log_namespace: LogNamespace,
+    now: DateTime<Utc>,
+) -> LogEvent {
+    // Events reported with the `events.k8s.io` API only have an event time, and the ones
+    // repeated in a series have the time they were last observed.
+    let timestamp = event
+        .series
+        .as_ref()
+        .and_then(|series| series.last_observed_time.as_ref())
+        .map(|time| time.0)
+        .or_else(|| event.last_timestamp.as_ref().map(|time| time.0))
+        .or_else(|| event.event_time.as_ref().map(|time| time.0))
+        .or_else(|| event.first_timestamp.as_ref().map(|time| time.0))
+        .unwrap_or(now);
+
+    let mut involved_object = object_reference(event.involved_object);
+    if let Some(labels) = pods.and_then(|pods| pod_labels(pods, &involved_object)) {
+        involved_object.insert("labels".to_owned(), labels);
+    }
+
+    let mut fields = BTreeMap::new();
+    insert(&mut fields, "message", event.message);
+    insert(&mut fields, "reason", event.reason);
+    insert(&mut fields, "type", event.type_);
+    insert(&mut fields, "action", event.action);
+    insert(
+        &mut fields,
+        "count",
+        event
+            .series
+            .as_ref()
+            .and_then(|series| series.count)
+            .or(event.count),
+    );
+    insert(
+        &mut fields,
+        "first_timestamp",
+        event.first_timestamp.map(|time| time.0),
+    );
+    insert(
+        &mut fields,
+        "last_timestamp",
+        event.last_timestamp.map(|time| time.0),
+    );
+    insert(
+        &mut fields,
+        "event_time",
+        event.event_time.map(|time| time.0),
+    );
+    insert(&mut fields, "name", event.metadata.name);
+    insert(&mut fields, "namespace", event.metadata.namespace);
+    insert(&mut fields, "uid", event.metadata.uid);
+    insert(
+        &mut fields,
+        "resource_version",
+        event.metadata.resource_version,
+    );
+    fields.insert("involved_object".to_owned(), Value::Object(involved_object));
+    insert(
+        &mut fields,
+        "related",
+        event
+            .related
+            .map(|related| Value::Object(object_reference(related))),
+    );
+    if let Some(source) = event.source {
+        let mut source_fields = BTreeMap::new();
+        insert(&mut source_fields, "component", source.component);
+        insert(&mut source_fields, "host", source.host);
+        fields.insert("source".to_owned(), Value::Object(source_fields));
+    }
+    insert(
+        &mut fields,
+        "reporting_component",
+        event.reporting_component,
+    );
+    insert(&mut fields, "reporting_instance", event.reporting_instance);
+
+    let mut log = log_namespace.new_log_from_data(Value::Object(fields));
+    log_namespace.insert_source_metadata(
+        Config::NAME,
+        &mut log,
+        Some(LegacyKey::Overwrite(log_schema().timestamp_key())),
+        path!("timestamp"),
+        timestamp,
+    );
+    log_namespace.insert_standard_vector_source_metadata(&mut log, Config::NAME, now);
+    log
+}
+
+fn insert(fields: &mut BTreeMap<String, Value>, key: &str, value: Option<impl Into<Value>>) {
+    if let Some(value) = value {
+        fields.insert(key.to_owned(), value.into());
+    }
+}
+
+fn object_reference(reference: ObjectReference) -> BTreeMap<String, Value> {
+    let mut fields = BTreeMap::new();
+    insert(&mut fields, "api_version", reference.api_version);
+    insert(&mut fields, "field_path", reference.field_path);
+    insert(&mut fields, "kind", reference.kind);
+    insert(&mut fields, "name", reference.name);
+    insert(&mut fields, "namespace", reference.namespace);
+    insert(&mut fields, "resource_version", reference.resource_version);
+    insert(&mut fields, "uid", reference.uid);
+    fields
+}
+
+/// Finds the labels of the `Pod` an event is about, if it's about one.
+fn pod_labels(pods: &Store<Pod>, involved_object: &BTreeMap<String, Value>) -> Option<Value> {
+    if involved_object.get("kind") != Some(&Value::from("Pod")) {
+        return None;
+    }
+    let name = involved_object.get("name")?.as_str()?;
+    let namespace = involved_object.get("namespace")?.as_str()?;
+
+    let pod = pods.get(&ObjectRef::<Pod>::new(name).within(namespace))?;
+    let labels = pod
+        .metadata
+        .labels
+        .iter()
+        .flatten()
+        .map(|(key, value)| (key.clone(), Value::from(value.as_str())))
+        .collect();
+    Some(Value::Object(labels))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use k8s_openapi::{
+        api::core::v1::EventSource,
+        apimachinery::pkg::apis::meta::v1::{ObjectMeta, Time},
+    };
+
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<Config>();
+    }
+
+    fn time(seconds: i64) -> Time {
+        use chrono::TimeZone;
+        Time(Utc.timestamp_opt(seconds, 0).single().unwrap())
+    }
+
+    fn oom_event() -> KubeEvent {
+        KubeEvent {
+            metadata: ObjectMeta {
+                name: Some("web-0.17a1b2c3d4e5f678".to_owned()),
+                namespace: Some("default".to_owned()),
+                resource_version: Some("1234".to_owned()),
+                ..ObjectMeta::default()
+            },
+            involved_object: ObjectReference {
+                kind: Some("Pod".to_owned()),
+                name: Some("web-0".to_owned()),
+                namespace: Some("default".to_owned()),
+                uid: Some("c0ffee".to_owned()),
+                ..ObjectReference::default()
+            },
+            reason: Some("OOMKilling".to_owned()),
+            message: Some("Memory cgroup out of memory: Killed process 4242".to_owned()),
+            type_: Some("Warning".to_owned()),
+            count: Some(3),
+            first_timestamp: Some(time(1_600_000_000)),
+            last_timestamp: Some(time(1_600_000_060)),
+            source: Some(EventSource {
+                component: Some("kernel-monitor".to_owned()),
+                host: Some("node-1".to_owned()),
+            }),
+            ..KubeEvent::default()
+        }
+    }
+
+    fn pod_store(pods: Vec<Pod>) -> Store<Pod> {
+        let mut writer = reflector::store::Writer::default();
+        let store = writer.as_reader();
+        writer.apply_watcher_event(&watcher::Event::Restarted(pods));
+        store
+    }
+
+    #[test]
+    fn creates_logs_with_involved_objects() {
+        let pods = pod_store(vec![Pod {
+            metadata: ObjectMeta {
+                name: Some("web-0".to_owned()),
+                namespace: Some("default".to_owned()),
+                labels: Some(BTreeMap::from([("app".to_owned(), "web".to_owned())])),
+                ..ObjectMeta::default()
+            },
+            ..Pod::default()
+        }]);
+
+        let log = create_log(oom_event(), Some(&pods), LogNamespace::Legacy, Utc::now());
+
+        assert_eq!(log["reason"], "OOMKilling".into());
+        assert_eq!(log["type"], "Warning".into());
+        assert_eq!(log["count"], 3.into());
+        assert_eq!(
+            log["message"],
+            "Memory cgroup out of memory: Killed process 4242".into()
+        );
+        assert_eq!(log["resource_version"], "1234".into());
+        assert_eq!(log["involved_object.kind"], "Pod".into());
+        assert_eq!(log["involved_object.name"], "web-0".into());
+        assert_eq!(log["involved_object.uid"], "c0ffee".into());
+        assert_eq!(log["involved_object.labels.app"], "web".into());
+        assert_eq!(log["source.host"], "node-1".into());
+        assert_eq!(
+            log[log_schema().timestamp_key()],
+            time(1_600_000_060).0.into()
+        );
+        assert_eq!(
+            log[log_schema().source_type_key()],
+            "kubernetes_events".into()
+        );
+    }
+
+    #[test]
+    fn leaves_out_labels_of_unknown_pods() {
+        let log = create_log(oom_event(), None, LogNamespace::Legacy, Utc::now());
+
+        assert_eq!(log["involved_object.name"], "web-0".into());
+        assert!(log.get("involved_object.labels").is_none());
+    }
+
+    #[test]
+    fn includes_vector_namespaced_fields() {
+        let config = Config {
+            log_namespace: Some(true),
+            ..Config::default()
+        };
+        let log = create_log(oom_event(), None, LogNamespace::Vector, Utc::now());
+        let meta = log.metadata().value();
+
+        assert_eq!(log["reason"], "OOMKilling".into());
+        assert!(log.get(log_schema().timestamp_key()).is_none());
+        assert_eq!(
+            meta.get(path!(Config::NAME, "timestamp")).unwrap(),
+            &Value::from(time(1_600_000_060).0)
+        );
+        assert_eq!(
+            meta.get(path!("vector", "source_type")).unwrap(),
+            &Value::from(Config::NAME)
+        );
+
+        let definition = config.outputs(LogNamespace::Vector)[0]
+            .clone()
+            .log_schema_definition
+            .unwrap();
+        definition.assert_valid_for_event(&log.into());
+    }
+
+    #[test]
+    fn output_schema_definition_legacy_namespace() {
+        let definition = Config::default().outputs(LogNamespace::Legacy)[0]
+            .clone()
+            .log_schema_definition
+            .unwrap();
+
+        assert_eq!(
+            definition.log_namespaces(),
+            &BTreeSet::from([LogNamespace::Legacy])
+        );
+        definition.assert_valid_for_event(
+            &create_log(oom_event(), None, LogNamespace::Legacy, Utc::now()).into(),
+        );
+    }
+
+    #[test]
+    fn orders_events_by_resource_version() {
+        let mut event = oom_event();
+        assert_eq!(resource_version(&event), Some(1234));
+
+        event.metadata.resource_version = Some("opaque".to_owned());
+        assert_eq!(resource_version(&event), None);
+    }
+}
@@ -21,6 +21,8 @@ pub mod demo_logs;
 pub mod dnstap;
 #[cfg(feature = "sources-docker_logs")]
 pub mod docker_logs;
+#[cfg(feature = "sources-elasticsearch")]
+pub mod elasticsearch;
 #[cfg(feature = "sources-eventstoredb_metrics")]
 pub mod eventstoredb_metrics;
 #[cfg(feature = "sources-exec")]
@@ -52,14 +54,20 @@ pub mod internal_metrics;
 pub mod journald;
 #[cfg(feature = "sources-kafka")]
 pub mod kafka;
+#[cfg(feature = "sources-kubernetes_events")]
+pub mod kubernetes_events;
 #[cfg(feature = "sources-kubernetes_logs")]
 pub mod kubernetes_logs;
 #[cfg(all(feature = "sources-logstash"))]
 pub mod logstash;
 #[cfg(feature = "sources-mongodb_metrics")]
 pub mod mongodb_metrics;
+#[cfg(feature = "sources-mqtt")]
+pub mod mqtt;
 #[cfg(all(feature = "sources-nats"))]
 pub mod nats;
+#[cfg(feature = "sources-netflow")]
+pub mod netflow;
 #[cfg(feature = "sources-nginx_metrics")]
 pub mod nginx_metrics;
 #[cfg(feature = "sources-opentelemetry")]
@@ -70,6 +78,8 @@ pub mod postgresql_metrics;
 pub mod prometheus;
 #[cfg(feature = "sources-redis")]
 pub mod redis;
+#[cfg(feature = "sources-snmp_trap")]
+pub mod snmp_trap;
 #[cfg(feature = "sources-socket")]
 pub mod socket;
 #[cfg(feature = "sources-splunk_hec")]
@@ -146,6 +156,10 @@ pub enum Sources {
     #[cfg(feature = "sources-docker_logs")]
     DockerLogs(#[configurable(derived)] docker_logs::DockerLogsConfig),
 
+    /// Elasticsearch.
+    #[cfg(feature = "sources-elasticsearch")]
+    Elasticsearch(#[configurable(derived)] elasticsearch::ElasticsearchSourceConfig),
+
     /// EventStoreDB Metrics.
     #[cfg(feature = "sources-eventstoredb_metrics")]
     EventstoredbMetrics(#[configurable(derived)] eventstoredb_metrics::EventStoreDbConfig),
@@ -208,6 +222,10 @@ pub enum Sources {
     #[cfg(feature = "sources-kafka")]
     Kafka(#[configurable(derived)] kafka::KafkaSourceConfig),
 
+    /// Kubernetes Events.
+    #[cfg(feature = "sources-kubernetes_events")]
+    KubernetesEvents(#[configurable(derived)] kubernetes_events::Config),
+
     /// Kubernetes Logs.
     #[cfg(feature = "sources-kubernetes_logs")]
     KubernetesLogs(#[configurable(derived)] kubernetes_logs::Config),
@@ -220,10 +238,18 @@ pub enum Sources {
     #[cfg(feature = "sources-mongodb_metrics")]
     MongodbMetrics(#[configurable(derived)] mongodb_metrics::MongoDbMetricsConfig),
 
+    /// MQTT.
+    #[cfg(feature = "sources-mqtt")]
+    Mqtt(#[configurable(derived)] mqtt::MqttSourceConfig),
+
     /// NATS.
     #[cfg(all(feature = "sources-nats"))]
     Nats(#[configurable(derived)] nats::NatsSourceConfig),
 
+    /// NetFlow, IPFIX and sFlow.
+    #[cfg(feature = "sources-netflow")]
+    Netflow(#[configurable(derived)] netflow::NetflowConfig),
+
     /// NGINX Metrics.
     #[cfg(feature = "sources-nginx_metrics")]
     NginxMetrics(#[configurable(derived)] nginx_metrics::NginxMetricsConfig),
@@ -270,6 +296,10 @@ pub enum Sources {
     #[cfg(test)]
     TestTripwire(#[configurable(derived)] crate::test_util::mock::sources::TripwireSourceConfig),
 
+    /// SNMP trap.
+    #[cfg(feature = "sources-snmp_trap")]
+    SnmpTrap(#[configurable(derived)] snmp_trap::SnmpTrapConfig),
+
     /// Socket.
     #[cfg(feature = "sources-socket")]
     Socket(#[configurable(derived)] socket::SocketConfig),
@@ -327,6 +357,8 @@ impl NamedComponent for Sources {
             Self::Dnstap(config) => config.get_component_name(),
             #[cfg(feature = "sources-docker_logs")]
             Self::DockerLogs(config) => config.get_component_name(),
+            #[cfg(feature = "sources-elasticsearch")]
+            Self::Elasticsearch(config) => config.get_component_name(),
             #[cfg(feature = "sources-eventstoredb_metrics")]
             Self::EventstoredbMetrics(config) => config.get_component_name(),
             #[cfg(feature = "sources-exec")]
@@ -357,14 +389,20 @@ impl NamedComponent for Sources {
             Self::Journald(config) => config.get_component_name(),
             #[cfg(feature = "sources-kafka")]
             Self::Kafka(config) => config.get_component_name(),
+            #[cfg(feature = "sources-kubernetes_events")]
+            Self::KubernetesEvents(config) => config.get_component_name(),
             #[cfg(feature = "sources-kubernetes_logs")]
             Self::KubernetesLogs(config) => config.get_component_name(),
             #[cfg(all(feature = "sources-logstash"))]
             Self::Logstash(config) => config.get_component_name(),
             #[cfg(feature = "sources-mongodb_metrics")]
             Self::MongodbMetrics(config) => config.get_component_name(),
+            #[cfg(feature = "sources-mqtt")]
+            Self::Mqtt(config) => config.get_component_name(),
             #[cfg(all(feature = "sources-nats"))]
             Self::Nats(config) => config.get_component_name(),
+            #[cfg(feature = "sources-netflow")]
+            Self::Netflow(config) => config.get_component_name(),
             #[cfg(feature = "sources-nginx_metrics")]
             Self::NginxMetrics(config) => config.get_component_name(),
             #[cfg(feature = "sources-opentelemetry")]
@@ -387,6 +425,8 @@ impl NamedComponent for Sources {
             Self::TestPanic(config) => config.get_component_name(),
             #[cfg(test)]
             Self::TestTripwire(config) => config.get_component_name(),
+            #[cfg(feature = "sources-snmp_trap")]
+            Self::SnmpTrap(config) => config.get_component_name(),
             #[cfg(feature = "sources-socket")]
             Self::Socket(config) => config.get_component_name(),
             #[cfg(feature = "sources-splunk_hec")]
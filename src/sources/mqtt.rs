@@ -0,0 +1,547 @@
+//! `MQTT` source.
+//! Subscribes to topic filters on an MQTT 3.1.1 broker.
+use std::time::Duration;
+
+use bytes::Bytes;
+use chrono::Utc;
+use codecs::decoding::{DeserializerConfig, FramingConfig, StreamDecodingError};
+use futures::{FutureExt, StreamExt};
+use lookup::owned_value_path;
+use rumqttc::{AsyncClient, Event as MqttEvent, EventLoop, Packet, Publish, SubscribeFilter};
+use snafu::{ResultExt, Snafu};
+use tokio_util::codec::FramedRead;
+use value::Kind;
+use vector_common::{
+    finalizer::OrderedFinalizer,
+    internal_event::{
+        error_stage, ByteSize, BytesReceived, EventsReceived, InternalEventHandle as _, Protocol,
+    },
+};
+use vector_config::{configurable_component, NamedComponent};
+use vector_core::{
+    config::{LegacyKey, LogNamespace, SourceAcknowledgementsConfig},
+    event::Event,
+    ByteSizeOf,
+};
+
+use crate::{
+    codecs::{Decoder, DecodingConfig},
+    config::{ComponentKey, GenerateConfig, Output, SourceConfig, SourceContext},
+    event::{BatchNotifier, BatchStatus},
+    internal_events::{
+        MqttAckError, MqttConnectionError, MqttDeliveryErrored, MqttSubscribeError,
+        StreamClosedError,
+    },
+    mqtt::{MqttConfigError, MqttConnectionConfig, MqttQoS},
+    serde::{bool_or_struct, default_decoding, default_framing_message_based},
+    shutdown::ShutdownSignal,
+    SourceSender,
+};
+
+/// The capacity of the channel between the MQTT client and its event loop.
+const CLIENT_CHANNEL_CAPACITY: usize = 1024;
+
+/// How long to wait before reconnecting after a connection error.
+const RECONNECT_DELAY: Duration = Duration::from_secs(1);
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("MQTT Config Error: {}", source))]
+    Config { source: MqttConfigError },
+    #[snafu(display("At least one topic filter is required"))]
+    NoTopics,
+}
+
+/// Configuration for the `mqtt` source.
+///
+/// Supports MQTT version 3.1.1
+#[configurable_component(source("mqtt"))]
+#[derive(Clone, Debug, Derivative)]
+#[derivative(Default)]
+#[serde(deny_unknown_fields)]
+pub struct MqttSourceConfig {
+    /// Connection options for the `mqtt` source.
+    pub(crate) connection: MqttConnectionConfig,
+
+    /// The topic filters to subscribe to.
+    ///
+    /// Filters can contain the `+` and `#` wildcards.
+    pub(crate) topics: Vec<String>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub(crate) qos: MqttQoS,
+
+    /// Whether or not to start a new session when connecting to the broker.
+    ///
+    /// When disabled, the broker keeps the subscriptions and the unacknowledged deliveries of the
+    /// client while it is disconnected, and redelivers them when it reconnects with the same
+    /// `client_id`.
+    #[serde(default)]
+    pub(crate) clean_session: bool,
+
+    /// The log field to put the topic of each message in.
+    #[serde(default = "default_topic_key")]
+    #[derivative(Default(value = "default_topic_key()"))]
+    pub(crate) topic_key: String,
+
+    /// The namespace to use for logs. This overrides the global setting.
+    #[configurable(metadata(docs::hidden))]
+    #[serde(default)]
+    pub log_namespace: Option<bool>,
+
+    #[configurable(derived)]
+    #[serde(default = "default_framing_message_based")]
+    #[derivative(Default(value = "default_framing_message_based()"))]
+    pub(crate) framing: FramingConfig,
+
+    #[configurable(derived)]
+    #[serde(default = "default_decoding")]
+    #[derivative(Default(value = "default_decoding()"))]
+    pub(crate) decoding: DeserializerConfig,
+
+    #[configurable(derived)]
+    #[serde(default, deserialize_with = "bool_or_struct")]
+    pub(crate) acknowledgements: SourceAcknowledgementsConfig,
+}
+
+fn default_topic_key() -> String {
+    "topic".into()
+}
+
+impl GenerateConfig for MqttSourceConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"
+            connection.host = "localhost"
+            topics = ["vector/#"]"#,
+        )
+        .unwrap()
+    }
+}
+
+impl MqttSourceConfig {
+    fn decoder(&self, log_namespace: LogNamespace) -> Decoder {
+        DecodingConfig::new(self.framing.clone(), self.decoding.clone(), log_namespace).build()
+    }
+
+    fn client(&self, key: &ComponentKey) -> Result<(AsyncClient, EventLoop), BuildError> {
+        if self.topics.is_empty() {
+            return Err(BuildError::NoTopics);
+        }
+
+        let mut options = self
+            .connection
+            .options(&format!("vector-{}", key))
+            .context(ConfigSnafu)?;
+        options.set_clean_session(self.clean_session);
+        // Deliveries are acknowledged once their events are committed, rather than on receipt.
+        options.set_manual_acks(true);
+
+        Ok(AsyncClient::new(options, CLIENT_CHANNEL_CAPACITY))
+    }
+}
+
+#[async_trait::async_trait]
+impl SourceConfig for MqttSourceConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        let log_namespace = cx.log_namespace(self.log_namespace);
+        let acknowledgements = cx.do_acknowledgements(self.acknowledgements);
+        let (client, eventloop) = self.client(&cx.key)?;
+
+        Ok(Box::pin(mqtt_source(
+            self.clone(),
+            client,
+            eventloop,
+            cx.shutdown,
+            cx.out,
+            log_namespace,
+            acknowledgements,
+        )))
+    }
+
+    fn outputs(&self, global_log_namespace: LogNamespace) -> Vec<Output> {
+        let log_namespace = global_log_namespace.merge(self.log_namespace);
+        let schema_definition = self
+            .decoding
+            .schema_definition(log_namespace)
+            .with_standard_vector_source_metadata()
+            .with_source_metadata(
+                MqttSourceConfig::NAME,
+                Some(LegacyKey::InsertIfEmpty(owned_value_path!(&self.topic_key))),
+                &owned_value_path!("topic"),
+                Kind::bytes(),
+                None,
+            );
+
+        vec![Output::default(self.decoding.output_type()).with_schema_definition(schema_definition)]
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        true
+    }
+}
+
+/// Runs the `MQTT` source, polling the event loop of the client for deliveries.
+///
+/// The event loop reconnects on its own when polled after a connection error. The subscriptions
+/// are renewed whenever the broker doesn't have a session for the client anymore.
+async fn mqtt_source(
+    config: MqttSourceConfig,
+    client: AsyncClient,
+    mut eventloop: EventLoop,
+    shutdown: ShutdownSignal,
+    mut out: SourceSender,
+    log_namespace: LogNamespace,
+    acknowledgements: bool,
+) -> Result<(), ()> {
+    let decoder = config.decoder(log_namespace);
+    let filters = config
+        .topics
+        .iter()
+        .map(|topic| SubscribeFilter::new(topic.clone(), config.qos.into()))
+        .collect::<Vec<_>>();
+    let bytes_received = register!(BytesReceived::from(Protocol::TCP));
+
+    // Deliveries must be acknowledged in the order they were received.
+    let (finalizer, mut ack_stream) =
+        OrderedFinalizer::<Publish>::maybe_new(acknowledgements, shutdown.clone());
+
+    debug!(message = "Starting MQTT source.", topics = ?config.topics);
+    let mut shutdown = shutdown.fuse();
+    loop {
+        tokio::select! {
+            _ = &mut shutdown => break,
+            entry = ack_stream.next() => {
+                if let Some((status, publish)) = entry {
+                    handle_ack(&client, status, &publish);
+                }
+            },
+            event = eventloop.poll() => match event {
+                Ok(MqttEvent::Incoming(Packet::ConnAck(connack))) => {
+                    if !connack.session_present {
+                        if let Err(error) = client.try_subscribe_many(filters.clone()) {
+                            emit!(MqttSubscribeError { error });
+                        }
+                    }
+                }
+                Ok(MqttEvent::Incoming(Packet::Publish(publish))) => {
+                    bytes_received.emit(ByteSize(publish.payload.len()));
+                    let events = decode(&config, &decoder, &publish, log_namespace).await;
+                    send_events(&client, &mut out, finalizer.as_ref(), events, publish).await?;
+                }
+                Ok(_) => {}
+                Err(error) => {
+                    emit!(MqttConnectionError {
+                        error,
+                        stage: error_stage::RECEIVING,
+                    });
+                    tokio::time::sleep(RECONNECT_DELAY).await;
+                }
+            },
+        }
+    }
+
+    if let Err(error) = client.try_disconnect() {
+        debug!(message = "Failed to disconnect from MQTT broker.", %error);
+    }
+
+    Ok(())
+}
+
+/// Decodes the payload of a delivery into events.
+async fn decode(
+    config: &MqttSourceConfig,
+    decoder: &Decoder,
+    publish: &Publish,
+    log_namespace: LogNamespace,
+) -> Vec<Event> {
+    let mut stream = FramedRead::new(publish.payload.as_ref(), decoder.clone());
+    let now = Utc::now();
+    let mut decoded = Vec::new();
+
+    while let Some(next) = stream.next().await {
+        match next {
+            Ok((events, _byte_size)) => {
+                emit!(EventsReceived {
+                    count: events.len(),
+                    byte_size: events.size_of(),
+                });
+
+                decoded.extend(events.into_iter().map(|mut event| {
+                    if let Event::Log(ref mut log) = event {
+                        log_namespace.insert_standard_vector_source_metadata(
+                            log,
+                            MqttSourceConfig::NAME,
+                            now,
+                        );
+                        log_namespace.insert_source_metadata(
+                            MqttSourceConfig::NAME,
+                            log,
+                            Some(LegacyKey::InsertIfEmpty(config.topic_key.as_str())),
+                            "topic",
+                            Bytes::from(publish.topic.clone()),
+                        );
+                    }
+                    event
+                }));
+            }
+            Err(error) => {
+                // Error is logged by `crate::codecs`, no further
+                // handling is needed here.
+                if !error.can_continue() {
+                    break;
+                }
+            }
+        }
+    }
+
+    decoded
+}
+
+/// Sends the events of a delivery along the pipeline, and acknowledges the delivery once they
+/// are committed.
+async fn send_events(
+    client: &AsyncClient,
+    out: &mut SourceSender,
+    finalizer: Option<&OrderedFinalizer<Publish>>,
+    events: Vec<Event>,
+    publish: Publish,
+) -> Result<(), ()> {
+    let count = events.len();
+    match finalizer {
+        Some(finalizer) => {
+            let (batch, receiver) = BatchNotifier::new_with_receiver();
+            let events = events
+                .into_iter()
+                .map(|event| event.with_batch_notifier(&batch));
+            out.send_batch(events).await.map_err(|error| {
+                emit!(StreamClosedError { error, count });
+            })?;
+            finalizer.add(publish, receiver);
+        }
+        None => {
+            out.send_batch(events).await.map_err(|error| {
+                emit!(StreamClosedError { error, count });
+            })?;
+            handle_ack(client, BatchStatus::Delivered, &publish);
+        }
+    }
+    Ok(())
+}
+
+/// Acknowledges a delivery once its events are committed.
+///
+/// MQTT has no way to reject a delivery, and the broker stops sending deliveries past a number of
+/// unacknowledged ones, so deliveries whose events errored are acknowledged too, with an error.
+fn handle_ack(client: &AsyncClient, status: BatchStatus, publish: &Publish) {
+    if status == BatchStatus::Errored {
+        emit!(MqttDeliveryErrored {
+            topic: &publish.topic
+        });
+    }
+
+    // The client only sends an acknowledgement for QoS 1 and 2 deliveries.
+    if let Err(error) = client.try_ack(publish) {
+        emit!(MqttAckError { error });
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use lookup::LookupBuf;
+    use value::kind::Collection;
+    use vector_core::schema::Definition;
+
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<MqttSourceConfig>();
+    }
+
+    #[test]
+    fn requires_topics() {
+        let config = MqttSourceConfig::default();
+        assert!(matches!(
+            config.client(&ComponentKey::from("mqtt")),
+            Err(BuildError::NoTopics)
+        ));
+    }
+
+    #[test]
+    fn output_schema_definition_vector_namespace() {
+        let config = MqttSourceConfig {
+            log_namespace: Some(true),
+            ..Default::default()
+        };
+
+        let definition = config.outputs(LogNamespace::Vector)[0]
+            .clone()
+            .log_schema_definition
+            .unwrap();
+
+        let expected_definition =
+            Definition::new_with_default_metadata(Kind::bytes(), [LogNamespace::Vector])
+                .with_meaning(LookupBuf::root(), "message")
+                .with_metadata_field(&owned_value_path!("vector", "source_type"), Kind::bytes())
+                .with_metadata_field(
+                    &owned_value_path!("vector", "ingest_timestamp"),
+                    Kind::timestamp(),
+                )
+                .with_metadata_field(&owned_value_path!("mqtt", "topic"), Kind::bytes());
+
+        assert_eq!(definition, expected_definition);
+    }
+
+    #[test]
+    fn output_schema_definition_legacy_namespace() {
+        let config = MqttSourceConfig::default();
+
+        let definition = config.outputs(LogNamespace::Legacy)[0]
+            .clone()
+            .log_schema_definition
+            .unwrap();
+
+        let expected_definition = Definition::new_with_default_metadata(
+            Kind::object(Collection::empty()),
+            [LogNamespace::Legacy],
+        )
+        .with_event_field(
+            &owned_value_path!("message"),
+            Kind::bytes(),
+            Some("message"),
+        )
+        .with_event_field(&owned_value_path!("timestamp"), Kind::timestamp(), None)
+        .with_event_field(&owned_value_path!("source_type"), Kind::bytes(), None)
+        .with_event_field(&owned_value_path!("topic"), Kind::bytes(), None);
+
+        assert_eq!(definition, expected_definition);
+    }
+}
+
+/// Integration tests use the docker compose files in `scripts/integration/docker-compose.mqtt.yml`.
+#[cfg(feature = "mqtt-integration-tests")]
+#[cfg(test)]
+mod integration_test {
+    use rumqttc::QoS;
+    use tokio::{task::JoinHandle, time::timeout};
+
+    use super::*;
+    use crate::{
+        event::EventStatus,
+        test_util::{
+            collect_n,
+            components::{assert_source_compliance, SOURCE_TAGS},
+            random_string,
+        },
+    };
+    use vector_core::config::log_schema;
+
+    fn make_config(topic: &str) -> MqttSourceConfig {
+        let mut config = MqttSourceConfig {
+            topics: vec![topic.to_string()],
+            ..Default::default()
+        };
+        config.connection.host =
+            std::env::var("MQTT_HOST").unwrap_or_else(|_| "localhost".to_string());
+        config.connection.client_id = Some(format!("vector-source-{}", random_string(10)));
+        config
+    }
+
+    /// Publishes the messages, and waits for the broker to acknowledge them.
+    async fn publish(topic: &str, qos: QoS, messages: &[&str]) {
+        let mut options = make_config(topic).connection.options("").unwrap();
+        options.set_clean_session(true);
+        let (client, mut eventloop) = AsyncClient::new(options, 16);
+        for message in messages {
+            client
+                .publish(topic, qos, false, message.as_bytes().to_vec())
+                .await
+                .unwrap();
+        }
+        // Drive the event loop until every message is acknowledged by the broker.
+        let mut pending = messages.len();
+        while pending > 0 {
+            match eventloop.poll().await.unwrap() {
+                MqttEvent::Incoming(Packet::PubAck(_) | Packet::PubComp(_)) => pending -= 1,
+                MqttEvent::Outgoing(rumqttc::Outgoing::Publish(_)) if qos == QoS::AtMostOnce => {
+                    pending -= 1
+                }
+                _ => {}
+            }
+        }
+    }
+
+    async fn start_source(
+        config: MqttSourceConfig,
+        out: SourceSender,
+        acknowledgements: bool,
+    ) -> JoinHandle<Result<(), ()>> {
+        let (client, eventloop) = config.client(&ComponentKey::from("mqtt")).unwrap();
+        let handle = tokio::spawn(mqtt_source(
+            config,
+            client,
+            eventloop,
+            ShutdownSignal::noop(),
+            out,
+            LogNamespace::Legacy,
+            acknowledgements,
+        ));
+        // Give the source time to connect and subscribe.
+        tokio::time::sleep(Duration::from_secs(1)).await;
+        handle
+    }
+
+    #[tokio::test]
+    async fn mqtt_source_consume_event() {
+        let id = random_string(10);
+        let topic = format!("test/{}/events", id);
+        let config = make_config(&format!("test/{}/#", id));
+
+        let events = assert_source_compliance(&SOURCE_TAGS, async move {
+            let (tx, rx) = SourceSender::new_test();
+            start_source(config, tx, false).await;
+            publish(&topic, QoS::AtLeastOnce, &["my message"]).await;
+            collect_n(rx, 1).await
+        })
+        .await;
+
+        let log = events[0].as_log();
+        assert_eq!(log[log_schema().message_key()], "my message".into());
+        assert_eq!(log["topic"], topic.into());
+        assert_eq!(log[log_schema().source_type_key()], "mqtt".into());
+    }
+
+    #[tokio::test]
+    async fn mqtt_source_acknowledges_errored_events() {
+        let topic = format!("test/{}", random_string(10));
+        let mut config = make_config(&topic);
+        config.qos = MqttQoS::AtLeastOnce;
+
+        // The events are errored downstream, but the delivery is acknowledged anyway.
+        let (tx, rx) = SourceSender::new_test_finalize(EventStatus::Errored);
+        let source = start_source(config.clone(), tx, true).await;
+        publish(&topic, QoS::AtLeastOnce, &["errored"]).await;
+        let events = collect_n(rx, 1).await;
+        assert_eq!(
+            events[0].as_log()[log_schema().message_key()],
+            "errored".into()
+        );
+        tokio::time::sleep(Duration::from_secs(1)).await;
+        source.abort();
+        tokio::time::sleep(Duration::from_secs(1)).await;
+
+        // Reconnecting with the same session doesn't get the delivery again.
+        let (tx, rx) = SourceSender::new_test_finalize(EventStatus::Delivered);
+        start_source(config, tx, true).await;
+        publish(&topic, QoS::AtLeastOnce, &["delivered"]).await;
+        let events = timeout(Duration::from_secs(10), collect_n(rx, 1))
+            .await
+            .expect("delivery was not received");
+        assert_eq!(
+            events[0].as_log()[log_schema().message_key()],
+            "delivered".into()
+        );
+    }
+}
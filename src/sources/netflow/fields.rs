@@ -0,0 +1,283 @@
+//! Gives the fields of flows the same names whatever the protocol they were exported with.
+use std::{
+    collections::BTreeMap,
+    fmt::Write as _,
+    net::{Ipv4Addr, Ipv6Addr},
+};
+
+use chrono::{TimeZone, Utc};
+use vector_core::event::Value;
+
+use super::reader::decode_unsigned;
+
+/// The fields of a flow, by their normalized names.
+pub(super) type Flow = BTreeMap<String, Value>;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum FieldKind {
+    Unsigned,
+    Ipv4,
+    Ipv6,
+    Mac,
+    Text,
+    /// Seconds since the Unix epoch.
+    Seconds,
+    /// Milliseconds since the Unix epoch.
+    Milliseconds,
+    /// Milliseconds since the exporter booted.
+    Uptime,
+}
+
+/// The names and kinds of the information elements registered by IANA, which NetFlow v9 field
+/// types share.
+const fn information_element(id: u16) -> Option<(&'static str, FieldKind)> {
+    use FieldKind::*;
+
+    Some(match id {
+        1 => ("bytes", Unsigned),
+        2 => ("packets", Unsigned),
+        4 => ("protocol", Unsigned),
+        5 => ("tos", Unsigned),
+        6 => ("tcp_flags", Unsigned),
+        7 => ("src_port", Unsigned),
+        8 => ("src_addr", Ipv4),
+        9 => ("src_mask", Unsigned),
+        10 => ("input_interface", Unsigned),
+        11 => ("dst_port", Unsigned),
+        12 => ("dst_addr", Ipv4),
+        13 => ("dst_mask", Unsigned),
+        14 => ("output_interface", Unsigned),
+        15 => ("next_hop", Ipv4),
+        16 => ("src_as", Unsigned),
+        17 => ("dst_as", Unsigned),
+        18 => ("bgp_next_hop", Ipv4),
+        21 => ("flow_end", Uptime),
+        22 => ("flow_start", Uptime),
+        27 => ("src_addr", Ipv6),
+        28 => ("dst_addr", Ipv6),
+        29 => ("src_mask", Unsigned),
+        30 => ("dst_mask", Unsigned),
+        31 => ("ipv6_flow_label", Unsigned),
+        32 => ("icmp_type_code", Unsigned),
+        34 => ("sampling_interval", Unsigned),
+        56 => ("src_mac", Mac),
+        58 => ("vlan", Unsigned),
+        60 => ("ip_version", Unsigned),
+        61 => ("direction", Unsigned),
+        62 => ("next_hop", Ipv6),
+        63 => ("bgp_next_hop", Ipv6),
+        80 => ("dst_mac", Mac),
+        82 => ("interface_name", Text),
+        85 => ("bytes_total", Unsigned),
+        86 => ("packets_total", Unsigned),
+        136 => ("flow_end_reason", Unsigned),
+        148 => ("flow_id", Unsigned),
+        150 => ("flow_start", Seconds),
+        151 => ("flow_end", Seconds),
+        152 => ("flow_start", Milliseconds),
+        153 => ("flow_end", Milliseconds),
+        176 => ("icmp_type", Unsigned),
+        177 => ("icmp_code", Unsigned),
+        225 => ("nat_src_addr", Ipv4),
+        226 => ("nat_dst_addr", Ipv4),
+        227 => ("nat_src_port", Unsigned),
+        228 => ("nat_dst_port", Unsigned),
+        305 => ("sampling_interval", Unsigned),
+        _ => return None,
+    })
+}
+
+/// Inserts the value of a field of a NetFlow v9 or IPFIX record into a flow.
+///
+/// Fields without a normalized name are named after their information element, as `ie_<id>`, or
+/// as `ie_<enterprise>_<id>` for the ones specific to an enterprise. `boot_time` is the time the
+/// exporter booted, in milliseconds since the Unix epoch, when the packet gives it.
+pub(super) fn insert_field(
+    flow: &mut Flow,
+    enterprise: Option<u32>,
+    id: u16,
+    bytes: &[u8],
+    boot_time: Option<i64>,
+) {
+    let known = match enterprise {
+        None => information_element(id),
+        Some(_) => None,
+    };
+
+    let (name, value) = match known.and_then(|(name, kind)| {
+        decode(kind, bytes, boot_time).map(|value| (name.to_owned(), value))
+    }) {
+        Some(field) => field,
+        None => {
+            let name = match (enterprise, known) {
+                (_, Some((name, FieldKind::Uptime))) => format!("{}_uptime", name),
+                (Some(enterprise), _) => format!("ie_{}_{}", enterprise, id),
+                (None, _) => format!("ie_{}", id),
+            };
+            (name, raw_value(bytes))
+        }
+    };
+
+    flow.insert(name, value);
+}
+
+fn decode(kind: FieldKind, bytes: &[u8], boot_time: Option<i64>) -> Option<Value> {
+    match kind {
+        FieldKind::Unsigned => decode_unsigned(bytes).map(unsigned_value),
+        FieldKind::Ipv4 => <[u8; 4]>::try_from(bytes)
+            .ok()
+            .map(|octets| Ipv4Addr::from(octets).to_string().into()),
+        FieldKind::Ipv6 => <[u8; 16]>::try_from(bytes)
+            .ok()
+            .map(|octets| Ipv6Addr::from(octets).to_string().into()),
+        FieldKind::Mac => <[u8; 6]>::try_from(bytes).ok().map(|mac| mac_value(&mac)),
+        FieldKind::Text => Some(
+            String::from_utf8_lossy(bytes)
+                .trim_end_matches('\0')
+                .to_owned()
+                .into(),
+        ),
+        FieldKind::Seconds => decode_unsigned(bytes)
+            .and_then(|seconds| i64::try_from(seconds).ok())
+            .and_then(|seconds| Utc.timestamp_opt(seconds, 0).single())
+            .map(Value::from),
+        FieldKind::Milliseconds => decode_unsigned(bytes)
+            .and_then(|millis| i64::try_from(millis).ok())
+            .and_then(timestamp_millis),
+        FieldKind::Uptime => {
+            let uptime = decode_unsigned(bytes).and_then(|uptime| i64::try_from(uptime).ok())?;
+            boot_time
+                .and_then(|boot_time| boot_time.checked_add(uptime))
+                .and_then(timestamp_millis)
+        }
+    }
+}
+
+/// Gives integers larger than the largest signed integer as strings, rather than wrapping them
+/// around to negative values.
+pub(super) fn unsigned_value(value: u64) -> Value {
+    i64::try_from(value).map_or_else(|_| value.to_string().into(), Value::from)
+}
+
+pub(super) fn mac_value(mac: &[u8; 6]) -> Value {
+    let mut formatted = String::with_capacity(17);
+    for (index, byte) in mac.iter().enumerate() {
+        if index > 0 {
+            formatted.push(':');
+        }
+        let _ = write!(formatted, "{:02x}", byte);
+    }
+    formatted.into()
+}
+
+pub(super) fn timestamp_millis(millis: i64) -> Option<Value> {
+    Utc.timestamp_millis_opt(millis).single().map(Value::from)
+}
+
+/// Gives fields that can't be decoded as integers when they're small enough, and as hexadecimal
+/// strings otherwise.
+fn raw_value(bytes: &[u8]) -> Value {
+    match decode_unsigned(bytes) {
+        Some(value) => unsigned_value(value),
+        None => {
+            let mut formatted = String::with_capacity(bytes.len() * 2);
+            for byte in bytes {
+                let _ = write!(formatted, "{:02x}", byte);
+            }
+            formatted.into()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn field(enterprise: Option<u32>, id: u16, bytes: &[u8], boot_time: Option<i64>) -> Flow {
+        let mut flow = Flow::new();
+        insert_field(&mut flow, enterprise, id, bytes, boot_time);
+        flow
+    }
+
+    #[test]
+    fn normalizes_known_fields() {
+        assert_eq!(
+            field(None, 1, &[0x01, 0x00], None)["bytes"],
+            Value::from(256)
+        );
+        assert_eq!(
+            field(None, 8, &[10, 0, 0, 1], None)["src_addr"],
+            Value::from("10.0.0.1")
+        );
+        assert_eq!(
+            field(None, 28, &Ipv6Addr::LOCALHOST.octets(), None)["dst_addr"],
+            Value::from("::1")
+        );
+        assert_eq!(
+            field(None, 56, &[0, 0x1b, 0x21, 0xaa, 0xbb, 0xcc], None)["src_mac"],
+            Value::from("00:1b:21:aa:bb:cc")
+        );
+        assert_eq!(
+            field(None, 82, b"eth0\0\0", None)["interface_name"],
+            Value::from("eth0")
+        );
+    }
+
+    #[test]
+    fn converts_timestamps() {
+        let expected = timestamp_millis(1_600_000_000_500).unwrap();
+
+        assert_eq!(
+            field(None, 152, &1_600_000_000_500u64.to_be_bytes(), None)["flow_start"],
+            expected
+        );
+        assert_eq!(
+            field(None, 22, &500u32.to_be_bytes(), Some(1_600_000_000_000))["flow_start"],
+            expected
+        );
+        assert_eq!(
+            field(None, 150, &1_600_000_000u32.to_be_bytes(), None)["flow_start"],
+            timestamp_millis(1_600_000_000_000).unwrap()
+        );
+    }
+
+    #[test]
+    fn keeps_uptimes_without_boot_time() {
+        assert_eq!(
+            field(None, 21, &500u32.to_be_bytes(), None)["flow_end_uptime"],
+            Value::from(500)
+        );
+    }
+
+    #[test]
+    fn keeps_overflowing_uptimes() {
+        let flow = field(
+            None,
+            22,
+            &0x7fff_ffff_ffff_ffffu64.to_be_bytes(),
+            Some(1_600_000_000_000),
+        );
+
+        assert!(!flow.contains_key("flow_start"));
+        assert_eq!(flow["flow_start_uptime"], Value::from(i64::MAX));
+    }
+
+    #[test]
+    fn names_unknown_fields_after_their_information_element() {
+        assert_eq!(field(None, 999, &[0x2a], None)["ie_999"], Value::from(42));
+        assert_eq!(
+            field(Some(9), 12, &[0xde, 0xad, 0xbe, 0xef, 0, 0, 0, 0, 1], None)["ie_9_12"],
+            Value::from("deadbeef0000000001")
+        );
+        // Fields of known information elements with unexpected lengths are kept as they are.
+        assert_eq!(
+            field(None, 8, &[0x0a, 0x00], None)["ie_8"],
+            Value::from(2560)
+        );
+    }
+
+    #[test]
+    fn gives_large_integers_as_strings() {
+        assert_eq!(unsigned_value(u64::MAX), Value::from(u64::MAX.to_string()));
+    }
+}
@@ -0,0 +1,631 @@
+//! `NetFlow` source.
+//! Receives NetFlow v5, NetFlow v9, IPFIX and sFlow v5 flow exports over UDP.
+use std::{
+    net::{IpAddr, Ipv4Addr, SocketAddr, SocketAddrV4},
+    num::NonZeroUsize,
+};
+
+use bytes::BytesMut;
+use chrono::{DateTime, Utc};
+use futures::FutureExt;
+use listenfd::ListenFd;
+use lookup::{owned_value_path, path};
+use value::{kind::Collection, Kind};
+use vector_common::internal_event::{
+    ByteSize, BytesReceived, EventsReceived, InternalEventHandle as _, Protocol,
+};
+use vector_config::{configurable_component, NamedComponent};
+use vector_core::{
+    config::{log_schema, DataType, LegacyKey, LogNamespace},
+    event::{
+        metric::{Metric, MetricKind, MetricTags, MetricValue},
+        Value,
+    },
+    schema::Definition,
+    ByteSizeOf,
+};
+
+use crate::{
+    config::{GenerateConfig, Output, Resource, SourceConfig, SourceContext},
+    event::Event,
+    internal_events::{
+        NetflowParseError, NetflowTemplateMissing, SocketBindError, SocketMode, SocketReceiveError,
+        StreamClosedError,
+    },
+    shutdown::ShutdownSignal,
+    sources::util::net::{try_bind_udp_socket, SocketListenAddr},
+    udp, SourceSender,
+};
+
+mod fields;
+mod netflow_v5;
+mod reader;
+mod sflow;
+mod template;
+
+use self::{
+    fields::Flow,
+    reader::{DecodeError, Reader},
+    template::{Decoded, TemplateCache},
+};
+
+/// Configuration for the `netflow` source.
+#[configurable_component(source("netflow"))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct NetflowConfig {
+    /// The address to listen for flow export packets on.
+    address: SocketListenAddr,
+
+    /// The maximum size, in bytes, of incoming packets.
+    ///
+    /// Packets larger than this are discarded.
+    #[serde(default = "default_max_length")]
+    max_length: usize,
+
+    /// The size, in bytes, of the receive buffer used for the listening socket.
+    ///
+    /// This should not typically needed to be changed.
+    receive_buffer_bytes: Option<usize>,
+
+    /// The maximum number of NetFlow v9 and IPFIX templates to keep, across all exporters.
+    ///
+    /// Past this number, the least recently used templates are evicted, and the records they
+    /// describe are dropped until their exporters send them again.
+    #[serde(default = "default_max_templates")]
+    max_templates: NonZeroUsize,
+
+    /// Overrides the name of the log field used to add the address of the exporter of each flow
+    /// to each event.
+    ///
+    /// By default, the [global `log_schema.host_key` option][global_host_key] is used.
+    ///
+    /// [global_host_key]: https://vector.dev/docs/reference/configuration/global-options/#log_schema.host_key
+    host_key: Option<String>,
+
+    /// When set, flows are emitted as metrics counting them, their bytes, and their packets,
+    /// rather than as logs.
+    #[configurable(derived)]
+    metrics: Option<FlowMetricsConfig>,
+
+    /// The namespace to use for logs. This overrides the global setting.
+    #[serde(default)]
+    log_namespace: Option<bool>,
+}
+
+/// Options for emitting flows as metrics.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct FlowMetricsConfig {
+    /// The namespace of the metrics.
+    #[serde(default = "default_namespace")]
+    namespace: String,
+
+    /// The fields of flows to tag the metrics with.
+    ///
+    /// The address of the exporter is always added, as the `host` tag. Fields missing from a
+    /// flow are left out of the tags of its metrics.
+    #[serde(default = "default_tags")]
+    tags: Vec<String>,
+
+    /// Whether the bytes and packets of sampled flows are multiplied by their sampling interval,
+    /// to estimate the traffic the samples were taken from.
+    #[serde(default = "crate::serde::default_true")]
+    scale_by_sampling_rate: bool,
+}
+
+/// The largest payload of a UDP datagram.
+const fn default_max_length() -> usize {
+    65_535
+}
+
+fn default_max_templates() -> NonZeroUsize {
+    NonZeroUsize::new(10_000).expect("static non-zero number")
+}
+
+fn default_namespace() -> String {
+    "netflow".to_owned()
+}
+
+fn default_tags() -> Vec<String> {
+    vec!["flow_type".to_owned(), "protocol".to_owned()]
+}
+
+impl GenerateConfig for NetflowConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            address: SocketListenAddr::SocketAddr(SocketAddr::V4(SocketAddrV4::new(
+                Ipv4Addr::UNSPECIFIED,
+                2055,
+            ))),
+            max_length: default_max_length(),
+            receive_buffer_bytes: None,
+            max_templates: default_max_templates(),
+            host_key: None,
+            metrics: None,
+            log_namespace: None,
+        })
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+impl SourceConfig for NetflowConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        let log_namespace = cx.log_namespace(self.log_namespace);
+
+        Ok(Box::pin(netflow_udp(
+            self.clone(),
+            cx.shutdown,
+            cx.out,
+            log_namespace,
+        )))
+    }
+
+    fn outputs(&self, global_log_namespace: LogNamespace) -> Vec<Output> {
+        if self.metrics.is_some() {
+            return vec![Output::default(DataType::Metric)];
+        }
+
+        let log_namespace = global_log_namespace.merge(self.log_namespace);
+        let schema_definition = self.schema_definition(log_namespace);
+
+        vec![Output::default(DataType::Log).with_schema_definition(schema_definition)]
+    }
+
+    fn resources(&self) -> Vec<Resource> {
+        vec![self.address.as_udp_resource()]
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        false
+    }
+}
+
+impl NetflowConfig {
+    fn host_key(&self) -> &str {
+        self.host_key
+            .as_deref()
+            .unwrap_or_else(|| log_schema().host_key())
+    }
+
+    fn schema_definition(&self, log_namespace: LogNamespace) -> Definition {
+        // The fields of flows depend on the protocol and, for template-based ones, on the
+        // templates of the exporters.
+        Definition::new_with_default_metadata(
+            Kind::object(Collection::from_unknown(
+                Kind::bytes().or_integer().or_timestamp(),
+            )),
+            [log_namespace],
+        )
+        .with_event_field(&owned_value_path!("flow_type"), Kind::bytes(), None)
+        .with_event_field(&owned_value_path!("sequence_number"), Kind::integer(), None)
+        .with_standard_vector_source_metadata()
+        .with_source_metadata(
+            Self::NAME,
+            Some(LegacyKey::InsertIfEmpty(owned_value_path!(self.host_key()))),
+            &owned_value_path!("host"),
+            Kind::bytes(),
+            Some("host"),
+        )
+    }
+}
+
+/// Decodes a packet of any of the supported protocols, which the first bytes of their headers
+/// tell apart.
+fn decode(
+    packet: &[u8],
+    exporter: SocketAddr,
+    templates: &mut TemplateCache,
+) -> Result<Decoded, DecodeError> {
+    let mut reader = Reader::new(packet);
+    let flows = match reader.u16()? {
+        5 => netflow_v5::decode(packet)?,
+        9 => return templates.decode_netflow_v9(packet, exporter),
+        10 => return templates.decode_ipfix(packet, exporter),
+        // The version of sFlow is the only one written on four bytes.
+        0 => match reader.u16()? {
+            5 => sflow::decode(packet)?,
+            version => {
+                return Err(DecodeError::UnsupportedVersion {
+                    version: u32::from(version),
+                })
+            }
+        },
+        version => {
+            return Err(DecodeError::UnsupportedVersion {
+                version: u32::from(version),
+            })
+        }
+    };
+
+    Ok(Decoded {
+        flows,
+        missing_templates: Vec::new(),
+    })
+}
+
+impl FlowMetricsConfig {
+    fn to_metrics(&self, flow: &Flow, exporter: IpAddr, timestamp: DateTime<Utc>) -> Vec<Metric> {
+        // The counters of the interfaces of sFlow agents aren't flows.
+        if flow.get("sample_type") == Some(&Value::from("counter")) {
+            return Vec::new();
+        }
+
+        let scale = if self.scale_by_sampling_rate {
+            flow.get("sampling_interval")
+                .and_then(Value::as_integer)
+                .filter(|interval| *interval > 0)
+                .unwrap_or(1)
+        } else {
+            1
+        };
+
+        let mut tags = MetricTags::default();
+        tags.insert("host".to_owned(), exporter.to_string());
+        for name in &self.tags {
+            if let Some(value) = flow.get(name) {
+                tags.insert(name.clone(), value.to_string_lossy().into_owned());
+            }
+        }
+
+        let counter = |name: &str, value: i64| {
+            Metric::new(
+                name,
+                MetricKind::Incremental,
+                MetricValue::Counter {
+                    value: value as f64,
+                },
+            )
+            .with_namespace(Some(self.namespace.clone()))
+            .with_tags(Some(tags.clone()))
+            .with_timestamp(Some(timestamp))
+        };
+
+        let mut metrics = vec![counter("flows_total", 1)];
+        for (field, name) in [("bytes", "bytes_total"), ("packets", "packets_total")] {
+            if let Some(value) = flow.get(field).and_then(Value::as_integer) {
+                metrics.push(counter(name, value.saturating_mul(scale)));
+            }
+        }
+        metrics
+    }
+}
+
+async fn netflow_udp(
+    config: NetflowConfig,
+    shutdown: ShutdownSignal,
+    mut out: SourceSender,
+    log_namespace: LogNamespace,
+) -> Result<(), ()> {
+    let listenfd = ListenFd::from_env();
+    let socket = try_bind_udp_socket(config.address, listenfd)
+        .await
+        .map_err(|error| {
+            emit!(SocketBindError {
+                mode: SocketMode::Udp,
+                error,
+            })
+        })?;
+
+    if let Some(receive_buffer_bytes) = config.receive_buffer_bytes {
+        if let Err(error) = udp::set_receive_buffer_size(&socket, receive_buffer_bytes) {
+            warn!(message = "Failed configuring receive buffer size on UDP socket.", %error);
+        }
+    }
+
+    let bytes_received = register!(BytesReceived::from(Protocol::UDP));
+
+    info!(message = "Listening.", address = %config.address);
+
+    let max_length = config.max_length;
+    let mut templates = TemplateCache::new(config.max_templates);
+    let mut shutdown = shutdown.fuse();
+    // We add 1 to the max_length in order to determine if the received data has been truncated.
+    let mut buf = BytesMut::with_capacity(max_length + 1);
+    loop {
+        buf.resize(max_length + 1, 0);
+        let (byte_size, peer_addr) = tokio::select! {
+            recv = socket.recv_from(&mut buf) => recv.map_err(|error| {
+                emit!(SocketReceiveError {
+                    mode: SocketMode::Udp,
+                    error,
+                })
+            })?,
+            _ = &mut shutdown => break,
+        };
+
+        bytes_received.emit(ByteSize(byte_size));
+
+        if byte_size > max_length {
+            warn!(
+                message = "Discarding packet larger than max_length.",
+                max_length = max_length,
+                internal_log_rate_limit = true
+            );
+            continue;
+        }
+
+        let decoded = match decode(&buf[..byte_size], peer_addr, &mut templates) {
+            Ok(decoded) => decoded,
+            Err(error) => {
+                emit!(NetflowParseError { error, peer_addr });
+                continue;
+            }
+        };
+        for template_id in decoded.missing_templates {
+            emit!(NetflowTemplateMissing {
+                template_id,
+                peer_addr,
+            });
+        }
+
+        let now = Utc::now();
+        let events = match &config.metrics {
+            Some(metrics) => decoded
+                .flows
+                .iter()
+                .flat_map(|flow| metrics.to_metrics(flow, peer_addr.ip(), now))
+                .map(Event::from)
+                .collect::<Vec<_>>(),
+            None => decoded
+                .flows
+                .into_iter()
+                .map(|flow| {
+                    let mut log = log_namespace.new_log_from_data(Value::Object(flow));
+                    log_namespace.insert_standard_vector_source_metadata(
+                        &mut log,
+                        NetflowConfig::NAME,
+                        now,
+                    );
+                    log_namespace.insert_source_metadata(
+                        NetflowConfig::NAME,
+                        &mut log,
+                        Some(LegacyKey::InsertIfEmpty(config.host_key())),
+                        path!("host"),
+                        peer_addr.ip().to_string(),
+                    );
+                    Event::from(log)
+                })
+                .collect(),
+        };
+        if events.is_empty() {
+            continue;
+        }
+
+        let count = events.len();
+        emit!(EventsReceived {
+            count,
+            byte_size: events.size_of(),
+        });
+
+        tokio::select! {
+            result = out.send_batch(events) => {
+                if let Err(error) = result {
+                    emit!(StreamClosedError { error, count });
+                    break;
+                }
+            }
+            _ = &mut shutdown => break,
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::BTreeSet, time::Duration};
+
+    use tokio::net::UdpSocket;
+
+    use super::*;
+    use crate::test_util::{
+        collect_n,
+        components::{assert_source_compliance, SOCKET_HIGH_CARDINALITY_PUSH_SOURCE_TAGS},
+        next_addr,
+    };
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<NetflowConfig>();
+    }
+
+    fn config(address: SocketAddr) -> NetflowConfig {
+        toml::from_str(&format!(r#"address = "{}""#, address)).unwrap()
+    }
+
+    async fn run(config: NetflowConfig, packets: Vec<Vec<u8>>, count: usize) -> Vec<Event> {
+        assert_source_compliance(&SOCKET_HIGH_CARDINALITY_PUSH_SOURCE_TAGS, async {
+            let address = match config.address {
+                SocketListenAddr::SocketAddr(address) => address,
+                SocketListenAddr::SystemdFd(_) => unreachable!(),
+            };
+            let (tx, rx) = SourceSender::new_test();
+            let source = config
+                .build(SourceContext::new_test(tx, None))
+                .await
+                .unwrap();
+            tokio::spawn(source);
+            // Wait for UDP to start listening
+            tokio::time::sleep(Duration::from_millis(100)).await;
+
+            let socket = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+            for packet in packets {
+                socket.send_to(&packet, address).await.unwrap();
+            }
+
+            collect_n(rx, count).await
+        })
+        .await
+    }
+
+    #[tokio::test]
+    async fn receives_all_protocols() {
+        let events = run(
+            config(next_addr()),
+            vec![
+                netflow_v5::tests::packet(),
+                template::tests::netflow_v9(),
+                template::tests::ipfix(),
+                sflow::tests::sflow(),
+            ],
+            5,
+        )
+        .await;
+
+        let flow_types = events
+            .iter()
+            .map(|event| event.as_log()["flow_type"].to_string_lossy().into_owned())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            flow_types,
+            ["netflow_v5", "netflow_v9", "ipfix", "sflow_v5", "sflow_v5"]
+        );
+
+        let log = events[0].as_log();
+        assert_eq!(log["src_addr"], "10.0.0.1".into());
+        assert_eq!(log["bytes"], 3456.into());
+        assert_eq!(log[log_schema().host_key()], "127.0.0.1".into());
+        assert_eq!(log[log_schema().source_type_key()], "netflow".into());
+    }
+
+    #[tokio::test]
+    async fn discards_invalid_packets() {
+        let events = run(
+            config(next_addr()),
+            vec![
+                b"not a flow".to_vec(),
+                vec![0, 7, 0, 0],
+                netflow_v5::tests::packet(),
+            ],
+            1,
+        )
+        .await;
+
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].as_log()["flow_type"], "netflow_v5".into());
+    }
+
+    #[tokio::test]
+    async fn emits_metrics() {
+        let mut config = config(next_addr());
+        config.metrics = Some(toml::from_str("tags = [\"protocol\", \"dst_port\"]").unwrap());
+
+        let events = run(config, vec![netflow_v5::tests::packet()], 3).await;
+        let metrics = events
+            .iter()
+            .map(|event| event.as_metric())
+            .collect::<Vec<_>>();
+
+        let names = metrics
+            .iter()
+            .map(|metric| metric.name())
+            .collect::<Vec<_>>();
+        assert_eq!(names, ["flows_total", "bytes_total", "packets_total"]);
+        for metric in &metrics {
+            assert_eq!(metric.namespace(), Some("netflow"));
+            assert_eq!(metric.tag_value("host").as_deref(), Some("127.0.0.1"));
+            assert_eq!(metric.tag_value("protocol").as_deref(), Some("6"));
+            assert_eq!(metric.tag_value("dst_port").as_deref(), Some("443"));
+        }
+        // The packet was sampled at one out of 100 packets.
+        assert_eq!(
+            metrics[1].value(),
+            &MetricValue::Counter { value: 345_600.0 }
+        );
+        assert_eq!(metrics[2].value(), &MetricValue::Counter { value: 1200.0 });
+    }
+
+    #[test]
+    fn skips_counter_samples_in_metrics() {
+        let metrics: FlowMetricsConfig = toml::from_str("scale_by_sampling_rate = false").unwrap();
+        let flows = sflow::decode(&sflow::tests::sflow()).unwrap();
+        let exporter = IpAddr::V4(Ipv4Addr::LOCALHOST);
+
+        let flow_metrics = metrics.to_metrics(&flows[0], exporter, Utc::now());
+        assert_eq!(flow_metrics.len(), 3);
+        assert_eq!(
+            flow_metrics[1].value(),
+            &MetricValue::Counter { value: 1514.0 }
+        );
+        assert_eq!(
+            flow_metrics[0].tag_value("flow_type").as_deref(),
+            Some("sflow_v5")
+        );
+
+        assert!(metrics
+            .to_metrics(&flows[1], exporter, Utc::now())
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn includes_vector_namespaced_fields() {
+        let mut config = config(next_addr());
+        config.log_namespace = Some(true);
+
+        let events = run(config.clone(), vec![netflow_v5::tests::packet()], 1).await;
+        let log = events[0].as_log();
+        let meta = log.metadata().value();
+
+        assert_eq!(log["flow_type"], "netflow_v5".into());
+        assert!(log.get(log_schema().host_key()).is_none());
+        assert_eq!(
+            meta.get(path!(NetflowConfig::NAME, "host")).unwrap(),
+            &Value::from("127.0.0.1")
+        );
+        assert_eq!(
+            meta.get(path!("vector", "source_type")).unwrap(),
+            &Value::from(NetflowConfig::NAME)
+        );
+
+        let definition = config.outputs(LogNamespace::Vector)[0]
+            .clone()
+            .log_schema_definition
+            .unwrap();
+        definition.assert_valid_for_event(&events[0]);
+    }
+
+    #[test]
+    fn output_schema_definition_legacy_namespace() {
+        let config = config(next_addr());
+        let definition = config.outputs(LogNamespace::Legacy)[0]
+            .clone()
+            .log_schema_definition
+            .unwrap();
+
+        assert_eq!(
+            definition.log_namespaces(),
+            &BTreeSet::from([LogNamespace::Legacy])
+        );
+        assert!(definition
+            .event_kind()
+            .as_object()
+            .unwrap()
+            .known()
+            .contains_key(&value::kind::Field::from("host")));
+    }
+
+    #[test]
+    fn outputs_metrics_when_configured() {
+        let mut config = config(next_addr());
+        config.metrics = Some(toml::from_str("").unwrap());
+
+        let outputs = config.outputs(LogNamespace::Legacy);
+        assert_eq!(outputs[0].ty, DataType::Metric);
+    }
+
+    #[tokio::test]
+    async fn stops_on_shutdown() {
+        let (tx, _rx) = SourceSender::new_test();
+        let (trigger, shutdown, _) = ShutdownSignal::new_wired();
+        let mut cx = SourceContext::new_test(tx, None);
+        cx.shutdown = shutdown;
+
+        let source = tokio::spawn(config(next_addr()).build(cx).await.unwrap());
+        drop(trigger);
+
+        assert_eq!(source.await.unwrap(), Ok(()));
+    }
+}
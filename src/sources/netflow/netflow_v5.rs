@@ -0,0 +1,151 @@
+//! Decodes NetFlow v5 packets, whose records all have the same fields.
+use vector_core::event::Value;
+
+use super::{
+    fields::{timestamp_millis, Flow},
+    reader::{DecodeError, Reader},
+};
+
+const HEADER_LENGTH: usize = 24;
+const RECORD_LENGTH: usize = 48;
+
+pub(super) fn decode(packet: &[u8]) -> Result<Vec<Flow>, DecodeError> {
+    let mut reader = Reader::new(packet);
+    let _version = reader.u16()?;
+    let count = usize::from(reader.u16()?);
+    let sys_uptime = reader.u32()?;
+    let unix_secs = reader.u32()?;
+    let unix_nsecs = reader.u32()?;
+    let sequence_number = reader.u32()?;
+    let engine_type = reader.u8()?;
+    let engine_id = reader.u8()?;
+    // The two high bits are the sampling mode, and the others the sampling interval.
+    let sampling_interval = reader.u16()? & 0x3fff;
+
+    if reader.remaining() < count * RECORD_LENGTH {
+        return Err(DecodeError::Truncated);
+    }
+    debug_assert_eq!(packet.len() - reader.remaining(), HEADER_LENGTH);
+
+    // The times of the records are given in milliseconds since the exporter booted.
+    let boot_time =
+        i64::from(unix_secs) * 1000 + i64::from(unix_nsecs / 1_000_000) - i64::from(sys_uptime);
+
+    let mut flows = Vec::with_capacity(count);
+    for _ in 0..count {
+        let mut flow = Flow::new();
+        flow.insert("flow_type".into(), "netflow_v5".into());
+        flow.insert("sequence_number".into(), sequence_number.into());
+        flow.insert("engine_type".into(), engine_type.into());
+        flow.insert("engine_id".into(), engine_id.into());
+        if sampling_interval > 0 {
+            flow.insert("sampling_interval".into(), sampling_interval.into());
+        }
+
+        flow.insert("src_addr".into(), reader.ipv4()?.to_string().into());
+        flow.insert("dst_addr".into(), reader.ipv4()?.to_string().into());
+        flow.insert("next_hop".into(), reader.ipv4()?.to_string().into());
+        flow.insert("input_interface".into(), reader.u16()?.into());
+        flow.insert("output_interface".into(), reader.u16()?.into());
+        flow.insert("packets".into(), reader.u32()?.into());
+        flow.insert("bytes".into(), reader.u32()?.into());
+        for name in ["flow_start", "flow_end"] {
+            let uptime = i64::from(reader.u32()?);
+            if let Some(timestamp) = timestamp_millis(boot_time + uptime) {
+                flow.insert(name.into(), timestamp);
+            }
+        }
+        flow.insert("src_port".into(), reader.u16()?.into());
+        flow.insert("dst_port".into(), reader.u16()?.into());
+        reader.skip(1)?;
+        flow.insert("tcp_flags".into(), reader.u8()?.into());
+        flow.insert("protocol".into(), reader.u8()?.into());
+        flow.insert("tos".into(), reader.u8()?.into());
+        flow.insert("src_as".into(), reader.u16()?.into());
+        flow.insert("dst_as".into(), reader.u16()?.into());
+        flow.insert("src_mask".into(), reader.u8()?.into());
+        flow.insert("dst_mask".into(), reader.u8()?.into());
+        reader.skip(2)?;
+        flow.insert("ip_version".into(), Value::from(4));
+
+        flows.push(flow);
+    }
+
+    Ok(flows)
+}
+
+#[cfg(test)]
+pub(super) mod tests {
+    use super::*;
+
+    /// A packet with a single record, of a TCP flow from 10.0.0.1:51234 to 10.0.0.2:443.
+    pub(in crate::sources::netflow) fn packet() -> Vec<u8> {
+        let mut packet = Vec::new();
+        packet.extend(5u16.to_be_bytes());
+        packet.extend(1u16.to_be_bytes());
+        packet.extend(10_000u32.to_be_bytes()); // sys_uptime
+        packet.extend(1_600_000_000u32.to_be_bytes()); // unix_secs
+        packet.extend(0u32.to_be_bytes()); // unix_nsecs
+        packet.extend(42u32.to_be_bytes()); // flow_sequence
+        packet.extend([1, 2]); // engine_type, engine_id
+        packet.extend((0x4000u16 | 100).to_be_bytes()); // sampling
+
+        packet.extend([10, 0, 0, 1, 10, 0, 0, 2, 10, 0, 0, 254]);
+        packet.extend(3u16.to_be_bytes());
+        packet.extend(4u16.to_be_bytes());
+        packet.extend(12u32.to_be_bytes());
+        packet.extend(3456u32.to_be_bytes());
+        packet.extend(4_000u32.to_be_bytes()); // first
+        packet.extend(9_000u32.to_be_bytes()); // last
+        packet.extend(51234u16.to_be_bytes());
+        packet.extend(443u16.to_be_bytes());
+        packet.extend([0, 0x1b, 6, 0]); // pad, tcp_flags, protocol, tos
+        packet.extend(64512u16.to_be_bytes());
+        packet.extend(64513u16.to_be_bytes());
+        packet.extend([24, 16, 0, 0]); // masks, pad
+        packet
+    }
+
+    #[test]
+    fn decodes_records() {
+        let flows = decode(&packet()).unwrap();
+        assert_eq!(flows.len(), 1);
+        let flow = &flows[0];
+
+        assert_eq!(flow["flow_type"], Value::from("netflow_v5"));
+        assert_eq!(flow["sequence_number"], Value::from(42));
+        assert_eq!(flow["sampling_interval"], Value::from(100));
+        assert_eq!(flow["src_addr"], Value::from("10.0.0.1"));
+        assert_eq!(flow["dst_addr"], Value::from("10.0.0.2"));
+        assert_eq!(flow["next_hop"], Value::from("10.0.0.254"));
+        assert_eq!(flow["input_interface"], Value::from(3));
+        assert_eq!(flow["output_interface"], Value::from(4));
+        assert_eq!(flow["packets"], Value::from(12));
+        assert_eq!(flow["bytes"], Value::from(3456));
+        assert_eq!(
+            flow["flow_start"],
+            timestamp_millis(1_599_999_994_000).unwrap()
+        );
+        assert_eq!(
+            flow["flow_end"],
+            timestamp_millis(1_599_999_999_000).unwrap()
+        );
+        assert_eq!(flow["src_port"], Value::from(51234));
+        assert_eq!(flow["dst_port"], Value::from(443));
+        assert_eq!(flow["tcp_flags"], Value::from(0x1b));
+        assert_eq!(flow["protocol"], Value::from(6));
+        assert_eq!(flow["src_as"], Value::from(64512));
+        assert_eq!(flow["dst_as"], Value::from(64513));
+        assert_eq!(flow["src_mask"], Value::from(24));
+        assert_eq!(flow["dst_mask"], Value::from(16));
+    }
+
+    #[test]
+    fn rejects_truncated_packets() {
+        let packet = packet();
+        assert_eq!(
+            decode(&packet[..packet.len() - 1]),
+            Err(DecodeError::Truncated)
+        );
+    }
+}
@@ -0,0 +1,121 @@
+//! Reads the big-endian fields of flow export packets.
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+use snafu::Snafu;
+
+#[derive(Debug, PartialEq, Eq, Snafu)]
+pub enum DecodeError {
+    #[snafu(display("unexpected end of packet"))]
+    Truncated,
+    #[snafu(display("unsupported version {}", version))]
+    UnsupportedVersion { version: u32 },
+    #[snafu(display("malformed packet: {}", reason))]
+    Malformed { reason: &'static str },
+}
+
+/// Reads the fields of a packet one after the other.
+#[derive(Debug, Clone)]
+pub(super) struct Reader<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    pub(super) const fn new(data: &'a [u8]) -> Self {
+        Self { data }
+    }
+
+    pub(super) const fn remaining(&self) -> usize {
+        self.data.len()
+    }
+
+    pub(super) const fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    pub(super) fn bytes(&mut self, length: usize) -> Result<&'a [u8], DecodeError> {
+        if self.data.len() < length {
+            return Err(DecodeError::Truncated);
+        }
+        let (bytes, rest) = self.data.split_at(length);
+        self.data = rest;
+        Ok(bytes)
+    }
+
+    /// Reads the next `length` bytes as a reader of their own.
+    pub(super) fn sub(&mut self, length: usize) -> Result<Reader<'a>, DecodeError> {
+        self.bytes(length).map(Reader::new)
+    }
+
+    pub(super) fn skip(&mut self, length: usize) -> Result<(), DecodeError> {
+        self.bytes(length).map(|_| ())
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N], DecodeError> {
+        let mut array = [0; N];
+        array.copy_from_slice(self.bytes(N)?);
+        Ok(array)
+    }
+
+    pub(super) fn u8(&mut self) -> Result<u8, DecodeError> {
+        self.array().map(u8::from_be_bytes)
+    }
+
+    pub(super) fn u16(&mut self) -> Result<u16, DecodeError> {
+        self.array().map(u16::from_be_bytes)
+    }
+
+    pub(super) fn u32(&mut self) -> Result<u32, DecodeError> {
+        self.array().map(u32::from_be_bytes)
+    }
+
+    pub(super) fn u64(&mut self) -> Result<u64, DecodeError> {
+        self.array().map(u64::from_be_bytes)
+    }
+
+    pub(super) fn ipv4(&mut self) -> Result<Ipv4Addr, DecodeError> {
+        self.array::<4>().map(Ipv4Addr::from)
+    }
+
+    pub(super) fn ipv6(&mut self) -> Result<Ipv6Addr, DecodeError> {
+        self.array::<16>().map(Ipv6Addr::from)
+    }
+}
+
+/// Decodes an unsigned integer of up to eight bytes, as the reduced-size encoding of IPFIX allows.
+pub(super) fn decode_unsigned(bytes: &[u8]) -> Option<u64> {
+    (!bytes.is_empty() && bytes.len() <= 8).then(|| {
+        bytes
+            .iter()
+            .fold(0, |value, &byte| (value << 8) | u64::from(byte))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_fields() {
+        let mut reader = Reader::new(&[0x01, 0x00, 0x02, 0x00, 0x00, 0x00, 0x03, 10, 0, 0, 1]);
+
+        assert_eq!(reader.u8(), Ok(1));
+        assert_eq!(reader.u16(), Ok(2));
+        assert_eq!(reader.u32(), Ok(3));
+        assert_eq!(reader.ipv4(), Ok(Ipv4Addr::new(10, 0, 0, 1)));
+        assert!(reader.is_empty());
+        assert_eq!(reader.u8(), Err(DecodeError::Truncated));
+
+        let mut reader = Reader::new(&[0, 0, 0, 0, 0, 0, 0x01, 0x00]);
+        assert_eq!(reader.u64(), Ok(256));
+        assert!(reader.is_empty());
+        assert_eq!(reader.u8(), Err(DecodeError::Truncated));
+    }
+
+    #[test]
+    fn decodes_reduced_size_integers() {
+        assert_eq!(decode_unsigned(&[0x01, 0x00]), Some(256));
+        assert_eq!(decode_unsigned(&[0xff; 8]), Some(u64::MAX));
+        assert_eq!(decode_unsigned(&[]), None);
+        assert_eq!(decode_unsigned(&[0; 9]), None);
+    }
+}
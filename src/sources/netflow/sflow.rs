@@ -0,0 +1,490 @@
+//! Decodes sFlow v5 datagrams, of flow samples taken from the packets the agents forward and of
+//! the counters of their interfaces.
+use std::net::IpAddr;
+
+use vector_core::event::Value;
+
+use super::{
+    fields::{mac_value, unsigned_value, Flow},
+    reader::{DecodeError, Reader},
+};
+
+const FLOW_SAMPLE: u32 = 1;
+const COUNTER_SAMPLE: u32 = 2;
+const EXPANDED_FLOW_SAMPLE: u32 = 3;
+const EXPANDED_COUNTER_SAMPLE: u32 = 4;
+
+const RAW_PACKET_HEADER: u32 = 1;
+const ETHERNET_FRAME: u32 = 2;
+const IPV4_DATA: u32 = 3;
+const IPV6_DATA: u32 = 4;
+const EXTENDED_SWITCH: u32 = 1001;
+const EXTENDED_ROUTER: u32 = 1002;
+const EXTENDED_GATEWAY: u32 = 1003;
+
+const GENERIC_INTERFACE_COUNTERS: u32 = 1;
+
+pub(super) fn decode(datagram: &[u8]) -> Result<Vec<Flow>, DecodeError> {
+    let mut reader = Reader::new(datagram);
+    let _version = reader.u32()?;
+    let agent_address = read_address(&mut reader)?;
+    let sub_agent_id = reader.u32()?;
+    let sequence_number = reader.u32()?;
+    let _uptime = reader.u32()?;
+    let sample_count = reader.u32()?;
+
+    let mut header = Flow::new();
+    header.insert("flow_type".into(), "sflow_v5".into());
+    header.insert("agent_address".into(), agent_address.to_string().into());
+    header.insert("sub_agent_id".into(), sub_agent_id.into());
+    header.insert("sequence_number".into(), sequence_number.into());
+
+    let mut flows = Vec::new();
+    for _ in 0..sample_count {
+        let (format, mut sample) = read_tagged(&mut reader)?;
+        let flow = match format {
+            FLOW_SAMPLE | EXPANDED_FLOW_SAMPLE => Some(decode_flow_sample(
+                &mut sample,
+                format == EXPANDED_FLOW_SAMPLE,
+                &header,
+            )?),
+            COUNTER_SAMPLE | EXPANDED_COUNTER_SAMPLE => {
+                decode_counter_sample(&mut sample, format == EXPANDED_COUNTER_SAMPLE, &header)?
+            }
+            // Samples specific to an enterprise, or of formats added after version 5.
+            _ => None,
+        };
+        flows.extend(flow);
+    }
+
+    Ok(flows)
+}
+
+/// Reads the format of a sample or record, which has the enterprise in its upper 20 bits, along
+/// with its data.
+///
+/// The formats of enterprises other than the standard one are given as zero, so that they match
+/// none of the standard ones.
+fn read_tagged<'a>(reader: &mut Reader<'a>) -> Result<(u32, Reader<'a>), DecodeError> {
+    let tag = reader.u32()?;
+    let length = reader.u32()? as usize;
+    let format = if tag >> 12 == 0 { tag & 0xfff } else { 0 };
+    Ok((format, reader.sub(length)?))
+}
+
+fn read_address(reader: &mut Reader<'_>) -> Result<IpAddr, DecodeError> {
+    match reader.u32()? {
+        1 => reader.ipv4().map(IpAddr::V4),
+        2 => reader.ipv6().map(IpAddr::V6),
+        _ => Err(DecodeError::Malformed {
+            reason: "unknown address type",
+        }),
+    }
+}
+
+/// Reads the source identifier of a sample, as its type and index.
+fn read_source_id(reader: &mut Reader<'_>, expanded: bool) -> Result<(u32, u32), DecodeError> {
+    if expanded {
+        Ok((reader.u32()?, reader.u32()?))
+    } else {
+        let source_id = reader.u32()?;
+        Ok((source_id >> 24, source_id & 0x00ff_ffff))
+    }
+}
+
+/// Reads the interface of a flow sample, whose two upper bits give its format. The index of a
+/// single interface has the format zero.
+fn read_interface(reader: &mut Reader<'_>, expanded: bool) -> Result<Option<u32>, DecodeError> {
+    let (format, value) = if expanded {
+        (reader.u32()?, reader.u32()?)
+    } else {
+        let interface = reader.u32()?;
+        (interface >> 30, interface & 0x3fff_ffff)
+    };
+    Ok((format == 0).then_some(value))
+}
+
+fn decode_flow_sample(
+    sample: &mut Reader<'_>,
+    expanded: bool,
+    header: &Flow,
+) -> Result<Flow, DecodeError> {
+    let mut flow = header.clone();
+    flow.insert("sample_type".into(), "flow".into());
+
+    let _sequence_number = sample.u32()?;
+    let (source_id_type, source_id_index) = read_source_id(sample, expanded)?;
+    let sampling_rate = sample.u32()?;
+    let _sample_pool = sample.u32()?;
+    let _drops = sample.u32()?;
+    let input = read_interface(sample, expanded)?;
+    let output = read_interface(sample, expanded)?;
+    let record_count = sample.u32()?;
+
+    flow.insert("source_id_type".into(), source_id_type.into());
+    flow.insert("source_id_index".into(), source_id_index.into());
+    flow.insert("sampling_interval".into(), sampling_rate.into());
+    if let Some(input) = input {
+        flow.insert("input_interface".into(), input.into());
+    }
+    if let Some(output) = output {
+        flow.insert("output_interface".into(), output.into());
+    }
+    // Each sample stands for a single packet.
+    flow.insert("packets".into(), Value::from(1));
+
+    for _ in 0..record_count {
+        let (format, mut record) = read_tagged(sample)?;
+        match format {
+            RAW_PACKET_HEADER => {
+                let protocol = record.u32()?;
+                let frame_length = record.u32()?;
+                let _stripped = record.u32()?;
+                let header_length = record.u32()? as usize;
+                let packet_header = record.bytes(header_length)?;
+
+                flow.insert("bytes".into(), frame_length.into());
+                // The header is cut at an arbitrary length, so whatever couldn't be read from it
+                // is left out.
+                let _ = decode_packet_header(protocol, packet_header, &mut flow);
+            }
+            ETHERNET_FRAME => {
+                flow.insert("bytes".into(), record.u32()?.into());
+                let src_mac = record.bytes(8)?;
+                let dst_mac = record.bytes(8)?;
+                insert_mac(&mut flow, "src_mac", src_mac);
+                insert_mac(&mut flow, "dst_mac", dst_mac);
+            }
+            IPV4_DATA | IPV6_DATA => {
+                // The length of the IP packet, which the one of the frame is preferred over.
+                let length = record.u32()?;
+                flow.entry("bytes".into()).or_insert_with(|| length.into());
+                flow.insert("protocol".into(), record.u32()?.into());
+                if format == IPV4_DATA {
+                    flow.insert("src_addr".into(), record.ipv4()?.to_string().into());
+                    flow.insert("dst_addr".into(), record.ipv4()?.to_string().into());
+                    flow.insert("ip_version".into(), Value::from(4));
+                } else {
+                    flow.insert("src_addr".into(), record.ipv6()?.to_string().into());
+                    flow.insert("dst_addr".into(), record.ipv6()?.to_string().into());
+                    flow.insert("ip_version".into(), Value::from(6));
+                }
+                flow.insert("src_port".into(), record.u32()?.into());
+                flow.insert("dst_port".into(), record.u32()?.into());
+                flow.insert("tcp_flags".into(), record.u32()?.into());
+                flow.insert("tos".into(), record.u32()?.into());
+            }
+            EXTENDED_SWITCH => {
+                flow.insert("src_vlan".into(), record.u32()?.into());
+                let _src_priority = record.u32()?;
+                flow.insert("dst_vlan".into(), record.u32()?.into());
+            }
+            EXTENDED_ROUTER => {
+                flow.insert(
+                    "next_hop".into(),
+                    read_address(&mut record)?.to_string().into(),
+                );
+                flow.insert("src_mask".into(), record.u32()?.into());
+                flow.insert("dst_mask".into(), record.u32()?.into());
+            }
+            EXTENDED_GATEWAY => {
+                flow.insert(
+                    "bgp_next_hop".into(),
+                    read_address(&mut record)?.to_string().into(),
+                );
+                let router_as = record.u32()?;
+                flow.insert("src_as".into(), record.u32()?.into());
+                let _src_peer_as = record.u32()?;
+
+                // The destination is in the last autonomous system of the path, or in the one
+                // of the router itself when the path is empty.
+                let mut dst_as = router_as;
+                for _ in 0..record.u32()? {
+                    let _segment_type = record.u32()?;
+                    for _ in 0..record.u32()? {
+                        dst_as = record.u32()?;
+                    }
+                }
+                flow.insert("dst_as".into(), dst_as.into());
+            }
+            _ => (),
+        }
+    }
+
+    Ok(flow)
+}
+
+fn decode_counter_sample(
+    sample: &mut Reader<'_>,
+    expanded: bool,
+    header: &Flow,
+) -> Result<Option<Flow>, DecodeError> {
+    let _sequence_number = sample.u32()?;
+    let _source_id = read_source_id(sample, expanded)?;
+    let record_count = sample.u32()?;
+
+    for _ in 0..record_count {
+        let (format, mut record) = read_tagged(sample)?;
+        if format != GENERIC_INTERFACE_COUNTERS {
+            continue;
+        }
+
+        let mut flow = header.clone();
+        flow.insert("sample_type".into(), "counter".into());
+        flow.insert("if_index".into(), record.u32()?.into());
+        flow.insert("if_type".into(), record.u32()?.into());
+        flow.insert("if_speed".into(), unsigned_value(record.u64()?));
+        flow.insert("if_direction".into(), record.u32()?.into());
+        flow.insert("if_status".into(), record.u32()?.into());
+        flow.insert("if_in_octets".into(), unsigned_value(record.u64()?));
+        for name in [
+            "if_in_unicast_packets",
+            "if_in_multicast_packets",
+            "if_in_broadcast_packets",
+            "if_in_discards",
+            "if_in_errors",
+            "if_in_unknown_protocols",
+        ] {
+            flow.insert(name.into(), record.u32()?.into());
+        }
+        flow.insert("if_out_octets".into(), unsigned_value(record.u64()?));
+        for name in [
+            "if_out_unicast_packets",
+            "if_out_multicast_packets",
+            "if_out_broadcast_packets",
+            "if_out_discards",
+            "if_out_errors",
+            "if_promiscuous_mode",
+        ] {
+            flow.insert(name.into(), record.u32()?.into());
+        }
+
+        // Samples have at most one record of each format.
+        return Ok(Some(flow));
+    }
+
+    Ok(None)
+}
+
+/// Inserts a MAC address, which sFlow pads to eight bytes.
+fn insert_mac(flow: &mut Flow, name: &str, bytes: &[u8]) {
+    if let Ok(mac) = <[u8; 6]>::try_from(&bytes[..6]) {
+        flow.insert(name.into(), mac_value(&mac));
+    }
+}
+
+/// The header protocols of raw packet header records this decodes, out of the ones sFlow defines.
+const HEADER_PROTOCOL_ETHERNET: u32 = 1;
+const HEADER_PROTOCOL_IPV4: u32 = 11;
+const HEADER_PROTOCOL_IPV6: u32 = 12;
+
+const ETHERTYPE_VLAN: u16 = 0x8100;
+const ETHERTYPE_IPV4: u16 = 0x0800;
+const ETHERTYPE_IPV6: u16 = 0x86dd;
+
+const IP_PROTOCOL_TCP: u8 = 6;
+const IP_PROTOCOL_UDP: u8 = 17;
+
+/// Decodes the headers of the link, network and transport layers of a sampled packet.
+fn decode_packet_header(protocol: u32, bytes: &[u8], flow: &mut Flow) -> Result<(), DecodeError> {
+    let mut reader = Reader::new(bytes);
+    let ethertype = match protocol {
+        HEADER_PROTOCOL_ETHERNET => {
+            let dst_mac = reader.bytes(6)?;
+            let src_mac = reader.bytes(6)?;
+            insert_mac(flow, "dst_mac", dst_mac);
+            insert_mac(flow, "src_mac", src_mac);
+
+            let mut ethertype = reader.u16()?;
+            if ethertype == ETHERTYPE_VLAN {
+                flow.insert("vlan".into(), (reader.u16()? & 0x0fff).into());
+                ethertype = reader.u16()?;
+            }
+            ethertype
+        }
+        HEADER_PROTOCOL_IPV4 => ETHERTYPE_IPV4,
+        HEADER_PROTOCOL_IPV6 => ETHERTYPE_IPV6,
+        _ => return Ok(()),
+    };
+
+    let ip_protocol = match ethertype {
+        ETHERTYPE_IPV4 => {
+            let version_and_length = reader.u8()?;
+            flow.insert("ip_version".into(), Value::from(4));
+            flow.insert("tos".into(), reader.u8()?.into());
+            // Total length, identification, fragmentation and time to live.
+            reader.skip(7)?;
+            let ip_protocol = reader.u8()?;
+            flow.insert("protocol".into(), ip_protocol.into());
+            reader.skip(2)?;
+            flow.insert("src_addr".into(), reader.ipv4()?.to_string().into());
+            flow.insert("dst_addr".into(), reader.ipv4()?.to_string().into());
+            let options_length = usize::from(version_and_length & 0x0f) * 4;
+            reader.skip(options_length.saturating_sub(20))?;
+            ip_protocol
+        }
+        ETHERTYPE_IPV6 => {
+            let version_class_and_label = reader.u32()?;
+            flow.insert("ip_version".into(), Value::from(6));
+            flow.insert(
+                "tos".into(),
+                ((version_class_and_label >> 20) & 0xff).into(),
+            );
+            flow.insert(
+                "ipv6_flow_label".into(),
+                (version_class_and_label & 0x000f_ffff).into(),
+            );
+            let _payload_length = reader.u16()?;
+            let ip_protocol = reader.u8()?;
+            flow.insert("protocol".into(), ip_protocol.into());
+            let _hop_limit = reader.u8()?;
+            flow.insert("src_addr".into(), reader.ipv6()?.to_string().into());
+            flow.insert("dst_addr".into(), reader.ipv6()?.to_string().into());
+            ip_protocol
+        }
+        _ => return Ok(()),
+    };
+
+    if ip_protocol == IP_PROTOCOL_TCP || ip_protocol == IP_PROTOCOL_UDP {
+        flow.insert("src_port".into(), reader.u16()?.into());
+        flow.insert("dst_port".into(), reader.u16()?.into());
+    }
+    if ip_protocol == IP_PROTOCOL_TCP {
+        // Sequence and acknowledgement numbers, then the data offset before the flags.
+        reader.skip(9)?;
+        flow.insert("tcp_flags".into(), reader.u8()?.into());
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+pub(super) mod tests {
+    use super::*;
+
+    fn tagged(format: u32, body: &[u8]) -> Vec<u8> {
+        let mut tagged = Vec::new();
+        tagged.extend(format.to_be_bytes());
+        tagged.extend((body.len() as u32).to_be_bytes());
+        tagged.extend(body);
+        tagged
+    }
+
+    fn words(words: &[u32]) -> Vec<u8> {
+        words.iter().flat_map(|word| word.to_be_bytes()).collect()
+    }
+
+    fn datagram(samples: &[Vec<u8>]) -> Vec<u8> {
+        let mut datagram = words(&[5, 1]);
+        datagram.extend([192, 0, 2, 10]);
+        datagram.extend(words(&[0, 11, 60_000, samples.len() as u32]));
+        datagram.extend(samples.concat());
+        datagram
+    }
+
+    /// The first bytes of an Ethernet frame with a VLAN tag, of a TCP packet from 10.0.0.1:51234
+    /// to 10.0.0.2:443 with the SYN flag.
+    fn packet_header() -> Vec<u8> {
+        let mut header = vec![0, 0x1b, 0x21, 0, 0, 2, 0, 0x1b, 0x21, 0, 0, 1];
+        header.extend([0x81, 0x00, 0x00, 0x0a, 0x08, 0x00]);
+        header.extend([0x45, 0x10, 0, 60, 0, 0, 0x40, 0, 64, 6, 0, 0]);
+        header.extend([10, 0, 0, 1, 10, 0, 0, 2]);
+        header.extend(51234u16.to_be_bytes());
+        header.extend(443u16.to_be_bytes());
+        header.extend([0, 0, 0, 1, 0, 0, 0, 0, 0x50, 0x02]);
+        header
+    }
+
+    fn flow_sample() -> Vec<u8> {
+        let header = packet_header();
+        let mut raw = words(&[HEADER_PROTOCOL_ETHERNET, 1514, 4, header.len() as u32]);
+        raw.extend(header);
+
+        let mut gateway = words(&[1]);
+        gateway.extend([10, 0, 0, 254]);
+        gateway.extend(words(&[64512, 64513, 64514, 1, 2, 2, 64600, 64601, 0, 0]));
+
+        let mut sample = words(&[1, 3, 512, 2048, 0, 3, 4, 2]);
+        sample.extend(tagged(RAW_PACKET_HEADER, &raw));
+        sample.extend(tagged(EXTENDED_GATEWAY, &gateway));
+        tagged(FLOW_SAMPLE, &sample)
+    }
+
+    fn counter_sample() -> Vec<u8> {
+        let mut counters = words(&[3, 6]);
+        counters.extend(10_000_000_000u64.to_be_bytes());
+        counters.extend(words(&[1, 3]));
+        counters.extend(123_456u64.to_be_bytes());
+        counters.extend(words(&[100, 2, 1, 0, 5, 0]));
+        counters.extend(654_321u64.to_be_bytes());
+        counters.extend(words(&[200, 4, 3, 0, 0, 0]));
+
+        let mut sample = words(&[1, 3, 2]);
+        sample.extend(tagged(2000, &[0; 8]));
+        sample.extend(tagged(GENERIC_INTERFACE_COUNTERS, &counters));
+        tagged(COUNTER_SAMPLE, &sample)
+    }
+
+    /// A datagram with a flow sample followed by a counter sample.
+    pub(in crate::sources::netflow) fn sflow() -> Vec<u8> {
+        datagram(&[flow_sample(), counter_sample()])
+    }
+
+    #[test]
+    fn decodes_flow_samples() {
+        let flows = decode(&sflow()).unwrap();
+        assert_eq!(flows.len(), 2);
+        let flow = &flows[0];
+
+        assert_eq!(flow["flow_type"], Value::from("sflow_v5"));
+        assert_eq!(flow["sample_type"], Value::from("flow"));
+        assert_eq!(flow["agent_address"], Value::from("192.0.2.10"));
+        assert_eq!(flow["sequence_number"], Value::from(11));
+        assert_eq!(flow["sampling_interval"], Value::from(512));
+        assert_eq!(flow["input_interface"], Value::from(3));
+        assert_eq!(flow["output_interface"], Value::from(4));
+        assert_eq!(flow["bytes"], Value::from(1514));
+        assert_eq!(flow["packets"], Value::from(1));
+        assert_eq!(flow["src_mac"], Value::from("00:1b:21:00:00:01"));
+        assert_eq!(flow["dst_mac"], Value::from("00:1b:21:00:00:02"));
+        assert_eq!(flow["vlan"], Value::from(10));
+        assert_eq!(flow["ip_version"], Value::from(4));
+        assert_eq!(flow["tos"], Value::from(0x10));
+        assert_eq!(flow["protocol"], Value::from(6));
+        assert_eq!(flow["src_addr"], Value::from("10.0.0.1"));
+        assert_eq!(flow["dst_addr"], Value::from("10.0.0.2"));
+        assert_eq!(flow["src_port"], Value::from(51234));
+        assert_eq!(flow["dst_port"], Value::from(443));
+        assert_eq!(flow["tcp_flags"], Value::from(0x02));
+        assert_eq!(flow["bgp_next_hop"], Value::from("10.0.0.254"));
+        assert_eq!(flow["src_as"], Value::from(64513));
+        assert_eq!(flow["dst_as"], Value::from(64601));
+    }
+
+    #[test]
+    fn decodes_counter_samples() {
+        let flows = decode(&sflow()).unwrap();
+        let counters = &flows[1];
+
+        assert_eq!(counters["sample_type"], Value::from("counter"));
+        assert_eq!(counters["if_index"], Value::from(3));
+        assert_eq!(counters["if_speed"], Value::from(10_000_000_000i64));
+        assert_eq!(counters["if_in_octets"], Value::from(123_456));
+        assert_eq!(counters["if_in_errors"], Value::from(5));
+        assert_eq!(counters["if_out_octets"], Value::from(654_321));
+        assert_eq!(counters["if_out_unicast_packets"], Value::from(200));
+    }
+
+    #[test]
+    fn skips_unknown_samples() {
+        let flows = decode(&datagram(&[tagged((5 << 12) | 1, &[0; 4])])).unwrap();
+        assert!(flows.is_empty());
+    }
+
+    #[test]
+    fn rejects_truncated_samples() {
+        let mut datagram = sflow();
+        datagram.truncate(datagram.len() - 4);
+        assert_eq!(decode(&datagram), Err(DecodeError::Truncated));
+    }
+}
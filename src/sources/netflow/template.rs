@@ -0,0 +1,598 @@
+//! Decodes NetFlow v9 and IPFIX packets, whose data records are described by templates the
+//! exporters send beforehand.
+use std::{net::SocketAddr, num::NonZeroUsize};
+
+use lru::LruCache;
+
+use super::{
+    fields::{insert_field, Flow},
+    reader::{DecodeError, Reader},
+};
+
+/// The length IPFIX gives to fields whose length is written before each of their values.
+const VARIABLE_LENGTH: u16 = 65535;
+
+/// The bit of IPFIX field identifiers marking the fields specific to an enterprise.
+const ENTERPRISE_BIT: u16 = 0x8000;
+
+/// The identifiers of data sets start at this value, lower ones are for templates.
+const MIN_DATA_SET_ID: u16 = 256;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct TemplateKey {
+    exporter: SocketAddr,
+    version: u16,
+    /// The source identifier of NetFlow v9, or the observation domain identifier of IPFIX.
+    domain: u32,
+    template_id: u16,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct FieldSpecifier {
+    enterprise: Option<u32>,
+    id: u16,
+    length: u16,
+}
+
+#[derive(Debug)]
+struct Template {
+    fields: Vec<FieldSpecifier>,
+    /// Options templates describe records about the exporter itself rather than flows, which
+    /// are skipped.
+    options: bool,
+}
+
+impl Template {
+    /// The length of the shortest record the template can describe, which tells records from
+    /// the padding at the end of sets apart.
+    fn min_record_length(&self) -> usize {
+        self.fields
+            .iter()
+            .map(|field| match field.length {
+                VARIABLE_LENGTH => 1,
+                length => usize::from(length),
+            })
+            .sum()
+    }
+}
+
+/// The templates each exporter sent, so that the data records of their later packets can be
+/// decoded.
+///
+/// Exporters are only known by the address packets come from, which can be spoofed, so the least
+/// recently used templates are evicted past a maximum number of them.
+#[derive(Debug)]
+pub(super) struct TemplateCache {
+    templates: LruCache<TemplateKey, Template>,
+}
+
+/// The flows of a packet, along with the identifiers of the templates its data sets referred to
+/// without the exporter having sent them yet.
+#[derive(Debug, Default)]
+pub(super) struct Decoded {
+    pub(super) flows: Vec<Flow>,
+    pub(super) missing_templates: Vec<u16>,
+}
+
+impl TemplateCache {
+    pub(super) fn new(max_templates: NonZeroUsize) -> Self {
+        Self {
+            templates: LruCache::new(max_templates),
+        }
+    }
+
+    pub(super) fn decode_netflow_v9(
+        &mut self,
+        packet: &[u8],
+        exporter: SocketAddr,
+    ) -> Result<Decoded, DecodeError> {
+        let mut reader = Reader::new(packet);
+        let version = reader.u16()?;
+        let _count = reader.u16()?;
+        let sys_uptime = reader.u32()?;
+        let unix_secs = reader.u32()?;
+        let sequence_number = reader.u32()?;
+        let source_id = reader.u32()?;
+
+        // The times of the records are given in milliseconds since the exporter booted.
+        let boot_time = i64::from(unix_secs) * 1000 - i64::from(sys_uptime);
+
+        let mut header = Flow::new();
+        header.insert("flow_type".into(), "netflow_v9".into());
+        header.insert("sequence_number".into(), sequence_number.into());
+        header.insert("source_id".into(), source_id.into());
+
+        let mut decoded = Decoded::default();
+        // Flowsets are padded to four bytes, so anything shorter than a flowset header is padding.
+        while reader.remaining() >= 4 {
+            let flowset_id = reader.u16()?;
+            let mut flowset = read_set(&mut reader)?;
+            let key = |template_id| TemplateKey {
+                exporter,
+                version,
+                domain: source_id,
+                template_id,
+            };
+
+            match flowset_id {
+                0 => {
+                    while flowset.remaining() >= 4 {
+                        let template_id = flowset.u16()?;
+                        // Templates have identifiers of data sets, so the rest is padding.
+                        if template_id < MIN_DATA_SET_ID {
+                            break;
+                        }
+                        let field_count = flowset.u16()?;
+                        let fields = (0..field_count)
+                            .map(|_| read_netflow_v9_field(&mut flowset))
+                            .collect::<Result<_, _>>()?;
+                        self.insert(key(template_id), fields, false)?;
+                    }
+                }
+                1 => {
+                    while flowset.remaining() >= 6 {
+                        let template_id = flowset.u16()?;
+                        if template_id < MIN_DATA_SET_ID {
+                            break;
+                        }
+                        // Both lengths are in bytes, of four bytes per field specifier.
+                        let scope_length = flowset.u16()?;
+                        let option_length = flowset.u16()?;
+                        let field_count =
+                            (usize::from(scope_length) + usize::from(option_length)) / 4;
+                        let fields = (0..field_count)
+                            .map(|_| read_netflow_v9_field(&mut flowset))
+                            .collect::<Result<_, _>>()?;
+                        self.insert(key(template_id), fields, true)?;
+                    }
+                }
+                id if id >= MIN_DATA_SET_ID => self.decode_data_set(
+                    &mut flowset,
+                    key(id),
+                    &header,
+                    Some(boot_time),
+                    &mut decoded,
+                )?,
+                _ => (),
+            }
+        }
+
+        Ok(decoded)
+    }
+
+    pub(super) fn decode_ipfix(
+        &mut self,
+        packet: &[u8],
+        exporter: SocketAddr,
+    ) -> Result<Decoded, DecodeError> {
+        let mut reader = Reader::new(packet);
+        let version = reader.u16()?;
+        let length = usize::from(reader.u16()?);
+        let _export_time = reader.u32()?;
+        let sequence_number = reader.u32()?;
+        let domain = reader.u32()?;
+
+        let mut message = reader.sub(length.checked_sub(16).ok_or(DecodeError::Malformed {
+            reason: "message length shorter than its header",
+        })?)?;
+
+        let mut header = Flow::new();
+        header.insert("flow_type".into(), "ipfix".into());
+        header.insert("sequence_number".into(), sequence_number.into());
+        header.insert("observation_domain_id".into(), domain.into());
+
+        let mut decoded = Decoded::default();
+        while !message.is_empty() {
+            let set_id = message.u16()?;
+            let mut set = read_set(&mut message)?;
+            let key = |template_id| TemplateKey {
+                exporter,
+                version,
+                domain,
+                template_id,
+            };
+
+            match set_id {
+                2 | 3 => {
+                    let options = set_id == 3;
+                    while set.remaining() >= 4 {
+                        let template_id = set.u16()?;
+                        let field_count = set.u16()?;
+                        if options && field_count > 0 {
+                            let _scope_field_count = set.u16()?;
+                        }
+
+                        // Templates without fields withdraw the ones sent before.
+                        if field_count == 0 {
+                            self.templates.pop(&key(template_id));
+                            continue;
+                        }
+
+                        let fields = (0..field_count)
+                            .map(|_| read_ipfix_field(&mut set))
+                            .collect::<Result<_, _>>()?;
+                        self.insert(key(template_id), fields, options)?;
+                    }
+                }
+                // IPFIX gives times as absolute values, so records don't need a boot time.
+                id if id >= MIN_DATA_SET_ID => {
+                    self.decode_data_set(&mut set, key(id), &header, None, &mut decoded)?
+                }
+                _ => (),
+            }
+        }
+
+        Ok(decoded)
+    }
+
+    fn insert(
+        &mut self,
+        key: TemplateKey,
+        fields: Vec<FieldSpecifier>,
+        options: bool,
+    ) -> Result<(), DecodeError> {
+        // Every record must take up some bytes of its set, or decoding its set would never end.
+        if fields.is_empty() {
+            return Err(DecodeError::Malformed {
+                reason: "template without fields",
+            });
+        }
+        if fields.iter().any(|field| field.length == 0) {
+            return Err(DecodeError::Malformed {
+                reason: "template field of zero length",
+            });
+        }
+
+        trace!(
+            message = "Received template.",
+            exporter = %key.exporter,
+            template_id = key.template_id,
+            fields = fields.len()
+        );
+        self.templates.put(key, Template { fields, options });
+        Ok(())
+    }
+
+    fn decode_data_set(
+        &mut self,
+        set: &mut Reader<'_>,
+        key: TemplateKey,
+        header: &Flow,
+        boot_time: Option<i64>,
+        decoded: &mut Decoded,
+    ) -> Result<(), DecodeError> {
+        let template = match self.templates.get(&key) {
+            Some(template) => template,
+            None => {
+                decoded.missing_templates.push(key.template_id);
+                return Ok(());
+            }
+        };
+
+        let min_record_length = template.min_record_length();
+        while set.remaining() >= min_record_length {
+            let mut flow = header.clone();
+            for field in &template.fields {
+                let length = match field.length {
+                    VARIABLE_LENGTH => match set.u8()? {
+                        255 => usize::from(set.u16()?),
+                        length => usize::from(length),
+                    },
+                    length => usize::from(length),
+                };
+                let bytes = set.bytes(length)?;
+                insert_field(&mut flow, field.enterprise, field.id, bytes, boot_time);
+            }
+
+            if !template.options {
+                decoded.flows.push(flow);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Reads the length of a set, after its identifier, and gives the rest of the set.
+fn read_set<'a>(reader: &mut Reader<'a>) -> Result<Reader<'a>, DecodeError> {
+    let length = usize::from(reader.u16()?);
+    let body_length = length.checked_sub(4).ok_or(DecodeError::Malformed {
+        reason: "set length shorter than its header",
+    })?;
+    reader.sub(body_length)
+}
+
+fn read_netflow_v9_field(reader: &mut Reader<'_>) -> Result<FieldSpecifier, DecodeError> {
+    Ok(FieldSpecifier {
+        enterprise: None,
+        id: reader.u16()?,
+        length: reader.u16()?,
+    })
+}
+
+fn read_ipfix_field(reader: &mut Reader<'_>) -> Result<FieldSpecifier, DecodeError> {
+    let id = reader.u16()?;
+    let length = reader.u16()?;
+    let enterprise = if id & ENTERPRISE_BIT != 0 {
+        Some(reader.u32()?)
+    } else {
+        None
+    };
+
+    Ok(FieldSpecifier {
+        enterprise,
+        id: id & !ENTERPRISE_BIT,
+        length,
+    })
+}
+
+#[cfg(test)]
+pub(super) mod tests {
+    use vector_core::event::Value;
+
+    use super::*;
+    use crate::sources::netflow::fields::timestamp_millis;
+
+    fn exporter() -> SocketAddr {
+        "192.0.2.1:2055".parse().unwrap()
+    }
+
+    fn cache() -> TemplateCache {
+        TemplateCache::new(NonZeroUsize::new(16).unwrap())
+    }
+
+    fn set(id: u16, body: &[u8]) -> Vec<u8> {
+        let mut set = Vec::new();
+        set.extend(id.to_be_bytes());
+        set.extend((body.len() as u16 + 4).to_be_bytes());
+        set.extend(body);
+        set
+    }
+
+    fn fields(fields: &[(u16, u16)]) -> Vec<u8> {
+        fields
+            .iter()
+            .flat_map(|(id, length)| id.to_be_bytes().into_iter().chain(length.to_be_bytes()))
+            .collect()
+    }
+
+    fn netflow_v9_packet(sets: &[Vec<u8>]) -> Vec<u8> {
+        let mut packet = Vec::new();
+        packet.extend(9u16.to_be_bytes());
+        packet.extend((sets.len() as u16).to_be_bytes());
+        packet.extend(10_000u32.to_be_bytes()); // sys_uptime
+        packet.extend(1_600_000_000u32.to_be_bytes()); // unix_secs
+        packet.extend(7u32.to_be_bytes()); // sequence
+        packet.extend(1u32.to_be_bytes()); // source_id
+        packet.extend(sets.concat());
+        packet
+    }
+
+    /// A NetFlow v9 template flowset, of a template with the addresses, the number of bytes and
+    /// the end of flows.
+    fn netflow_v9_template() -> Vec<u8> {
+        let mut body = Vec::new();
+        body.extend(256u16.to_be_bytes());
+        body.extend(4u16.to_be_bytes());
+        body.extend(fields(&[(8, 4), (12, 4), (1, 4), (21, 4)]));
+        set(0, &body)
+    }
+
+    fn netflow_v9_data() -> Vec<u8> {
+        let mut body = Vec::new();
+        body.extend([10, 0, 0, 1, 10, 0, 0, 2]);
+        body.extend(1500u32.to_be_bytes());
+        body.extend(9_000u32.to_be_bytes());
+        // Padding to four bytes.
+        body.extend([0, 0, 0, 0]);
+        set(256, &body)
+    }
+
+    /// A NetFlow v9 packet with a template flowset followed by a data flowset of one record.
+    pub(in crate::sources::netflow) fn netflow_v9() -> Vec<u8> {
+        netflow_v9_packet(&[netflow_v9_template(), netflow_v9_data()])
+    }
+
+    fn ipfix_packet(sets: &[Vec<u8>]) -> Vec<u8> {
+        let sets = sets.concat();
+        let mut packet = Vec::new();
+        packet.extend(10u16.to_be_bytes());
+        packet.extend((sets.len() as u16 + 16).to_be_bytes());
+        packet.extend(1_600_000_000u32.to_be_bytes()); // export_time
+        packet.extend(3u32.to_be_bytes()); // sequence
+        packet.extend(5u32.to_be_bytes()); // observation domain
+        packet.extend(sets);
+        packet
+    }
+
+    /// An IPFIX template set, of a template with the source address, the start of flows in
+    /// milliseconds, an interface name of variable length and a field specific to an enterprise.
+    fn ipfix_template() -> Vec<u8> {
+        let mut body = Vec::new();
+        body.extend(300u16.to_be_bytes());
+        body.extend(4u16.to_be_bytes());
+        body.extend(fields(&[(8, 4), (152, 8), (82, VARIABLE_LENGTH)]));
+        body.extend((ENTERPRISE_BIT | 12).to_be_bytes());
+        body.extend(2u16.to_be_bytes());
+        body.extend(9u32.to_be_bytes());
+        set(2, &body)
+    }
+
+    fn ipfix_data() -> Vec<u8> {
+        let mut body = Vec::new();
+        body.extend([10, 0, 0, 1]);
+        body.extend(1_600_000_000_500u64.to_be_bytes());
+        body.push(4);
+        body.extend(b"eth0");
+        body.extend(42u16.to_be_bytes());
+        set(300, &body)
+    }
+
+    /// An IPFIX message with a template set followed by a data set of one record.
+    pub(in crate::sources::netflow) fn ipfix() -> Vec<u8> {
+        ipfix_packet(&[ipfix_template(), ipfix_data()])
+    }
+
+    #[test]
+    fn decodes_netflow_v9_records() {
+        let mut cache = cache();
+        let decoded = cache.decode_netflow_v9(&netflow_v9(), exporter()).unwrap();
+
+        assert!(decoded.missing_templates.is_empty());
+        assert_eq!(decoded.flows.len(), 1);
+        let flow = &decoded.flows[0];
+        assert_eq!(flow["flow_type"], Value::from("netflow_v9"));
+        assert_eq!(flow["sequence_number"], Value::from(7));
+        assert_eq!(flow["source_id"], Value::from(1));
+        assert_eq!(flow["src_addr"], Value::from("10.0.0.1"));
+        assert_eq!(flow["dst_addr"], Value::from("10.0.0.2"));
+        assert_eq!(flow["bytes"], Value::from(1500));
+        assert_eq!(
+            flow["flow_end"],
+            timestamp_millis(1_599_999_999_000).unwrap()
+        );
+    }
+
+    #[test]
+    fn keeps_templates_per_exporter() {
+        let mut cache = cache();
+        cache
+            .decode_netflow_v9(&netflow_v9_packet(&[netflow_v9_template()]), exporter())
+            .unwrap();
+        assert_eq!(cache.templates.len(), 1);
+
+        let data = netflow_v9_packet(&[netflow_v9_data()]);
+        let decoded = cache.decode_netflow_v9(&data, exporter()).unwrap();
+        assert_eq!(decoded.flows.len(), 1);
+
+        let other = "192.0.2.2:2055".parse().unwrap();
+        let decoded = cache.decode_netflow_v9(&data, other).unwrap();
+        assert!(decoded.flows.is_empty());
+        assert_eq!(decoded.missing_templates, vec![256]);
+    }
+
+    #[test]
+    fn skips_netflow_v9_options_records() {
+        let mut body = Vec::new();
+        body.extend(257u16.to_be_bytes());
+        body.extend(4u16.to_be_bytes()); // scope length
+        body.extend(4u16.to_be_bytes()); // option length
+        body.extend(fields(&[(1, 4), (34, 4)]));
+        body.extend([0, 0]);
+        let template = set(1, &body);
+        let data = set(257, &[0, 0, 0, 1, 0, 0, 0, 100]);
+
+        let mut cache = cache();
+        let decoded = cache
+            .decode_netflow_v9(&netflow_v9_packet(&[template, data]), exporter())
+            .unwrap();
+        assert_eq!(cache.templates.len(), 1);
+        assert!(decoded.flows.is_empty());
+        assert!(decoded.missing_templates.is_empty());
+    }
+
+    #[test]
+    fn decodes_ipfix_records() {
+        let mut cache = cache();
+        let decoded = cache.decode_ipfix(&ipfix(), exporter()).unwrap();
+
+        assert_eq!(decoded.flows.len(), 1);
+        let flow = &decoded.flows[0];
+        assert_eq!(flow["flow_type"], Value::from("ipfix"));
+        assert_eq!(flow["observation_domain_id"], Value::from(5));
+        assert_eq!(flow["src_addr"], Value::from("10.0.0.1"));
+        assert_eq!(
+            flow["flow_start"],
+            timestamp_millis(1_600_000_000_500).unwrap()
+        );
+        assert_eq!(flow["interface_name"], Value::from("eth0"));
+        assert_eq!(flow["ie_9_12"], Value::from(42));
+    }
+
+    #[test]
+    fn withdraws_ipfix_templates() {
+        let mut cache = cache();
+        cache
+            .decode_ipfix(&ipfix_packet(&[ipfix_template()]), exporter())
+            .unwrap();
+        assert_eq!(cache.templates.len(), 1);
+
+        let mut withdrawal = Vec::new();
+        withdrawal.extend(300u16.to_be_bytes());
+        withdrawal.extend(0u16.to_be_bytes());
+        cache
+            .decode_ipfix(&ipfix_packet(&[set(2, &withdrawal)]), exporter())
+            .unwrap();
+        assert_eq!(cache.templates.len(), 0);
+
+        let decoded = cache
+            .decode_ipfix(&ipfix_packet(&[ipfix_data()]), exporter())
+            .unwrap();
+        assert_eq!(decoded.missing_templates, vec![300]);
+    }
+
+    #[test]
+    fn rejects_malformed_sets() {
+        let mut cache = cache();
+        let packet = netflow_v9_packet(&[vec![1, 0, 0, 2]]);
+        assert!(matches!(
+            cache.decode_netflow_v9(&packet, exporter()),
+            Err(DecodeError::Malformed { .. })
+        ));
+
+        let mut packet = ipfix();
+        packet.pop();
+        assert!(matches!(
+            cache.decode_ipfix(&packet, exporter()),
+            Err(DecodeError::Truncated)
+        ));
+    }
+
+    #[test]
+    fn rejects_templates_of_empty_records() {
+        let mut cache = cache();
+        let mut body = Vec::new();
+        body.extend(256u16.to_be_bytes());
+        body.extend(2u16.to_be_bytes());
+        body.extend(fields(&[(8, 0), (12, 0)]));
+        let packet = netflow_v9_packet(&[set(0, &body), set(256, &[0; 8])]);
+        assert!(matches!(
+            cache.decode_netflow_v9(&packet, exporter()),
+            Err(DecodeError::Malformed { .. })
+        ));
+        assert_eq!(cache.templates.len(), 0);
+
+        let mut body = Vec::new();
+        body.extend(300u16.to_be_bytes());
+        body.extend(1u16.to_be_bytes());
+        body.extend(fields(&[(8, 0)]));
+        let packet = ipfix_packet(&[set(2, &body), set(300, &[0; 8])]);
+        assert!(matches!(
+            cache.decode_ipfix(&packet, exporter()),
+            Err(DecodeError::Malformed { .. })
+        ));
+        assert_eq!(cache.templates.len(), 0);
+    }
+
+    #[test]
+    fn evicts_least_recently_used_templates() {
+        let mut cache = TemplateCache::new(NonZeroUsize::new(2).unwrap());
+        let template = netflow_v9_packet(&[netflow_v9_template()]);
+        let exporters = ["192.0.2.1:2055", "192.0.2.2:2055", "192.0.2.3:2055"]
+            .map(|exporter| exporter.parse::<SocketAddr>().unwrap());
+
+        cache.decode_netflow_v9(&template, exporters[0]).unwrap();
+        cache.decode_netflow_v9(&template, exporters[1]).unwrap();
+        // Decoding records keeps the template of the first exporter in use.
+        let data = netflow_v9_packet(&[netflow_v9_data()]);
+        let decoded = cache.decode_netflow_v9(&data, exporters[0]).unwrap();
+        assert_eq!(decoded.flows.len(), 1);
+
+        cache.decode_netflow_v9(&template, exporters[2]).unwrap();
+        assert_eq!(cache.templates.len(), 2);
+        let decoded = cache.decode_netflow_v9(&data, exporters[0]).unwrap();
+        assert_eq!(decoded.flows.len(), 1);
+        let decoded = cache.decode_netflow_v9(&data, exporters[1]).unwrap();
+        assert_eq!(decoded.missing_templates, vec![256]);
+    }
+}
@@ -0,0 +1,299 @@
+//! A reader of the subset of the Basic Encoding Rules (BER) used by SNMP messages.
+use std::{fmt, str::FromStr};
+
+use snafu::Snafu;
+
+/// The tags of the types found in SNMP messages.
+pub(super) mod tag {
+    pub const INTEGER: u8 = 0x02;
+    pub const OCTET_STRING: u8 = 0x04;
+    pub const NULL: u8 = 0x05;
+    pub const OBJECT_IDENTIFIER: u8 = 0x06;
+    pub const SEQUENCE: u8 = 0x30;
+    pub const IP_ADDRESS: u8 = 0x40;
+    pub const COUNTER32: u8 = 0x41;
+    pub const GAUGE32: u8 = 0x42;
+    pub const TIMETICKS: u8 = 0x43;
+    pub const OPAQUE: u8 = 0x44;
+    pub const COUNTER64: u8 = 0x46;
+    pub const NO_SUCH_OBJECT: u8 = 0x80;
+    pub const NO_SUCH_INSTANCE: u8 = 0x81;
+    pub const END_OF_MIB_VIEW: u8 = 0x82;
+    pub const TRAP_V1: u8 = 0xa4;
+    pub const TRAP_V2: u8 = 0xa7;
+}
+
+#[derive(Debug, PartialEq, Eq, Snafu)]
+pub enum BerError {
+    #[snafu(display("unexpected end of data"))]
+    Truncated,
+    #[snafu(display("expected tag 0x{:02x}, found 0x{:02x}", expected, found))]
+    UnexpectedTag { expected: u8, found: u8 },
+    #[snafu(display("unsupported length encoding"))]
+    InvalidLength,
+    #[snafu(display("invalid integer"))]
+    InvalidInteger,
+    #[snafu(display("invalid object identifier"))]
+    InvalidObjectIdentifier,
+}
+
+/// Reads the elements encoded one after the other in a buffer.
+#[derive(Debug)]
+pub(super) struct Reader<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    pub(super) const fn new(data: &'a [u8]) -> Self {
+        Self { data }
+    }
+
+    pub(super) const fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// Reads the next element, returning its tag and contents.
+    pub(super) fn read_any(&mut self) -> Result<(u8, &'a [u8]), BerError> {
+        let (&tag, rest) = self.data.split_first().ok_or(BerError::Truncated)?;
+        let (&first, mut rest) = rest.split_first().ok_or(BerError::Truncated)?;
+
+        let length = if first & 0x80 == 0 {
+            usize::from(first)
+        } else {
+            // The indefinite form, with no octets, isn't allowed for SNMP.
+            let octets = usize::from(first & 0x7f);
+            if octets == 0 || octets > std::mem::size_of::<usize>() {
+                return Err(BerError::InvalidLength);
+            }
+            if rest.len() < octets {
+                return Err(BerError::Truncated);
+            }
+            let (octets, remaining) = rest.split_at(octets);
+            rest = remaining;
+            octets
+                .iter()
+                .fold(0, |length, &octet| (length << 8) | usize::from(octet))
+        };
+
+        if rest.len() < length {
+            return Err(BerError::Truncated);
+        }
+        let (contents, rest) = rest.split_at(length);
+        self.data = rest;
+        Ok((tag, contents))
+    }
+
+    /// Reads the next element, which must have the given tag, returning its contents.
+    pub(super) fn read(&mut self, expected: u8) -> Result<&'a [u8], BerError> {
+        match self.read_any()? {
+            (found, contents) if found == expected => Ok(contents),
+            (found, _) => Err(BerError::UnexpectedTag { expected, found }),
+        }
+    }
+
+    /// Reads a constructed element, such as a sequence, returning a reader of its elements.
+    pub(super) fn read_constructed(&mut self, expected: u8) -> Result<Reader<'a>, BerError> {
+        self.read(expected).map(Reader::new)
+    }
+
+    pub(super) fn read_integer(&mut self) -> Result<i64, BerError> {
+        self.read(tag::INTEGER).and_then(decode_integer)
+    }
+
+    pub(super) fn read_object_identifier(&mut self) -> Result<Oid, BerError> {
+        self.read(tag::OBJECT_IDENTIFIER)
+            .and_then(decode_object_identifier)
+    }
+}
+
+/// Decodes a two's complement integer.
+pub(super) fn decode_integer(contents: &[u8]) -> Result<i64, BerError> {
+    if contents.is_empty() || contents.len() > 8 {
+        return Err(BerError::InvalidInteger);
+    }
+
+    let initial = if contents[0] & 0x80 == 0 { 0 } else { -1 };
+    Ok(contents
+        .iter()
+        .fold(initial, |value, &octet| (value << 8) | i64::from(octet)))
+}
+
+/// Decodes an unsigned integer, such as a counter, which is encoded like an `INTEGER` so has an
+/// extra leading zero octet when its high bit is set.
+pub(super) fn decode_unsigned(contents: &[u8]) -> Result<u64, BerError> {
+    let contents = match contents {
+        [0, rest @ ..] if !rest.is_empty() => rest,
+        contents => contents,
+    };
+    if contents.is_empty() || contents.len() > 8 {
+        return Err(BerError::InvalidInteger);
+    }
+
+    Ok(contents
+        .iter()
+        .fold(0, |value, &octet| (value << 8) | u64::from(octet)))
+}
+
+pub(super) fn decode_object_identifier(contents: &[u8]) -> Result<Oid, BerError> {
+    let mut subidentifiers = Vec::with_capacity(contents.len() + 1);
+    let mut value: u32 = 0;
+    for &octet in contents {
+        // A leading 0x80 octet pads a subidentifier, which isn't allowed.
+        if value == 0 && octet == 0x80 {
+            return Err(BerError::InvalidObjectIdentifier);
+        }
+        value = value
+            .checked_mul(128)
+            .map(|value| value | u32::from(octet & 0x7f))
+            .ok_or(BerError::InvalidObjectIdentifier)?;
+
+        if octet & 0x80 == 0 {
+            // The first two arcs are combined into the first subidentifier.
+            if subidentifiers.is_empty() {
+                let first = (value / 40).min(2);
+                subidentifiers.push(first);
+                subidentifiers.push(value - first * 40);
+            } else {
+                subidentifiers.push(value);
+            }
+            value = 0;
+        }
+    }
+
+    // Either there were no octets, or the last one had its continuation bit set.
+    if subidentifiers.is_empty() || contents.last().map_or(true, |&octet| octet & 0x80 != 0) {
+        return Err(BerError::InvalidObjectIdentifier);
+    }
+
+    Ok(Oid(subidentifiers))
+}
+
+/// An object identifier, such as `1.3.6.1.2.1.1.3.0`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Oid(pub(super) Vec<u32>);
+
+impl Oid {
+    pub(super) fn as_slice(&self) -> &[u32] {
+        &self.0
+    }
+}
+
+impl fmt::Display for Oid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, subidentifier) in self.0.iter().enumerate() {
+            if index > 0 {
+                f.write_str(".")?;
+            }
+            write!(f, "{}", subidentifier)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Oid {
+    type Err = std::num::ParseIntError;
+
+    /// Parses the dotted form of an object identifier, with an optional leading dot.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.strip_prefix('.')
+            .unwrap_or(s)
+            .split('.')
+            .map(str::parse)
+            .collect::<Result<_, _>>()
+            .map(Oid)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_short_and_long_lengths() {
+        let mut data = vec![
+            tag::OCTET_STRING,
+            0x02,
+            b'h',
+            b'i',
+            tag::OCTET_STRING,
+            0x81,
+            200,
+        ];
+        data.extend([b'x'; 200]);
+        let mut reader = Reader::new(&data);
+
+        assert_eq!(reader.read(tag::OCTET_STRING).unwrap(), b"hi");
+        assert_eq!(reader.read(tag::OCTET_STRING).unwrap().len(), 200);
+        assert!(reader.is_empty());
+    }
+
+    #[test]
+    fn rejects_truncated_elements() {
+        let mut reader = Reader::new(&[tag::OCTET_STRING, 0x05, b'h', b'i']);
+        assert_eq!(reader.read_any(), Err(BerError::Truncated));
+    }
+
+    #[test]
+    fn rejects_indefinite_lengths() {
+        let mut reader = Reader::new(&[tag::SEQUENCE, 0x80, 0x00, 0x00]);
+        assert_eq!(reader.read_any(), Err(BerError::InvalidLength));
+    }
+
+    #[test]
+    fn rejects_unexpected_tags() {
+        let mut reader = Reader::new(&[tag::NULL, 0x00]);
+        assert_eq!(
+            reader.read_integer(),
+            Err(BerError::UnexpectedTag {
+                expected: tag::INTEGER,
+                found: tag::NULL
+            })
+        );
+    }
+
+    #[test]
+    fn decodes_integers() {
+        assert_eq!(decode_integer(&[0x00]), Ok(0));
+        assert_eq!(decode_integer(&[0x7f]), Ok(127));
+        assert_eq!(decode_integer(&[0x00, 0x80]), Ok(128));
+        assert_eq!(decode_integer(&[0xff]), Ok(-1));
+        assert_eq!(decode_integer(&[0xff, 0x7f]), Ok(-129));
+        assert_eq!(decode_integer(&[]), Err(BerError::InvalidInteger));
+    }
+
+    #[test]
+    fn decodes_unsigned_integers() {
+        assert_eq!(
+            decode_unsigned(&[0x00, 0xff, 0xff, 0xff, 0xff]),
+            Ok(u32::MAX.into())
+        );
+        assert_eq!(
+            decode_unsigned(&[0x00, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff]),
+            Ok(u64::MAX)
+        );
+        assert_eq!(decode_unsigned(&[0x00]), Ok(0));
+    }
+
+    #[test]
+    fn decodes_object_identifiers() {
+        let oid =
+            decode_object_identifier(&[0x2b, 0x06, 0x01, 0x04, 0x01, 0x8f, 0x65, 0x02]).unwrap();
+        assert_eq!(oid.to_string(), "1.3.6.1.4.1.2021.2");
+        assert_eq!(oid, "1.3.6.1.4.1.2021.2".parse().unwrap());
+
+        assert_eq!(
+            decode_object_identifier(&[0x2b, 0x86]),
+            Err(BerError::InvalidObjectIdentifier)
+        );
+        assert_eq!(
+            decode_object_identifier(&[]),
+            Err(BerError::InvalidObjectIdentifier)
+        );
+    }
+
+    #[test]
+    fn parses_object_identifiers() {
+        assert_eq!(".1.3.6".parse::<Oid>().unwrap(), Oid(vec![1, 3, 6]));
+        assert!("1.3.x".parse::<Oid>().is_err());
+    }
+}
@@ -0,0 +1,209 @@
+//! Translates object identifiers into names, from MIBs compiled into mappings of names to
+//! object identifiers.
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use snafu::{ResultExt, Snafu};
+
+use super::ber::Oid;
+
+#[derive(Debug, Snafu)]
+pub enum MibError {
+    #[snafu(display("Could not read MIB directory {:?}: {}", path, source))]
+    ReadDirectory {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    #[snafu(display("Could not read MIB file {:?}: {}", path, source))]
+    ReadFile {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    #[snafu(display("Could not parse MIB file {:?}: {}", path, source))]
+    ParseJson {
+        path: PathBuf,
+        source: serde_json::Error,
+    },
+    #[snafu(display("Invalid mapping on line {} of MIB file {:?}", line, path))]
+    InvalidLine { path: PathBuf, line: usize },
+}
+
+/// The names of the objects defined by a set of MIBs.
+#[derive(Debug, Default)]
+pub(super) struct Mibs {
+    names: HashMap<Vec<u32>, String>,
+}
+
+impl Mibs {
+    /// Loads the mappings of the files of a directory, in the order of their names.
+    ///
+    /// Files with the `json` extension are read as the output of the JSON code generator of
+    /// `pysmi`, with the name of their module. Files with the `txt` extension are read as lines
+    /// of names and object identifiers separated by whitespace, optionally quoted, such as the
+    /// output of `snmptranslate -Tz`. Other files are ignored.
+    pub(super) fn load(directory: &Path) -> Result<Self, MibError> {
+        let mut paths = fs::read_dir(directory)
+            .and_then(|entries| {
+                entries
+                    .map(|entry| entry.map(|entry| entry.path()))
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .context(ReadDirectorySnafu { path: directory })?;
+        paths.sort();
+
+        let mut mibs = Self::default();
+        for path in paths {
+            match path.extension().and_then(|extension| extension.to_str()) {
+                Some("json") => mibs.load_json(&path)?,
+                Some("txt") => mibs.load_text(&path)?,
+                _ => continue,
+            }
+        }
+
+        debug!(
+            message = "Loaded MIBs.",
+            directory = ?directory,
+            objects = mibs.names.len()
+        );
+        Ok(mibs)
+    }
+
+    fn load_json(&mut self, path: &Path) -> Result<(), MibError> {
+        let contents = fs::read(path).context(ReadFileSnafu { path })?;
+        let symbols = serde_json::from_slice::<HashMap<String, serde_json::Value>>(&contents)
+            .context(ParseJsonSnafu { path })?;
+
+        let module = symbols
+            .get("meta")
+            .and_then(|meta| meta.get("module"))
+            .and_then(|module| module.as_str());
+        for symbol in symbols.values() {
+            let name = symbol.get("name").and_then(|name| name.as_str());
+            let oid = symbol
+                .get("oid")
+                .and_then(|oid| oid.as_str())
+                .and_then(|oid| oid.parse::<Oid>().ok());
+            if let (Some(name), Some(oid)) = (name, oid) {
+                let name = match module {
+                    Some(module) => format!("{}::{}", module, name),
+                    None => name.to_owned(),
+                };
+                self.names.insert(oid.0, name);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn load_text(&mut self, path: &Path) -> Result<(), MibError> {
+        let contents = fs::read_to_string(path).context(ReadFileSnafu { path })?;
+
+        for (index, line) in contents.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let mut fields = line.split_whitespace().map(|field| field.trim_matches('"'));
+            let mapping = match (fields.next(), fields.next(), fields.next()) {
+                (Some(name), Some(oid), None) => oid.parse::<Oid>().ok().map(|oid| (oid, name)),
+                _ => None,
+            };
+            let (oid, name) = mapping.ok_or(MibError::InvalidLine {
+                path: path.to_owned(),
+                line: index + 1,
+            })?;
+            self.names.insert(oid.0, name.to_owned());
+        }
+
+        Ok(())
+    }
+
+    /// Names an object identifier after the longest object identifier the MIBs define that it
+    /// starts with, followed by the rest of its subidentifiers, such as `IF-MIB::ifIndex.3`.
+    pub(super) fn translate(&self, oid: &Oid) -> Option<String> {
+        let subidentifiers = oid.as_slice();
+        (1..=subidentifiers.len()).rev().find_map(|length| {
+            self.names.get(&subidentifiers[..length]).map(|name| {
+                let mut name = name.clone();
+                for subidentifier in &subidentifiers[length..] {
+                    name.push('.');
+                    name.push_str(&subidentifier.to_string());
+                }
+                name
+            })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn translate(mibs: &Mibs, oid: &str) -> Option<String> {
+        mibs.translate(&oid.parse().unwrap())
+    }
+
+    #[test]
+    fn loads_json_and_text_mappings() {
+        let directory = tempfile::tempdir().unwrap();
+        fs::write(
+            directory.path().join("IF-MIB.json"),
+            r#"{
+                "ifIndex": {"name": "ifIndex", "oid": "1.3.6.1.2.1.2.2.1.1", "class": "objecttype"},
+                "linkDown": {"name": "linkDown", "oid": "1.3.6.1.6.3.1.1.5.3", "class": "notificationtype"},
+                "InterfaceIndex": {"name": "InterfaceIndex", "class": "textualconvention"},
+                "meta": {"module": "IF-MIB"}
+            }"#,
+        )
+        .unwrap();
+        fs::write(
+            directory.path().join("mappings.txt"),
+            "# snmptranslate -Tz\n\"sysUpTime\"\t\t\"1.3.6.1.2.1.1.3\"\n\nenterprises .1.3.6.1.4.1\n",
+        )
+        .unwrap();
+        fs::write(directory.path().join("README"), "not a mapping").unwrap();
+
+        let mibs = Mibs::load(directory.path()).unwrap();
+
+        assert_eq!(
+            translate(&mibs, "1.3.6.1.2.1.2.2.1.1.3").as_deref(),
+            Some("IF-MIB::ifIndex.3")
+        );
+        assert_eq!(
+            translate(&mibs, "1.3.6.1.6.3.1.1.5.3").as_deref(),
+            Some("IF-MIB::linkDown")
+        );
+        assert_eq!(
+            translate(&mibs, "1.3.6.1.2.1.1.3.0").as_deref(),
+            Some("sysUpTime.0")
+        );
+        assert_eq!(
+            translate(&mibs, "1.3.6.1.4.1.2021.2").as_deref(),
+            Some("enterprises.2021.2")
+        );
+        assert_eq!(translate(&mibs, "1.3.6.1.2.1.4"), None);
+    }
+
+    #[test]
+    fn rejects_invalid_lines() {
+        let directory = tempfile::tempdir().unwrap();
+        fs::write(
+            directory.path().join("mappings.txt"),
+            "sysUpTime 1.3.6.1.2.1.1.3\nsysDescr\n",
+        )
+        .unwrap();
+
+        let error = Mibs::load(directory.path()).unwrap_err();
+        assert!(matches!(error, MibError::InvalidLine { line: 2, .. }));
+    }
+
+    #[test]
+    fn rejects_missing_directories() {
+        let error = Mibs::load(Path::new("/nonexistent/mibs")).unwrap_err();
+        assert!(matches!(error, MibError::ReadDirectory { .. }));
+    }
+}
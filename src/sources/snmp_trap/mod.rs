@@ -0,0 +1,469 @@
+//! `SNMP trap` source.
+//! Receives SNMPv1 and SNMPv2c traps over UDP.
+use std::{
+    net::{Ipv4Addr, SocketAddr, SocketAddrV4},
+    path::PathBuf,
+};
+
+use bytes::BytesMut;
+use chrono::Utc;
+use futures::FutureExt;
+use listenfd::ListenFd;
+use lookup::{owned_value_path, path};
+use value::{
+    kind::{Collection, Field},
+    Kind,
+};
+use vector_common::internal_event::{
+    ByteSize, BytesReceived, EventsReceived, InternalEventHandle as _, Protocol,
+};
+use vector_config::{configurable_component, NamedComponent};
+use vector_core::{
+    config::{log_schema, DataType, LegacyKey, LogNamespace},
+    schema::Definition,
+    ByteSizeOf,
+};
+
+use crate::{
+    config::{GenerateConfig, Output, Resource, SourceConfig, SourceContext},
+    event::Event,
+    internal_events::{
+        SnmpTrapParseError, SocketBindError, SocketMode, SocketReceiveError, StreamClosedError,
+    },
+    shutdown::ShutdownSignal,
+    sources::util::net::{try_bind_udp_socket, SocketListenAddr},
+    udp, SourceSender,
+};
+
+mod ber;
+mod mib;
+mod trap;
+
+use self::{mib::Mibs, trap::Trap};
+
+/// Configuration for the `snmp_trap` source.
+#[configurable_component(source("snmp_trap"))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct SnmpTrapConfig {
+    /// The address to listen for traps on.
+    address: SocketListenAddr,
+
+    /// The maximum size, in bytes, of incoming traps.
+    ///
+    /// Traps larger than this are discarded.
+    #[serde(default = "default_max_length")]
+    max_length: usize,
+
+    /// The size, in bytes, of the receive buffer used for the listening socket.
+    ///
+    /// This should not typically needed to be changed.
+    receive_buffer_bytes: Option<usize>,
+
+    /// Overrides the name of the log field used to add the address of the sender of each trap
+    /// to each event.
+    ///
+    /// By default, the [global `log_schema.host_key` option][global_host_key] is used.
+    ///
+    /// [global_host_key]: https://vector.dev/docs/reference/configuration/global-options/#log_schema.host_key
+    host_key: Option<String>,
+
+    /// A directory of MIBs compiled into mappings of names to object identifiers, used to name the
+    /// object identifiers of traps.
+    ///
+    /// Files with the `.json` extension are read as the output of the JSON code generator of
+    /// `pysmi`, such as `mibdump --destination-format json`. Files with the `.txt` extension are
+    /// read as lines of a name and an object identifier separated by whitespace, such as the
+    /// output of `snmptranslate -Tz`. Other files are ignored.
+    ///
+    /// When set, the names of the enterprise, the trap, and the variable bindings are added to
+    /// each event, in the `enterprise_name`, `trap_name`, and `name` fields respectively.
+    mib_directory: Option<PathBuf>,
+
+    /// The namespace to use for logs. This overrides the global setting.
+    #[serde(default)]
+    log_namespace: Option<bool>,
+}
+
+/// The largest payload of a UDP datagram.
+const fn default_max_length() -> usize {
+    65_535
+}
+
+impl GenerateConfig for SnmpTrapConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            address: SocketListenAddr::SocketAddr(SocketAddr::V4(SocketAddrV4::new(
+                Ipv4Addr::UNSPECIFIED,
+                162,
+            ))),
+            max_length: default_max_length(),
+            receive_buffer_bytes: None,
+            host_key: None,
+            mib_directory: None,
+            log_namespace: None,
+        })
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+impl SourceConfig for SnmpTrapConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        let log_namespace = cx.log_namespace(self.log_namespace);
+        let mibs = self.mib_directory.as_deref().map(Mibs::load).transpose()?;
+
+        Ok(Box::pin(snmp_trap_udp(
+            self.clone(),
+            mibs,
+            cx.shutdown,
+            cx.out,
+            log_namespace,
+        )))
+    }
+
+    fn outputs(&self, global_log_namespace: LogNamespace) -> Vec<Output> {
+        let log_namespace = global_log_namespace.merge(self.log_namespace);
+        let schema_definition = self.schema_definition(log_namespace);
+
+        vec![Output::default(DataType::Log).with_schema_definition(schema_definition)]
+    }
+
+    fn resources(&self) -> Vec<Resource> {
+        vec![self.address.as_udp_resource()]
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        false
+    }
+}
+
+impl SnmpTrapConfig {
+    fn host_key(&self) -> &str {
+        self.host_key
+            .as_deref()
+            .unwrap_or_else(|| log_schema().host_key())
+    }
+
+    fn schema_definition(&self, log_namespace: LogNamespace) -> Definition {
+        let varbind = Kind::object(
+            Collection::from_unknown(Kind::never())
+                .with_known(Field::from("name"), Kind::bytes().or_undefined())
+                .with_known(Field::from("oid"), Kind::bytes())
+                .with_known(Field::from("type"), Kind::bytes())
+                .with_known(Field::from("value"), Kind::any()),
+        );
+
+        Definition::new_with_default_metadata(Kind::object(Collection::empty()), [log_namespace])
+            .with_event_field(&owned_value_path!("version"), Kind::bytes(), None)
+            .with_event_field(&owned_value_path!("community"), Kind::bytes(), None)
+            .with_event_field(&owned_value_path!("enterprise"), Kind::bytes(), None)
+            .optional_field(&owned_value_path!("enterprise_name"), Kind::bytes(), None)
+            .with_event_field(&owned_value_path!("agent_address"), Kind::bytes(), None)
+            .with_event_field(&owned_value_path!("generic_trap"), Kind::integer(), None)
+            .with_event_field(&owned_value_path!("specific_trap"), Kind::integer(), None)
+            .optional_field(&owned_value_path!("uptime"), Kind::integer(), None)
+            .with_event_field(&owned_value_path!("trap_oid"), Kind::bytes(), None)
+            .optional_field(&owned_value_path!("trap_name"), Kind::bytes(), None)
+            .with_event_field(
+                &owned_value_path!("varbinds"),
+                Kind::array(Collection::from_unknown(varbind)),
+                None,
+            )
+            .with_standard_vector_source_metadata()
+            .with_source_metadata(
+                Self::NAME,
+                Some(LegacyKey::InsertIfEmpty(owned_value_path!(self.host_key()))),
+                &owned_value_path!("host"),
+                Kind::bytes(),
+                Some("host"),
+            )
+    }
+}
+
+async fn snmp_trap_udp(
+    config: SnmpTrapConfig,
+    mibs: Option<Mibs>,
+    shutdown: ShutdownSignal,
+    mut out: SourceSender,
+    log_namespace: LogNamespace,
+) -> Result<(), ()> {
+    let listenfd = ListenFd::from_env();
+    let socket = try_bind_udp_socket(config.address, listenfd)
+        .await
+        .map_err(|error| {
+            emit!(SocketBindError {
+                mode: SocketMode::Udp,
+                error,
+            })
+        })?;
+
+    if let Some(receive_buffer_bytes) = config.receive_buffer_bytes {
+        if let Err(error) = udp::set_receive_buffer_size(&socket, receive_buffer_bytes) {
+            warn!(message = "Failed configuring receive buffer size on UDP socket.", %error);
+        }
+    }
+
+    let bytes_received = register!(BytesReceived::from(Protocol::UDP));
+
+    info!(message = "Listening.", address = %config.address);
+
+    let max_length = config.max_length;
+    let mut shutdown = shutdown.fuse();
+    // We add 1 to the max_length in order to determine if the received data has been truncated.
+    let mut buf = BytesMut::with_capacity(max_length + 1);
+    loop {
+        buf.resize(max_length + 1, 0);
+        let (byte_size, peer_addr) = tokio::select! {
+            recv = socket.recv_from(&mut buf) => recv.map_err(|error| {
+                emit!(SocketReceiveError {
+                    mode: SocketMode::Udp,
+                    error,
+                })
+            })?,
+            _ = &mut shutdown => break,
+        };
+
+        bytes_received.emit(ByteSize(byte_size));
+
+        if byte_size > max_length {
+            warn!(
+                message = "Discarding trap larger than max_length.",
+                max_length = max_length,
+                internal_log_rate_limit = true
+            );
+            continue;
+        }
+
+        let mut trap = match Trap::decode(&buf[..byte_size]) {
+            Ok(trap) => trap,
+            Err(error) => {
+                emit!(SnmpTrapParseError { error, peer_addr });
+                continue;
+            }
+        };
+        // Traps name their agent only when they went through a proxy, or for SNMPv1.
+        trap.agent_address.get_or_insert(peer_addr.ip());
+
+        let mut log = log_namespace.new_log_from_data(trap.into_value(mibs.as_ref()));
+        log_namespace.insert_standard_vector_source_metadata(
+            &mut log,
+            SnmpTrapConfig::NAME,
+            Utc::now(),
+        );
+        log_namespace.insert_source_metadata(
+            SnmpTrapConfig::NAME,
+            &mut log,
+            Some(LegacyKey::InsertIfEmpty(config.host_key())),
+            path!("host"),
+            peer_addr.ip().to_string(),
+        );
+
+        let event = Event::from(log);
+        emit!(EventsReceived {
+            count: 1,
+            byte_size: event.size_of(),
+        });
+
+        tokio::select! {
+            result = out.send_event(event) => {
+                if let Err(error) = result {
+                    emit!(StreamClosedError { error, count: 1 });
+                    break;
+                }
+            }
+            _ = &mut shutdown => break,
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::BTreeSet, time::Duration};
+
+    use tokio::net::UdpSocket;
+    use vector_core::event::Value;
+
+    use super::*;
+    use crate::test_util::{
+        collect_n,
+        components::{assert_source_compliance, SOCKET_HIGH_CARDINALITY_PUSH_SOURCE_TAGS},
+        next_addr,
+    };
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<SnmpTrapConfig>();
+    }
+
+    fn config(address: SocketAddr) -> SnmpTrapConfig {
+        toml::from_str(&format!(r#"address = "{}""#, address)).unwrap()
+    }
+
+    async fn run(config: SnmpTrapConfig, traps: Vec<Vec<u8>>, count: usize) -> Vec<Event> {
+        assert_source_compliance(&SOCKET_HIGH_CARDINALITY_PUSH_SOURCE_TAGS, async {
+            let address = match config.address {
+                SocketListenAddr::SocketAddr(address) => address,
+                SocketListenAddr::SystemdFd(_) => unreachable!(),
+            };
+            let (tx, rx) = SourceSender::new_test();
+            let source = config
+                .build(SourceContext::new_test(tx, None))
+                .await
+                .unwrap();
+            tokio::spawn(source);
+            // Wait for UDP to start listening
+            tokio::time::sleep(Duration::from_millis(100)).await;
+
+            let socket = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+            for trap in traps {
+                socket.send_to(&trap, address).await.unwrap();
+            }
+
+            collect_n(rx, count).await
+        })
+        .await
+    }
+
+    #[tokio::test]
+    async fn receives_v1_traps() {
+        let events = run(config(next_addr()), vec![trap::tests::v1_trap()], 1).await;
+        let log = events[0].as_log();
+
+        assert_eq!(log["version"], "1".into());
+        assert_eq!(log["community"], "public".into());
+        assert_eq!(log["enterprise"], "1.3.6.1.4.1.8072.3.2.10".into());
+        assert_eq!(log["agent_address"], "10.0.0.1".into());
+        assert_eq!(log["generic_trap"], 2.into());
+        assert_eq!(log["specific_trap"], 0.into());
+        assert_eq!(log["uptime"], 51354.into());
+        assert_eq!(log["trap_oid"], "1.3.6.1.6.3.1.1.5.3".into());
+        assert_eq!(log["varbinds[0].oid"], "1.3.6.1.2.1.2.2.1.1.3".into());
+        assert_eq!(log["varbinds[0].value"], 3.into());
+        assert_eq!(log[log_schema().host_key()], "127.0.0.1".into());
+        assert_eq!(log[log_schema().source_type_key()], "snmp_trap".into());
+    }
+
+    #[tokio::test]
+    async fn receives_v2c_traps_from_their_sender() {
+        let events = run(
+            config(next_addr()),
+            vec![trap::tests::v2_trap("1.3.6.1.4.1.8072.2.3.0.1", &[])],
+            1,
+        )
+        .await;
+        let log = events[0].as_log();
+
+        assert_eq!(log["version"], "2c".into());
+        assert_eq!(log["agent_address"], "127.0.0.1".into());
+        assert_eq!(log["enterprise"], "1.3.6.1.4.1.8072.2.3".into());
+        assert_eq!(log["specific_trap"], 1.into());
+        assert_eq!(log["varbinds"], Value::Array(vec![]));
+    }
+
+    #[tokio::test]
+    async fn discards_invalid_traps() {
+        let events = run(
+            config(next_addr()),
+            vec![b"not a trap".to_vec(), trap::tests::v1_trap()],
+            1,
+        )
+        .await;
+
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].as_log()["version"], "1".into());
+    }
+
+    #[tokio::test]
+    async fn translates_oids_with_mibs() {
+        let directory = tempfile::tempdir().unwrap();
+        std::fs::write(
+            directory.path().join("mappings.txt"),
+            "ifIndex 1.3.6.1.2.1.2.2.1.1\nlinkDown 1.3.6.1.6.3.1.1.5.3\n",
+        )
+        .unwrap();
+        let mut config = config(next_addr());
+        config.mib_directory = Some(directory.path().to_owned());
+
+        let events = run(config, vec![trap::tests::v1_trap()], 1).await;
+        let log = events[0].as_log();
+
+        assert_eq!(log["trap_name"], "linkDown".into());
+        assert_eq!(log["varbinds[0].name"], "ifIndex.3".into());
+        assert!(log.get("enterprise_name").is_none());
+    }
+
+    #[tokio::test]
+    async fn includes_vector_namespaced_fields() {
+        let mut config = config(next_addr());
+        config.log_namespace = Some(true);
+
+        let events = run(config.clone(), vec![trap::tests::v1_trap()], 1).await;
+        let log = events[0].as_log();
+        let meta = log.metadata().value();
+
+        assert_eq!(log["version"], "1".into());
+        assert!(log.get(log_schema().host_key()).is_none());
+        assert_eq!(
+            meta.get(path!(SnmpTrapConfig::NAME, "host")).unwrap(),
+            &Value::from("127.0.0.1")
+        );
+        assert_eq!(
+            meta.get(path!("vector", "source_type")).unwrap(),
+            &Value::from(SnmpTrapConfig::NAME)
+        );
+
+        let definition = config.outputs(LogNamespace::Vector)[0]
+            .clone()
+            .log_schema_definition
+            .unwrap();
+        definition.assert_valid_for_event(&events[0]);
+    }
+
+    #[test]
+    fn output_schema_definition_legacy_namespace() {
+        let config = config(next_addr());
+        let definition = config.outputs(LogNamespace::Legacy)[0]
+            .clone()
+            .log_schema_definition
+            .unwrap();
+
+        assert_eq!(
+            definition.log_namespaces(),
+            &BTreeSet::from([LogNamespace::Legacy])
+        );
+        assert!(definition
+            .event_kind()
+            .as_object()
+            .unwrap()
+            .known()
+            .contains_key(&Field::from("host")));
+    }
+
+    #[tokio::test]
+    async fn fails_to_build_with_missing_mib_directory() {
+        let mut config = config(next_addr());
+        config.mib_directory = Some("/nonexistent/mibs".into());
+
+        let (tx, _rx) = SourceSender::new_test();
+        assert!(config
+            .build(SourceContext::new_test(tx, None))
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn stops_on_shutdown() {
+        let (tx, _rx) = SourceSender::new_test();
+        let (trigger, shutdown, _) = ShutdownSignal::new_wired();
+        let mut cx = SourceContext::new_test(tx, None);
+        cx.shutdown = shutdown;
+
+        let source = tokio::spawn(config(next_addr()).build(cx).await.unwrap());
+        drop(trigger);
+
+        assert_eq!(source.await.unwrap(), Ok(()));
+    }
+}
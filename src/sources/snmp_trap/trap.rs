@@ -0,0 +1,595 @@
+//! Decodes SNMPv1 and SNMPv2c trap messages.
+use std::{
+    collections::BTreeMap,
+    net::{IpAddr, Ipv4Addr},
+};
+
+use bytes::Bytes;
+use snafu::{ResultExt, Snafu};
+use vector_core::event::Value;
+
+use super::{
+    ber::{self, tag, BerError, Oid, Reader},
+    mib::Mibs,
+};
+
+/// `sysUpTime.0`, the first variable binding of SNMPv2 traps.
+const SYS_UP_TIME: &[u32] = &[1, 3, 6, 1, 2, 1, 1, 3, 0];
+/// `snmpTrapOID.0`, the second variable binding of SNMPv2 traps.
+const SNMP_TRAP_OID: &[u32] = &[1, 3, 6, 1, 6, 3, 1, 1, 4, 1, 0];
+/// `snmpTrapEnterprise.0`, which carries the enterprise of generic SNMPv2 traps.
+const SNMP_TRAP_ENTERPRISE: &[u32] = &[1, 3, 6, 1, 6, 3, 1, 1, 4, 3, 0];
+/// `snmpTrapAddress.0`, which carries the address of the agent of SNMPv2 traps forwarded by proxies.
+const SNMP_TRAP_ADDRESS: &[u32] = &[1, 3, 6, 1, 6, 3, 18, 1, 3, 0];
+/// `snmpTraps`, under which the generic traps are defined.
+const SNMP_TRAPS: &[u32] = &[1, 3, 6, 1, 6, 3, 1, 1, 5];
+
+/// The generic trap number of traps defined by an enterprise rather than by the standard.
+const ENTERPRISE_SPECIFIC: i64 = 6;
+
+#[derive(Debug, Snafu)]
+pub enum TrapError {
+    #[snafu(display("malformed message: {}", source))]
+    Malformed { source: BerError },
+    #[snafu(display("unsupported SNMP version {}", version))]
+    UnsupportedVersion { version: i64 },
+    #[snafu(display("unsupported PDU type 0x{:02x}, only traps are supported", pdu_type))]
+    UnsupportedPdu { pdu_type: u8 },
+    #[snafu(display("invalid agent address"))]
+    InvalidAgentAddress,
+    #[snafu(display("invalid generic trap {}", generic_trap))]
+    InvalidGenericTrap { generic_trap: i64 },
+    #[snafu(display("missing snmpTrapOID.0 variable binding"))]
+    MissingTrapOid,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) enum Version {
+    V1,
+    V2c,
+}
+
+/// A trap, with the fields of SNMPv1 traps whatever the version it was sent with.
+///
+/// SNMPv2 traps are converted as described by [RFC 3584][rfc3584], so that the same fields
+/// identify traps whatever their version.
+///
+/// [rfc3584]: https://www.rfc-editor.org/rfc/rfc3584#section-3.2
+#[derive(Debug, PartialEq)]
+pub(super) struct Trap {
+    pub(super) version: Version,
+    pub(super) community: Bytes,
+    pub(super) enterprise: Oid,
+    /// The address of the agent, when given by the trap itself.
+    pub(super) agent_address: Option<IpAddr>,
+    pub(super) generic_trap: i64,
+    pub(super) specific_trap: i64,
+    /// The time since the agent was last initialized, in hundredths of a second.
+    pub(super) uptime: Option<u32>,
+    pub(super) trap_oid: Oid,
+    pub(super) varbinds: Vec<VarBind>,
+}
+
+#[derive(Debug, PartialEq)]
+pub(super) struct VarBind {
+    pub(super) oid: Oid,
+    pub(super) value: VarValue,
+}
+
+#[derive(Debug, PartialEq)]
+pub(super) enum VarValue {
+    Integer(i64),
+    OctetString(Bytes),
+    Null,
+    ObjectIdentifier(Oid),
+    IpAddress(Ipv4Addr),
+    Counter32(u32),
+    Gauge32(u32),
+    TimeTicks(u32),
+    Opaque(Bytes),
+    Counter64(u64),
+    NoSuchObject,
+    NoSuchInstance,
+    EndOfMibView,
+}
+
+impl Trap {
+    /// Decodes a trap from the payload of a datagram.
+    pub(super) fn decode(payload: &[u8]) -> Result<Self, TrapError> {
+        let mut message = Reader::new(payload)
+            .read_constructed(tag::SEQUENCE)
+            .context(MalformedSnafu)?;
+        let version = match message.read_integer().context(MalformedSnafu)? {
+            0 => Version::V1,
+            1 => Version::V2c,
+            version => return Err(TrapError::UnsupportedVersion { version }),
+        };
+        let community =
+            Bytes::copy_from_slice(message.read(tag::OCTET_STRING).context(MalformedSnafu)?);
+
+        let (pdu_type, pdu) = message.read_any().context(MalformedSnafu)?;
+        let mut pdu = Reader::new(pdu);
+        match (version, pdu_type) {
+            (Version::V1, tag::TRAP_V1) => decode_v1(community, &mut pdu),
+            (Version::V2c, tag::TRAP_V2) => decode_v2(community, &mut pdu),
+            (_, pdu_type) => Err(TrapError::UnsupportedPdu { pdu_type }),
+        }
+    }
+
+    /// Converts the trap into the value of an event, naming its object identifiers when the
+    /// MIBs define them.
+    pub(super) fn into_value(self, mibs: Option<&Mibs>) -> Value {
+        let mut value = Value::Object(BTreeMap::new());
+        let version = match self.version {
+            Version::V1 => "1",
+            Version::V2c => "2c",
+        };
+        value.insert("version", version);
+        value.insert("community", self.community);
+        value.insert("enterprise", self.enterprise.to_string());
+        if let Some(agent_address) = self.agent_address {
+            value.insert("agent_address", agent_address.to_string());
+        }
+        value.insert("generic_trap", self.generic_trap);
+        value.insert("specific_trap", self.specific_trap);
+        if let Some(uptime) = self.uptime {
+            value.insert("uptime", uptime);
+        }
+        value.insert("trap_oid", self.trap_oid.to_string());
+
+        if let Some(mibs) = mibs {
+            if let Some(name) = mibs.translate(&self.enterprise) {
+                value.insert("enterprise_name", name);
+            }
+            if let Some(name) = mibs.translate(&self.trap_oid) {
+                value.insert("trap_name", name);
+            }
+        }
+
+        let varbinds = self
+            .varbinds
+            .into_iter()
+            .map(|varbind| varbind.into_value(mibs))
+            .collect::<Vec<_>>();
+        value.insert("varbinds", varbinds);
+
+        value
+    }
+}
+
+impl VarBind {
+    fn into_value(self, mibs: Option<&Mibs>) -> Value {
+        let mut value = Value::Object(BTreeMap::new());
+        if let Some(name) = mibs.and_then(|mibs| mibs.translate(&self.oid)) {
+            value.insert("name", name);
+        }
+        value.insert("oid", self.oid.to_string());
+        value.insert("type", self.value.type_name());
+        value.insert("value", self.value.into_value());
+        value
+    }
+}
+
+impl VarValue {
+    fn decode(tag: u8, contents: &[u8]) -> Result<Self, BerError> {
+        Ok(match tag {
+            tag::INTEGER => Self::Integer(ber::decode_integer(contents)?),
+            tag::OCTET_STRING => Self::OctetString(Bytes::copy_from_slice(contents)),
+            tag::NULL => Self::Null,
+            tag::OBJECT_IDENTIFIER => {
+                Self::ObjectIdentifier(ber::decode_object_identifier(contents)?)
+            }
+            tag::IP_ADDRESS => {
+                let octets = <[u8; 4]>::try_from(contents).map_err(|_| BerError::InvalidLength)?;
+                Self::IpAddress(Ipv4Addr::from(octets))
+            }
+            tag::COUNTER32 => Self::Counter32(decode_u32(contents)?),
+            tag::GAUGE32 => Self::Gauge32(decode_u32(contents)?),
+            tag::TIMETICKS => Self::TimeTicks(decode_u32(contents)?),
+            tag::OPAQUE => Self::Opaque(Bytes::copy_from_slice(contents)),
+            tag::COUNTER64 => Self::Counter64(ber::decode_unsigned(contents)?),
+            tag::NO_SUCH_OBJECT => Self::NoSuchObject,
+            tag::NO_SUCH_INSTANCE => Self::NoSuchInstance,
+            tag::END_OF_MIB_VIEW => Self::EndOfMibView,
+            // Values of types outside of the SNMP SMI are kept as they were received.
+            _ => Self::Opaque(Bytes::copy_from_slice(contents)),
+        })
+    }
+
+    const fn type_name(&self) -> &'static str {
+        match self {
+            Self::Integer(_) => "integer",
+            Self::OctetString(_) => "octet_string",
+            Self::Null => "null",
+            Self::ObjectIdentifier(_) => "object_identifier",
+            Self::IpAddress(_) => "ip_address",
+            Self::Counter32(_) => "counter32",
+            Self::Gauge32(_) => "gauge32",
+            Self::TimeTicks(_) => "timeticks",
+            Self::Opaque(_) => "opaque",
+            Self::Counter64(_) => "counter64",
+            Self::NoSuchObject => "no_such_object",
+            Self::NoSuchInstance => "no_such_instance",
+            Self::EndOfMibView => "end_of_mib_view",
+        }
+    }
+
+    fn into_value(self) -> Value {
+        match self {
+            Self::Integer(value) => value.into(),
+            Self::OctetString(value) | Self::Opaque(value) => value.into(),
+            Self::ObjectIdentifier(value) => value.to_string().into(),
+            Self::IpAddress(value) => value.to_string().into(),
+            Self::Counter32(value) | Self::Gauge32(value) | Self::TimeTicks(value) => value.into(),
+            // Integers are signed, so the largest counters are given as strings rather than
+            // wrapping around to negative values.
+            Self::Counter64(value) => {
+                i64::try_from(value).map_or_else(|_| value.to_string().into(), Value::from)
+            }
+            Self::Null | Self::NoSuchObject | Self::NoSuchInstance | Self::EndOfMibView => {
+                Value::Null
+            }
+        }
+    }
+}
+
+/// Decodes an SNMPv1 `Trap-PDU`.
+fn decode_v1(community: Bytes, pdu: &mut Reader<'_>) -> Result<Trap, TrapError> {
+    let enterprise = pdu.read_object_identifier().context(MalformedSnafu)?;
+    let agent_address = <[u8; 4]>::try_from(pdu.read(tag::IP_ADDRESS).context(MalformedSnafu)?)
+        .map_err(|_| TrapError::InvalidAgentAddress)?;
+    let generic_trap = pdu.read_integer().context(MalformedSnafu)?;
+    if !(0..=ENTERPRISE_SPECIFIC).contains(&generic_trap) {
+        return Err(TrapError::InvalidGenericTrap { generic_trap });
+    }
+    let specific_trap = pdu.read_integer().context(MalformedSnafu)?;
+    let uptime =
+        decode_u32(pdu.read(tag::TIMETICKS).context(MalformedSnafu)?).context(MalformedSnafu)?;
+    let varbinds = decode_varbinds(pdu)?;
+
+    // The inverse of the conversion of SNMPv2 traps, from section 3.1 of RFC 3584.
+    let trap_oid = if generic_trap == ENTERPRISE_SPECIFIC {
+        let mut trap_oid = enterprise.clone();
+        trap_oid.0.extend([0, specific_trap as u32]);
+        trap_oid
+    } else {
+        let mut trap_oid = Oid(SNMP_TRAPS.to_vec());
+        trap_oid.0.push((generic_trap + 1) as u32);
+        trap_oid
+    };
+
+    Ok(Trap {
+        version: Version::V1,
+        community,
+        enterprise,
+        agent_address: Some(IpAddr::V4(Ipv4Addr::from(agent_address))),
+        generic_trap,
+        specific_trap,
+        uptime: Some(uptime),
+        trap_oid,
+        varbinds,
+    })
+}
+
+/// Decodes an SNMPv2 `SNMPv2-Trap-PDU`, converting it as described by section 3.2 of RFC 3584.
+fn decode_v2(community: Bytes, pdu: &mut Reader<'_>) -> Result<Trap, TrapError> {
+    // The request ID, error status, and error index don't mean anything for traps.
+    for _ in 0..3 {
+        pdu.read_integer().context(MalformedSnafu)?;
+    }
+    let mut varbinds = decode_varbinds(pdu)?;
+
+    // `sysUpTime.0` and `snmpTrapOID.0` are given as fields, rather than as variable bindings.
+    let mut uptime = None;
+    let mut trap_oid = None;
+    varbinds.retain(|varbind| match &varbind.value {
+        VarValue::TimeTicks(ticks) if varbind.oid.as_slice() == SYS_UP_TIME => {
+            uptime = Some(*ticks);
+            false
+        }
+        VarValue::ObjectIdentifier(oid) if varbind.oid.as_slice() == SNMP_TRAP_OID => {
+            trap_oid = Some(oid.clone());
+            false
+        }
+        _ => true,
+    });
+    let trap_oid = trap_oid.ok_or(TrapError::MissingTrapOid)?;
+
+    let (enterprise, generic_trap, specific_trap) = match trap_oid.as_slice() {
+        [prefix @ .., last] if prefix == SNMP_TRAPS && (1..=6).contains(last) => {
+            let enterprise = varbinds
+                .iter()
+                .find_map(|varbind| match &varbind.value {
+                    VarValue::ObjectIdentifier(oid)
+                        if varbind.oid.as_slice() == SNMP_TRAP_ENTERPRISE =>
+                    {
+                        Some(oid.clone())
+                    }
+                    _ => None,
+                })
+                .unwrap_or_else(|| Oid(SNMP_TRAPS.to_vec()));
+            (enterprise, i64::from(*last) - 1, 0)
+        }
+        [prefix @ .., 0, last] | [prefix @ .., last] => {
+            (Oid(prefix.to_vec()), ENTERPRISE_SPECIFIC, i64::from(*last))
+        }
+        [] => return Err(TrapError::MissingTrapOid),
+    };
+
+    let agent_address = varbinds.iter().find_map(|varbind| match varbind.value {
+        VarValue::IpAddress(address) if varbind.oid.as_slice() == SNMP_TRAP_ADDRESS => {
+            Some(IpAddr::V4(address))
+        }
+        _ => None,
+    });
+
+    Ok(Trap {
+        version: Version::V2c,
+        community,
+        enterprise,
+        agent_address,
+        generic_trap,
+        specific_trap,
+        uptime,
+        trap_oid,
+        varbinds,
+    })
+}
+
+fn decode_varbinds(pdu: &mut Reader<'_>) -> Result<Vec<VarBind>, TrapError> {
+    let mut list = pdu
+        .read_constructed(tag::SEQUENCE)
+        .context(MalformedSnafu)?;
+    let mut varbinds = Vec::new();
+    while !list.is_empty() {
+        let mut varbind = list
+            .read_constructed(tag::SEQUENCE)
+            .context(MalformedSnafu)?;
+        let oid = varbind.read_object_identifier().context(MalformedSnafu)?;
+        let (tag, contents) = varbind.read_any().context(MalformedSnafu)?;
+        let value = VarValue::decode(tag, contents).context(MalformedSnafu)?;
+        varbinds.push(VarBind { oid, value });
+    }
+    Ok(varbinds)
+}
+
+fn decode_u32(contents: &[u8]) -> Result<u32, BerError> {
+    ber::decode_unsigned(contents)
+        .and_then(|value| u32::try_from(value).map_err(|_| BerError::InvalidInteger))
+}
+
+#[cfg(test)]
+pub(super) mod tests {
+    use super::*;
+
+    /// Encodes an element with the given tag and contents.
+    pub(in crate::sources::snmp_trap) fn tlv(tag: u8, contents: &[u8]) -> Vec<u8> {
+        let mut encoded = vec![tag];
+        if contents.len() < 0x80 {
+            encoded.push(contents.len() as u8);
+        } else {
+            encoded.push(0x82);
+            encoded.extend((contents.len() as u16).to_be_bytes());
+        }
+        encoded.extend(contents);
+        encoded
+    }
+
+    fn oid(oid: &str) -> Vec<u8> {
+        let oid = oid.parse::<Oid>().unwrap();
+        let mut contents = vec![(oid.0[0] * 40 + oid.0[1]) as u8];
+        for &subidentifier in &oid.0[2..] {
+            let mut octets = vec![(subidentifier & 0x7f) as u8];
+            let mut rest = subidentifier >> 7;
+            while rest > 0 {
+                octets.push((rest & 0x7f) as u8 | 0x80);
+                rest >>= 7;
+            }
+            contents.extend(octets.into_iter().rev());
+        }
+        tlv(tag::OBJECT_IDENTIFIER, &contents)
+    }
+
+    fn varbind(name: &str, value: Vec<u8>) -> Vec<u8> {
+        tlv(tag::SEQUENCE, &[oid(name), value].concat())
+    }
+
+    /// An SNMPv1 `linkDown` trap from an agent at 10.0.0.1.
+    pub(in crate::sources::snmp_trap) fn v1_trap() -> Vec<u8> {
+        let varbinds = tlv(
+            tag::SEQUENCE,
+            &varbind("1.3.6.1.2.1.2.2.1.1.3", tlv(tag::INTEGER, &[3])),
+        );
+        let pdu = tlv(
+            tag::TRAP_V1,
+            &[
+                oid("1.3.6.1.4.1.8072.3.2.10"),
+                tlv(tag::IP_ADDRESS, &[10, 0, 0, 1]),
+                tlv(tag::INTEGER, &[2]),
+                tlv(tag::INTEGER, &[0]),
+                tlv(tag::TIMETICKS, &[0x00, 0xc8, 0x9a]),
+                varbinds,
+            ]
+            .concat(),
+        );
+        tlv(
+            tag::SEQUENCE,
+            &[
+                tlv(tag::INTEGER, &[0]),
+                tlv(tag::OCTET_STRING, b"public"),
+                pdu,
+            ]
+            .concat(),
+        )
+    }
+
+    /// An SNMPv2c trap, with the given `snmpTrapOID.0` and extra variable bindings.
+    pub(in crate::sources::snmp_trap) fn v2_trap(trap_oid: &str, extra: &[Vec<u8>]) -> Vec<u8> {
+        let mut varbinds = vec![
+            varbind("1.3.6.1.2.1.1.3.0", tlv(tag::TIMETICKS, &[0x01, 0x00])),
+            varbind("1.3.6.1.6.3.1.1.4.1.0", oid(trap_oid)),
+        ];
+        varbinds.extend_from_slice(extra);
+        let pdu = tlv(
+            tag::TRAP_V2,
+            &[
+                tlv(tag::INTEGER, &[0x12, 0x34]),
+                tlv(tag::INTEGER, &[0]),
+                tlv(tag::INTEGER, &[0]),
+                tlv(tag::SEQUENCE, &varbinds.concat()),
+            ]
+            .concat(),
+        );
+        tlv(
+            tag::SEQUENCE,
+            &[
+                tlv(tag::INTEGER, &[1]),
+                tlv(tag::OCTET_STRING, b"private"),
+                pdu,
+            ]
+            .concat(),
+        )
+    }
+
+    #[test]
+    fn decodes_v1_traps() {
+        let trap = Trap::decode(&v1_trap()).unwrap();
+
+        assert_eq!(trap.version, Version::V1);
+        assert_eq!(trap.community, "public");
+        assert_eq!(trap.enterprise.to_string(), "1.3.6.1.4.1.8072.3.2.10");
+        assert_eq!(trap.agent_address, Some("10.0.0.1".parse().unwrap()));
+        assert_eq!(trap.generic_trap, 2);
+        assert_eq!(trap.specific_trap, 0);
+        assert_eq!(trap.uptime, Some(51354));
+        assert_eq!(trap.trap_oid.to_string(), "1.3.6.1.6.3.1.1.5.3");
+        assert_eq!(
+            trap.varbinds,
+            vec![VarBind {
+                oid: "1.3.6.1.2.1.2.2.1.1.3".parse().unwrap(),
+                value: VarValue::Integer(3),
+            }]
+        );
+    }
+
+    #[test]
+    fn decodes_v2_enterprise_specific_traps() {
+        let trap = Trap::decode(&v2_trap(
+            "1.3.6.1.4.1.8072.2.3.0.1",
+            &[varbind(
+                "1.3.6.1.4.1.8072.2.3.2.1",
+                tlv(
+                    tag::COUNTER64,
+                    &[0x00, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff],
+                ),
+            )],
+        ))
+        .unwrap();
+
+        assert_eq!(trap.version, Version::V2c);
+        assert_eq!(trap.community, "private");
+        assert_eq!(trap.enterprise.to_string(), "1.3.6.1.4.1.8072.2.3");
+        assert_eq!(trap.agent_address, None);
+        assert_eq!(trap.generic_trap, 6);
+        assert_eq!(trap.specific_trap, 1);
+        assert_eq!(trap.uptime, Some(256));
+        assert_eq!(
+            trap.varbinds,
+            vec![VarBind {
+                oid: "1.3.6.1.4.1.8072.2.3.2.1".parse().unwrap(),
+                value: VarValue::Counter64(u64::MAX),
+            }]
+        );
+    }
+
+    #[test]
+    fn decodes_v2_generic_traps() {
+        let trap = Trap::decode(&v2_trap(
+            "1.3.6.1.6.3.1.1.5.4",
+            &[
+                varbind("1.3.6.1.6.3.1.1.4.3.0", oid("1.3.6.1.4.1.9")),
+                varbind(
+                    "1.3.6.1.6.3.18.1.3.0",
+                    tlv(tag::IP_ADDRESS, &[192, 168, 1, 1]),
+                ),
+            ],
+        ))
+        .unwrap();
+
+        assert_eq!(trap.enterprise.to_string(), "1.3.6.1.4.1.9");
+        assert_eq!(trap.agent_address, Some("192.168.1.1".parse().unwrap()));
+        assert_eq!(trap.generic_trap, 3);
+        assert_eq!(trap.specific_trap, 0);
+    }
+
+    #[test]
+    fn rejects_v2_traps_without_trap_oid() {
+        let pdu = tlv(
+            tag::TRAP_V2,
+            &[
+                tlv(tag::INTEGER, &[1]),
+                tlv(tag::INTEGER, &[0]),
+                tlv(tag::INTEGER, &[0]),
+                tlv(tag::SEQUENCE, &[]),
+            ]
+            .concat(),
+        );
+        let message = tlv(
+            tag::SEQUENCE,
+            &[
+                tlv(tag::INTEGER, &[1]),
+                tlv(tag::OCTET_STRING, b"public"),
+                pdu,
+            ]
+            .concat(),
+        );
+
+        assert!(matches!(
+            Trap::decode(&message),
+            Err(TrapError::MissingTrapOid)
+        ));
+    }
+
+    #[test]
+    fn rejects_snmpv3() {
+        let message = tlv(tag::SEQUENCE, &tlv(tag::INTEGER, &[3]));
+        assert!(matches!(
+            Trap::decode(&message),
+            Err(TrapError::UnsupportedVersion { version: 3 })
+        ));
+    }
+
+    #[test]
+    fn rejects_truncated_messages() {
+        let message = v1_trap();
+        assert!(matches!(
+            Trap::decode(&message[..message.len() - 1]),
+            Err(TrapError::Malformed { .. })
+        ));
+    }
+
+    #[test]
+    fn converts_into_values() {
+        let value = Trap::decode(&v1_trap()).unwrap().into_value(None);
+
+        assert_eq!(value.get("version"), Some(&Value::from("1")));
+        assert_eq!(value.get("agent_address"), Some(&Value::from("10.0.0.1")));
+        assert_eq!(value.get("generic_trap"), Some(&Value::from(2)));
+        assert_eq!(
+            value.get("varbinds[0].oid"),
+            Some(&Value::from("1.3.6.1.2.1.2.2.1.1.3"))
+        );
+        assert_eq!(value.get("varbinds[0].type"), Some(&Value::from("integer")));
+        assert_eq!(value.get("varbinds[0].value"), Some(&Value::from(3)));
+        assert_eq!(value.get("varbinds[0].name"), None);
+    }
+
+    #[test]
+    fn gives_large_counters_as_strings() {
+        assert_eq!(
+            VarValue::Counter64(u64::MAX).into_value(),
+            Value::from(u64::MAX.to_string())
+        );
+        assert_eq!(VarValue::Counter64(42).into_value(), Value::from(42));
+    }
+}
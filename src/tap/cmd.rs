@@ -4,12 +4,13 @@ use colored::{ColoredString, Colorize};
 use tokio_stream::StreamExt;
 use url::Url;
 use vector_api_client::{
-    connect_subscription_client,
+    connect_subscription_client_with_options,
     gql::{
+        event_lineage_subscription::{EventLineageSubscriptionEventLineage, LineageAction},
         output_events_by_component_id_patterns_subscription::OutputEventsByComponentIdPatternsSubscriptionOutputEventsByComponentIdPatterns,
-        TapEncodingFormat, TapSubscriptionExt,
+        TapEncodingFormat, TapSubscriptionExt, TapSubscriptionOptions,
     },
-    Client,
+    Client, SubscriptionClient,
 };
 
 use crate::{
@@ -34,7 +35,10 @@ pub(crate) async fn cmd(opts: &super::Opts, mut signal_rx: SignalRx) -> exitcode
 
     // Return early with instructions for enabling the API if the endpoint isn't reachable
     // via a healthcheck.
-    if Client::new_with_healthcheck(url.clone()).await.is_none() {
+    if Client::new_with_healthcheck(url.clone(), &opts.connect)
+        .await
+        .is_none()
+    {
         return exitcode::UNAVAILABLE;
     }
 
@@ -85,24 +89,33 @@ async fn run(
     outputs_patterns: Vec<String>,
     formatter: EventFormatter,
 ) -> exitcode::ExitCode {
-    let subscription_client = match connect_subscription_client(url).await {
-        Ok(c) => c,
-        Err(e) => {
-            #[allow(clippy::print_stderr)]
-            {
-                eprintln!("[tap] Couldn't connect to Vector API via WebSockets: {}", e);
+    let subscription_client =
+        match connect_subscription_client_with_options(url, &opts.connect).await {
+            Ok(c) => c,
+            Err(e) => {
+                #[allow(clippy::print_stderr)]
+                {
+                    eprintln!("[tap] Couldn't connect to Vector API via WebSockets: {}", e);
+                }
+                return exitcode::UNAVAILABLE;
             }
-            return exitcode::UNAVAILABLE;
-        }
-    };
+        };
+
+    if let Some(condition) = &opts.trace {
+        return trace(&subscription_client, condition.clone(), opts.limit as i64).await;
+    }
 
     tokio::pin! {
         let stream = subscription_client.output_events_by_component_id_patterns_subscription(
             outputs_patterns,
             opts.inputs_of.clone(),
             opts.format,
-            opts.limit as i64,
-            opts.interval as i64,
+            TapSubscriptionOptions {
+                limit: opts.limit as i64,
+                interval: opts.interval as i64,
+                filter: opts.filter.clone(),
+                fields: opts.fields.clone(),
+            },
         );
     };
 
@@ -112,6 +125,13 @@ async fn run(
     loop {
         let message = stream.next().await;
         if let Some(Some(res)) = message {
+            // The subscription is rejected, such as for an invalid filter.
+            if let Some(errors) = res.errors.filter(|errors| !errors.is_empty()) {
+                for error in errors {
+                    eprintln!("[tap] {}", error);
+                }
+                return exitcode::USAGE;
+            }
             if let Some(d) = res.data {
                 for tap_event in d.output_events_by_component_id_patterns.iter() {
                     match tap_event {
@@ -138,6 +158,57 @@ async fn run(
     }
 }
 
+/// Prints what components do to the events traced through the topology, as they do it.
+async fn trace(
+    subscription_client: &SubscriptionClient,
+    condition: String,
+    limit: i64,
+) -> exitcode::ExitCode {
+    let mut stream = subscription_client.event_lineage_subscription(condition, limit);
+
+    #[allow(clippy::print_stdout)]
+    #[allow(clippy::print_stderr)]
+    loop {
+        let message = stream.next().await;
+        if let Some(Some(res)) = message {
+            // The subscription is rejected, such as for an invalid condition.
+            if let Some(errors) = res.errors.filter(|errors| !errors.is_empty()) {
+                for error in errors {
+                    eprintln!("[tap] {}", error);
+                }
+                return exitcode::USAGE;
+            }
+            if let Some(d) = res.data {
+                println!("{}", format_lineage_record(&d.event_lineage));
+            }
+        } else {
+            return exitcode::TEMPFAIL;
+        }
+    }
+}
+
+fn format_lineage_record(record: &EventLineageSubscriptionEventLineage) -> String {
+    let action = match &record.action {
+        LineageAction::INGESTED => "ingested",
+        LineageAction::PASSED => "passed",
+        LineageAction::MUTATED => "mutated",
+        LineageAction::DROPPED => "dropped",
+        LineageAction::DELIVERED => "delivered",
+        LineageAction::FAILED => "failed",
+        LineageAction::Other(action) => action.as_str(),
+    };
+    let output = record
+        .output
+        .as_ref()
+        .map(|output| format!(" to output {:?}", output))
+        .unwrap_or_default();
+
+    format!(
+        "[trace {}] {} {:?}: {}{}",
+        record.trace_id, record.component_kind, record.component_id, action, output
+    )
+}
+
 #[derive(Clone)]
 struct EventFormatter {
     meta: bool,
@@ -3,7 +3,7 @@ mod cmd;
 use clap::Parser;
 pub(crate) use cmd::cmd;
 use url::Url;
-use vector_api_client::gql::TapEncodingFormat;
+use vector_api_client::{gql::TapEncodingFormat, ConnectOptions};
 
 #[derive(Parser, Debug, Clone)]
 #[command(rename_all = "kebab-case")]
@@ -36,6 +36,18 @@ pub struct Opts {
     #[arg(value_delimiter(','), long)]
     inputs_of: Vec<String>,
 
+    /// VRL condition that events must match, evaluated by Vector before sampling (e.g. '.tenant == "acme"')
+    #[arg(long)]
+    filter: Option<String>,
+
+    /// VRL condition selecting events as sources ingest them, to trace what each component does to them instead of tapping events (e.g. '.tenant == "acme"'). At most `--limit` events are traced
+    #[arg(long)]
+    trace: Option<String>,
+
+    /// Fields of log events to output (comma-separated; e.g. 'message,http.status')
+    #[arg(value_delimiter(','), long)]
+    fields: Vec<String>,
+
     /// Quiet output includes only events
     #[arg(short, long)]
     quiet: bool,
@@ -47,4 +59,7 @@ pub struct Opts {
     /// Whether to reconnect if the underlying Vector API connection drops. By default, tap will attempt to reconnect if the connection drops.
     #[arg(short, long)]
     no_reconnect: bool,
+
+    #[command(flatten)]
+    connect: ConnectOptions,
 }
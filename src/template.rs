@@ -5,7 +5,7 @@ use chrono::{
     format::{strftime::StrftimeItems, Item},
     Utc,
 };
-use lookup::lookup_v2::parse_target_path;
+use lookup::lookup_v2::{parse_target_path, OwnedTargetPath};
 use once_cell::sync::Lazy;
 use regex::Regex;
 use snafu::Snafu;
@@ -24,10 +24,15 @@ pub enum TemplateParseError {
     StrftimeError,
     #[snafu(display("Invalid field path in template {:?} (see https://vector.dev/docs/reference/configuration/template-syntax/)", path))]
     InvalidPathSyntax { path: String },
+    #[snafu(display("Unknown filter in template {:?} (see https://vector.dev/docs/reference/configuration/template-syntax/)", filter))]
+    UnknownFilter { filter: String },
+    #[snafu(display("Invalid argument {:?} for the {:?} filter in template (see https://vector.dev/docs/reference/configuration/template-syntax/)", argument, filter))]
+    InvalidFilterArgument { filter: String, argument: String },
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, Snafu)]
 pub enum TemplateRenderingError {
+    /// The fields of references without a fallback, and of their fallback fields, are missing.
     #[snafu(display("Missing fields on event: {:?}", missing_keys))]
     MissingKeys { missing_keys: Vec<String> },
 }
@@ -101,7 +106,7 @@ impl TryFrom<Cow<'_, str>> for Template {
                     Part::Literal(lit) => lit.len(),
                     // We can't really put a useful number here, assume at least one byte will come
                     // from the input event.
-                    Part::Reference(_path) => 1,
+                    Part::Reference(_reference) => 1,
                     Part::Strftime(parsed) => parsed.reserve_size(),
                 })
                 .sum();
@@ -157,21 +162,10 @@ impl Template {
             match part {
                 Part::Literal(lit) => out.push_str(lit),
                 Part::Strftime(items) => out.push_str(&render_timestamp(items, event)),
-                Part::Reference(key) => {
-                    out.push_str(
-                        &match event {
-                            EventRef::Log(log) => log.get(&**key).map(Value::to_string_lossy),
-                            EventRef::Metric(metric) => {
-                                render_metric_field(key, metric).map(Cow::Borrowed)
-                            }
-                            EventRef::Trace(trace) => trace.get(&key).map(Value::to_string_lossy),
-                        }
-                        .unwrap_or_else(|| {
-                            missing_keys.push(key.to_owned());
-                            Cow::Borrowed("")
-                        }),
-                    );
-                }
+                Part::Reference(reference) => match reference.render(event) {
+                    Ok(value) => out.push_str(&value),
+                    Err(missing) => missing_keys.extend(missing),
+                },
             }
         }
         if missing_keys.is_empty() {
@@ -181,21 +175,37 @@ impl Template {
         }
     }
 
+    /// Returns the fields referenced by the template, including fallback fields.
     pub fn get_fields(&self) -> Option<Vec<String>> {
         let parts: Vec<_> = self
             .parts
             .iter()
             .filter_map(|part| {
                 if let Part::Reference(r) = part {
-                    Some(r.to_owned())
+                    Some(r.fields())
                 } else {
                     None
                 }
             })
+            .flatten()
             .collect();
         (!parts.is_empty()).then_some(parts)
     }
 
+    /// Returns the paths of the fields the template can't be rendered without, which are those
+    /// referenced without a fallback.
+    pub fn required_fields(&self) -> Vec<OwnedTargetPath> {
+        self.parts
+            .iter()
+            .filter_map(|part| match part {
+                Part::Reference(reference) if !reference.has_fallback() => {
+                    Some(reference.field.path.clone())
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
     pub fn get_ref(&self) -> &str {
         &self.src
     }
@@ -218,7 +228,219 @@ enum Part {
     /// A literal piece of text containing a time format string.
     Strftime(ParsedStrftime),
     /// A reference to the source event, to be copied from the relevant field or tag.
-    Reference(String),
+    Reference(Reference),
+}
+
+/// A field or tag of the source event, with the filters applied to its value.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+struct Reference {
+    field: Field,
+    filters: Vec<Filter>,
+}
+
+impl Reference {
+    /// Renders the value of the field, or returns the fields that are missing.
+    fn render<'a>(&self, event: EventRef<'a>) -> Result<Cow<'a, str>, Vec<String>> {
+        let mut missing_keys = Vec::new();
+        let mut value = self.field.render(event);
+        if value.is_none() {
+            missing_keys.push(self.field.key.clone());
+        }
+
+        // Filters apply to values, so those before a fallback don't apply to it.
+        for filter in &self.filters {
+            value = match (value, filter) {
+                (None, Filter::Default(Fallback::Literal(literal))) => {
+                    Some(Cow::Owned(literal.clone()))
+                }
+                (None, Filter::Default(Fallback::Field(field))) => {
+                    let fallback = field.render(event);
+                    if fallback.is_none() {
+                        missing_keys.push(field.key.clone());
+                    }
+                    fallback
+                }
+                (Some(value), filter) => Some(filter.apply(value)),
+                (None, _) => None,
+            };
+        }
+
+        value.ok_or(missing_keys)
+    }
+
+    fn has_fallback(&self) -> bool {
+        self.filters
+            .iter()
+            .any(|filter| matches!(filter, Filter::Default(_)))
+    }
+
+    fn fields(&self) -> impl Iterator<Item = String> + '_ {
+        std::iter::once(self.field.key.clone()).chain(self.filters.iter().filter_map(|filter| {
+            match filter {
+                Filter::Default(Fallback::Field(field)) => Some(field.key.clone()),
+                _ => None,
+            }
+        }))
+    }
+}
+
+/// A field of a log or trace, or the name, namespace or a tag of a metric.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+struct Field {
+    /// The path of the field, as written in the template.
+    key: String,
+    path: OwnedTargetPath,
+}
+
+impl Field {
+    fn parse(key: &str) -> Result<Self, TemplateParseError> {
+        let path = parse_target_path(key).map_err(|_| TemplateParseError::InvalidPathSyntax {
+            path: key.to_owned(),
+        })?;
+        Ok(Self {
+            key: key.to_owned(),
+            path,
+        })
+    }
+
+    fn render<'a>(&self, event: EventRef<'a>) -> Option<Cow<'a, str>> {
+        match event {
+            EventRef::Log(log) => log.get(&self.path).map(Value::to_string_lossy),
+            EventRef::Metric(metric) => render_metric_field(&self.key, metric).map(Cow::Borrowed),
+            EventRef::Trace(trace) => trace.get(&self.key).map(Value::to_string_lossy),
+        }
+    }
+}
+
+/// A filter applied to the value of a reference, such as `{{ tenant | lowercase }}`.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+enum Filter {
+    /// Renders a fallback if the field is missing: `default: "unknown"` or `default: other.field`.
+    Default(Fallback),
+    /// Converts the value to lowercase.
+    Lowercase,
+    /// Converts the value to uppercase.
+    Uppercase,
+    /// Converts the value to lowercase ASCII letters and digits, separated by dashes.
+    Slugify,
+    /// Keeps the first characters of the value: `truncate: 16`.
+    Truncate(usize),
+}
+
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+enum Fallback {
+    Literal(String),
+    Field(Field),
+}
+
+impl Filter {
+    fn parse(src: &str) -> Result<Self, TemplateParseError> {
+        let (name, argument) = match src.split_once(':') {
+            Some((name, argument)) => (name.trim(), Some(argument.trim())),
+            None => (src.trim(), None),
+        };
+        let invalid_argument = || TemplateParseError::InvalidFilterArgument {
+            filter: name.to_owned(),
+            argument: argument.unwrap_or_default().to_owned(),
+        };
+
+        match (name, argument) {
+            ("default", Some(argument)) => Ok(Self::Default(
+                if let Some(literal) = parse_string_literal(argument) {
+                    Fallback::Literal(literal.ok_or_else(invalid_argument)?)
+                } else {
+                    Fallback::Field(Field::parse(argument)?)
+                },
+            )),
+            ("lowercase", None) => Ok(Self::Lowercase),
+            ("uppercase", None) => Ok(Self::Uppercase),
+            ("slugify", None) => Ok(Self::Slugify),
+            ("truncate", Some(argument)) => argument
+                .parse()
+                .map(Self::Truncate)
+                .map_err(|_| invalid_argument()),
+            ("default" | "lowercase" | "uppercase" | "slugify" | "truncate", _) => {
+                Err(invalid_argument())
+            }
+            _ => Err(TemplateParseError::UnknownFilter {
+                filter: name.to_owned(),
+            }),
+        }
+    }
+
+    fn apply<'a>(&self, value: Cow<'a, str>) -> Cow<'a, str> {
+        match self {
+            Self::Default(_) => value,
+            Self::Lowercase => Cow::Owned(value.to_lowercase()),
+            Self::Uppercase => Cow::Owned(value.to_uppercase()),
+            Self::Slugify => {
+                let mut slug = String::with_capacity(value.len());
+                for c in value.chars() {
+                    if c.is_ascii_alphanumeric() {
+                        slug.push(c.to_ascii_lowercase());
+                    } else if !slug.is_empty() && !slug.ends_with('-') {
+                        slug.push('-');
+                    }
+                }
+                if slug.ends_with('-') {
+                    slug.pop();
+                }
+                Cow::Owned(slug)
+            }
+            Self::Truncate(length) => {
+                let end = value.char_indices().nth(*length).map(|(end, _)| end);
+                match (value, end) {
+                    (Cow::Borrowed(value), Some(end)) => Cow::Borrowed(&value[..end]),
+                    (Cow::Owned(mut value), Some(end)) => {
+                        value.truncate(end);
+                        Cow::Owned(value)
+                    }
+                    (value, None) => value,
+                }
+            }
+        }
+    }
+}
+
+/// Parses a double-quoted string, in which `\"` and `\\` are escaped. Returns `None` if the
+/// source isn't quoted, and `Some(None)` if it's invalid.
+fn parse_string_literal(src: &str) -> Option<Option<String>> {
+    let inner = src.strip_prefix('"')?;
+    let mut literal = String::with_capacity(inner.len());
+    let mut chars = inner.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => return Some(chars.as_str().is_empty().then_some(literal)),
+            '\\' => match chars.next() {
+                Some(escaped @ ('"' | '\\')) => literal.push(escaped),
+                _ => return Some(None),
+            },
+            c => literal.push(c),
+        }
+    }
+    Some(None)
+}
+
+/// Splits the source of a reference at the `|` separating filters, outside of quoted strings.
+fn split_filters(src: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut start = 0;
+    let mut quoted = false;
+    let mut escaped = false;
+    for (i, c) in src.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if quoted => escaped = true,
+            '"' => quoted = !quoted,
+            '|' if !quoted => {
+                parts.push(&src[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(&src[start..]);
+    parts
 }
 
 // Wrap the parsed time formatter in order to provide `impl Hash` and some convenience functions.
@@ -301,15 +523,11 @@ fn parse_template(src: &str) -> Result<Vec<Part>, TemplateParseError> {
             parts.push(parse_literal(&src[last_end..all.start()])?);
         }
 
-        let path = cap[1].trim().to_owned();
-
-        // This checks the syntax, but doesn't yet store it for use later
-        // see: https://github.com/vectordotdev/vector/issues/14864
-        if parse_target_path(&path).is_err() {
-            return Err(TemplateParseError::InvalidPathSyntax { path });
-        }
+        let mut sources = split_filters(&cap[1]).into_iter();
+        let field = Field::parse(sources.next().unwrap_or_default().trim())?;
+        let filters = sources.map(Filter::parse).collect::<Result<_, _>>()?;
 
-        parts.push(Part::Reference(path));
+        parts.push(Part::Reference(Reference { field, filters }));
         last_end = all.end();
     }
     if src.len() > last_end {
@@ -373,6 +591,25 @@ mod tests {
         assert_eq!(f4, None);
     }
 
+    #[test]
+    fn required_fields() {
+        let template =
+            Template::try_from("{{ foo }}-{{ bar | default: baz }}-{{ qux.quux | uppercase }}")
+                .unwrap();
+
+        assert_eq!(
+            template.required_fields(),
+            vec![
+                parse_target_path("foo").unwrap(),
+                parse_target_path("qux.quux").unwrap()
+            ]
+        );
+        assert!(Template::try_from("nofield")
+            .unwrap()
+            .required_fields()
+            .is_empty());
+    }
+
     #[test]
     fn is_dynamic() {
         assert!(Template::try_from("/kube-demo/%F").unwrap().is_dynamic());
@@ -610,6 +847,114 @@ mod tests {
         .with_timestamp(Some(Utc.ymd(2002, 3, 4).and_hms(5, 6, 7)))
     }
 
+    #[test]
+    fn render_log_default() {
+        let mut event = Event::Log(LogEvent::from("hello world"));
+        event.as_mut_log().insert("fallback", "other");
+        let template = Template::try_from(
+            r#"{{ tenant | default: "unknown" }}-{{ tenant | default: fallback }}-{{ tenant | default: "a \"|\" b" }}"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            Ok(Bytes::from(r#"unknown-other-a "|" b"#)),
+            template.render(&event)
+        );
+    }
+
+    #[test]
+    fn render_log_filters() {
+        let mut event = Event::Log(LogEvent::from("hello world"));
+        event.as_mut_log().insert("tenant", "Acme Corp.");
+        event.as_mut_log().insert("nested.name", "Éclair");
+        let template = Template::try_from(
+            "{{ tenant | lowercase }}/{{ tenant | uppercase }}/{{ tenant | slugify }}/{{ nested.name | truncate: 2 }}/{{ nested.name | truncate: 10 }}",
+        )
+        .unwrap();
+
+        assert_eq!(
+            Ok(Bytes::from("acme corp./ACME CORP./acme-corp/Éc/Éclair")),
+            template.render(&event)
+        );
+    }
+
+    #[test]
+    fn render_log_filters_apply_to_fallbacks() {
+        let event = Event::Log(LogEvent::from("hello world"));
+        let template =
+            Template::try_from(r#"{{ tenant | uppercase | default: "Unknown" | lowercase }}"#)
+                .unwrap();
+
+        assert_eq!(Ok(Bytes::from("unknown")), template.render(&event));
+    }
+
+    #[test]
+    fn render_log_missing_fallback_fields() {
+        let event = Event::Log(LogEvent::from("hello world"));
+        let template =
+            Template::try_from("{{ tenant | default: org.id | default: org.name }}").unwrap();
+
+        assert_eq!(
+            Err(TemplateRenderingError::MissingKeys {
+                missing_keys: vec![
+                    "tenant".to_string(),
+                    "org.id".to_string(),
+                    "org.name".to_string()
+                ]
+            }),
+            template.render(&event)
+        );
+        assert_eq!(
+            template.get_fields(),
+            Some(vec![
+                "tenant".to_string(),
+                "org.id".to_string(),
+                "org.name".to_string()
+            ])
+        );
+    }
+
+    #[test]
+    fn render_metric_with_default() {
+        let metric = sample_metric();
+        let template =
+            Template::try_from(r#"{{ tags.tenant | default: "none" }}-{{ name | uppercase }}"#)
+                .unwrap();
+
+        assert_eq!(Ok(Bytes::from("none-A-COUNTER")), template.render(&metric));
+    }
+
+    #[test]
+    fn filter_errors() {
+        assert_eq!(
+            Template::try_from("{{ foo | capitalize }}").unwrap_err(),
+            TemplateParseError::UnknownFilter {
+                filter: "capitalize".to_string()
+            }
+        );
+        assert_eq!(
+            Template::try_from("{{ foo | truncate: many }}").unwrap_err(),
+            TemplateParseError::InvalidFilterArgument {
+                filter: "truncate".to_string(),
+                argument: "many".to_string()
+            }
+        );
+        assert_eq!(
+            Template::try_from("{{ foo | default }}").unwrap_err(),
+            TemplateParseError::InvalidFilterArgument {
+                filter: "default".to_string(),
+                argument: "".to_string()
+            }
+        );
+        assert_eq!(
+            Template::try_from(r#"{{ foo | default: "unterminated }}"#).unwrap_err(),
+            TemplateParseError::InvalidFilterArgument {
+                filter: "default".to_string(),
+                argument: r#""unterminated"#.to_string()
+            }
+        );
+    }
+
     #[test]
     fn strftime_error() {
         assert_eq!(
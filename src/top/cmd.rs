@@ -3,7 +3,7 @@ use std::time::Duration;
 use futures_util::future::join_all;
 use tokio::sync::oneshot;
 use url::Url;
-use vector_api_client::{connect_subscription_client, Client};
+use vector_api_client::{connect_subscription_client_with_options, Client};
 
 use super::{
     dashboard::{init_dashboard, is_tty},
@@ -37,7 +37,7 @@ pub async fn cmd(opts: &super::Opts) -> exitcode::ExitCode {
     });
 
     // Create a new API client for connecting to the local/remote Vector instance.
-    let client = match Client::new_with_healthcheck(url.clone()).await {
+    let client = match Client::new_with_healthcheck(url.clone(), &opts.connect).await {
         Some(client) => client,
         None => return exitcode::UNAVAILABLE,
     };
@@ -73,13 +73,16 @@ pub async fn cmd(opts: &super::Opts) -> exitcode::ExitCode {
             };
             let _ = tx.send(EventType::InitializeState(state)).await;
 
-            let subscription_client = match connect_subscription_client(ws_url.clone()).await {
-                Ok(c) => c,
-                Err(_) => {
-                    tokio::time::sleep(Duration::from_millis(RECONNECT_DELAY)).await;
-                    continue;
-                }
-            };
+            let subscription_client =
+                match connect_subscription_client_with_options(ws_url.clone(), &opts_clone.connect)
+                    .await
+                {
+                    Ok(c) => c,
+                    Err(_) => {
+                        tokio::time::sleep(Duration::from_millis(RECONNECT_DELAY)).await;
+                        continue;
+                    }
+                };
 
             // Subscribe to updated metrics
             let finished =
@@ -1,4 +1,4 @@
-use std::io::stdout;
+use std::{cmp::Ordering, io::stdout};
 
 use crossterm::{
     cursor::Show,
@@ -104,17 +104,330 @@ fn format_metric(total: i64, throughput: i64, human_metrics: bool) -> String {
     }
 }
 
-const NUM_COLUMNS: usize = 8;
-static HEADER: [&str; NUM_COLUMNS] = [
-    "ID",
-    "Output",
-    "Kind",
-    "Type",
-    "Events In",
-    "Events Out",
-    "Bytes",
-    "Errors",
-];
+/// Format the share of a CPU core used by a component, returning `--` if unused
+fn format_cpu_usage(cpu_usage: f64) -> String {
+    if cpu_usage > 0.0 {
+        format!("{:.2}%", cpu_usage)
+    } else {
+        "--".into()
+    }
+}
+
+/// Format a count, humanized or with thousands separation
+fn format_count(n: i64, human_metrics: bool) -> String {
+    if human_metrics {
+        n.human_format()
+    } else {
+        n.thousands_format()
+    }
+}
+
+/// Format the share of a buffer in use, returning `--` if the buffer is unlimited
+fn format_buffer_usage(usage: Option<f64>) -> String {
+    match usage {
+        Some(usage) => format!("{:.1}%", usage),
+        None => "--".into(),
+    }
+}
+
+/// The views of the components, switched between with the tab key
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum View {
+    Components,
+    Buffers,
+    Errors,
+    Outputs,
+}
+
+impl Default for View {
+    fn default() -> Self {
+        View::Components
+    }
+}
+
+impl View {
+    const fn title(self) -> &'static str {
+        match self {
+            View::Components => "Components",
+            View::Buffers => "Buffers",
+            View::Errors => "Errors",
+            View::Outputs => "Outputs",
+        }
+    }
+
+    const fn next(self) -> Self {
+        match self {
+            View::Components => View::Buffers,
+            View::Buffers => View::Errors,
+            View::Errors => View::Outputs,
+            View::Outputs => View::Components,
+        }
+    }
+
+    /// The header and the width, as a percentage, of each column
+    const fn columns(self) -> &'static [(&'static str, u16)] {
+        match self {
+            View::Components => &[
+                ("ID", 15),
+                ("Output", 10),
+                ("Kind", 10),
+                ("Type", 10),
+                ("Events In", 10),
+                ("Events Out", 10),
+                ("Bytes", 10),
+                ("Errors", 8),
+                ("CPU", 7),
+            ],
+            View::Buffers => &[
+                ("ID", 20),
+                ("Type", 15),
+                ("Events", 15),
+                ("Bytes", 15),
+                ("Usage", 15),
+                ("Discarded", 15),
+            ],
+            View::Errors => &[
+                ("ID", 25),
+                ("Kind", 15),
+                ("Type", 15),
+                ("Errors", 20),
+                ("Errors/s", 20),
+            ],
+            View::Outputs => &[
+                ("ID", 25),
+                ("Output", 20),
+                ("Type", 15),
+                ("Events Out", 20),
+                ("Events/s", 15),
+            ],
+        }
+    }
+
+    /// The lines of the components matching the filter, in the order of the components
+    fn lines(self, state: &state::State, filter: &str, human_metrics: bool) -> Vec<Line> {
+        let components = state
+            .components
+            .values()
+            .filter(|r| r.key.id().contains(filter));
+
+        match self {
+            View::Components => components
+                .map(|r| Line {
+                    cells: vec![
+                        r.key.id().to_string(),
+                        (!r.has_displayable_outputs())
+                            .then_some("--")
+                            .unwrap_or_default()
+                            .to_string(),
+                        r.kind.clone(),
+                        r.component_type.clone(),
+                        format_metric(
+                            r.received_events_total,
+                            r.received_events_throughput_sec,
+                            human_metrics,
+                        ),
+                        format_metric(
+                            r.sent_events_total,
+                            r.sent_events_throughput_sec,
+                            human_metrics,
+                        ),
+                        format_metric(
+                            r.processed_bytes_total,
+                            r.processed_bytes_throughput_sec,
+                            human_metrics,
+                        ),
+                        format_count(r.errors, human_metrics),
+                        format_cpu_usage(r.cpu_usage),
+                    ],
+                    sort_values: vec![
+                        SortValue::from(r.key.id()),
+                        SortValue::from(""),
+                        SortValue::from(r.kind.as_str()),
+                        SortValue::from(r.component_type.as_str()),
+                        SortValue::from(r.received_events_total),
+                        SortValue::from(r.sent_events_total),
+                        SortValue::from(r.processed_bytes_total),
+                        SortValue::from(r.errors),
+                        SortValue::Number(r.cpu_usage),
+                    ],
+                    outputs: r
+                        .outputs
+                        .iter()
+                        .filter(|_| r.has_displayable_outputs())
+                        .map(|(id, output)| {
+                            let mut cells = vec![String::new(); View::Components.columns().len()];
+                            cells[1] = id.clone();
+                            cells[5] = format_metric(
+                                output.sent_events_total,
+                                output.sent_events_throughput_sec,
+                                human_metrics,
+                            );
+                            cells
+                        })
+                        .collect(),
+                })
+                .collect(),
+            View::Buffers => components
+                .filter(|r| r.kind == "sink")
+                .map(|r| Line {
+                    cells: vec![
+                        r.key.id().to_string(),
+                        r.component_type.clone(),
+                        format_count(r.buffer.events, human_metrics),
+                        r.buffer.byte_size.human_format_bytes(),
+                        format_buffer_usage(r.buffer.usage()),
+                        format_count(r.buffer.discarded_events_total, human_metrics),
+                    ],
+                    sort_values: vec![
+                        SortValue::from(r.key.id()),
+                        SortValue::from(r.component_type.as_str()),
+                        SortValue::from(r.buffer.events),
+                        SortValue::from(r.buffer.byte_size),
+                        SortValue::Number(r.buffer.usage().unwrap_or_default()),
+                        SortValue::from(r.buffer.discarded_events_total),
+                    ],
+                    outputs: Vec::new(),
+                })
+                .collect(),
+            View::Errors => components
+                .map(|r| Line {
+                    cells: vec![
+                        r.key.id().to_string(),
+                        r.kind.clone(),
+                        r.component_type.clone(),
+                        format_count(r.errors, human_metrics),
+                        r.errors_throughput_sec.human_format(),
+                    ],
+                    sort_values: vec![
+                        SortValue::from(r.key.id()),
+                        SortValue::from(r.kind.as_str()),
+                        SortValue::from(r.component_type.as_str()),
+                        SortValue::from(r.errors),
+                        SortValue::from(r.errors_throughput_sec),
+                    ],
+                    outputs: Vec::new(),
+                })
+                .collect(),
+            View::Outputs => components
+                .filter(|r| r.has_displayable_outputs())
+                .flat_map(|r| {
+                    r.outputs.iter().map(move |(id, output)| Line {
+                        cells: vec![
+                            r.key.id().to_string(),
+                            id.clone(),
+                            r.component_type.clone(),
+                            format_count(output.sent_events_total, human_metrics),
+                            output.sent_events_throughput_sec.human_format(),
+                        ],
+                        sort_values: vec![
+                            SortValue::from(r.key.id()),
+                            SortValue::from(id.as_str()),
+                            SortValue::from(r.component_type.as_str()),
+                            SortValue::from(output.sent_events_total),
+                            SortValue::from(output.sent_events_throughput_sec),
+                        ],
+                        outputs: Vec::new(),
+                    })
+                })
+                .collect(),
+        }
+    }
+}
+
+/// A value the lines of a view are sorted by
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
+enum SortValue {
+    Text(String),
+    Number(f64),
+}
+
+impl From<&str> for SortValue {
+    fn from(text: &str) -> Self {
+        SortValue::Text(text.to_owned())
+    }
+}
+
+impl From<i64> for SortValue {
+    fn from(n: i64) -> Self {
+        SortValue::Number(n as f64)
+    }
+}
+
+/// A line of a view, with a value to sort it by per column, followed by the rows of the outputs
+/// of its component, if any
+struct Line {
+    cells: Vec<String>,
+    sort_values: Vec<SortValue>,
+    outputs: Vec<Vec<String>>,
+}
+
+/// The column the lines of the current view are sorted by
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct Sort {
+    column: usize,
+    descending: bool,
+}
+
+impl Sort {
+    /// Sorts lines, keeping the order of the components between lines of equal values
+    fn apply(self, lines: &mut [Line]) {
+        lines.sort_by(|a, b| {
+            let ordering = a.sort_values[self.column]
+                .partial_cmp(&b.sort_values[self.column])
+                .unwrap_or(Ordering::Equal);
+            if self.descending {
+                ordering.reverse()
+            } else {
+                ordering
+            }
+        });
+    }
+}
+
+/// What to show, as controlled by the keyboard
+#[derive(Debug, Clone, Default)]
+struct Controls {
+    view: View,
+    sort: Sort,
+    filter: String,
+    editing_filter: bool,
+}
+
+impl Controls {
+    /// Updates the controls from a key press, returning `false` to quit
+    fn handle_key(&mut self, key: KeyCode) -> bool {
+        if self.editing_filter {
+            match key {
+                KeyCode::Char(c) => self.filter.push(c),
+                KeyCode::Backspace => {
+                    self.filter.pop();
+                }
+                KeyCode::Enter => self.editing_filter = false,
+                KeyCode::Esc => {
+                    self.filter.clear();
+                    self.editing_filter = false;
+                }
+                _ => {}
+            }
+            return true;
+        }
+
+        match key {
+            KeyCode::Esc | KeyCode::Char('q') => return false,
+            KeyCode::Tab => {
+                self.view = self.view.next();
+                self.sort = Sort::default();
+            }
+            KeyCode::Char('s') => {
+                self.sort.column = (self.sort.column + 1) % self.view.columns().len();
+            }
+            KeyCode::Char('r') => self.sort.descending = !self.sort.descending,
+            KeyCode::Char('/') => self.editing_filter = true,
+            _ => {}
+        }
+        true
+    }
+}
 
 struct Widgets<'a> {
     constraints: Vec<Constraint>,
@@ -167,93 +480,72 @@ impl<'a> Widgets<'a> {
     }
 
     /// Renders a components table, showing sources, transforms and sinks in tabular form, with
-    /// statistics pulled from `ComponentsState`,
-    fn components_table<B: Backend>(&self, f: &mut Frame<B>, state: &state::State, area: Rect) {
-        // Header columns
-        let header = HEADER
+    /// statistics pulled from `ComponentsState`, in the current view of the controls
+    fn components_table<B: Backend>(
+        &self,
+        f: &mut Frame<B>,
+        state: &state::State,
+        controls: &Controls,
+        area: Rect,
+    ) {
+        let columns = controls.view.columns();
+
+        // Header columns, marking the one the lines are sorted by
+        let header = columns
             .iter()
-            .map(|s| Cell::from(*s).style(Style::default().add_modifier(Modifier::BOLD)))
+            .enumerate()
+            .map(|(i, (title, _))| {
+                let title = match (i == controls.sort.column, controls.sort.descending) {
+                    (true, false) => format!("{} ▲", title),
+                    (true, true) => format!("{} ▼", title),
+                    (false, _) => title.to_string(),
+                };
+                Cell::from(title).style(Style::default().add_modifier(Modifier::BOLD))
+            })
             .collect::<Vec<_>>();
 
         // Data columns
+        let mut lines = controls
+            .view
+            .lines(state, &controls.filter, self.opts.human_metrics);
+        controls.sort.apply(&mut lines);
+
         let mut items = Vec::new();
-        for (_, r) in state.components.iter() {
-            let mut data = vec![
-                r.key.id().to_string(),
-                (!r.has_displayable_outputs())
-                    .then_some("--")
-                    .unwrap_or_default()
-                    .to_string(),
-                r.kind.clone(),
-                r.component_type.clone(),
-            ];
-
-            let formatted_metrics = [
-                format_metric(
-                    r.received_events_total,
-                    r.received_events_throughput_sec,
-                    self.opts.human_metrics,
-                ),
-                format_metric(
-                    r.sent_events_total,
-                    r.sent_events_throughput_sec,
-                    self.opts.human_metrics,
-                ),
-                format_metric(
-                    r.processed_bytes_total,
-                    r.processed_bytes_throughput_sec,
-                    self.opts.human_metrics,
-                ),
-                if self.opts.human_metrics {
-                    r.errors.human_format()
-                } else {
-                    r.errors.thousands_format()
-                },
-            ];
-
-            data.extend_from_slice(&formatted_metrics);
-            items.push(Row::new(data).style(Style::default()));
-
-            // Add output rows
-            if r.has_displayable_outputs() {
-                for (id, output) in r.outputs.iter() {
-                    let sent_events_metric = format_metric(
-                        output.sent_events_total,
-                        output.sent_events_throughput_sec,
-                        self.opts.human_metrics,
-                    );
-                    let mut data = [""; NUM_COLUMNS]
-                        .into_iter()
-                        .map(Cell::from)
-                        .collect::<Vec<_>>();
-                    data[1] = Cell::from(id.as_ref());
-                    data[5] = Cell::from(sent_events_metric);
-                    items.push(Row::new(data).style(Style::default()));
-                }
+        for line in lines {
+            items.push(Row::new(line.cells).style(Style::default()));
+            for output in line.outputs {
+                items.push(Row::new(output).style(Style::default()));
             }
         }
 
+        let widths = columns
+            .iter()
+            .map(|(_, width)| Constraint::Percentage(*width))
+            .collect::<Vec<_>>();
+
         let w = Table::new(items)
             .header(Row::new(header).bottom_margin(1))
-            .block(Block::default().borders(Borders::ALL).title("Components"))
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(controls.view.title()),
+            )
             .column_spacing(2)
-            .widths(&[
-                Constraint::Percentage(15), // ID
-                Constraint::Percentage(15), // Output
-                Constraint::Percentage(10), // Kind
-                Constraint::Percentage(10), // Type
-                Constraint::Percentage(10), // Events In
-                Constraint::Percentage(10), // Events Out
-                Constraint::Percentage(10), // Bytes
-                Constraint::Percentage(10), // Errors
-            ]);
+            .widths(&widths);
 
         f.render_widget(w, area);
     }
 
     /// Alerts the user to resize the window to view columns
-    fn components_resize_window<B: Backend>(&self, f: &mut Frame<B>, area: Rect) {
-        let block = Block::default().borders(Borders::ALL).title("Components");
+    fn components_resize_window<B: Backend>(
+        &self,
+        f: &mut Frame<B>,
+        controls: &Controls,
+        area: Rect,
+    ) {
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(controls.view.title());
         let w = Paragraph::new("Expand the window to > 80 chars to view metrics")
             .block(block)
             .wrap(Wrap { trim: true });
@@ -261,9 +553,26 @@ impl<'a> Widgets<'a> {
         f.render_widget(w, area);
     }
 
-    /// Renders a box showing instructions on how to exit from `vector top`.
-    fn quit_box<B: Backend>(&self, f: &mut Frame<B>, area: Rect) {
-        let text = vec![Spans::from("To quit, press ESC or 'q'")];
+    /// Renders a box showing the filter, and instructions on how to control and exit from
+    /// `vector top`.
+    fn quit_box<B: Backend>(&self, f: &mut Frame<B>, controls: &Controls, area: Rect) {
+        let text = if controls.editing_filter {
+            vec![Spans::from(format!(
+                "Filter by ID: {}_ (Enter to apply, ESC to clear)",
+                controls.filter
+            ))]
+        } else {
+            let mut spans = vec![Span::from(
+                "Tab: switch view | s: sort | r: reverse | /: filter | ESC or 'q': quit",
+            )];
+            if !controls.filter.is_empty() {
+                spans.push(Span::styled(
+                    format!(" | Filter: {}", controls.filter),
+                    Style::default().fg(Color::Yellow),
+                ));
+            }
+            vec![Spans::from(spans)]
+        };
 
         let block = Block::default()
             .borders(Borders::ALL)
@@ -277,7 +586,7 @@ impl<'a> Widgets<'a> {
     }
 
     /// Draw a single frame. Creates a layout and renders widgets into it.
-    fn draw<B: Backend>(&self, f: &mut Frame<B>, state: state::State) {
+    fn draw<B: Backend>(&self, f: &mut Frame<B>, state: &state::State, controls: &Controls) {
         let size = f.size();
         let rects = Layout::default()
             .constraints(self.constraints.clone())
@@ -287,12 +596,12 @@ impl<'a> Widgets<'a> {
 
         // Require a minimum of 80 chars of line width to display the table
         if size.width >= 80 {
-            self.components_table(f, &state, rects[1]);
+            self.components_table(f, state, controls, rects[1]);
         } else {
-            self.components_resize_window(f, rects[1]);
+            self.components_resize_window(f, controls, rects[1]);
         }
 
-        self.quit_box(f, rects[2]);
+        self.quit_box(f, controls, rects[2]);
     }
 }
 
@@ -331,17 +640,24 @@ pub async fn init_dashboard<'a>(
     terminal.clear()?;
 
     let widgets = Widgets::new(url, opts);
+    let mut controls = Controls::default();
+    let mut state = None;
 
     loop {
         tokio::select! {
-            Some(state) = state_rx.recv() => {
-                terminal.draw(|f| widgets.draw(f, state))?;
+            Some(new_state) = state_rx.recv() => {
+                let state = state.insert(new_state);
+                terminal.draw(|f| widgets.draw(f, state, &controls))?;
             },
             k = key_press_rx.recv() => {
-                if let KeyCode::Esc | KeyCode::Char('q') = k.unwrap() {
+                if !controls.handle_key(k.unwrap()) {
                     let _ = key_press_kill_tx.send(());
                     break
                 }
+                // Redraw the last state, as shown by the updated controls
+                if let Some(state) = &state {
+                    terminal.draw(|f| widgets.draw(f, state, &controls))?;
+                }
             }
             _ = &mut shutdown_rx => {
                 let _ = key_press_kill_tx.send(());
@@ -456,4 +772,113 @@ mod tests {
         assert_eq!((N * (N * (N * N))).human_format_bytes(), "1.00 TiB");
         assert_eq!((N * (N * (N * (N * N)))).human_format_bytes(), "1.00 PiB");
     }
+
+    fn component(
+        id: &str,
+        kind: &str,
+        errors: i64,
+        buffer: state::BufferMetrics,
+    ) -> state::ComponentRow {
+        state::ComponentRow {
+            key: id.into(),
+            kind: kind.to_string(),
+            component_type: "test".to_string(),
+            outputs: Default::default(),
+            processed_bytes_total: 0,
+            processed_bytes_throughput_sec: 0,
+            received_events_total: 0,
+            received_events_throughput_sec: 0,
+            sent_events_total: 0,
+            sent_events_throughput_sec: 0,
+            errors,
+            errors_throughput_sec: 0,
+            buffer,
+            cpu_usage: 0.0,
+        }
+    }
+
+    fn test_state() -> state::State {
+        let buffer = state::BufferMetrics {
+            events: 50,
+            byte_size: 1024,
+            max_events: Some(200),
+            max_byte_size: None,
+            discarded_events_total: 3,
+        };
+        state::State::new(
+            [
+                component("in", "source", 5, Default::default()),
+                component("parse", "transform", 10, Default::default()),
+                component("out", "sink", 1, buffer),
+            ]
+            .into_iter()
+            .map(|r| (r.key.clone(), r))
+            .collect(),
+        )
+    }
+
+    fn cells(state: &state::State, controls: &Controls, column: usize) -> Vec<String> {
+        let mut lines = controls.view.lines(state, &controls.filter, false);
+        controls.sort.apply(&mut lines);
+        lines
+            .into_iter()
+            .map(|line| line.cells[column].clone())
+            .collect()
+    }
+
+    #[test]
+    /// Lines should be sorted by the selected column, in the selected order
+    fn sort_lines() {
+        let state = test_state();
+        let mut controls = Controls::default();
+        assert_eq!(cells(&state, &controls, 0), ["in", "out", "parse"]);
+
+        controls.handle_key(KeyCode::Tab);
+        controls.handle_key(KeyCode::Tab);
+        assert_eq!(controls.view, View::Errors);
+        for _ in 0..3 {
+            controls.handle_key(KeyCode::Char('s'));
+        }
+        controls.handle_key(KeyCode::Char('r'));
+        assert_eq!(cells(&state, &controls, 0), ["parse", "in", "out"]);
+
+        // Switching views resets the sort
+        controls.handle_key(KeyCode::Tab);
+        assert_eq!(controls.sort, Sort::default());
+    }
+
+    #[test]
+    /// Lines should be filtered by ID, with keys typed into the filter while editing it
+    fn filter_lines() {
+        let state = test_state();
+        let mut controls = Controls::default();
+
+        for key in [KeyCode::Char('/'), KeyCode::Char('q'), KeyCode::Backspace] {
+            assert!(controls.handle_key(key));
+        }
+        for c in "ou".chars() {
+            controls.handle_key(KeyCode::Char(c));
+        }
+        controls.handle_key(KeyCode::Enter);
+        assert_eq!(cells(&state, &controls, 0), ["out"]);
+
+        controls.handle_key(KeyCode::Char('/'));
+        controls.handle_key(KeyCode::Esc);
+        assert_eq!(cells(&state, &controls, 0), ["in", "out", "parse"]);
+        assert!(!controls.handle_key(KeyCode::Esc));
+    }
+
+    #[test]
+    /// The buffers view should only show sinks, with the share of their buffer in use
+    fn buffers_view() {
+        let state = test_state();
+        let controls = Controls {
+            view: View::Buffers,
+            ..Default::default()
+        };
+
+        assert_eq!(cells(&state, &controls, 0), ["out"]);
+        assert_eq!(cells(&state, &controls, 4), ["25.0%"]);
+        assert_eq!(cells(&state, &controls, 5), ["3"]);
+    }
 }
@@ -1,7 +1,4 @@
-use std::{
-    collections::{BTreeMap, HashMap},
-    sync::Arc,
-};
+use std::{collections::BTreeMap, sync::Arc};
 
 use tokio::task::JoinHandle;
 use tokio_stream::StreamExt;
@@ -10,7 +7,7 @@ use vector_api_client::{
     Client, SubscriptionClient,
 };
 
-use super::state::{self, OutputMetrics};
+use super::state::{self, BufferMetrics, OutputMetrics};
 use crate::{config::ComponentKey, top::state::SentEventsMetric};
 
 /// Components that have been added
@@ -28,7 +25,7 @@ async fn component_added(client: Arc<SubscriptionClient>, tx: state::EventTx) {
                     key,
                     kind: c.on.to_string(),
                     component_type: c.component_type,
-                    outputs: HashMap::new(),
+                    outputs: BTreeMap::new(),
                     received_events_total: 0,
                     received_events_throughput_sec: 0,
                     sent_events_total: 0,
@@ -36,6 +33,9 @@ async fn component_added(client: Arc<SubscriptionClient>, tx: state::EventTx) {
                     processed_bytes_total: 0,
                     processed_bytes_throughput_sec: 0,
                     errors: 0,
+                    errors_throughput_sec: 0,
+                    buffer: BufferMetrics::default(),
+                    cpu_usage: 0.0,
                 }))
                 .await;
         }
@@ -236,6 +236,76 @@ async fn errors_totals(client: Arc<SubscriptionClient>, tx: state::EventTx, inte
     }
 }
 
+async fn errors_throughputs(client: Arc<SubscriptionClient>, tx: state::EventTx, interval: i64) {
+    tokio::pin! {
+        let stream = client.component_errors_throughputs_subscription(interval);
+    };
+
+    while let Some(Some(res)) = stream.next().await {
+        if let Some(d) = res.data {
+            let c = d.component_errors_throughputs;
+            let _ = tx
+                .send(state::EventType::ErrorsThroughputs(
+                    interval,
+                    c.into_iter()
+                        .map(|c| (ComponentKey::from(c.component_id.as_str()), c.throughput))
+                        .collect(),
+                ))
+                .await;
+        }
+    }
+}
+
+async fn buffer_usages(client: Arc<SubscriptionClient>, tx: state::EventTx, interval: i64) {
+    tokio::pin! {
+        let stream = client.component_buffer_usages_subscription(interval);
+    };
+
+    while let Some(Some(res)) = stream.next().await {
+        if let Some(d) = res.data {
+            let c = d.component_buffer_usages;
+            let _ = tx
+                .send(state::EventType::BufferUsages(
+                    c.into_iter()
+                        .map(|c| {
+                            (
+                                ComponentKey::from(c.component_id.as_str()),
+                                BufferMetrics {
+                                    events: c.events as i64,
+                                    byte_size: c.byte_size as i64,
+                                    max_events: c.max_events.map(|v| v as i64),
+                                    max_byte_size: c.max_byte_size.map(|v| v as i64),
+                                    discarded_events_total: c.discarded_events_total as i64,
+                                },
+                            )
+                        })
+                        .collect(),
+                ))
+                .await;
+        }
+    }
+}
+
+async fn cpu_time_throughputs(client: Arc<SubscriptionClient>, tx: state::EventTx, interval: i64) {
+    tokio::pin! {
+        let stream = client.component_cpu_time_throughputs_subscription(interval);
+    };
+
+    while let Some(Some(res)) = stream.next().await {
+        if let Some(d) = res.data {
+            let c = d.component_cpu_time_throughputs;
+            let _ = tx
+                .send(state::EventType::CpuTimeThroughputs(
+                    interval,
+                    c.into_iter()
+                        .map(|c| (ComponentKey::from(c.component_id.as_str()), c.throughput))
+                        .collect(),
+                ))
+                .await;
+        }
+    }
+}
+
 /// Subscribe to each metrics channel through a separate client. This is a temporary workaround
 /// until client multiplexing is fixed. In future, we should be able to use a single client
 pub fn subscribe(
@@ -278,7 +348,14 @@ pub fn subscribe(
             tx.clone(),
             interval,
         )),
-        tokio::spawn(errors_totals(Arc::clone(&client), tx, interval)),
+        tokio::spawn(errors_totals(Arc::clone(&client), tx.clone(), interval)),
+        tokio::spawn(errors_throughputs(
+            Arc::clone(&client),
+            tx.clone(),
+            interval,
+        )),
+        tokio::spawn(buffer_usages(Arc::clone(&client), tx.clone(), interval)),
+        tokio::spawn(cpu_time_throughputs(Arc::clone(&client), tx, interval)),
     ]
 }
 
@@ -322,6 +399,9 @@ pub async fn init_components(client: &Client) -> Result<state::State, ()> {
                         processed_bytes_total: d.on.processed_bytes_total(),
                         processed_bytes_throughput_sec: 0,
                         errors: 0,
+                        errors_throughput_sec: 0,
+                        buffer: BufferMetrics::default(),
+                        cpu_usage: 0.0,
                     },
                 ))
             })
@@ -7,6 +7,7 @@ mod state;
 use clap::Parser;
 pub use cmd::cmd;
 use url::Url;
+use vector_api_client::ConnectOptions;
 
 #[derive(Parser, Debug, Clone)]
 #[command(rename_all = "kebab-case")]
@@ -26,4 +27,7 @@ pub struct Opts {
     /// Whether to reconnect if the underlying Vector API connection drops. By default, top will attempt to reconnect if the connection drops.
     #[arg(short, long)]
     no_reconnect: bool,
+
+    #[command(flatten)]
+    connect: ConnectOptions,
 }
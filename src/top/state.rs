@@ -32,6 +32,11 @@ pub enum EventType {
     /// Interval + identified metric
     ProcessedBytesThroughputs(i64, Vec<IdentifiedMetric>),
     ErrorsTotals(Vec<IdentifiedMetric>),
+    /// Interval in ms + identified metric
+    ErrorsThroughputs(i64, Vec<IdentifiedMetric>),
+    BufferUsages(Vec<(ComponentKey, BufferMetrics)>),
+    /// Interval in ms + identified metric, in nanoseconds of CPU time
+    CpuTimeThroughputs(i64, Vec<IdentifiedMetric>),
     ComponentAdded(ComponentRow),
     ComponentRemoved(ComponentKey),
     ConnectionUpdated(ConnectionStatus),
@@ -106,12 +111,33 @@ impl From<i64> for OutputMetrics {
     }
 }
 
+/// The usage of the buffer of a sink, summed across the stages of the buffer
+#[derive(Debug, Clone, Default)]
+pub struct BufferMetrics {
+    pub events: i64,
+    pub byte_size: i64,
+    pub max_events: Option<i64>,
+    pub max_byte_size: Option<i64>,
+    pub discarded_events_total: i64,
+}
+
+impl BufferMetrics {
+    /// Percentage of the buffer in use, by events if it is limited by events, or by bytes
+    pub fn usage(&self) -> Option<f64> {
+        match (self.max_events, self.max_byte_size) {
+            (Some(max), _) if max > 0 => Some(self.events as f64 * 100.0 / max as f64),
+            (_, Some(max)) if max > 0 => Some(self.byte_size as f64 * 100.0 / max as f64),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct ComponentRow {
     pub key: ComponentKey,
     pub kind: String,
     pub component_type: String,
-    pub outputs: HashMap<String, OutputMetrics>,
+    pub outputs: BTreeMap<String, OutputMetrics>,
     pub processed_bytes_total: i64,
     pub processed_bytes_throughput_sec: i64,
     pub received_events_total: i64,
@@ -119,6 +145,10 @@ pub struct ComponentRow {
     pub sent_events_total: i64,
     pub sent_events_throughput_sec: i64,
     pub errors: i64,
+    pub errors_throughput_sec: i64,
+    pub buffer: BufferMetrics,
+    /// Percentage of a CPU core spent polling the tasks of the component
+    pub cpu_usage: f64,
 }
 
 impl ComponentRow {
@@ -208,6 +238,28 @@ pub async fn updater(mut event_rx: EventRx) -> StateRx {
                         }
                     }
                 }
+                EventType::ErrorsThroughputs(interval, rows) => {
+                    for (key, v) in rows {
+                        if let Some(r) = state.components.get_mut(&key) {
+                            r.errors_throughput_sec =
+                                (v as f64 * (1000.0 / interval as f64)) as i64;
+                        }
+                    }
+                }
+                EventType::BufferUsages(rows) => {
+                    for (key, buffer) in rows {
+                        if let Some(r) = state.components.get_mut(&key) {
+                            r.buffer = buffer;
+                        }
+                    }
+                }
+                EventType::CpuTimeThroughputs(interval, rows) => {
+                    for (key, v) in rows {
+                        if let Some(r) = state.components.get_mut(&key) {
+                            r.cpu_usage = v as f64 / (interval as f64 * 10_000.0);
+                        }
+                    }
+                }
                 EventType::ComponentAdded(c) => {
                     let _ = state.components.insert(c.key.clone(), c);
                 }
@@ -226,3 +278,51 @@ pub async fn updater(mut event_rx: EventRx) -> StateRx {
 
     rx
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn component_row(key: &ComponentKey) -> ComponentRow {
+        ComponentRow {
+            key: key.clone(),
+            kind: "transform".to_owned(),
+            component_type: "remap".to_owned(),
+            outputs: BTreeMap::new(),
+            processed_bytes_total: 0,
+            processed_bytes_throughput_sec: 0,
+            received_events_total: 0,
+            received_events_throughput_sec: 0,
+            sent_events_total: 0,
+            sent_events_throughput_sec: 0,
+            errors: 0,
+            errors_throughput_sec: 0,
+            buffer: BufferMetrics::default(),
+            cpu_usage: 0.0,
+        }
+    }
+
+    #[tokio::test]
+    async fn converts_cpu_time_to_usage() {
+        let key = ComponentKey::from("remap");
+        let (tx, rx) = mpsc::channel(1);
+        let mut state_rx = updater(rx).await;
+
+        let components = [(key.clone(), component_row(&key))].into_iter().collect();
+        tx.send(EventType::InitializeState(State::new(components)))
+            .await
+            .unwrap();
+        state_rx.recv().await.unwrap();
+
+        // Half a second of CPU time over a two seconds interval is a quarter of a core.
+        tx.send(EventType::CpuTimeThroughputs(
+            2000,
+            vec![(key.clone(), 500_000_000)],
+        ))
+        .await
+        .unwrap();
+        let state = state_rx.recv().await.unwrap();
+
+        assert_eq!(state.components[&key].cpu_usage, 25.0);
+    }
+}
@@ -44,7 +44,13 @@ use crate::{
     },
     event::{EventArray, EventContainer},
     internal_events::EventsReceived,
+    internal_telemetry::{
+        cpu_time::CpuTimeExt,
+        latency::Latency,
+        lineage::{self, TransformLineage},
+    },
     shutdown::SourceShutdownCoordinator,
+    sinks::util::dropped::DroppedSender,
     source_sender::CHUNK_SIZE,
     spawn_named,
     topology::task::TaskError,
@@ -180,7 +186,9 @@ pub async fn build_pieces(
 
         let mut builder = {
             let _span = span.enter();
-            SourceSender::builder().with_buffer(*SOURCE_SENDER_BUFFER_SIZE)
+            SourceSender::builder()
+                .with_buffer(*SOURCE_SENDER_BUFFER_SIZE)
+                .with_source_id(key.clone())
         };
         let mut pumps = Vec::new();
         let mut controls = HashMap::new();
@@ -397,15 +405,6 @@ pub async fn build_pieces(
         let typetag = sink.inner.get_component_name();
         let input_type = sink.inner.input().data_type();
 
-        if config.schema.validation {
-            // At this point, we've validated that all transforms are valid, including any
-            // transform that mutates the schema provided by their sources. We can now validate the
-            // schema expectations of each individual sink.
-            if let Err(mut err) = schema::validate_sink_expectations(key, sink, config) {
-                errors.append(&mut err);
-            };
-        }
-
         let (tx, rx) = if let Some(buffer) = buffers.remove(key) {
             buffer
         } else {
@@ -434,11 +433,47 @@ pub async fn build_pieces(
             }
         };
 
+        // Sinks that reroute the events they reject get a sender for their outputs, which are
+        // pumped into fanouts the same way source outputs are.
+        let sink_outputs = sink.inner.outputs();
+        let mut pumps = Vec::with_capacity(sink_outputs.len());
+        let dropped = if sink_outputs.is_empty() {
+            None
+        } else {
+            let mut builder = SourceSender::builder().with_buffer(TOPOLOGY_BUFFER_SIZE.get());
+            for output in sink_outputs {
+                let mut rx = builder.add_output(output.clone());
+
+                let (mut fanout, control) = Fanout::new();
+                pumps.push(async move {
+                    debug!("Sink output pump starting.");
+
+                    while let Some(array) = rx.next().await {
+                        if let Err(error) = fanout.send(array).await {
+                            debug!(message = "Sink output pump finished with an error.", %error);
+                            return;
+                        }
+                    }
+
+                    debug!("Sink output pump finished normally.");
+                });
+                outputs.insert(
+                    OutputId {
+                        component: key.clone(),
+                        port: output.port,
+                    },
+                    control,
+                );
+            }
+            Some(DroppedSender::new(builder.build(), key, typetag))
+        };
+
         let cx = SinkContext {
             healthcheck,
             globals: config.global.clone(),
             proxy: ProxyConfig::merge_with_env(&config.global.proxy, sink.proxy()),
             schema: config.schema,
+            dropped,
         };
 
         let (sink, healthcheck) = match sink.inner.build(cx).await {
@@ -451,6 +486,7 @@ pub async fn build_pieces(
 
         let (trigger, tripwire) = Tripwire::new();
 
+        let component_id = key.clone();
         let sink = async move {
             debug!("Sink starting.");
 
@@ -468,26 +504,43 @@ pub async fn build_pieces(
 
             let mut rx = wrap(rx);
 
-            sink.run(
-                rx.by_ref()
-                    .filter(|events: &EventArray| ready(filter_events_type(events, input_type)))
-                    .inspect(|events| {
-                        emit!(EventsReceived {
-                            count: events.len(),
-                            byte_size: events.estimated_json_encoded_size_of(),
+            let pumps = pumps.into_iter().map(tokio::spawn).collect::<Vec<_>>();
+
+            let mut latency = Latency::default();
+            let result = sink
+                .run(
+                    rx.by_ref()
+                        .filter(|events: &EventArray| ready(filter_events_type(events, input_type)))
+                        .map(move |mut events| {
+                            lineage::deliver(&component_id, &mut events);
+                            latency.track(&mut events);
+                            events
                         })
-                    })
-                    .take_until_if(tripwire),
-            )
-            .await
-            .map(|_| {
-                debug!("Sink finished normally.");
-                TaskOutput::Sink(rx)
-            })
-            .map_err(|_| {
-                debug!("Sink finished with an error.");
-                TaskError::Opaque
-            })
+                        .inspect(|events| {
+                            emit!(EventsReceived {
+                                count: events.len(),
+                                byte_size: events.estimated_json_encoded_size_of(),
+                            })
+                        })
+                        .take_until_if(tripwire),
+                )
+                .await;
+
+            // The sink has dropped its outputs' sender by now, so the pumps finish once they've
+            // forwarded whatever was left.
+            for pump in pumps {
+                let _ = pump.await;
+            }
+
+            result
+                .map(|_| {
+                    debug!("Sink finished normally.");
+                    TaskOutput::Sink(rx)
+                })
+                .map_err(|_| {
+                    debug!("Sink finished with an error.");
+                    TaskError::Opaque
+                })
         };
 
         let task = Task::new(key.clone(), typetag, sink);
@@ -631,7 +684,13 @@ fn build_sync_transform(
 ) -> (Task, HashMap<OutputId, fanout::ControlChannel>) {
     let (outputs, controls) = TransformOutputs::new(node.outputs);
 
-    let runner = Runner::new(t, input_rx, node.input_details.data_type(), outputs);
+    let runner = Runner::new(
+        t,
+        node.key.clone(),
+        input_rx,
+        node.input_details.data_type(),
+        outputs,
+    );
     let transform = if node.enable_concurrency {
         runner.run_concurrently().boxed()
     } else {
@@ -668,6 +727,7 @@ fn build_sync_transform(
 
 struct Runner {
     transform: Box<dyn SyncTransform>,
+    key: Arc<ComponentKey>,
     input_rx: Option<BufferReceiver<EventArray>>,
     input_type: DataType,
     outputs: TransformOutputs,
@@ -678,12 +738,14 @@ struct Runner {
 impl Runner {
     fn new(
         transform: Box<dyn SyncTransform>,
+        key: ComponentKey,
         input_rx: BufferReceiver<EventArray>,
         input_type: DataType,
         outputs: TransformOutputs,
     ) -> Self {
         Self {
             transform,
+            key: Arc::new(key),
             input_rx: Some(input_rx),
             input_type,
             outputs,
@@ -726,7 +788,11 @@ impl Runner {
         self.timer.start_wait();
         while let Some(events) = input_rx.next().await {
             self.on_events_received(&events);
+            let mut lineage = TransformLineage::default();
+            lineage.receive(events.iter_events());
             self.transform.transform_all(events, &mut outputs_buf);
+            lineage.send(&self.key, outputs_buf.iter_events());
+            lineage.drop_unsent(&self.key);
             self.send_outputs(&mut outputs_buf)
                 .await
                 .map_err(TaskError::wrapped)?;
@@ -775,13 +841,18 @@ impl Runner {
                             }
 
                             let mut t = self.transform.clone();
+                            let key = Arc::clone(&self.key);
                             let mut outputs_buf = self.outputs.new_buf_with_capacity(len);
                             let task = tokio::spawn(async move {
+                                let mut lineage = TransformLineage::default();
                                 for events in input_arrays {
+                                    lineage.receive(events.iter_events());
                                     t.transform_all(events, &mut outputs_buf);
                                 }
+                                lineage.send(&key, outputs_buf.iter_events());
+                                lineage.drop_unsent(&key);
                                 outputs_buf
-                            }.in_current_span());
+                            }.track_cpu_time().in_current_span());
                             in_flight.push_back(task);
                         }
                         None => {
@@ -814,17 +885,32 @@ fn build_task_transform(
 
     let input_rx = crate::utilization::wrap(input_rx.into_stream());
 
+    let lineage = Arc::new(Mutex::new(TransformLineage::default()));
+    let received_lineage = Arc::clone(&lineage);
+    let component_id = key.clone();
+    let received_component_id = key.clone();
+
     let filtered = input_rx
         .filter(move |events| ready(filter_events_type(events, input_type)))
-        .inspect(|events| {
+        .map(move |mut events| {
             emit!(EventsReceived {
                 count: events.len(),
                 byte_size: events.estimated_json_encoded_size_of(),
-            })
+            });
+            TransformLineage::receive_tracked(
+                &received_lineage,
+                &received_component_id,
+                &mut events,
+            );
+            events
         });
     let stream = t
         .transform(Box::pin(filtered))
-        .inspect(|events: &EventArray| {
+        .inspect(move |events: &EventArray| {
+            lineage.lock().expect("lineage mutex poisoned").send(
+                &component_id,
+                events.iter_events().map(|event| (None, event)),
+            );
             emit!(EventsSent {
                 count: events.len(),
                 byte_size: events.estimated_json_encoded_size_of(),
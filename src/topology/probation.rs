@@ -0,0 +1,427 @@
+//! Probation of reloaded configurations.
+//!
+//! Snapshots of the internal metrics taken around a reload are turned into rates, which are
+//! compared between the previous and the reloaded configuration to detect regressions. Throughput
+//! is compared per sink, for the sinks of both configurations only.
+
+use std::{collections::HashMap, fmt};
+
+use tokio::time::Instant;
+
+use crate::{
+    config::ProbationOptions,
+    event::{Metric, MetricValue},
+};
+
+/// The cumulative values of the metrics watched during probation.
+#[derive(Clone, Debug)]
+pub(super) struct Snapshot {
+    at: Instant,
+    errors: HashMap<String, f64>,
+    sent_events: HashMap<String, f64>,
+}
+
+impl Snapshot {
+    /// Captures the current values, if the metrics are initialized.
+    pub(super) fn capture() -> Option<Self> {
+        let controller = crate::metrics::Controller::get().ok()?;
+        Some(Self::from_metrics(
+            controller.capture_metrics(),
+            Instant::now(),
+        ))
+    }
+
+    fn from_metrics(metrics: impl IntoIterator<Item = Metric>, at: Instant) -> Self {
+        let mut errors = HashMap::new();
+        let mut sent_events = HashMap::new();
+
+        for metric in metrics {
+            let value = match metric.value() {
+                MetricValue::Counter { value } => *value,
+                _ => continue,
+            };
+
+            match metric.name() {
+                "component_errors_total" => {
+                    if let Some(component_id) = metric.tag_value("component_id") {
+                        *errors.entry(component_id).or_insert(0.0) += value;
+                    }
+                }
+                "component_sent_events_total"
+                    if metric.tag_value("component_kind").as_deref() == Some("sink") =>
+                {
+                    if let Some(component_id) = metric.tag_value("component_id") {
+                        *sent_events.entry(component_id).or_insert(0.0) += value;
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        Self {
+            at,
+            errors,
+            sent_events,
+        }
+    }
+}
+
+/// The rates, per second, of the watched metrics between two snapshots.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub(super) struct Rates {
+    errors: HashMap<String, f64>,
+    throughput: HashMap<String, Throughput>,
+}
+
+/// The events sent by a sink between two snapshots, and their rate per second.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Throughput {
+    events: f64,
+    rate: f64,
+}
+
+impl Rates {
+    pub(super) fn between(start: &Snapshot, end: &Snapshot) -> Self {
+        let elapsed = end.at.saturating_duration_since(start.at).as_secs_f64();
+        if elapsed <= 0.0 {
+            return Self::default();
+        }
+
+        let errors = end
+            .errors
+            .iter()
+            .map(|(component_id, value)| {
+                let previous = start.errors.get(component_id).copied().unwrap_or(0.0);
+                (component_id.clone(), increase(previous, *value) / elapsed)
+            })
+            .collect();
+
+        let throughput = end
+            .sent_events
+            .iter()
+            .map(|(component_id, value)| {
+                let previous = start.sent_events.get(component_id).copied().unwrap_or(0.0);
+                let events = increase(previous, *value);
+                let rate = events / elapsed;
+                (component_id.clone(), Throughput { events, rate })
+            })
+            .collect();
+
+        Self { errors, throughput }
+    }
+}
+
+/// Counters of removed and re-added components start over, in which case their whole value is
+/// the increase.
+fn increase(start: f64, end: f64) -> f64 {
+    if end >= start {
+        end - start
+    } else {
+        end
+    }
+}
+
+/// Why a reloaded configuration failed probation.
+#[derive(Clone, Debug, PartialEq)]
+pub(super) enum Regression {
+    Healthchecks,
+    ErrorRate {
+        components: Vec<String>,
+    },
+    Throughput {
+        sink: String,
+        baseline: f64,
+        current: f64,
+    },
+}
+
+impl fmt::Display for Regression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Healthchecks => write!(f, "sink healthchecks failed"),
+            Self::ErrorRate { components } => write!(
+                f,
+                "error rate increased for components: {}",
+                components.join(", ")
+            ),
+            Self::Throughput {
+                sink,
+                baseline,
+                current,
+            } => write!(
+                f,
+                "throughput of sink {} dropped from {:.2} to {:.2} events/sec",
+                sink, baseline, current
+            ),
+        }
+    }
+}
+
+/// Compares the rates observed during probation against those of the previous configuration.
+///
+/// The error rates are only compared for the given components, which are those of the reloaded
+/// configuration. The throughput is only compared for the given sinks, which are those of both
+/// configurations, and only for those that sent enough events under the previous configuration to
+/// be meaningful.
+pub(super) fn compare(
+    options: &ProbationOptions,
+    components: &[String],
+    sinks: &[String],
+    baseline: &Rates,
+    probation: &Rates,
+) -> Option<Regression> {
+    let errors = |rates: &Rates, component_id: &String| {
+        rates.errors.get(component_id).copied().unwrap_or(0.0)
+    };
+    let mut components = components
+        .iter()
+        .filter(|component_id| {
+            errors(probation, component_id) - errors(baseline, component_id)
+                > options.max_error_rate_increase
+        })
+        .cloned()
+        .collect::<Vec<_>>();
+
+    if !components.is_empty() {
+        components.sort();
+        return Some(Regression::ErrorRate { components });
+    }
+
+    let mut sinks = sinks.iter().collect::<Vec<_>>();
+    sinks.sort();
+    sinks.into_iter().find_map(|sink| {
+        let baseline = baseline
+            .throughput
+            .get(sink)
+            .filter(|baseline| baseline.events >= options.min_baseline_events as f64)?;
+        let current = probation
+            .throughput
+            .get(sink)
+            .map_or(0.0, |current| current.rate);
+
+        (current < baseline.rate * options.min_throughput_ratio).then(|| Regression::Throughput {
+            sink: sink.clone(),
+            baseline: baseline.rate,
+            current,
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::event::{MetricKind, MetricTags};
+
+    fn counter(name: &str, tags: &[(&str, &str)], value: f64) -> Metric {
+        let tags = tags
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect::<MetricTags>();
+        Metric::new(name, MetricKind::Absolute, MetricValue::Counter { value })
+            .with_tags(Some(tags))
+    }
+
+    fn options() -> ProbationOptions {
+        ProbationOptions {
+            window_secs: 60,
+            max_error_rate_increase: 1.0,
+            min_throughput_ratio: 0.5,
+            min_baseline_events: 100,
+        }
+    }
+
+    fn rates(errors: &[(&str, f64)], throughput: &[(&str, f64, f64)]) -> Rates {
+        Rates {
+            errors: errors
+                .iter()
+                .map(|(component_id, rate)| (component_id.to_string(), *rate))
+                .collect(),
+            throughput: throughput
+                .iter()
+                .map(|(component_id, events, rate)| {
+                    let throughput = Throughput {
+                        events: *events,
+                        rate: *rate,
+                    };
+                    (component_id.to_string(), throughput)
+                })
+                .collect(),
+        }
+    }
+
+    fn components(components: &[&str]) -> Vec<String> {
+        components
+            .iter()
+            .map(|component_id| component_id.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn computes_rates_between_snapshots() {
+        let at = Instant::now();
+        let start = Snapshot::from_metrics(
+            vec![
+                counter("component_errors_total", &[("component_id", "in")], 10.0),
+                counter(
+                    "component_sent_events_total",
+                    &[("component_id", "out"), ("component_kind", "sink")],
+                    100.0,
+                ),
+            ],
+            at,
+        );
+        let end = Snapshot::from_metrics(
+            vec![
+                counter("component_errors_total", &[("component_id", "in")], 30.0),
+                counter("component_errors_total", &[("component_id", "new")], 5.0),
+                counter("component_errors_total", &[], 100.0),
+                counter(
+                    "component_sent_events_total",
+                    &[("component_id", "out"), ("component_kind", "sink")],
+                    300.0,
+                ),
+                counter(
+                    "component_sent_events_total",
+                    &[("component_id", "other"), ("component_kind", "sink")],
+                    50.0,
+                ),
+                counter(
+                    "component_sent_events_total",
+                    &[("component_id", "in"), ("component_kind", "source")],
+                    1000.0,
+                ),
+            ],
+            at + Duration::from_secs(10),
+        );
+
+        assert_eq!(
+            Rates::between(&start, &end),
+            rates(
+                &[("in", 2.0), ("new", 0.5)],
+                &[("out", 200.0, 20.0), ("other", 50.0, 5.0)]
+            )
+        );
+    }
+
+    #[test]
+    fn detects_error_rate_regression() {
+        let baseline = rates(&[("in", 1.0), ("out", 0.0)], &[]);
+        let components = components(&["in", "out", "new"]);
+
+        assert_eq!(
+            compare(
+                &options(),
+                &components,
+                &[],
+                &baseline,
+                &rates(&[("in", 1.5), ("out", 0.5)], &[])
+            ),
+            None
+        );
+        assert_eq!(
+            compare(
+                &options(),
+                &components,
+                &[],
+                &baseline,
+                &rates(&[("in", 3.0), ("out", 0.5), ("new", 2.0)], &[])
+            ),
+            Some(Regression::ErrorRate {
+                components: vec!["in".to_owned(), "new".to_owned()]
+            })
+        );
+        // Components that are not in the reloaded configuration are not compared.
+        assert_eq!(
+            compare(
+                &options(),
+                &components,
+                &[],
+                &baseline,
+                &rates(&[("in", 1.0), ("removed", 10.0), ("other", 10.0)], &[])
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn detects_throughput_regression_per_sink() {
+        let baseline = rates(&[], &[("out", 1000.0, 10.0), ("other", 1000.0, 10.0)]);
+
+        assert_eq!(
+            compare(
+                &options(),
+                &[],
+                &components(&["out", "other"]),
+                &baseline,
+                &rates(&[], &[("out", 600.0, 6.0), ("other", 600.0, 6.0)])
+            ),
+            None
+        );
+        // The throughput of a sink dropping is not offset by another sink.
+        assert_eq!(
+            compare(
+                &options(),
+                &[],
+                &components(&["out", "other"]),
+                &baseline,
+                &rates(&[], &[("out", 400.0, 4.0), ("other", 1600.0, 16.0)])
+            ),
+            Some(Regression::Throughput {
+                sink: "out".to_owned(),
+                baseline: 10.0,
+                current: 4.0
+            })
+        );
+        // Sinks that are not in both configurations are not compared.
+        assert_eq!(
+            compare(
+                &options(),
+                &[],
+                &components(&["other"]),
+                &baseline,
+                &rates(&[], &[("other", 1000.0, 10.0)])
+            ),
+            None
+        );
+        assert_eq!(
+            compare(
+                &options(),
+                &[],
+                &components(&["out", "new"]),
+                &baseline,
+                &rates(&[], &[("out", 1000.0, 10.0)])
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn skips_throughput_of_sparse_baselines() {
+        assert_eq!(
+            compare(
+                &options(),
+                &[],
+                &components(&["out"]),
+                &rates(&[], &[("out", 99.0, 10.0)]),
+                &rates(&[], &[("out", 0.0, 0.0)])
+            ),
+            None
+        );
+        assert_eq!(
+            compare(
+                &options(),
+                &[],
+                &components(&["out"]),
+                &rates(&[], &[("out", 100.0, 10.0)]),
+                &rates(&[], &[])
+            ),
+            Some(Regression::Throughput {
+                sink: "out".to_owned(),
+                baseline: 10.0,
+                current: 0.0
+            })
+        );
+    }
+}
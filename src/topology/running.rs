@@ -18,17 +18,21 @@ use vector_common::trigger::DisabledTrigger;
 use super::{TapOutput, TapResource};
 use crate::{
     config::{
-        ComponentKey, Config, ConfigDiff, HealthcheckOptions, Inputs, OutputId, Resource,
-        SourceConfig,
+        ComponentKey, Config, ConfigDiff, HealthcheckOptions, Inputs, OutputId, ProbationOptions,
+        Resource, SourceConfig,
     },
     event::EventArray,
+    internal_events::VectorReloadRolledBack,
+    internal_telemetry::cpu_time::CpuTimeExt,
     shutdown::SourceShutdownCoordinator,
     spawn_named,
     topology::{
         build_or_log_errors, builder,
         builder::Pieces,
         fanout::{ControlChannel, ControlMessage},
-        handle_errors, retain, take_healthchecks,
+        handle_errors,
+        probation::{self, Rates, Regression, Snapshot},
+        retain, take_healthchecks,
         task::TaskOutput,
         BuiltBuffer, TaskHandle, WatchRx, WatchTx,
     },
@@ -46,6 +50,17 @@ pub struct RunningTopology {
     abort_tx: mpsc::UnboundedSender<()>,
     watch: (WatchTx, WatchRx),
     pub(crate) running: Arc<AtomicBool>,
+    baseline: Option<Snapshot>,
+    probation: Option<Probation>,
+}
+
+/// A reloaded configuration on probation, along with the configuration to roll back to.
+struct Probation {
+    previous_config: Config,
+    options: ProbationOptions,
+    baseline: Option<Rates>,
+    start: Option<Snapshot>,
+    deadline: Instant,
 }
 
 impl RunningTopology {
@@ -61,6 +76,8 @@ impl RunningTopology {
             abort_tx,
             watch: watch::channel(TapResource::default()),
             running: Arc::new(AtomicBool::new(true)),
+            baseline: Snapshot::capture(),
+            probation: None,
         }
     }
 
@@ -202,11 +219,85 @@ impl RunningTopology {
     /// topology back to its previous state.  If either of these scenarios occur, then `Ok(false)`
     /// is returned.
     ///
+    /// If the new configuration enables `reload.probation`, it is put on probation: see
+    /// [`end_probation`][Self::end_probation]. Reloading while a configuration is on probation
+    /// ends that probation without rolling it back.
+    ///
     /// # Errors
     ///
     /// If all changes from the new configuration cannot be made, and the current configuration
     /// cannot be fully restored, then `Err(())` is returned.
     pub async fn reload_config_and_respawn(&mut self, new_config: Config) -> Result<bool, ()> {
+        self.reload(new_config, true).await
+    }
+
+    /// Gets the instant at which the probation of the reloaded configuration ends, if any.
+    pub fn probation_deadline(&self) -> Option<Instant> {
+        self.probation.as_ref().map(|probation| probation.deadline)
+    }
+
+    /// Ends the probation of the reloaded configuration.
+    ///
+    /// The error rates of components and the throughput of each sink kept from the previous
+    /// configuration observed during the probation window are compared against those observed
+    /// under the previous configuration. Sinks that sent too few events under the previous
+    /// configuration are not compared. If the pipeline regressed, the previous configuration is
+    /// reloaded, and `Ok(true)` is returned if it was restored.
+    /// Otherwise, or if no configuration is on probation, `Ok(false)` is returned.
+    ///
+    /// # Errors
+    ///
+    /// If the previous configuration cannot be reloaded, and the reloaded configuration cannot be
+    /// fully restored, then `Err(())` is returned.
+    pub async fn end_probation(&mut self) -> Result<bool, ()> {
+        let probation = match self.probation.take() {
+            Some(probation) => probation,
+            None => return Ok(false),
+        };
+
+        // Only the error rates of the components of the reloaded configuration, and the throughput
+        // of the sinks of both configurations, are compared.
+        let components = self
+            .config
+            .sources()
+            .map(|(key, _)| key)
+            .chain(self.config.transforms().map(|(key, _)| key))
+            .chain(self.config.sinks().map(|(key, _)| key))
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        let sinks = self
+            .config
+            .sinks()
+            .filter(|(key, _)| probation.previous_config.sink(key).is_some())
+            .map(|(key, _)| key.to_string())
+            .collect::<Vec<_>>();
+
+        let regression = match (probation.baseline, probation.start, Snapshot::capture()) {
+            (Some(baseline), Some(start), Some(end)) => probation::compare(
+                &probation.options,
+                &components,
+                &sinks,
+                &baseline,
+                &Rates::between(&start, &end),
+            ),
+            _ => None,
+        };
+
+        match regression {
+            Some(regression) => {
+                emit!(VectorReloadRolledBack {
+                    reason: &regression.to_string()
+                });
+                self.reload(probation.previous_config, false).await
+            }
+            None => {
+                info!("Reloaded configuration passed probation.");
+                Ok(false)
+            }
+        }
+    }
+
+    async fn reload(&mut self, new_config: Config, probation: bool) -> Result<bool, ()> {
         info!("Reloading running topology with new configuration.");
 
         if self.config.global != new_config.global {
@@ -240,18 +331,43 @@ impl RunningTopology {
         {
             // If healthchecks are configured for any of the changing/new components, try running
             // them before moving forward with connecting and spawning.  In some cases, healthchecks
-            // failing may be configured as a non-blocking issue and so we'll still continue on.
+            // failing may be configured as a non-blocking issue and so we'll still continue on,
+            // unless the new configuration is going on probation.
+            let probation_options = new_config.reload.probation.filter(|_| probation);
+            let mut healthchecks = new_config.healthchecks;
+            if probation_options.is_some() {
+                healthchecks.require_healthy = true;
+            }
+
             if self
-                .run_healthchecks(&diff, &mut new_pieces, new_config.healthchecks)
+                .run_healthchecks(&diff, &mut new_pieces, healthchecks)
                 .await
             {
                 self.connect_diff(&diff, &mut new_pieces).await;
                 self.spawn_diff(&diff, new_pieces);
-                self.config = new_config;
+                let previous_config = std::mem::replace(&mut self.config, new_config);
+
+                let start = Snapshot::capture();
+                self.probation = probation_options.map(|options| Probation {
+                    previous_config,
+                    options,
+                    baseline: self
+                        .baseline
+                        .as_ref()
+                        .zip(start.as_ref())
+                        .map(|(baseline, start)| Rates::between(baseline, start)),
+                    start: start.clone(),
+                    deadline: Instant::now() + Duration::from_secs(options.window_secs),
+                });
+                self.baseline = start;
 
                 info!("New configuration loaded successfully.");
 
                 return Ok(true);
+            } else if probation_options.is_some() {
+                emit!(VectorReloadRolledBack {
+                    reason: &Regression::Healthchecks.to_string()
+                });
             }
         }
 
@@ -439,6 +555,7 @@ impl RunningTopology {
         for key in &diff.sinks.to_remove {
             debug!(component = %key, "Removing sink.");
             self.remove_inputs(key, diff, new_config).await;
+            self.remove_outputs(key);
         }
 
         // After that, for any changed sinks, we temporarily detach their inputs (not remove) so
@@ -467,6 +584,7 @@ impl RunningTopology {
                 buffer_tx.insert(key.clone(), self.inputs.get(key).unwrap().clone());
             }
             self.remove_inputs(key, diff, new_config).await;
+            self.remove_outputs(key);
         }
 
         // Now that we've disconnected or temporarily detached the inputs to all changed/removed
@@ -550,6 +668,18 @@ impl RunningTopology {
             self.setup_outputs(key, new_pieces).await;
         }
 
+        // Sinks that reroute the events they reject have outputs as well, so those need to be
+        // configured before wiring up any inputs too.
+        for key in diff.sinks.changed_and_added() {
+            if new_pieces.outputs.contains_key(key) {
+                debug!(component = %key, "Configuring outputs for sink.");
+                if let Some(task) = new_pieces.tasks.get(key) {
+                    tap_metadata.insert(key, ("sink", task.typetag().to_string()));
+                }
+                self.setup_outputs(key, new_pieces).await;
+            }
+        }
+
         // Now that all possible outputs are configured, we can start wiring up inputs, starting
         // with transforms.
         for key in diff.transforms.changed_and_added() {
@@ -823,7 +953,9 @@ impl RunningTopology {
         }
 
         let task_name = format!(">> {} ({})", task.typetag(), task.id());
-        let task = handle_errors(task, self.abort_tx.clone()).instrument(task_span);
+        let task = handle_errors(task, self.abort_tx.clone())
+            .track_cpu_time()
+            .instrument(task_span);
         let spawned = spawn_named(task, task_name.as_ref());
         if let Some(previous) = self.tasks.insert(key.clone(), spawned) {
             drop(previous); // detach and forget
@@ -860,7 +992,9 @@ impl RunningTopology {
         }
 
         let task_name = format!(">> {} ({}) >>", task.typetag(), task.id());
-        let task = handle_errors(task, self.abort_tx.clone()).instrument(task_span);
+        let task = handle_errors(task, self.abort_tx.clone())
+            .track_cpu_time()
+            .instrument(task_span);
         let spawned = spawn_named(task, task_name.as_ref());
         if let Some(previous) = self.tasks.insert(key.clone(), spawned) {
             drop(previous); // detach and forget
@@ -898,7 +1032,9 @@ impl RunningTopology {
         }
 
         let task_name = format!("{} ({}) >>", task.typetag(), task.id());
-        let task = handle_errors(task, self.abort_tx.clone()).instrument(task_span.clone());
+        let task = handle_errors(task, self.abort_tx.clone())
+            .track_cpu_time()
+            .instrument(task_span.clone());
         let spawned = spawn_named(task, task_name.as_ref());
         if let Some(previous) = self.tasks.insert(key.clone(), spawned) {
             drop(previous); // detach and forget
@@ -909,7 +1045,9 @@ impl RunningTopology {
 
         // Now spawn the actual source task.
         let source_task = new_pieces.source_tasks.remove(key).unwrap();
-        let source_task = handle_errors(source_task, self.abort_tx.clone()).instrument(task_span);
+        let source_task = handle_errors(source_task, self.abort_tx.clone())
+            .track_cpu_time()
+            .instrument(task_span);
         self.source_tasks
             .insert(key.clone(), spawn_named(source_task, task_name.as_ref()));
     }
@@ -936,5 +1074,14 @@ fn get_changed_outputs(diff: &ConfigDiff, output_ids: Inputs<OutputId>) -> Vec<O
         );
     }
 
+    for sink_key in &diff.sinks.to_change {
+        changed_outputs.extend(
+            output_ids
+                .iter()
+                .filter(|id| &id.component == sink_key)
+                .cloned(),
+        );
+    }
+
     changed_outputs
 }
@@ -3,12 +3,9 @@ use value::Kind;
 
 pub(super) use crate::schema::Definition;
 
-use crate::{
-    config::{
-        ComponentKey, Config, Output, OutputId, SinkConfig, SinkOuter, SourceConfig,
-        TransformConfig,
-    },
-    topology,
+use crate::config::{
+    ComponentKey, Config, DataType, Output, OutputId, SinkConfig, SinkOuter, SourceConfig,
+    TransformConfig,
 };
 
 /// Create a new [`Definition`] by recursively merging all provided inputs into a given component.
@@ -106,6 +103,11 @@ pub fn merged_definition(
                 ));
             }
         }
+        // If the input is a sink output, it carries events the sink rejected unchanged, so the
+        // definitions of the sink's own inputs are passed through as-is (recursively).
+        if let Some(inputs) = config.sink_inputs(key) {
+            definition = definition.merge(merged_definition(inputs, config, cache));
+        }
     }
     definition
 }
@@ -205,6 +207,13 @@ pub(super) fn expanded_definitions(
                 // pipeline definitions.
                 definitions.append(&mut expanded_definitions);
             }
+
+        // A sink output passes rejected events through unchanged, so each of the sink's inputs is
+        // expanded into its own pipeline, as for transforms without a definition of their own.
+        } else if let Some(inputs) = config.sink_inputs(key) {
+            for input in inputs {
+                definitions.append(&mut expanded_definitions(&[input.clone()], config, cache));
+            }
         }
     }
 
@@ -216,10 +225,15 @@ pub(super) fn expanded_definitions(
     definitions
 }
 
-pub(super) fn validate_sink_expectations(
+/// Validates the schema requirement of the given sink against the definitions of the pipelines
+/// feeding into each of its inputs.
+///
+/// Errors name the input the pipeline feeds the sink through, as the component expected to
+/// provide the missing or invalid fields.
+pub(crate) fn validate_sink_expectations(
     key: &ComponentKey,
     sink: &SinkOuter<OutputId>,
-    config: &topology::Config,
+    config: &Config,
 ) -> Result<(), Vec<String>> {
     let mut errors = vec![];
 
@@ -227,25 +241,37 @@ pub(super) fn validate_sink_expectations(
     // this sink.
     let input = sink.inner.input();
     let requirement = input.schema_requirement();
+    if requirement.is_empty() {
+        return Ok(());
+    }
 
-    // Get all pipeline definitions feeding into this sink.
     let mut cache = HashMap::default();
-    let definitions = expanded_definitions(&sink.inputs, config, &mut cache);
-
-    // Validate each individual definition against the sink requirement.
-    for definition in definitions {
-        if let Err(err) = requirement.validate(&definition) {
-            errors.append(
-                &mut err
-                    .errors()
-                    .iter()
-                    .cloned()
-                    .map(|err| format!("schema error in component {}: {}", key, err))
-                    .collect(),
-            );
+    let mut merged_cache = HashMap::default();
+
+    for sink_input in &sink.inputs {
+        // Schema definitions only describe log events, so inputs that can't carry any have nothing
+        // to validate.
+        if !output_has_logs(sink_input, config, &mut merged_cache) {
+            continue;
+        }
+
+        // Validate each individual pipeline definition feeding into this input against the sink
+        // requirement.
+        for definition in expanded_definitions(&[sink_input.clone()], config, &mut cache) {
+            if let Err(err) = requirement.validate(&definition) {
+                errors.extend(err.errors().iter().map(|err| {
+                    format!(
+                        "Input \"{}\" of sink \"{}\" fails its schema requirement: {}",
+                        sink_input, key, err
+                    )
+                }));
+            }
         }
     }
 
+    // Multiple pipelines feeding into the same input can fail in the same way.
+    errors.dedup();
+
     if !errors.is_empty() {
         return Err(errors);
     }
@@ -253,6 +279,30 @@ pub(super) fn validate_sink_expectations(
     Ok(())
 }
 
+/// Whether the given output can carry log events.
+fn output_has_logs(
+    output_id: &OutputId,
+    config: &Config,
+    cache: &mut HashMap<(bool, Vec<OutputId>), Definition>,
+) -> bool {
+    let key = &output_id.component;
+    let output = if let Ok(output) = config.source_output_for_port(key, &output_id.port) {
+        output
+    } else if let Some(inputs) = config.transform_inputs(key) {
+        let merged_definition = merged_definition(inputs, config, cache);
+        config
+            .transform_output_for_port(key, &output_id.port, &merged_definition)
+            .ok()
+            .flatten()
+    } else {
+        config
+            .sink(key)
+            .and_then(|sink| get_output_for_port(sink.inner.outputs(), &output_id.port))
+    };
+
+    output.map_or(false, |output| output.ty.contains(DataType::Log))
+}
+
 pub trait ComponentContainer {
     fn schema_enabled(&self) -> bool;
 
@@ -266,6 +316,13 @@ pub trait ComponentContainer {
         merged_definition: &Definition,
     ) -> Option<Vec<Output>>;
 
+    /// Gets the inputs of the sink with the given key, if it exists.
+    ///
+    /// Only sinks with outputs can feed into other components.
+    fn sink_inputs(&self, _key: &ComponentKey) -> Option<&[OutputId]> {
+        None
+    }
+
     /// Gets the transform output for the given port.
     ///
     /// Returns Err(()) if there is no transform with the given key
@@ -328,6 +385,10 @@ impl ComponentContainer for Config {
         self.transform(key)
             .map(|source| source.inner.outputs(merged_definition))
     }
+
+    fn sink_inputs(&self, key: &ComponentKey) -> Option<&[OutputId]> {
+        self.sink(key).map(|sink| &sink.inputs[..])
+    }
 }
 
 #[cfg(test)]
@@ -1,4 +1,5 @@
 use tokio::sync::oneshot::{channel, Receiver};
+use vector_common::assert_event_data_eq;
 use vector_core::event::{Event, EventArray, EventContainer, LogEvent};
 
 use crate::{
@@ -54,7 +55,7 @@ async fn test_function_transform_single_event() {
         assert_eq!(events.len(), 1);
 
         let event = events.remove(0);
-        assert_eq!(original_event, event);
+        assert_event_data_eq!(original_event, event);
     })
     .await;
 }
@@ -73,7 +74,7 @@ async fn test_sync_transform_single_event() {
         assert_eq!(events.len(), 1);
 
         let event = events.remove(0);
-        assert_eq!(original_event, event);
+        assert_event_data_eq!(original_event, event);
     })
     .await;
 }
@@ -91,7 +92,7 @@ async fn test_task_transform_single_event() {
         assert_eq!(events.len(), 1);
 
         let event = events.remove(0);
-        assert_eq!(original_event, event);
+        assert_event_data_eq!(original_event, event);
     })
     .await;
 }
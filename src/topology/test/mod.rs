@@ -25,6 +25,7 @@ use tokio::{
     time::{sleep, Duration},
 };
 use vector_buffers::{BufferConfig, BufferType, WhenFull};
+use vector_common::assert_event_data_eq;
 
 mod backpressure;
 mod compliance;
@@ -146,7 +147,7 @@ async fn topology_source_and_sink() {
 
     let res = out1.flat_map(into_event_stream).collect::<Vec<_>>().await;
 
-    assert_eq!(vec![event], res);
+    assert_event_data_eq!(vec![event], res);
 }
 
 #[tokio::test]
@@ -177,8 +178,8 @@ async fn topology_multiple_sources() {
 
     topology.stop().await;
 
-    assert_eq!(out_event1, Some(event1.into()));
-    assert_eq!(out_event2, Some(event2.into()));
+    assert_event_data_eq!(out_event1, Some(EventArray::from(event1)));
+    assert_event_data_eq!(out_event2, Some(EventArray::from(event2)));
 }
 
 #[tokio::test]
@@ -211,8 +212,8 @@ async fn topology_multiple_sinks() {
 
     // We should see that both sinks got the exact same event:
     let expected = vec![event];
-    assert_eq!(expected, res1);
-    assert_eq!(expected, res2);
+    assert_event_data_eq!(expected, res1);
+    assert_event_data_eq!(expected, res2);
 }
 
 #[tokio::test]
@@ -284,7 +285,7 @@ async fn topology_remove_one_source() {
     topology.stop().await;
 
     let res = h_out1.await.unwrap();
-    assert_eq!(vec![event1], res);
+    assert_event_data_eq!(vec![event1], res);
 }
 
 #[tokio::test]
@@ -320,8 +321,8 @@ async fn topology_remove_one_sink() {
     let res1 = out1.flat_map(into_event_stream).collect::<Vec<_>>().await;
     let res2 = out2.flat_map(into_event_stream).collect::<Vec<_>>().await;
 
-    assert_eq!(vec![event], res1);
-    assert_eq!(Vec::<Event>::new(), res2);
+    assert_event_data_eq!(vec![event], res1);
+    assert_event_data_eq!(Vec::<Event>::new(), res2);
 }
 
 #[tokio::test]
@@ -427,8 +428,8 @@ async fn topology_swap_source() {
     // We should see that despite replacing a sink of the same name, sending to source #1 -- which
     // the sink at `out1` was initially connected to -- does not send to either sink #1 or sink #2,
     // as we've removed it from the topology prior to the sends.
-    assert_eq!(Vec::<Event>::new(), res1);
-    assert_eq!(vec![event2], res2);
+    assert_event_data_eq!(Vec::<Event>::new(), res1);
+    assert_event_data_eq!(vec![event2], res2);
 }
 
 #[tokio::test]
@@ -537,8 +538,8 @@ async fn topology_swap_sink() {
     // We should see that since source #1 and #2 were the same, despite both being added as `in1`,
     // that source #1 was not rebuilt, so the item sent to source #1 was the item that got sent to
     // the new sink, which _was_ rebuilt:
-    assert_eq!(Vec::<Event>::new(), res1);
-    assert_eq!(vec![event1], res2);
+    assert_event_data_eq!(Vec::<Event>::new(), res1);
+    assert_event_data_eq!(vec![event1], res2);
 }
 
 #[ignore] // TODO: issue #2186
@@ -643,7 +644,7 @@ async fn topology_rebuild_connected() {
     topology.stop().await;
 
     let res = h_out1.await.unwrap();
-    assert_eq!(vec![event1, event2], res);
+    assert_event_data_eq!(vec![event1, event2], res);
 }
 
 #[tokio::test]
@@ -692,8 +693,8 @@ async fn topology_rebuild_connected_transform() {
 
     let res1 = h_out1.await.unwrap();
     let res2 = h_out2.await.unwrap();
-    assert_eq!(Vec::<Event>::new(), res1);
-    assert_eq!(vec![event], res2);
+    assert_event_data_eq!(Vec::<Event>::new(), res1);
+    assert_event_data_eq!(vec![event], res2);
 }
 
 #[tokio::test]
@@ -11,13 +11,17 @@ use vector_buffers::{BufferConfig, BufferType, WhenFull};
 use vector_core::config::ComponentKey;
 
 use crate::{
-    config::Config,
+    config::{Config, ProbationOptions},
     sinks::prometheus::exporter::PrometheusExporterConfig,
     sources::{
         internal_metrics::InternalMetricsConfig, prometheus::PrometheusRemoteWriteConfig,
         splunk_hec::SplunkConfig,
     },
-    test_util::{self, mock::basic_sink, next_addr, start_topology, temp_dir, wait_for_tcp},
+    test_util::{
+        self,
+        mock::{basic_sink, basic_source},
+        next_addr, start_topology, temp_dir, wait_for_tcp,
+    },
 };
 
 fn internal_metrics_source() -> InternalMetricsConfig {
@@ -311,3 +315,67 @@ async fn reload_sink_test(
         _ = crash_stream.next() => panic!(),
     }
 }
+
+fn sink_sent_events(component_id: &'static str, count: u64) {
+    metrics::counter!(
+        "component_sent_events_total", count,
+        "component_id" => component_id,
+        "component_kind" => "sink",
+    );
+}
+
+// The component IDs are unique to this test, as the metrics registry may be shared with other
+// tests, and the clock is paused so that the rates compared are exact.
+#[tokio::test(start_paused = true)]
+async fn topology_reload_probation() {
+    test_util::trace_init();
+    crate::metrics::init_test();
+
+    let (_in, source) = basic_source();
+    let mut old_config = Config::builder();
+    old_config.add_source("probation_in", source);
+    old_config.add_sink("probation_out", &["probation_in"], basic_sink(1).1);
+
+    let mut new_config = old_config.clone();
+    new_config.reload.probation = Some(ProbationOptions {
+        window_secs: 60,
+        max_error_rate_increase: 1.0,
+        min_throughput_ratio: 0.5,
+        min_baseline_events: 100,
+    });
+    new_config.add_sink("probation_added", &["probation_in"], basic_sink(1).1);
+    let new_config = new_config.build().unwrap();
+    let added = ComponentKey::from("probation_added");
+
+    let (mut topology, _crash) = start_topology(old_config.build().unwrap(), false).await;
+    assert!(!topology.end_probation().await.unwrap());
+
+    // The sink kept by the reload stops sending events during probation, which rolls it back.
+    sink_sent_events("probation_out", 1000);
+    tokio::time::advance(Duration::from_secs(10)).await;
+    assert!(topology
+        .reload_config_and_respawn(new_config.clone())
+        .await
+        .unwrap());
+    assert!(topology.probation_deadline().is_some());
+    assert!(topology.config().sink(&added).is_some());
+
+    tokio::time::advance(Duration::from_secs(10)).await;
+    assert!(topology.end_probation().await.unwrap());
+    assert!(topology.probation_deadline().is_none());
+    assert!(topology.config().sink(&added).is_none());
+
+    // The sink keeps sending events during probation.
+    sink_sent_events("probation_out", 1000);
+    tokio::time::advance(Duration::from_secs(10)).await;
+    assert!(topology
+        .reload_config_and_respawn(new_config)
+        .await
+        .unwrap());
+
+    sink_sent_events("probation_out", 1000);
+    tokio::time::advance(Duration::from_secs(10)).await;
+    assert!(!topology.end_probation().await.unwrap());
+    assert!(topology.probation_deadline().is_none());
+    assert!(topology.config().sink(&added).is_some());
+}
@@ -26,6 +26,65 @@ pub struct AggregateConfig {
     /// Over this period metrics with the same series data (name, namespace, tags, …) will be aggregated.
     #[serde(default = "default_interval_ms")]
     pub interval_ms: u64,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub mode: AggregationMode,
+
+    /// Tags removed from metrics before they are aggregated.
+    ///
+    /// Metrics whose series only differ by these tags are aggregated together, for example to roll
+    /// up the metrics of every host of a service by dropping the `host` tag.
+    #[serde(default)]
+    pub drop_tags: Vec<String>,
+}
+
+/// The function used to aggregate counters and gauges of the same series.
+///
+/// Metrics of other types are always aggregated as in `auto` mode.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum AggregationMode {
+    /// Sums incremental metrics and keeps the latest value of absolute metrics.
+    Auto,
+
+    /// Sums the values.
+    Sum,
+
+    /// Counts the metrics, emitted as a gauge.
+    Count,
+
+    /// Averages the values, emitted as a gauge.
+    Mean,
+
+    /// Keeps the smallest value, emitted as a gauge.
+    Min,
+
+    /// Keeps the largest value, emitted as a gauge.
+    Max,
+
+    /// Computes the population standard deviation of the values, emitted as a gauge.
+    Stdev,
+
+    /// Keeps the first value.
+    First,
+
+    /// Keeps the latest value.
+    Latest,
+
+    /// Computes the change per second over the interval, emitted as a gauge.
+    ///
+    /// The change is the sum of incremental values, the sum of the increases of absolute counters,
+    /// or the difference between the first and the latest values of absolute gauges. A decreasing
+    /// absolute counter is considered to have been reset.
+    Rate,
+}
+
+impl Default for AggregationMode {
+    fn default() -> Self {
+        Self::Auto
+    }
 }
 
 const fn default_interval_ms() -> u64 {
@@ -51,52 +110,231 @@ impl TransformConfig for AggregateConfig {
 
 type MetricEntry = (metric::MetricData, EventMetadata);
 
+/// The aggregated state of a series.
+#[derive(Debug)]
+enum Aggregation {
+    /// Metrics aggregated as in `auto` mode.
+    Merged(MetricEntry),
+
+    /// Statistics of the values of counters or gauges.
+    Stats(Statistics, EventMetadata),
+}
+
+/// Running statistics of the values of a counter or gauge series.
+#[derive(Debug)]
+struct Statistics {
+    /// The latest metric data, used as the base of the flushed metric.
+    data: metric::MetricData,
+    count: u64,
+    sum: f64,
+    min: f64,
+    max: f64,
+    first: f64,
+    latest: f64,
+    /// The increase of absolute counters, restarting from zero whenever they decrease.
+    increase: f64,
+    mean: f64,
+    /// The sum of the squared differences from the mean, see Welford's online algorithm.
+    m2: f64,
+}
+
+impl Statistics {
+    fn new(data: metric::MetricData, value: f64) -> Self {
+        Self {
+            data,
+            count: 1,
+            sum: value,
+            min: value,
+            max: value,
+            first: value,
+            latest: value,
+            increase: 0.0,
+            mean: value,
+            m2: 0.0,
+        }
+    }
+
+    /// Adds a value to the statistics, returns `false` if the kind or type of the metric doesn't
+    /// match the ones of the series.
+    fn update(&mut self, data: &metric::MetricData, value: f64) -> bool {
+        if self.data.kind != data.kind
+            || std::mem::discriminant(&self.data.value) != std::mem::discriminant(&data.value)
+        {
+            return false;
+        }
+
+        self.data.time = data.time;
+        self.count += 1;
+        self.sum += value;
+        self.min = self.min.min(value);
+        self.max = self.max.max(value);
+        self.increase += if value < self.latest {
+            // The counter was reset, so it increased from zero.
+            value
+        } else {
+            value - self.latest
+        };
+        self.latest = value;
+
+        let delta = value - self.mean;
+        self.mean += delta / self.count as f64;
+        self.m2 += delta * (value - self.mean);
+
+        true
+    }
+
+    fn finish(self, mode: AggregationMode, interval: Duration) -> metric::MetricData {
+        let time = self.data.time;
+        let gauge = |value| {
+            metric::MetricData::from_parts(
+                time,
+                metric::MetricKind::Absolute,
+                metric::MetricValue::Gauge { value },
+            )
+        };
+
+        let value = match mode {
+            AggregationMode::Auto | AggregationMode::Sum => self.sum,
+            AggregationMode::First => self.first,
+            AggregationMode::Latest => self.latest,
+            AggregationMode::Count => return gauge(self.count as f64),
+            AggregationMode::Mean => return gauge(self.mean),
+            AggregationMode::Min => return gauge(self.min),
+            AggregationMode::Max => return gauge(self.max),
+            AggregationMode::Stdev => return gauge((self.m2 / self.count as f64).sqrt()),
+            AggregationMode::Rate => {
+                let change = match (self.data.kind, &self.data.value) {
+                    (metric::MetricKind::Incremental, _) => self.sum,
+                    (metric::MetricKind::Absolute, metric::MetricValue::Counter { .. }) => {
+                        self.increase
+                    }
+                    (metric::MetricKind::Absolute, _) => self.latest - self.first,
+                };
+                return gauge(change / interval.as_secs_f64());
+            }
+        };
+
+        let mut data = self.data;
+        match data.value_mut() {
+            metric::MetricValue::Counter { value: existing }
+            | metric::MetricValue::Gauge { value: existing } => *existing = value,
+            _ => unreachable!("statistics are only kept for counters and gauges"),
+        }
+        data
+    }
+}
+
+/// Returns the value of counters and gauges.
+const fn scalar_value(value: &metric::MetricValue) -> Option<f64> {
+    match value {
+        metric::MetricValue::Counter { value } | metric::MetricValue::Gauge { value } => {
+            Some(*value)
+        }
+        _ => None,
+    }
+}
+
 #[derive(Debug)]
 pub struct Aggregate {
     interval: Duration,
-    map: HashMap<metric::MetricSeries, MetricEntry>,
+    mode: AggregationMode,
+    drop_tags: Vec<String>,
+    map: HashMap<metric::MetricSeries, Aggregation>,
 }
 
 impl Aggregate {
     pub fn new(config: &AggregateConfig) -> crate::Result<Self> {
         Ok(Self {
             interval: Duration::from_millis(config.interval_ms),
+            mode: config.mode,
+            drop_tags: config.drop_tags.clone(),
             map: Default::default(),
         })
     }
 
     fn record(&mut self, event: Event) {
-        let (series, data, metadata) = event.into_metric().into_parts();
+        let (mut series, data, metadata) = event.into_metric().into_parts();
+        for tag in &self.drop_tags {
+            series.remove_tag(tag);
+        }
+
+        match scalar_value(&data.value) {
+            Some(value) if self.mode != AggregationMode::Auto => {
+                self.record_value(series, data, metadata, value);
+            }
+            _ => self.record_merged(series, data, metadata),
+        }
 
+        emit!(AggregateEventRecorded);
+    }
+
+    fn record_merged(
+        &mut self,
+        series: metric::MetricSeries,
+        data: metric::MetricData,
+        metadata: EventMetadata,
+    ) {
         match data.kind {
             metric::MetricKind::Incremental => match self.map.entry(series) {
                 Entry::Occupied(mut entry) => {
                     let existing = entry.get_mut();
                     // In order to update (add) the new and old kind's must match
-                    if existing.0.kind == data.kind && existing.0.update(&data) {
-                        existing.1.merge(metadata);
-                    } else {
-                        emit!(AggregateUpdateFailed);
-                        *existing = (data, metadata);
+                    if let Aggregation::Merged(existing) = existing {
+                        if existing.0.kind == data.kind && existing.0.update(&data) {
+                            existing.1.merge(metadata);
+                            return;
+                        }
                     }
+                    emit!(AggregateUpdateFailed);
+                    *existing = Aggregation::Merged((data, metadata));
                 }
                 Entry::Vacant(entry) => {
-                    entry.insert((data, metadata));
+                    entry.insert(Aggregation::Merged((data, metadata)));
                 }
             },
             metric::MetricKind::Absolute => {
                 // Always replace/store
-                self.map.insert(series, (data, metadata));
+                self.map
+                    .insert(series, Aggregation::Merged((data, metadata)));
             }
         };
+    }
 
-        emit!(AggregateEventRecorded);
+    fn record_value(
+        &mut self,
+        series: metric::MetricSeries,
+        data: metric::MetricData,
+        metadata: EventMetadata,
+        value: f64,
+    ) {
+        match self.map.entry(series) {
+            Entry::Occupied(mut entry) => {
+                let existing = entry.get_mut();
+                if let Aggregation::Stats(stats, existing_metadata) = existing {
+                    if stats.update(&data, value) {
+                        existing_metadata.merge(metadata);
+                        return;
+                    }
+                }
+                emit!(AggregateUpdateFailed);
+                *existing = Aggregation::Stats(Statistics::new(data, value), metadata);
+            }
+            Entry::Vacant(entry) => {
+                entry.insert(Aggregation::Stats(Statistics::new(data, value), metadata));
+            }
+        }
     }
 
     fn flush_into(&mut self, output: &mut Vec<Event>) {
         let map = std::mem::take(&mut self.map);
         for (series, entry) in map.into_iter() {
-            let metric = metric::Metric::from_parts(series, entry.0, entry.1);
+            let (data, metadata) = match entry {
+                Aggregation::Merged(entry) => entry,
+                Aggregation::Stats(stats, metadata) => {
+                    (stats.finish(self.mode, self.interval), metadata)
+                }
+            };
+            let metric = metric::Metric::from_parts(series, data, metadata);
             output.push(Event::Metric(metric));
         }
 
@@ -147,6 +385,7 @@ mod tests {
     use futures::stream;
     use tokio::sync::mpsc;
     use tokio_stream::wrappers::ReceiverStream;
+    use vector_core::metric_tags;
 
     use super::*;
     use crate::{
@@ -172,6 +411,7 @@ mod tests {
     fn incremental() {
         let mut agg = Aggregate::new(&AggregateConfig {
             interval_ms: 1000_u64,
+            ..Default::default()
         })
         .unwrap();
 
@@ -242,6 +482,7 @@ mod tests {
     fn absolute() {
         let mut agg = Aggregate::new(&AggregateConfig {
             interval_ms: 1000_u64,
+            ..Default::default()
         })
         .unwrap();
 
@@ -307,6 +548,7 @@ mod tests {
     fn conflicting_value_type() {
         let mut agg = Aggregate::new(&AggregateConfig {
             interval_ms: 1000_u64,
+            ..Default::default()
         })
         .unwrap();
 
@@ -364,6 +606,7 @@ mod tests {
     fn conflicting_kinds() {
         let mut agg = Aggregate::new(&AggregateConfig {
             interval_ms: 1000_u64,
+            ..Default::default()
         })
         .unwrap();
 
@@ -414,6 +657,165 @@ mod tests {
         assert_eq!(&summed, &out[0]);
     }
 
+    fn record_all(config: &AggregateConfig, inputs: Vec<Event>) -> Vec<Event> {
+        let mut agg = Aggregate::new(config).unwrap();
+        for event in inputs {
+            agg.record(event);
+        }
+        let mut out = vec![];
+        agg.flush_into(&mut out);
+        out
+    }
+
+    #[test]
+    fn modes() {
+        let gauge = |value| {
+            make_metric(
+                "gauge_a",
+                metric::MetricKind::Absolute,
+                metric::MetricValue::Gauge { value },
+            )
+        };
+        let inputs = [2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0]
+            .into_iter()
+            .map(gauge)
+            .collect::<Vec<_>>();
+
+        for (mode, expected) in [
+            (AggregationMode::Auto, 9.0),
+            (AggregationMode::Sum, 40.0),
+            (AggregationMode::Count, 8.0),
+            (AggregationMode::Mean, 5.0),
+            (AggregationMode::Min, 2.0),
+            (AggregationMode::Max, 9.0),
+            (AggregationMode::Stdev, 2.0),
+            (AggregationMode::First, 2.0),
+            (AggregationMode::Latest, 9.0),
+            (AggregationMode::Rate, 3.5),
+        ] {
+            let config = AggregateConfig {
+                interval_ms: 2000_u64,
+                mode,
+                ..Default::default()
+            };
+            let out = record_all(&config, inputs.clone());
+            assert_eq!(vec![gauge(expected)], out, "mode={:?}", mode);
+        }
+    }
+
+    #[test]
+    fn modes_keep_kind_or_emit_gauges() {
+        let counter = |value| {
+            make_metric(
+                "counter_a",
+                metric::MetricKind::Incremental,
+                metric::MetricValue::Counter { value },
+            )
+        };
+        let inputs = vec![counter(1.0), counter(2.0), counter(3.0)];
+
+        let config = AggregateConfig {
+            interval_ms: 2000_u64,
+            mode: AggregationMode::Max,
+            ..Default::default()
+        };
+        let out = record_all(&config, inputs.clone());
+        assert_eq!(
+            vec![make_metric(
+                "counter_a",
+                metric::MetricKind::Absolute,
+                metric::MetricValue::Gauge { value: 3.0 },
+            )],
+            out
+        );
+
+        let config = AggregateConfig {
+            interval_ms: 2000_u64,
+            mode: AggregationMode::Rate,
+            ..Default::default()
+        };
+        let out = record_all(&config, inputs.clone());
+        assert_eq!(
+            vec![make_metric(
+                "counter_a",
+                metric::MetricKind::Absolute,
+                metric::MetricValue::Gauge { value: 3.0 },
+            )],
+            out
+        );
+
+        let config = AggregateConfig {
+            interval_ms: 2000_u64,
+            mode: AggregationMode::First,
+            ..Default::default()
+        };
+        let out = record_all(&config, inputs);
+        assert_eq!(vec![counter(1.0)], out);
+    }
+
+    #[test]
+    fn rate_of_reset_counter() {
+        let counter = |value| {
+            make_metric(
+                "counter_a",
+                metric::MetricKind::Absolute,
+                metric::MetricValue::Counter { value },
+            )
+        };
+        let config = AggregateConfig {
+            interval_ms: 1000_u64,
+            mode: AggregationMode::Rate,
+            ..Default::default()
+        };
+
+        let rate = |value| {
+            make_metric(
+                "counter_a",
+                metric::MetricKind::Absolute,
+                metric::MetricValue::Gauge { value },
+            )
+        };
+
+        let out = record_all(&config, vec![counter(10.0), counter(20.0), counter(5.0)]);
+        assert_eq!(vec![rate(15.0)], out);
+
+        // The counter is reset within the interval.
+        let out = record_all(&config, vec![counter(10.0), counter(5.0), counter(30.0)]);
+        assert_eq!(vec![rate(30.0)], out);
+    }
+
+    #[test]
+    fn drop_tags() {
+        let counter = |host, value| {
+            Event::Metric(
+                Metric::new(
+                    "requests",
+                    metric::MetricKind::Incremental,
+                    metric::MetricValue::Counter { value },
+                )
+                .with_tags(Some(metric_tags!("host" => host, "service" => "api"))),
+            )
+        };
+        let config = AggregateConfig {
+            interval_ms: 1000_u64,
+            mode: AggregationMode::Sum,
+            drop_tags: vec!["host".into()],
+        };
+
+        let out = record_all(&config, vec![counter("a", 1.0), counter("b", 2.0)]);
+        assert_eq!(
+            vec![Event::Metric(
+                Metric::new(
+                    "requests",
+                    metric::MetricKind::Incremental,
+                    metric::MetricValue::Counter { value: 3.0 },
+                )
+                .with_tags(Some(metric_tags!("service" => "api"))),
+            )],
+            out
+        );
+    }
+
     #[tokio::test]
     async fn transform_shutdown() {
         let agg = toml::from_str::<AggregateConfig>(
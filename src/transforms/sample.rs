@@ -1,3 +1,10 @@
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+use serde_with::serde_as;
+use snafu::Snafu;
 use vector_config::configurable_component;
 
 use crate::{
@@ -10,6 +17,7 @@ use crate::{
 };
 
 /// Configuration for the `sample` transform.
+#[serde_as]
 #[configurable_component(transform("sample"))]
 #[derive(Clone, Debug)]
 #[serde(deny_unknown_fields)]
@@ -18,6 +26,9 @@ pub struct SampleConfig {
     ///
     /// For example, `rate = 10` means 1 out of every 10 events will be forwarded and the rest will
     /// be dropped.
+    ///
+    /// When `target_events_per_sec` is set, this is the initial rate used for each value of
+    /// `key_field` until its first window has elapsed.
     pub rate: u64,
 
     /// The name of the log field whose value will be hashed to determine if the event should be
@@ -31,6 +42,28 @@ pub struct SampleConfig {
 
     /// A logical condition used to exclude events from sampling.
     pub exclude: Option<AnyCondition>,
+
+    /// The number of events per second to forward for each value of `key_field`.
+    ///
+    /// When set, the rate is adjusted at the end of every window so that each value of `key_field`
+    /// forwards roughly this many events per second, regardless of how much traffic it receives.
+    /// Events are then count rated within each value of `key_field`, rather than being hashed. If
+    /// `key_field` is left unspecified, all events share a single rate.
+    ///
+    /// Each forwarded event has its effective rate written to the `sample_rate` field, so it can
+    /// be re-weighted downstream.
+    #[configurable(metadata(docs::examples = 100.0))]
+    pub target_events_per_sec: Option<f64>,
+
+    /// The time window over which the rate is adjusted when `target_events_per_sec` is set, in
+    /// seconds.
+    #[serde(default = "default_window_secs")]
+    #[serde_as(as = "serde_with::DurationSeconds<f64>")]
+    pub window_secs: Duration,
+}
+
+const fn default_window_secs() -> Duration {
+    Duration::from_secs(1)
 }
 
 impl GenerateConfig for SampleConfig {
@@ -39,6 +72,8 @@ impl GenerateConfig for SampleConfig {
             rate: 10,
             key_field: None,
             exclude: None::<AnyCondition>,
+            target_events_per_sec: None,
+            window_secs: default_window_secs(),
         })
         .unwrap()
     }
@@ -47,14 +82,27 @@ impl GenerateConfig for SampleConfig {
 #[async_trait::async_trait]
 impl TransformConfig for SampleConfig {
     async fn build(&self, context: &TransformContext) -> crate::Result<Transform> {
-        Ok(Transform::function(Sample::new(
+        if self.rate == 0 {
+            return Err(Box::new(ConfigError::NonZeroRate));
+        }
+
+        let mut sample = Sample::new(
             self.rate,
             self.key_field.clone(),
             self.exclude
                 .as_ref()
                 .map(|condition| condition.build(&context.enrichment_tables))
                 .transpose()?,
-        )))
+        );
+
+        if let Some(events_per_sec) = self.target_events_per_sec {
+            if events_per_sec.is_nan() || events_per_sec <= 0.0 || self.window_secs.is_zero() {
+                return Err(Box::new(ConfigError::NonPositiveTarget));
+            }
+            sample = sample.with_target_rate(events_per_sec, self.window_secs);
+        }
+
+        Ok(Transform::function(sample))
     }
 
     fn input(&self) -> Input {
@@ -67,12 +115,21 @@ impl TransformConfig for SampleConfig {
     }
 }
 
+#[derive(Debug, Snafu)]
+pub enum ConfigError {
+    #[snafu(display("`rate` must be non-zero"))]
+    NonZeroRate,
+    #[snafu(display("`target_events_per_sec` and `window_secs` must be positive"))]
+    NonPositiveTarget,
+}
+
 #[derive(Clone)]
 pub struct Sample {
     rate: u64,
     key_field: Option<String>,
     exclude: Option<Condition>,
     count: u64,
+    target: Option<TargetRate>,
 }
 
 impl Sample {
@@ -82,7 +139,73 @@ impl Sample {
             key_field,
             exclude,
             count: 0,
+            target: None,
+        }
+    }
+
+    /// Adjusts the rate for each value of `key_field` once per `window`, aiming to forward
+    /// `events_per_sec` events per second for each of them.
+    pub fn with_target_rate(mut self, events_per_sec: f64, window: Duration) -> Self {
+        self.target = Some(TargetRate {
+            events_per_sec,
+            window,
+            initial_rate: self.rate,
+            window_start: Instant::now(),
+            keys: HashMap::new(),
+        });
+        self
+    }
+}
+
+/// Per-key rate state for target-rate sampling.
+#[derive(Clone, Copy, Debug)]
+struct KeyRate {
+    rate: u64,
+    seen: u64,
+}
+
+#[derive(Clone, Debug)]
+struct TargetRate {
+    events_per_sec: f64,
+    window: Duration,
+    initial_rate: u64,
+    window_start: Instant,
+    keys: HashMap<Option<String>, KeyRate>,
+}
+
+impl TargetRate {
+    /// Returns the effective rate if the event should be kept.
+    fn sample(&mut self, key: Option<String>, now: Instant) -> Option<u64> {
+        self.roll_window(now);
+
+        let initial_rate = self.initial_rate;
+        let state = self.keys.entry(key).or_insert(KeyRate {
+            rate: initial_rate,
+            seen: 0,
+        });
+        let keep = state.seen % state.rate == 0;
+        state.seen += 1;
+        keep.then_some(state.rate)
+    }
+
+    /// Once the window has elapsed, recomputes each key's rate from the number of events seen
+    /// during it, and forgets keys that did not see any.
+    fn roll_window(&mut self, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.window_start);
+        if elapsed < self.window {
+            return;
         }
+
+        let budget = self.events_per_sec * elapsed.as_secs_f64();
+        self.keys.retain(|_, state| {
+            if state.seen == 0 {
+                return false;
+            }
+            state.rate = ((state.seen as f64 / budget).ceil() as u64).max(1);
+            state.seen = 0;
+            true
+        });
+        self.window_start = now;
     }
 }
 
@@ -112,6 +235,17 @@ impl FunctionTransform for Sample {
             })
             .map(|v| v.to_string_lossy());
 
+        if let Some(target) = self.target.as_mut() {
+            match target.sample(value, Instant::now()) {
+                Some(rate) => {
+                    insert_sample_rate(&mut event, rate);
+                    output.push(event);
+                }
+                None => emit!(SampleEventDiscarded),
+            }
+            return;
+        }
+
         let num = if let Some(value) = value {
             seahash::hash(value.as_bytes())
         } else {
@@ -121,11 +255,7 @@ impl FunctionTransform for Sample {
         self.count = (self.count + 1) % self.rate;
 
         if num % self.rate == 0 {
-            match event {
-                Event::Log(ref mut event) => event.insert("sample_rate", self.rate.to_string()),
-                Event::Trace(ref mut event) => event.insert("sample_rate", self.rate.to_string()),
-                Event::Metric(_) => panic!("component can never receive metric events"),
-            };
+            insert_sample_rate(&mut event, self.rate);
             output.push(event);
         } else {
             emit!(SampleEventDiscarded);
@@ -133,6 +263,14 @@ impl FunctionTransform for Sample {
     }
 }
 
+fn insert_sample_rate(event: &mut Event, rate: u64) {
+    match event {
+        Event::Log(event) => event.insert("sample_rate", rate.to_string()),
+        Event::Trace(event) => event.insert("sample_rate", rate.to_string()),
+        Event::Metric(_) => panic!("component can never receive metric events"),
+    };
+}
+
 #[cfg(test)]
 mod tests {
     use approx::assert_relative_eq;
@@ -338,6 +476,59 @@ mod tests {
         assert_eq!(total_passed, 1);
     }
 
+    #[test]
+    fn target_rate_adjusts_per_key() {
+        let mut sampler = Sample::new(1, Some("service".into()), None)
+            .with_target_rate(10.0, Duration::from_secs(1));
+        let start = sampler.target.as_ref().unwrap().window_start;
+
+        let event = |service: &str| {
+            let mut log = LogEvent::from("hello");
+            log.insert("service", service);
+            Event::Log(log)
+        };
+        let run = |sampler: &mut Sample, service: &str, count: usize, now: Instant| {
+            let target = sampler.target.as_mut().unwrap();
+            (0..count)
+                .filter_map(|_| target.sample(Some(service.into()), now))
+                .collect::<Vec<_>>()
+        };
+
+        // The initial rate forwards everything during the first window.
+        assert_eq!(run(&mut sampler, "busy", 1000, start).len(), 1000);
+        assert_eq!(run(&mut sampler, "quiet", 5, start).len(), 5);
+
+        // After the window, the busy key is sampled down to its budget and the quiet one is not.
+        let next = start + Duration::from_secs(1);
+        let busy = run(&mut sampler, "busy", 1000, next);
+        assert_eq!(busy.len(), 10);
+        assert!(busy.iter().all(|rate| *rate == 100));
+        let quiet = run(&mut sampler, "quiet", 5, next);
+        assert_eq!(quiet, vec![1; 5]);
+
+        // Kept events are annotated with the effective rate of their key.
+        let passing = transform_one(&mut sampler, event("busy")).unwrap();
+        assert_eq!(passing.as_log()["sample_rate"], "100".into());
+
+        // Keys that go idle for a window are forgotten and start over at the initial rate.
+        run(&mut sampler, "busy", 1, next + Duration::from_secs(1));
+        run(&mut sampler, "busy", 1, next + Duration::from_secs(2));
+        let target = sampler.target.as_ref().unwrap();
+        assert!(!target.keys.contains_key(&Some("quiet".into())));
+    }
+
+    #[tokio::test]
+    async fn rejects_invalid_target() {
+        let config = SampleConfig {
+            rate: 1,
+            key_field: None,
+            exclude: None,
+            target_events_per_sec: Some(0.0),
+            window_secs: default_window_secs(),
+        };
+        assert!(config.build(&TransformContext::default()).await.is_err());
+    }
+
     #[tokio::test]
     async fn emits_internal_events() {
         assert_transform_compliance(async move {
@@ -345,6 +536,8 @@ mod tests {
                 rate: 1,
                 key_field: None,
                 exclude: None,
+                target_events_per_sec: None,
+                window_secs: default_window_secs(),
             };
             let (tx, rx) = mpsc::channel(1);
             let (topology, mut out) = create_topology(ReceiverStream::new(rx), config).await;
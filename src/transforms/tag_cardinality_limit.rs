@@ -1,21 +1,28 @@
-use std::{collections::HashSet, fmt, future::ready, pin::Pin};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
 
 use bloom::{BloomFilter, ASMS};
-use futures::{Stream, StreamExt};
-use hashbrown::HashMap;
+use hashbrown::HashMap as TagsMap;
 use vector_config::configurable_component;
+use vector_core::transform::SyncTransform;
 
 use crate::{
     config::{DataType, GenerateConfig, Input, Output, TransformConfig, TransformContext},
     event::Event,
     internal_events::{
         TagCardinalityLimitRejectingEvent, TagCardinalityLimitRejectingTag,
-        TagCardinalityValueLimitReached,
+        TagCardinalityLimitReset, TagCardinalityValueLimitReached,
     },
     schema,
-    transforms::{TaskTransform, Transform},
+    transforms::{Transform, TransformOutputsBuf},
 };
 
+const DROPPED: &str = "dropped";
+
 /// Configuration for the `tag_cardinality_limit` transform.
 #[configurable_component(transform("tag_cardinality_limit"))]
 #[derive(Clone, Debug)]
@@ -30,6 +37,33 @@ pub struct TagCardinalityLimitConfig {
 
     #[serde(flatten)]
     pub mode: Mode,
+
+    /// Overrides of the limits for specific tag keys, applied to every metric.
+    ///
+    /// The key is the tag key the override applies to.
+    #[serde(default)]
+    pub per_tag: HashMap<String, TagLimitConfig>,
+
+    /// Overrides of the limits for specific metrics.
+    ///
+    /// The key is the name of the metric, without its namespace, the override applies to. The tag
+    /// values of these metrics are tracked separately from the tag values of other metrics.
+    #[serde(default)]
+    pub per_metric: HashMap<String, PerMetricConfig>,
+
+    /// The interval after which all accepted tag values are forgotten, in seconds.
+    ///
+    /// Once reset, new tag values are accepted again until the limits are reached. If not set,
+    /// accepted tag values are kept for the lifetime of the transform.
+    pub reset_interval_secs: Option<u64>,
+
+    /// Reroutes rejected metrics to a named output.
+    ///
+    /// When set, metrics dropped with `drop_event` and the original, unmodified version of metrics
+    /// stripped of tags with `drop_tag` are forwarded to a specially-named output, `dropped`, for
+    /// further inspection.
+    #[serde(default = "crate::serde::default_false")]
+    pub reroute_dropped: bool,
 }
 
 /// Controls the approach taken for tracking tag cardinality.
@@ -67,7 +101,7 @@ pub struct BloomFilterConfig {
 /// Possible actions to take when an event arrives that would exceed the cardinality limit for one
 /// or more of its tags.
 #[configurable_component]
-#[derive(Clone, Debug)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
 #[serde(rename_all = "snake_case")]
 pub enum LimitExceededAction {
     /// Drop the tag(s) that would exceed the configured limit.
@@ -77,10 +111,44 @@ pub enum LimitExceededAction {
     DropEvent,
 }
 
-#[derive(Debug)]
-pub struct TagCardinalityLimit {
-    config: TagCardinalityLimitConfig,
-    accepted_tags: HashMap<String, TagValueSet>,
+/// Overrides of the limits for a tag key.
+#[configurable_component]
+#[derive(Clone, Debug, Default)]
+#[serde(deny_unknown_fields)]
+pub struct TagLimitConfig {
+    /// How many distinct values to accept for the tag key.
+    ///
+    /// If not set, the enclosing `value_limit` is used.
+    pub value_limit: Option<u32>,
+
+    /// The action to take when the limit of the tag key is exceeded.
+    ///
+    /// If not set, the enclosing `limit_exceeded_action` is used.
+    #[configurable(derived)]
+    pub limit_exceeded_action: Option<LimitExceededAction>,
+}
+
+/// Overrides of the limits for a metric.
+#[configurable_component]
+#[derive(Clone, Debug, Default)]
+#[serde(deny_unknown_fields)]
+pub struct PerMetricConfig {
+    /// How many distinct values to accept for any given key of the metric.
+    ///
+    /// If not set, the top-level limits, including `per_tag`, are used.
+    pub value_limit: Option<u32>,
+
+    /// The action to take when a limit of the metric is exceeded.
+    ///
+    /// If not set, the top-level limits, including `per_tag`, are used.
+    #[configurable(derived)]
+    pub limit_exceeded_action: Option<LimitExceededAction>,
+
+    /// Overrides of the limits for specific tag keys of the metric.
+    ///
+    /// The key is the tag key the override applies to.
+    #[serde(default)]
+    pub per_tag: HashMap<String, TagLimitConfig>,
 }
 
 const fn default_limit_exceeded_action() -> LimitExceededAction {
@@ -101,6 +169,10 @@ impl GenerateConfig for TagCardinalityLimitConfig {
             mode: Mode::Exact,
             value_limit: default_value_limit(),
             limit_exceeded_action: default_limit_exceeded_action(),
+            per_tag: HashMap::new(),
+            per_metric: HashMap::new(),
+            reset_interval_secs: None,
+            reroute_dropped: false,
         })
         .unwrap()
     }
@@ -109,7 +181,7 @@ impl GenerateConfig for TagCardinalityLimitConfig {
 #[async_trait::async_trait]
 impl TransformConfig for TagCardinalityLimitConfig {
     async fn build(&self, _context: &TransformContext) -> crate::Result<Transform> {
-        Ok(Transform::event_task(TagCardinalityLimit::new(
+        Ok(Transform::synchronous(TagCardinalityLimit::new(
             self.clone(),
         )))
     }
@@ -119,7 +191,54 @@ impl TransformConfig for TagCardinalityLimitConfig {
     }
 
     fn outputs(&self, _: &schema::Definition) -> Vec<Output> {
-        vec![Output::default(DataType::Metric)]
+        if self.reroute_dropped {
+            vec![
+                Output::default(DataType::Metric),
+                Output::default(DataType::Metric).with_port(DROPPED),
+            ]
+        } else {
+            vec![Output::default(DataType::Metric)]
+        }
+    }
+}
+
+impl TagCardinalityLimitConfig {
+    /// Resolves the limit of a tag key, from the most specific override to the top-level settings.
+    fn tag_limit(&self, metric: Option<&PerMetricConfig>, key: &str) -> TagLimit {
+        let mut limit = TagLimit {
+            value_limit: self.value_limit,
+            action: self.limit_exceeded_action,
+        };
+
+        if let Some(config) = self.per_tag.get(key) {
+            limit.apply(config.value_limit, config.limit_exceeded_action);
+        }
+        if let Some(metric) = metric {
+            limit.apply(metric.value_limit, metric.limit_exceeded_action);
+            if let Some(config) = metric.per_tag.get(key) {
+                limit.apply(config.value_limit, config.limit_exceeded_action);
+            }
+        }
+
+        limit
+    }
+}
+
+/// The effective limit of a tag key.
+#[derive(Clone, Copy, Debug)]
+struct TagLimit {
+    value_limit: u32,
+    action: LimitExceededAction,
+}
+
+impl TagLimit {
+    fn apply(&mut self, value_limit: Option<u32>, action: Option<LimitExceededAction>) {
+        if let Some(value_limit) = value_limit {
+            self.value_limit = value_limit;
+        }
+        if let Some(action) = action {
+            self.action = action;
+        }
     }
 }
 
@@ -183,11 +302,73 @@ impl TagValueSet {
     }
 }
 
+/// The accepted values of every tag key.
+#[derive(Debug)]
+struct AcceptedTags {
+    /// Accepted values of metrics without a `per_metric` override, by tag key.
+    shared: TagsMap<String, TagValueSet>,
+    /// Accepted values of metrics with a `per_metric` override, by metric name and tag key.
+    per_metric: TagsMap<String, TagsMap<String, TagValueSet>>,
+    last_reset: Instant,
+}
+
+impl AcceptedTags {
+    fn new() -> Self {
+        Self {
+            shared: TagsMap::new(),
+            per_metric: TagsMap::new(),
+            last_reset: Instant::now(),
+        }
+    }
+
+    /// Forgets every accepted value if the reset interval elapsed since the last reset.
+    fn reset_if_expired(&mut self, interval: Option<Duration>, now: Instant) {
+        if let Some(interval) = interval {
+            if now.duration_since(self.last_reset) >= interval {
+                self.shared.clear();
+                self.per_metric.clear();
+                self.last_reset = now;
+                emit!(TagCardinalityLimitReset);
+            }
+        }
+    }
+
+    fn get(&self, metric: Option<&str>, key: &str) -> Option<&TagValueSet> {
+        match metric {
+            Some(metric) => self.per_metric.get(metric)?.get(key),
+            None => self.shared.get(key),
+        }
+    }
+
+    fn get_or_insert(
+        &mut self,
+        metric: Option<&str>,
+        key: &str,
+        value_limit: u32,
+        mode: &Mode,
+    ) -> &mut TagValueSet {
+        let tags = match metric {
+            Some(metric) => self.per_metric.entry_ref(metric).or_default(),
+            None => &mut self.shared,
+        };
+
+        tags.entry_ref(key)
+            .or_insert_with(|| TagValueSet::new(value_limit, mode))
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct TagCardinalityLimit {
+    config: TagCardinalityLimitConfig,
+    // Shared between clones of the transform, so they all enforce the same limits.
+    accepted_tags: Arc<Mutex<AcceptedTags>>,
+}
+
 impl TagCardinalityLimit {
     fn new(config: TagCardinalityLimitConfig) -> Self {
         Self {
             config,
-            accepted_tags: HashMap::new(),
+            accepted_tags: Arc::new(Mutex::new(AcceptedTags::new())),
         }
     }
 
@@ -199,11 +380,15 @@ impl TagCardinalityLimit {
     /// for the key and returns true, otherwise returns false.  A false return
     /// value indicates to the caller that the value is not accepted for this
     /// key, and the configured limit_exceeded_action should be taken.
-    fn try_accept_tag(&mut self, key: &str, value: &str) -> bool {
-        let tag_value_set = self
-            .accepted_tags
-            .entry_ref(key)
-            .or_insert_with(|| TagValueSet::new(self.config.value_limit, &self.config.mode));
+    fn try_accept_tag(
+        accepted_tags: &mut AcceptedTags,
+        mode: &Mode,
+        metric: Option<&str>,
+        key: &str,
+        value: &str,
+        value_limit: u32,
+    ) -> bool {
+        let tag_value_set = accepted_tags.get_or_insert(metric, key, value_limit, mode);
 
         if tag_value_set.contains(value) {
             // Tag value has already been accepted, nothing more to do.
@@ -211,11 +396,11 @@ impl TagCardinalityLimit {
         }
 
         // Tag value not yet part of the accepted set.
-        if tag_value_set.len() < self.config.value_limit as usize {
+        if tag_value_set.len() < value_limit as usize {
             // accept the new value
             tag_value_set.insert(value);
 
-            if tag_value_set.len() == self.config.value_limit as usize {
+            if tag_value_set.len() == value_limit as usize {
                 emit!(TagCardinalityValueLimitReached { key });
             }
 
@@ -228,72 +413,108 @@ impl TagCardinalityLimit {
 
     /// Checks if recording a key and value corresponding to a tag on an incoming Metric would
     /// exceed the cardinality limit.
-    fn tag_limit_exceeded(&self, key: &str, value: &str) -> bool {
-        self.accepted_tags
-            .get(key)
-            .map(|value_set| {
-                !value_set.contains(value) && value_set.len() >= self.config.value_limit as usize
-            })
+    fn tag_limit_exceeded(
+        accepted_tags: &AcceptedTags,
+        metric: Option<&str>,
+        key: &str,
+        value: &str,
+        value_limit: u32,
+    ) -> bool {
+        accepted_tags
+            .get(metric, key)
+            .map(|value_set| !value_set.contains(value) && value_set.len() >= value_limit as usize)
             .unwrap_or(false)
     }
 
-    /// Record a key and value corresponding to a tag on an incoming Metric.
-    fn record_tag_value(&mut self, key: &str, value: &str) {
-        self.accepted_tags
-            .entry_ref(key)
-            .or_insert_with(|| TagValueSet::new(self.config.value_limit, &self.config.mode))
-            .insert(value);
-    }
-
-    fn transform_one(&mut self, mut event: Event) -> Option<Event> {
-        let metric = event.as_mut_metric();
-        if let Some(tags_map) = metric.tags_mut() {
-            match self.config.limit_exceeded_action {
-                LimitExceededAction::DropEvent => {
-                    // This needs to check all the tags, to ensure that the ordering of tag names
-                    // doesn't change the behavior of the check.
-                    for (key, value) in tags_map.iter_single() {
-                        if self.tag_limit_exceeded(key, value) {
-                            emit!(TagCardinalityLimitRejectingEvent {
-                                tag_key: key,
-                                tag_value: value,
-                            });
-                            return None;
-                        }
-                    }
-                    for (key, value) in tags_map.iter_single() {
-                        self.record_tag_value(key, value);
+    /// Runs a metric through the limits.
+    ///
+    /// Returns the metric to forward, if any, and the original metric to send to the `dropped`
+    /// output if it was rejected or stripped of tags and `reroute_dropped` is enabled.
+    fn transform_one(&mut self, mut event: Event) -> (Option<Event>, Option<Event>) {
+        let config = &self.config;
+        let mut accepted_tags = self
+            .accepted_tags
+            .lock()
+            .expect("Accepted tags mutex is poisoned");
+        accepted_tags.reset_if_expired(
+            config.reset_interval_secs.map(Duration::from_secs),
+            Instant::now(),
+        );
+
+        let (metric, metric_config) =
+            match config.per_metric.get_key_value(event.as_metric().name()) {
+                Some((name, metric_config)) => (Some(name.as_str()), Some(metric_config)),
+                None => (None, None),
+            };
+
+        let tags_map = match event.as_metric().tags() {
+            Some(tags_map) => tags_map,
+            None => return (Some(event), None),
+        };
+
+        // This needs to check all the tags, to ensure that the ordering of tag names doesn't
+        // change the behavior of the check.
+        for (key, value) in tags_map.iter_single() {
+            let limit = config.tag_limit(metric_config, key);
+            if limit.action == LimitExceededAction::DropEvent
+                && Self::tag_limit_exceeded(&accepted_tags, metric, key, value, limit.value_limit)
+            {
+                emit!(TagCardinalityLimitRejectingEvent {
+                    tag_key: key,
+                    tag_value: value,
+                });
+                return (None, config.reroute_dropped.then_some(event));
+            }
+        }
+
+        let original = config.reroute_dropped.then(|| event.clone());
+        let mut stripped = false;
+        if let Some(tags_map) = event.as_mut_metric().tags_mut() {
+            tags_map.retain(|key, value| {
+                let limit = config.tag_limit(metric_config, key);
+                match limit.action {
+                    LimitExceededAction::DropEvent => {
+                        accepted_tags
+                            .get_or_insert(metric, key, limit.value_limit, &config.mode)
+                            .insert(value);
+                        true
                     }
-                }
-                LimitExceededAction::DropTag => {
-                    tags_map.retain(|key, value| {
-                        if self.try_accept_tag(key, value) {
+                    LimitExceededAction::DropTag => {
+                        if Self::try_accept_tag(
+                            &mut accepted_tags,
+                            &config.mode,
+                            metric,
+                            key,
+                            value,
+                            limit.value_limit,
+                        ) {
                             true
                         } else {
                             emit!(TagCardinalityLimitRejectingTag {
                                 tag_key: key,
                                 tag_value: value,
                             });
+                            stripped = true;
                             false
                         }
-                    });
+                    }
                 }
-            }
+            });
         }
-        Some(event)
+
+        (Some(event), original.filter(|_| stripped))
     }
 }
 
-impl TaskTransform<Event> for TagCardinalityLimit {
-    fn transform(
-        self: Box<Self>,
-        task: Pin<Box<dyn Stream<Item = Event> + Send>>,
-    ) -> Pin<Box<dyn Stream<Item = Event> + Send>>
-    where
-        Self: 'static,
-    {
-        let mut inner = self;
-        Box::pin(task.filter_map(move |v| ready(inner.transform_one(v))))
+impl SyncTransform for TagCardinalityLimit {
+    fn transform(&mut self, event: Event, output: &mut TransformOutputsBuf) {
+        let (event, dropped) = self.transform_one(event);
+        if let Some(event) = event {
+            output.push(event);
+        }
+        if let Some(dropped) = dropped {
+            output.push_named(DROPPED, dropped);
+        }
     }
 }
 
@@ -329,7 +550,7 @@ mod tests {
         )
     }
 
-    const fn make_transform_hashset(
+    fn make_transform_hashset(
         value_limit: u32,
         limit_exceeded_action: LimitExceededAction,
     ) -> TagCardinalityLimitConfig {
@@ -337,10 +558,14 @@ mod tests {
             value_limit,
             limit_exceeded_action,
             mode: Mode::Exact,
+            per_tag: HashMap::new(),
+            per_metric: HashMap::new(),
+            reset_interval_secs: None,
+            reroute_dropped: false,
         }
     }
 
-    const fn make_transform_bloom(
+    fn make_transform_bloom(
         value_limit: u32,
         limit_exceeded_action: LimitExceededAction,
     ) -> TagCardinalityLimitConfig {
@@ -350,6 +575,10 @@ mod tests {
             mode: Mode::Probabilistic(BloomFilterConfig {
                 cache_size_per_key: default_cache_size(),
             }),
+            per_tag: HashMap::new(),
+            per_metric: HashMap::new(),
+            reset_interval_secs: None,
+            reroute_dropped: false,
         }
     }
 
@@ -508,14 +737,174 @@ mod tests {
         // And then check if the new value for the second tag was not recorded by the above event.
         let event4 = make_metric(make_tags("val1", "val3"));
 
-        let new_event1 = transform.transform_one(event1.clone());
-        let new_event2 = transform.transform_one(event2.clone());
-        let new_event3 = transform.transform_one(event3);
-        let new_event4 = transform.transform_one(event4.clone());
+        let (new_event1, _) = transform.transform_one(event1.clone());
+        let (new_event2, _) = transform.transform_one(event2.clone());
+        let (new_event3, _) = transform.transform_one(event3);
+        let (new_event4, _) = transform.transform_one(event4.clone());
 
         assert_eq!(new_event1, Some(event1));
         assert_eq!(new_event2, Some(event2));
         assert_eq!(new_event3, None);
         assert_eq!(new_event4, Some(event4));
     }
+
+    fn make_named_metric(name: &str, tags: MetricTags) -> Event {
+        Event::Metric(
+            Metric::new(
+                name,
+                metric::MetricKind::Incremental,
+                metric::MetricValue::Counter { value: 1.0 },
+            )
+            .with_tags(Some(tags)),
+        )
+    }
+
+    #[test]
+    fn per_tag_overrides() {
+        let mut config = make_transform_hashset(1, LimitExceededAction::DropTag);
+        config.per_tag.insert(
+            "endpoint".to_owned(),
+            TagLimitConfig {
+                value_limit: Some(2),
+                limit_exceeded_action: None,
+            },
+        );
+        config.per_tag.insert(
+            "status".to_owned(),
+            TagLimitConfig {
+                value_limit: None,
+                limit_exceeded_action: Some(LimitExceededAction::DropEvent),
+            },
+        );
+        let mut transform = TagCardinalityLimit::new(config);
+
+        let event1 = make_metric(metric_tags!("endpoint" => "a", "status" => "200"));
+        let event2 = make_metric(metric_tags!("endpoint" => "b", "status" => "200"));
+        // The limit of "endpoint" is reached, its new value is dropped.
+        let event3 = make_metric(metric_tags!("endpoint" => "c", "status" => "200"));
+        // The limit of "status" is reached, the whole event is dropped.
+        let event4 = make_metric(metric_tags!("endpoint" => "a", "status" => "500"));
+
+        let (new_event1, _) = transform.transform_one(event1.clone());
+        let (new_event2, _) = transform.transform_one(event2.clone());
+        let (new_event3, _) = transform.transform_one(event3);
+        let (new_event4, _) = transform.transform_one(event4);
+
+        assert_eq!(new_event1, Some(event1));
+        assert_eq!(new_event2, Some(event2));
+        assert_eq!(
+            new_event3.unwrap().as_metric().tags(),
+            Some(&metric_tags!("status" => "200"))
+        );
+        assert_eq!(new_event4, None);
+    }
+
+    #[test]
+    fn per_metric_overrides() {
+        let mut config = make_transform_hashset(1, LimitExceededAction::DropEvent);
+        config.per_metric.insert(
+            "requests".to_owned(),
+            PerMetricConfig {
+                value_limit: Some(2),
+                limit_exceeded_action: None,
+                per_tag: HashMap::from([(
+                    "status".to_owned(),
+                    TagLimitConfig {
+                        value_limit: Some(1),
+                        limit_exceeded_action: Some(LimitExceededAction::DropTag),
+                    },
+                )]),
+            },
+        );
+        let mut transform = TagCardinalityLimit::new(config);
+
+        let other1 = make_named_metric("other", metric_tags!("endpoint" => "a"));
+        let other2 = make_named_metric("other", metric_tags!("endpoint" => "b"));
+        // Values of overridden metrics are tracked separately from other metrics.
+        let requests1 = make_named_metric(
+            "requests",
+            metric_tags!("endpoint" => "b", "status" => "200"),
+        );
+        let requests2 = make_named_metric(
+            "requests",
+            metric_tags!("endpoint" => "c", "status" => "500"),
+        );
+        let requests3 = make_named_metric(
+            "requests",
+            metric_tags!("endpoint" => "d", "status" => "200"),
+        );
+
+        let (new_other1, _) = transform.transform_one(other1.clone());
+        let (new_other2, _) = transform.transform_one(other2);
+        let (new_requests1, _) = transform.transform_one(requests1.clone());
+        let (new_requests2, _) = transform.transform_one(requests2);
+        let (new_requests3, _) = transform.transform_one(requests3);
+
+        assert_eq!(new_other1, Some(other1));
+        assert_eq!(new_other2, None);
+        assert_eq!(new_requests1, Some(requests1));
+        assert_eq!(
+            new_requests2.unwrap().as_metric().tags(),
+            Some(&metric_tags!("endpoint" => "c"))
+        );
+        assert_eq!(new_requests3, None);
+    }
+
+    #[test]
+    fn reset_interval() {
+        let mut config = make_transform_hashset(1, LimitExceededAction::DropEvent);
+        config.reset_interval_secs = Some(60);
+        let mut transform = TagCardinalityLimit::new(config);
+
+        let event1 = make_metric(metric_tags!("tag1" => "val1"));
+        let event2 = make_metric(metric_tags!("tag1" => "val2"));
+
+        assert!(transform.transform_one(event1).0.is_some());
+        assert_eq!(transform.transform_one(event2.clone()).0, None);
+
+        transform.accepted_tags.lock().unwrap().last_reset -= Duration::from_secs(60);
+
+        assert_eq!(transform.transform_one(event2.clone()).0, Some(event2));
+    }
+
+    #[test]
+    fn reroute_dropped_event() {
+        let mut config = make_transform_hashset(1, LimitExceededAction::DropEvent);
+        config.reroute_dropped = true;
+        let mut transform = TagCardinalityLimit::new(config);
+
+        let event1 = make_metric(metric_tags!("tag1" => "val1"));
+        let event2 = make_metric(metric_tags!("tag1" => "val2"));
+
+        assert_eq!(
+            transform.transform_one(event1.clone()),
+            (Some(event1), None)
+        );
+        assert_eq!(
+            transform.transform_one(event2.clone()),
+            (None, Some(event2))
+        );
+    }
+
+    #[test]
+    fn reroute_dropped_tag() {
+        let mut config = make_transform_hashset(1, LimitExceededAction::DropTag);
+        config.reroute_dropped = true;
+        let mut transform = TagCardinalityLimit::new(config);
+
+        let event1 = make_metric(metric_tags!("tag1" => "val1", "tag2" => "val1"));
+        let event2 = make_metric(metric_tags!("tag1" => "val2", "tag2" => "val1"));
+
+        assert_eq!(
+            transform.transform_one(event1.clone()),
+            (Some(event1), None)
+        );
+
+        let (new_event2, dropped) = transform.transform_one(event2.clone());
+        assert_eq!(
+            new_event2.unwrap().as_metric().tags(),
+            Some(&metric_tags!("tag2" => "val1"))
+        );
+        assert_eq!(dropped, Some(event2));
+    }
 }
@@ -47,6 +47,71 @@ api: {
 				of the address set using the `bind` parameter.
 				"""
 		}
+		tls: {
+			common:      false
+			required:    false
+			description: """
+				Configures the TLS options of the API. These are the same options as the `tls`
+				options of sources, such as `enabled`, `crt_file`, `key_file` and `ca_file`. With
+				`verify_certificate`, clients must present a certificate signed by `ca_file`.
+				"""
+			type: object: options: {}
+		}
+		auth: {
+			common:      false
+			required:    false
+			description: """
+				Requires clients of the `/graphql` endpoint to authenticate, either with a bearer
+				token in the `Authorization` header, or with a client certificate. Clients of
+				WebSockets, such as the Playground, can send the header in the `Authorization` key
+				of the connection initialization payload instead. `vector tap` and `vector top`
+				take the `--token` option, or the `VECTOR_API_TOKEN` environment variable, and the
+				`--ca-file`, `--crt-file` and `--key-file` options.
+				"""
+			type: object: options: {
+				tokens: {
+					common:      true
+					required:    false
+					description: "The bearer tokens that clients can authenticate with."
+					type: array: {
+						default: []
+						items: type: object: options: {
+							token: {
+								description: "The token, which clients send in the `Authorization: Bearer <token>` header."
+								required:    true
+								type: string: {
+									examples: ["${VECTOR_API_TOKEN}"]
+								}
+							}
+							scope: {
+								description: "What clients that authenticate with this token are allowed to do."
+								required:    true
+								type: string: enum: {
+									read_only: "Query the components, health and metrics of Vector, without tapping events."
+									tap:       "Tap the events flowing through components, in addition to the `read_only` scope."
+								}
+							}
+						}
+					}
+				}
+				client_certificate_scope: {
+					common:      false
+					required:    false
+					description: """
+						What clients that authenticate with a client certificate rather than a token are
+						allowed to do. Requires `tls.enabled` and `tls.verify_certificate`. By default,
+						clients must authenticate with a token.
+						"""
+					type: string: {
+						default: null
+						enum: {
+							read_only: "Query the components, health and metrics of Vector, without tapping events."
+							tap:       "Tap the events flowing through components, in addition to the `read_only` scope."
+						}
+					}
+				}
+			}
+		}
 	}
 
 	endpoints: {
@@ -66,6 +131,12 @@ api: {
 							there were any errors in your query.
 							"""
 					}
+					"401": {
+						description: """
+							The client failed to authenticate with the credentials
+							required by the `auth` option.
+							"""
+					}
 				}
 			}
 		}
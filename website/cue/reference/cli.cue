@@ -96,6 +96,10 @@ cli: {
 			description: env_vars.VECTOR_REQUIRE_HEALTHY.description
 			env_var:     "VECTOR_REQUIRE_HEALTHY"
 		}
+		"latency-tracking": {
+			description: env_vars.VECTOR_LATENCY_TRACKING.description
+			env_var:     "VECTOR_LATENCY_TRACKING"
+		}
 		"verbose": {
 			_short:      "v"
 			description: "Enable more detailed logging. Repeat to reduce further. Overrides `--verbose`."
@@ -173,7 +177,55 @@ cli: {
 
 	options: _core_options
 
+	// Options to connect to an API with authentication and TLS enabled
+	_api_connect_options: {
+		"token": {
+			description: "Bearer token to authenticate to the Vector API with"
+			type:        "string"
+			env_var:     "VECTOR_API_TOKEN"
+		}
+		"ca-file": {
+			description: "CA certificate, in PEM format, to verify the certificate of the Vector API with"
+			type:        "string"
+		}
+		"crt-file": {
+			description: "Client certificate, in PEM format, to authenticate to the Vector API with. Requires `--key-file`."
+			type:        "string"
+		}
+		"key-file": {
+			description: "Private key of the client certificate, in PEM (PKCS#8) format. Requires `--crt-file`."
+			type:        "string"
+		}
+	}
+
 	commands: {
+		"config diff": {
+			description: """
+				Compare two Vector configurations, showing the components that reloading from the old
+				to the new configuration would add, rebuild, or remove, the changed options of each of
+				them, and the sink buffers that would be dropped. Exits with a non-zero exit code if the
+				configurations differ, which is useful as a gate in CI. The values of sensitive options,
+				such as passwords and tokens, are redacted.
+				"""
+
+			example: "vector config diff /etc/vector/vector.toml ./vector.toml"
+
+			flags: _default_flags
+
+			args: {
+				old: {
+					description: "The current configuration, as a file or a directory of files"
+					required:    true
+					type:        "string"
+				}
+				new: {
+					description: "The new configuration, as a file or a directory of files"
+					required:    true
+					type:        "string"
+				}
+			}
+		}
+
 		"graph": {
 			description: """
 				Generate a visual representation of topologies. The output is in the [DOT format](\(urls.dot_format)),
@@ -291,7 +343,7 @@ cli: {
 				}
 			}
 
-			options: {
+			options: _api_connect_options & {
 				"interval": {
 					_short:      "i"
 					description: "Interval to sample events at, in milliseconds"
@@ -328,6 +380,26 @@ cli: {
 					description: "Components (sources, transforms) to observe for their inputs (comma-separated; accepts glob patterns)"
 					type:        "list"
 				}
+				"filter": {
+					description: """
+						VRL condition that events must match, such as `.tenant == "acme"`. The condition is
+						evaluated by the observed Vector instance before events are sampled.
+						"""
+					type: "string"
+				}
+				"fields": {
+					description: "Fields of log events to output (comma-separated; e.g. `message,http.status`)"
+					type:        "list"
+				}
+				"trace": {
+					description: """
+						VRL condition, such as `.tenant == "acme"`, selecting up to `limit` events as sources
+						ingest them. Instead of tapping events, each traced event is followed through the
+						topology, printing whether components passed, mutated, routed or dropped it, and
+						whether sinks delivered it.
+						"""
+					type: "string"
+				}
 			}
 
 			args: {
@@ -348,7 +420,9 @@ cli: {
 		"top": {
 			description: """
 				Display topology and metrics in the console, for a local or remote Vector
-				instance
+				instance. Press Tab to switch between the components, buffers, errors and
+				outputs views, `s` and `r` to change the column and order the components are
+				sorted by, and `/` to filter the components by ID.
 				"""
 
 			flags: _default_flags & {
@@ -365,7 +439,7 @@ cli: {
 				}
 			}
 
-			options: {
+			options: _api_connect_options & {
 				"refresh-interval": {
 					_short:      "i"
 					description: "How often the screen refreshes (in milliseconds)"
@@ -596,6 +670,10 @@ cli: {
 			description: "Watch for changes in the configuration file and reload accordingly"
 			type: bool: default: false
 		}
+		VECTOR_LATENCY_TRACKING: {
+			description: "Record the latency of events from their source to the sinks delivering them, in the `source_to_sink_latency_seconds` internal metric. This tracks the delivery of every event reaching a sink, which has a cost."
+			type: bool: default: false
+		}
 		VECTOR_INTERNAL_LOG_RATE_LIMIT: {
 			description: "Set the internal log rate limit. This limits Vector from emitting identical logs more than once over the given number of seconds."
 			type: uint: {
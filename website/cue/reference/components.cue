@@ -140,6 +140,11 @@ components: {
 			outputs: #Outputs
 		}
 
+		// Sinks only have outputs when they can reroute the events they failed to deliver.
+		if Kind == "sink" {
+			outputs?: #Outputs
+		}
+
 		// `support` communicates the varying levels of support of the component.
 		support: #Support & {_args: kind: Kind}
 
@@ -1198,8 +1203,9 @@ components: {
 		if Kind == "transform" {
 			telemetry: metrics: {
 				// Default metrics for each transform
-				processed_events_total: components.sources.internal_metrics.output.metrics.processed_events_total
-				processed_bytes_total:  components.sources.internal_metrics.output.metrics.processed_bytes_total
+				component_cpu_time_nanoseconds_total: components.sources.internal_metrics.output.metrics.component_cpu_time_nanoseconds_total
+				processed_events_total:               components.sources.internal_metrics.output.metrics.processed_events_total
+				processed_bytes_total:                components.sources.internal_metrics.output.metrics.processed_bytes_total
 			}
 		}
 
@@ -0,0 +1,65 @@
+package metadata
+
+components: _mqtt: {
+	features: {
+		collect: from: {
+			service: services.mqtt
+			interface: {
+				socket: {
+					api: {
+						title: "MQTT protocol"
+						url:   urls.mqtt_v3_1_1
+					}
+					direction: "incoming"
+					port:      1883
+					protocols: ["tcp"]
+					ssl: "optional"
+				}
+			}
+		}
+
+		send: to: {
+			service: services.mqtt
+			interface: {
+				socket: {
+					api: {
+						title: "MQTT protocol"
+						url:   urls.mqtt_v3_1_1
+					}
+					direction: "outgoing"
+					protocols: ["tcp"]
+					ssl: "optional"
+				}
+			}
+		}
+	}
+
+	support: {
+		requirements: []
+		notices: []
+		warnings: []
+	}
+
+	how_it_works: {
+		qos: {
+			title: "Quality of service"
+			body: """
+				MQTT deliveries are made with one of three quality of service levels, set with the `qos`
+				option: `at_most_once` (QoS 0), `at_least_once` (QoS 1) and `exactly_once` (QoS 2). The
+				broker downgrades deliveries to the level of the subscription they match, so both ends
+				must use at least `at_least_once` for deliveries to survive a connection loss.
+				"""
+		}
+		client_id: {
+			title: "Client ID"
+			body: """
+				Brokers identify clients, and keep their sessions, by their `client_id`. A broker
+				disconnects a client when another one connects with the same ID, so each `mqtt`
+				component connecting to the same broker needs its own `client_id`. By default,
+				sources connect with `vector-` followed by their component ID, and sinks with a
+				random ID. Instances of Vector running the same sources against the same broker
+				need to set their own `client_id`.
+				"""
+		}
+	}
+}
@@ -734,5 +734,6 @@ components: sinks: [Name=string]: {
 		buffer_sent_events_total:             components.sources.internal_metrics.output.metrics.buffer_sent_events_total
 		buffer_sent_event_bytes_total:        components.sources.internal_metrics.output.metrics.buffer_sent_event_bytes_total
 		buffer_discarded_events_total:        components.sources.internal_metrics.output.metrics.buffer_discarded_events_total
+		source_to_sink_latency_seconds:       components.sources.internal_metrics.output.metrics.source_to_sink_latency_seconds
 	}
 }
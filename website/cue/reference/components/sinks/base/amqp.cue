@@ -158,6 +158,12 @@ base: components: sinks: amqp: configuration: {
 						while doing additional parsing on it, as this could lead to the encoding emitting empty strings for the given
 						event.
 						"""
+					syslog: """
+						Syslog serialization, as specified in [RFC 5424][rfc5424] or [RFC 3164][rfc3164].
+
+						[rfc5424]: https://www.rfc-editor.org/rfc/rfc5424
+						[rfc3164]: https://www.rfc-editor.org/rfc/rfc3164
+						"""
 					text: """
 						Plaintext serialization.
 
@@ -178,6 +184,112 @@ base: components: sinks: amqp: configuration: {
 				required:    false
 				type: array: items: type: string: syntax: "literal"
 			}
+			syslog: {
+				description: """
+					Syslog serializer options.
+
+					The defaults match the fields set by the `syslog` decoder, so that decoded events are encoded
+					back as they were received.
+					"""
+				relevant_when: "codec = \"syslog\""
+				required:      false
+				type: object: options: {
+					app_name_field: {
+						description: "The field containing the name of the application that produced the event."
+						required:    false
+						type: string: {
+							default: "appname"
+							syntax:  "literal"
+						}
+					}
+					facility_field: {
+						description: """
+							The field containing the facility of the event.
+
+							The facility can either be a name, such as `local0`, or its numeric code. Events without a
+							valid facility use `user`.
+							"""
+						required: false
+						type: string: {
+							default: "facility"
+							syntax:  "literal"
+						}
+					}
+					hostname_field: {
+						description: """
+							The field containing the hostname of the event.
+
+							Events without it use the global `host_key` field instead.
+							"""
+						required: false
+						type: string: {
+							default: "hostname"
+							syntax:  "literal"
+						}
+					}
+					msg_id_field: {
+						description: "The field containing the type of the message."
+						required:    false
+						type: string: {
+							default: "msgid"
+							syntax:  "literal"
+						}
+					}
+					proc_id_field: {
+						description: "The field containing the process ID of the application that produced the event."
+						required:    false
+						type: string: {
+							default: "procid"
+							syntax:  "literal"
+						}
+					}
+					rfc: {
+						description: "The syslog protocol to encode events with."
+						required:    false
+						type: string: {
+							default: "rfc5424"
+							enum: {
+								rfc3164: """
+									The BSD syslog protocol, as specified in [RFC 3164][rfc3164].
+
+									Message IDs and structured data aren't part of this format, and are left out.
+
+									[rfc3164]: https://www.rfc-editor.org/rfc/rfc3164
+									"""
+								rfc5424: """
+									The syslog protocol, as specified in [RFC 5424][rfc5424].
+
+									[rfc5424]: https://www.rfc-editor.org/rfc/rfc5424
+									"""
+							}
+						}
+					}
+					severity_field: {
+						description: """
+							The field containing the severity of the event.
+
+							The severity can either be a name, such as `warning`, or its numeric code. Events without a
+							valid severity use `informational`.
+							"""
+						required: false
+						type: string: {
+							default: "severity"
+							syntax:  "literal"
+						}
+					}
+					structured_data_field: {
+						description: """
+							The field containing the structured data of the event.
+
+							The field must be an object, whose keys are the IDs of the structured data elements, and
+							whose values are objects of the parameters of each element. When unset, no structured data
+							is sent.
+							"""
+						required: false
+						type: string: syntax: "literal"
+					}
+				}
+			}
 			timestamp_format: {
 				description: "Format used for timestamp fields."
 				required:    false
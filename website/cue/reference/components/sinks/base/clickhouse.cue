@@ -155,6 +155,30 @@ base: components: sinks: clickhouse: configuration: {
 		required:    true
 		type: string: syntax: "literal"
 	}
+	format: {
+		description: "The format events are inserted in."
+		required:    false
+		type: string: {
+			default: "json_each_row"
+			enum: {
+				json_each_row: """
+					Events are sent as JSON objects, which Clickhouse parses and converts to the types of the columns.
+
+					This is the default.
+					"""
+				native: """
+					Events are coerced to the types of the columns of the table, and sent column by column in binary.
+
+					The columns of the table are fetched when the sink starts.
+					"""
+				row_binary: """
+					Events are coerced to the types of the columns of the table, and sent row by row in binary.
+
+					The columns of the table are fetched when the sink starts.
+					"""
+			}
+		}
+	}
 	request: {
 		description: """
 			Middleware settings for outbound requests.
@@ -277,8 +301,12 @@ base: components: sinks: clickhouse: configuration: {
 		}
 	}
 	skip_unknown_fields: {
-		description: "Sets `input_format_skip_unknown_fields`, allowing Clickhouse to discard fields not present in the table schema."
-		required:    false
+		description: """
+			Sets `input_format_skip_unknown_fields`, allowing Clickhouse to discard fields not present in the table schema.
+
+			Only used by the `json_each_row` format, as the binary formats only ever send the columns of the table.
+			"""
+		required: false
 		type: bool: default: false
 	}
 	table: {
@@ -534,6 +534,18 @@ base: components: sinks: elasticsearch: configuration: {
 		required: false
 		type: bool: default: false
 	}
+	reroute_dropped: {
+		description: """
+			Whether or not to reroute events that Elasticsearch permanently rejected to the `dropped`
+			output, instead of dropping them.
+
+			Events are rejected when the whole bulk request fails with a client error, or when their own
+			item in the bulk response fails with one, such as a mapping error. Rejected events are
+			annotated with the error and the status code Elasticsearch returned.
+			"""
+		required: false
+		type: bool: default: false
+	}
 	suppress_type_name: {
 		description: """
 			Whether or not to send the `type` field to Elasticsearch.
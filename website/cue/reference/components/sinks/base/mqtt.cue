@@ -0,0 +1,364 @@
+package metadata
+
+base: components: sinks: mqtt: configuration: {
+	acknowledgements: {
+		description: """
+			Controls how acknowledgements are handled for this sink.
+
+			See [End-to-end Acknowledgements][e2e_acks] for more information on how Vector handles event acknowledgement.
+
+			[e2e_acks]: https://vector.dev/docs/about/under-the-hood/architecture/end-to-end-acknowledgements/
+			"""
+		required: false
+		type: object: options: enabled: {
+			description: """
+				Whether or not end-to-end acknowledgements are enabled.
+
+				When enabled for a sink, any source connected to that sink, where the source supports
+				end-to-end acknowledgements as well, will wait for events to be acknowledged by the sink
+				before acknowledging them at the source.
+
+				Enabling or disabling acknowledgements at the sink level takes precedence over any global
+				[`acknowledgements`][global_acks] configuration.
+
+				[global_acks]: https://vector.dev/docs/reference/configuration/global-options/#acknowledgements
+				"""
+			required: false
+			type: bool: {}
+		}
+	}
+	connection: {
+		description: "Connection options for the `mqtt` sink."
+		required:    true
+		type: object: options: {
+			client_id: {
+				description: """
+					The client ID to connect with.
+
+					The broker keeps the session of the client, including unacknowledged deliveries, under this
+					ID. Two clients connected with the same ID disconnect each other.
+
+					By default, sources connect with an ID derived from their component ID, so that their
+					session is resumed when they restart, and sinks connect with a random ID.
+					"""
+				required: false
+				type: string: syntax: "literal"
+			}
+			host: {
+				description: "The MQTT broker to connect to."
+				required:    true
+				type: string: syntax: "literal"
+			}
+			keep_alive_secs: {
+				description: "The interval, in seconds, at which the client pings the broker to keep the connection alive."
+				required:    false
+				type: uint: default: 60
+			}
+			password: {
+				description: "The password to authenticate with."
+				required:    false
+				type: string: syntax: "literal"
+			}
+			port: {
+				description: "The TCP port of the MQTT broker."
+				required:    false
+				type: uint: default: 1883
+			}
+			tls: {
+				description: "Configures the TLS options for incoming/outgoing connections."
+				required:    false
+				type: object: options: {
+					alpn_protocols: {
+						description: """
+							Sets the list of supported ALPN protocols.
+
+							Declare the supported ALPN protocols, which are used during negotiation with peer. Prioritized in the order
+							they are defined.
+							"""
+						required: false
+						type: array: items: type: string: syntax: "literal"
+					}
+					ca_file: {
+						description: """
+							Absolute path to an additional CA certificate file.
+
+							The certficate must be in the DER or PEM (X.509) format. Additionally, the certificate can be provided as an inline string in PEM format.
+							"""
+						required: false
+						type: string: syntax: "literal"
+					}
+					crt_file: {
+						description: """
+							Absolute path to a certificate file used to identify this server.
+
+							The certificate must be in DER, PEM (X.509), or PKCS#12 format. Additionally, the certificate can be provided as
+							an inline string in PEM format.
+
+							If this is set, and is not a PKCS#12 archive, `key_file` must also be set.
+							"""
+						required: false
+						type: string: syntax: "literal"
+					}
+					enabled: {
+						description: """
+							Whether or not to require TLS for incoming/outgoing connections.
+
+							When enabled and used for incoming connections, an identity certificate is also required. See `tls.crt_file` for
+							more information.
+							"""
+						required: false
+						type: bool: {}
+					}
+					key_file: {
+						description: """
+							Absolute path to a private key file used to identify this server.
+
+							The key must be in DER or PEM (PKCS#8) format. Additionally, the key can be provided as an inline string in PEM format.
+							"""
+						required: false
+						type: string: syntax: "literal"
+					}
+					key_pass: {
+						description: """
+							Passphrase used to unlock the encrypted key file.
+
+							This has no effect unless `key_file` is set.
+							"""
+						required: false
+						type: string: syntax: "literal"
+					}
+					verify_certificate: {
+						description: """
+							Enables certificate verification.
+
+							If enabled, certificates must be valid in terms of not being expired, as well as being issued by a trusted
+							issuer. This verification operates in a hierarchical manner, checking that not only the leaf certificate (the
+							certificate presented by the client/server) is valid, but also that the issuer of that certificate is valid, and
+							so on until reaching a root certificate.
+
+							Relevant for both incoming and outgoing connections.
+
+							Do NOT set this to `false` unless you understand the risks of not verifying the validity of certificates.
+							"""
+						required: false
+						type: bool: {}
+					}
+					verify_hostname: {
+						description: """
+							Enables hostname verification.
+
+							If enabled, the hostname used to connect to the remote host must be present in the TLS certificate presented by
+							the remote host, either as the Common Name or as an entry in the Subject Alternative Name extension.
+
+							Only relevant for outgoing connections.
+
+							Do NOT set this to `false` unless you understand the risks of not verifying the remote hostname.
+							"""
+						required: false
+						type: bool: {}
+					}
+				}
+			}
+			user: {
+				description: "The username to authenticate with."
+				required:    false
+				type: string: syntax: "literal"
+			}
+		}
+	}
+	encoding: {
+		description: "Encoding configuration."
+		required:    true
+		type: object: options: {
+			avro: {
+				description:   "Apache Avro serializer options."
+				relevant_when: "codec = \"avro\""
+				required:      true
+				type: object: options: schema: {
+					description: "The Avro schema."
+					required:    true
+					type: string: syntax: "literal"
+				}
+			}
+			codec: {
+				required: true
+				type: string: enum: {
+					avro:        "Apache Avro serialization."
+					gelf:        "GELF serialization."
+					json:        "JSON serialization."
+					logfmt:      "Logfmt serialization."
+					native:      "Native Vector serialization based on Protocol Buffers."
+					native_json: "Native Vector serialization based on JSON."
+					raw_message: """
+						No serialization.
+
+						This encoding, specifically, will only encode the `message` field of a log event. Users should take care if
+						they're modifying their log events (such as by using a `remap` transform, etc) and removing the message field
+						while doing additional parsing on it, as this could lead to the encoding emitting empty strings for the given
+						event.
+						"""
+					syslog: """
+						Syslog serialization, as specified in [RFC 5424][rfc5424] or [RFC 3164][rfc3164].
+
+						[rfc5424]: https://www.rfc-editor.org/rfc/rfc5424
+						[rfc3164]: https://www.rfc-editor.org/rfc/rfc3164
+						"""
+					text: """
+						Plaintext serialization.
+
+						This encoding, specifically, will only encode the `message` field of a log event. Users should take care if
+						they're modifying their log events (such as by using a `remap` transform, etc) and removing the message field
+						while doing additional parsing on it, as this could lead to the encoding emitting empty strings for the given
+						event.
+						"""
+				}
+			}
+			except_fields: {
+				description: "List of fields that will be excluded from the encoded event."
+				required:    false
+				type: array: items: type: string: syntax: "literal"
+			}
+			only_fields: {
+				description: "List of fields that will be included in the encoded event."
+				required:    false
+				type: array: items: type: string: syntax: "literal"
+			}
+			syslog: {
+				description: """
+					Syslog serializer options.
+
+					The defaults match the fields set by the `syslog` decoder, so that decoded events are encoded
+					back as they were received.
+					"""
+				relevant_when: "codec = \"syslog\""
+				required:      false
+				type: object: options: {
+					app_name_field: {
+						description: "The field containing the name of the application that produced the event."
+						required:    false
+						type: string: {
+							default: "appname"
+							syntax:  "literal"
+						}
+					}
+					facility_field: {
+						description: """
+							The field containing the facility of the event.
+
+							The facility can either be a name, such as `local0`, or its numeric code. Events without a
+							valid facility use `user`.
+							"""
+						required: false
+						type: string: {
+							default: "facility"
+							syntax:  "literal"
+						}
+					}
+					hostname_field: {
+						description: """
+							The field containing the hostname of the event.
+
+							Events without it use the global `host_key` field instead.
+							"""
+						required: false
+						type: string: {
+							default: "hostname"
+							syntax:  "literal"
+						}
+					}
+					msg_id_field: {
+						description: "The field containing the type of the message."
+						required:    false
+						type: string: {
+							default: "msgid"
+							syntax:  "literal"
+						}
+					}
+					proc_id_field: {
+						description: "The field containing the process ID of the application that produced the event."
+						required:    false
+						type: string: {
+							default: "procid"
+							syntax:  "literal"
+						}
+					}
+					rfc: {
+						description: "The syslog protocol to encode events with."
+						required:    false
+						type: string: {
+							default: "rfc5424"
+							enum: {
+								rfc3164: """
+									The BSD syslog protocol, as specified in [RFC 3164][rfc3164].
+
+									Message IDs and structured data aren't part of this format, and are left out.
+
+									[rfc3164]: https://www.rfc-editor.org/rfc/rfc3164
+									"""
+								rfc5424: """
+									The syslog protocol, as specified in [RFC 5424][rfc5424].
+
+									[rfc5424]: https://www.rfc-editor.org/rfc/rfc5424
+									"""
+							}
+						}
+					}
+					severity_field: {
+						description: """
+							The field containing the severity of the event.
+
+							The severity can either be a name, such as `warning`, or its numeric code. Events without a
+							valid severity use `informational`.
+							"""
+						required: false
+						type: string: {
+							default: "severity"
+							syntax:  "literal"
+						}
+					}
+					structured_data_field: {
+						description: """
+							The field containing the structured data of the event.
+
+							The field must be an object, whose keys are the IDs of the structured data elements, and
+							whose values are objects of the parameters of each element. When unset, no structured data
+							is sent.
+							"""
+						required: false
+						type: string: syntax: "literal"
+					}
+				}
+			}
+			timestamp_format: {
+				description: "Format used for timestamp fields."
+				required:    false
+				type: string: enum: {
+					rfc3339: "Represent the timestamp as a RFC 3339 timestamp."
+					unix:    "Represent the timestamp as a Unix timestamp."
+				}
+			}
+		}
+	}
+	qos: {
+		description: "The quality of service level of MQTT deliveries."
+		required:    false
+		type: string: {
+			default: "at_least_once"
+			enum: {
+				at_least_once: "Messages are delivered at least once, and may be duplicated (QoS 1)."
+				at_most_once:  "Messages are delivered at most once, and may be lost (QoS 0)."
+				exactly_once:  "Messages are delivered exactly once (QoS 2)."
+			}
+		}
+	}
+	retain: {
+		description: "Whether the broker retains the last message published to the topic."
+		required:    false
+		type: bool: default: false
+	}
+	topic: {
+		description: "The MQTT topic to publish messages to."
+		required:    true
+		type: string: syntax: "template"
+	}
+}
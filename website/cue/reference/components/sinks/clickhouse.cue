@@ -100,14 +100,56 @@ components: sinks: clickhouse: {
 				examples: ["mytable"]
 			}
 		}
+		format: {
+			common:      false
+			description: "The format events are inserted in. See [insert formats](#insert-formats)."
+			required:    false
+			type: string: {
+				default: "json_each_row"
+				enum: {
+					json_each_row: "Events are sent as JSON objects, which Clickhouse parses and converts to the types of the columns."
+					native:        "Events are coerced to the types of the columns of the table, and sent column by column in binary."
+					row_binary:    "Events are coerced to the types of the columns of the table, and sent row by row in binary."
+				}
+			}
+		}
 		skip_unknown_fields: {
 			common:      true
-			description: "Sets `input_format_skip_unknown_fields`, allowing Clickhouse to discard fields not present in the table schema."
+			description: "Sets `input_format_skip_unknown_fields`, allowing Clickhouse to discard fields not present in the table schema. Only used by the `json_each_row` format."
 			required:    false
 			type: bool: default: false
 		}
 	}
 
+	how_it_works: {
+		insert_formats: {
+			title: "Insert formats"
+			body: """
+				By default, events are sent as `JSONEachRow`, which the server parses and converts to the
+				types of the columns. For tables receiving a lot of events, the `row_binary` and `native`
+				formats spare the server parsing JSON: the columns of the table are fetched with
+				`DESCRIBE TABLE` when the sink starts, the fields of events are coerced to the types of the
+				columns, and the values are sent in binary, with `native` sending each batch column by
+				column.
+
+				Each column is filled with the top-level field named like it or, failing that, with the
+				field at the path given by its name, so that a `request.method` column is filled with the
+				`method` field of the `request` object. Missing fields are inserted as `NULL` in `Nullable`
+				columns and as the default value of their type otherwise, rather than as the `DEFAULT`
+				expression of the column. `MATERIALIZED` and `ALIAS` columns are computed by the server.
+
+				Strings are parsed into numbers, dates, UUIDs, and IP addresses, timestamps and numbers of
+				seconds are converted to `Date`, `DateTime`, and `DateTime64` columns, arrays to `Array`
+				columns, and objects to `Map` columns. Events with a field that can't be coerced, such as a
+				number out of the range of its column, are dropped with an error naming the column, its
+				type, and where the value is within it.
+
+				The sink fails to start if the table has a column of a type that can't be coerced to, such
+				as `Tuple`, `Nested`, `JSON`, or 128-bit and 256-bit integers.
+				"""
+		}
+	}
+
 	input: {
 		logs:    true
 		metrics: null
@@ -115,6 +157,7 @@ components: sinks: clickhouse: {
 	}
 
 	telemetry: metrics: {
+		component_errors_total:           components.sources.internal_metrics.output.metrics.component_errors_total
 		component_sent_bytes_total:       components.sources.internal_metrics.output.metrics.component_sent_bytes_total
 		component_sent_events_total:      components.sources.internal_metrics.output.metrics.component_sent_events_total
 		component_sent_event_bytes_total: components.sources.internal_metrics.output.metrics.component_sent_event_bytes_total
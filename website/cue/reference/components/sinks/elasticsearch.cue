@@ -373,8 +373,32 @@ components: sinks: elasticsearch: {
 			required: false
 			type: bool: default: false
 		}
+		reroute_dropped: {
+			common: false
+			description: """
+				Whether or not to reroute events that Elasticsearch permanently rejected to the `dropped`
+				output, instead of dropping them. Events are rejected when the whole bulk request fails with a
+				client error, or when their own item in the bulk response fails with one, such as a mapping error.
+				"""
+			required: false
+			type: bool: default: false
+		}
 	}
 
+	outputs: [
+		{
+			name: "dropped"
+			description: """
+				When `reroute_dropped` is set to `true`, events that Elasticsearch permanently rejected are
+				sent to this output instead of being dropped. For a sink component named `foo`, this
+				output can be accessed by specifying `foo.dropped` as the input to another component.
+				Events sent to this output are annotated with `metadata.dropped`, which contains the
+				`reason`, the `message` and the `status` returned by Elasticsearch, along with the
+				`component_id`, `component_type` and `component_kind` of this sink.
+				"""
+		},
+	]
+
 	input: {
 		logs:    true
 		metrics: null
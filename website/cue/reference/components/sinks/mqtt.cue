@@ -0,0 +1,56 @@
+package metadata
+
+components: sinks: mqtt: {
+	title: "MQTT"
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "stream"
+		service_providers: []
+		stateful: false
+	}
+
+	features: {
+		acknowledgements: true
+		healthcheck: enabled: true
+		send: {
+			compression: enabled: false
+			encoding: {
+				enabled: true
+				codec: {
+					enabled: true
+					enum: ["json", "text"]
+				}
+			}
+			request: enabled: false
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        false
+				enabled_by_scheme:      false
+			}
+			to: components._mqtt.features.send.to
+		}
+	}
+
+	support: components._mqtt.support
+
+	configuration: base.components.sinks.mqtt.configuration
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	how_it_works: components._mqtt.how_it_works
+
+	telemetry: metrics: {
+		component_errors_total:      components.sources.internal_metrics.output.metrics.component_errors_total
+		component_sent_bytes_total:  components.sources.internal_metrics.output.metrics.component_sent_bytes_total
+		component_sent_events_total: components.sources.internal_metrics.output.metrics.component_sent_events_total
+	}
+}
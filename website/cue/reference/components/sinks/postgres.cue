@@ -0,0 +1,108 @@
+package metadata
+
+components: sinks: postgres: {
+	title: "PostgreSQL"
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "batch"
+		service_providers: []
+		stateful: false
+	}
+
+	features: {
+		acknowledgements: true
+		healthcheck: enabled: true
+		send: {
+			batch: {
+				enabled:      true
+				common:       false
+				max_bytes:    10_000_000
+				max_events:   1000
+				timeout_secs: 1.0
+			}
+			compression: enabled: false
+			encoding: {
+				enabled: true
+				codec: enabled: false
+			}
+			request: {
+				enabled: true
+				headers: false
+			}
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        false
+				enabled_by_scheme:      false
+			}
+			to: {
+				service: {
+					name:     "PostgreSQL Server"
+					thing:    "a \(name)"
+					url:      urls.postgresql
+					versions: ">= 9.5"
+				}
+
+				interface: {
+					socket: {
+						direction: "outgoing"
+						protocols: ["tcp"]
+						ssl: "optional"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		requirements: [
+			"""
+				[PostgreSQL](\(urls.postgresql)) version `>= 9.5` is required for `on_conflict`.
+				""",
+		]
+		warnings: []
+		notices: []
+	}
+
+	configuration: base.components.sinks.postgres.configuration
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	how_it_works: {
+		methods: {
+			title: "Insert methods"
+			body: """
+				Each batch of events is inserted with a single transaction, so that either all or none
+				of its rows are inserted. The `insert` method uses multi-row `INSERT` statements, and
+				is the only one supporting `on_conflict`. The `copy_csv` and `copy_binary` methods
+				stream the rows with `COPY ... FROM STDIN`, which is faster for large batches.
+				"""
+		}
+		types: {
+			title: "Column types"
+			body: """
+				The `insert` and `copy_binary` methods look up the types of the columns, and convert
+				the value of each field to the type of its column. Strings are parsed into numbers,
+				booleans and timestamps, and integers are taken as seconds since the Unix epoch for
+				timestamp columns. Batches holding a value that can't be converted are rejected.
+
+				The `copy_csv` method sends every value as text, and lets the server parse it into
+				the type of its column, which supports columns of any type.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_sent_bytes_total:       components.sources.internal_metrics.output.metrics.component_sent_bytes_total
+		component_sent_events_total:      components.sources.internal_metrics.output.metrics.component_sent_events_total
+		component_sent_event_bytes_total: components.sources.internal_metrics.output.metrics.component_sent_event_bytes_total
+	}
+}
@@ -178,7 +178,32 @@ components: sinks: splunk_hec_logs: {
 			required: false
 			type: bool: default: false
 		}
+		reroute_dropped: {
+			common: false
+			description: """
+				Whether or not to reroute events that Splunk permanently rejected to the `dropped` output,
+				instead of dropping them. Events are rejected when Splunk responds to the request carrying them
+				with a client error, such as `400 Bad Request`.
+				"""
+			required: false
+			type: bool: default: false
+		}
 	}
+
+	outputs: [
+		{
+			name: "dropped"
+			description: """
+				When `reroute_dropped` is set to `true`, events that Splunk permanently rejected are
+				sent to this output instead of being dropped. For a sink component named `foo`, this
+				output can be accessed by specifying `foo.dropped` as the input to another component.
+				Events sent to this output are annotated with `metadata.dropped`, which contains the
+				`reason`, the `message` and the `status` returned by Splunk, along with the
+				`component_id`, `component_type` and `component_kind` of this sink.
+				"""
+		},
+	]
+
 	input: {
 		logs:    true
 		metrics: null
@@ -195,6 +195,22 @@ components: sources: aws_s3: components._aws & {
 				```
 				"""
 		}
+		list_strategy: {
+			title: "Listing buckets"
+			body:  """
+				With `strategy = "list"`, Vector periodically walks the configured bucket, or `list.prefix`
+				within it, using `ListObjectsV2` and reads every object matching `list.key_pattern` and the
+				`list.modified_after`/`list.modified_before` range. This works for buckets without event
+				notifications, and for backfilling historical objects.
+
+				Objects that have been read are recorded, along with their last-modified time, in a
+				checkpoint within the `data_dir`, so they are not read again across restarts unless they are
+				overwritten. With acknowledgements enabled, an object is only recorded once all of its events
+				have been delivered. The objects read from each listed page, and those read before shutdown,
+				are appended to a journal next to the checkpoint, which is folded into the checkpoint once
+				per walk of the bucket. Objects read since the last append may be read again after a crash.
+				"""
+		}
 	}
 
 	permissions: iam: [
@@ -208,6 +224,10 @@ components: sources: aws_s3: components._aws & {
 				{
 					_action: "GetObject"
 				},
+				{
+					_action:       "ListBucket"
+					required_when: "[`strategy`](#strategy) is set to `list`"
+				},
 			]
 		},
 		{
@@ -105,6 +105,72 @@ base: components: sources: aws_s3: configuration: {
 		required:    false
 		type: string: syntax: "literal"
 	}
+	list: {
+		description: """
+			Configuration options for listing a bucket.
+
+			Only relevant when `strategy = "list"`.
+			"""
+		required: false
+		type: object: options: {
+			bucket: {
+				description: "The name of the bucket to read objects from."
+				required:    true
+				type: string: syntax: "literal"
+			}
+			data_dir: {
+				description: """
+					The directory used to persist the checkpoint of objects that have already been read.
+
+					By default, the global `data_dir` option is used. Please make sure the user Vector is running as has write permissions to this directory.
+					"""
+				required: false
+				type: string: syntax: "literal"
+			}
+			key_pattern: {
+				description: "A regular expression that object keys must match in order to be read."
+				required:    false
+				type: string: {
+					examples: ["\\.log(\\.gz)?$"]
+					syntax: "literal"
+				}
+			}
+			modified_after: {
+				description: "Only objects last modified at or after this time are read."
+				required:    false
+				type: string: {
+					examples: ["2022-11-01T00:00:00Z"]
+					syntax: "literal"
+				}
+			}
+			modified_before: {
+				description: "Only objects last modified before this time are read."
+				required:    false
+				type: string: {
+					examples: ["2022-12-01T00:00:00Z"]
+					syntax: "literal"
+				}
+			}
+			poll_secs: {
+				description: """
+					How long to wait between listings of the bucket, in seconds.
+
+					Each listing walks the whole bucket, or `prefix`, and reads any matching object that has not
+					been read yet, or that has been modified since it was last read.
+					"""
+				required: false
+				type: uint: default: 60
+			}
+			prefix: {
+				description: "Only objects whose key starts with this prefix are read."
+				required:    false
+				type: string: {
+					examples: ["logs/"]
+					syntax: "literal"
+				}
+			}
+		}
+	}
 	multiline: {
 		description: """
 			Multiline aggregation configuration.
@@ -327,11 +393,20 @@ base: components: sources: aws_s3: configuration: {
 		required:    false
 		type: string: {
 			default: "sqs"
-			enum: sqs: """
-				Consumes objects by processing bucket notification events sent to an [AWS SQS queue][aws_sqs].
+			enum: {
+				list: """
+					Consumes objects by periodically listing a bucket with [`ListObjectsV2`][list_objects_v2].
 
-				[aws_sqs]: https://aws.amazon.com/sqs/
-				"""
+					Useful for buckets without event notifications, or to backfill historical objects.
+
+					[list_objects_v2]: https://docs.aws.amazon.com/AmazonS3/latest/API/API_ListObjectsV2.html
+					"""
+				sqs: """
+					Consumes objects by processing bucket notification events sent to an [AWS SQS queue][aws_sqs].
+
+					[aws_sqs]: https://aws.amazon.com/sqs/
+					"""
+			}
 		}
 	}
 	tls_options: {
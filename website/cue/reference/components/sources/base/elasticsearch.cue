@@ -0,0 +1,268 @@
+package metadata
+
+base: components: sources: elasticsearch: configuration: {
+	auth: {
+		description: "Authentication strategies."
+		required:    false
+		type: object: options: {
+			access_key_id: {
+				description:   "The AWS access key ID."
+				relevant_when: "strategy = \"aws\""
+				required:      true
+				type: string: syntax: "literal"
+			}
+			assume_role: {
+				description:   "The ARN of the role to assume."
+				relevant_when: "strategy = \"aws\""
+				required:      true
+				type: string: syntax: "literal"
+			}
+			credentials_file: {
+				description:   "Path to the credentials file."
+				relevant_when: "strategy = \"aws\""
+				required:      true
+				type: string: syntax: "literal"
+			}
+			load_timeout_secs: {
+				description:   "Timeout for successfully loading any credentials, in seconds."
+				relevant_when: "strategy = \"aws\""
+				required:      false
+				type: uint: {}
+			}
+			password: {
+				description:   "Basic authentication password."
+				relevant_when: "strategy = \"basic\""
+				required:      true
+				type: string: syntax: "literal"
+			}
+			profile: {
+				description:   "The credentials profile to use."
+				relevant_when: "strategy = \"aws\""
+				required:      false
+				type: string: syntax: "literal"
+			}
+			region: {
+				description: """
+					The AWS region to send STS requests to.
+
+					If not set, this will default to the configured region
+					for the service itself.
+					"""
+				relevant_when: "strategy = \"aws\""
+				required:      false
+				type: string: syntax: "literal"
+			}
+			secret_access_key: {
+				description:   "The AWS secret access key."
+				relevant_when: "strategy = \"aws\""
+				required:      true
+				type: string: syntax: "literal"
+			}
+			strategy: {
+				required: true
+				type: string: enum: {
+					aws:   "Amazon OpenSearch Service-specific authentication."
+					basic: "HTTP Basic Authentication."
+				}
+			}
+			user: {
+				description:   "Basic authentication username."
+				relevant_when: "strategy = \"basic\""
+				required:      true
+				type: string: syntax: "literal"
+			}
+		}
+	}
+	aws: {
+		description: "Configuration of the region/endpoint to use when interacting with an AWS service."
+		required:    false
+		type: object: options: {
+			endpoint: {
+				description: "The API endpoint of the service."
+				required:    false
+				type: string: syntax: "literal"
+			}
+			region: {
+				description: "The AWS region to use."
+				required:    false
+				type: string: syntax: "literal"
+			}
+		}
+	}
+	data_dir: {
+		description: """
+			The directory used to persist the progress of the query.
+
+			By default, the global `data_dir` option is used. Please make sure the user Vector is running as has write permissions to this directory.
+			"""
+		required: false
+		type: string: syntax: "literal"
+	}
+	endpoint: {
+		description: "The Elasticsearch or OpenSearch endpoint to query, such as `http://10.24.32.122:9200`."
+		required:    true
+		type: string: syntax: "literal"
+	}
+	id_key: {
+		description: """
+			The field to add the ID of each document to.
+
+			The ID is added to the `elasticsearch.id` metadata field when the `vector` log namespace
+			is used.
+			"""
+		required: false
+		type: string: {
+			default: "_id"
+			syntax:  "literal"
+		}
+	}
+	index: {
+		description: """
+			The indices to query, such as `logs-*`, as a comma-separated list of index names,
+			aliases, data streams or patterns.
+			"""
+		required: true
+		type: string: syntax: "literal"
+	}
+	index_key: {
+		description: """
+			The field to add the index of each document to.
+
+			The index is added to the `elasticsearch.index` metadata field when the `vector` log
+			namespace is used.
+			"""
+		required: false
+		type: string: {
+			default: "_index"
+			syntax:  "literal"
+		}
+	}
+	keep_alive_secs: {
+		description: "How long to keep the point in time of the search alive between pages, in seconds."
+		required:    false
+		type: uint: default: 60
+	}
+	page_size: {
+		description: "The number of documents to request per page."
+		required:    false
+		type: uint: default: 1000
+	}
+	poll_interval_secs: {
+		description: """
+			The interval between polls for new documents, in seconds.
+
+			By default, the source stops once the query is read to the end. Polling requires the
+			`timestamp_field` option to be set.
+			"""
+		required: false
+		type: uint: {}
+	}
+	query: {
+		description: """
+			The query to run, in the query DSL, as JSON.
+
+			By default, all the documents are matched.
+			"""
+		required: false
+		type: string: {
+			default: "{\"match_all\":{}}"
+			syntax:  "literal"
+		}
+	}
+	timestamp_field: {
+		description: """
+			The field to sort documents by and to resume from, such as `@timestamp`.
+
+			When set, documents without this field are skipped, and progress is checkpointed after
+			each page. Otherwise, the documents are read in no particular order, and a restart of
+			Vector before the end of the query reads it again from the start.
+			"""
+		required: false
+		type: string: syntax: "literal"
+	}
+	tls: {
+		description: "TLS configuration."
+		required:    false
+		type: object: options: {
+			alpn_protocols: {
+				description: """
+					Sets the list of supported ALPN protocols.
+
+					Declare the supported ALPN protocols, which are used during negotiation with peer. Prioritized in the order
+					they are defined.
+					"""
+				required: false
+				type: array: items: type: string: syntax: "literal"
+			}
+			ca_file: {
+				description: """
+					Absolute path to an additional CA certificate file.
+
+					The certficate must be in the DER or PEM (X.509) format. Additionally, the certificate can be provided as an inline string in PEM format.
+					"""
+				required: false
+				type: string: syntax: "literal"
+			}
+			crt_file: {
+				description: """
+					Absolute path to a certificate file used to identify this server.
+
+					The certificate must be in DER, PEM (X.509), or PKCS#12 format. Additionally, the certificate can be provided as
+					an inline string in PEM format.
+
+					If this is set, and is not a PKCS#12 archive, `key_file` must also be set.
+					"""
+				required: false
+				type: string: syntax: "literal"
+			}
+			key_file: {
+				description: """
+					Absolute path to a private key file used to identify this server.
+
+					The key must be in DER or PEM (PKCS#8) format. Additionally, the key can be provided as an inline string in PEM format.
+					"""
+				required: false
+				type: string: syntax: "literal"
+			}
+			key_pass: {
+				description: """
+					Passphrase used to unlock the encrypted key file.
+
+					This has no effect unless `key_file` is set.
+					"""
+				required: false
+				type: string: syntax: "literal"
+			}
+			verify_certificate: {
+				description: """
+					Enables certificate verification.
+
+					If enabled, certificates must be valid in terms of not being expired, as well as being issued by a trusted
+					issuer. This verification operates in a hierarchical manner, checking that not only the leaf certificate (the
+					certificate presented by the client/server) is valid, but also that the issuer of that certificate is valid, and
+					so on until reaching a root certificate.
+
+					Relevant for both incoming and outgoing connections.
+
+					Do NOT set this to `false` unless you understand the risks of not verifying the validity of certificates.
+					"""
+				required: false
+				type: bool: {}
+			}
+			verify_hostname: {
+				description: """
+					Enables hostname verification.
+
+					If enabled, the hostname used to connect to the remote host must be present in the TLS certificate presented by
+					the remote host, either as the Common Name or as an entry in the Subject Alternative Name extension.
+
+					Only relevant for outgoing connections.
+
+					Do NOT set this to `false` unless you understand the risks of not verifying the remote hostname.
+					"""
+				required: false
+				type: bool: {}
+			}
+		}
+	}
+}
@@ -56,7 +56,7 @@ base: components: sources: host_metrics: configuration: {
 		description: """
 			The list of host metric collector services to use.
 
-			Defaults to all collectors.
+			Defaults to all collectors except `process`.
 			"""
 		required: false
 		type: array: items: type: string: enum: {
@@ -68,6 +68,7 @@ base: components: sources: host_metrics: configuration: {
 			load:       "Load average."
 			memory:     "Memory."
 			network:    "Network."
+			process:    "Process."
 		}
 	}
 	disk: {
@@ -206,6 +207,85 @@ base: components: sources: host_metrics: configuration: {
 			}
 		}
 	}
+	process: {
+		description: """
+			Options for the “process” metrics collector.
+
+			This collector is only available on Linux systems. As every process adds its own set of
+			metrics, it is not part of the default collectors.
+			"""
+		required: false
+		type: object: options: {
+			base_dir: {
+				description: "Base procfs directory, for testing use only"
+				required:    false
+				type: string: syntax: "literal"
+			}
+			cmdlines: {
+				description: """
+					Lists of process command line patterns to include or exclude.
+
+					The command line of a process is its arguments joined by spaces. Kernel threads don't have a
+					command line, so they are excluded as soon as any pattern is included.
+					"""
+				required: false
+				type: object: {
+					default: {
+						excludes: null
+						includes: null
+					}
+					options: {
+						excludes: {
+							description: "Any patterns which should be excluded."
+							required:    false
+							type: array: items: type: string: syntax: "literal"
+						}
+						includes: {
+							description: "Any patterns which should be included."
+							required:    false
+							type: array: items: type: string: syntax: "literal"
+						}
+					}
+				}
+			}
+			max_processes: {
+				description: """
+					The maximum number of processes to report metrics for.
+
+					When more processes match the filters, only the ones with the highest CPU time are reported.
+					This bounds the cardinality of the emitted metrics.
+					"""
+				required: false
+				type: uint: default: 100
+			}
+			names: {
+				description: """
+					Lists of process name patterns to include or exclude.
+
+					The name of a process is the name of its executable, truncated to 15 characters.
+					"""
+				required: false
+				type: object: {
+					default: {
+						excludes: null
+						includes: null
+					}
+					options: {
+						excludes: {
+							description: "Any patterns which should be excluded."
+							required:    false
+							type: array: items: type: string: syntax: "literal"
+						}
+						includes: {
+							description: "Any patterns which should be included."
+							required:    false
+							type: array: items: type: string: syntax: "literal"
+						}
+					}
+				}
+			}
+		}
+	}
 	scrape_interval_secs: {
 		description: "The interval between metric gathering, in seconds."
 		required:    false
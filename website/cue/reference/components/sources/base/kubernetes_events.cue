@@ -0,0 +1,58 @@
+package metadata
+
+base: components: sources: kubernetes_events: configuration: {
+	data_dir: {
+		description: """
+			The directory used to persist the resource version of the last event sent.
+
+			By default, the global `data_dir` option is used. Please make sure the user Vector is running as has write permissions to this directory.
+			"""
+		required: false
+		type: string: syntax: "literal"
+	}
+	delay_deletion_ms: {
+		description: """
+			How long to delay removing `Pod`s from our map when we receive a deletion
+			event from the watched stream, so that the events about deleted `Pod`s are
+			still given their labels.
+			"""
+		required: false
+		type: uint: default: 60000
+	}
+	field_selector: {
+		description: """
+			Specifies the field selector to filter `Event`s with, such as `type=Warning` or
+			`involvedObject.namespace=default`.
+			"""
+		required: false
+		type: string: syntax: "literal"
+	}
+	include_pod_labels: {
+		description: """
+			Whether or not to add the labels of the `Pod`s that events are about, in the
+			`involved_object.labels` field.
+
+			The `Pod`s of the whole cluster are watched to do so.
+			"""
+		required: false
+		type: bool: default: true
+	}
+	kube_config_file: {
+		description: """
+			Optional path to a kubeconfig file readable by Vector. If not set,
+			Vector will try to connect to Kubernetes using in-cluster configuration.
+			"""
+		required: false
+		type: string: syntax: "literal"
+	}
+	label_selector: {
+		description: "Specifies the label selector to filter `Event`s with."
+		required:    false
+		type: string: syntax: "literal"
+	}
+	log_namespace: {
+		description: "The namespace to use for logs. This overrides the global setting."
+		required:    false
+		type: bool: {}
+	}
+}
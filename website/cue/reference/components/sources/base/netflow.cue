@@ -0,0 +1,95 @@
+package metadata
+
+base: components: sources: netflow: configuration: {
+	address: {
+		description: "The address to listen for flow export packets on."
+		required:    true
+		type: {
+			number: {}
+			string: syntax: "literal"
+		}
+	}
+	host_key: {
+		description: """
+			Overrides the name of the log field used to add the address of the exporter of each flow
+			to each event.
+
+			By default, the [global `log_schema.host_key` option][global_host_key] is used.
+
+			[global_host_key]: https://vector.dev/docs/reference/configuration/global-options/#log_schema.host_key
+			"""
+		required: false
+		type: string: syntax: "literal"
+	}
+	log_namespace: {
+		description: "The namespace to use for logs. This overrides the global setting."
+		required:    false
+		type: bool: {}
+	}
+	max_length: {
+		description: """
+			The maximum size, in bytes, of incoming packets.
+
+			Packets larger than this are discarded.
+			"""
+		required: false
+		type: uint: default: 65535
+	}
+	max_templates: {
+		description: """
+			The maximum number of NetFlow v9 and IPFIX templates to keep, across all exporters.
+
+			Past this number, the least recently used templates are evicted, and the records they
+			describe are dropped until their exporters send them again.
+			"""
+		required: false
+		type: uint: default: 10000
+	}
+	metrics: {
+		description: """
+			When set, flows are emitted as metrics counting them, their bytes, and their packets,
+			rather than as logs.
+			"""
+		required: false
+		type: object: options: {
+			namespace: {
+				description: "The namespace of the metrics."
+				required:    false
+				type: string: {
+					default: "netflow"
+					syntax:  "literal"
+				}
+			}
+			scale_by_sampling_rate: {
+				description: """
+					Whether the bytes and packets of sampled flows are multiplied by their sampling interval,
+					to estimate the traffic the samples were taken from.
+					"""
+				required: false
+				type: bool: default: true
+			}
+			tags: {
+				description: """
+					The fields of flows to tag the metrics with.
+
+					The address of the exporter is always added, as the `host` tag. Fields missing from a
+					flow are left out of the tags of its metrics.
+					"""
+				required: false
+				type: array: {
+					default: ["flow_type", "protocol"]
+					items: type: string: syntax: "literal"
+				}
+			}
+		}
+	}
+	receive_buffer_bytes: {
+		description: """
+			The size, in bytes, of the receive buffer used for the listening socket.
+
+			This should not typically needed to be changed.
+			"""
+		required: false
+		type: uint: {}
+	}
+}
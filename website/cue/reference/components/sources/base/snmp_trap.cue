@@ -0,0 +1,63 @@
+package metadata
+
+base: components: sources: snmp_trap: configuration: {
+	address: {
+		description: "The address to listen for traps on."
+		required:    true
+		type: {
+			number: {}
+			string: syntax: "literal"
+		}
+	}
+	host_key: {
+		description: """
+			Overrides the name of the log field used to add the address of the sender of each trap
+			to each event.
+
+			By default, the [global `log_schema.host_key` option][global_host_key] is used.
+
+			[global_host_key]: https://vector.dev/docs/reference/configuration/global-options/#log_schema.host_key
+			"""
+		required: false
+		type: string: syntax: "literal"
+	}
+	log_namespace: {
+		description: "The namespace to use for logs. This overrides the global setting."
+		required:    false
+		type: bool: {}
+	}
+	max_length: {
+		description: """
+			The maximum size, in bytes, of incoming traps.
+
+			Traps larger than this are discarded.
+			"""
+		required: false
+		type: uint: default: 65535
+	}
+	mib_directory: {
+		description: """
+			A directory of MIBs compiled into mappings of names to object identifiers, used to name the
+			object identifiers of traps.
+
+			Files with the `.json` extension are read as the output of the JSON code generator of
+			`pysmi`, such as `mibdump --destination-format json`. Files with the `.txt` extension are
+			read as lines of a name and an object identifier separated by whitespace, such as the
+			output of `snmptranslate -Tz`. Other files are ignored.
+
+			When set, the names of the enterprise, the trap, and the variable bindings are added to
+			each event, in the `enterprise_name`, `trap_name`, and `name` fields respectively.
+			"""
+		required: false
+		type: string: syntax: "literal"
+	}
+	receive_buffer_bytes: {
+		description: """
+			The size, in bytes, of the receive buffer used for the listening socket.
+
+			This should not typically needed to be changed.
+			"""
+		required: false
+		type: uint: {}
+	}
+}
@@ -0,0 +1,148 @@
+package metadata
+
+components: sources: elasticsearch: {
+	title: "Elasticsearch"
+
+	description: """
+		Reads the documents of \(services.elasticsearch.name) or OpenSearch indices matching a query,
+		such as to migrate them between clusters or to replay them into new pipelines, and
+		optionally polls for new documents.
+		"""
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		deployment_roles: ["aggregator"]
+		development:   "beta"
+		egress_method: "batch"
+		stateful:      false
+	}
+
+	features: {
+		acknowledgements: false
+		collect: {
+			checkpoint: enabled: true
+			from: {
+				service: services.elasticsearch
+
+				interface: {
+					socket: {
+						api: {
+							title: "Elasticsearch search API"
+							url:   urls.elasticsearch_search_after
+						}
+						direction: "outgoing"
+						protocols: ["http"]
+						ssl: "optional"
+					}
+				}
+			}
+			proxy: enabled: true
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        false
+				enabled_by_scheme:      true
+			}
+		}
+		multiline: enabled: false
+	}
+
+	support: {
+		requirements: [
+			"""
+				Elasticsearch 7.10 or later, or OpenSearch 2.4 or later, is required for
+				[point in time](\(urls.elasticsearch_point_in_time)) searches.
+				""",
+		]
+		warnings: []
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: base.components.sources.elasticsearch.configuration
+
+	output: logs: document: {
+		description: "A document of an index, with the fields of its source."
+		fields: {
+			"*": {
+				description: "The fields of the `_source` of the document."
+				required:    true
+				type: "*": {}
+			}
+			_id: {
+				description: "The ID of the document. This field's name is set by the `id_key` option."
+				required:    true
+				type: string: {
+					examples: ["TWR8yYQBfxGGd6rL5nBk"]
+				}
+			}
+			_index: {
+				description: "The index of the document. This field's name is set by the `index_key` option."
+				required:    true
+				type: string: {
+					examples: ["logs-2022.11.01"]
+				}
+			}
+			source_type: {
+				description: "The name of the source type."
+				required:    true
+				type: string: {
+					examples: ["elasticsearch"]
+				}
+			}
+			timestamp: {
+				description: "The time the document was read, unless the document has a `timestamp` field."
+				required:    true
+				type: timestamp: {}
+			}
+		}
+	}
+
+	how_it_works: {
+		pagination: {
+			title: "Pagination"
+			body: """
+				The query is run in a [point in time](\(urls.elasticsearch_point_in_time)) of the indices,
+				so that all its pages are taken from the same state of the indices, and the pages are
+				requested with [`search_after`](\(urls.elasticsearch_search_after)). Whether the endpoint
+				is served by Elasticsearch or by OpenSearch, whose [point in time](\(urls.opensearch_point_in_time))
+				API differs, is told by its root endpoint.
+				"""
+		}
+		checkpointing: {
+			title: "Checkpointing"
+			body: """
+				With the `timestamp_field` option, documents are sorted by that field, and the value of
+				the field in the last document sent, with the IDs of the documents sent with that value,
+				are checkpointed in the `data_dir` after each page. When Vector restarts, or when polling
+				with the `poll_interval_secs` option, the query is run again from that value, leaving out
+				the documents already sent. Documents indexed late with a value older than the checkpoint
+				aren't read.
+
+				Without the `timestamp_field` option, the end of the query is checkpointed, so that the
+				query isn't read again when Vector restarts. Remove the checkpoint from the `data_dir` to
+				read it again.
+				"""
+		}
+		reindexing: {
+			title: "Reindexing"
+			body: """
+				Elasticsearch rejects documents with `_index` or `_id` fields. To index the documents
+				read into another cluster with the `elasticsearch` sink, keeping their IDs, set its
+				`id_key` option to `_id` and its `encoding.except_fields` option to `["_index", "_id"]`.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_errors_total:               components.sources.internal_metrics.output.metrics.component_errors_total
+		component_received_bytes_total:       components.sources.internal_metrics.output.metrics.component_received_bytes_total
+		component_received_events_total:      components.sources.internal_metrics.output.metrics.component_received_events_total
+		component_received_event_bytes_total: components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
+	}
+}
@@ -68,7 +68,7 @@ components: sources: host_metrics: {
 
 	configuration: {
 		collectors: {
-			description: "The list of host metric collector services to use. Defaults to all collectors."
+			description: "The list of host metric collector services to use. Defaults to all collectors except `process`."
 			common:      true
 			required:    false
 			type: array: {
@@ -83,6 +83,7 @@ components: sources: host_metrics: {
 						host:       "Metrics related to host"
 						memory:     "Metrics related to memory utilization."
 						network:    "Metrics related to network utilization."
+						process:    "Metrics related to individual processes (Linux only)."
 					}
 				}
 			}
@@ -401,6 +402,109 @@ components: sources: host_metrics: {
 				}
 			}
 		}
+		process: {
+			common:      false
+			description: """
+				Options for the "process" metrics collector.
+
+				Note: this collector is only available on Linux systems, and is not part of the default collectors.
+				"""
+			required: false
+			type: object: options: {
+				names: {
+					common:      false
+					required:    false
+					description: "Lists of process name patterns to include or exclude."
+					type: object: options: {
+						includes: {
+							required: false
+							common:   false
+							description: """
+								The list of process name patterns for which to gather metrics.
+
+								Defaults to including all processes.
+
+								The patterns are matched using globbing.
+								"""
+							type: array: {
+								default: ["*"]
+								items: type: string: {
+									examples: ["vector", "postgres*"]
+								}
+							}
+						}
+						excludes: {
+							required: false
+							common:   false
+							description: """
+								The list of process name patterns for which to not gather metrics.
+
+								Defaults to excluding no processes.
+
+								The patterns are matched using globbing.
+								"""
+							type: array: {
+								default: []
+								items: type: string: {
+									examples: ["vector", "postgres*"]
+								}
+							}
+						}
+					}
+				}
+				cmdlines: {
+					common:      false
+					required:    false
+					description: "Lists of process command line patterns to include or exclude."
+					type: object: options: {
+						includes: {
+							required: false
+							common:   false
+							description: """
+								The list of process command line patterns for which to gather metrics.
+
+								Defaults to including all processes.
+
+								The patterns are matched using globbing.
+								"""
+							type: array: {
+								default: ["*"]
+								items: type: string: {
+									examples: ["*--config*"]
+								}
+							}
+						}
+						excludes: {
+							required: false
+							common:   false
+							description: """
+								The list of process command line patterns for which to not gather metrics.
+
+								Defaults to excluding no processes.
+
+								The patterns are matched using globbing.
+								"""
+							type: array: {
+								default: []
+								items: type: string: {
+									examples: ["*--config*"]
+								}
+							}
+						}
+					}
+				}
+				max_processes: {
+					common:      false
+					required:    false
+					description: "The maximum number of processes to report metrics for. When more processes match the filters, only the ones with the highest CPU time are reported."
+					type: uint: {
+						unit:    null
+						default: 100
+						examples: [10, 500]
+					}
+				}
+			}
+		}
 	}
 
 	output: metrics: {
@@ -502,6 +606,25 @@ components: sources: host_metrics: {
 		network_transmit_packets_drop_total: _host & _network_nomac & {description: "The number of packets dropped during transmits on this interface."}
 		network_transmit_packets_total:      _host & _network_nomac & {description: "The number of packets transmitted on this interface."}
 
+		// Host processes
+		process_cpu_seconds_total: _host & {
+			description: "The number of CPU seconds used by the process in different modes."
+			type:        "counter"
+			tags:        _process_tags & {
+				mode: {
+					description: "Which mode the process was running in during the given time."
+					required:    true
+					examples: ["system", "user"]
+				}
+			}
+			relevant_when: "OS is Linux"
+		}
+		process_memory_rss_bytes:     _host & _process_gauge & {description: "The number of bytes of main memory used by the process (resident set size)."}
+		process_memory_virtual_bytes: _host & _process_gauge & {description: "The number of bytes of virtual memory mapped by the process."}
+		process_open_fds:             _host & _process_gauge & {description: "The number of file descriptors opened by the process. Only available for processes readable by Vector."}
+		process_start_time_seconds:   _host & _process_gauge & {description: "The UNIX timestamp the process was started at."}
+		process_threads:              _host & _process_gauge & {description: "The number of threads of the process."}
+
 		// Helpers
 		_host: {
 			default_namespace: "host"
@@ -591,6 +714,24 @@ components: sources: host_metrics: {
 			}
 		}
 		_network_nomac: _network_gauge & {relevant_when: "OS is not macOS"}
+		_process_tags:  _host_metrics_tags & {
+			collector: examples: ["process"]
+			name: {
+				description: "The name of the process executable."
+				required:    true
+				examples: ["vector", "postgres"]
+			}
+			pid: {
+				description: "The process identifier."
+				required:    true
+				examples: ["1", "4242"]
+			}
+		}
+		_process_gauge: {
+			type:          "gauge"
+			tags:          _process_tags
+			relevant_when: "OS is Linux"
+		}
 	}
 
 	telemetry: metrics: {
@@ -192,6 +192,12 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _internal_metrics_tags
 		}
+		reload_rollbacks_total: {
+			description:       "The total number of reloaded configurations that were rolled back after failing probation."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _internal_metrics_tags
+		}
 		reloaded_total: {
 			description:       "The total number of times the Vector instance has been reloaded."
 			type:              "counter"
@@ -508,6 +514,12 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _component_tags
 		}
+		component_cpu_time_nanoseconds_total: {
+			description:       "The total time, in nanoseconds, spent polling the tasks of this component. This is the time the component kept a worker thread busy."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
 		component_discarded_events_total: {
 			description:       "The number of events dropped by this component."
 			type:              "counter"
@@ -984,6 +996,18 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _component_tags
 		}
+		source_to_sink_latency_seconds: {
+			description:       "The time elapsed between an event being sent by a source and it being delivered by a sink, expressed as fractional seconds. Events that fail to be delivered are not recorded. Only recorded with the `--latency-tracking` flag."
+			type:              "histogram"
+			default_namespace: "vector"
+			tags:              _component_tags & {
+				source_id: {
+					description: "The ID of the source the event was ingested by."
+					required:    true
+					examples: ["my_source"]
+				}
+			}
+		}
 		splunk_pending_acks: {
 			description:       "The number of outstanding Splunk HEC indexer acknowledgement acks."
 			type:              "gauge"
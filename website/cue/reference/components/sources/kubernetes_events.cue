@@ -0,0 +1,236 @@
+package metadata
+
+components: sources: kubernetes_events: {
+	title: "Kubernetes Events"
+
+	description: """
+		Collects the events of a Kubernetes cluster, such as the ones reporting
+		out of memory kills, failed scheduling, or crash loops, from the Kubernetes API.
+		"""
+
+	classes: {
+		commonly_used: false
+		delivery:      "best_effort"
+		deployment_roles: ["aggregator"]
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {
+		acknowledgements: false
+		collect: {
+			checkpoint: enabled: true
+			from: {
+				service: services.kubernetes
+
+				interface: {
+					socket: {
+						api: {
+							title: "Kubernetes Events API"
+							url:   urls.kubernetes_events
+						}
+						direction: "outgoing"
+						protocols: ["http"]
+						ssl: "required"
+					}
+				}
+			}
+		}
+		multiline: enabled: false
+	}
+
+	support: {
+		requirements: [
+			"""
+				[Kubernetes](\(urls.kubernetes)) version `\(services.kubernetes.versions)` is required.
+				""",
+			"""
+				The service account Vector runs as must be allowed to `list` and `watch` `events`, and
+				`pods` unless `include_pod_labels` is `false`.
+				""",
+		]
+		warnings: []
+		notices: []
+	}
+
+	installation: {
+		platform_name: "kubernetes"
+	}
+
+	configuration: base.components.sources.kubernetes_events.configuration
+
+	output: logs: event: {
+		description: "A Kubernetes event."
+		fields: {
+			action: {
+				description: "What action was taken, or failed, regarding the involved object."
+				required:    false
+				type: string: {
+					examples: ["Binding"]
+				}
+			}
+			count: {
+				description: "The number of times the event occurred."
+				required:    false
+				type: uint: {
+					examples: [3]
+					unit: null
+				}
+			}
+			event_time: {
+				description: "The time the event was first observed, for events reported with the `events.k8s.io` API."
+				required:    false
+				type: timestamp: {}
+			}
+			first_timestamp: {
+				description: "The time the event was first recorded."
+				required:    false
+				type: timestamp: {}
+			}
+			involved_object: {
+				description: "The object the event is about."
+				required:    true
+				type: object: {
+					examples: [
+						{
+							"kind":      "Pod"
+							"name":      "web-0"
+							"namespace": "default"
+							"uid":       "3f6a1b2c-8d2e-4c1a-9f0e-2a7b5c9d1e4f"
+							"labels": {
+								"app": "web"
+							}
+						},
+					]
+					options: {}
+				}
+			}
+			last_timestamp: {
+				description: "The time the event was last recorded."
+				required:    false
+				type: timestamp: {}
+			}
+			message: {
+				description: "The human-readable description of the event."
+				required:    false
+				type: string: {
+					examples: ["Memory cgroup out of memory: Killed process 4242 (nginx)"]
+				}
+			}
+			name: {
+				description: "The name of the event object."
+				required:    false
+				type: string: {
+					examples: ["web-0.17a1b2c3d4e5f678"]
+				}
+			}
+			namespace: {
+				description: "The namespace of the event object."
+				required:    false
+				type: string: {
+					examples: ["default"]
+				}
+			}
+			reason: {
+				description: "The short, machine-readable reason of the event."
+				required:    false
+				type: string: {
+					examples: ["OOMKilling", "FailedScheduling", "BackOff"]
+				}
+			}
+			related: {
+				description: "The secondary object the event is about, if any."
+				required:    false
+				type: object: {
+					examples: []
+					options: {}
+				}
+			}
+			reporting_component: {
+				description: "The controller that reported the event."
+				required:    false
+				type: string: {
+					examples: ["kubelet"]
+				}
+			}
+			reporting_instance: {
+				description: "The instance of the controller that reported the event."
+				required:    false
+				type: string: {
+					examples: ["node-1"]
+				}
+			}
+			resource_version: {
+				description: "The resource version of the event object."
+				required:    false
+				type: string: {
+					examples: ["1234"]
+				}
+			}
+			source: {
+				description: "The component, and the host, that reported the event."
+				required:    false
+				type: object: {
+					examples: [{"component": "kubelet", "host": "node-1"}]
+					options: {}
+				}
+			}
+			source_type: {
+				description: "The name of the source type."
+				required:    true
+				type: string: {
+					examples: ["kubernetes_events"]
+				}
+			}
+			timestamp: {
+				description: "The time the event was last observed, recorded, or first recorded, whichever is known."
+				required:    true
+				type: timestamp: {}
+			}
+			type: {
+				description: "The type of the event."
+				required:    false
+				type: string: {
+					enum: {
+						Normal:  "An event reporting normal operation."
+						Warning: "An event reporting a problem."
+					}
+				}
+			}
+			uid: {
+				description: "The UID of the event object."
+				required:    false
+				type: string: {
+					examples: ["6c1e2f3a-4b5d-4e6f-8a9b-0c1d2e3f4a5b"]
+				}
+			}
+		}
+	}
+
+	how_it_works: {
+		deduplication: {
+			title: "Deduplication"
+			body: """
+				The events already emitted are listed again by the Kubernetes API whenever the watch
+				restarts, including when Vector restarts. The resource version of the last event sent is
+				checkpointed in the `data_dir`, and only the events with a greater resource version are
+				emitted, so that each change to an event is emitted once.
+				"""
+		}
+		enrichment: {
+			title: "Enrichment"
+			body: """
+				The object each event is about is given in the `involved_object` field. When it's a
+				`Pod`, its labels are added in `involved_object.labels`, from a cache of the `Pod`s of the
+				cluster kept up to date with the Kubernetes API, unless `include_pod_labels` is `false`.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_errors_total:               components.sources.internal_metrics.output.metrics.component_errors_total
+		component_received_events_total:      components.sources.internal_metrics.output.metrics.component_received_events_total
+		component_received_event_bytes_total: components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
+	}
+}
@@ -0,0 +1,262 @@
+package metadata
+
+components: sources: netflow: {
+	_port: 2055
+
+	title: "NetFlow"
+
+	classes: {
+		commonly_used: false
+		delivery:      "best_effort"
+		deployment_roles: ["aggregator"]
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      true
+	}
+
+	features: {
+		acknowledgements: false
+		multiline: enabled: false
+		receive: {
+			from: {
+				service: services.netflow
+				interface: socket: {
+					api: {
+						title: "NetFlow, IPFIX, and sFlow"
+						url:   urls.rfc_7011
+					}
+					direction: "incoming"
+					port:      _port
+					protocols: ["udp"]
+					ssl: "disabled"
+				}
+			}
+			receive_buffer_bytes: enabled: true
+			tls: enabled:                  false
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: base.components.sources.netflow.configuration
+
+	output: {
+		logs: flow: {
+			description: """
+				An individual flow, or an individual sample of sFlow. Only the most common fields are
+				listed here, see [how flows are normalized](#flow-fields) for the others.
+				"""
+			fields: {
+				bytes: {
+					description: "The number of bytes of the flow, or the length of the sampled frame for sFlow."
+					required:    false
+					type: uint: {
+						examples: [3456]
+						unit: "bytes"
+					}
+				}
+				dst_addr: {
+					description: "The destination address of the flow."
+					required:    false
+					type: string: {
+						examples: ["10.0.0.2", "2001:db8::2"]
+					}
+				}
+				dst_port: {
+					description: "The destination port of the flow."
+					required:    false
+					type: uint: {
+						examples: [443]
+						unit: null
+					}
+				}
+				flow_end: {
+					description: "The time the last packet of the flow was seen."
+					required:    false
+					type: timestamp: {}
+				}
+				flow_start: {
+					description: "The time the first packet of the flow was seen."
+					required:    false
+					type: timestamp: {}
+				}
+				flow_type: {
+					description: "The protocol the flow was exported with."
+					required:    true
+					type: string: {
+						enum: {
+							netflow_v5: "NetFlow v5"
+							netflow_v9: "NetFlow v9"
+							ipfix:      "IPFIX"
+							sflow_v5:   "sFlow v5"
+						}
+					}
+				}
+				host: {
+					description: "The address of the exporter of the flow."
+					required:    true
+					type: string: {
+						examples: ["192.0.2.1"]
+					}
+				}
+				packets: {
+					description: "The number of packets of the flow, which is `1` for samples of sFlow."
+					required:    false
+					type: uint: {
+						examples: [12]
+						unit: null
+					}
+				}
+				protocol: {
+					description: "The IP protocol number of the flow, such as `6` for TCP and `17` for UDP."
+					required:    false
+					type: uint: {
+						examples: [6]
+						unit: null
+					}
+				}
+				sampling_interval: {
+					description: "The number of packets out of which one was sampled, when the exporter samples packets."
+					required:    false
+					type: uint: {
+						examples: [100]
+						unit: null
+					}
+				}
+				sequence_number: {
+					description: "The sequence number of the packet the flow was exported in."
+					required:    true
+					type: uint: {
+						examples: [42]
+						unit: null
+					}
+				}
+				source_type: {
+					description: "The name of the source type."
+					required:    true
+					type: string: {
+						examples: ["netflow"]
+					}
+				}
+				src_addr: {
+					description: "The source address of the flow."
+					required:    false
+					type: string: {
+						examples: ["10.0.0.1", "2001:db8::1"]
+					}
+				}
+				src_port: {
+					description: "The source port of the flow."
+					required:    false
+					type: uint: {
+						examples: [51234]
+						unit: null
+					}
+				}
+				timestamp: fields._current_timestamp
+			}
+		}
+		metrics: {
+			_tags: {
+				host: {
+					description: "The address of the exporter of the flow."
+					required:    true
+					examples: ["192.0.2.1"]
+				}
+				flow_type: {
+					description: "The `flow_type` field of the flow, unless the `metrics.tags` option leaves it out."
+					required:    false
+					examples: ["ipfix"]
+				}
+				protocol: {
+					description: "The `protocol` field of the flow, unless the `metrics.tags` option leaves it out."
+					required:    false
+					examples: ["6"]
+				}
+			}
+			flows_total: {
+				description:       "The number of flows, emitted instead of logs when `metrics` is set."
+				type:              "counter"
+				default_namespace: "netflow"
+				tags:              _tags
+			}
+			bytes_total: {
+				description:       "The number of bytes of the flows, emitted instead of logs when `metrics` is set."
+				type:              "counter"
+				default_namespace: "netflow"
+				tags:              _tags
+			}
+			packets_total: {
+				description:       "The number of packets of the flows, emitted instead of logs when `metrics` is set."
+				type:              "counter"
+				default_namespace: "netflow"
+				tags:              _tags
+			}
+		}
+	}
+
+	how_it_works: {
+		protocols: {
+			title: "Protocols"
+			body: """
+				[NetFlow v5](\(urls.netflow)), [NetFlow v9](\(urls.rfc_3954)),
+				[IPFIX](\(urls.rfc_7011)), and [sFlow v5](\(urls.sflow_v5)) are received on the same
+				port, and told apart by the version in the header of each packet.
+
+				NetFlow v9 and IPFIX describe their data records with templates sent beforehand. The
+				templates are kept for each exporter and observation domain, so that the records of
+				their later packets can be decoded. Records whose template hasn't been received yet,
+				such as the ones sent right after Vector starts, are discarded with a warning until the
+				exporter sends its templates again. The records of options templates, which describe the
+				exporter rather than flows, are discarded.
+
+				The flow samples of sFlow are given one event each, with the fields decoded from the
+				headers of the sampled packet and from the extended switch, router, and gateway records.
+				The generic interface counters of counter samples are given as events with the
+				`sample_type` field set to `counter` and `if_*` fields.
+				"""
+		}
+		flow_fields: {
+			title: "Flow fields"
+			body: """
+				Fields are given the same names whatever the protocol, such as `src_addr`, `dst_port`,
+				`bytes`, `packets`, `input_interface`, `src_as`, or `tcp_flags`. The fields of
+				NetFlow v9 and IPFIX records without such a name are named after their
+				[information element](\(urls.ipfix_information_elements)), as `ie_<id>`, or as
+				`ie_<enterprise>_<id>` for the ones specific to an enterprise.
+
+				Addresses are given in their usual textual form, and times as timestamps. Times relative
+				to the boot of an IPFIX exporter, which its packets don't give, are given as the number of
+				milliseconds since the boot in `flow_start_uptime` and `flow_end_uptime` fields instead.
+				Integers too large for a signed 64-bit integer are given as strings, and values of
+				unknown fields larger than eight bytes as hexadecimal strings.
+				"""
+		}
+		metrics: {
+			title: "Metrics"
+			body: """
+				When the `metrics` option is set, each flow is emitted as the `flows_total`,
+				`bytes_total`, and `packets_total` counters rather than as a log, tagged with the fields
+				of the `metrics.tags` option. The bytes and packets of flows with a sampling interval are
+				multiplied by it, unless `metrics.scale_by_sampling_rate` is `false`. The counter samples
+				of sFlow are left out.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_discarded_events_total:     components.sources.internal_metrics.output.metrics.component_discarded_events_total
+		component_errors_total:               components.sources.internal_metrics.output.metrics.component_errors_total
+		component_received_bytes_total:       components.sources.internal_metrics.output.metrics.component_received_bytes_total
+		component_received_events_total:      components.sources.internal_metrics.output.metrics.component_received_events_total
+		component_received_event_bytes_total: components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
+	}
+}
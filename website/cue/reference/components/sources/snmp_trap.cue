@@ -0,0 +1,214 @@
+package metadata
+
+components: sources: snmp_trap: {
+	_port: 162
+
+	title: "SNMP Trap"
+
+	classes: {
+		commonly_used: false
+		delivery:      "best_effort"
+		deployment_roles: ["aggregator"]
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {
+		acknowledgements: false
+		multiline: enabled: false
+		receive: {
+			from: {
+				service: services.snmp
+				interface: socket: {
+					api: {
+						title: "SNMP traps"
+						url:   urls.rfc_3416
+					}
+					direction: "incoming"
+					port:      _port
+					protocols: ["udp"]
+					ssl: "disabled"
+				}
+			}
+			receive_buffer_bytes: enabled: true
+			tls: enabled:                  false
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: base.components.sources.snmp_trap.configuration
+
+	output: logs: trap: {
+		description: "An individual SNMP trap."
+		fields: {
+			agent_address: {
+				description: """
+					The address of the agent that generated the trap. It's given by SNMPv1 traps, and
+					by the `snmpTrapAddress.0` variable binding of SNMPv2c traps forwarded by proxies.
+					Otherwise, it's the address of the sender of the trap.
+					"""
+				required: true
+				type: string: {
+					examples: ["10.0.0.1"]
+				}
+			}
+			community: {
+				description: "The community of the trap."
+				required:    true
+				type: string: {
+					examples: ["public"]
+				}
+			}
+			enterprise: {
+				description: "The object identifier of the enterprise that defines the trap."
+				required:    true
+				type: string: {
+					examples: ["1.3.6.1.4.1.8072.3.2.10"]
+				}
+			}
+			enterprise_name: {
+				description: "The name of the enterprise, when `mib_directory` is set and defines it."
+				required:    false
+				type: string: {
+					examples: ["NET-SNMP-TC::linux"]
+				}
+			}
+			generic_trap: {
+				description: """
+					The generic trap number, from `0` for `coldStart` to `5` for `egpNeighborLoss`, or
+					`6` for traps specific to the enterprise.
+					"""
+				required: true
+				type: uint: {
+					examples: [2]
+					unit: null
+				}
+			}
+			host: {
+				description: "The address of the sender of the trap."
+				required:    true
+				type: string: {
+					examples: ["10.0.0.1"]
+				}
+			}
+			source_type: {
+				description: "The name of the source type."
+				required:    true
+				type: string: {
+					examples: ["snmp_trap"]
+				}
+			}
+			specific_trap: {
+				description: "The trap number specific to the enterprise, when `generic_trap` is `6`."
+				required:    true
+				type: uint: {
+					examples: [0]
+					unit: null
+				}
+			}
+			timestamp: fields._current_timestamp
+			trap_name: {
+				description: "The name of the trap, when `mib_directory` is set and defines it."
+				required:    false
+				type: string: {
+					examples: ["IF-MIB::linkDown"]
+				}
+			}
+			trap_oid: {
+				description: "The object identifier of the trap, which is the `snmpTrapOID.0` of SNMPv2c traps."
+				required:    true
+				type: string: {
+					examples: ["1.3.6.1.6.3.1.1.5.3"]
+				}
+			}
+			uptime: {
+				description: "The time since the agent was last initialized, in hundredths of a second."
+				required:    false
+				type: uint: {
+					examples: [51354]
+					unit: null
+				}
+			}
+			varbinds: {
+				description: """
+					The variable bindings of the trap, as objects with the `oid`, `type`, and `value` of
+					each binding, and its `name` when `mib_directory` is set and defines it.
+					"""
+				required: true
+				type: array: items: type: object: {
+					examples: [{
+						name:  "IF-MIB::ifIndex.3"
+						oid:   "1.3.6.1.2.1.2.2.1.1.3"
+						type:  "integer"
+						value: 3
+					}]
+					options: {}
+				}
+			}
+			version: {
+				description: "The version of SNMP the trap was sent with."
+				required:    true
+				type: string: {
+					enum: {
+						"1":  "SNMPv1"
+						"2c": "SNMPv2c"
+					}
+				}
+			}
+		}
+	}
+
+	how_it_works: {
+		versions: {
+			title: "SNMP versions"
+			body: """
+				SNMPv1 and SNMPv2c traps are supported. SNMPv2c traps are given the fields of SNMPv1
+				traps as described by [RFC 3584](\(urls.rfc_3584)), so that the `enterprise`,
+				`generic_trap`, and `specific_trap` fields identify traps whatever their version. Their
+				`sysUpTime.0` and `snmpTrapOID.0` variable bindings are given as the `uptime` and
+				`trap_oid` fields instead.
+
+				Informs, SNMPv3 messages, and PDUs other than traps are discarded.
+				"""
+		}
+		mibs: {
+			title: "MIBs"
+			body: """
+				Object identifiers are named after the MIBs of the `mib_directory`, compiled into
+				mappings of names to object identifiers by tools such as
+				[`pysmi`](\(urls.pysmi)) or `snmptranslate -Tz`. An object identifier is named after
+				the longest object identifier it starts with that the MIBs define, followed by the
+				rest of its subidentifiers, so that the instance `1.3.6.1.2.1.2.2.1.1.3` of
+				`IF-MIB::ifIndex` is named `IF-MIB::ifIndex.3`.
+				"""
+		}
+		varbind_values: {
+			title: "Variable binding values"
+			body: """
+				Integers, counters, gauges, and time ticks are given as integers, except `counter64`
+				values too large for a signed 64-bit integer, which are given as strings. Object
+				identifiers and IP addresses are given in their dotted form, octet strings and opaque
+				values as they were received, and `null`, `no_such_object`, `no_such_instance`, and
+				`end_of_mib_view` values as `null`.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_discarded_events_total:     components.sources.internal_metrics.output.metrics.component_discarded_events_total
+		component_errors_total:               components.sources.internal_metrics.output.metrics.component_errors_total
+		component_received_bytes_total:       components.sources.internal_metrics.output.metrics.component_received_bytes_total
+		component_received_events_total:      components.sources.internal_metrics.output.metrics.component_received_events_total
+		component_received_event_bytes_total: components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
+	}
+}
@@ -170,6 +170,21 @@ components: transforms: aggregate: {
 				values 93 and 95 would result in a single `absolute` `gauge` with the value of 95. More complex
 				types like `distribution`, `histogram`, `set`, and `summary` behave similarly with `incremental`
 				values being combined in a manner that makes sense based on their type.
+
+				This is the behavior of the default `auto` mode. Other modes apply a single function to the
+				values of `counter` and `gauge` metrics of the same series instead: `sum`, `first` and `latest`
+				keep the kind and type of the metrics, while `count`, `mean`, `min`, `max`, `stdev` and `rate`
+				are emitted as `absolute` `gauge` metrics.
+				"""
+		}
+
+		rollups: {
+			title: "Rollups"
+			body: """
+				Tags listed in `drop_tags` are removed from metrics before they are aggregated, so series
+				that only differ by these tags are aggregated together. For example, dropping the `host` tag
+				with the `sum` mode turns the per-host request counters of a service into a single counter
+				for the whole service.
 				"""
 		}
 
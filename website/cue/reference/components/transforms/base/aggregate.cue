@@ -1,11 +1,55 @@
 package metadata
 
-base: components: transforms: aggregate: configuration: interval_ms: {
-	description: """
-		The interval between flushes, in milliseconds.
-
-		Over this period metrics with the same series data (name, namespace, tags, …) will be aggregated.
-		"""
-	required: false
-	type: uint: default: 10000
+base: components: transforms: aggregate: configuration: {
+	drop_tags: {
+		description: """
+			Tags removed from metrics before they are aggregated.
+
+			Metrics whose series only differ by these tags are aggregated together, for example to roll
+			up the metrics of every host of a service by dropping the `host` tag.
+			"""
+		required: false
+		type: array: {
+			default: []
+			items: type: string: syntax: "literal"
+		}
+	}
+	interval_ms: {
+		description: """
+			The interval between flushes, in milliseconds.
+
+			Over this period metrics with the same series data (name, namespace, tags, …) will be aggregated.
+			"""
+		required: false
+		type: uint: default: 10000
+	}
+	mode: {
+		description: """
+			The function used to aggregate counters and gauges of the same series.
+
+			Metrics of other types are always aggregated as in `auto` mode.
+			"""
+		required: false
+		type: string: {
+			default: "auto"
+			enum: {
+				auto:   "Sums incremental metrics and keeps the latest value of absolute metrics."
+				count:  "Counts the metrics, emitted as a gauge."
+				first:  "Keeps the first value."
+				latest: "Keeps the latest value."
+				max:    "Keeps the largest value, emitted as a gauge."
+				mean:   "Averages the values, emitted as a gauge."
+				min:    "Keeps the smallest value, emitted as a gauge."
+				rate: """
+					Computes the change per second over the interval, emitted as a gauge.
+
+					The change is the sum of incremental values, the sum of the increases of absolute counters,
+					or the difference between the first and the latest values of absolute gauges. A decreasing
+					absolute counter is considered to have been reset.
+					"""
+				stdev: "Computes the population standard deviation of the values, emitted as a gauge."
+				sum:   "Sums the values."
+			}
+		}
+	}
 }
@@ -27,8 +27,37 @@ base: components: transforms: sample: configuration: {
 
 			For example, `rate = 10` means 1 out of every 10 events will be forwarded and the rest will
 			be dropped.
+
+			When `target_events_per_sec` is set, this is the initial rate used for each value of
+			`key_field` until its first window has elapsed.
 			"""
 		required: true
 		type: uint: {}
 	}
+	target_events_per_sec: {
+		description: """
+			The number of events per second to forward for each value of `key_field`.
+
+			When set, the rate is adjusted at the end of every window so that each value of `key_field`
+			forwards roughly this many events per second, regardless of how much traffic it receives.
+			Events are then count rated within each value of `key_field`, rather than being hashed. If
+			`key_field` is left unspecified, all events share a single rate.
+
+			Each forwarded event has its effective rate written to the `sample_rate` field, so it can
+			be re-weighted downstream.
+			"""
+		required: false
+		type: float: examples: [100.0]
+	}
+	window_secs: {
+		description: """
+			The time window over which the rate is adjusted when `target_events_per_sec` is set, in
+			seconds.
+			"""
+		required: false
+		type: float: {
+			default: 1.0
+			unit:    "seconds"
+		}
+	}
 }
@@ -45,6 +45,133 @@ base: components: transforms: tag_cardinality_limit: configuration: {
 				"""
 		}
 	}
+	per_metric: {
+		description: """
+			Overrides of the limits for specific metrics.
+
+			The key is the name of the metric, without its namespace, the override applies to. The tag
+			values of these metrics are tracked separately from the tag values of other metrics.
+			"""
+		required: false
+		type: object: options: "*": {
+			description: "Overrides of the limits for a metric."
+			required:    true
+			type: object: options: {
+				limit_exceeded_action: {
+					description: """
+						The action to take when a limit of the metric is exceeded.
+
+						If not set, the top-level limits, including `per_tag`, are used.
+						"""
+					required: false
+					type: string: enum: {
+						drop_event: "Drop the entire event itself."
+						drop_tag:   "Drop the tag(s) that would exceed the configured limit."
+					}
+				}
+				per_tag: {
+					description: """
+						Overrides of the limits for specific tag keys of the metric.
+
+						The key is the tag key the override applies to.
+						"""
+					required: false
+					type: object: options: "*": {
+						description: "Overrides of the limits for a tag key."
+						required:    true
+						type: object: options: {
+							limit_exceeded_action: {
+								description: """
+									The action to take when the limit of the tag key is exceeded.
+
+									If not set, the enclosing `limit_exceeded_action` is used.
+									"""
+								required: false
+								type: string: enum: {
+									drop_event: "Drop the entire event itself."
+									drop_tag:   "Drop the tag(s) that would exceed the configured limit."
+								}
+							}
+							value_limit: {
+								description: """
+									How many distinct values to accept for the tag key.
+
+									If not set, the enclosing `value_limit` is used.
+									"""
+								required: false
+								type: uint: {}
+							}
+						}
+					}
+				}
+				value_limit: {
+					description: """
+						How many distinct values to accept for any given key of the metric.
+
+						If not set, the top-level limits, including `per_tag`, are used.
+						"""
+					required: false
+					type: uint: {}
+				}
+			}
+		}
+	}
+	per_tag: {
+		description: """
+			Overrides of the limits for specific tag keys, applied to every metric.
+
+			The key is the tag key the override applies to.
+			"""
+		required: false
+		type: object: options: "*": {
+			description: "Overrides of the limits for a tag key."
+			required:    true
+			type: object: options: {
+				limit_exceeded_action: {
+					description: """
+						The action to take when the limit of the tag key is exceeded.
+
+						If not set, the enclosing `limit_exceeded_action` is used.
+						"""
+					required: false
+					type: string: enum: {
+						drop_event: "Drop the entire event itself."
+						drop_tag:   "Drop the tag(s) that would exceed the configured limit."
+					}
+				}
+				value_limit: {
+					description: """
+						How many distinct values to accept for the tag key.
+
+						If not set, the enclosing `value_limit` is used.
+						"""
+					required: false
+					type: uint: {}
+				}
+			}
+		}
+	}
+	reroute_dropped: {
+		description: """
+			Reroutes rejected metrics to a named output.
+
+			When set, metrics dropped with `drop_event` and the original, unmodified version of metrics
+			stripped of tags with `drop_tag` are forwarded to a specially-named output, `dropped`, for
+			further inspection.
+			"""
+		required: false
+		type: bool: default: false
+	}
+	reset_interval_secs: {
+		description: """
+			The interval after which all accepted tag values are forgotten, in seconds.
+
+			Once reset, new tag values are accepted again until the limits are reached. If not set,
+			accepted tag values are kept for the lifetime of the transform.
+			"""
+		required: false
+		type: uint: {}
+	}
 	value_limit: {
 		description: "How many distinct values to accept for any given key."
 		required:    false
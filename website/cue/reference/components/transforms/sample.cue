@@ -32,6 +32,22 @@ components: transforms: sample: {
 		traces:  true
 	}
 
+	how_it_works: {
+		target_rate: {
+			title: "Target rate sampling"
+			body: """
+				By default, a fixed `1/rate` of events is forwarded. When `target_events_per_sec` is set,
+				the rate is instead recomputed at the end of every `window_secs` window for each value of
+				`key_field`, based on how many events that value received during the window. Busy values
+				are sampled more aggressively and quiet values are forwarded in full, so that each value
+				forwards roughly `target_events_per_sec` events per second.
+
+				Every forwarded event carries its effective rate in the `sample_rate` field, which can be
+				used to re-weight counts downstream.
+				"""
+		}
+	}
+
 	telemetry: metrics: {
 		events_discarded_total: components.sources.internal_metrics.output.metrics.events_discarded_total
 	}
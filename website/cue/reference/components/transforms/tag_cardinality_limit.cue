@@ -180,8 +180,45 @@ components: transforms: tag_cardinality_limit: {
 				for more info.
 				"""
 		}
+
+		overrides: {
+			title: "Per-metric and per-tag limits"
+			body: """
+				The top-level `value_limit` and `limit_exceeded_action` apply to every tag key.
+				They can be overridden for specific tag keys with `per_tag`, and for specific
+				metrics with `per_metric`. The most specific setting wins: a tag key override of
+				a metric, then the metric override, then the top-level tag key override, and
+				finally the top-level settings.
+
+				The tag values of metrics listed in `per_metric` are tracked separately, so their
+				values don't count against the limits of other metrics.
+				"""
+		}
+
+		reset_interval: {
+			title: "Resetting the cache"
+			body: """
+				When `reset_interval_secs` is set, every accepted tag value is forgotten once the
+				interval elapses, and new values are accepted again until the limits are reached.
+				This bounds how long a burst of unexpected values blocks legitimate new values.
+				"""
+		}
 	}
 
+	outputs: [
+		components._default_output,
+		{
+			name: "dropped"
+			description: """
+				This transform also implements an additional `dropped` output. When
+				`reroute_dropped` is set to `true`, metrics dropped with `drop_event` and the
+				original, unmodified form of metrics stripped of tags with `drop_tag` are sent
+				to the `dropped` output. For a transform component named `foo`, this output can
+				be accessed by specifying `foo.dropped` as the input to another component.
+				"""
+		},
+	]
+
 	telemetry: metrics: {
 		tag_value_limit_exceeded_total: components.sources.internal_metrics.output.metrics.tag_value_limit_exceeded_total
 		value_limit_reached_total:      components.sources.internal_metrics.output.metrics.value_limit_reached_total
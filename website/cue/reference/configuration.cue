@@ -367,6 +367,79 @@ configuration: {
 			}
 		}
 
+		reload: {
+			common: false
+			description: """
+				Configures how reloads of the configuration are handled.
+				"""
+			required: false
+			type: object: {
+				examples: []
+				options: {
+					probation: {
+						common: false
+						description: """
+							Puts reloaded configurations on probation. The health checks of the reloaded
+							sinks are required to pass, and for the probation window, the error rates of
+							components and the throughput of each sink kept from the previous configuration
+							are compared against those of the previous configuration. If the pipeline regressed, the previous configuration is
+							restored and a warning describing the regression is logged.
+							"""
+						required: false
+						type: object: {
+							examples: []
+							options: {
+								window_secs: {
+									common: true
+									description: "How long, in seconds, to monitor the pipeline after a reload. Must be greater than zero."
+									required: false
+									type: uint: {
+										default: 60
+										unit:    "seconds"
+									}
+								}
+								max_error_rate_increase: {
+									common: false
+									description: """
+										The increase, in errors per second, of the error rate of any component
+										that is considered a regression.
+										"""
+									required: false
+									type: float: {
+										default: 1.0
+									}
+								}
+								min_throughput_ratio: {
+									common: false
+									description: """
+										The ratio of the previous throughput of a sink, in events sent per second,
+										below which its throughput is considered a regression. Must be between 0 and 1.
+										"""
+									required: false
+									type: float: {
+										default: 0.5
+									}
+								}
+								min_baseline_events: {
+									common: false
+									description: """
+										The number of events a sink must have sent under the previous
+										configuration for its throughput to be compared. Sinks that sent fewer
+										events are not checked for throughput regressions.
+										"""
+									required: false
+									type: uint: {
+										default: 100
+										unit:    "events"
+									}
+								}
+							}
+						}
+					}
+				}
+			}
+		}
+
 		secret: {
 			common: false
 			description: """
@@ -0,0 +1,8 @@
+package metadata
+
+services: netflow: {
+	name:     "NetFlow, IPFIX, and sFlow"
+	thing:    "a flow exporter"
+	url:      urls.netflow
+	versions: null
+}
@@ -0,0 +1,8 @@
+package metadata
+
+services: snmp: {
+	name:     "SNMP"
+	thing:    "an \(name) agent"
+	url:      urls.snmp
+	versions: null
+}
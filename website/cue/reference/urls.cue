@@ -177,6 +177,8 @@ urls: {
 	elasticsearch_id_field:                     "https://www.elastic.co/guide/en/elasticsearch/reference/current/mapping-id-field.html"
 	elasticsearch_id_performance:               "https://www.elastic.co/guide/en/elasticsearch/reference/master/tune-for-indexing-speed.html#_use_auto_generated_ids"
 	elasticsearch_ignore_malformed:             "https://www.elastic.co/guide/en/elasticsearch/reference/current/ignore-malformed.html"
+	elasticsearch_point_in_time:                "https://www.elastic.co/guide/en/elasticsearch/reference/current/point-in-time-api.html"
+	elasticsearch_search_after:                 "https://www.elastic.co/guide/en/elasticsearch/reference/current/paginate-search-results.html#search-after"
 	encoded_word:                               "https://datatracker.ietf.org/doc/html/rfc2047#section-2"
 	encoding_charset_labels:                    "https://encoding.spec.whatwg.org/#concept-encoding-get"
 	encoding_standard:                          "https://encoding.spec.whatwg.org/"
@@ -269,6 +271,7 @@ urls: {
 	ip_ntoa:                                    "https://linux.die.net/man/3/inet_ntoa"
 	ip_ntop:                                    "https://linux.die.net/man/3/inet_ntop"
 	ip_pton:                                    "https://linux.die.net/man/3/inet_pton"
+	ipfix_information_elements:                 "https://www.iana.org/assignments/ipfix/ipfix.xhtml"
 	iso_8601:                                   "\(wikipedia)/wiki/ISO_8601"
 	iso3166_2:                                  "\(wikipedia)/wiki/ISO_3166-2"
 	issue_1694:                                 "\(vector_repo)/issues/1694"
@@ -289,6 +292,7 @@ urls: {
 	kubernetes_api_server:                      "\(kubernetes)/docs/reference/command-line-tools-reference/kube-apiserver/"
 	kubernetes_authorization:                   "\(kubernetes)/docs/reference/access-authn-authz/authorization/"
 	kubernetes_daemonset:                       "\(kubernetes)/docs/concepts/workloads/controllers/daemonset/"
+	kubernetes_events:                          "\(kubernetes)/docs/reference/kubernetes-api/cluster-resources/event-v1/"
 	kubernetes_example_daemonset:               "\(vector_repo)/blob/master/config/kubernetes/vector-daemonset.yaml"
 	kubernetes_limit_resources:                 "\(kubernetes)/docs/tasks/configure-pod-container/assign-cpu-resource/"
 	kubernetes_logging_architecture:            "\(kubernetes)/docs/concepts/cluster-administration/logging/"
@@ -349,11 +353,14 @@ urls: {
 	mongodb:                                    "https://www.mongodb.com"
 	mongodb_command_server_status:              "https://docs.mongodb.com/manual/reference/command/serverStatus/"
 	mongodb_connection_string_uri_format:       "https://docs.mongodb.com/manual/reference/connection-string/"
+	mqtt:                                       "https://mqtt.org/"
+	mqtt_v3_1_1:                                "https://docs.oasis-open.org/mqtt/mqtt/v3.1.1/mqtt-v3.1.1.html"
 	musl_builder_docker_image:                  "\(vector_repo)/blob/master/scripts/ci-docker-images/builder-x86_64-unknown-linux-musl/Dockerfile"
 	native_proto_schema:                        "\(vector_repo)/blob/master/lib/vector-core/proto/event.proto"
 	native_json_schema:                         "\(vector_repo)/blob/master/lib/codecs/tests/data/native_encoding/schema.cue"
 	nats:                                       "https://nats.io/"
 	nats_rs:                                    "\(github)/nats-io/nats.rs"
+	netflow:                                    "\(wikipedia)/wiki/NetFlow"
 	new_bug_report:                             "\(vector_repo)/issues/new?labels=type%3A+bug"
 	new_feature_request:                        "\(vector_repo)/issues/new?labels=type%3A+new+feature"
 	new_relic:                                  "https://newrelic.com/"
@@ -371,6 +378,7 @@ urls: {
 	nix:                                        "https://nixos.org/nix/"
 	nixos:                                      "https://nixos.org/"
 	nixpkgs_9682:                               "\(github)/NixOS/nixpkgs/issues/9682"
+	opensearch_point_in_time:                   "https://opensearch.org/docs/latest/search-plugins/point-in-time/"
 	openssl:                                    "https://www.openssl.org/"
 	opentelemetry:                              "https://opentelemetry.io"
 	opentelemetry_protocol:                     "\(opentelemetry)/docs/reference/specification/protocol/otlp/"
@@ -409,6 +417,7 @@ urls: {
 	protobuf:                                   "https://developers.google.com/protocol-buffers"
 	pulsar:                                     "https://pulsar.apache.org/"
 	pulsar_protocol:                            "https://pulsar.apache.org/docs/en/develop-binary-protocol/"
+	pysmi:                                      "https://github.com/etingof/pysmi"
 	raspbian:                                   "https://www.raspbian.org/"
 	rdkafka:                                    "\(github)/edenhill/librdkafka"
 	regex:                                      "\(wikipedia)/wiki/Regular_expression"
@@ -424,9 +433,13 @@ urls: {
 	rfc_2460:                                   "https://tools.ietf.org/html/rfc2460"
 	rfc_2822:                                   "https://tools.ietf.org/html/rfc2822#section-3.3"
 	rfc_3339:                                   "https://tools.ietf.org/html/rfc3339"
+	rfc_3416:                                   "https://tools.ietf.org/html/rfc3416"
+	rfc_3584:                                   "https://tools.ietf.org/html/rfc3584"
+	rfc_3954:                                   "https://tools.ietf.org/html/rfc3954"
 	rfc_4180:                                   "https://tools.ietf.org/html/rfc4180"
 	rfc_6587_3_4_1:                             "https://tools.ietf.org/html/rfc6587#section-3.4.1"
 	rfc_6891:                                   "https://tools.ietf.org/html/rfc6891"
+	rfc_7011:                                   "https://tools.ietf.org/html/rfc7011"
 	rhel:                                       "https://www.redhat.com/en/technologies/linux-platforms/enterprise-linux"
 	rpm:                                        "https://rpm.org/"
 	rust:                                       "https://www.rust-lang.org/"
@@ -450,12 +463,14 @@ urls: {
 	sematext_monitoring:                        "https://sematext.com/docs/monitoring/"
 	sematext_registration:                      "https://apps.sematext.com/ui/registration"
 	semver:                                     "https://semver.org/"
+	sflow_v5:                                   "https://sflow.org/sflow_version_5.txt"
 	sha1:                                       "\(wikipedia)/wiki/SHA-1"
 	sha2:                                       "\(wikipedia)/wiki/SHA-2"
 	sha3:                                       "\(wikipedia)/wiki/SHA-3"
 	signal:                                     "\(wikipedia)/wiki/Signal_(IPC)"
 	snake_case:                                 "\(wikipedia)/wiki/Snake_case"
 	snappy:                                     "https://google.github.io/snappy/"
+	snmp:                                       "\(wikipedia)/wiki/Simple_Network_Management_Protocol"
 	socket:                                     "\(wikipedia)/wiki/Network_socket"
 	splunk:                                     "https://www.splunk.com"
 	splunk_hec:                                 "https://dev.splunk.com/enterprise/docs/dataapps/httpeventcollector/"